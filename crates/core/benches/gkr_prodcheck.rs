@@ -49,10 +49,10 @@ fn bench_polyval(c: &mut Criterion) {
 
 			// Setup witness
 			let (numerator, denominator) = create_numerator_denominator::<FW>(n);
-			let witness = ProdcheckWitness::<FW> {
-				t_poly: numerator.specialize_arc_dyn(),
-				u_poly: denominator.specialize_arc_dyn(),
-			};
+			let witness = ProdcheckWitness::<FW>::new(
+				numerator.specialize_arc_dyn(),
+				denominator.specialize_arc_dyn(),
+			);
 
 			// Setup claim
 			let mut oracles = MultilinearOracleSet::<F>::new();
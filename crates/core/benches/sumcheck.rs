@@ -298,10 +298,7 @@ where
 		})
 		.sum();
 
-	let sumcheck_claim = SumcheckClaim {
-		poly: composite_poly,
-		sum,
-	};
+	let sumcheck_claim = SumcheckClaim::new(composite_poly, sum)?;
 
 	Ok(sumcheck_claim)
 }
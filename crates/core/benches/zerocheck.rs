@@ -288,9 +288,7 @@ where
 	let composite_poly =
 		CompositePolyOracle::new(poly.n_vars(), inner, poly.composition.clone()).unwrap();
 
-	let zerocheck_claim = ZerocheckClaim {
-		poly: composite_poly,
-	};
+	let zerocheck_claim = ZerocheckClaim::new(composite_poly)?;
 
 	Ok(zerocheck_claim)
 }
@@ -0,0 +1,157 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Observing and sampling non-field data with a challenger.
+//!
+//! Protocols occasionally need to bind data that is not made of field elements into the
+//! transcript, such as digests from external systems, and to sample integers rather than field
+//! elements, such as query indices and proof-of-work challenges. The traits in this module provide
+//! a single encoding for both, and are implemented for every challenger that can observe
+//! [`BinaryField8b`] elements and sample bits.
+
+use binius_field::BinaryField8b;
+use p3_challenger::{CanObserve, CanSampleBits};
+use p3_util::log2_ceil_usize;
+use std::ops::Range;
+
+/// A challenger that can observe raw byte strings.
+pub trait CanObserveBytes {
+	/// Observes a byte string.
+	///
+	/// The string is observed as [`BinaryField8b`] elements, prefixed with its length as a
+	/// little-endian `u64`. The prefix makes the encoding of a sequence of byte strings
+	/// unambiguous, so observing `"ab"` then `"c"` differs from observing `"a"` then `"bc"`.
+	fn observe_bytes(&mut self, bytes: &[u8]);
+}
+
+impl<Challenger> CanObserveBytes for Challenger
+where
+	Challenger: CanObserve<BinaryField8b>,
+{
+	fn observe_bytes(&mut self, bytes: &[u8]) {
+		let len = (bytes.len() as u64).to_le_bytes();
+		let elems = len
+			.iter()
+			.chain(bytes)
+			.map(|&byte| BinaryField8b::new(byte))
+			.collect::<Vec<_>>();
+		self.observe_slice(&elems);
+	}
+}
+
+/// A challenger that can sample uniformly random bytes.
+pub trait CanSampleBytes {
+	/// Samples `n_bytes` uniformly random bytes.
+	fn sample_bytes(&mut self, n_bytes: usize) -> Vec<u8>;
+}
+
+impl<Challenger> CanSampleBytes for Challenger
+where
+	Challenger: CanSampleBits<usize>,
+{
+	fn sample_bytes(&mut self, n_bytes: usize) -> Vec<u8> {
+		(0..n_bytes).map(|_| self.sample_bits(8) as u8).collect()
+	}
+}
+
+/// A challenger that can sample uniformly random integers in a range.
+pub trait CanSampleRange {
+	/// Samples an integer uniformly from `range`.
+	///
+	/// The integer is sampled by rejection sampling. The challenger samples
+	/// $\lceil \log_2 n \rceil$ bits, where $n$ is the length of the range, and samples again
+	/// while the value is not less than $n$. Each attempt is accepted with probability greater than
+	/// 1/2, and the sampled value is exactly uniform. Ranges with a power of two length, such as
+	/// Merkle tree leaf indices, are always sampled in one attempt, with the same bits as
+	/// [`CanSampleBits::sample_bits`].
+	///
+	/// ## Panics
+	///
+	/// Panics if `range` is empty.
+	fn sample_range(&mut self, range: Range<usize>) -> usize;
+}
+
+impl<Challenger> CanSampleRange for Challenger
+where
+	Challenger: CanSampleBits<usize>,
+{
+	fn sample_range(&mut self, range: Range<usize>) -> usize {
+		assert!(!range.is_empty(), "cannot sample from an empty range");
+		let len = range.end - range.start;
+		let bits = log2_ceil_usize(len);
+		loop {
+			let value = self.sample_bits(bits);
+			if value < len {
+				return range.start + value;
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::challenger::new_hasher_challenger;
+	use binius_field::BinaryField128b;
+	use binius_hash::GroestlHasher;
+	use p3_challenger::CanSample;
+
+	#[test]
+	fn test_observe_bytes_is_unambiguous() {
+		let mut challenger_1 = new_hasher_challenger::<BinaryField8b, GroestlHasher<_>>();
+		challenger_1.observe_bytes(b"ab");
+		challenger_1.observe_bytes(b"c");
+
+		let mut challenger_2 = new_hasher_challenger::<BinaryField8b, GroestlHasher<_>>();
+		challenger_2.observe_bytes(b"a");
+		challenger_2.observe_bytes(b"bc");
+
+		let mut challenger_3 = new_hasher_challenger::<BinaryField8b, GroestlHasher<_>>();
+		challenger_3.observe_bytes(b"ab");
+		challenger_3.observe_bytes(b"c");
+
+		let sample_1: BinaryField128b = challenger_1.sample();
+		let sample_2: BinaryField128b = challenger_2.sample();
+		let sample_3: BinaryField128b = challenger_3.sample();
+		assert_ne!(sample_1, sample_2);
+		assert_eq!(sample_1, sample_3);
+	}
+
+	#[test]
+	fn test_sample_range() {
+		let mut challenger = new_hasher_challenger::<BinaryField8b, GroestlHasher<_>>();
+		challenger.observe_bytes(b"test_sample_range");
+
+		// Every value of a small range is sampled, and nothing outside it.
+		let mut seen = [false; 5];
+		for _ in 0..200 {
+			let value = challenger.sample_range(10..15);
+			assert!((10..15).contains(&value));
+			seen[value - 10] = true;
+		}
+		assert!(seen.iter().all(|&seen| seen));
+
+		assert_eq!(challenger.sample_range(7..8), 7);
+
+		// Power of two ranges consume the same bits as sample_bits.
+		let mut other = challenger.clone();
+		assert_eq!(challenger.sample_range(0..1 << 10), other.sample_bits(10));
+	}
+
+	#[test]
+	fn test_sample_bytes() {
+		let mut challenger = new_hasher_challenger::<BinaryField8b, GroestlHasher<_>>();
+		let mut other = challenger.clone();
+		let bytes = challenger.sample_bytes(3);
+		assert_eq!(bytes.len(), 3);
+		let expected = (0..3)
+			.map(|_| other.sample_bits(8) as u8)
+			.collect::<Vec<_>>();
+		assert_eq!(bytes, expected);
+	}
+
+	#[test]
+	#[should_panic]
+	fn test_sample_empty_range() {
+		new_hasher_challenger::<BinaryField8b, GroestlHasher<_>>().sample_range(3..3);
+	}
+}
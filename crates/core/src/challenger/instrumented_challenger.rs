@@ -0,0 +1,86 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use getset::CopyGetters;
+use p3_challenger::{CanObserve, CanSample, CanSampleBits};
+use std::mem;
+
+/// A wrapper over a challenger that emits a tracing event for every observation and sample.
+///
+/// Observations emit an `observe` event carrying the number of bytes observed, and samples emit a
+/// `sample` event carrying the number of bytes sampled, which can be aggregated per sub-protocol
+/// with [`binius_utils::cost_report::CostReportLayer`]. The totals are also counted by the wrapper
+/// itself, for callers that report costs without a tracing subscriber.
+///
+/// The verifier observes the claims and parameters it knows itself as well as the proof, so the
+/// observed bytes measure the transcript, not the proof size. The verifiers report the proof data
+/// they read separately, with [`binius_utils::cost_report::record_proof_bytes`].
+#[derive(Debug, Clone, CopyGetters)]
+pub struct InstrumentedChallenger<Challenger> {
+	challenger: Challenger,
+	/// The total number of bytes observed.
+	#[getset(get_copy = "pub")]
+	observed_bytes: usize,
+	/// The total number of samples, counting each call to `sample` or `sample_bits` once.
+	#[getset(get_copy = "pub")]
+	n_samples: usize,
+}
+
+impl<Challenger> InstrumentedChallenger<Challenger> {
+	pub fn new(challenger: Challenger) -> Self {
+		Self {
+			challenger,
+			observed_bytes: 0,
+			n_samples: 0,
+		}
+	}
+
+	pub fn into_inner(self) -> Challenger {
+		self.challenger
+	}
+}
+
+impl<Challenger, T> CanObserve<T> for InstrumentedChallenger<Challenger>
+where
+	Challenger: CanObserve<T>,
+{
+	fn observe(&mut self, value: T) {
+		tracing::event!(name: "observe", tracing::Level::TRACE, { bytes = mem::size_of::<T>() });
+		self.observed_bytes += mem::size_of::<T>();
+		self.challenger.observe(value);
+	}
+
+	fn observe_slice(&mut self, values: &[T])
+	where
+		T: Clone,
+	{
+		tracing::event!(
+			name: "observe",
+			tracing::Level::TRACE,
+			{ bytes = mem::size_of_val(values) }
+		);
+		self.observed_bytes += mem::size_of_val(values);
+		self.challenger.observe_slice(values);
+	}
+}
+
+impl<Challenger, T> CanSample<T> for InstrumentedChallenger<Challenger>
+where
+	Challenger: CanSample<T>,
+{
+	fn sample(&mut self) -> T {
+		tracing::event!(name: "sample", tracing::Level::TRACE, { bytes = mem::size_of::<T>() });
+		self.n_samples += 1;
+		self.challenger.sample()
+	}
+}
+
+impl<Challenger> CanSampleBits<usize> for InstrumentedChallenger<Challenger>
+where
+	Challenger: CanSampleBits<usize>,
+{
+	fn sample_bits(&mut self, bits: usize) -> usize {
+		tracing::event!(name: "sample", tracing::Level::TRACE, { bytes = bits.div_ceil(8) });
+		self.n_samples += 1;
+		self.challenger.sample_bits(bits)
+	}
+}
@@ -8,13 +8,19 @@
 //!
 //! [Plonky3]: <https://github.com/plonky3/plonky3>
 
+mod bytes;
 mod duplex;
 pub mod field_challenger;
 mod hasher;
+mod instrumented_challenger;
 mod isomorphic_challenger;
+mod transcript;
 
+pub use bytes::{CanObserveBytes, CanSampleBytes, CanSampleRange};
 pub use duplex::new as new_duplex_challenger;
 pub use field_challenger::FieldChallenger;
 pub use hasher::new as new_hasher_challenger;
+pub use instrumented_challenger::InstrumentedChallenger;
 pub use isomorphic_challenger::IsomorphicChallenger;
 pub use p3_challenger::{CanObserve, CanSample, CanSampleBits};
+pub use transcript::Transcript;
@@ -0,0 +1,121 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use crate::params::TranscriptParams;
+use binius_field::Field;
+use p3_challenger::{CanObserve, CanSample, CanSampleBits};
+
+/// A Fiat-Shamir transcript bound to the protocol parameters.
+///
+/// The transcript wraps a challenger and observes the encoding of the [`TranscriptParams`] on
+/// construction, before any challenge can be sampled. Every challenge then depends on the code
+/// rate, the number of test queries, and the protocol features, so a proof produced under one
+/// choice of parameters does not verify under another, and the parameters alone describe how to
+/// check a proof.
+#[derive(Debug, Clone)]
+pub struct Transcript<Challenger> {
+	challenger: Challenger,
+	params: TranscriptParams,
+}
+
+impl<Challenger> Transcript<Challenger> {
+	/// Wraps a fresh challenger and observes the parameters with it.
+	///
+	/// Each word of [`TranscriptParams::encode`] is written as 64 zero or one field elements.
+	pub fn new<F>(mut challenger: Challenger, params: TranscriptParams) -> Self
+	where
+		F: Field,
+		Challenger: CanObserve<F>,
+	{
+		let encoded = params
+			.encode()
+			.into_iter()
+			.flat_map(|word| {
+				(0..64).map(move |i| {
+					if (word >> i) & 1 == 1 {
+						F::ONE
+					} else {
+						F::ZERO
+					}
+				})
+			})
+			.collect::<Vec<_>>();
+		challenger.observe_slice(&encoded);
+		Self { challenger, params }
+	}
+
+	pub fn params(&self) -> &TranscriptParams {
+		&self.params
+	}
+
+	pub fn into_inner(self) -> Challenger {
+		self.challenger
+	}
+}
+
+impl<Challenger, T> CanObserve<T> for Transcript<Challenger>
+where
+	Challenger: CanObserve<T>,
+{
+	fn observe(&mut self, value: T) {
+		self.challenger.observe(value);
+	}
+
+	fn observe_slice(&mut self, values: &[T])
+	where
+		T: Clone,
+	{
+		self.challenger.observe_slice(values);
+	}
+}
+
+impl<Challenger, T> CanSample<T> for Transcript<Challenger>
+where
+	Challenger: CanSample<T>,
+{
+	fn sample(&mut self) -> T {
+		self.challenger.sample()
+	}
+}
+
+impl<Challenger> CanSampleBits<usize> for Transcript<Challenger>
+where
+	Challenger: CanSampleBits<usize>,
+{
+	fn sample_bits(&mut self, bits: usize) -> usize {
+		self.challenger.sample_bits(bits)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		challenger::new_hasher_challenger, params::VerifyParams,
+		protocols::sumcheck_v2::ProtocolFeatures,
+	};
+	use binius_field::{BinaryField128b, BinaryField8b};
+	use binius_hash::GroestlHasher;
+
+	type F = BinaryField128b;
+
+	fn sample_with(params: TranscriptParams) -> F {
+		let challenger = new_hasher_challenger::<BinaryField8b, GroestlHasher<BinaryField8b>>();
+		let mut transcript = Transcript::new::<F>(challenger, params);
+		transcript.observe(F::new(5));
+		transcript.sample()
+	}
+
+	#[test]
+	fn test_challenges_depend_on_params() {
+		let params = VerifyParams::default().transcript_params(100, ProtocolFeatures::empty());
+		assert_eq!(sample_with(params), sample_with(params));
+
+		let fewer_queries =
+			VerifyParams::default().transcript_params(99, ProtocolFeatures::empty());
+		assert_ne!(sample_with(params), sample_with(fewer_queries));
+
+		let with_features = VerifyParams::default()
+			.transcript_params(100, ProtocolFeatures::UNCOMPRESSED_ROUND_PROOFS);
+		assert_ne!(sample_with(params), sample_with(with_features));
+	}
+}
@@ -0,0 +1,271 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! The top-level error type of the crate.
+//!
+//! Each module has its own error enum, which nests the errors of the modules it builds on. Callers
+//! that only need to tell failures apart coarsely, such as service layers mapping failures to
+//! user-facing responses, can convert any of them into [`Error`] and match on its
+//! [`ErrorCode`] and [`ErrorCategory`] instead. The module error is kept as the
+//! [`std::error::Error::source`] of the wrapper, and the wrapper only displays the module name, so
+//! that reporters walking the source chain do not print the module error twice.
+
+use crate::{
+	oracle, poly_commit, polynomial,
+	protocols::{
+		evalcheck, folding, gkr_circuit, gkr_gpa, lasso, msetcheck, opening, ring_switch, sumcheck,
+		sumcheck_v2,
+	},
+	witness,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	#[error("polynomial error")]
+	Polynomial(#[from] polynomial::Error),
+	#[error("oracle error")]
+	Oracle(#[from] oracle::Error),
+	#[error("witness error")]
+	Witness(#[from] witness::Error),
+	#[error("sumcheck error")]
+	Sumcheck(#[from] sumcheck::Error),
+	#[error("sumcheck v2 error")]
+	SumcheckV2(#[from] sumcheck_v2::Error),
+	#[error("evalcheck error")]
+	Evalcheck(#[from] evalcheck::Error),
+	#[error("msetcheck error")]
+	Msetcheck(#[from] msetcheck::Error),
+	#[error("lasso error")]
+	Lasso(#[from] lasso::Error),
+	#[error("gkr grand product error")]
+	GkrGpa(#[from] gkr_gpa::Error),
+	#[error("gkr circuit error")]
+	GkrCircuit(#[from] gkr_circuit::Error),
+	#[error("ring switch error")]
+	RingSwitch(#[from] ring_switch::Error),
+	#[error("folding error")]
+	Folding(#[from] folding::Error),
+	#[error("opening error")]
+	Opening(#[from] opening::Error),
+	#[error("polynomial commitment error")]
+	PolyCommit(#[from] poly_commit::Error),
+}
+
+/// A stable code identifying the module an [`Error`] comes from.
+///
+/// The numeric values are part of the public interface. Codes are never renumbered or reused, and
+/// new codes are only added for new modules or new kinds of failure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum ErrorCode {
+	Polynomial = 100,
+	Oracle = 200,
+	Witness = 300,
+	Sumcheck = 400,
+	SumcheckVerification = 401,
+	Evalcheck = 500,
+	EvalcheckVerification = 501,
+	Msetcheck = 600,
+	SumcheckV2 = 700,
+	SumcheckV2Verification = 701,
+	// 800 belonged to the removed LogUp module and is not reused.
+	Lasso = 900,
+	GkrGpa = 1000,
+	GkrCircuit = 1100,
+	GkrCircuitVerification = 1101,
+	RingSwitch = 1200,
+	RingSwitchVerification = 1201,
+	Folding = 1300,
+	FoldingVerification = 1301,
+	Opening = 1400,
+	OpeningVerification = 1401,
+	PolyCommit = 1500,
+	PolyCommitVerification = 1501,
+}
+
+impl ErrorCode {
+	pub fn as_u16(self) -> u16 {
+		self as u16
+	}
+
+	pub fn as_str(self) -> &'static str {
+		match self {
+			Self::Polynomial => "polynomial",
+			Self::Oracle => "oracle",
+			Self::Witness => "witness",
+			Self::Sumcheck => "sumcheck",
+			Self::SumcheckVerification => "sumcheck.verification",
+			Self::Evalcheck => "evalcheck",
+			Self::EvalcheckVerification => "evalcheck.verification",
+			Self::Msetcheck => "msetcheck",
+			Self::SumcheckV2 => "sumcheck_v2",
+			Self::SumcheckV2Verification => "sumcheck_v2.verification",
+			Self::Lasso => "lasso",
+			Self::GkrGpa => "gkr_gpa",
+			Self::GkrCircuit => "gkr_circuit",
+			Self::GkrCircuitVerification => "gkr_circuit.verification",
+			Self::RingSwitch => "ring_switch",
+			Self::RingSwitchVerification => "ring_switch.verification",
+			Self::Folding => "folding",
+			Self::FoldingVerification => "folding.verification",
+			Self::Opening => "opening",
+			Self::OpeningVerification => "opening.verification",
+			Self::PolyCommit => "poly_commit",
+			Self::PolyCommitVerification => "poly_commit.verification",
+		}
+	}
+}
+
+/// The kind of failure behind an [`Error`], independent of the module it comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCategory {
+	/// The claims, oracles or parameters passed in are malformed or inconsistent.
+	InvalidInput,
+	/// The witness is missing, malformed, or does not satisfy the claims.
+	InvalidWitness,
+	/// A proof was rejected by the verifier.
+	VerificationFailure,
+}
+
+impl Error {
+	pub fn code(&self) -> ErrorCode {
+		match self {
+			Self::Polynomial(_) => ErrorCode::Polynomial,
+			Self::Oracle(_) => ErrorCode::Oracle,
+			Self::Witness(_) => ErrorCode::Witness,
+			Self::Sumcheck(sumcheck::Error::Verification(_)) => ErrorCode::SumcheckVerification,
+			Self::Sumcheck(_) => ErrorCode::Sumcheck,
+			Self::SumcheckV2(sumcheck_v2::Error::Verification(_)) => {
+				ErrorCode::SumcheckV2Verification
+			}
+			Self::SumcheckV2(_) => ErrorCode::SumcheckV2,
+			Self::Evalcheck(evalcheck::Error::Verification(_)) => ErrorCode::EvalcheckVerification,
+			Self::Evalcheck(_) => ErrorCode::Evalcheck,
+			Self::Msetcheck(_) => ErrorCode::Msetcheck,
+			Self::Lasso(_) => ErrorCode::Lasso,
+			Self::GkrGpa(_) => ErrorCode::GkrGpa,
+			Self::GkrCircuit(gkr_circuit::Error::Verification(_)) => {
+				ErrorCode::GkrCircuitVerification
+			}
+			Self::GkrCircuit(_) => ErrorCode::GkrCircuit,
+			Self::RingSwitch(ring_switch::Error::Verification(_)) => {
+				ErrorCode::RingSwitchVerification
+			}
+			Self::RingSwitch(_) => ErrorCode::RingSwitch,
+			Self::Folding(folding::Error::Verification(_)) => ErrorCode::FoldingVerification,
+			Self::Folding(_) => ErrorCode::Folding,
+			Self::Opening(opening::Error::Verification(_)) => ErrorCode::OpeningVerification,
+			Self::Opening(_) => ErrorCode::Opening,
+			Self::PolyCommit(poly_commit::Error::Verification(_)) => {
+				ErrorCode::PolyCommitVerification
+			}
+			Self::PolyCommit(_) => ErrorCode::PolyCommit,
+		}
+	}
+
+	/// The kind of failure, looking through the module errors nested in the wrapped error.
+	pub fn category(&self) -> ErrorCategory {
+		match self {
+			Self::Witness(_)
+			| Self::Sumcheck(sumcheck::Error::NaiveValidation)
+			| Self::SumcheckV2(
+				sumcheck_v2::Error::SumcheckNaiveValidationFailure { .. }
+				| sumcheck_v2::Error::ZerocheckNaiveValidationFailure { .. }
+				| sumcheck_v2::Error::Witness(_),
+			)
+			| Self::Evalcheck(evalcheck::Error::InvalidWitness(_) | evalcheck::Error::Witness(_))
+			| Self::Msetcheck(
+				msetcheck::Error::WitnessDimensionalityMismatch
+				| msetcheck::Error::WitnessNumVariablesMismatch
+				| msetcheck::Error::WitnessSmallerThanUnderlier
+				| msetcheck::Error::Witness(_),
+			)
+			| Self::Lasso(
+				lasso::Error::WitnessSmallerThanUnderlier
+				| lasso::Error::MappingSizeMismatch
+				| lasso::Error::MappingIndexOutOfBounds
+				| lasso::Error::WitnessNumVariablesMismatch
+				| lasso::Error::LassoCountTypeTooSmall
+				| lasso::Error::Witness(_),
+			)
+			| Self::GkrCircuit(gkr_circuit::Error::ProverClaimWitnessMismatch)
+			| Self::RingSwitch(ring_switch::Error::WitnessNumVariablesMismatch)
+			| Self::Folding(
+				folding::Error::IncorrectNumberOfMultilinears { .. }
+				| folding::Error::IncorrectNumberOfVariables { .. },
+			) => ErrorCategory::InvalidWitness,
+			Self::Sumcheck(sumcheck::Error::Verification(_))
+			| Self::SumcheckV2(sumcheck_v2::Error::Verification(_))
+			| Self::Evalcheck(evalcheck::Error::Verification(_))
+			| Self::GkrCircuit(gkr_circuit::Error::Verification(_))
+			| Self::GkrGpa(gkr_gpa::Error::GkrCircuit(gkr_circuit::Error::Verification(_)))
+			| Self::RingSwitch(
+				ring_switch::Error::Verification(_)
+				| ring_switch::Error::Sumcheck(sumcheck_v2::Error::Verification(_)),
+			)
+			| Self::Folding(
+				folding::Error::Verification(_)
+				| folding::Error::Sumcheck(sumcheck_v2::Error::Verification(_)),
+			)
+			| Self::Opening(
+				opening::Error::Verification(_)
+				| opening::Error::Sumcheck(sumcheck_v2::Error::Verification(_)),
+			)
+			| Self::PolyCommit(poly_commit::Error::Verification(_)) => ErrorCategory::VerificationFailure,
+			_ => ErrorCategory::InvalidInput,
+		}
+	}
+}
+
+impl From<sumcheck_v2::VerificationError> for Error {
+	fn from(err: sumcheck_v2::VerificationError) -> Self {
+		Self::SumcheckV2(err.into())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::error::Error as _;
+
+	#[test]
+	fn test_error_codes_and_categories() {
+		let err = Error::from(sumcheck_v2::VerificationError::NumberOfRounds);
+		assert_eq!(err.code(), ErrorCode::SumcheckV2Verification);
+		assert_eq!(err.code().as_u16(), 701);
+		assert_eq!(err.category(), ErrorCategory::VerificationFailure);
+		assert_eq!(err.to_string(), "sumcheck v2 error");
+
+		let err = Error::from(sumcheck_v2::Error::ClaimsOutOfOrder);
+		assert_eq!(err.code(), ErrorCode::SumcheckV2);
+		assert_eq!(err.category(), ErrorCategory::InvalidInput);
+
+		let err = Error::from(opening::Error::Sumcheck(
+			sumcheck_v2::VerificationError::NumberOfRounds.into(),
+		));
+		assert_eq!(err.code(), ErrorCode::Opening);
+		assert_eq!(err.category(), ErrorCategory::VerificationFailure);
+
+		let err = Error::from(poly_commit::Error::Verification(
+			poly_commit::VerificationError::IncorrectEvaluation,
+		));
+		assert_eq!(err.code(), ErrorCode::PolyCommitVerification);
+		assert_eq!(err.code().as_str(), "poly_commit.verification");
+
+		let err = Error::from(lasso::Error::LassoCountTypeTooSmall);
+		assert_eq!(err.code(), ErrorCode::Lasso);
+		assert_eq!(err.category(), ErrorCategory::InvalidWitness);
+
+		let err = Error::from(msetcheck::Error::Witness(witness::Error::MissingWitness { id: 3 }));
+		assert_eq!(err.code(), ErrorCode::Msetcheck);
+		assert_eq!(err.category(), ErrorCategory::InvalidWitness);
+	}
+
+	#[test]
+	fn test_error_preserves_source() {
+		let err = Error::from(evalcheck::Error::InvalidWitness(7));
+		let source = err.source().expect("the module error is the source");
+		assert_eq!(source.to_string(), evalcheck::Error::InvalidWitness(7).to_string());
+		assert!(source.downcast_ref::<evalcheck::Error>().is_some());
+		assert!(!err.to_string().contains(&source.to_string()));
+	}
+}
@@ -0,0 +1,319 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! A regression corpus of golden proofs.
+//!
+//! A golden proof is a proof of a small fixed circuit, produced from a deterministic witness and a
+//! freshly constructed default challenger. The corpus in `test_vectors/golden_proofs.json` holds
+//! the standard encoding of each proof as a hexadecimal string. The crate's tests check that the
+//! verifier still accepts every golden proof and that the prover reproduces it byte for byte, so
+//! accidental changes to the Fiat-Shamir transcript or the proof format are caught.
+//!
+//! Intentional format changes must regenerate the corpus, which the `regenerate_golden_proofs`
+//! test does when the `BINIUS_REGENERATE_GOLDEN_PROOFS` environment variable is set:
+//!
+//! ```text
+//! BINIUS_REGENERATE_GOLDEN_PROOFS=1 cargo test -p binius_core regenerate_golden_proofs
+//! ```
+//!
+//! The regenerated `test_vectors/golden_proofs.json` is committed with the change. Until it covers
+//! every golden circuit, `test_golden_proofs` fails with [`Error::MissingCircuit`].
+
+use crate::{
+	challenger::{new_hasher_challenger, CanObserve, CanSample},
+	polynomial::{
+		composition::BivariateProduct, IsomorphicEvaluationDomainFactory, MultilinearExtension,
+		MultilinearExtensionSpecialized,
+	},
+	protocols::sumcheck_v2::{
+		self, batch_verify,
+		prove::{batch_prove_labeled, batch_prove_with_features, RegularSumcheckProver},
+		ClaimLabel, CompositeSumClaim, DecodingError, Proof, ProofEncoding, ProtocolFeatures,
+		SumcheckClaim,
+	},
+};
+use binius_field::{BinaryField128b, BinaryField32b, BinaryField8b};
+use binius_hash::GroestlHasher;
+use binius_utils::bail;
+use serde::{Deserialize, Serialize};
+
+type F = BinaryField32b;
+type FE = BinaryField128b;
+type FDomain = BinaryField8b;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	#[error("invalid JSON: {0}")]
+	Json(#[from] serde_json::Error),
+	#[error("invalid hexadecimal proof for circuit {0:?}")]
+	InvalidHex(String),
+	#[error("unknown golden circuit {0:?}")]
+	UnknownCircuit(String),
+	#[error("the corpus has no golden proof for circuit {0:?}")]
+	MissingCircuit(String),
+	#[error("golden proof for circuit {circuit:?} is not reproduced by the prover")]
+	ProofMismatch { circuit: String },
+	#[error("decoding error: {0}")]
+	Decoding(#[from] DecodingError),
+	#[error("sumcheck error: {0}")]
+	Sumcheck(#[from] sumcheck_v2::Error),
+}
+
+/// The golden proof corpus.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GoldenProofs {
+	pub proofs: Vec<GoldenProof>,
+}
+
+/// The proof of a golden circuit, in the [`ProofEncoding::Standard`] encoding.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GoldenProof {
+	pub circuit: String,
+	pub proof: String,
+}
+
+impl GoldenProofs {
+	pub fn from_json(json: &str) -> Result<Self, Error> {
+		Ok(serde_json::from_str(json)?)
+	}
+
+	pub fn to_json(&self) -> Result<String, Error> {
+		Ok(serde_json::to_string_pretty(self)?)
+	}
+}
+
+/// A batched sumcheck over products of two multilinears, one claim per entry of `n_vars`.
+///
+/// A circuit either labels its claims and discloses the labels, or proves with protocol features,
+/// since the labeled prover does not take features.
+struct GoldenCircuit {
+	name: &'static str,
+	n_vars: &'static [usize],
+	label: Option<&'static str>,
+	features: ProtocolFeatures,
+}
+
+const GOLDEN_CIRCUITS: [GoldenCircuit; 4] = [
+	GoldenCircuit {
+		name: "product_8",
+		n_vars: &[8],
+		label: None,
+		features: ProtocolFeatures::empty(),
+	},
+	GoldenCircuit {
+		name: "batch_8_6_4",
+		n_vars: &[8, 6, 4],
+		label: None,
+		features: ProtocolFeatures::empty(),
+	},
+	GoldenCircuit {
+		name: "labeled_product_5",
+		n_vars: &[5],
+		label: Some("bitwise_and"),
+		features: ProtocolFeatures::empty(),
+	},
+	GoldenCircuit {
+		name: "uncompressed_product_5",
+		n_vars: &[5],
+		label: None,
+		features: ProtocolFeatures::UNCOMPRESSED_ROUND_PROOFS,
+	},
+];
+
+/// The names of the golden circuits, which every complete corpus covers.
+pub fn golden_circuit_names() -> impl Iterator<Item = &'static str> {
+	GOLDEN_CIRCUITS.iter().map(|circuit| circuit.name)
+}
+
+/// Proves every golden circuit and returns the corpus.
+pub fn generate_golden_proofs() -> Result<GoldenProofs, Error> {
+	let proofs = GOLDEN_CIRCUITS
+		.iter()
+		.map(|circuit| {
+			Ok(GoldenProof {
+				circuit: circuit.name.to_string(),
+				proof: encode_hex(&prove(circuit)?.encode(ProofEncoding::Standard)),
+			})
+		})
+		.collect::<Result<_, Error>>()?;
+	Ok(GoldenProofs { proofs })
+}
+
+/// Checks that the verifier accepts every proof in the corpus and that the prover reproduces it.
+///
+/// The corpus must cover every golden circuit, so that an empty or truncated corpus does not pass
+/// vacuously.
+pub fn run_golden_proofs(golden: &GoldenProofs) -> Result<(), Error> {
+	for golden_proof in golden.proofs.iter() {
+		let circuit = GOLDEN_CIRCUITS
+			.iter()
+			.find(|circuit| circuit.name == golden_proof.circuit)
+			.ok_or_else(|| Error::UnknownCircuit(golden_proof.circuit.clone()))?;
+		let bytes = decode_hex(&golden_proof.proof)
+			.ok_or_else(|| Error::InvalidHex(golden_proof.circuit.clone()))?;
+
+		let proof = Proof::<FE>::decode(&bytes, ProofEncoding::Standard)?;
+		batch_verify(&claims(circuit)?, proof, new_challenger())?;
+
+		if prove(circuit)?.encode(ProofEncoding::Standard) != bytes {
+			bail!(Error::ProofMismatch {
+				circuit: golden_proof.circuit.clone(),
+			});
+		}
+	}
+
+	if let Some(missing) = golden_circuit_names()
+		.find(|&name| !golden.proofs.iter().any(|proof| proof.circuit == name))
+	{
+		bail!(Error::MissingCircuit(missing.to_string()));
+	}
+	Ok(())
+}
+
+fn new_challenger() -> impl CanObserve<FE> + CanSample<FE> {
+	new_hasher_challenger::<BinaryField8b, GroestlHasher<BinaryField8b>>()
+}
+
+/// The deterministic witness of the claim at `index`, with the sum of the product.
+///
+/// The evaluations come from a fixed integer hash rather than a seeded RNG, whose output is not
+/// stable across versions of the `rand` crate.
+fn witness(index: usize, n_vars: usize) -> (Vec<MultilinearExtensionSpecialized<F, FE>>, FE) {
+	let column = |column: u32| {
+		let seed = (index as u32 * 2 + column + 1).wrapping_mul(0x85eb_ca6b);
+		(0..1u32 << n_vars)
+			.map(|i| F::new(i.wrapping_mul(0x9e37_79b9) ^ seed))
+			.collect::<Vec<_>>()
+	};
+	let (a, b) = (column(0), column(1));
+	let sum = a.iter().zip(b.iter()).map(|(&a, &b)| FE::from(a * b)).sum();
+	let multilinears = [a, b]
+		.into_iter()
+		.map(|values| {
+			MultilinearExtension::from_values(values)
+				.expect("the number of values is a power of two")
+				.specialize()
+		})
+		.collect();
+	(multilinears, sum)
+}
+
+fn claims(circuit: &GoldenCircuit) -> Result<Vec<SumcheckClaim<FE, BivariateProduct>>, Error> {
+	circuit
+		.n_vars
+		.iter()
+		.enumerate()
+		.map(|(index, &n_vars)| {
+			let (_, sum) = witness(index, n_vars);
+			let claim = SumcheckClaim::new(
+				n_vars,
+				2,
+				vec![CompositeSumClaim {
+					composition: BivariateProduct,
+					sum,
+				}],
+			)?;
+			Ok(match circuit.label {
+				Some(label) => claim.with_label(label),
+				None => claim,
+			})
+		})
+		.collect()
+}
+
+fn prove(circuit: &GoldenCircuit) -> Result<Proof<FE>, Error> {
+	let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+	let provers = circuit
+		.n_vars
+		.iter()
+		.enumerate()
+		.map(|(index, &n_vars)| {
+			let (multilinears, sum) = witness(index, n_vars);
+			RegularSumcheckProver::<FDomain, _, _, _>::new(
+				multilinears,
+				[CompositeSumClaim {
+					composition: BivariateProduct,
+					sum,
+				}],
+				domain_factory.clone(),
+				|_| 1,
+			)
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+
+	let (_, proof) = match circuit.label {
+		Some(label) => {
+			let labels = vec![Some(ClaimLabel::new(label)); provers.len()];
+			batch_prove_labeled(provers, labels, true, new_challenger())?
+		}
+		None => batch_prove_with_features(provers, circuit.features, new_challenger())?,
+	};
+	Ok(proof)
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+	bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+	if hex.len() % 2 != 0 {
+		return None;
+	}
+	(0..hex.len())
+		.step_by(2)
+		.map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use assert_matches::assert_matches;
+
+	const GOLDEN_PROOFS: &str = include_str!("../test_vectors/golden_proofs.json");
+	const REGENERATE: &str =
+		"BINIUS_REGENERATE_GOLDEN_PROOFS=1 cargo test -p binius_core regenerate_golden_proofs";
+
+	#[test]
+	fn test_golden_proofs() {
+		let golden = GoldenProofs::from_json(GOLDEN_PROOFS).unwrap();
+		if let Err(err) = run_golden_proofs(&golden) {
+			panic!(
+				"{err}; if the change is intentional, regenerate the corpus with `{REGENERATE}`"
+			);
+		}
+	}
+
+	#[test]
+	fn test_golden_proofs_detect_changes() {
+		let golden = generate_golden_proofs().unwrap();
+		let golden = GoldenProofs::from_json(&golden.to_json().unwrap()).unwrap();
+		assert!(golden_circuit_names().eq(golden.proofs.iter().map(|p| p.circuit.as_str())));
+		run_golden_proofs(&golden).unwrap();
+
+		// Flipping a bit of a round coefficient is rejected by the verifier.
+		let mut corrupted = golden.clone();
+		let proof = &mut corrupted.proofs[1].proof;
+		let last = proof.pop().unwrap();
+		proof.push(if last == '0' { '1' } else { '0' });
+		assert_matches!(run_golden_proofs(&corrupted), Err(Error::Sumcheck(_)));
+
+		let mut renamed = golden.clone();
+		renamed.proofs[0].circuit = "product_9".to_string();
+		assert_matches!(run_golden_proofs(&renamed), Err(Error::UnknownCircuit(_)));
+
+		let mut truncated = golden;
+		truncated.proofs.pop();
+		assert_matches!(run_golden_proofs(&truncated), Err(Error::MissingCircuit(_)));
+		assert_matches!(run_golden_proofs(&GoldenProofs::default()), Err(Error::MissingCircuit(_)));
+	}
+
+	#[test]
+	fn regenerate_golden_proofs() {
+		if std::env::var_os("BINIUS_REGENERATE_GOLDEN_PROOFS").is_none() {
+			return;
+		}
+		let json = generate_golden_proofs().unwrap().to_json().unwrap();
+		let path = concat!(env!("CARGO_MANIFEST_DIR"), "/test_vectors/golden_proofs.json");
+		std::fs::write(path, json + "\n").unwrap();
+	}
+}
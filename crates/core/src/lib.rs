@@ -7,15 +7,31 @@
 #![allow(clippy::suspicious_op_assign_impl)]
 
 pub mod challenger;
+pub mod error;
+#[cfg(any(test, feature = "test-vectors"))]
+pub mod golden_proofs;
 pub mod linalg;
 pub mod linear_code;
 pub mod merkle_tree;
 pub mod oracle;
+pub mod params;
 pub mod poly_commit;
 pub mod polynomial;
 pub mod protocols;
+pub mod prover_context;
+pub mod prover_key;
+pub mod prover_pipeline;
 #[allow(clippy::module_inception)]
 pub mod reed_solomon;
+#[cfg(any(test, feature = "test-vectors"))]
+pub mod test_vectors;
 pub mod witness;
+#[cfg(feature = "arrow")]
+pub mod witness_arrow;
+pub mod witness_dump;
+pub mod witness_fuzz;
+pub mod witness_replay;
+pub mod witness_scheduler;
 
 pub use core::iter::Step;
+pub use error::{Error, ErrorCategory, ErrorCode};
@@ -0,0 +1,117 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use crate::oracle::{CompositePolyOracle, Error, MultilinearOracleSet, OracleId};
+use binius_field::TowerField;
+use binius_utils::bail;
+
+/// Oracles of a [`MultilinearOracleSet`] that no constraint or claim of a statement depends on.
+///
+/// A committed oracle that is not referenced, directly or through virtual oracles, by any
+/// constraint or claim is unconstrained: the prover may commit to arbitrary values for it. This is
+/// almost always a bug where a column was committed but its constraints were forgotten.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OracleAudit {
+	/// Committed oracles that no constraint or claim depends on
+	pub unconstrained_committed: Vec<OracleId>,
+	/// Virtual oracles that no constraint or claim depends on
+	pub unused_virtual: Vec<OracleId>,
+}
+
+impl OracleAudit {
+	/// Whether every committed and virtual oracle is used by some constraint or claim.
+	pub fn is_clean(&self) -> bool {
+		self.unconstrained_committed.is_empty() && self.unused_virtual.is_empty()
+	}
+
+	/// Fails if any committed or virtual oracle is unused, for provers running in strict mode.
+	pub fn check(&self) -> Result<(), Error> {
+		if !self.is_clean() {
+			bail!(Error::UnconstrainedOracles {
+				committed: self.unconstrained_committed.clone(),
+				virtual_: self.unused_virtual.clone(),
+			});
+		}
+		Ok(())
+	}
+}
+
+impl<F: TowerField> MultilinearOracleSet<F> {
+	/// Finds the committed and virtual oracles that none of the `constraints` or the oracles in
+	/// `claims` depend on.
+	///
+	/// An oracle is used if it is an inner polynomial of a constraint, the subject of a claim, or
+	/// if a used virtual oracle is derived from it. Transparent oracles are never reported.
+	pub fn audit<'a>(
+		&self,
+		constraints: impl IntoIterator<Item = &'a CompositePolyOracle<F>>,
+		claims: impl IntoIterator<Item = OracleId>,
+	) -> Result<OracleAudit, Error> {
+		let mut used = vec![false; self.size()];
+		let mut stack = constraints
+			.into_iter()
+			.flat_map(|constraint| constraint.inner_polys_oracle_ids().collect::<Vec<_>>())
+			.chain(claims)
+			.collect::<Vec<_>>();
+		while let Some(id) = stack.pop() {
+			let used_id = used.get_mut(id).ok_or(Error::InvalidOracleId(id))?;
+			if !*used_id {
+				*used_id = true;
+				stack.extend(self.inner_ids(id));
+			}
+		}
+
+		let mut audit = OracleAudit::default();
+		for id in (0..self.size()).filter(|&id| !used[id]) {
+			if self.is_committed(id) {
+				audit.unconstrained_committed.push(id);
+			} else if !self.is_transparent(id) {
+				audit.unused_virtual.push(id);
+			}
+		}
+		Ok(audit)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{oracle::ShiftVariant, polynomial::IdentityCompositionPoly};
+	use assert_matches::assert_matches;
+	use binius_field::{BinaryField128b, BinaryField1b};
+
+	#[test]
+	fn test_audit_finds_unconstrained_oracles() {
+		let n_vars = 4;
+		let mut oracles = MultilinearOracleSet::<BinaryField128b>::new();
+		let batch_id = oracles.add_committed_batch(n_vars, BinaryField1b::TOWER_LEVEL);
+		let [a, b, c, d] = oracles.add_committed_multiple(batch_id);
+		let a_shifted = oracles
+			.add_shifted(a, 1, n_vars, ShiftVariant::CircularLeft)
+			.unwrap();
+		let bc = oracles.add_interleaved(b, c).unwrap();
+		let d_shifted = oracles
+			.add_shifted(d, 1, n_vars, ShiftVariant::LogicalLeft)
+			.unwrap();
+
+		let constraint = CompositePolyOracle::new(
+			n_vars,
+			vec![oracles.oracle(a_shifted)],
+			IdentityCompositionPoly,
+		)
+		.unwrap();
+
+		let audit = oracles.audit([&constraint], []).unwrap();
+		assert_eq!(audit.unconstrained_committed, vec![b, c, d]);
+		assert_eq!(audit.unused_virtual, vec![bc, d_shifted]);
+		assert_matches!(audit.check(), Err(Error::UnconstrainedOracles { .. }));
+
+		let audit = oracles.audit([&constraint], [bc, d_shifted]).unwrap();
+		assert!(audit.is_clean());
+		assert_matches!(audit.check(), Ok(()));
+
+		assert_matches!(
+			oracles.audit([&constraint], [oracles.size()]),
+			Err(Error::InvalidOracleId(_))
+		);
+	}
+}
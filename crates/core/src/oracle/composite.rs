@@ -55,6 +55,37 @@ impl<F: Field> CompositePolyOracle<F> {
 		)
 	}
 
+	/// Checks that the composition fits in the tower level of the inner oracles.
+	///
+	/// The prover evaluates the composition over the witnesses of the inner oracles, which are
+	/// packed in the field of the highest oracle tower level, so the constants of a composition of
+	/// a higher tower level cannot be represented. The exception is a composition of the tower
+	/// level `field_tower_level` of the claim field, such as a mix of constraints with a verifier
+	/// challenge, which the prover has to evaluate over the claim field anyway.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::CompositionTowerLevelTooHigh`] if the composition tower level exceeds the tower
+	///   level of all inner oracles and is below `field_tower_level`
+	pub fn check_tower_levels(&self, field_tower_level: usize) -> Result<(), Error> {
+		let composition_tower_level = self.composition.binary_tower_level();
+		let max_oracle_tower_level = self
+			.inner
+			.iter()
+			.map(MultilinearPolyOracle::binary_tower_level)
+			.max()
+			.unwrap_or(0);
+		if composition_tower_level > max_oracle_tower_level
+			&& composition_tower_level < field_tower_level
+		{
+			bail!(Error::CompositionTowerLevelTooHigh {
+				composition_tower_level,
+				max_oracle_tower_level,
+			});
+		}
+		Ok(())
+	}
+
 	pub fn n_vars(&self) -> usize {
 		self.n_vars
 	}
@@ -76,6 +107,7 @@ impl<F: Field> CompositePolyOracle<F> {
 mod tests {
 	use super::*;
 	use crate::{oracle::MultilinearOracleSet, polynomial::Error as PolynomialError};
+	use assert_matches::assert_matches;
 	use binius_field::{BinaryField128b, BinaryField2b, BinaryField32b, BinaryField8b, TowerField};
 
 	#[derive(Clone, Debug)]
@@ -151,4 +183,52 @@ mod tests {
 		.unwrap();
 		assert_eq!(composite.binary_tower_level(), BinaryField32b::TOWER_LEVEL);
 	}
+
+	#[test]
+	fn test_check_tower_levels() {
+		type F = BinaryField128b;
+
+		let n_vars = 5;
+
+		let mut oracles = MultilinearOracleSet::<F>::new();
+		let batch_id_2b = oracles.add_committed_batch(n_vars, BinaryField2b::TOWER_LEVEL);
+		let poly_2b = oracles.add_committed(batch_id_2b);
+
+		let batch_id_32b = oracles.add_committed_batch(n_vars, BinaryField32b::TOWER_LEVEL);
+		let poly_32b = oracles.add_committed(batch_id_32b);
+
+		let composite = CompositePolyOracle::new(
+			n_vars,
+			vec![
+				oracles.oracle(poly_2b),
+				oracles.oracle(poly_2b),
+				oracles.oracle(poly_32b),
+			],
+			TestByteComposition,
+		)
+		.unwrap();
+		composite.check_tower_levels(F::TOWER_LEVEL).unwrap();
+
+		let composite = CompositePolyOracle::new(
+			n_vars,
+			vec![
+				oracles.oracle(poly_2b),
+				oracles.oracle(poly_2b),
+				oracles.oracle(poly_2b),
+			],
+			TestByteComposition,
+		)
+		.unwrap();
+		composite
+			.check_tower_levels(BinaryField8b::TOWER_LEVEL)
+			.unwrap();
+		assert_matches!(
+			composite.check_tower_levels(F::TOWER_LEVEL),
+			Err(Error::CompositionTowerLevelTooHigh {
+				composition_tower_level,
+				max_oracle_tower_level,
+			}) if composition_tower_level == BinaryField8b::TOWER_LEVEL
+				&& max_oracle_tower_level == BinaryField2b::TOWER_LEVEL
+		);
+	}
 }
@@ -1,6 +1,6 @@
 // Copyright 2024 Ulvetanna Inc.
 
-use crate::oracle::OracleId;
+use crate::oracle::{BatchId, OracleId};
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -20,8 +20,44 @@ pub enum Error {
 		"n_vars ({n_vars}) must be at least as big as the requested log_degree ({log_degree})"
 	)]
 	NotEnoughVarsForPacking { n_vars: usize, log_degree: usize },
+	#[error("the number of stacked instances ({n_instances}) must be a power of two")]
+	InvalidNumberOfInstances { n_instances: usize },
+	#[error("stacked instances must have the same number of columns")]
+	InstanceColumnsMismatch,
+	#[error("index {index} is out of range for a trace dimension of size 2^{log_size}")]
+	InvalidTraceIndex { index: usize, log_size: usize },
+	#[error("a single entry of the trace would be a multilinear with no variables")]
+	EmptyTraceProjection,
 	#[error("no oracle exists in this MultilinearOracleSet with id {0}")]
 	InvalidOracleId(OracleId),
+	#[error("no committed batch exists in this MultilinearOracleSet with id {0}")]
+	InvalidBatchId(BatchId),
+	#[error("committed batch {batch_id} has tower level {tower_level}, expected a batch of bits")]
+	CommittedBatchNotBinary {
+		batch_id: BatchId,
+		tower_level: usize,
+	},
+	#[error("oracle {id} is not committed, so its data cannot be shared with another batch")]
+	SharedOracleNotCommitted { id: OracleId },
+	#[error("committed batch {batch_id} cannot share data with committed batch {source_batch_id}")]
+	SharedBatchMismatch {
+		batch_id: BatchId,
+		source_batch_id: BatchId,
+	},
+	#[error("oracle {id} is not committed, so it cannot be read in bit-reversed order")]
+	BitReversedNotCommitted { id: OracleId },
+	#[error("oracle {id} is bit-reversed, so it cannot be shifted or packed")]
+	BitReversedInner { id: OracleId },
 	#[error("tower_level ({tower_level}) exceeds maximum")]
 	TowerLevelTooHigh { tower_level: usize },
+	#[error("composition tower level ({composition_tower_level}) exceeds the tower level of the composed oracles ({max_oracle_tower_level})")]
+	CompositionTowerLevelTooHigh {
+		composition_tower_level: usize,
+		max_oracle_tower_level: usize,
+	},
+	#[error("committed oracles {committed:?} are unconstrained and virtual oracles {virtual_:?} are unused")]
+	UnconstrainedOracles {
+		committed: Vec<OracleId>,
+		virtual_: Vec<OracleId>,
+	},
 }
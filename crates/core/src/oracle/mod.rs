@@ -6,12 +6,19 @@
 //!
 //! [DP23]: <https://eprint.iacr.org/2023/1784>
 
+mod audit;
 mod committed;
 mod composite;
 mod error;
 mod multilinear;
+mod simplify;
+mod stacked;
+mod trace2d;
 
+pub use audit::*;
 pub use committed::*;
 pub use composite::*;
 pub use error::Error;
 pub use multilinear::*;
+pub use stacked::*;
+pub use trace2d::*;
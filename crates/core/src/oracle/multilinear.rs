@@ -4,7 +4,7 @@ use crate::{
 	oracle::{BatchId, CommittedBatch, CommittedId, CompositePolyOracle, Error},
 	polynomial::{Error as PolynomialError, IdentityCompositionPoly, MultivariatePoly},
 };
-use binius_field::{Field, TowerField};
+use binius_field::{BinaryField1b, ExtensionField, Field, TowerField};
 use binius_utils::bail;
 use getset::{CopyGetters, Getters};
 use std::{fmt::Debug, sync::Arc};
@@ -24,9 +24,9 @@ struct CommittedBatchMeta {
 
 /// Metadata about multilinear oracles.
 ///
-/// This is kept internal to [`MultilinearOracleSet`].
+/// This is kept internal to the `oracle` module.
 #[derive(Debug, Clone)]
-enum MultilinearOracleMeta<F: TowerField> {
+pub(super) enum MultilinearOracleMeta<F: TowerField> {
 	Transparent(Arc<dyn MultivariatePoly<F>>),
 	Committed(CommittedId),
 	Repeating {
@@ -59,6 +59,9 @@ enum MultilinearOracleMeta<F: TowerField> {
 		inner_id: OracleId,
 		n_vars: usize,
 	},
+	BitReversed {
+		inner_id: OracleId,
+	},
 }
 
 /// An ordered set of multilinear polynomial oracles.
@@ -73,6 +76,8 @@ enum MultilinearOracleMeta<F: TowerField> {
 pub struct MultilinearOracleSet<F: TowerField> {
 	batches: Vec<CommittedBatchMeta>,
 	oracles: Vec<MultilinearOracleMeta<F>>,
+	/// The committed oracles added with [`Self::add_committed_shared`], with their sources.
+	shared: Vec<(OracleId, OracleId)>,
 }
 
 impl<F: TowerField> MultilinearOracleSet<F> {
@@ -81,6 +86,7 @@ impl<F: TowerField> MultilinearOracleSet<F> {
 		Self {
 			batches: Vec::new(),
 			oracles: Vec::new(),
+			shared: Vec::new(),
 		}
 	}
 
@@ -121,10 +127,123 @@ impl<F: TowerField> MultilinearOracleSet<F> {
 		oracle_id
 	}
 
+	/// Adds a committed oracle to a batch that holds the same data as a committed oracle of another
+	/// batch.
+	///
+	/// This is for columns used by several tables that are committed in different batches, such as
+	/// a column shared between a CPU table and a hash table. The new oracle is committed in its
+	/// batch like any other, but its witness is the witness of `source_id`, registered with
+	/// [`crate::witness::MultilinearExtensionIndex::update_shared`] without copying the data. The
+	/// polynomial commitment schemes commit each batch as a whole, so the column is encoded once
+	/// per batch.
+	///
+	/// Nothing binds the two committed oracles to each other by itself. The proof must include
+	/// [`crate::protocols::evalcheck::prove_shared_columns`], which proves that both are the same
+	/// multilinear by evaluating them at a common random point.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::InvalidBatchId`] if `batch_id` is not in the set
+	/// * [`Error::InvalidOracleId`] if `source_id` is not in the set
+	/// * [`Error::SharedOracleNotCommitted`] if `source_id` is not a committed oracle
+	/// * [`Error::SharedBatchMismatch`] if `batch_id` is the batch of `source_id`, or does not have
+	///   its number of variables and tower level
+	pub fn add_committed_shared(
+		&mut self,
+		batch_id: BatchId,
+		source_id: OracleId,
+	) -> Result<OracleId, Error> {
+		let Some(source) = self.oracles.get(source_id) else {
+			bail!(Error::InvalidOracleId(source_id));
+		};
+		let MultilinearOracleMeta::Committed(CommittedId {
+			batch_id: source_batch_id,
+			..
+		}) = *source
+		else {
+			bail!(Error::SharedOracleNotCommitted { id: source_id });
+		};
+
+		let Some(batch) = self.batches.get(batch_id) else {
+			bail!(Error::InvalidBatchId(batch_id));
+		};
+		let source_batch = &self.batches[source_batch_id];
+		if batch_id == source_batch_id
+			|| batch.n_vars != source_batch.n_vars
+			|| batch.tower_level != source_batch.tower_level
+		{
+			bail!(Error::SharedBatchMismatch {
+				batch_id,
+				source_batch_id,
+			});
+		}
+
+		let id = self.add_committed(batch_id);
+		self.shared.push((id, source_id));
+		Ok(id)
+	}
+
+	/// The committed oracles added with [`Self::add_committed_shared`], each with the ID of the
+	/// oracle it shares its data with.
+	pub fn shared_committed(&self) -> impl Iterator<Item = (OracleId, OracleId)> + '_ {
+		self.shared.iter().copied()
+	}
+
 	pub fn add_committed_multiple<const N: usize>(&mut self, batch_id: BatchId) -> [OracleId; N] {
 		[0; N].map(|_| self.add_committed(batch_id))
 	}
 
+	/// Adds a committed oracle of 1-bit values to a batch, along with a packed view of it.
+	///
+	/// The committed oracle holds data that is logically 1-bit, and constraints use it as
+	/// elements of `F` embedded from the 1-bit subfield. Its batch must have tower level 0, so that
+	/// the polynomial commitment scheme commits the bits rather than full field elements. The
+	/// packed view is a [`Packed`] oracle packing `2^log_degree` consecutive bits
+	/// into one element of tower level `log_degree`, for protocols that want to read the data in
+	/// larger field elements. Evalcheck reduces claims on the packed view to claims on the
+	/// committed bits, so the two stay consistent without committing the data twice.
+	///
+	/// Returns the IDs of the committed oracle and of its packed view. The arguments are checked
+	/// before anything is added, so on error the set is left unchanged.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::InvalidBatchId`] if `batch_id` is not in the set
+	/// * [`Error::CommittedBatchNotBinary`] if the batch does not have tower level 0
+	/// * [`Error::TowerLevelTooHigh`] if `log_degree` exceeds the tower level of `F`
+	/// * [`Error::NotEnoughVarsForPacking`] if `log_degree` exceeds the number of variables of the
+	///   batch
+	pub fn add_committed_packed(
+		&mut self,
+		batch_id: BatchId,
+		log_degree: usize,
+	) -> Result<(OracleId, OracleId), Error> {
+		let Some(batch) = self.batches.get(batch_id) else {
+			bail!(Error::InvalidBatchId(batch_id));
+		};
+		if batch.tower_level != BinaryField1b::TOWER_LEVEL {
+			bail!(Error::CommittedBatchNotBinary {
+				batch_id,
+				tower_level: batch.tower_level,
+			});
+		}
+		if log_degree > F::TOWER_LEVEL {
+			bail!(Error::TowerLevelTooHigh {
+				tower_level: log_degree,
+			});
+		}
+		if log_degree > batch.n_vars {
+			bail!(Error::NotEnoughVarsForPacking {
+				n_vars: batch.n_vars,
+				log_degree,
+			});
+		}
+
+		let committed_id = self.add_committed(batch_id);
+		let packed_id = self.add_packed(committed_id, log_degree)?;
+		Ok((committed_id, packed_id))
+	}
+
 	pub fn add_repeating(&mut self, id: OracleId, log_count: usize) -> Result<OracleId, Error> {
 		if id >= self.oracles.len() {
 			bail!(Error::InvalidOracleId(id));
@@ -183,6 +302,10 @@ impl<F: TowerField> MultilinearOracleSet<F> {
 			bail!(Error::InvalidOracleId(id));
 		}
 
+		if self.is_bit_reversed(id) {
+			bail!(Error::BitReversedInner { id });
+		}
+
 		let inner_n_vars = self.n_vars(id);
 		if block_bits > inner_n_vars {
 			bail!(PolynomialError::InvalidBlockSize {
@@ -211,6 +334,10 @@ impl<F: TowerField> MultilinearOracleSet<F> {
 			bail!(Error::InvalidOracleId(id));
 		}
 
+		if self.is_bit_reversed(id) {
+			bail!(Error::BitReversedInner { id });
+		}
+
 		let inner_n_vars = self.n_vars(id);
 		if log_degree > inner_n_vars {
 			bail!(Error::NotEnoughVarsForPacking {
@@ -302,6 +429,36 @@ impl<F: TowerField> MultilinearOracleSet<F> {
 		Ok(id)
 	}
 
+	/// Adds the oracle whose evaluations are those of a committed oracle in bit-reversed order.
+	///
+	/// The new oracle $f$ is related to the committed oracle $g$ by
+	/// $f(x_0, \ldots, x_{n-1}) = g(x_{n-1}, \ldots, x_0)$, so the hypercube evaluations of $f$ are
+	/// laid out in bit-reversed order in the witness of $g$. This lets large columns be produced in
+	/// the natural order of the additive NTT and committed without permuting them:
+	///
+	/// * the witness of the new oracle is that of $g$, shared with
+	///   [`crate::witness::MultilinearExtensionIndex::update_shared`];
+	/// * sumcheck provers over the stored data fold with
+	///   [`crate::protocols::sumcheck_v2::FoldDirection::HighToLow`], which binds the variables of
+	///   $f$ in the order a verifier folding low-to-high expects;
+	/// * evalcheck reduces an evaluation claim on $f$ to a claim on $g$ at the reversed point,
+	///   which the polynomial commitment scheme opens on the data as it was committed.
+	///
+	/// The witness of the new oracle is not laid out in lexicographic order, so it cannot be the
+	/// inner oracle of a shifted or packed oracle, whose reductions read the witness of their inner
+	/// oracle directly.
+	pub fn add_bit_reversed(&mut self, id: OracleId) -> Result<OracleId, Error> {
+		if id >= self.oracles.len() {
+			bail!(Error::InvalidOracleId(id));
+		}
+		if !self.is_committed(id) {
+			bail!(Error::BitReversedNotCommitted { id });
+		}
+
+		let id = self.add(MultilinearOracleMeta::BitReversed { inner_id: id });
+		Ok(id)
+	}
+
 	pub fn committed_batch(&self, id: BatchId) -> CommittedBatch {
 		let batch = &self.batches[id];
 		CommittedBatch {
@@ -338,6 +495,52 @@ impl<F: TowerField> MultilinearOracleSet<F> {
 		self.oracle(self.committed_oracle_id(id))
 	}
 
+	/// Number of oracles in the set. Oracle IDs range from 0 up to this value.
+	pub fn size(&self) -> usize {
+		self.oracles.len()
+	}
+
+	pub(super) fn is_committed(&self, id: OracleId) -> bool {
+		matches!(self.oracles[id], MultilinearOracleMeta::Committed(_))
+	}
+
+	pub(super) fn is_transparent(&self, id: OracleId) -> bool {
+		matches!(self.oracles[id], MultilinearOracleMeta::Transparent(_))
+	}
+
+	fn is_bit_reversed(&self, id: OracleId) -> bool {
+		matches!(self.oracles[id], MultilinearOracleMeta::BitReversed { .. })
+	}
+
+	pub(super) fn meta(&self, id: OracleId) -> &MultilinearOracleMeta<F> {
+		&self.oracles[id]
+	}
+
+	/// Replaces the metadata of an oracle with that of an equivalent oracle.
+	pub(super) fn replace_meta(&mut self, id: OracleId, meta: MultilinearOracleMeta<F>) {
+		self.oracles[id] = meta;
+	}
+
+	/// IDs of the oracles that the given oracle is directly derived from.
+	pub(super) fn inner_ids(&self, id: OracleId) -> Vec<OracleId> {
+		use MultilinearOracleMeta::*;
+		match &self.oracles[id] {
+			Transparent(_) | Committed(_) => vec![],
+			Repeating { inner_id, .. }
+			| Shifted { inner_id, .. }
+			| Packed { inner_id, .. }
+			| Projected { inner_id, .. }
+			| ZeroPadded { inner_id, .. }
+			| BitReversed { inner_id } => vec![*inner_id],
+			Interleaved(inner_id_0, inner_id_1) | Merged(inner_id_0, inner_id_1) => {
+				vec![*inner_id_0, *inner_id_1]
+			}
+			LinearCombination { inner, .. } => {
+				inner.iter().map(|(inner_id, _)| *inner_id).collect()
+			}
+		}
+	}
+
 	pub fn oracle(&self, id: OracleId) -> MultilinearPolyOracle<F> {
 		match &self.oracles[id] {
 			MultilinearOracleMeta::Transparent(poly) => MultilinearPolyOracle::Transparent(
@@ -428,6 +631,10 @@ impl<F: TowerField> MultilinearOracleSet<F> {
 					n_vars: *n_vars,
 				}
 			}
+			MultilinearOracleMeta::BitReversed { inner_id } => MultilinearPolyOracle::BitReversed {
+				id,
+				inner: Box::new(self.oracle(*inner_id)),
+			},
 		}
 	}
 
@@ -452,6 +659,7 @@ impl<F: TowerField> MultilinearOracleSet<F> {
 			} => self.n_vars(*inner_id) - values.len(),
 			LinearCombination { n_vars, .. } => *n_vars,
 			ZeroPadded { n_vars, .. } => *n_vars,
+			BitReversed { inner_id } => self.n_vars(*inner_id),
 		}
 	}
 
@@ -473,15 +681,34 @@ impl<F: TowerField> MultilinearOracleSet<F> {
 				inner_id,
 				log_degree,
 			} => self.tower_level(*inner_id) + log_degree,
-			Projected { .. } => F::TOWER_LEVEL,
-			// TODO: We can derive this more tightly by inspecting the coefficients and inner
-			// polynomials.
-			LinearCombination { .. } => F::TOWER_LEVEL,
-			ZeroPadded { .. } => F::TOWER_LEVEL,
+			Projected {
+				inner_id, values, ..
+			} => values
+				.iter()
+				.map(|&value| element_tower_level(value))
+				.fold(self.tower_level(*inner_id), usize::max),
+			LinearCombination { offset, inner, .. } => inner
+				.iter()
+				.map(|&(inner_id, coeff)| {
+					self.tower_level(inner_id).max(element_tower_level(coeff))
+				})
+				.fold(element_tower_level(*offset), usize::max),
+			ZeroPadded { inner_id, .. } | BitReversed { inner_id } => self.tower_level(*inner_id),
 		}
 	}
 }
 
+/// Returns the smallest tower level of a subfield of `F` that contains `elem`.
+fn element_tower_level<F: TowerField>(elem: F) -> usize {
+	// In the canonical tower basis, the subfield at level $\iota$ is spanned by the first
+	// $2^\iota$ basis elements.
+	ExtensionField::<BinaryField1b>::iter_bases(&elem)
+		.enumerate()
+		.filter(|(_, coord)| *coord != BinaryField1b::ZERO)
+		.last()
+		.map_or(0, |(i, _)| (i + 1).next_power_of_two().ilog2() as usize)
+}
+
 /// A multilinear polynomial oracle in the polynomial IOP model.
 ///
 /// In the multilinear polynomial IOP model, a prover sends multilinear polynomials to an oracle,
@@ -527,6 +754,11 @@ pub enum MultilinearPolyOracle<F: Field> {
 		inner: Box<MultilinearPolyOracle<F>>,
 		n_vars: usize,
 	},
+	/// See [`MultilinearOracleSet::add_bit_reversed`].
+	BitReversed {
+		id: OracleId,
+		inner: Box<MultilinearPolyOracle<F>>,
+	},
 }
 
 /// A transparent multilinear polynomial oracle.
@@ -578,9 +810,10 @@ pub struct Projected<F: Field> {
 	values: Vec<F>,
 	#[get_copy = "pub"]
 	projection_variant: ProjectionVariant,
+	tower_level: usize,
 }
 
-impl<F: Field> Projected<F> {
+impl<F: TowerField> Projected<F> {
 	fn new(
 		inner: MultilinearPolyOracle<F>,
 		values: Vec<F>,
@@ -591,16 +824,30 @@ impl<F: Field> Projected<F> {
 		if values_len >= n_vars {
 			bail!(Error::InvalidProjection { n_vars, values_len });
 		}
+		let tower_level = values
+			.iter()
+			.map(|&value| element_tower_level(value))
+			.fold(inner.binary_tower_level(), usize::max);
 		Ok(Self {
 			inner: inner.into(),
 			values,
 			projection_variant,
+			tower_level,
 		})
 	}
+}
 
+impl<F: Field> Projected<F> {
 	fn n_vars(&self) -> usize {
 		self.inner.n_vars() - self.values.len()
 	}
+
+	/// Maximum tower level of the oracle's values over the boolean hypercube.
+	///
+	/// This accounts for the tower levels of the projection values as well as the inner oracle.
+	pub fn binary_tower_level(&self) -> usize {
+		self.tower_level
+	}
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -675,9 +922,10 @@ pub struct LinearCombination<F: Field> {
 	#[get_copy = "pub"]
 	offset: F,
 	inner: Vec<(Box<MultilinearPolyOracle<F>>, F)>,
+	tower_level: usize,
 }
 
-impl<F: Field> LinearCombination<F> {
+impl<F: TowerField> LinearCombination<F> {
 	fn new(
 		n_vars: usize,
 		offset: F,
@@ -691,14 +939,29 @@ impl<F: Field> LinearCombination<F> {
 				}
 				Ok((Box::new(poly), coeff))
 			})
-			.collect::<Result<_, _>>()?;
+			.collect::<Result<Vec<_>, _>>()?;
 
+		let tower_level = inner
+			.iter()
+			.map(|(poly, coeff)| poly.binary_tower_level().max(element_tower_level(*coeff)))
+			.fold(element_tower_level(offset), usize::max);
 		Ok(Self {
 			n_vars,
 			offset,
 			inner,
+			tower_level,
 		})
 	}
+}
+
+impl<F: Field> LinearCombination<F> {
+	/// Maximum tower level of the oracle's values over the boolean hypercube.
+	///
+	/// This accounts for the tower levels of the coefficients and offset as well as the inner
+	/// oracles.
+	pub fn binary_tower_level(&self) -> usize {
+		self.tower_level
+	}
 
 	pub fn n_polys(&self) -> usize {
 		self.inner.len()
@@ -727,6 +990,7 @@ impl<F: Field> MultilinearPolyOracle<F> {
 			Packed(id, _) => *id,
 			LinearCombination(id, _) => *id,
 			ZeroPadded { id, .. } => *id,
+			BitReversed { id, .. } => *id,
 		}
 	}
 
@@ -745,6 +1009,7 @@ impl<F: Field> MultilinearPolyOracle<F> {
 			Packed(_, packed) => packed.inner().n_vars() - packed.log_degree(),
 			LinearCombination(_, lin_com) => lin_com.n_vars,
 			ZeroPadded { n_vars, .. } => *n_vars,
+			BitReversed { inner, .. } => inner.n_vars(),
 		}
 	}
 
@@ -759,17 +1024,11 @@ impl<F: Field> MultilinearPolyOracle<F> {
 				poly0.binary_tower_level().max(poly1.binary_tower_level())
 			}
 			Merged(_, poly0, poly1) => poly0.binary_tower_level().max(poly1.binary_tower_level()),
-			// TODO: This is wrong, should be F::TOWER_LEVEL
-			Projected(_, projected) => projected.inner().binary_tower_level(),
+			Projected(_, projected) => projected.binary_tower_level(),
 			Shifted(_, shifted) => shifted.inner().binary_tower_level(),
 			Packed(_, packed) => packed.log_degree + packed.inner().binary_tower_level(),
-			LinearCombination(_, lin_com) => lin_com
-				.inner
-				.iter()
-				.map(|(poly, _)| poly.binary_tower_level())
-				.max()
-				.unwrap_or(0),
-			ZeroPadded { inner, .. } => inner.binary_tower_level(),
+			LinearCombination(_, lin_com) => lin_com.binary_tower_level(),
+			ZeroPadded { inner, .. } | BitReversed { inner, .. } => inner.binary_tower_level(),
 		}
 	}
 
@@ -779,3 +1038,166 @@ impl<F: Field> MultilinearPolyOracle<F> {
 		composite.expect("Can always apply the identity composition to one variable")
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use binius_field::{BinaryField128b, BinaryField32b, BinaryField8b};
+
+	type F = BinaryField128b;
+
+	#[test]
+	fn test_element_tower_level() {
+		assert_eq!(element_tower_level(F::ZERO), 0);
+		assert_eq!(element_tower_level(F::ONE), 0);
+		assert_eq!(element_tower_level(F::from(BinaryField8b::new(2))), 1);
+		assert_eq!(element_tower_level(F::from(BinaryField8b::new(0x80))), 3);
+		assert_eq!(element_tower_level(F::from(BinaryField32b::new(0x100))), 4);
+		assert_eq!(element_tower_level(F::new(1 << 127)), 7);
+	}
+
+	#[test]
+	fn test_add_committed_packed() {
+		let n_vars = 8;
+		let mut oracles = MultilinearOracleSet::<F>::new();
+		let batch_id = oracles.add_committed_batch(n_vars, BinaryField1b::TOWER_LEVEL);
+		let (committed, packed) = oracles.add_committed_packed(batch_id, 5).unwrap();
+
+		assert_eq!(oracles.committed_batch(batch_id).n_polys, 1);
+		assert_eq!(oracles.tower_level(committed), 0);
+		assert_eq!(oracles.n_vars(packed), n_vars - 5);
+		assert_eq!(oracles.tower_level(packed), 5);
+		let MultilinearPolyOracle::Packed(_, packed) = oracles.oracle(packed) else {
+			panic!("expected a packed oracle");
+		};
+		assert_eq!(packed.inner().id(), committed);
+
+		assert!(matches!(
+			oracles.add_committed_packed(batch_id, 8),
+			Err(Error::TowerLevelTooHigh { tower_level: 8 })
+		));
+		let wide_batch_id = oracles.add_committed_batch(n_vars, BinaryField8b::TOWER_LEVEL);
+		assert!(matches!(
+			oracles.add_committed_packed(wide_batch_id, 2),
+			Err(Error::CommittedBatchNotBinary { tower_level: 3, .. })
+		));
+		assert!(matches!(
+			oracles.add_committed_packed(wide_batch_id + 1, 2),
+			Err(Error::InvalidBatchId(_))
+		));
+
+		let short_batch_id = oracles.add_committed_batch(2, BinaryField1b::TOWER_LEVEL);
+		let n_oracles = oracles.size();
+		assert!(matches!(
+			oracles.add_committed_packed(short_batch_id, 3),
+			Err(Error::NotEnoughVarsForPacking {
+				n_vars: 2,
+				log_degree: 3
+			})
+		));
+		assert_eq!(oracles.size(), n_oracles);
+		assert_eq!(oracles.committed_batch(short_batch_id).n_polys, 0);
+	}
+
+	#[test]
+	fn test_add_committed_shared() {
+		let n_vars = 6;
+		let mut oracles = MultilinearOracleSet::<F>::new();
+		let cpu_batch_id = oracles.add_committed_batch(n_vars, BinaryField8b::TOWER_LEVEL);
+		let hash_batch_id = oracles.add_committed_batch(n_vars, BinaryField8b::TOWER_LEVEL);
+		let [source, _] = oracles.add_committed_multiple(cpu_batch_id);
+		let shared = oracles.add_committed_shared(hash_batch_id, source).unwrap();
+
+		assert_eq!(oracles.committed_batch(hash_batch_id).n_polys, 1);
+		assert_eq!(oracles.shared_committed().collect::<Vec<_>>(), vec![(shared, source)]);
+
+		assert!(matches!(
+			oracles.add_committed_shared(cpu_batch_id, source),
+			Err(Error::SharedBatchMismatch { .. })
+		));
+		let narrow_batch_id = oracles.add_committed_batch(n_vars, BinaryField1b::TOWER_LEVEL);
+		assert!(matches!(
+			oracles.add_committed_shared(narrow_batch_id, source),
+			Err(Error::SharedBatchMismatch { .. })
+		));
+		let packed = oracles.add_packed(source, 1).unwrap();
+		assert!(matches!(
+			oracles.add_committed_shared(hash_batch_id, packed),
+			Err(Error::SharedOracleNotCommitted { .. })
+		));
+		assert!(matches!(
+			oracles.add_committed_shared(narrow_batch_id + 1, source),
+			Err(Error::InvalidBatchId(_))
+		));
+	}
+
+	#[test]
+	fn test_virtual_oracle_tower_levels() {
+		let n_vars = 4;
+		let mut oracles = MultilinearOracleSet::<F>::new();
+		let batch_id = oracles.add_committed_batch(n_vars, BinaryField1b::TOWER_LEVEL);
+		let [a, b] = oracles.add_committed_multiple(batch_id);
+
+		let sum = oracles
+			.add_linear_combination(n_vars, [(a, F::ONE), (b, F::ONE)])
+			.unwrap();
+		let scaled = oracles
+			.add_linear_combination(n_vars, [(a, F::from(BinaryField8b::new(0x10)))])
+			.unwrap();
+		let offset = oracles
+			.add_linear_combination_with_offset(
+				n_vars,
+				F::from(BinaryField32b::new(0x10000)),
+				[(a, F::ONE)],
+			)
+			.unwrap();
+		let projected = oracles
+			.add_projected(
+				scaled,
+				vec![F::from(BinaryField8b::new(3))],
+				ProjectionVariant::FirstVars,
+			)
+			.unwrap();
+		let padded = oracles.add_zero_padded(sum, n_vars + 1).unwrap();
+		let bit_reversed = oracles.add_bit_reversed(a).unwrap();
+
+		for (id, expected) in [
+			(sum, 0),
+			(scaled, 3),
+			(offset, 5),
+			(projected, 3),
+			(padded, 0),
+			(bit_reversed, 0),
+		] {
+			assert_eq!(oracles.tower_level(id), expected);
+			assert_eq!(oracles.oracle(id).binary_tower_level(), expected);
+		}
+	}
+	#[test]
+	fn test_add_bit_reversed() {
+		let n_vars = 4;
+		let mut oracles = MultilinearOracleSet::<F>::new();
+		let batch_id = oracles.add_committed_batch(n_vars, BinaryField1b::TOWER_LEVEL);
+		let [a, b] = oracles.add_committed_multiple(batch_id);
+		let sum = oracles
+			.add_linear_combination(n_vars, [(a, F::ONE), (b, F::ONE)])
+			.unwrap();
+
+		let bit_reversed = oracles.add_bit_reversed(a).unwrap();
+		assert_eq!(oracles.n_vars(bit_reversed), n_vars);
+		assert_eq!(oracles.inner_ids(bit_reversed), vec![a]);
+
+		assert!(matches!(
+			oracles.add_bit_reversed(sum),
+			Err(Error::BitReversedNotCommitted { id }) if id == sum
+		));
+		assert!(matches!(
+			oracles.add_shifted(bit_reversed, 1, 2, ShiftVariant::CircularLeft),
+			Err(Error::BitReversedInner { id }) if id == bit_reversed
+		));
+		assert!(matches!(
+			oracles.add_packed(bit_reversed, 1),
+			Err(Error::BitReversedInner { id }) if id == bit_reversed
+		));
+	}
+}
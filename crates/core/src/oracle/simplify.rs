@@ -0,0 +1,305 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use crate::oracle::{
+	MultilinearOracleMeta, MultilinearOracleSet, OracleId, ProjectionVariant, ShiftVariant,
+};
+use binius_field::TowerField;
+use std::collections::HashMap;
+
+impl<F: TowerField> MultilinearOracleSet<F> {
+	/// Collapses chains of virtual oracles into single equivalent virtual oracles.
+	///
+	/// Each oracle derived from an oracle of the same kind is rewritten in place, keeping its ID,
+	/// to be derived directly from the oracles further down the chain:
+	///
+	/// * a shift of a shift with the same block size and variant becomes a single shift, unless
+	///   the combined offset is zero or leaves the block
+	/// * a linear combination of linear combinations becomes a single linear combination, with the
+	///   offsets folded in, the coefficients of repeated oracles summed, and zero terms dropped
+	/// * a projection of a projection with the same variant becomes a single projection
+	/// * a packing of a packing becomes a single packing
+	///
+	/// A projection of a packed oracle is left as is. Projecting the variables of a packed oracle
+	/// fixes variables in the middle of its inner oracle, which no single oracle expresses.
+	///
+	/// The rewritten oracles are the same polynomials, so their witnesses are unchanged, but
+	/// evalcheck reduces claims on them in fewer steps and with smaller proofs. As the proofs
+	/// depend on the structure of the oracles, the prover and verifier must both simplify their
+	/// oracle sets. The intermediate oracles of a collapsed chain that nothing else depends on are
+	/// left in the set and reported as unused by [`Self::audit`].
+	///
+	/// Returns the IDs of the rewritten oracles, in increasing order.
+	pub fn simplify(&mut self) -> Vec<OracleId> {
+		// Oracles are only derived from oracles with smaller IDs, so the oracles an oracle is
+		// derived from are simplified before it, and a single pass collapses whole chains.
+		let mut rewritten = Vec::new();
+		for id in 0..self.size() {
+			if let Some(meta) = self.simplified_meta(id) {
+				self.replace_meta(id, meta);
+				rewritten.push(id);
+			}
+		}
+		rewritten
+	}
+
+	/// The metadata of an oracle equivalent to the given one with a shorter chain, if any.
+	fn simplified_meta(&self, id: OracleId) -> Option<MultilinearOracleMeta<F>> {
+		use MultilinearOracleMeta::*;
+		match self.meta(id) {
+			Shifted {
+				inner_id,
+				offset,
+				block_bits,
+				variant,
+			} => {
+				let Shifted {
+					inner_id: inner_inner_id,
+					offset: inner_offset,
+					block_bits: inner_block_bits,
+					variant: inner_variant,
+				} = self.meta(*inner_id)
+				else {
+					return None;
+				};
+				if (inner_block_bits, inner_variant) != (block_bits, variant) {
+					return None;
+				}
+
+				let block_size = 1 << *block_bits;
+				let offset = match variant {
+					ShiftVariant::CircularLeft => (offset + inner_offset) % block_size,
+					ShiftVariant::LogicalLeft | ShiftVariant::LogicalRight => offset + inner_offset,
+				};
+				(offset != 0 && offset < block_size).then(|| Shifted {
+					inner_id: *inner_inner_id,
+					offset,
+					block_bits: *block_bits,
+					variant: *variant,
+				})
+			}
+			LinearCombination {
+				n_vars,
+				offset,
+				inner,
+			} => {
+				let mut new_offset = *offset;
+				let mut new_inner = Vec::<(OracleId, F)>::new();
+				let mut positions = HashMap::new();
+				let mut add_term = |term_id: OracleId, coeff: F| {
+					let position = *positions.entry(term_id).or_insert_with(|| {
+						new_inner.push((term_id, F::ZERO));
+						new_inner.len() - 1
+					});
+					new_inner[position].1 += coeff;
+				};
+				for &(inner_id, coeff) in inner {
+					match self.meta(inner_id) {
+						LinearCombination {
+							offset: inner_offset,
+							inner: inner_inner,
+							..
+						} => {
+							new_offset += coeff * *inner_offset;
+							for &(inner_inner_id, inner_coeff) in inner_inner {
+								add_term(inner_inner_id, coeff * inner_coeff);
+							}
+						}
+						_ => add_term(inner_id, coeff),
+					}
+				}
+				new_inner.retain(|&(_, coeff)| coeff != F::ZERO);
+
+				(new_offset != *offset || new_inner != *inner).then(|| LinearCombination {
+					n_vars: *n_vars,
+					offset: new_offset,
+					inner: new_inner,
+				})
+			}
+			Projected {
+				inner_id,
+				values,
+				variant,
+			} => {
+				let Projected {
+					inner_id: inner_inner_id,
+					values: inner_values,
+					variant: inner_variant,
+				} = self.meta(*inner_id)
+				else {
+					return None;
+				};
+				if inner_variant != variant {
+					return None;
+				}
+
+				// The values of the outer projection fix the variables next to those fixed by the
+				// inner projection, on the side away from it.
+				let values = match variant {
+					ProjectionVariant::FirstVars => {
+						[inner_values.as_slice(), values.as_slice()].concat()
+					}
+					ProjectionVariant::LastVars => {
+						[values.as_slice(), inner_values.as_slice()].concat()
+					}
+				};
+				Some(Projected {
+					inner_id: *inner_inner_id,
+					values,
+					variant: *variant,
+				})
+			}
+			Packed {
+				inner_id,
+				log_degree,
+			} => {
+				let Packed {
+					inner_id: inner_inner_id,
+					log_degree: inner_log_degree,
+				} = self.meta(*inner_id)
+				else {
+					return None;
+				};
+
+				// The tower basis of an extension is the tensor product of the bases of the
+				// intermediate extensions, so packing twice is packing once by the total degree.
+				Some(Packed {
+					inner_id: *inner_inner_id,
+					log_degree: inner_log_degree + log_degree,
+				})
+			}
+			_ => None,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::oracle::MultilinearPolyOracle;
+	use binius_field::{BinaryField128b, BinaryField1b, BinaryField8b, Field};
+
+	type F = BinaryField128b;
+
+	#[test]
+	fn test_simplify_collapses_chains() {
+		let n_vars = 6;
+		let mut oracles = MultilinearOracleSet::<F>::new();
+		let batch_id = oracles.add_committed_batch(n_vars, BinaryField1b::TOWER_LEVEL);
+		let [a, b] = oracles.add_committed_multiple(batch_id);
+
+		let shifted_1 = oracles
+			.add_shifted(a, 5, 3, ShiftVariant::CircularLeft)
+			.unwrap();
+		let shifted_2 = oracles
+			.add_shifted(shifted_1, 2, 3, ShiftVariant::CircularLeft)
+			.unwrap();
+		let shifted_3 = oracles
+			.add_shifted(shifted_2, 3, 3, ShiftVariant::CircularLeft)
+			.unwrap();
+
+		let c = F::from(BinaryField8b::new(3));
+		let lin_com_1 = oracles
+			.add_linear_combination_with_offset(n_vars, F::ONE, [(a, F::ONE), (b, c)])
+			.unwrap();
+		let lin_com_2 = oracles
+			.add_linear_combination(n_vars, [(lin_com_1, c), (b, F::ONE), (a, c)])
+			.unwrap();
+
+		let projected_1 = oracles
+			.add_projected(a, vec![F::ONE], ProjectionVariant::LastVars)
+			.unwrap();
+		let projected_2 = oracles
+			.add_projected(projected_1, vec![c, F::ZERO], ProjectionVariant::LastVars)
+			.unwrap();
+
+		let packed_1 = oracles.add_packed(a, 1).unwrap();
+		let packed_2 = oracles.add_packed(packed_1, 2).unwrap();
+
+		assert_eq!(
+			oracles.simplify(),
+			vec![shifted_2, shifted_3, lin_com_2, projected_2, packed_2]
+		);
+
+		// The offsets 5 + 2 + 3 wrap around the block of size 8 to 2.
+		let MultilinearPolyOracle::Shifted(_, shifted) = oracles.oracle(shifted_3) else {
+			panic!("expected a shifted oracle");
+		};
+		assert_eq!(shifted.inner().id(), a);
+		assert_eq!(shifted.shift_offset(), 2);
+
+		// c (1 + a + c b) + b + c a = c + (1 + c^2) b, with the terms in a cancelling out.
+		let MultilinearPolyOracle::LinearCombination(_, lin_com) = oracles.oracle(lin_com_2) else {
+			panic!("expected a linear combination oracle");
+		};
+		assert_eq!(lin_com.offset(), c);
+		assert_eq!(lin_com.polys().map(|poly| poly.id()).collect::<Vec<_>>(), vec![b]);
+		assert_eq!(lin_com.coefficients().collect::<Vec<_>>(), vec![F::ONE + c * c]);
+
+		let MultilinearPolyOracle::Projected(_, projected) = oracles.oracle(projected_2) else {
+			panic!("expected a projected oracle");
+		};
+		assert_eq!(projected.inner().id(), a);
+		assert_eq!(projected.values(), &vec![c, F::ZERO, F::ONE]);
+		assert_eq!(oracles.n_vars(projected_2), n_vars - 3);
+
+		let MultilinearPolyOracle::Packed(_, packed) = oracles.oracle(packed_2) else {
+			panic!("expected a packed oracle");
+		};
+		assert_eq!(packed.inner().id(), a);
+		assert_eq!(packed.log_degree(), 3);
+		assert_eq!(oracles.n_vars(packed_2), n_vars - 3);
+		assert_eq!(oracles.tower_level(packed_2), 3);
+
+		// A second pass finds nothing left to collapse.
+		assert_eq!(oracles.simplify(), vec![]);
+	}
+
+	#[test]
+	fn test_simplify_keeps_incompatible_chains() {
+		let n_vars = 4;
+		let mut oracles = MultilinearOracleSet::<F>::new();
+		let batch_id = oracles.add_committed_batch(n_vars, BinaryField1b::TOWER_LEVEL);
+		let [a] = oracles.add_committed_multiple(batch_id);
+
+		// Logical shifts that together shift past the end of the block.
+		let logical_1 = oracles
+			.add_shifted(a, 3, 2, ShiftVariant::LogicalLeft)
+			.unwrap();
+		let logical_2 = oracles
+			.add_shifted(logical_1, 1, 2, ShiftVariant::LogicalLeft)
+			.unwrap();
+		// Circular shifts that together shift by a whole block.
+		let circular_1 = oracles
+			.add_shifted(a, 1, 2, ShiftVariant::CircularLeft)
+			.unwrap();
+		let circular_2 = oracles
+			.add_shifted(circular_1, 3, 2, ShiftVariant::CircularLeft)
+			.unwrap();
+		// Shifts with different variants or block sizes.
+		oracles
+			.add_shifted(logical_1, 1, 2, ShiftVariant::LogicalRight)
+			.unwrap();
+		oracles
+			.add_shifted(circular_1, 1, 3, ShiftVariant::CircularLeft)
+			.unwrap();
+		// Projections of different variants, and a projection of a packed oracle.
+		let projected = oracles
+			.add_projected(a, vec![F::ONE], ProjectionVariant::FirstVars)
+			.unwrap();
+		oracles
+			.add_projected(projected, vec![F::ONE], ProjectionVariant::LastVars)
+			.unwrap();
+		let packed = oracles.add_packed(a, 1).unwrap();
+		oracles
+			.add_projected(packed, vec![F::ONE], ProjectionVariant::LastVars)
+			.unwrap();
+		// A linear combination with nothing to fold.
+		oracles
+			.add_linear_combination(n_vars, [(a, F::ONE), (circular_2, F::ONE)])
+			.unwrap();
+
+		assert_eq!(oracles.simplify(), vec![]);
+		assert_eq!(oracles.inner_ids(logical_2), vec![logical_1]);
+		assert_eq!(oracles.inner_ids(circular_2), vec![circular_1]);
+	}
+}
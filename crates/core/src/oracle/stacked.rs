@@ -0,0 +1,335 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use crate::{
+	oracle::{CompositePolyOracle, Error, MultilinearOracleSet, OracleId},
+	polynomial::{CompositionPoly, Error as PolynomialError},
+	witness::{Error as WitnessError, MultilinearExtensionIndex},
+};
+use binius_field::{
+	as_packed_field::{PackScalar, PackedType},
+	underlier::UnderlierType,
+	ExtensionField, PackedField, TowerField,
+};
+use binius_utils::bail;
+use getset::{CopyGetters, Getters};
+use std::fmt::Debug;
+
+/// The columns of several instances of the same gadget, stacked along extra variables.
+///
+/// When a circuit instantiates a gadget $G = 2^k$ times over disjoint groups of columns, the $G$
+/// copies of each column are merged into one oracle with $k$ extra high variables, which select the
+/// instance. Instance $g$ occupies the hypercube vertices with index $g \cdot 2^n + x$, so the
+/// stacked witness is the concatenation of the instance witnesses in order. The gadget
+/// constraints are then expressed once over the stacked columns, which replaces $G$ zerocheck
+/// claims with a single one over $n + k$ variables.
+///
+/// The stacked columns are trees of [`MultilinearOracleSet::add_merged`] oracles, so evaluation
+/// claims on them reduce to claims on the instance columns with evalcheck.
+#[derive(Debug, Clone, Getters, CopyGetters)]
+pub struct StackedInstances {
+	/// The number of variables of the stacked columns.
+	#[getset(get_copy = "pub")]
+	n_vars: usize,
+	/// The base-2 logarithm of the number of instances.
+	#[getset(get_copy = "pub")]
+	log_instances: usize,
+	/// The stacked oracle of each gadget column.
+	#[getset(get = "pub")]
+	columns: Vec<OracleId>,
+	/// The merged oracles of each column with their two halves, children before parents.
+	merges: Vec<Vec<(OracleId, OracleId, OracleId)>>,
+}
+
+impl<F: TowerField> MultilinearOracleSet<F> {
+	/// Stacks the columns of gadget instances, given as the list of column oracles of each
+	/// instance.
+	///
+	/// The number of instances must be a power of two, the instances must have the same number of
+	/// columns, and all columns must have the same number of variables.
+	pub fn add_stacked_instances(
+		&mut self,
+		instances: &[Vec<OracleId>],
+	) -> Result<StackedInstances, Error> {
+		let n_instances = instances.len();
+		if !n_instances.is_power_of_two() {
+			bail!(Error::InvalidNumberOfInstances { n_instances });
+		}
+		let n_columns = instances[0].len();
+		if instances.iter().any(|instance| instance.len() != n_columns) {
+			bail!(Error::InstanceColumnsMismatch);
+		}
+		if let Some(&id) = instances.iter().flatten().find(|&&id| id >= self.size()) {
+			bail!(Error::InvalidOracleId(id));
+		}
+
+		// Check the number of variables up front so that no oracles are added on failure.
+		let inner_n_vars = instances[0].first().map_or(0, |&id| self.n_vars(id));
+		if instances
+			.iter()
+			.flatten()
+			.any(|&id| self.n_vars(id) != inner_n_vars)
+		{
+			bail!(Error::NumberOfVariablesMismatch);
+		}
+		let log_instances = n_instances.ilog2() as usize;
+
+		let mut columns = Vec::with_capacity(n_columns);
+		let mut merges = Vec::with_capacity(n_columns);
+		for column in 0..n_columns {
+			let mut column_merges = Vec::with_capacity(n_instances - 1);
+
+			// Merging adjacent pairs at each level makes the lowest extra variable select the
+			// lowest bit of the instance index.
+			let mut level = instances
+				.iter()
+				.map(|instance| instance[column])
+				.collect::<Vec<_>>();
+			while level.len() > 1 {
+				level = level
+					.chunks(2)
+					.map(|pair| {
+						let merged = self.add_merged(pair[0], pair[1])?;
+						column_merges.push((merged, pair[0], pair[1]));
+						Ok(merged)
+					})
+					.collect::<Result<_, Error>>()?;
+			}
+
+			columns.push(level[0]);
+			merges.push(column_merges);
+		}
+
+		Ok(StackedInstances {
+			n_vars: inner_n_vars + log_instances,
+			log_instances,
+			columns,
+			merges,
+		})
+	}
+}
+
+impl StackedInstances {
+	/// Expresses a gadget constraint once over the stacked columns, as a composite of all of them
+	/// in column order.
+	pub fn composite_oracle<F: TowerField>(
+		&self,
+		oracles: &MultilinearOracleSet<F>,
+		composition: impl CompositionPoly<F> + 'static,
+	) -> Result<CompositePolyOracle<F>, Error> {
+		let inner = self.columns.iter().map(|&id| oracles.oracle(id)).collect();
+		CompositePolyOracle::new(self.n_vars, inner, composition)
+	}
+
+	/// Adds the witnesses of all stacked columns to the index, given the witnesses of the instance
+	/// columns.
+	///
+	/// All columns must have scalars in the field `FS`. Gadgets with columns in several fields
+	/// should stack each column with [`Self::stack_column_witness`].
+	pub fn stack_witness<'a, U, FW, FS>(
+		&self,
+		witness: MultilinearExtensionIndex<'a, U, FW>,
+	) -> Result<MultilinearExtensionIndex<'a, U, FW>, WitnessError>
+	where
+		U: UnderlierType + PackScalar<FW> + PackScalar<FS> + Debug,
+		FW: ExtensionField<FS>,
+		FS: TowerField,
+	{
+		(0..self.columns.len()).try_fold(witness, |witness, column| {
+			self.stack_column_witness::<U, FW, FS>(witness, column)
+		})
+	}
+
+	/// Adds the witness of the stacked column at index `column` to the index, given the witnesses
+	/// of the instance columns.
+	///
+	/// The stacked witnesses are concatenations of the instance witnesses, so each instance column
+	/// must fill at least one underlier. The instance witnesses are copied once into the witness
+	/// of the stacked column, and the witness of every intermediate merged oracle is a view of the
+	/// sub-range of that buffer covering its instances.
+	pub fn stack_column_witness<'a, U, FW, FS>(
+		&self,
+		witness: MultilinearExtensionIndex<'a, U, FW>,
+		column: usize,
+	) -> Result<MultilinearExtensionIndex<'a, U, FW>, WitnessError>
+	where
+		U: UnderlierType + PackScalar<FW> + PackScalar<FS> + Debug,
+		FW: ExtensionField<FS>,
+		FS: TowerField,
+	{
+		let merges = &self.merges[column];
+		if merges.is_empty() {
+			return Ok(witness);
+		}
+
+		// The halves of the first-level merges are the instance columns in order.
+		let n_instances = merges.len() + 1;
+		let instance_ids = merges[..n_instances / 2]
+			.iter()
+			.flat_map(|&(_, id0, id1)| [id0, id1]);
+
+		let mut underliers = Vec::new();
+		for id in instance_ids {
+			let (instance, tower_level, n_vars) = witness.backing_underliers(id)?;
+			if tower_level != FS::TOWER_LEVEL {
+				bail!(WitnessError::OracleTowerHeightMismatch {
+					oracle_id: id,
+					oracle_level: tower_level,
+					field_level: FS::TOWER_LEVEL,
+				});
+			}
+			if n_vars < PackedType::<U, FS>::LOG_WIDTH {
+				bail!(PolynomialError::PackedFieldNotFilled {
+					length: 1 << n_vars,
+					packed_width: PackedType::<U, FS>::WIDTH,
+				});
+			}
+			underliers.extend_from_slice(instance);
+		}
+		let instance_len = underliers.len() / n_instances;
+
+		// The merges of each level cover consecutive groups of instances, twice as large as those
+		// of the level below.
+		let mut ranges = Vec::with_capacity(merges.len());
+		let mut remaining = merges.as_slice();
+		let mut group_len = 2 * instance_len;
+		while !remaining.is_empty() {
+			let (level, rest) = remaining.split_at(underliers.len() / group_len);
+			for (i, &(merged, _, _)) in level.iter().enumerate() {
+				ranges.push((merged, i * group_len..(i + 1) * group_len));
+			}
+			remaining = rest;
+			group_len *= 2;
+		}
+
+		witness.update_owned_ranges::<FS>(underliers, ranges)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::polynomial::{MultilinearPoly, MultilinearQuery};
+	use binius_field::{
+		underlier::{Random, WithUnderlier},
+		BinaryField128b, BinaryField32b, Field, PackedBinaryField4x32b,
+	};
+	use rand::{rngs::StdRng, SeedableRng};
+
+	type U = <PackedBinaryField4x32b as WithUnderlier>::Underlier;
+	type F = BinaryField128b;
+	type P = PackedType<U, F>;
+
+	#[test]
+	fn test_stacked_instances() {
+		let mut oracles = MultilinearOracleSet::<F>::new();
+		let batch_id = oracles.add_committed_batch(4, BinaryField32b::TOWER_LEVEL);
+		let instances = (0..4)
+			.map(|_| oracles.add_committed_multiple::<2>(batch_id).to_vec())
+			.collect::<Vec<_>>();
+		let stacked = oracles.add_stacked_instances(&instances).unwrap();
+		assert_eq!(stacked.n_vars(), 6);
+		assert_eq!(stacked.log_instances(), 2);
+		assert_eq!(stacked.columns().len(), 2);
+
+		let mut rng = StdRng::seed_from_u64(0);
+		let instance_witnesses = instances
+			.iter()
+			.flatten()
+			.map(|&id| (id, (0..4).map(|_| U::random(&mut rng)).collect::<Vec<_>>()))
+			.collect::<Vec<_>>();
+		let witness = MultilinearExtensionIndex::<U, F>::new()
+			.update_owned::<BinaryField32b, _>(instance_witnesses)
+			.unwrap();
+		let witness = stacked
+			.stack_witness::<U, F, BinaryField32b>(witness)
+			.unwrap();
+
+		for (column, &stacked_id) in stacked.columns().iter().enumerate() {
+			let stacked_poly = witness.get_multilin_poly(stacked_id).unwrap();
+			assert_eq!(stacked_poly.n_vars(), 6);
+			for (g, instance) in instances.iter().enumerate() {
+				let instance_poly = witness.get_multilin_poly(instance[column]).unwrap();
+				for x in 0..1 << 4 {
+					assert_eq!(
+						stacked_poly.evaluate_on_hypercube((g << 4) | x).unwrap(),
+						instance_poly.evaluate_on_hypercube(x).unwrap()
+					);
+				}
+			}
+
+			// The stacked oracle evaluates to the instance column selected by the extra variables.
+			let mut point = vec![F::new(7), F::new(11), F::new(13), F::new(17)];
+			point.extend([F::ZERO, F::ONE]);
+			let query = MultilinearQuery::<P>::with_full_query(&point).unwrap();
+			let instance_poly = witness.get_multilin_poly(instances[2][column]).unwrap();
+			let instance_query = MultilinearQuery::<P>::with_full_query(&point[..4]).unwrap();
+			assert_eq!(
+				stacked_poly.evaluate(&query).unwrap(),
+				instance_poly.evaluate(&instance_query).unwrap()
+			);
+		}
+	}
+
+	#[test]
+	fn test_stacked_merges_share_column_buffer() {
+		let mut oracles = MultilinearOracleSet::<F>::new();
+		let batch_id = oracles.add_committed_batch(2, BinaryField32b::TOWER_LEVEL);
+		let instances = (0..8)
+			.map(|_| vec![oracles.add_committed(batch_id)])
+			.collect::<Vec<_>>();
+		let stacked = oracles.add_stacked_instances(&instances).unwrap();
+
+		let mut rng = StdRng::seed_from_u64(0);
+		let instance_witnesses = instances
+			.iter()
+			.flatten()
+			.map(|&id| (id, vec![U::random(&mut rng)]))
+			.collect::<Vec<_>>();
+		let witness = MultilinearExtensionIndex::<U, F>::new()
+			.update_owned::<BinaryField32b, _>(instance_witnesses)
+			.unwrap();
+		let witness = stacked
+			.stack_witness::<U, F, BinaryField32b>(witness)
+			.unwrap();
+
+		let (column, _, _) = witness.backing_underliers(stacked.columns()[0]).unwrap();
+		let column_range = column.as_ptr_range();
+		for &(merged, id0, id1) in &stacked.merges[0] {
+			// Every merged oracle is a view of the stacked column, not a copy.
+			let (underliers, _, n_vars) = witness.backing_underliers(merged).unwrap();
+			assert!(column_range.contains(&underliers.as_ptr()));
+
+			let merged_poly = witness.get_multilin_poly(merged).unwrap();
+			for (half, id) in [id0, id1].into_iter().enumerate() {
+				let half_poly = witness.get_multilin_poly(id).unwrap();
+				for x in 0..1 << (n_vars - 1) {
+					assert_eq!(
+						merged_poly
+							.evaluate_on_hypercube((half << (n_vars - 1)) | x)
+							.unwrap(),
+						half_poly.evaluate_on_hypercube(x).unwrap()
+					);
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn test_stacked_instances_invalid() {
+		let mut oracles = MultilinearOracleSet::<F>::new();
+		let batch_id = oracles.add_committed_batch(4, BinaryField32b::TOWER_LEVEL);
+		let instances = (0..3)
+			.map(|_| vec![oracles.add_committed(batch_id)])
+			.collect::<Vec<_>>();
+		assert!(matches!(
+			oracles.add_stacked_instances(&instances),
+			Err(Error::InvalidNumberOfInstances { n_instances: 3 })
+		));
+
+		let ragged = vec![instances[0].clone(), vec![]];
+		assert!(matches!(
+			oracles.add_stacked_instances(&ragged),
+			Err(Error::InstanceColumnsMismatch)
+		));
+	}
+}
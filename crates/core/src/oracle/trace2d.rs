@@ -0,0 +1,326 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use crate::{
+	oracle::{
+		CompositePolyOracle, Error, MultilinearOracleSet, OracleId, ProjectionVariant, ShiftVariant,
+	},
+	polynomial::CompositionPoly,
+};
+use binius_field::{Field, TowerField};
+use binius_utils::bail;
+use getset::CopyGetters;
+
+/// A table of $2^r$ rows by $2^c$ registers held in a single multilinear oracle.
+///
+/// The low $c$ variables of the oracle select the register and the high $r$ variables select the
+/// row, so the value of register $j$ in row $i$ is at the hypercube vertex $i \cdot 2^c + j$ and the
+/// witness is the table in row-major order. The trace derives the oracles of the usual access
+/// patterns from the variable split, so that circuits do not have to track which variables are
+/// which:
+///
+/// * a register column, as a function of the row, is a projection of the register variables
+/// * a row, as a function of the register, is a projection of the row variables
+/// * a register column in the next rows is a shift of the register column within the rows
+/// * the whole table in the next rows is a shift by whole rows within the table
+///
+/// Constraints between registers of the same row are then composites of register columns, see
+/// [`Self::composite_oracle`].
+///
+/// Either dimension may have a single entry. With a single register, its column is the table
+/// oracle itself, and likewise for the row of a table with a single row. A single entry of the
+/// table would have no variables left, so a table with a single row has no register columns and a
+/// table with a single register has no rows.
+#[derive(Debug, Clone, Copy, CopyGetters)]
+#[getset(get_copy = "pub")]
+pub struct Trace2D {
+	/// The oracle holding the table.
+	oracle_id: OracleId,
+	/// The base-2 logarithm of the number of rows.
+	log_rows: usize,
+	/// The base-2 logarithm of the number of registers.
+	log_registers: usize,
+}
+
+impl<F: TowerField> MultilinearOracleSet<F> {
+	/// Views the oracle `oracle_id` as a table of `2^log_rows` rows by `2^log_registers`
+	/// registers.
+	///
+	/// The oracle must have `log_rows + log_registers` variables.
+	pub fn trace_2d(
+		&self,
+		oracle_id: OracleId,
+		log_rows: usize,
+		log_registers: usize,
+	) -> Result<Trace2D, Error> {
+		if oracle_id >= self.size() {
+			bail!(Error::InvalidOracleId(oracle_id));
+		}
+		if self.n_vars(oracle_id) != log_rows + log_registers {
+			bail!(Error::IncorrectNumberOfVariables {
+				expected: log_rows + log_registers,
+			});
+		}
+		Ok(Trace2D {
+			oracle_id,
+			log_rows,
+			log_registers,
+		})
+	}
+}
+
+impl Trace2D {
+	/// The number of variables of the table oracle.
+	pub fn n_vars(&self) -> usize {
+		self.log_rows + self.log_registers
+	}
+
+	/// Adds the column of the register `register`, a multilinear over the row variables.
+	///
+	/// With a single register, the column is the table oracle and nothing is added.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::InvalidTraceIndex`] if `register` is out of range
+	/// * [`Error::EmptyTraceProjection`] if the table has a single row
+	pub fn add_register<F: TowerField>(
+		&self,
+		oracles: &mut MultilinearOracleSet<F>,
+		register: usize,
+	) -> Result<OracleId, Error> {
+		if register >= 1 << self.log_registers {
+			bail!(Error::InvalidTraceIndex {
+				index: register,
+				log_size: self.log_registers,
+			});
+		}
+		if self.log_rows == 0 {
+			bail!(Error::EmptyTraceProjection);
+		}
+		if self.log_registers == 0 {
+			return Ok(self.oracle_id);
+		}
+		oracles.add_projected(
+			self.oracle_id,
+			index_bits(register, self.log_registers),
+			ProjectionVariant::FirstVars,
+		)
+	}
+
+	/// Adds the column of the register `register`, shifted by `offset` rows.
+	///
+	/// The shift is within the rows of the table, so with [`ShiftVariant::CircularLeft`] row $i$
+	/// of the shifted column is the register in row $i + \mathit{offset}$ modulo the number of
+	/// rows.
+	pub fn add_register_shifted<F: TowerField>(
+		&self,
+		oracles: &mut MultilinearOracleSet<F>,
+		register: usize,
+		offset: usize,
+		variant: ShiftVariant,
+	) -> Result<OracleId, Error> {
+		let column = self.add_register(oracles, register)?;
+		oracles.add_shifted(column, offset, self.log_rows, variant)
+	}
+
+	/// Adds the row `row`, a multilinear over the register variables.
+	///
+	/// With a single row, the row is the table oracle and nothing is added.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::InvalidTraceIndex`] if `row` is out of range
+	/// * [`Error::EmptyTraceProjection`] if the table has a single register
+	pub fn add_row<F: TowerField>(
+		&self,
+		oracles: &mut MultilinearOracleSet<F>,
+		row: usize,
+	) -> Result<OracleId, Error> {
+		if row >= 1 << self.log_rows {
+			bail!(Error::InvalidTraceIndex {
+				index: row,
+				log_size: self.log_rows,
+			});
+		}
+		if self.log_registers == 0 {
+			bail!(Error::EmptyTraceProjection);
+		}
+		if self.log_rows == 0 {
+			return Ok(self.oracle_id);
+		}
+		oracles.add_projected(
+			self.oracle_id,
+			index_bits(row, self.log_rows),
+			ProjectionVariant::LastVars,
+		)
+	}
+
+	/// Adds the whole table shifted by `offset` rows, a table of the same shape.
+	///
+	/// The registers stay in place, so a constraint between the table and the shifted table
+	/// relates each register to the registers `offset` rows away.
+	pub fn add_rows_shifted<F: TowerField>(
+		&self,
+		oracles: &mut MultilinearOracleSet<F>,
+		offset: usize,
+		variant: ShiftVariant,
+	) -> Result<Trace2D, Error> {
+		let oracle_id = oracles.add_shifted(
+			self.oracle_id,
+			offset << self.log_registers,
+			self.n_vars(),
+			variant,
+		)?;
+		Ok(Trace2D { oracle_id, ..*self })
+	}
+
+	/// Expresses a constraint between registers of the same row, as a composite of the given
+	/// register columns over the row variables.
+	///
+	/// The columns are oracles returned by [`Self::add_register`] or
+	/// [`Self::add_register_shifted`], in the order of the composition variables.
+	pub fn composite_oracle<F: TowerField>(
+		&self,
+		oracles: &MultilinearOracleSet<F>,
+		columns: &[OracleId],
+		composition: impl CompositionPoly<F> + 'static,
+	) -> Result<CompositePolyOracle<F>, Error> {
+		let inner = columns.iter().map(|&id| oracles.oracle(id)).collect();
+		CompositePolyOracle::new(self.log_rows, inner, composition)
+	}
+}
+
+/// The bits of `index`, least significant first, as field elements.
+fn index_bits<F: Field>(index: usize, n_bits: usize) -> Vec<F> {
+	(0..n_bits)
+		.map(|bit| {
+			if (index >> bit) & 1 == 1 {
+				F::ONE
+			} else {
+				F::ZERO
+			}
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{oracle::MultilinearPolyOracle, protocols::test_utils::TestProductComposition};
+	use binius_field::{BinaryField128b, BinaryField32b};
+
+	type F = BinaryField128b;
+
+	#[test]
+	fn test_trace_2d_access_patterns() {
+		let mut oracles = MultilinearOracleSet::<F>::new();
+		let batch_id = oracles.add_committed_batch(5, BinaryField32b::TOWER_LEVEL);
+		let table = oracles.add_committed(batch_id);
+		let trace = oracles.trace_2d(table, 3, 2).unwrap();
+
+		// Register 2 fixes the register variables to its bits, least significant first.
+		let register = trace.add_register(&mut oracles, 2).unwrap();
+		let MultilinearPolyOracle::Projected(_, projected) = oracles.oracle(register) else {
+			panic!("expected a projected oracle");
+		};
+		assert_eq!(projected.values(), &vec![F::ZERO, F::ONE]);
+		assert_eq!(projected.projection_variant(), ProjectionVariant::FirstVars);
+		assert_eq!(oracles.n_vars(register), 3);
+
+		let row = trace.add_row(&mut oracles, 5).unwrap();
+		let MultilinearPolyOracle::Projected(_, projected) = oracles.oracle(row) else {
+			panic!("expected a projected oracle");
+		};
+		assert_eq!(projected.values(), &vec![F::ONE, F::ZERO, F::ONE]);
+		assert_eq!(projected.projection_variant(), ProjectionVariant::LastVars);
+		assert_eq!(oracles.n_vars(row), 2);
+
+		let next_register = trace
+			.add_register_shifted(&mut oracles, 2, 1, ShiftVariant::LogicalRight)
+			.unwrap();
+		let MultilinearPolyOracle::Shifted(_, shifted) = oracles.oracle(next_register) else {
+			panic!("expected a shifted oracle");
+		};
+		assert_eq!(shifted.shift_offset(), 1);
+		assert_eq!(shifted.block_size(), 3);
+
+		// Shifting the table by a row shifts by the number of registers within the whole table.
+		let next_rows = trace
+			.add_rows_shifted(&mut oracles, 1, ShiftVariant::CircularLeft)
+			.unwrap();
+		assert_eq!(next_rows.log_rows(), 3);
+		let MultilinearPolyOracle::Shifted(_, shifted) = oracles.oracle(next_rows.oracle_id())
+		else {
+			panic!("expected a shifted oracle");
+		};
+		assert_eq!(shifted.shift_offset(), 4);
+		assert_eq!(shifted.block_size(), 5);
+
+		let constraint = trace
+			.composite_oracle(&oracles, &[register, next_register], TestProductComposition::new(2))
+			.unwrap();
+		assert_eq!(constraint.n_vars(), 3);
+	}
+
+	#[test]
+	fn test_trace_2d_invalid() {
+		let mut oracles = MultilinearOracleSet::<F>::new();
+		let batch_id = oracles.add_committed_batch(5, BinaryField32b::TOWER_LEVEL);
+		let table = oracles.add_committed(batch_id);
+		assert!(matches!(
+			oracles.trace_2d(table, 2, 2),
+			Err(Error::IncorrectNumberOfVariables { expected: 4 })
+		));
+
+		let trace = oracles.trace_2d(table, 3, 2).unwrap();
+		assert!(matches!(
+			trace.add_register(&mut oracles, 4),
+			Err(Error::InvalidTraceIndex {
+				index: 4,
+				log_size: 2
+			})
+		));
+		assert!(matches!(
+			trace.add_row(&mut oracles, 8),
+			Err(Error::InvalidTraceIndex {
+				index: 8,
+				log_size: 3
+			})
+		));
+	}
+
+	#[test]
+	fn test_trace_2d_single_row_or_register() {
+		let mut oracles = MultilinearOracleSet::<F>::new();
+		let batch_id = oracles.add_committed_batch(3, BinaryField32b::TOWER_LEVEL);
+		let table = oracles.add_committed(batch_id);
+
+		// With a single register, its column is the whole table.
+		let column = oracles.trace_2d(table, 3, 0).unwrap();
+		let n_oracles = oracles.size();
+		assert_eq!(column.add_register(&mut oracles, 0).unwrap(), table);
+		assert_eq!(oracles.size(), n_oracles);
+		let next_register = column
+			.add_register_shifted(&mut oracles, 0, 1, ShiftVariant::LogicalRight)
+			.unwrap();
+		assert_eq!(oracles.n_vars(next_register), 3);
+		let next_rows = column
+			.add_rows_shifted(&mut oracles, 1, ShiftVariant::LogicalRight)
+			.unwrap();
+		assert_eq!(oracles.n_vars(next_rows.oracle_id()), 3);
+		assert!(matches!(column.add_row(&mut oracles, 2), Err(Error::EmptyTraceProjection)));
+		let constraint = column
+			.composite_oracle(&oracles, &[table, next_register], TestProductComposition::new(2))
+			.unwrap();
+		assert_eq!(constraint.n_vars(), 3);
+
+		// With a single row, the row is the whole table.
+		let row = oracles.trace_2d(table, 0, 3).unwrap();
+		let n_oracles = oracles.size();
+		assert_eq!(row.add_row(&mut oracles, 0).unwrap(), table);
+		assert_eq!(oracles.size(), n_oracles);
+		assert!(matches!(row.add_register(&mut oracles, 5), Err(Error::EmptyTraceProjection)));
+		assert!(row
+			.add_rows_shifted(&mut oracles, 1, ShiftVariant::CircularLeft)
+			.is_err());
+	}
+}
@@ -0,0 +1,568 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Parameters shared by the proving and verification entry points.
+//!
+//! [`ProveParams`] and [`VerifyParams`] consolidate the knobs that are otherwise passed around as
+//! positional arguments, most of them plain integers. They are constructed through builders that
+//! start from sane defaults and validate the values on [`ProveParamsBuilder::build`] and
+//! [`VerifyParamsBuilder::build`].
+
+use crate::{
+	oracle::{self, CompositePolyOracle, MultilinearOracleSet, OracleAudit, OracleId},
+	protocols::{abstract_sumcheck::SwitchoverPolicy, sumcheck_v2::ProtocolFeatures},
+};
+use binius_field::TowerField;
+use binius_utils::bail;
+use getset::CopyGetters;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use std::sync::Arc;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	#[error("security_bits ({security_bits}) must be in range 1..={max}")]
+	InvalidSecurityBits { security_bits: usize, max: usize },
+	#[error("log_inv_rate must be at least 1")]
+	InvalidLogInvRate,
+	#[error("n_threads must be at least 1")]
+	InvalidThreadCount,
+	#[error("max_batch_claims must be at least 1")]
+	InvalidMaxBatchClaims,
+	#[error("thread pool error: {0}")]
+	ThreadPool(#[from] rayon::ThreadPoolBuildError),
+}
+
+/// Parameters of the polynomial commitment scheme.
+///
+/// The prover and the verifier must use equal parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, CopyGetters)]
+#[getset(get_copy = "pub")]
+pub struct PcsParams {
+	/// The target security level in bits.
+	security_bits: usize,
+	/// The base-2 logarithm of the inverse Reed–Solomon code rate.
+	log_inv_rate: usize,
+	/// Whether to use the conservative bound on the number of test queries, which does not rely
+	/// on the proximity gap conjectures for Reed–Solomon codes.
+	conservative_testing: bool,
+}
+
+impl PcsParams {
+	/// The largest supported security level, bounded by the size of the 128-bit challenge field.
+	pub const MAX_SECURITY_BITS: usize = 128;
+
+	fn validate(&self) -> Result<(), Error> {
+		if self.security_bits == 0 || self.security_bits > Self::MAX_SECURITY_BITS {
+			bail!(Error::InvalidSecurityBits {
+				security_bits: self.security_bits,
+				max: Self::MAX_SECURITY_BITS,
+			});
+		}
+		if self.log_inv_rate == 0 {
+			bail!(Error::InvalidLogInvRate);
+		}
+		Ok(())
+	}
+}
+
+impl Default for PcsParams {
+	fn default() -> Self {
+		Self {
+			security_bits: 100,
+			log_inv_rate: 1,
+			conservative_testing: false,
+		}
+	}
+}
+
+/// The thread pool the prover runs its parallel work on.
+#[derive(Debug, Clone, Default)]
+pub enum ProverThreadPool {
+	/// The global rayon thread pool.
+	#[default]
+	Global,
+	/// A dedicated thread pool, supplied by the caller or built once by
+	/// [`ProveParamsBuilder::build`] for the number of threads set with
+	/// [`ProveParamsBuilder::n_threads`].
+	///
+	/// This confines proving to the threads of the pool, for example to a subset of cores in a
+	/// multi-tenant service. Pools built with a custom spawn function, see
+	/// [`rayon::ThreadPoolBuilder::spawn_handler`], can pin their threads or run them on an
+	/// executor of the caller.
+	Pool(Arc<ThreadPool>),
+}
+
+impl PartialEq for ProverThreadPool {
+	fn eq(&self, other: &Self) -> bool {
+		match (self, other) {
+			(Self::Global, Self::Global) => true,
+			(Self::Pool(lhs), Self::Pool(rhs)) => Arc::ptr_eq(lhs, rhs),
+			_ => false,
+		}
+	}
+}
+
+impl Eq for ProverThreadPool {}
+
+/// Parameters for the prover.
+#[derive(Debug, Clone, PartialEq, Eq, CopyGetters)]
+pub struct ProveParams {
+	#[getset(get_copy = "pub")]
+	pcs: PcsParams,
+	thread_pool: ProverThreadPool,
+	/// The switchover round of the sumcheck provers.
+	#[getset(get_copy = "pub")]
+	switchover: SwitchoverPolicy,
+	/// The maximum number of claims proven in one batched sumcheck, or `None` for no limit.
+	///
+	/// Larger statements are split into several batches with
+	/// [`crate::protocols::sumcheck_v2::prove::batch_prove_split`]. The verifier derives the
+	/// batches from the same limit, see [`VerifyParams::max_batch_claims`].
+	#[getset(get_copy = "pub")]
+	max_batch_claims: Option<usize>,
+	/// Whether proving fails on oracles that the statement leaves unconstrained, see
+	/// [`Self::audit`].
+	#[getset(get_copy = "pub")]
+	strict_audit: bool,
+}
+
+impl ProveParams {
+	pub fn builder() -> ProveParamsBuilder {
+		ProveParamsBuilder::default()
+	}
+
+	/// Returns the parameters the verifier must use to check proofs produced with these.
+	pub fn verify_params(&self) -> VerifyParams {
+		VerifyParams {
+			pcs: self.pcs,
+			max_batch_claims: self.max_batch_claims,
+		}
+	}
+
+	/// Returns the parameters to bind into the transcript, see [`VerifyParams::transcript_params`].
+	pub fn transcript_params(
+		&self,
+		n_test_queries: usize,
+		features: ProtocolFeatures,
+	) -> TranscriptParams {
+		self.verify_params()
+			.transcript_params(n_test_queries, features)
+	}
+
+	/// The switchover function for the sumcheck provers.
+	pub fn switchover_fn(&self) -> impl Fn(usize) -> usize + Copy {
+		self.switchover.switchover_fn()
+	}
+
+	pub fn thread_pool(&self) -> &ProverThreadPool {
+		&self.thread_pool
+	}
+
+	/// The number of threads to prove with, or `None` to use the global thread pool.
+	pub fn n_threads(&self) -> Option<usize> {
+		match &self.thread_pool {
+			ProverThreadPool::Global => None,
+			ProverThreadPool::Pool(pool) => Some(pool.current_num_threads()),
+		}
+	}
+
+	/// Audits the oracles of a statement before proving it, see [`MultilinearOracleSet::audit`].
+	///
+	/// Prover entry points call this with the constraints and the claims of the statement. In
+	/// strict mode, unconstrained committed oracles and unused virtual oracles are an error.
+	/// Otherwise they are logged as warnings and the audit is returned.
+	///
+	/// ## Throws
+	///
+	/// * [`oracle::Error::UnconstrainedOracles`] in strict mode if the audit finds any oracles
+	/// * [`oracle::Error::InvalidOracleId`] if a claim is on an oracle outside of the set
+	pub fn audit<'a, F: TowerField>(
+		&self,
+		oracles: &MultilinearOracleSet<F>,
+		constraints: impl IntoIterator<Item = &'a CompositePolyOracle<F>>,
+		claims: impl IntoIterator<Item = OracleId>,
+	) -> Result<OracleAudit, oracle::Error> {
+		let audit = oracles.audit(constraints, claims)?;
+		if self.strict_audit {
+			audit.check()?;
+		} else if !audit.is_clean() {
+			tracing::warn!(
+				unconstrained_committed = ?audit.unconstrained_committed,
+				unused_virtual = ?audit.unused_virtual,
+				"Statement leaves oracles unconstrained"
+			);
+		}
+		Ok(audit)
+	}
+
+	/// Runs `f` on the [`ProverThreadPool`] of the parameters.
+	///
+	/// The pool is built once with the parameters, so repeated calls reuse its threads.
+	pub fn install<R: Send>(&self, f: impl FnOnce() -> R + Send) -> R {
+		match &self.thread_pool {
+			ProverThreadPool::Global => f(),
+			ProverThreadPool::Pool(pool) => pool.install(f),
+		}
+	}
+}
+
+impl Default for ProveParams {
+	fn default() -> Self {
+		Self {
+			pcs: PcsParams::default(),
+			thread_pool: ProverThreadPool::Global,
+			switchover: SwitchoverPolicy::default(),
+			max_batch_claims: None,
+			strict_audit: false,
+		}
+	}
+}
+
+/// Builder for [`ProveParams`].
+#[derive(Debug, Default, Clone)]
+pub struct ProveParamsBuilder {
+	params: ProveParams,
+	n_threads: Option<usize>,
+}
+
+impl ProveParamsBuilder {
+	pub fn security_bits(mut self, security_bits: usize) -> Self {
+		self.params.pcs.security_bits = security_bits;
+		self
+	}
+
+	pub fn log_inv_rate(mut self, log_inv_rate: usize) -> Self {
+		self.params.pcs.log_inv_rate = log_inv_rate;
+		self
+	}
+
+	pub fn conservative_testing(mut self, conservative_testing: bool) -> Self {
+		self.params.pcs.conservative_testing = conservative_testing;
+		self
+	}
+
+	/// Proves on a dedicated thread pool with `n_threads` threads, built by [`Self::build`].
+	pub fn n_threads(mut self, n_threads: usize) -> Self {
+		self.params.thread_pool = ProverThreadPool::Global;
+		self.n_threads = Some(n_threads);
+		self
+	}
+
+	/// Proves on the given thread pool instead of the global one.
+	///
+	/// This replaces any number of threads set with [`Self::n_threads`], and vice versa.
+	pub fn thread_pool(mut self, pool: Arc<ThreadPool>) -> Self {
+		self.params.thread_pool = ProverThreadPool::Pool(pool);
+		self.n_threads = None;
+		self
+	}
+
+	/// Uses the [`SwitchoverPolicy::Standard`] heuristic with the offset `switchover_k`.
+	pub fn switchover_k(mut self, switchover_k: isize) -> Self {
+		self.params.switchover = SwitchoverPolicy::Standard { k: switchover_k };
+		self
+	}
+
+	pub fn switchover(mut self, switchover: SwitchoverPolicy) -> Self {
+		self.params.switchover = switchover;
+		self
+	}
+
+	pub fn max_batch_claims(mut self, max_batch_claims: usize) -> Self {
+		self.params.max_batch_claims = Some(max_batch_claims);
+		self
+	}
+
+	/// Fails proving on unconstrained oracles, see [`ProveParams::audit`].
+	pub fn strict_audit(mut self, strict_audit: bool) -> Self {
+		self.params.strict_audit = strict_audit;
+		self
+	}
+
+	/// Validates and returns the parameters, building the thread pool if a number of threads is
+	/// set.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::InvalidSecurityBits`] if the security level is zero or exceeds
+	///   [`PcsParams::MAX_SECURITY_BITS`]
+	/// * [`Error::InvalidLogInvRate`] if the code rate is 1
+	/// * [`Error::InvalidThreadCount`] if the number of threads is zero
+	/// * [`Error::InvalidMaxBatchClaims`] if the maximum number of claims in a batch is zero
+	/// * [`Error::ThreadPool`] if the thread pool cannot be built
+	pub fn build(mut self) -> Result<ProveParams, Error> {
+		self.params.pcs.validate()?;
+		if self.n_threads == Some(0) {
+			bail!(Error::InvalidThreadCount);
+		}
+		if self.params.max_batch_claims == Some(0) {
+			bail!(Error::InvalidMaxBatchClaims);
+		}
+		if let Some(n_threads) = self.n_threads {
+			let pool = ThreadPoolBuilder::new().num_threads(n_threads).build()?;
+			self.params.thread_pool = ProverThreadPool::Pool(Arc::new(pool));
+		}
+		Ok(self.params)
+	}
+}
+
+/// Parameters for the verifier.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, CopyGetters)]
+#[getset(get_copy = "pub")]
+pub struct VerifyParams {
+	pcs: PcsParams,
+	/// The maximum number of claims proven in one batched sumcheck, which determines how
+	/// [`crate::protocols::sumcheck_v2::batch_verify_split`] groups the claims into batches.
+	max_batch_claims: Option<usize>,
+}
+
+impl VerifyParams {
+	pub fn builder() -> VerifyParamsBuilder {
+		VerifyParamsBuilder::default()
+	}
+
+	/// Returns the parameters to bind into the transcript.
+	///
+	/// The number of test queries is chosen by the polynomial commitment scheme from these
+	/// parameters and the shape of the committed batch, and `features` are the protocol features
+	/// the proof is produced with.
+	pub fn transcript_params(
+		&self,
+		n_test_queries: usize,
+		features: ProtocolFeatures,
+	) -> TranscriptParams {
+		TranscriptParams {
+			pcs: self.pcs,
+			n_test_queries,
+			features,
+		}
+	}
+}
+
+/// Builder for [`VerifyParams`].
+#[derive(Debug, Default, Clone)]
+pub struct VerifyParamsBuilder {
+	params: VerifyParams,
+}
+
+impl VerifyParamsBuilder {
+	pub fn security_bits(mut self, security_bits: usize) -> Self {
+		self.params.pcs.security_bits = security_bits;
+		self
+	}
+
+	pub fn log_inv_rate(mut self, log_inv_rate: usize) -> Self {
+		self.params.pcs.log_inv_rate = log_inv_rate;
+		self
+	}
+
+	pub fn conservative_testing(mut self, conservative_testing: bool) -> Self {
+		self.params.pcs.conservative_testing = conservative_testing;
+		self
+	}
+
+	pub fn max_batch_claims(mut self, max_batch_claims: usize) -> Self {
+		self.params.max_batch_claims = Some(max_batch_claims);
+		self
+	}
+
+	/// Validates and returns the parameters.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::InvalidSecurityBits`] if the security level is zero or exceeds
+	///   [`PcsParams::MAX_SECURITY_BITS`]
+	/// * [`Error::InvalidLogInvRate`] if the code rate is 1
+	/// * [`Error::InvalidMaxBatchClaims`] if the maximum number of claims in a batch is zero
+	pub fn build(self) -> Result<VerifyParams, Error> {
+		self.params.pcs.validate()?;
+		if self.params.max_batch_claims == Some(0) {
+			bail!(Error::InvalidMaxBatchClaims);
+		}
+		Ok(self.params)
+	}
+}
+
+/// The protocol parameters that determine the proof messages, which are bound into the
+/// Fiat-Shamir transcript by [`crate::challenger::Transcript`].
+///
+/// Prover-only settings, such as the number of threads and the sumcheck switchover, do not change
+/// the proof messages and are not included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, CopyGetters)]
+#[getset(get_copy = "pub")]
+pub struct TranscriptParams {
+	pcs: PcsParams,
+	/// The number of columns opened by the polynomial commitment scheme.
+	n_test_queries: usize,
+	/// The optional protocol features used by the prover.
+	features: ProtocolFeatures,
+}
+
+impl TranscriptParams {
+	/// The version of the encoding in [`Self::encode`], which is its first word.
+	pub const ENCODING_VERSION: u64 = 1;
+
+	/// Encodes the parameters as a fixed-length sequence of 64-bit words.
+	///
+	/// Every parameter occupies one word in a fixed order, so distinct parameters have distinct
+	/// encodings.
+	pub fn encode(&self) -> [u64; 6] {
+		[
+			Self::ENCODING_VERSION,
+			self.pcs.security_bits as u64,
+			self.pcs.log_inv_rate as u64,
+			self.pcs.conservative_testing as u64,
+			self.n_test_queries as u64,
+			self.features.bits() as u64,
+		]
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use assert_matches::assert_matches;
+	use binius_field::BinaryField128b;
+
+	#[test]
+	fn test_builder_defaults_are_valid() {
+		let params = ProveParams::builder().build().unwrap();
+		assert_eq!(params, ProveParams::default());
+		assert_eq!(params.verify_params(), VerifyParams::builder().build().unwrap());
+	}
+
+	#[test]
+	fn test_builder_rejects_invalid_params() {
+		assert_matches!(
+			ProveParams::builder().security_bits(0).build(),
+			Err(Error::InvalidSecurityBits { .. })
+		);
+		assert_matches!(
+			VerifyParams::builder().security_bits(256).build(),
+			Err(Error::InvalidSecurityBits { .. })
+		);
+		assert_matches!(
+			ProveParams::builder().log_inv_rate(0).build(),
+			Err(Error::InvalidLogInvRate)
+		);
+		assert_matches!(
+			ProveParams::builder().n_threads(0).build(),
+			Err(Error::InvalidThreadCount)
+		);
+		assert_matches!(
+			ProveParams::builder().max_batch_claims(0).build(),
+			Err(Error::InvalidMaxBatchClaims)
+		);
+		assert_matches!(
+			VerifyParams::builder().max_batch_claims(0).build(),
+			Err(Error::InvalidMaxBatchClaims)
+		);
+	}
+
+	#[test]
+	fn test_transcript_params_encoding_is_injective() {
+		let params = VerifyParams::default();
+		let base = params.transcript_params(100, ProtocolFeatures::empty());
+		assert_eq!(ProveParams::default().transcript_params(100, ProtocolFeatures::empty()), base);
+
+		let others = [
+			params.transcript_params(101, ProtocolFeatures::empty()),
+			params.transcript_params(100, ProtocolFeatures::UNCOMPRESSED_ROUND_PROOFS),
+			VerifyParams::builder()
+				.log_inv_rate(2)
+				.build()
+				.unwrap()
+				.transcript_params(100, ProtocolFeatures::empty()),
+			VerifyParams::builder()
+				.conservative_testing(true)
+				.build()
+				.unwrap()
+				.transcript_params(100, ProtocolFeatures::empty()),
+		];
+		for other in others {
+			assert_ne!(other.encode(), base.encode());
+		}
+	}
+
+	#[test]
+	fn test_strict_audit_rejects_unconstrained_oracles() {
+		let mut oracles = MultilinearOracleSet::<BinaryField128b>::new();
+		let batch_id = oracles.add_committed_batch(4, 0);
+		let [a, b] = oracles.add_committed_multiple(batch_id);
+
+		let audit = ProveParams::default().audit(&oracles, [], [a]).unwrap();
+		assert_eq!(audit.unconstrained_committed, vec![b]);
+
+		let strict = ProveParams::builder().strict_audit(true).build().unwrap();
+		assert_matches!(
+			strict.audit(&oracles, [], [a]),
+			Err(oracle::Error::UnconstrainedOracles { .. })
+		);
+		assert!(strict.audit(&oracles, [], [a, b]).unwrap().is_clean());
+	}
+
+	#[test]
+	fn test_install_uses_requested_threads() {
+		let params = ProveParams::builder().n_threads(2).build().unwrap();
+		assert_eq!(params.n_threads(), Some(2));
+		assert_eq!(params.install(rayon::current_num_threads), 2);
+
+		// The pool is built once and shared by the copies of the parameters.
+		let ProverThreadPool::Pool(pool) = params.thread_pool() else {
+			panic!("expected a dedicated pool");
+		};
+		let clone = params.clone();
+		let ProverThreadPool::Pool(cloned_pool) = clone.thread_pool() else {
+			panic!("expected a dedicated pool");
+		};
+		assert!(Arc::ptr_eq(pool, cloned_pool));
+	}
+
+	#[test]
+	fn test_install_uses_injected_pool() {
+		let pool = Arc::new(
+			rayon::ThreadPoolBuilder::new()
+				.num_threads(3)
+				.thread_name(|index| format!("prover-{index}"))
+				.build()
+				.unwrap(),
+		);
+		let params = ProveParams::builder()
+			.n_threads(2)
+			.thread_pool(pool.clone())
+			.build()
+			.unwrap();
+		assert_eq!(params.n_threads(), Some(3));
+		assert_eq!(params.thread_pool(), &ProverThreadPool::Pool(pool));
+
+		let thread_name = params.install(|| std::thread::current().name().map(str::to_string));
+		assert!(thread_name.unwrap().starts_with("prover-"));
+	}
+
+	#[test]
+	fn test_switchover_policy() {
+		let params = ProveParams::builder().switchover_k(1).build().unwrap();
+		assert_eq!(params.switchover(), SwitchoverPolicy::Standard { k: 1 });
+		assert_eq!(params.switchover_fn()(16), 5);
+
+		let params = ProveParams::builder()
+			.switchover(SwitchoverPolicy::AfterRounds(3))
+			.build()
+			.unwrap();
+		assert_eq!(params.switchover_fn()(16), 3);
+		assert_eq!(params.switchover_fn()(1), 3);
+
+		// A folded multilinear of 2^20 16-byte elements fits in 1 MiB after 4 rounds.
+		assert_eq!(
+			SwitchoverPolicy::with_memory_budget::<BinaryField128b>(20, 1 << 20),
+			SwitchoverPolicy::AfterRounds(4)
+		);
+		assert_eq!(
+			SwitchoverPolicy::with_memory_budget::<BinaryField128b>(20, 1 << 30),
+			SwitchoverPolicy::AfterRounds(1)
+		);
+		assert_eq!(
+			SwitchoverPolicy::with_memory_budget::<BinaryField128b>(3, 0),
+			SwitchoverPolicy::AfterRounds(3)
+		);
+	}
+}
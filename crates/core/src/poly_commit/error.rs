@@ -22,6 +22,11 @@ pub enum Error {
 	NumBatchedMismatchError { err_str: String },
 	#[error("cannot calculate parameters satisfying the security target")]
 	ParameterError,
+	#[error("cannot sample {n_queries} distinct query indices from a domain of size 2^{log_domain_size}")]
+	TooManyDistinctQueries {
+		n_queries: usize,
+		log_domain_size: usize,
+	},
 	#[error("field error: {0}")]
 	Field(#[from] binius_field::Error),
 	#[error("polynomial error: {0}")]
@@ -2,8 +2,10 @@
 
 mod error;
 mod pcs;
+pub mod query_sampling;
 pub mod tensor_pcs;
 
 pub use error::*;
 pub use pcs::*;
+pub use query_sampling::{sample_query_indices, DuplicatePolicy, QueryPolicy};
 pub use tensor_pcs::{BasicTensorPCS, BlockTensorPCS, TensorPCS};
@@ -19,6 +19,12 @@ where
 
 	fn n_vars(&self) -> usize;
 
+	/// The number of test queries opened in an evaluation proof.
+	///
+	/// This determines the soundness of the opening, so it is bound into the Fiat-Shamir
+	/// transcript together with the protocol parameters.
+	fn n_test_queries(&self) -> usize;
+
 	/// Commit to a batch of polynomials
 	fn commit<Data>(
 		&self,
@@ -0,0 +1,130 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Sampling of the codeword positions opened by the polynomial commitment schemes.
+//!
+//! The prover and the verifier of a scheme sample the same query indices from the transcript, so
+//! both must use the same [`QueryPolicy`]. The policy determines how the sampled indices relate to
+//! the number of queries used in the soundness analysis:
+//!
+//! * With [`DuplicatePolicy::Allow`], every query is an independent uniform index, as assumed by
+//!   the error bounds in Section 3.5 of [DP23] and by
+//!   [`super::tensor_pcs::calculate_n_test_queries`]. The number of queries is the number of
+//!   samples, even if some of them repeat, and a repeated index is opened again.
+//! * With [`DuplicatePolicy::Resample`], the indices are distinct, which is sampling without
+//!   replacement. The probability that all queries miss a fixed set of positions is at most the
+//!   probability with replacement, so the bounds for `n_queries` independent queries still hold,
+//!   and the proof never opens a position twice. The number of samples drawn from the transcript
+//!   varies, and `n_queries` must not exceed the number of positions.
+//! * Sorting the indices only reorders the openings, and does not affect soundness.
+//!
+//! [DP23]: <https://eprint.iacr.org/2023/1784>
+
+use super::error::Error;
+use crate::challenger::CanSampleBits;
+use binius_utils::bail;
+use std::collections::HashSet;
+
+/// How repeated query indices are handled.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicatePolicy {
+	/// Keeps repeated indices, so every query is sampled independently.
+	#[default]
+	Allow,
+	/// Samples again whenever an index repeats, so the queries are distinct.
+	Resample,
+}
+
+/// The policy for sampling query indices.
+///
+/// The default policy allows duplicates and keeps the indices in the order they were sampled,
+/// which is the sampling the tensor PCS has always used.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct QueryPolicy {
+	pub duplicates: DuplicatePolicy,
+	/// Whether the indices are returned in ascending order.
+	pub sorted: bool,
+}
+
+/// Samples `n_queries` indices into a domain of size $2^\text{log_domain_size}$.
+///
+/// Every index is sampled with [`CanSampleBits::sample_bits`].
+pub fn sample_query_indices<Challenger>(
+	challenger: &mut Challenger,
+	log_domain_size: usize,
+	n_queries: usize,
+	policy: QueryPolicy,
+) -> Result<Vec<usize>, Error>
+where
+	Challenger: CanSampleBits<usize>,
+{
+	let mut indices = match policy.duplicates {
+		DuplicatePolicy::Allow => (0..n_queries)
+			.map(|_| challenger.sample_bits(log_domain_size))
+			.collect::<Vec<_>>(),
+		DuplicatePolicy::Resample => {
+			if log_domain_size < usize::BITS as usize && n_queries > 1 << log_domain_size {
+				bail!(Error::TooManyDistinctQueries {
+					n_queries,
+					log_domain_size,
+				});
+			}
+			let mut seen = HashSet::with_capacity(n_queries);
+			let mut indices = Vec::with_capacity(n_queries);
+			while indices.len() < n_queries {
+				let index = challenger.sample_bits(log_domain_size);
+				if seen.insert(index) {
+					indices.push(index);
+				}
+			}
+			indices
+		}
+	};
+	if policy.sorted {
+		indices.sort_unstable();
+	}
+	Ok(indices)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::challenger::new_hasher_challenger;
+	use assert_matches::assert_matches;
+	use binius_field::BinaryField8b;
+	use binius_hash::GroestlHasher;
+
+	#[test]
+	fn test_default_policy_matches_sample_bits() {
+		let mut challenger = new_hasher_challenger::<BinaryField8b, GroestlHasher<_>>();
+		let mut other = challenger.clone();
+		let indices =
+			sample_query_indices(&mut challenger, 10, 20, QueryPolicy::default()).unwrap();
+		let expected = (0..20).map(|_| other.sample_bits(10)).collect::<Vec<_>>();
+		assert_eq!(indices, expected);
+	}
+
+	#[test]
+	fn test_resample_duplicates() {
+		let mut challenger = new_hasher_challenger::<BinaryField8b, GroestlHasher<_>>();
+		let policy = QueryPolicy {
+			duplicates: DuplicatePolicy::Resample,
+			sorted: true,
+		};
+		// Sampling every position of a small domain forces resampling.
+		let indices = sample_query_indices(&mut challenger, 3, 8, policy).unwrap();
+		assert_eq!(indices, (0..8).collect::<Vec<_>>());
+
+		let indices = sample_query_indices(&mut challenger, 6, 40, policy).unwrap();
+		assert_eq!(indices.len(), 40);
+		assert!(indices.windows(2).all(|pair| pair[0] < pair[1]));
+		assert!(indices.iter().all(|&index| index < 1 << 6));
+
+		assert_matches!(
+			sample_query_indices(&mut challenger, 3, 9, policy),
+			Err(Error::TooManyDistinctQueries {
+				n_queries: 9,
+				log_domain_size: 3
+			})
+		);
+	}
+}
@@ -5,9 +5,11 @@ use crate::{
 	challenger::{CanObserve, CanSample, CanSampleBits},
 	linear_code::LinearCode,
 	merkle_tree::{MerkleTreeVCS, VectorCommitScheme},
-	poly_commit::PolyCommitScheme,
+	params::PcsParams,
+	poly_commit::{sample_query_indices, PolyCommitScheme, QueryPolicy},
 	polynomial::{
-		multilinear_query::MultilinearQuery, Error as PolynomialError, MultilinearExtension,
+		hypercube_vertex_index, multilinear_query::MultilinearQuery, Error as PolynomialError,
+		MultilinearExtension, PointStructure,
 	},
 	reed_solomon::reed_solomon::ReedSolomonCode,
 };
@@ -28,9 +30,39 @@ use binius_utils::bail;
 use p3_matrix::{dense::RowMajorMatrix, MatrixRowSlices};
 use p3_util::{log2_ceil_usize, log2_strict_usize};
 use rayon::prelude::*;
-use std::{cmp::min, iter::repeat_with, marker::PhantomData, mem, ops::Deref};
+use std::{cmp::min, iter, marker::PhantomData, mem, ops::Deref};
 use tracing::instrument;
 
+/// The tensor expansion of the row coordinates of an opening query, specialized to the structure
+/// of the coordinates.
+enum ColumnExpansion<PF, PE> {
+	HypercubeVertex(usize),
+	Subfield(Vec<PF>),
+	Extension(Vec<PE>),
+}
+
+impl<F, FE, PF, PE> ColumnExpansion<PF, PE>
+where
+	F: Field,
+	FE: ExtensionField<F>,
+	PF: PackedField<Scalar = F>,
+	PE: PackedField<Scalar = FE>,
+{
+	/// Evaluates the multilinear extension of an opened column at the row coordinates.
+	fn evaluate(&self, column: &[F]) -> FE {
+		let column = MultilinearExtension::from_values_slice(column)
+			.expect("leaf is guaranteed power of two length due to check_proof_shape");
+		match self {
+			Self::HypercubeVertex(index) => column.evaluate_on_hypercube(*index).map(FE::from),
+			Self::Subfield(expansion) => column
+				.inner_prod_with_expansion::<F, PF>(expansion)
+				.map(FE::from),
+			Self::Extension(expansion) => column.inner_prod_with_expansion(expansion),
+		}
+		.expect("failed to evaluate")
+	}
+}
+
 /// Creates a new multilinear from a batch of multilinears and a mixing challenge
 ///
 /// REQUIRES:
@@ -223,6 +255,10 @@ where
 		self.log_rows() + self.log_cols()
 	}
 
+	fn n_test_queries(&self) -> usize {
+		self.n_test_queries
+	}
+
 	#[instrument(skip_all, name = "tensor_pcs::commit", level = "debug")]
 	fn commit<Data>(
 		&self,
@@ -231,56 +267,9 @@ where
 	where
 		Data: Deref<Target = [PackedType<U, F>]> + Send + Sync,
 	{
-		for poly in polys {
-			if poly.n_vars() != self.n_vars() {
-				bail!(Error::IncorrectPolynomialSize {
-					expected: self.n_vars(),
-				});
-			}
-		}
-
-		// These conditions are checked by the constructor, so are safe to assert defensively
-		let pi_width = PackedType::<U, FI>::WIDTH;
-		debug_assert_eq!(self.code.dim() % pi_width, 0);
-
-		// Dimensions as an intermediate field matrix.
-		let n_rows = 1 << self.log_rows;
-		let n_cols_enc = self.code.len();
-
 		let results = polys
 			.par_iter()
-			.map(|poly| -> Result<_, Error> {
-				let mut encoded =
-					vec![PackedType::<U, FI>::default(); n_rows * n_cols_enc / pi_width];
-				let poly_vals_packed =
-					<PackedType<U, FI> as PackedExtension<F>>::cast_exts(poly.evals());
-
-				transpose::transpose(
-					PackedType::<U, FI>::unpack_scalars(poly_vals_packed),
-					PackedType::<U, FI>::unpack_scalars_mut(
-						&mut encoded[..n_rows * self.code.dim() / pi_width],
-					),
-					1 << self.code.dim_bits(),
-					1 << self.log_rows,
-				);
-
-				self.code
-					.encode_batch_inplace(
-						<PackedType<U, FI> as PackedExtension<FA>>::cast_bases_mut(&mut encoded),
-						self.log_rows + log2_strict_usize(<FI as ExtensionField<FA>>::DEGREE),
-					)
-					.map_err(|err| Error::EncodeError(Box::new(err)))?;
-
-				let mut digests = vec![H::Digest::default(); n_cols_enc];
-				encoded
-					.par_chunks_exact(n_rows / pi_width)
-					.map(H::hash)
-					.collect_into_vec(&mut digests);
-
-				let encoded_mat = RowMajorMatrix::new(encoded, n_rows / pi_width);
-
-				Ok((digests, encoded_mat))
-			})
+			.map(|poly| self.encode_and_hash(poly))
 			.collect::<Vec<_>>();
 
 		let mut encoded_mats = Vec::with_capacity(polys.len());
@@ -349,8 +338,14 @@ where
 		let t_prime = mix_t_primes(log_n_cols, &t_primes, mixing_coefficients)?;
 
 		challenger.observe_slice(PackedType::<U, FE>::unpack_scalars(t_prime.evals()));
-		let merkle_proofs = repeat_with(|| challenger.sample_bits(code_len_bits))
-			.take(self.n_test_queries)
+		let indices = sample_query_indices(
+			challenger,
+			code_len_bits,
+			self.n_test_queries,
+			QueryPolicy::default(),
+		)?;
+		let merkle_proofs = indices
+			.into_iter()
 			.map(|index| {
 				let vcs_proof = self
 					.vcs
@@ -435,13 +430,17 @@ where
 
 		challenger.observe_slice(<PackedType<U, FE>>::unpack_scalars(proof.mixed_t_prime.evals()));
 
-		// Check evaluation of t' matches the claimed value
-		let multilin_query =
-			MultilinearQuery::<PackedType<U, FE>>::with_full_query(&query[..log_n_cols])?;
-		let computed_value = proof
-			.mixed_t_prime
-			.evaluate(&multilin_query)
-			.expect("query is the correct size by check_proof_shape checks");
+		// Check evaluation of t' matches the claimed value. At a hypercube vertex this is a single
+		// evaluation of t', which avoids the tensor expansion of the query.
+		let computed_value = match hypercube_vertex_index(&query[..log_n_cols]) {
+			Some(index) => proof.mixed_t_prime.evaluate_on_hypercube(index),
+			None => {
+				let multilin_query =
+					MultilinearQuery::<PackedType<U, FE>>::with_full_query(&query[..log_n_cols])?;
+				proof.mixed_t_prime.evaluate(&multilin_query)
+			}
+		}
+		.expect("query is the correct size by check_proof_shape checks");
 		if computed_value != value {
 			bail!(VerificationError::IncorrectEvaluation);
 		}
@@ -454,12 +453,14 @@ where
 		self.encode_ext(proof.mixed_t_prime.evals(), &mut u_prime)?;
 
 		// Check vector commitment openings.
-		let columns = proof
-			.vcs_proofs
-			.into_iter()
-			.map(|(cols, vcs_proof)| {
-				let index = challenger.sample_bits(code_len_bits);
-
+		let indices = sample_query_indices(
+			challenger,
+			code_len_bits,
+			self.n_test_queries,
+			QueryPolicy::default(),
+		)?;
+		let columns = iter::zip(indices, proof.vcs_proofs)
+			.map(|(index, (cols, vcs_proof))| {
 				let leaf_digests = cols.iter().map(H::hash);
 
 				self.vcs
@@ -517,22 +518,25 @@ where
 			})
 			.collect::<Vec<_>>();
 
-		// Batch evaluate all opened columns
-		let multilin_query =
-			MultilinearQuery::<PackedType<U, FE>>::with_full_query(&query[log_n_cols..])?;
+		// Batch evaluate all opened columns. When the row coordinates of the query are boolean or
+		// lie in the base field, the columns are evaluated without extension field multiplications.
+		let row_query = &query[log_n_cols..];
+		let column_expansion = match PointStructure::<F>::detect(row_query) {
+			PointStructure::HypercubeVertex(index) => ColumnExpansion::HypercubeVertex(index),
+			PointStructure::Subfield(row_query) => ColumnExpansion::Subfield(
+				MultilinearQuery::<PackedType<U, F>>::with_full_query(&row_query)?.into_expansion(),
+			),
+			PointStructure::Generic => ColumnExpansion::Extension(
+				MultilinearQuery::<PackedType<U, FE>>::with_full_query(row_query)?.into_expansion(),
+			),
+		};
 		let incorrect_evaluation = column_tests
 			.par_iter()
 			.map(|(expected, leaves)| {
-				let actual_evals =
-					leaves
-						.par_iter()
-						.map(|leaf| {
-							MultilinearExtension::from_values_slice(leaf)
-						.expect("leaf is guaranteed power of two length due to check_proof_shape")
-						.evaluate(&multilin_query)
-						.expect("failed to evaluate")
-						})
-						.collect::<Vec<_>>();
+				let actual_evals = leaves
+					.par_iter()
+					.map(|leaf| column_expansion.evaluate(leaf))
+					.collect::<Vec<_>>();
 				(expected, actual_evals)
 			})
 			.any(|(expected_result, unmixed_actual_results)| {
@@ -560,6 +564,247 @@ where
 	}
 }
 
+impl<U, F, FA, FI, FE, LC, H, VCS> TensorPCS<U, F, FA, FI, FE, LC, H, VCS>
+where
+	U: PackScalar<F>
+		+ PackScalar<FA>
+		+ PackScalar<FI, Packed: PackedFieldIndexable>
+		+ PackScalar<FE, Packed: PackedFieldIndexable>,
+	F: Field,
+	FA: Field,
+	FI: ExtensionField<F> + ExtensionField<FA>,
+	FE: ExtensionField<F> + ExtensionField<FI>,
+	LC: LinearCode<P = PackedType<U, FA>> + Sync,
+	H: HashDigest<PackedType<U, FI>> + Sync,
+	H::Digest: Copy + Default + Send,
+	VCS: VectorCommitScheme<H::Digest> + Sync,
+{
+	/// Starts an incremental commitment to a batch of polynomials.
+	///
+	/// See [`StreamingCommit`] for details.
+	pub fn commit_streaming(&self) -> StreamingCommit<'_, U, F, FA, FI, FE, LC, H, VCS> {
+		StreamingCommit {
+			pcs: self,
+			encoded_mats: Vec::new(),
+			all_digests: Vec::new(),
+		}
+	}
+
+	/// Commits to a batch of polynomials, overlapping the encoding of each polynomial with the
+	/// hashing of the previous one.
+	///
+	/// Encoding runs sequentially on a dedicated thread while the column hashing is parallelized
+	/// over the rayon thread pool. This is intended for linear codes whose
+	/// [`LinearCode::encode_batch_inplace`] offloads work to an accelerator such as a GPU: the
+	/// device encodes the next polynomial while CPU threads hash the current one. The stages hand
+	/// over each encoding through a rendezvous channel, so at most two polynomials are in flight at
+	/// a time: the one being hashed, and the one being encoded or waiting to be handed over.
+	///
+	/// Returns the same commitment and committed data as [`PolyCommitScheme::commit`].
+	#[allow(clippy::type_complexity)]
+	#[instrument(skip_all, name = "tensor_pcs::commit_pipelined", level = "debug")]
+	pub fn commit_pipelined<Data>(
+		&self,
+		polys: &[MultilinearExtension<PackedType<U, F>, Data>],
+	) -> Result<(VCS::Commitment, (Vec<RowMajorMatrix<PackedType<U, FI>>>, VCS::Committed)), Error>
+	where
+		Data: Deref<Target = [PackedType<U, F>]> + Send + Sync,
+	{
+		let mut streaming = self.commit_streaming();
+		std::thread::scope(|scope| -> Result<(), Error> {
+			// A zero-capacity channel blocks the encoder until the hasher takes the encoding, which
+			// bounds the pipeline to two encoded polynomials.
+			let (sender, receiver) = std::sync::mpsc::sync_channel(0);
+			scope.spawn(move || {
+				for poly in polys {
+					let encoded = self.encode(poly);
+					let failed = encoded.is_err();
+					// The receiver only hangs up after an error, in which case encoding stops.
+					if sender.send(encoded).is_err() || failed {
+						break;
+					}
+				}
+			});
+			for encoded in receiver {
+				let (digests, encoded_mat) = self.hash_columns(encoded?);
+				streaming.all_digests.push(digests);
+				streaming.encoded_mats.push(encoded_mat);
+			}
+			Ok(())
+		})?;
+		streaming.finish()
+	}
+
+	/// Encodes a single polynomial and hashes the columns of its encoding.
+	fn encode_and_hash<Data>(
+		&self,
+		poly: &MultilinearExtension<PackedType<U, F>, Data>,
+	) -> Result<(Vec<H::Digest>, RowMajorMatrix<PackedType<U, FI>>), Error>
+	where
+		Data: Deref<Target = [PackedType<U, F>]> + Send + Sync,
+	{
+		let encoded = self.encode(poly)?;
+		Ok(self.hash_columns(encoded))
+	}
+
+	/// Encodes the rows of a single polynomial, returning the encoded matrix in column-major
+	/// order.
+	fn encode<Data>(
+		&self,
+		poly: &MultilinearExtension<PackedType<U, F>, Data>,
+	) -> Result<Vec<PackedType<U, FI>>, Error>
+	where
+		Data: Deref<Target = [PackedType<U, F>]> + Send + Sync,
+	{
+		if poly.n_vars() != self.n_vars() {
+			bail!(Error::IncorrectPolynomialSize {
+				expected: self.n_vars(),
+			});
+		}
+
+		// These conditions are checked by the constructor, so are safe to assert defensively
+		let pi_width = PackedType::<U, FI>::WIDTH;
+		debug_assert_eq!(self.code.dim() % pi_width, 0);
+
+		// Dimensions as an intermediate field matrix.
+		let n_rows = 1 << self.log_rows;
+		let n_cols_enc = self.code.len();
+
+		let mut encoded = vec![PackedType::<U, FI>::default(); n_rows * n_cols_enc / pi_width];
+		let poly_vals_packed = <PackedType<U, FI> as PackedExtension<F>>::cast_exts(poly.evals());
+
+		transpose::transpose(
+			PackedType::<U, FI>::unpack_scalars(poly_vals_packed),
+			PackedType::<U, FI>::unpack_scalars_mut(
+				&mut encoded[..n_rows * self.code.dim() / pi_width],
+			),
+			1 << self.code.dim_bits(),
+			1 << self.log_rows,
+		);
+
+		self.code
+			.encode_batch_inplace(
+				<PackedType<U, FI> as PackedExtension<FA>>::cast_bases_mut(&mut encoded),
+				self.log_rows + log2_strict_usize(<FI as ExtensionField<FA>>::DEGREE),
+			)
+			.map_err(|err| Error::EncodeError(Box::new(err)))?;
+
+		Ok(encoded)
+	}
+
+	/// Hashes the columns of an encoded polynomial.
+	fn hash_columns(
+		&self,
+		encoded: Vec<PackedType<U, FI>>,
+	) -> (Vec<H::Digest>, RowMajorMatrix<PackedType<U, FI>>) {
+		let n_rows = 1 << self.log_rows;
+		let pi_width = PackedType::<U, FI>::WIDTH;
+
+		let mut digests = vec![H::Digest::default(); self.code.len()];
+		encoded
+			.par_chunks_exact(n_rows / pi_width)
+			.map(H::hash)
+			.collect_into_vec(&mut digests);
+
+		let encoded_mat = RowMajorMatrix::new(encoded, n_rows / pi_width);
+		(digests, encoded_mat)
+	}
+}
+
+/// An incremental commitment to a batch of polynomials with a [`TensorPCS`].
+///
+/// Each polynomial is encoded and its columns hashed as soon as it is absorbed, so the caller
+/// may drop the polynomial and reuse its memory afterwards. Absorbing polynomials while the
+/// remaining ones are still being generated hides the commitment latency behind witness
+/// generation. Only the Merkle tree over the column digests is built by [`Self::finish`].
+///
+/// The commitment is identical to the one produced by [`PolyCommitScheme::commit`] on the
+/// absorbed polynomials, in order.
+pub struct StreamingCommit<'a, U, F, FA, FI, FE, LC, H, VCS>
+where
+	U: PackScalar<F> + PackScalar<FA> + PackScalar<FI> + PackScalar<FE>,
+	F: Field,
+	FA: Field,
+	FI: Field,
+	FE: Field,
+	LC: LinearCode<P = PackedType<U, FA>>,
+	H: HashDigest<PackedType<U, FI>>,
+	VCS: VectorCommitScheme<H::Digest>,
+{
+	pcs: &'a TensorPCS<U, F, FA, FI, FE, LC, H, VCS>,
+	encoded_mats: Vec<RowMajorMatrix<PackedType<U, FI>>>,
+	all_digests: Vec<Vec<H::Digest>>,
+}
+
+impl<'a, U, F, FA, FI, FE, LC, H, VCS> StreamingCommit<'a, U, F, FA, FI, FE, LC, H, VCS>
+where
+	U: PackScalar<F>
+		+ PackScalar<FA>
+		+ PackScalar<FI, Packed: PackedFieldIndexable>
+		+ PackScalar<FE, Packed: PackedFieldIndexable>,
+	F: Field,
+	FA: Field,
+	FI: ExtensionField<F> + ExtensionField<FA>,
+	FE: ExtensionField<F> + ExtensionField<FI>,
+	LC: LinearCode<P = PackedType<U, FA>> + Sync,
+	H: HashDigest<PackedType<U, FI>> + Sync,
+	H::Digest: Copy + Default + Send,
+	VCS: VectorCommitScheme<H::Digest> + Sync,
+{
+	/// The number of polynomials absorbed so far.
+	pub fn n_absorbed(&self) -> usize {
+		self.all_digests.len()
+	}
+
+	/// Encodes and hashes the next polynomial in the batch.
+	pub fn absorb<Data>(
+		&mut self,
+		poly: &MultilinearExtension<PackedType<U, F>, Data>,
+	) -> Result<(), Error>
+	where
+		Data: Deref<Target = [PackedType<U, F>]> + Send + Sync,
+	{
+		let (digests, encoded_mat) = self.pcs.encode_and_hash(poly)?;
+		self.all_digests.push(digests);
+		self.encoded_mats.push(encoded_mat);
+		Ok(())
+	}
+
+	/// Absorbs polynomials in order as they are yielded by `polys`.
+	///
+	/// Each polynomial is dropped once it is absorbed. When `polys` is the receiving end of a
+	/// bounded [`std::sync::mpsc::sync_channel`] fed by a witness generation thread, this forms
+	/// a pipeline that holds at most the channel bound of unencoded polynomials in memory.
+	pub fn absorb_all<Data>(
+		&mut self,
+		polys: impl IntoIterator<Item = MultilinearExtension<PackedType<U, F>, Data>>,
+	) -> Result<(), Error>
+	where
+		Data: Deref<Target = [PackedType<U, F>]> + Send + Sync,
+	{
+		for poly in polys {
+			self.absorb(&poly)?;
+		}
+		Ok(())
+	}
+
+	/// Commits to the column digests of all absorbed polynomials.
+	///
+	/// Returns the same commitment and committed data as [`PolyCommitScheme::commit`].
+	#[allow(clippy::type_complexity)]
+	pub fn finish(
+		self,
+	) -> Result<(VCS::Commitment, (Vec<RowMajorMatrix<PackedType<U, FI>>>, VCS::Committed)), Error>
+	{
+		let (commitment, vcs_committed) = self
+			.pcs
+			.vcs
+			.commit_batch(&self.all_digests)
+			.map_err(|err| Error::VectorCommit(Box::new(err)))?;
+		Ok((commitment, (self.encoded_mats, vcs_committed)))
+	}
+}
+
 impl<U, F, FA, FI, FE, LC, H, VCS> TensorPCS<U, F, FA, FI, FE, LC, H, VCS>
 where
 	U: PackScalar<F> + PackScalar<FA> + PackScalar<FI> + PackScalar<FE>,
@@ -969,16 +1214,59 @@ where
 	best_pcs
 }
 
+/// Find the TensorPCS parameterization that optimizes proof size for the given [`PcsParams`].
+///
+/// This is equivalent to [`find_proof_size_optimal_pcs`], taking the security level, code rate and
+/// testing strategy from `params`.
+pub fn find_proof_size_optimal_pcs_with_params<U, F, FA, FI, FE>(
+	params: &PcsParams,
+	n_vars: usize,
+	n_polys: usize,
+) -> Option<
+	TensorPCS<
+		U,
+		F,
+		FA,
+		FI,
+		FE,
+		ReedSolomonCode<PackedType<U, FA>>,
+		HasherDigest<PackedType<U, FI>, GroestlHasher<PackedType<U, FI>>>,
+		GroestlMerkleTreeVCS,
+	>,
+>
+where
+	U: PackScalar<F>
+		+ PackScalar<FA, Packed: PackedFieldIndexable>
+		+ PackScalar<FI, Packed: PackedFieldIndexable>
+		+ PackScalar<FE, Packed: PackedFieldIndexable>
+		+ PackScalar<BinaryField8b>
+		+ Divisible<u8>,
+	F: Field,
+	FA: BinaryField,
+	FI: ExtensionField<F> + ExtensionField<FA> + ExtensionField<BinaryField8b>,
+	FE: BinaryField + ExtensionField<F> + ExtensionField<FA> + ExtensionField<FI>,
+{
+	find_proof_size_optimal_pcs(
+		params.security_bits(),
+		n_vars,
+		n_polys,
+		params.log_inv_rate(),
+		params.conservative_testing(),
+	)
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
 	use crate::challenger::new_hasher_challenger;
+	use assert_matches::assert_matches;
 	use binius_field::{
 		arch::OptimalUnderlier128b, BinaryField128b, BinaryField16b, BinaryField1b, BinaryField32b,
 		PackedBinaryField128x1b, PackedBinaryField16x8b, PackedBinaryField1x128b,
 		PackedBinaryField4x32b,
 	};
 	use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
+	use std::iter::repeat_with;
 
 	#[test]
 	fn test_simple_commit_prove_verify_without_error() {
@@ -1028,6 +1316,77 @@ mod tests {
 			.unwrap();
 	}
 
+	#[test]
+	fn test_commit_prove_verify_at_structured_points() {
+		type Packed = PackedBinaryField16x8b;
+
+		let rs_code = ReedSolomonCode::new(5, 2, NTTOptions::default()).unwrap();
+		let n_test_queries =
+			calculate_n_test_queries_reed_solomon::<_, BinaryField128b, _>(100, 4, &rs_code)
+				.unwrap();
+		let pcs = <BasicTensorPCS<
+			OptimalUnderlier128b,
+			BinaryField8b,
+			BinaryField8b,
+			BinaryField128b,
+			_,
+			_,
+			_,
+		>>::new_using_groestl_merkle_tree(4, rs_code, n_test_queries)
+		.unwrap();
+
+		let mut rng = StdRng::seed_from_u64(0);
+		let evals = repeat_with(|| Packed::random(&mut rng))
+			.take((1 << pcs.n_vars()) / Packed::WIDTH)
+			.collect::<Vec<_>>();
+		let poly = MultilinearExtension::from_values(evals).unwrap();
+		let polys = [poly.to_ref()];
+
+		let (commitment, committed) = pcs.commit(&polys).unwrap();
+
+		let boolean_query = repeat_with(|| BinaryField128b::from(BinaryField1b::random(&mut rng)))
+			.take(pcs.n_vars())
+			.collect::<Vec<_>>();
+		let subfield_query = repeat_with(|| BinaryField128b::from(BinaryField8b::random(&mut rng)))
+			.take(pcs.n_vars())
+			.collect::<Vec<_>>();
+		let mut mixed_query = boolean_query.clone();
+		mixed_query[pcs.n_vars() - 1] = BinaryField128b::random(&mut rng);
+
+		for query in [boolean_query, subfield_query, mixed_query] {
+			let multilin_query =
+				MultilinearQuery::<PackedBinaryField1x128b>::with_full_query(&query).unwrap();
+			let value = poly.evaluate(&multilin_query).unwrap();
+
+			let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+			for (claimed_value, is_correct) in
+				[(value, true), (value + BinaryField128b::ONE, false)]
+			{
+				let mut prove_challenger = challenger.clone();
+				let proof = pcs
+					.prove_evaluation(&mut prove_challenger, &committed, &polys, &query)
+					.unwrap();
+
+				let mut verify_challenger = challenger.clone();
+				let result = pcs.verify_evaluation(
+					&mut verify_challenger,
+					&commitment,
+					&query,
+					proof,
+					&[claimed_value],
+				);
+				if is_correct {
+					result.unwrap();
+				} else {
+					assert_matches!(
+						result,
+						Err(Error::Verification(VerificationError::IncorrectEvaluation))
+					);
+				}
+			}
+		}
+	}
+
 	#[test]
 	fn test_simple_commit_prove_verify_batch_without_error() {
 		type Packed = PackedBinaryField16x8b;
@@ -1082,6 +1441,120 @@ mod tests {
 			.unwrap();
 	}
 
+	#[test]
+	fn test_streaming_commit_matches_batch_commit() {
+		type Packed = PackedBinaryField16x8b;
+
+		let rs_code = ReedSolomonCode::new(5, 2, NTTOptions::default()).unwrap();
+		let n_test_queries =
+			calculate_n_test_queries_reed_solomon::<_, BinaryField128b, _>(100, 4, &rs_code)
+				.unwrap();
+		let pcs = <BasicTensorPCS<
+			OptimalUnderlier128b,
+			BinaryField8b,
+			BinaryField8b,
+			BinaryField128b,
+			_,
+			_,
+			_,
+		>>::new_using_groestl_merkle_tree(4, rs_code, n_test_queries)
+		.unwrap();
+
+		let batch_size = 4;
+		let n_vars = pcs.n_vars();
+		let random_poly = move |rng: &mut StdRng| {
+			let evals = repeat_with(|| Packed::random(&mut *rng))
+				.take((1 << n_vars) / Packed::WIDTH)
+				.collect::<Vec<_>>();
+			MultilinearExtension::from_values(evals).unwrap()
+		};
+		let mut rng = StdRng::seed_from_u64(0);
+		let polys = repeat_with(|| random_poly(&mut rng))
+			.take(batch_size)
+			.collect::<Vec<_>>();
+		let (commitment, _) = pcs.commit(&polys).unwrap();
+
+		// Generate the same polynomials on another thread while absorbing them.
+		let (sender, receiver) = std::sync::mpsc::sync_channel(1);
+		let mut streaming = pcs.commit_streaming();
+		std::thread::scope(|scope| {
+			scope.spawn(move || {
+				let mut rng = StdRng::seed_from_u64(0);
+				for _ in 0..batch_size {
+					sender.send(random_poly(&mut rng)).unwrap();
+				}
+			});
+			streaming.absorb_all(receiver).unwrap();
+		});
+		assert_eq!(streaming.n_absorbed(), batch_size);
+		let (streamed_commitment, streamed_committed) = streaming.finish().unwrap();
+		assert_eq!(streamed_commitment.0, commitment.0);
+
+		let mut challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+		let query = repeat_with(|| challenger.sample())
+			.take(pcs.n_vars())
+			.collect::<Vec<_>>();
+		let multilin_query =
+			MultilinearQuery::<PackedBinaryField1x128b>::with_full_query(&query).unwrap();
+		let values = polys
+			.iter()
+			.map(|poly| poly.evaluate(&multilin_query).unwrap())
+			.collect::<Vec<_>>();
+
+		let mut prove_challenger = challenger.clone();
+		let proof = pcs
+			.prove_evaluation(&mut prove_challenger, &streamed_committed, &polys, &query)
+			.unwrap();
+
+		let mut verify_challenger = challenger.clone();
+		pcs.verify_evaluation(&mut verify_challenger, &commitment, &query, proof, &values)
+			.unwrap();
+	}
+
+	#[test]
+	fn test_pipelined_commit_matches_batch_commit() {
+		type Packed = PackedBinaryField16x8b;
+
+		let rs_code = ReedSolomonCode::new(5, 2, NTTOptions::default()).unwrap();
+		let n_test_queries =
+			calculate_n_test_queries_reed_solomon::<_, BinaryField128b, _>(100, 4, &rs_code)
+				.unwrap();
+		let pcs = <BasicTensorPCS<
+			OptimalUnderlier128b,
+			BinaryField8b,
+			BinaryField8b,
+			BinaryField128b,
+			_,
+			_,
+			_,
+		>>::new_using_groestl_merkle_tree(4, rs_code, n_test_queries)
+		.unwrap();
+
+		let mut rng = StdRng::seed_from_u64(0);
+		let polys = repeat_with(|| {
+			let evals = repeat_with(|| Packed::random(&mut rng))
+				.take((1 << pcs.n_vars()) / Packed::WIDTH)
+				.collect::<Vec<_>>();
+			MultilinearExtension::from_values(evals).unwrap()
+		})
+		.take(5)
+		.collect::<Vec<_>>();
+
+		let (commitment, (encoded_mats, _)) = pcs.commit(&polys).unwrap();
+		let (pipelined_commitment, (pipelined_encoded_mats, _)) =
+			pcs.commit_pipelined(&polys).unwrap();
+		assert_eq!(pipelined_commitment.0, commitment.0);
+		for (pipelined, expected) in pipelined_encoded_mats.iter().zip(&encoded_mats) {
+			assert_eq!(pipelined.values, expected.values);
+		}
+
+		let short_poly = MultilinearExtension::from_values(vec![Packed::default()]).unwrap();
+		assert_matches!(
+			pcs.commit_pipelined(&[short_poly]),
+			Err(Error::IncorrectPolynomialSize { .. })
+		);
+	}
+
 	#[test]
 	fn test_packed_1b_commit_prove_verify_without_error() {
 		let rs_code = ReedSolomonCode::new(5, 2, NTTOptions::default()).unwrap();
@@ -17,6 +17,29 @@ pub struct IndexComposition<C, const N: usize> {
 	composition: C,
 }
 
+impl<C, const N: usize> IndexComposition<C, N> {
+	/// Constructs an adapter that evaluates `composition` at the given `indices` of a query with
+	/// `n_vars` variables.
+	pub fn new(n_vars: usize, indices: [usize; N], composition: C) -> Result<Self, Error> {
+		if indices.iter().any(|&index| index >= n_vars) {
+			bail!(Error::ArgumentRangeError {
+				arg: "indices".to_string(),
+				range: 0..n_vars,
+			});
+		}
+
+		Ok(Self {
+			n_vars,
+			indices,
+			composition,
+		})
+	}
+
+	pub fn indices(&self) -> &[usize; N] {
+		&self.indices
+	}
+}
+
 impl<P: PackedField, C: CompositionPoly<P>, const N: usize> CompositionPoly<P>
 	for IndexComposition<C, N>
 {
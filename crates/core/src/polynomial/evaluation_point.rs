@@ -0,0 +1,110 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use binius_field::{ExtensionField, Field};
+
+/// The structure of a multilinear evaluation point, detected from its coordinates.
+///
+/// Evaluation points produced by projections and by the zero-padding of oracles often have
+/// coordinates that are $0$ or $1$, or that lie in a small subfield. Evaluating a multilinear at
+/// such a point is cheaper than at a generic point: a hypercube vertex selects a single evaluation,
+/// and the tensor expansion of a subfield point can be computed and multiplied in the subfield.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PointStructure<FS: Field> {
+	/// All coordinates are $0$ or $1$, and the point is the hypercube vertex with this index.
+	HypercubeVertex(usize),
+	/// All coordinates lie in the subfield `FS`.
+	Subfield(Vec<FS>),
+	/// The point has coordinates outside of the subfield `FS`.
+	Generic,
+}
+
+impl<FS: Field> PointStructure<FS> {
+	/// Detects the structure of `point`, preferring the hypercube vertex case over the subfield
+	/// case.
+	pub fn detect<F: ExtensionField<FS>>(point: &[F]) -> Self {
+		if let Some(index) = hypercube_vertex_index(point) {
+			return Self::HypercubeVertex(index);
+		}
+
+		point
+			.iter()
+			.map(|&coord| coord.try_into().ok())
+			.collect::<Option<Vec<_>>>()
+			.map_or(Self::Generic, Self::Subfield)
+	}
+}
+
+/// Returns the index of the hypercube vertex at `point`, if all coordinates are $0$ or $1$.
+///
+/// The coordinate $i$ determines the bit $i$ of the index, which is consistent with the order of
+/// the tensor expansion in [`MultilinearQuery`](super::MultilinearQuery).
+pub fn hypercube_vertex_index<F: Field>(point: &[F]) -> Option<usize> {
+	if point.len() >= usize::BITS as usize {
+		return None;
+	}
+
+	point.iter().enumerate().try_fold(0, |index, (i, &coord)| {
+		if coord == F::ZERO {
+			Some(index)
+		} else if coord == F::ONE {
+			Some(index | 1 << i)
+		} else {
+			None
+		}
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::polynomial::{MultilinearExtension, MultilinearQuery};
+	use binius_field::{BinaryField128b, BinaryField32b, BinaryField8b};
+	use rand::{rngs::StdRng, SeedableRng};
+	use std::iter::repeat_with;
+
+	#[test]
+	fn test_detect_hypercube_vertex() {
+		type F = BinaryField128b;
+		let point = [F::ONE, F::ZERO, F::ONE, F::ONE];
+		assert_eq!(
+			PointStructure::<BinaryField8b>::detect(&point),
+			PointStructure::HypercubeVertex(13)
+		);
+		assert_eq!(hypercube_vertex_index::<F>(&[]), Some(0));
+	}
+
+	#[test]
+	fn test_detect_subfield() {
+		type F = BinaryField128b;
+		let point = [F::ONE, F::new(3), F::new(200)];
+		assert_eq!(
+			PointStructure::<BinaryField8b>::detect(&point),
+			PointStructure::Subfield(vec![
+				BinaryField8b::new(1),
+				BinaryField8b::new(3),
+				BinaryField8b::new(200)
+			])
+		);
+
+		let point = [F::ONE, F::new(1 << 12)];
+		assert_eq!(PointStructure::<BinaryField8b>::detect(&point), PointStructure::Generic);
+	}
+
+	#[test]
+	fn test_vertex_evaluation_matches_query() {
+		type F = BinaryField32b;
+		let mut rng = StdRng::seed_from_u64(0);
+		let n_vars = 5;
+
+		let evals = repeat_with(|| <F as Field>::random(&mut rng))
+			.take(1 << n_vars)
+			.collect::<Vec<_>>();
+		let poly = MultilinearExtension::from_values(evals).unwrap();
+
+		let point = [F::ONE, F::ONE, F::ZERO, F::ONE, F::ZERO];
+		let index = hypercube_vertex_index(&point).unwrap();
+		let query = MultilinearQuery::<F>::with_full_query(&point).unwrap();
+		let eval: F = poly.evaluate(&query).unwrap();
+		assert_eq!(poly.evaluate_on_hypercube(index).unwrap(), eval);
+	}
+}
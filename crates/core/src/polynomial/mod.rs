@@ -2,6 +2,7 @@
 
 pub mod composition;
 pub mod error;
+pub mod evaluation_point;
 pub mod multilinear;
 pub mod multilinear_extension;
 pub mod multilinear_query;
@@ -11,6 +12,7 @@ pub mod univariate;
 pub mod util;
 
 pub use error::*;
+pub use evaluation_point::*;
 pub use multilinear::*;
 pub use multilinear_extension::*;
 pub use multilinear_query::*;
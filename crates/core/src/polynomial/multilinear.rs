@@ -1,19 +1,28 @@
 // Copyright 2023-2024 Ulvetanna Inc.
 
 use crate::polynomial::{
-	multilinear_query::MultilinearQuery, Error, MultilinearExtensionSpecialized,
+	multilinear_query::MultilinearQuery, util::check_expansion_len, Error,
+	MultilinearExtensionSpecialized,
 };
-use binius_field::PackedField;
-use binius_utils::array_2d::Array2D;
+use binius_field::{packed::get_packed_slice, PackedField};
+use binius_utils::{array_2d::Array2D, bail};
+use rayon::prelude::*;
 use std::{
+	cmp::{max, min},
 	fmt::Debug,
 	ops::{Deref, Range},
+	sync::OnceLock,
 };
 
+/// Base-2 logarithm of the number of scalars fetched per subcube in the default implementation of
+/// [`MultilinearPoly::inner_prod_with_expansion`].
+const INNER_PRODUCT_LOG_CHUNK_SIZE: usize = 12;
+
 /// Represents a multilinear polynomial.
 ///
 /// This interface includes no generic methods, in order to support the creation of trait objects.
-pub trait MultilinearPoly<P: PackedField>: Debug {
+/// Implementations must be thread-safe, so that the default methods can evaluate in parallel.
+pub trait MultilinearPoly<P: PackedField>: Debug + Send + Sync {
 	/// Number of variables.
 	fn n_vars(&self) -> usize;
 
@@ -47,7 +56,66 @@ pub trait MultilinearPoly<P: PackedField>: Debug {
 		scalar: P::Scalar,
 	) -> Result<P::Scalar, Error>;
 
-	fn evaluate(&self, query: &MultilinearQuery<P>) -> Result<P::Scalar, Error>;
+	/// Evaluate the polynomial at the point of a query.
+	fn evaluate(&self, query: &MultilinearQuery<P>) -> Result<P::Scalar, Error> {
+		if query.n_vars() != self.n_vars() {
+			bail!(Error::IncorrectQuerySize {
+				expected: self.n_vars(),
+			});
+		}
+		self.inner_prod_with_expansion(query.expansion())
+	}
+
+	/// Compute the inner product of the hypercube evaluations with a tensor product expansion.
+	///
+	/// With the expansion of a query, this is the evaluation of the polynomial at the query point.
+	/// Provers often hold the expansion already, for example the equality indicator of the
+	/// sumcheck challenges, and can evaluate many multilinears against it without rebuilding a
+	/// [`MultilinearQuery`]. The expansion must have `max(1, 2^n / P::WIDTH)` packed elements,
+	/// where n is `self.n_vars()`.
+	///
+	/// The default implementation accumulates packed products over subcube evaluations, with the
+	/// subcubes split across threads.
+	fn inner_prod_with_expansion(&self, expansion: &[P]) -> Result<P::Scalar, Error> {
+		let n_vars = self.n_vars();
+		check_expansion_len(n_vars, expansion)?;
+
+		if n_vars < P::LOG_WIDTH {
+			return (0..1 << n_vars)
+				.map(|i| self.evaluate_on_hypercube_and_scale(i, get_packed_slice(expansion, i)))
+				.sum();
+		}
+
+		let log_chunk_size = min(n_vars, max(P::LOG_WIDTH, INNER_PRODUCT_LOG_CHUNK_SIZE));
+		let chunk_len = 1 << (log_chunk_size - P::LOG_WIDTH);
+		let acc = expansion
+			.par_chunks(chunk_len)
+			.enumerate()
+			.map_init(
+				|| vec![P::zero(); chunk_len],
+				|evals, (chunk_index, expansion_chunk)| {
+					self.subcube_evals(log_chunk_size, chunk_index, evals)?;
+					Ok(evals
+						.iter()
+						.zip(expansion_chunk)
+						.fold(P::zero(), |acc, (&eval, &coeff)| acc + eval * coeff))
+				},
+			)
+			.try_reduce(P::zero, |acc, chunk_acc| Ok(acc + chunk_acc))?;
+		Ok(acc.iter().sum())
+	}
+
+	/// Returns the value of the polynomial if it is constant over the boolean hypercube.
+	///
+	/// Provers use this to detect multilinears that are identically zero or constant, as selector
+	/// columns often are, and skip the per-round work on them. The default implementation checks
+	/// the hypercube evaluations one by one.
+	fn constant_value(&self) -> Option<P::Scalar> {
+		let value = self.evaluate_on_hypercube(0).ok()?;
+		(1..1 << self.n_vars())
+			.all(|index| self.evaluate_on_hypercube(index).ok() == Some(value))
+			.then_some(value)
+	}
 
 	fn evaluate_partial_low(
 		&self,
@@ -82,7 +150,7 @@ pub trait MultilinearPoly<P: PackedField>: Debug {
 
 impl<T, P: PackedField> MultilinearPoly<P> for T
 where
-	T: Deref + Debug,
+	T: Deref + Debug + Send + Sync,
 	T::Target: MultilinearPoly<P>,
 {
 	fn n_vars(&self) -> usize {
@@ -113,6 +181,14 @@ where
 		(**self).evaluate(query)
 	}
 
+	fn inner_prod_with_expansion(&self, expansion: &[P]) -> Result<P::Scalar, Error> {
+		(**self).inner_prod_with_expansion(expansion)
+	}
+
+	fn constant_value(&self) -> Option<P::Scalar> {
+		(**self).constant_value()
+	}
+
 	fn evaluate_partial_low(
 		&self,
 		query: &MultilinearQuery<P>,
@@ -142,3 +218,93 @@ where
 		(**self).subcube_evals(vars, index, dst)
 	}
 }
+
+/// A multilinear that computes [`MultilinearPoly::constant_value`] at most once.
+///
+/// Detecting a constant multilinear scans its hypercube evaluations. Witnesses are shared by the
+/// provers of all claims on an oracle, so the witness index wraps them in this type and every
+/// prover constructed over the same witness reuses the first result.
+#[derive(Debug)]
+pub struct ConstantValueCached<P: PackedField, M> {
+	inner: M,
+	constant_value: OnceLock<Option<P::Scalar>>,
+}
+
+impl<P: PackedField, M: MultilinearPoly<P>> ConstantValueCached<P, M> {
+	pub fn new(inner: M) -> Self {
+		Self {
+			inner,
+			constant_value: OnceLock::new(),
+		}
+	}
+}
+
+impl<P: PackedField, M: MultilinearPoly<P>> MultilinearPoly<P> for ConstantValueCached<P, M> {
+	fn n_vars(&self) -> usize {
+		self.inner.n_vars()
+	}
+
+	fn size(&self) -> usize {
+		self.inner.size()
+	}
+
+	fn extension_degree(&self) -> usize {
+		self.inner.extension_degree()
+	}
+
+	fn evaluate_on_hypercube(&self, index: usize) -> Result<P::Scalar, Error> {
+		self.inner.evaluate_on_hypercube(index)
+	}
+
+	fn evaluate_on_hypercube_and_scale(
+		&self,
+		index: usize,
+		scalar: P::Scalar,
+	) -> Result<P::Scalar, Error> {
+		self.inner.evaluate_on_hypercube_and_scale(index, scalar)
+	}
+
+	fn evaluate(&self, query: &MultilinearQuery<P>) -> Result<P::Scalar, Error> {
+		self.inner.evaluate(query)
+	}
+
+	fn inner_prod_with_expansion(&self, expansion: &[P]) -> Result<P::Scalar, Error> {
+		self.inner.inner_prod_with_expansion(expansion)
+	}
+
+	fn constant_value(&self) -> Option<P::Scalar> {
+		*self
+			.constant_value
+			.get_or_init(|| self.inner.constant_value())
+	}
+
+	fn evaluate_partial_low(
+		&self,
+		query: &MultilinearQuery<P>,
+	) -> Result<MultilinearExtensionSpecialized<P, P>, Error> {
+		self.inner.evaluate_partial_low(query)
+	}
+
+	fn evaluate_partial_high(
+		&self,
+		query: &MultilinearQuery<P>,
+	) -> Result<MultilinearExtensionSpecialized<P, P>, Error> {
+		self.inner.evaluate_partial_high(query)
+	}
+
+	fn evaluate_subcube(
+		&self,
+		indices: Range<usize>,
+		query: &MultilinearQuery<P>,
+		evals_0: &mut Array2D<P>,
+		evals_1: &mut Array2D<P>,
+		col_index: usize,
+	) -> Result<(), Error> {
+		self.inner
+			.evaluate_subcube(indices, query, evals_0, evals_1, col_index)
+	}
+
+	fn subcube_evals(&self, vars: usize, index: usize, dst: &mut [P]) -> Result<(), Error> {
+		self.inner.subcube_evals(vars, index, dst)
+	}
+}
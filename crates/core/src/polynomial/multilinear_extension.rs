@@ -1,7 +1,7 @@
 // Copyright 2023 Ulvetanna Inc.
 
 use super::{error::Error, multilinear::MultilinearPoly, multilinear_query::MultilinearQuery};
-use crate::polynomial::util::PackingDeref;
+use crate::polynomial::util::{check_expansion_len, PackingDeref};
 use binius_field::{
 	as_packed_field::{AsSinglePacked, PackScalar, PackedType},
 	packed::{
@@ -11,8 +11,7 @@ use binius_field::{
 	util::inner_product_par,
 	ExtensionField, Field, PackedField,
 };
-use binius_utils::{array_2d::Array2D, bail};
-use bytemuck::zeroed_vec;
+use binius_utils::{alloc::zeroed_std_vec, array_2d::Array2D, bail};
 use p3_util::log2_strict_usize;
 use rayon::prelude::*;
 use std::{
@@ -56,6 +55,120 @@ impl<P: PackedField> MultilinearExtension<P> {
 	pub fn from_values(v: Vec<P>) -> Result<Self, Error> {
 		MultilinearExtension::from_values_generic(v)
 	}
+
+	/// Constructs a multilinear extension from its hypercube evaluations given as unpacked scalars.
+	///
+	/// The scalars are packed in parallel. The number of scalars must be a power of two and no
+	/// less than the packing width.
+	pub fn from_scalars(scalars: &[P::Scalar]) -> Result<Self, Error> {
+		if !scalars.len().is_power_of_two() {
+			bail!(Error::PowerOfTwoLengthRequired);
+		}
+		if scalars.len() < P::WIDTH {
+			bail!(Error::PackedFieldNotFilled {
+				length: scalars.len(),
+				packed_width: P::WIDTH,
+			});
+		}
+
+		let evals = scalars
+			.par_chunks_exact(P::WIDTH)
+			.map(|chunk| P::from_fn(|i| chunk[i]))
+			.collect();
+		MultilinearExtension::from_values(evals)
+	}
+
+	/// Partially evaluate the polynomial in place with assignment to the low-indexed variables.
+	///
+	/// This computes the same polynomial as [`Self::partial_evaluate_low`] into the same packed
+	/// field, overwriting the leading evaluations instead of allocating a new buffer.
+	pub fn partial_evaluate_low_in_place(&mut self, values: &[P::Scalar]) -> Result<(), Error> {
+		if self.mu < values.len() {
+			bail!(Error::IncorrectQuerySize { expected: self.mu });
+		}
+		if values.is_empty() {
+			return Ok(());
+		}
+
+		let n_vars = values.len();
+		let query = MultilinearQuery::<P>::with_full_query(values)?;
+		let query_expansion = query.expansion();
+		let new_n_vars = self.mu - n_vars;
+		let result_evals_len = 1 << new_n_vars.saturating_sub(P::LOG_WIDTH);
+		let n_scalars = min(P::WIDTH, 1 << new_n_vars);
+
+		let eval_chunk = |chunk: &[P], n_scalars: usize| {
+			P::from_fn(|j| {
+				if j >= n_scalars {
+					return P::Scalar::ZERO;
+				}
+				iter_packed_slice(query_expansion)
+					.take(1 << n_vars)
+					.enumerate()
+					.map(|(t, basis_eval)| basis_eval * get_packed_slice(chunk, (j << n_vars) + t))
+					.sum()
+			})
+		};
+
+		if new_n_vars >= P::LOG_WIDTH {
+			// The result at packed index `i` only reads the `2^n_vars` packed evaluations from
+			// packed index `i << n_vars` on. The results are computed in parallel into the first
+			// element of each of these chunks, and then moved to the front in ascending order,
+			// which never overwrites a result that is still to be moved.
+			self.evals
+				.par_chunks_exact_mut(1 << n_vars)
+				.for_each(|chunk| chunk[0] = eval_chunk(chunk, P::WIDTH));
+			for i in 1..result_evals_len {
+				self.evals[i] = self.evals[i << n_vars];
+			}
+		} else {
+			// The result occupies a single packed element.
+			self.evals[0] = eval_chunk(&self.evals, n_scalars);
+		}
+
+		self.evals.truncate(result_evals_len);
+		self.mu = new_n_vars.max(P::LOG_WIDTH);
+		Ok(())
+	}
+
+	/// Partially evaluate the polynomial in place with assignment to the high-indexed variables.
+	///
+	/// This computes the same polynomial as [`Self::partial_evaluate_high`] into the same packed
+	/// field. The highest variables are folded one at a time, each fold combining the two halves
+	/// of the evaluations with packed arithmetic and truncating the upper half.
+	pub fn partial_evaluate_high_in_place(&mut self, values: &[P::Scalar]) -> Result<(), Error> {
+		if self.mu < values.len() {
+			bail!(Error::IncorrectQuerySize { expected: self.mu });
+		}
+
+		let mut n_vars = self.mu;
+		for &value in values.iter().rev() {
+			n_vars -= 1;
+			if n_vars >= P::LOG_WIDTH {
+				let half = 1 << (n_vars - P::LOG_WIDTH);
+				let (evals_0, evals_1) = self.evals.split_at_mut(half);
+				evals_0
+					.par_iter_mut()
+					.zip(evals_1.par_iter())
+					.for_each(|(eval_0, &eval_1)| *eval_0 += (eval_1 - *eval_0) * value);
+				self.evals.truncate(half);
+			} else {
+				// The remaining evaluations occupy a single packed element.
+				let half = 1 << n_vars;
+				let evals = self.evals[0];
+				self.evals[0] = P::from_fn(|j| {
+					if j >= half {
+						return P::Scalar::ZERO;
+					}
+					let eval_0 = evals.get(j);
+					eval_0 + (evals.get(j | half) - eval_0) * value
+				});
+			}
+		}
+
+		self.mu = n_vars.max(P::LOG_WIDTH);
+		Ok(())
+	}
 }
 
 impl<P: PackedField, Data: Deref<Target = [P]>> MultilinearExtension<P, Data> {
@@ -133,6 +246,33 @@ where
 	P: PackedField,
 	Data: Deref<Target = [P]> + Send + Sync,
 {
+	/// Returns the hypercube evaluations as unpacked scalars, in lexicographic order.
+	///
+	/// This is the inverse of [`MultilinearExtension::from_scalars`].
+	pub fn to_scalars(&self) -> Vec<P::Scalar> {
+		let n_scalars = min(self.size(), P::WIDTH);
+		let mut scalars = vec![P::Scalar::ZERO; self.size()];
+		scalars
+			.par_chunks_mut(n_scalars)
+			.zip(self.evals().par_iter())
+			.for_each(|(chunk, packed)| {
+				for (scalar, value) in chunk.iter_mut().zip(packed.iter()) {
+					*scalar = value;
+				}
+			});
+		scalars
+	}
+
+	/// Returns the value of the polynomial if it is constant over the boolean hypercube.
+	pub fn constant_value(&self) -> Option<P::Scalar> {
+		let value = self.evals().first()?.get(0);
+		let packed_value = P::broadcast(value);
+		self.evals()
+			.par_iter()
+			.all(|&packed| packed == packed_value)
+			.then_some(value)
+	}
+
 	pub fn evaluate<FE, PE>(&self, query: &MultilinearQuery<PE>) -> Result<FE, Error>
 	where
 		FE: ExtensionField<P::Scalar>,
@@ -141,7 +281,20 @@ where
 		if self.mu != query.n_vars() {
 			bail!(Error::IncorrectQuerySize { expected: self.mu });
 		}
-		Ok(inner_product_par(query.expansion(), &self.evals))
+		self.inner_prod_with_expansion(query.expansion())
+	}
+
+	/// Computes the inner product of the hypercube evaluations with a tensor product expansion.
+	///
+	/// The expansion of a query with $\mu$ variables makes this the evaluation at the query point.
+	/// The expansion must have exactly `max(2^mu / PE::WIDTH, 1)` packed elements.
+	pub fn inner_prod_with_expansion<FE, PE>(&self, expansion: &[PE]) -> Result<FE, Error>
+	where
+		FE: ExtensionField<P::Scalar>,
+		PE: PackedField<Scalar = FE>,
+	{
+		check_expansion_len(self.mu, expansion)?;
+		Ok(inner_product_par(expansion, &self.evals))
 	}
 
 	/// Partially evaluate the polynomial with assignment to the high-indexed variables.
@@ -217,7 +370,7 @@ where
 		}
 
 		let mut result =
-			zeroed_vec(1 << ((self.mu - query.n_vars()).saturating_sub(PE::LOG_WIDTH)));
+			zeroed_std_vec(1 << ((self.mu - query.n_vars()).saturating_sub(PE::LOG_WIDTH)));
 		self.evaluate_partial_low_into(query, &mut result)?;
 		MultilinearExtension::from_values(result)
 	}
@@ -283,6 +436,40 @@ where
 
 		Ok(())
 	}
+
+	/// Partially evaluate the polynomial at the given values of the high-indexed variables.
+	///
+	/// The values represent $(z_{\mu - k}, ..., z_{\mu - 1})$. This is
+	/// [`Self::evaluate_partial_high`] with the query expanded from the values, for callers that
+	/// do not reuse the query across several polynomials.
+	pub fn partial_evaluate_high<PE>(
+		&self,
+		values: &[PE::Scalar],
+	) -> Result<MultilinearExtension<PE>, Error>
+	where
+		PE: PackedField,
+		PE::Scalar: ExtensionField<P::Scalar>,
+	{
+		let query = MultilinearQuery::<PE>::with_full_query(values)?;
+		self.evaluate_partial_high(&query)
+	}
+
+	/// Partially evaluate the polynomial at the given values of the low-indexed variables.
+	///
+	/// The values represent $(z_0, ..., z_{k-1})$. This is [`Self::evaluate_partial_low`] with the
+	/// query expanded from the values, for callers that do not reuse the query across several
+	/// polynomials.
+	pub fn partial_evaluate_low<PE>(
+		&self,
+		values: &[PE::Scalar],
+	) -> Result<MultilinearExtension<PE>, Error>
+	where
+		PE: PackedField,
+		PE::Scalar: ExtensionField<P::Scalar>,
+	{
+		let query = MultilinearQuery::<PE>::with_full_query(values)?;
+		self.evaluate_partial_low(&query)
+	}
 }
 
 impl<P, Data> MultilinearExtension<P, Data>
@@ -381,6 +568,19 @@ where
 	}
 }
 
+impl<P, PE, Data> AsMut<MultilinearExtension<P, Data>>
+	for MultilinearExtensionSpecialized<P, PE, Data>
+where
+	P: PackedField,
+	PE: PackedField,
+	PE::Scalar: ExtensionField<P::Scalar>,
+	Data: Deref<Target = [P]>,
+{
+	fn as_mut(&mut self) -> &mut MultilinearExtension<P, Data> {
+		&mut self.0
+	}
+}
+
 impl<P, PE, Data> MultilinearPoly<PE> for MultilinearExtensionSpecialized<P, PE, Data>
 where
 	P: PackedField + Debug,
@@ -410,8 +610,12 @@ where
 		Ok(scalar * eval)
 	}
 
-	fn evaluate(&self, query: &MultilinearQuery<PE>) -> Result<PE::Scalar, Error> {
-		self.0.evaluate(query)
+	fn inner_prod_with_expansion(&self, expansion: &[PE]) -> Result<PE::Scalar, Error> {
+		self.0.inner_prod_with_expansion(expansion)
+	}
+
+	fn constant_value(&self) -> Option<PE::Scalar> {
+		self.0.constant_value().map(Into::into)
 	}
 
 	fn evaluate_partial_low(
@@ -661,6 +865,7 @@ pub type MultilinearExtensionBorrowed<'a, P> = MultilinearExtension<P, &'a [P]>;
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use assert_matches::assert_matches;
 	use itertools::Itertools;
 	use rand::{rngs::StdRng, SeedableRng};
 	use std::iter::repeat_with;
@@ -681,6 +886,48 @@ mod tests {
 		assert_eq!(iter_packed_slice(result1.expansion()).collect_vec(), result2);
 	}
 
+	#[test]
+	fn test_from_scalars_to_scalars_roundtrip() {
+		let mut rng = StdRng::seed_from_u64(0);
+		let scalars = repeat_with(|| Field::random(&mut rng))
+			.take(64)
+			.collect::<Vec<F>>();
+
+		let poly = MultilinearExtension::<P>::from_scalars(&scalars).unwrap();
+		assert_eq!(poly.n_vars(), 6);
+		assert_eq!(iter_packed_slice(poly.evals()).collect_vec(), scalars);
+		assert_eq!(poly.to_scalars(), scalars);
+
+		assert_matches!(
+			MultilinearExtension::<P>::from_scalars(&scalars[..48]),
+			Err(Error::PowerOfTwoLengthRequired)
+		);
+		assert_matches!(
+			MultilinearExtension::<P>::from_scalars(&scalars[..4]),
+			Err(Error::PackedFieldNotFilled { .. })
+		);
+	}
+
+	#[test]
+	fn test_constant_value() {
+		let value = F::new(7);
+		let mut scalars = vec![value; 64];
+		let poly = MultilinearExtension::<P>::from_scalars(&scalars).unwrap();
+		assert_eq!(poly.constant_value(), Some(value));
+		assert_eq!(
+			MultilinearPoly::<P>::constant_value(&poly.to_ref().specialize::<P>()),
+			Some(value)
+		);
+
+		let zeros = MultilinearExtension::<P>::zeros(6).unwrap();
+		assert_eq!(zeros.constant_value(), Some(F::ZERO));
+
+		scalars[37] = F::new(8);
+		let poly = MultilinearExtension::<P>::from_scalars(&scalars).unwrap();
+		assert_eq!(poly.constant_value(), None);
+		assert_eq!(MultilinearPoly::<P>::constant_value(&poly.to_ref().specialize::<P>()), None);
+	}
+
 	#[test]
 	fn test_evaluate_on_hypercube() {
 		let mut values = vec![F::ZERO; 64];
@@ -817,6 +1064,39 @@ mod tests {
 		assert_eq!(m1[(0, 1)], BinaryField128b::new(9));
 	}
 
+	#[test]
+	fn test_inner_prod_with_expansion() {
+		let mut rng = StdRng::seed_from_u64(0);
+		let poly = MultilinearExtension::from_values(
+			repeat_with(|| PackedBinaryField4x32b::random(&mut rng))
+				.take(1 << 4)
+				.collect(),
+		)
+		.unwrap()
+		.specialize::<BinaryField128b>();
+
+		let q = repeat_with(|| <BinaryField128b as PackedField>::random(&mut rng))
+			.take(6)
+			.collect::<Vec<_>>();
+		let query = MultilinearQuery::with_full_query(&q).unwrap();
+		assert_eq!(
+			poly.inner_prod_with_expansion(query.expansion()).unwrap(),
+			poly.evaluate(&query).unwrap()
+		);
+		assert_eq!(
+			poly.clone()
+				.upcast_arc_dyn()
+				.inner_prod_with_expansion(query.expansion())
+				.unwrap(),
+			poly.evaluate(&query).unwrap()
+		);
+
+		assert_matches!(
+			poly.inner_prod_with_expansion(&query.expansion()[1..]),
+			Err(Error::InvalidPackedValuesLength)
+		);
+	}
+
 	#[test]
 	fn test_evaluate_partial_high_low_evaluate_consistent() {
 		let mut rng = StdRng::seed_from_u64(0);
@@ -853,4 +1133,48 @@ mod tests {
 			eval
 		);
 	}
+
+	#[test]
+	fn test_partial_evaluate_in_place_consistent() {
+		type P = PackedBinaryField4x32b;
+		let mut rng = StdRng::seed_from_u64(0);
+
+		// Covers results spanning several packed elements and results within a single one.
+		for (n_vars, n_values) in [(6, 0), (6, 1), (6, 3), (6, 5), (2, 1), (2, 2)] {
+			let values = repeat_with(|| P::random(&mut rng))
+				.take(1 << (n_vars - P::LOG_WIDTH))
+				.collect::<Vec<_>>();
+			let me = MultilinearExtension::from_values(values).unwrap();
+			let z = repeat_with(|| <BinaryField32b as Field>::random(&mut rng))
+				.take(n_values)
+				.collect::<Vec<_>>();
+			let query = MultilinearQuery::<P>::with_full_query(&z).unwrap();
+
+			let low = me.partial_evaluate_low::<P>(&z).unwrap();
+			assert_eq!(low, me.evaluate_partial_low(&query).unwrap());
+			let mut low_in_place = me.clone();
+			low_in_place.partial_evaluate_low_in_place(&z).unwrap();
+			assert_eq!(low_in_place, low);
+
+			let high = me.partial_evaluate_high::<P>(&z).unwrap();
+			assert_eq!(high, me.evaluate_partial_high(&query).unwrap());
+			let mut high_in_place = me.clone();
+			high_in_place.partial_evaluate_high_in_place(&z).unwrap();
+			assert_eq!(high_in_place, high);
+		}
+	}
+
+	#[test]
+	fn test_partial_evaluate_in_place_too_many_values() {
+		let mut me = MultilinearExtension::<PackedBinaryField4x32b>::zeros(2).unwrap();
+		let z = vec![BinaryField32b::ONE; 3];
+		assert_matches!(
+			me.partial_evaluate_low_in_place(&z),
+			Err(Error::IncorrectQuerySize { expected: 2 })
+		);
+		assert_matches!(
+			me.partial_evaluate_high_in_place(&z),
+			Err(Error::IncorrectQuerySize { expected: 2 })
+		);
+	}
 }
@@ -1,11 +1,16 @@
 // Copyright 2023 Ulvetanna Inc.
 
 use super::util::tensor_prod_eq_ind;
-use crate::polynomial::Error as PolynomialError;
-use binius_field::{Field, PackedField};
-use binius_utils::bail;
-use bytemuck::zeroed_vec;
-use std::cmp::max;
+use crate::polynomial::{Error as PolynomialError, MultilinearExtension};
+use binius_field::{
+	packed::{get_packed_slice, set_packed_slice},
+	Field, PackedField,
+};
+use binius_utils::{
+	alloc::{zeroed_vec, AlignedVec},
+	bail,
+};
+use std::{cmp::max, ops::Deref, sync::Arc};
 
 /// Tensor product expansion of sumcheck round challenges.
 ///
@@ -16,7 +21,7 @@ use std::cmp::max;
 /// before it becomes more efficient to switch over to the method that store folded multilinears.
 #[derive(Debug, Clone)]
 pub struct MultilinearQuery<P: PackedField> {
-	expanded_query: Vec<P>,
+	expanded_query: AlignedVec<P>,
 	// We want to avoid initializing data at the moment when vector is growing,
 	// So we allocate zeroed vector and keep track of the length of the initialized part.
 	expanded_query_len: usize,
@@ -54,12 +59,12 @@ impl<P: PackedField> MultilinearQuery<P> {
 		&self.expanded_query[0..self.expanded_query_len]
 	}
 
-	pub fn into_expansion(mut self) -> Vec<P> {
-		// Trim query vector to the actual size
-		self.expanded_query
-			.resize(self.expanded_query_len, P::zero());
-
-		self.expanded_query
+	/// Returns the expansion as a `Vec`.
+	///
+	/// The expansion is kept in an aligned buffer, which cannot be handed off as a `Vec`, so this
+	/// copies the initialized part.
+	pub fn into_expansion(self) -> Vec<P> {
+		self.expansion().to_vec()
 	}
 
 	pub fn update(
@@ -86,11 +91,109 @@ impl<P: PackedField> MultilinearQuery<P> {
 			n_vars: new_n_vars,
 		})
 	}
+
+	/// Extends the query with coordinates that bind variables below the current ones.
+	///
+	/// Each coordinate becomes the new lowest query variable, so after `update_low(&[r_0, r_1])`
+	/// on an empty query the expansion is that of the query $(r_1, r_0)$. This is the order in
+	/// which sumcheck provers folding high-to-low bind their variables.
+	pub fn update_low(
+		mut self,
+		extra_query_coordinates: &[P::Scalar],
+	) -> Result<Self, PolynomialError> {
+		let old_n_vars = self.n_vars;
+		let new_n_vars = old_n_vars + extra_query_coordinates.len();
+		let new_length = max((1 << new_n_vars) / P::WIDTH, 1);
+		if new_length > self.expanded_query.len() {
+			bail!(PolynomialError::MultilinearQueryFull {
+				max_query_vars: old_n_vars,
+			});
+		}
+
+		let expansion = &mut self.expanded_query[..new_length];
+		for (i, &r_i) in extra_query_coordinates.iter().enumerate() {
+			// Spread the expansion from the back, so that no value is overwritten before it is
+			// read.
+			for j in (0..1 << (old_n_vars + i)).rev() {
+				let x = get_packed_slice(expansion, j);
+				let prod = x * r_i;
+				set_packed_slice(expansion, j << 1, x - prod);
+				set_packed_slice(expansion, j << 1 | 1, prod);
+			}
+		}
+
+		Ok(Self {
+			expanded_query: self.expanded_query,
+			expanded_query_len: new_length,
+			n_vars: new_n_vars,
+		})
+	}
+}
+
+/// A tensor product expansion at a fixed evaluation point, which can be shared between consumers.
+///
+/// The expansion at an $n$-variate point has size $2^n$. Provers that evaluate multilinears at the
+/// same point, such as the evalcheck prover and the sumcheck provers for the claims it reduces to,
+/// should build the expansion once and share it as an `Arc<MultilinearQueryRef<P>>`. Unlike a
+/// [`MultilinearQuery`], the expansion cannot be updated after construction, so it is safe to
+/// share and always matches [`Self::eval_point`].
+///
+/// A `MultilinearQueryRef` dereferences to the underlying [`MultilinearQuery`], so it can be
+/// passed to any method that takes a query.
+#[derive(Debug)]
+pub struct MultilinearQueryRef<P: PackedField> {
+	eval_point: Vec<P::Scalar>,
+	query: MultilinearQuery<P>,
+}
+
+impl<P: PackedField> MultilinearQueryRef<P> {
+	pub fn new(eval_point: &[P::Scalar]) -> Result<Arc<Self>, PolynomialError> {
+		let query = MultilinearQuery::with_full_query(eval_point)?;
+		Ok(Arc::new(Self {
+			eval_point: eval_point.to_vec(),
+			query,
+		}))
+	}
+
+	pub fn eval_point(&self) -> &[P::Scalar] {
+		&self.eval_point
+	}
+
+	/// Returns the multilinear extension of the equality indicator at the evaluation point.
+	///
+	/// Its evaluations over the hypercube are the expansion itself, so the returned multilinear
+	/// shares the expansion instead of copying it.
+	pub fn eq_ind_multilinear(
+		self: &Arc<Self>,
+	) -> Result<MultilinearExtension<P, SharedExpansion<P>>, PolynomialError> {
+		MultilinearExtension::from_values_generic(SharedExpansion(self.clone()))
+	}
+}
+
+impl<P: PackedField> Deref for MultilinearQueryRef<P> {
+	type Target = MultilinearQuery<P>;
+
+	fn deref(&self) -> &Self::Target {
+		&self.query
+	}
+}
+
+/// The expansion of a shared [`MultilinearQueryRef`], as the evaluations of a multilinear
+/// extension.
+#[derive(Debug, Clone)]
+pub struct SharedExpansion<P: PackedField>(Arc<MultilinearQueryRef<P>>);
+
+impl<P: PackedField> Deref for SharedExpansion<P> {
+	type Target = [P];
+
+	fn deref(&self) -> &Self::Target {
+		self.0.expansion()
+	}
 }
 
 #[cfg(test)]
 mod tests {
-	use super::MultilinearQuery;
+	use super::{MultilinearQuery, MultilinearQueryRef};
 	use crate::protocols::test_utils::macros::felts;
 
 	macro_rules! expand_query {
@@ -173,4 +276,44 @@ mod tests {
 			felts!(BinaryField16b[3, 2, 2, 1, 2, 1, 1, 3, 2, 1, 1, 3, 1, 3, 3, 2])
 		);
 	}
+
+	#[test]
+	fn test_update_low_reverses_coordinates() {
+		use binius_field::{BinaryField32b, PackedBinaryField4x32b};
+
+		let coordinates = [2, 3, 5, 7, 11].map(BinaryField32b::new);
+		let mut reversed = coordinates;
+		reversed.reverse();
+
+		let query = MultilinearQuery::<PackedBinaryField4x32b>::new(5)
+			.unwrap()
+			.update_low(&coordinates[..2])
+			.unwrap()
+			.update_low(&coordinates[2..])
+			.unwrap();
+		let expected =
+			MultilinearQuery::<PackedBinaryField4x32b>::with_full_query(&reversed).unwrap();
+		assert_eq!(query.n_vars(), 5);
+		assert_eq!(query.expansion(), expected.expansion());
+	}
+
+	#[test]
+	fn test_shared_query_eq_ind_multilinear() {
+		use crate::polynomial::transparent::eq_ind::EqIndPartialEval;
+		use binius_field::{BinaryField32b, PackedBinaryField4x32b};
+		use std::sync::Arc;
+
+		let eval_point = [2, 3, 5, 7].map(BinaryField32b::new);
+		let shared = MultilinearQueryRef::<PackedBinaryField4x32b>::new(&eval_point).unwrap();
+		assert_eq!(shared.eval_point(), eval_point);
+		assert_eq!(shared.n_vars(), 4);
+
+		let eq_ind = shared.eq_ind_multilinear().unwrap();
+		let expected = EqIndPartialEval::new(4, eval_point.to_vec())
+			.unwrap()
+			.multilinear_extension::<PackedBinaryField4x32b>()
+			.unwrap();
+		assert_eq!(eq_ind.evals(), expected.evals());
+		assert_eq!(Arc::strong_count(&shared), 2);
+	}
 }
@@ -4,8 +4,9 @@ use super::{
 	error::Error, multilinear_query::MultilinearQuery, MultilinearExtension, MultilinearPoly,
 };
 use auto_impl::auto_impl;
-use binius_field::{ExtensionField, PackedField, TowerField};
+use binius_field::{ExtensionField, Field, PackedField, TowerField};
 use binius_utils::bail;
+use rayon::prelude::*;
 use std::{borrow::Borrow, fmt::Debug, marker::PhantomData, sync::Arc};
 
 /// A multivariate polynomial over a binary tower field.
@@ -24,6 +25,35 @@ pub trait MultivariatePoly<P>: Debug + Send + Sync {
 
 	/// Returns the maximum binary tower level of all constants in the arithmetic expression.
 	fn binary_tower_level(&self) -> usize;
+
+	/// Returns the evaluations of the polynomial over the boolean hypercube.
+	///
+	/// The evaluation at index $i$ is at the vertex whose coordinate $k$ is bit $k$ of $i$, as in
+	/// [`MultilinearExtension`]. The default implementation evaluates the polynomial at every
+	/// vertex. Polynomials with a cheaper way to compute their multilinear extension, such as a
+	/// tensor expansion, should override it.
+	fn hypercube_evals(&self) -> Result<Vec<P>, Error>
+	where
+		P: Field,
+	{
+		let n_vars = self.n_vars();
+		(0..1usize << n_vars)
+			.into_par_iter()
+			.map_init(
+				|| vec![P::ZERO; n_vars],
+				|query, index| {
+					for (k, coord) in query.iter_mut().enumerate() {
+						*coord = if (index >> k) & 1 == 1 {
+							P::ONE
+						} else {
+							P::ZERO
+						};
+					}
+					self.evaluate(query)
+				},
+			)
+			.collect()
+	}
 }
 
 /// A multivariate polynomial that defines a composition of `MultilinearComposite`.
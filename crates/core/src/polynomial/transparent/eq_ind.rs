@@ -62,6 +62,13 @@ impl<F: TowerField, P: PackedField<Scalar = F>> MultivariatePoly<P> for EqIndPar
 	fn binary_tower_level(&self) -> usize {
 		F::TOWER_LEVEL
 	}
+
+	fn hypercube_evals(&self) -> Result<Vec<P>, Error>
+	where
+		P: Field,
+	{
+		Ok(MultilinearQuery::<P>::with_full_query(&self.r)?.into_expansion())
+	}
 }
 
 #[cfg(test)]
@@ -104,4 +111,31 @@ mod tests {
 			test_eq_consistency_help(n_vars);
 		}
 	}
+
+	#[test]
+	fn test_hypercube_evals_match_vertex_evaluations() {
+		type F = BinaryField32b;
+		let n_vars = 4;
+
+		let mut rng = StdRng::seed_from_u64(0);
+		let r = repeat_with(|| F::random(&mut rng))
+			.take(n_vars)
+			.collect::<Vec<_>>();
+		let eq_r = EqIndPartialEval::new(n_vars, r).unwrap();
+
+		let evals = MultivariatePoly::<F>::hypercube_evals(&eq_r).unwrap();
+		assert_eq!(evals.len(), 1 << n_vars);
+		for (index, &eval) in evals.iter().enumerate() {
+			let vertex = (0..n_vars)
+				.map(|k| {
+					if (index >> k) & 1 == 1 {
+						F::one()
+					} else {
+						F::zero()
+					}
+				})
+				.collect::<Vec<_>>();
+			assert_eq!(eq_r.evaluate(&vertex).unwrap(), eval);
+		}
+	}
 }
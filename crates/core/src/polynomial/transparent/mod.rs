@@ -4,7 +4,10 @@ pub mod constant;
 pub mod disjoint_product;
 pub mod eq_ind;
 pub mod multilinear_extension;
+pub mod ring_switch_eq_ind;
 pub mod select_row;
 pub mod shift_ind;
 pub mod step_down;
+pub mod step_up;
+pub mod subcube_ind;
 pub mod tower_basis;
@@ -0,0 +1,192 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use crate::polynomial::{
+	multilinear_query::MultilinearQuery, Error, MultilinearExtension, MultivariatePoly,
+};
+use binius_field::{BinaryField1b, ExtensionField, Field, PackedField, TowerField};
+use binius_utils::bail;
+use rayon::prelude::*;
+
+/// The multilinear polynomial that ring-switching reduces the evaluation of a packed 1-bit
+/// multilinear to a sumcheck against.
+///
+/// Let $\beta_0, \ldots, \beta_{d-1}$ be the canonical basis of $F$ over $\mathbb{F}_2$ and
+/// $\phi_v : F \to \mathbb{F}_2$ the projection onto the coordinate of $\beta_v$. Given the
+/// evaluation point $r$ of the packed variables and the row batching challenges $r''$, this is the
+/// multilinear extension of
+///
+/// $$
+/// A(w) = \sum_{v \in \{0, 1\}^{\kappa}} \widetilde{eq}(r'', v) \cdot \phi_v(\widetilde{eq}(r, w)).
+/// $$
+///
+/// $A$ is not $F$-linear in $\widetilde{eq}(r, w)$, so it is evaluated at a point $r'$ in the
+/// tensor algebra $F \otimes_{\mathbb{F}_2} F$, where
+/// $\sum_w \widetilde{eq}(r', w) \otimes \widetilde{eq}(r, w)$ factors as a product of $n$ simple
+/// tensor sums.
+#[derive(Debug, Clone)]
+pub struct RingSwitchEqInd<F: Field> {
+	eval_point: Vec<F>,
+	row_batch_challenges: Vec<F>,
+}
+
+impl<F: TowerField> RingSwitchEqInd<F> {
+	pub fn new(eval_point: Vec<F>, row_batch_challenges: Vec<F>) -> Result<Self, Error> {
+		let log_degree = F::DEGREE.ilog2() as usize;
+		if row_batch_challenges.len() != log_degree {
+			bail!(Error::IncorrectQuerySize {
+				expected: log_degree,
+			});
+		}
+		Ok(Self {
+			eval_point,
+			row_batch_challenges,
+		})
+	}
+
+	pub fn multilinear_extension<P: PackedField<Scalar = F>>(
+		&self,
+	) -> Result<MultilinearExtension<P>, Error> {
+		let n_vars = self.eval_point.len();
+		if n_vars < P::LOG_WIDTH {
+			bail!(Error::PackedFieldNotFilled {
+				length: 1 << n_vars,
+				packed_width: 1 << P::LOG_WIDTH,
+			});
+		}
+
+		let eq_ind = MultilinearQuery::<F>::with_full_query(&self.eval_point)?.into_expansion();
+		let row_batch_coeffs =
+			MultilinearQuery::<F>::with_full_query(&self.row_batch_challenges)?.into_expansion();
+		let values = eq_ind
+			.par_chunks(P::WIDTH)
+			.map(|chunk| P::from_fn(|j| batch_bits(chunk[j], &row_batch_coeffs)))
+			.collect();
+		MultilinearExtension::from_values(values)
+	}
+}
+
+impl<F: TowerField> MultivariatePoly<F> for RingSwitchEqInd<F> {
+	fn n_vars(&self) -> usize {
+		self.eval_point.len()
+	}
+
+	fn degree(&self) -> usize {
+		self.eval_point.len()
+	}
+
+	fn evaluate(&self, query: &[F]) -> Result<F, Error> {
+		let n_vars = self.eval_point.len();
+		if query.len() != n_vars {
+			bail!(Error::IncorrectQuerySize { expected: n_vars });
+		}
+
+		// The tensor algebra element is stored as its row components $T_v$, with
+		// $T = \sum_v T_v \otimes \beta_v$, starting from $1 \otimes 1$.
+		let mut row_components = vec![F::ZERO; F::DEGREE];
+		row_components[0] = F::ONE;
+		for (&q_i, &r_i) in query.iter().zip(self.eval_point.iter()) {
+			let term_one = mul_by_column(&row_components, r_i);
+			let term_two = mul_by_column(&row_components, F::ONE - r_i);
+			for (component, (term_one, term_two)) in row_components
+				.iter_mut()
+				.zip(term_one.into_iter().zip(term_two))
+			{
+				*component = q_i * term_one + (F::ONE - q_i) * term_two;
+			}
+		}
+
+		let row_batch_coeffs =
+			MultilinearQuery::<F>::with_full_query(&self.row_batch_challenges)?.into_expansion();
+		let result = row_components
+			.into_iter()
+			.zip(row_batch_coeffs)
+			.map(|(component, coeff)| component * coeff)
+			.sum();
+		Ok(result)
+	}
+
+	fn binary_tower_level(&self) -> usize {
+		F::TOWER_LEVEL
+	}
+}
+
+/// Multiplies a tensor algebra element by $1 \otimes y$, given its row components.
+fn mul_by_column<F: TowerField>(row_components: &[F], y: F) -> Vec<F> {
+	let mut result = vec![F::ZERO; F::DEGREE];
+	for (v, &component) in row_components.iter().enumerate() {
+		if component == F::ZERO {
+			continue;
+		}
+		let basis = <F as ExtensionField<BinaryField1b>>::basis(v)
+			.expect("row_components has length equal to the extension degree");
+		for (result_v, bit) in result
+			.iter_mut()
+			.zip(ExtensionField::<BinaryField1b>::iter_bases(&(basis * y)))
+		{
+			if bit == BinaryField1b::ONE {
+				*result_v += component;
+			}
+		}
+	}
+	result
+}
+
+/// Computes $\sum_v c_v \cdot \phi_v(x)$ for the coefficients $c_v$.
+fn batch_bits<F: TowerField>(x: F, coeffs: &[F]) -> F {
+	ExtensionField::<BinaryField1b>::iter_bases(&x)
+		.zip(coeffs)
+		.filter(|(bit, _)| *bit == BinaryField1b::ONE)
+		.map(|(_, &coeff)| coeff)
+		.sum()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::protocols::test_utils::hypercube_evals_from_oracle;
+	use binius_field::{BinaryField128b, BinaryField32b, PackedBinaryField4x32b};
+	use rand::{rngs::StdRng, SeedableRng};
+	use std::iter::repeat_with;
+
+	fn random_poly<F: TowerField>(n_vars: usize) -> RingSwitchEqInd<F> {
+		let mut rng = StdRng::seed_from_u64(0);
+		let eval_point = repeat_with(|| F::random(&mut rng)).take(n_vars).collect();
+		let row_batch_challenges = repeat_with(|| F::random(&mut rng))
+			.take(F::DEGREE.ilog2() as usize)
+			.collect();
+		RingSwitchEqInd::new(eval_point, row_batch_challenges).unwrap()
+	}
+
+	fn test_consistency_help<F: TowerField>(n_vars: usize) {
+		let poly = random_poly::<F>(n_vars);
+		let multilin = poly.multilinear_extension::<F>().unwrap();
+		assert_eq!(hypercube_evals_from_oracle::<F>(&poly), multilin.evals());
+
+		let mut rng = StdRng::seed_from_u64(1);
+		let query = repeat_with(|| F::random(&mut rng))
+			.take(n_vars)
+			.collect::<Vec<_>>();
+		let multilin_query = MultilinearQuery::<F>::with_full_query(&query).unwrap();
+		assert_eq!(poly.evaluate(&query).unwrap(), multilin.evaluate(&multilin_query).unwrap());
+	}
+
+	#[test]
+	fn test_consistency_between_multilinear_extension_and_multivariate_poly() {
+		for n_vars in 0..5 {
+			test_consistency_help::<BinaryField32b>(n_vars);
+			test_consistency_help::<BinaryField128b>(n_vars);
+		}
+	}
+
+	#[test]
+	fn test_packed_multilinear_extension() {
+		let poly = random_poly::<BinaryField32b>(5);
+		let packed = poly
+			.multilinear_extension::<PackedBinaryField4x32b>()
+			.unwrap();
+		let expected = hypercube_evals_from_oracle::<BinaryField32b>(&poly);
+		for (index, &value) in expected.iter().enumerate() {
+			assert_eq!(packed.evaluate_on_hypercube(index).unwrap(), value);
+		}
+	}
+}
@@ -81,6 +81,12 @@ impl<F: Field> MultivariatePoly<F> for SelectRow {
 	fn binary_tower_level(&self) -> usize {
 		0
 	}
+
+	fn hypercube_evals(&self) -> Result<Vec<F>, Error> {
+		let mut evals = vec![F::ZERO; 1 << self.n_vars];
+		evals[self.index] = F::ONE;
+		Ok(evals)
+	}
 }
 
 #[cfg(test)]
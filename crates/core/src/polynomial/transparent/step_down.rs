@@ -16,6 +16,9 @@ use binius_utils::bail;
 /// ```
 ///
 /// This is useful for making constraints that are not enforced at the last rows of the trace
+///
+/// The boundary indices are accepted: index 0 gives the all-zero polynomial and index
+/// `1 << n_vars` gives the all-one polynomial.
 #[derive(Debug, Clone)]
 pub struct StepDown {
 	n_vars: usize,
@@ -24,10 +27,10 @@ pub struct StepDown {
 
 impl StepDown {
 	pub fn new(n_vars: usize, index: usize) -> Result<Self, Error> {
-		if index < 1 || index >= (1 << n_vars) {
+		if index > (1 << n_vars) {
 			bail!(Error::ArgumentRangeError {
 				arg: "index".into(),
-				range: 1..(1 << n_vars),
+				range: 0..(1 << n_vars) + 1,
 			})
 		} else {
 			Ok(Self { n_vars, index })
@@ -68,6 +71,9 @@ impl<F: Field> MultivariatePoly<F> for StepDown {
 		if query.len() != n_vars {
 			bail!(Error::IncorrectQuerySize { expected: n_vars });
 		}
+		if self.index == 1 << n_vars {
+			return Ok(F::ONE);
+		}
 		let mut k = self.index;
 
 		// `result` is the evaluation of the complimentary "step-up" function that is 0 at indices 0..self.index and 1
@@ -90,6 +96,12 @@ impl<F: Field> MultivariatePoly<F> for StepDown {
 	fn binary_tower_level(&self) -> usize {
 		0
 	}
+
+	fn hypercube_evals(&self) -> Result<Vec<F>, Error> {
+		let mut evals = vec![F::ZERO; 1 << self.n_vars];
+		evals[..self.index].fill(F::ONE);
+		Ok(evals)
+	}
 }
 
 #[cfg(test)]
@@ -183,10 +195,27 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn test_step_down_trace_boundary_indices() {
+		assert_eq!(stepdown_evals::<BinaryField1b>(0, 0), felts!(BinaryField1b[0]));
+		assert_eq!(stepdown_evals::<BinaryField1b>(0, 1), felts!(BinaryField1b[1]));
+		assert_eq!(stepdown_evals::<BinaryField1b>(2, 0), felts!(BinaryField1b[0, 0, 0, 0]));
+		assert_eq!(stepdown_evals::<BinaryField1b>(2, 4), felts!(BinaryField1b[1, 1, 1, 1]));
+		assert_eq!(
+			stepdown_evals::<PackedBinaryField128x1b>(9, 0),
+			packed_slice::<PackedBinaryField128x1b>(&[(0..512, 0)])
+		);
+		assert_eq!(
+			stepdown_evals::<PackedBinaryField128x1b>(9, 512),
+			packed_slice::<PackedBinaryField128x1b>(&[(0..512, 1)])
+		);
+		assert!(StepDown::new(2, 5).is_err());
+	}
+
 	#[test]
 	fn test_consistency_between_multilinear_extension_and_multilinear_poly_oracle() {
-		for n_vars in 1..5 {
-			for index in 1..(1 << n_vars) {
+		for n_vars in 0..5 {
+			for index in 0..=(1 << n_vars) {
 				let step_down = StepDown::new(n_vars, index).unwrap();
 				assert_eq!(
 					hypercube_evals_from_oracle::<BinaryField1b>(&step_down),
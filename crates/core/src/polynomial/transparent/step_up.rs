@@ -0,0 +1,167 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use crate::polynomial::{Error, MultilinearExtension, MultivariatePoly};
+use binius_field::{BinaryField1b, Field, PackedField};
+use binius_utils::bail;
+
+/// Represents a multilinear F2-polynomial whose evaluations over the hypercube are 0 until a
+/// specified index where they change to 1.
+///
+/// ```txt
+///     (1 << n_vars)
+/// <-------------------->
+/// 0,0 .. 0,0,1,1, .. 1,1
+///            ^
+///            index of first 1
+/// ```
+///
+/// This is the complement of [`super::step_down::StepDown`] and is useful for making constraints
+/// that are not enforced at the first rows of the trace. The boundary indices are accepted: index
+/// 0 gives the all-one polynomial and index `1 << n_vars` gives the all-zero polynomial.
+#[derive(Debug, Clone)]
+pub struct StepUp {
+	n_vars: usize,
+	index: usize,
+}
+
+impl StepUp {
+	pub fn new(n_vars: usize, index: usize) -> Result<Self, Error> {
+		if index > (1 << n_vars) {
+			bail!(Error::ArgumentRangeError {
+				arg: "index".into(),
+				range: 0..(1 << n_vars) + 1,
+			})
+		} else {
+			Ok(Self { n_vars, index })
+		}
+	}
+
+	pub fn multilinear_extension<P: PackedField<Scalar = BinaryField1b>>(
+		&self,
+	) -> Result<MultilinearExtension<P>, Error> {
+		if self.n_vars < P::LOG_WIDTH {
+			bail!(Error::PackedFieldNotFilled {
+				length: 1 << self.n_vars,
+				packed_width: 1 << P::LOG_WIDTH,
+			});
+		}
+		let log_packed_length = self.n_vars - P::LOG_WIDTH;
+		let packed_index = self.index / P::WIDTH;
+		let mut result = vec![P::zero(); 1 << log_packed_length];
+		if packed_index < result.len() {
+			result[packed_index + 1..].fill(P::one());
+			for i in self.index % P::WIDTH..P::WIDTH {
+				result[packed_index].set(i, P::Scalar::ONE);
+			}
+		}
+		MultilinearExtension::from_values(result)
+	}
+}
+
+impl<F: Field> MultivariatePoly<F> for StepUp {
+	fn degree(&self) -> usize {
+		self.n_vars
+	}
+
+	fn n_vars(&self) -> usize {
+		self.n_vars
+	}
+
+	fn evaluate(&self, query: &[F]) -> Result<F, Error> {
+		let n_vars = MultivariatePoly::<F>::n_vars(self);
+		if query.len() != n_vars {
+			bail!(Error::IncorrectQuerySize { expected: n_vars });
+		}
+		if self.index == 1 << n_vars {
+			return Ok(F::ZERO);
+		}
+		let mut k = self.index;
+
+		let mut result = F::ONE;
+		for q in query {
+			if k & 1 == 1 {
+				// interpolate a line that is 0 at 0 and `result` at 1, at the point q
+				result *= q;
+			} else {
+				// interpolate a line that is `result` at 0 and 1 at 1, and evaluate at q
+				result = result * (F::ONE - q) + q;
+			}
+			k >>= 1;
+		}
+
+		Ok(result)
+	}
+
+	fn binary_tower_level(&self) -> usize {
+		0
+	}
+
+	fn hypercube_evals(&self) -> Result<Vec<F>, Error> {
+		let mut evals = vec![F::ZERO; 1 << self.n_vars];
+		evals[self.index..].fill(F::ONE);
+		Ok(evals)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::StepUp;
+	use crate::protocols::test_utils::{hypercube_evals_from_oracle, macros::felts, packed_slice};
+	use binius_field::{
+		BinaryField1b, PackedBinaryField128x1b, PackedBinaryField256x1b, PackedField,
+	};
+
+	#[test]
+	fn test_step_up_trace_without_packing_simple_cases() {
+		assert_eq!(stepup_evals::<BinaryField1b>(2, 0), felts!(BinaryField1b[1, 1, 1, 1]));
+		assert_eq!(stepup_evals::<BinaryField1b>(2, 1), felts!(BinaryField1b[0, 1, 1, 1]));
+		assert_eq!(stepup_evals::<BinaryField1b>(2, 2), felts!(BinaryField1b[0, 0, 1, 1]));
+		assert_eq!(stepup_evals::<BinaryField1b>(2, 3), felts!(BinaryField1b[0, 0, 0, 1]));
+		assert_eq!(stepup_evals::<BinaryField1b>(2, 4), felts!(BinaryField1b[0, 0, 0, 0]));
+		assert!(StepUp::new(2, 5).is_err());
+	}
+
+	#[test]
+	fn test_step_up_trace_with_packing() {
+		assert_eq!(
+			stepup_evals::<PackedBinaryField128x1b>(9, 314),
+			packed_slice::<PackedBinaryField128x1b>(&[(0..314, 0), (314..512, 1)])
+		);
+		assert_eq!(
+			stepup_evals::<PackedBinaryField256x1b>(10, 555),
+			packed_slice::<PackedBinaryField256x1b>(&[(0..555, 0), (555..1024, 1)])
+		);
+		assert_eq!(
+			stepup_evals::<PackedBinaryField256x1b>(10, 1024),
+			packed_slice::<PackedBinaryField256x1b>(&[(0..1024, 0)])
+		);
+	}
+
+	#[test]
+	fn test_consistency_between_multilinear_extension_and_multilinear_poly_oracle() {
+		for n_vars in 0..5 {
+			for index in 0..=(1 << n_vars) {
+				let step_up = StepUp::new(n_vars, index).unwrap();
+				assert_eq!(
+					hypercube_evals_from_oracle::<BinaryField1b>(&step_up),
+					step_up
+						.multilinear_extension::<BinaryField1b>()
+						.unwrap()
+						.evals()
+				);
+			}
+		}
+	}
+
+	fn stepup_evals<P>(n_vars: usize, index: usize) -> Vec<P>
+	where
+		P: PackedField<Scalar = BinaryField1b>,
+	{
+		StepUp::new(n_vars, index)
+			.unwrap()
+			.multilinear_extension::<P>()
+			.unwrap()
+			.evals()
+			.to_vec()
+	}
+}
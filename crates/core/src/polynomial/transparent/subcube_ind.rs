@@ -0,0 +1,190 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use crate::polynomial::{Error, MultilinearExtension, MultivariatePoly};
+use binius_field::{Field, PackedField};
+use binius_utils::bail;
+use getset::CopyGetters;
+
+/// Represents the indicator of a subcube of the boolean hypercube, in which some variables are
+/// fixed to boolean values and the remaining variables are free.
+///
+/// ```txt
+///     n_vars = 3, X_1 fixed to 1
+///
+///     index:  0  1  2  3  4  5  6  7
+///     value:  0  0  1  1  0  0  1  1
+/// ```
+///
+/// The subcube is a coset of the subspace of $\mathbb{F}_2^n$ spanned by the free coordinates.
+/// The multilinear extension of its indicator is the product of $X_i$ or $1 - X_i$ over the fixed
+/// variables, depending on the value they are fixed to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, CopyGetters)]
+pub struct SubcubeIndicator {
+	#[getset(get_copy = "pub")]
+	n_vars: usize,
+	/// Bitmask of the fixed variables.
+	#[getset(get_copy = "pub")]
+	fixed_mask: usize,
+	/// Bitmask of the values of the fixed variables, a subset of `fixed_mask`.
+	#[getset(get_copy = "pub")]
+	fixed_values: usize,
+}
+
+impl SubcubeIndicator {
+	/// Constructs the indicator of the subcube where each variable index in `fixed_vars` is fixed
+	/// to the given value.
+	pub fn new(
+		n_vars: usize,
+		fixed_vars: impl IntoIterator<Item = (usize, bool)>,
+	) -> Result<Self, Error> {
+		if n_vars >= usize::BITS as usize {
+			bail!(Error::TooManyVariables);
+		}
+
+		let mut fixed_mask = 0;
+		let mut fixed_values = 0;
+		for (var, value) in fixed_vars {
+			if var >= n_vars {
+				bail!(Error::ArgumentRangeError {
+					arg: "fixed_vars".into(),
+					range: 0..n_vars,
+				});
+			}
+			fixed_mask |= 1 << var;
+			fixed_values = (fixed_values & !(1 << var)) | (usize::from(value) << var);
+		}
+
+		Ok(Self {
+			n_vars,
+			fixed_mask,
+			fixed_values,
+		})
+	}
+
+	/// The number of fixed variables, which is also the total degree of the indicator.
+	pub fn n_fixed_vars(&self) -> usize {
+		self.fixed_mask.count_ones() as usize
+	}
+
+	/// The value variable `var` is fixed to, or `None` if the variable is free.
+	pub fn fixed_value(&self, var: usize) -> Option<bool> {
+		(var < self.n_vars && (self.fixed_mask >> var) & 1 == 1)
+			.then(|| (self.fixed_values >> var) & 1 == 1)
+	}
+
+	/// Whether the hypercube vertex at `index` lies in the subcube.
+	pub fn contains(&self, index: usize) -> bool {
+		index >> self.n_vars == 0 && index & self.fixed_mask == self.fixed_values
+	}
+
+	pub fn multilinear_extension<P: PackedField>(&self) -> Result<MultilinearExtension<P>, Error> {
+		if self.n_vars < P::LOG_WIDTH {
+			bail!(Error::PackedFieldNotFilled {
+				length: 1 << self.n_vars,
+				packed_width: 1 << P::LOG_WIDTH,
+			});
+		}
+		let values = (0..1 << (self.n_vars - P::LOG_WIDTH))
+			.map(|i| {
+				P::from_fn(|j| {
+					if self.contains((i << P::LOG_WIDTH) | j) {
+						P::Scalar::ONE
+					} else {
+						P::Scalar::ZERO
+					}
+				})
+			})
+			.collect();
+		MultilinearExtension::from_values(values)
+	}
+}
+
+impl<F: Field> MultivariatePoly<F> for SubcubeIndicator {
+	fn degree(&self) -> usize {
+		self.n_fixed_vars()
+	}
+
+	fn n_vars(&self) -> usize {
+		self.n_vars
+	}
+
+	fn evaluate(&self, query: &[F]) -> Result<F, Error> {
+		if query.len() != self.n_vars {
+			bail!(Error::IncorrectQuerySize {
+				expected: self.n_vars,
+			});
+		}
+		let result = query
+			.iter()
+			.enumerate()
+			.filter_map(|(var, &q)| {
+				self.fixed_value(var)
+					.map(|value| if value { q } else { F::ONE - q })
+			})
+			.product();
+		Ok(result)
+	}
+
+	fn binary_tower_level(&self) -> usize {
+		0
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::protocols::test_utils::hypercube_evals_from_oracle;
+	use assert_matches::assert_matches;
+	use binius_field::{BinaryField128b, BinaryField1b, PackedBinaryField128x1b};
+
+	#[test]
+	fn test_subcube_indicator_evals() {
+		let indicator = SubcubeIndicator::new(3, [(1, true)]).unwrap();
+		let expected = [0u8, 0, 1, 1, 0, 0, 1, 1].map(BinaryField1b::from);
+		assert_eq!(
+			indicator
+				.multilinear_extension::<BinaryField1b>()
+				.unwrap()
+				.evals(),
+			expected
+		);
+		assert_eq!(indicator.fixed_value(1), Some(true));
+		assert_eq!(indicator.fixed_value(0), None);
+		assert_eq!(indicator.n_fixed_vars(), 1);
+	}
+
+	#[test]
+	fn test_consistency_between_multilinear_extension_and_multilinear_poly_oracle() {
+		for n_vars in 1..5 {
+			for fixed_mask in 0..(1 << n_vars) {
+				let fixed_vars = (0..n_vars)
+					.filter(|var| (fixed_mask >> var) & 1 == 1)
+					.map(|var| (var, var % 2 == 0));
+				let indicator = SubcubeIndicator::new(n_vars, fixed_vars).unwrap();
+				assert_eq!(
+					hypercube_evals_from_oracle::<BinaryField128b>(&indicator),
+					indicator
+						.multilinear_extension::<BinaryField128b>()
+						.unwrap()
+						.evals()
+				);
+			}
+		}
+	}
+
+	#[test]
+	fn test_packed_multilinear_extension() {
+		let indicator = SubcubeIndicator::new(9, [(0, false), (8, true)]).unwrap();
+		let packed = indicator
+			.multilinear_extension::<PackedBinaryField128x1b>()
+			.unwrap();
+		for index in 0..(1 << 9) {
+			let expected = BinaryField1b::from((index % 2 == 0 && index >= 256) as u8);
+			assert_eq!(packed.evaluate_on_hypercube(index).unwrap(), expected);
+		}
+		assert_matches!(
+			SubcubeIndicator::new(3, [(3, true)]),
+			Err(Error::ArgumentRangeError { .. })
+		);
+	}
+}
@@ -1,6 +1,6 @@
 // Copyright 2024 Ulvetanna Inc.
 
-use super::Error;
+use super::{Error, MultilinearPoly};
 use binius_field::{
 	as_packed_field::{PackScalar, PackedType},
 	underlier::{UnderlierType, WithUnderlier},
@@ -8,7 +8,11 @@ use binius_field::{
 };
 use binius_utils::bail;
 use rayon::prelude::*;
-use std::{cmp::max, marker::PhantomData, ops::Deref};
+use std::{
+	cmp::{max, min},
+	marker::PhantomData,
+	ops::Deref,
+};
 
 /// Tensor Product expansion of values with partial eq indicator evaluated at extra_query_coordinates
 ///
@@ -76,6 +80,92 @@ pub fn tensor_prod_eq_ind<P: PackedField>(
 	Ok(())
 }
 
+/// Checks that a tensor product expansion has `max(1, 2^n_vars / P::WIDTH)` packed elements, which
+/// is the length of the expansion of a query with `n_vars` variables.
+pub(crate) fn check_expansion_len<P: PackedField>(
+	n_vars: usize,
+	expansion: &[P],
+) -> Result<(), Error> {
+	if expansion.len() != max(1, (1 << n_vars) / P::WIDTH) {
+		bail!(Error::InvalidPackedValuesLength);
+	}
+	Ok(())
+}
+
+/// Base-2 logarithm of the number of scalars processed by one task in
+/// [`fill_linear_combination`].
+const LINEAR_COMBINATION_LOG_CHUNK_SIZE: usize = 12;
+
+/// Evaluates a linear combination of multilinears over the boolean hypercube.
+///
+/// Writes $c + \sum_i a_i m_i(v)$ to `out` for every vertex $v$, where $c$ is `offset` and the
+/// $(m_i, a_i)$ pairs are given by `inner`. The output is computed in a single parallel pass over
+/// memory: each task fills a chunk of `out` from subcube evaluations of every inner multilinear,
+/// so the output chunk stays in cache while the terms are accumulated.
+///
+/// Requires `out.len()` to equal `max(1, 2^n_vars / P::WIDTH)` and every inner multilinear to
+/// have `n_vars` variables.
+pub fn fill_linear_combination<P, M>(
+	n_vars: usize,
+	offset: P::Scalar,
+	inner: &[(M, P::Scalar)],
+	out: &mut [P],
+) -> Result<(), Error>
+where
+	P: PackedField,
+	M: MultilinearPoly<P> + Sync,
+{
+	if out.len() != max(1, (1 << n_vars) / P::WIDTH) {
+		bail!(Error::InvalidPackedValuesLength);
+	}
+	for (multilin, _) in inner {
+		if multilin.n_vars() != n_vars {
+			bail!(Error::IncorrectNumberOfVariables {
+				expected: n_vars,
+				actual: multilin.n_vars(),
+			});
+		}
+	}
+
+	if n_vars < P::LOG_WIDTH {
+		for i in 0..1 << n_vars {
+			let mut value = offset;
+			for (multilin, coeff) in inner {
+				value += multilin.evaluate_on_hypercube_and_scale(i, *coeff)?;
+			}
+			out[0].set(i, value);
+		}
+		return Ok(());
+	}
+
+	let log_chunk_size = min(n_vars, max(P::LOG_WIDTH, LINEAR_COMBINATION_LOG_CHUNK_SIZE));
+	let packed_chunk_size = 1 << (log_chunk_size - P::LOG_WIDTH);
+	let packed_offset = P::broadcast(offset);
+
+	out.par_chunks_mut(packed_chunk_size)
+		.enumerate()
+		.try_for_each_init(
+			|| vec![P::zero(); packed_chunk_size],
+			|scratch, (chunk_index, out_chunk)| -> Result<_, Error> {
+				out_chunk.fill(packed_offset);
+				for (multilin, coeff) in inner {
+					multilin.subcube_evals(log_chunk_size, chunk_index, scratch)?;
+					if *coeff == P::Scalar::ONE {
+						for (out_i, &eval_i) in out_chunk.iter_mut().zip(scratch.iter()) {
+							*out_i += eval_i;
+						}
+					} else {
+						let packed_coeff = P::broadcast(*coeff);
+						for (out_i, &eval_i) in out_chunk.iter_mut().zip(scratch.iter()) {
+							*out_i += eval_i * packed_coeff;
+						}
+					}
+				}
+				Ok(())
+			},
+		)
+}
+
 /// A wrapper for containers of underlier types that dereferences as packed field slices.
 #[derive(Debug, Clone)]
 pub struct PackingDeref<U, F, Data>(Data, PhantomData<F>)
@@ -112,11 +202,11 @@ mod tests {
 
 	use crate::polynomial::{
 		multilinear_query::MultilinearQuery, transparent::eq_ind::EqIndPartialEval,
-		MultilinearExtension, MultivariatePoly,
+		MultilinearExtension, MultilinearPoly, MultivariatePoly,
 	};
-	use binius_field::{BinaryField32b, Field, PackedField, TowerField};
+	use binius_field::{BinaryField32b, Field, PackedBinaryField4x32b, PackedField, TowerField};
 
-	use super::tensor_prod_eq_ind;
+	use super::{fill_linear_combination, tensor_prod_eq_ind};
 
 	fn get_expected_eval<F, P>(
 		log_n_values: usize,
@@ -238,4 +328,36 @@ mod tests {
 			}
 		}
 	}
+
+	#[test]
+	fn test_fill_linear_combination_matches_naive() {
+		type P = PackedBinaryField4x32b;
+		type F = BinaryField32b;
+		let mut rng = StdRng::seed_from_u64(0);
+
+		for n_vars in [2, 3, 5, 13] {
+			let inner = repeat_with(|| {
+				let values = repeat_with(|| P::random(&mut rng))
+					.take((1 << n_vars) / P::WIDTH)
+					.collect::<Vec<_>>();
+				let multilin = MultilinearExtension::from_values(values)
+					.unwrap()
+					.specialize::<P>();
+				(multilin, F::random(&mut rng))
+			})
+			.take(3)
+			.collect::<Vec<_>>();
+			let offset = F::random(&mut rng);
+
+			let mut out = vec![P::zero(); (1 << n_vars) / P::WIDTH];
+			fill_linear_combination(n_vars, offset, &inner, &mut out).unwrap();
+
+			for i in 0..1 << n_vars {
+				let expected = inner.iter().fold(offset, |acc, (multilin, coeff)| {
+					acc + multilin.evaluate_on_hypercube(i).unwrap() * coeff
+				});
+				assert_eq!(out[i / P::WIDTH].get(i % P::WIDTH), expected);
+			}
+		}
+	}
 }
@@ -9,7 +9,7 @@ use crate::{
 use auto_impl::auto_impl;
 use binius_field::{Field, PackedField};
 use binius_utils::bail;
-use std::hash::Hash;
+use std::{hash::Hash, mem};
 
 #[derive(Debug, Clone)]
 pub struct AbstractSumcheckRound<F> {
@@ -239,3 +239,51 @@ pub fn standard_switchover_heuristic(k: isize) -> impl Fn(usize) -> usize + Copy
 		switchover_round.max(1) as usize
 	}
 }
+
+/// The round in which the sumcheck provers switch over from evaluating the small field
+/// multilinears with a tensor product query to storing their folded large field evaluations.
+///
+/// Switching over later saves the memory of the folded multilinears, at the cost of the inner
+/// products with the tensor product query, which double in length every round until the
+/// switchover.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwitchoverPolicy {
+	/// The round of [`standard_switchover_heuristic`] with the offset `k`.
+	Standard { k: isize },
+	/// A fixed round, regardless of the extension degree.
+	AfterRounds(usize),
+}
+
+impl Default for SwitchoverPolicy {
+	fn default() -> Self {
+		Self::Standard { k: -2 }
+	}
+}
+
+impl SwitchoverPolicy {
+	/// Computes the earliest switchover after which a folded multilinear with `n_vars` variables
+	/// over the large field `F` takes at most `max_folded_bytes` bytes.
+	///
+	/// Multilinears that are small enough are switched over after the first round.
+	pub fn with_memory_budget<F: Field>(n_vars: usize, max_folded_bytes: usize) -> Self {
+		let folded_bytes = |round: usize| mem::size_of::<F>() << (n_vars - round);
+		let round = (1..n_vars)
+			.find(|&round| folded_bytes(round) <= max_folded_bytes)
+			.unwrap_or(n_vars);
+		Self::AfterRounds(round.max(1))
+	}
+
+	/// The switchover round of a multilinear whose evaluations are in a subfield of the given
+	/// extension degree of the large field.
+	pub fn switchover_round(&self, extension_degree: usize) -> usize {
+		match *self {
+			Self::Standard { k } => standard_switchover_heuristic(k)(extension_degree),
+			Self::AfterRounds(round) => round.max(1),
+		}
+	}
+
+	/// The switchover function taken by the sumcheck prover constructors.
+	pub fn switchover_fn(self) -> impl Fn(usize) -> usize + Copy {
+		move |extension_degree| self.switchover_round(extension_degree)
+	}
+}
@@ -233,10 +233,6 @@ where
 			..
 		} = self;
 
-		// Partial query for folding
-		let single_variable_partial_query =
-			MultilinearQuery::with_full_query(&[prev_rd_challenge])?;
-
 		// Perform switchover and/or folding
 		let any_transparent_left = multilinears
 			.par_iter_mut()
@@ -272,8 +268,9 @@ where
 						ref mut large_field_folded_multilinear,
 					} => {
 						// Post-switchover, simply halve large field MLE.
-						*large_field_folded_multilinear = large_field_folded_multilinear
-							.evaluate_partial_low(&single_variable_partial_query)?;
+						large_field_folded_multilinear
+							.as_mut()
+							.partial_evaluate_low_in_place(&[prev_rd_challenge])?;
 
 						Ok(None)
 					}
@@ -3,6 +3,7 @@
 use crate::{
 	oracle::{BatchId, CommittedId, CompositePolyOracle, Error as OracleError, OracleId},
 	polynomial::Error as PolynomialError,
+	protocols::{ring_switch::Error as RingSwitchError, sumcheck_v2::Error as SumcheckError},
 };
 use binius_field::Field;
 
@@ -20,6 +21,8 @@ pub enum Error {
 	ConflictingEvals(BatchId),
 	#[error("missing evaluation in batch {0}")]
 	MissingEvals(BatchId),
+	#[error("batch {0} does not consist of 1-bit multilinears and cannot be ring-switched")]
+	RingSwitchNonBinaryBatch(BatchId),
 	#[error("oracle error: {0}")]
 	Oracle(#[from] OracleError),
 	#[error("polynomial error: {0}")]
@@ -28,6 +31,10 @@ pub enum Error {
 	Verification(#[from] VerificationError),
 	#[error("witness error: {0}")]
 	Witness(#[from] crate::witness::Error),
+	#[error("ring switching error: {0}")]
+	RingSwitch(#[from] RingSwitchError),
+	#[error("sumcheck error: {0}")]
+	Sumcheck(#[from] SumcheckError),
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -38,6 +45,10 @@ pub enum VerificationError {
 	IncorrectCompositePolyEvaluation(String),
 	#[error("subproof type or shape does not match the claim")]
 	SubproofMismatch,
+	#[error("the number of ring-switching proofs does not match the number of claims")]
+	RingSwitchProofMismatch,
+	#[error("expected {expected} evaluations of shared committed oracles")]
+	SharedColumnEvalsMismatch { expected: usize },
 }
 
 impl VerificationError {
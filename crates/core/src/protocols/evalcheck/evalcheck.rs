@@ -1,8 +1,9 @@
 // Copyright 2023 Ulvetanna Inc.
 
 use super::error::Error;
-use crate::oracle::{
-	BatchId, CommittedBatch, CommittedId, CompositePolyOracle, MultilinearPolyOracle,
+use crate::{
+	oracle::{BatchId, CommittedBatch, CommittedId, CompositePolyOracle, MultilinearPolyOracle},
+	protocols::{ring_switch::RingSwitchProof, sumcheck_v2},
 };
 use binius_field::Field;
 use binius_utils::bail;
@@ -32,6 +33,12 @@ pub struct EvalcheckMultilinearClaim<F: Field> {
 	pub is_random_point: bool,
 }
 
+/// The proof of an evalcheck claim.
+///
+/// The proof only holds the evaluations of the inner polynomials that the verifier cannot derive
+/// itself. The evaluation points of the claims and subclaims are not part of the proof: the
+/// verifier derives them from the points of the claims it verifies, which come from the
+/// challenges of the sumchecks it has already verified.
 #[derive(Debug)]
 pub enum EvalcheckProof<F: Field> {
 	Transparent,
@@ -57,6 +64,16 @@ pub enum EvalcheckProof<F: Field> {
 	ZeroPadded(F, Box<EvalcheckProof<F>>),
 }
 
+/// The proof of the ring-switching reduction of the committed evaluation claims on a batch of
+/// 1-bit multilinears.
+#[derive(Debug, Clone)]
+pub struct RingSwitchBatchProof<F: Field> {
+	/// The ring-switching proofs, one per claim in the order they were accumulated
+	pub ring_switch_proofs: Vec<RingSwitchProof<F>>,
+	/// The proof of the batched sumcheck over the packed multilinears
+	pub sumcheck_proof: sumcheck_v2::Proof<F>,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct CommittedEvalClaim<F: Field> {
 	pub id: CommittedId,
@@ -164,6 +181,15 @@ impl<F: Field> BatchCommittedEvalClaims<F> {
 		Ok(Some(SameQueryPcsClaim { eval_point, evals }))
 	}
 
+	/// The number of claims accumulated for a batch so far.
+	pub fn n_claims(&self, batch_id: BatchId) -> Result<usize, Error> {
+		let claims = self
+			.claims_by_batch
+			.get(batch_id)
+			.ok_or(Error::UnknownBatchId(batch_id))?;
+		Ok(claims.len())
+	}
+
 	/// Take out potentially non-same-query claims of a batch for additional processing - one example
 	/// would be an extra sumcheck round to convert non-same-query claims into same query claims
 	pub fn take_claims(&mut self, batch_id: BatchId) -> Result<Vec<CommittedEvalClaim<F>>, Error> {
@@ -9,6 +9,7 @@ mod error;
 #[allow(clippy::module_inception)]
 mod evalcheck;
 mod prove;
+mod shared;
 pub mod subclaims;
 #[cfg(test)]
 mod tests;
@@ -17,4 +18,5 @@ mod verify;
 pub use error::*;
 pub use evalcheck::*;
 pub use prove::*;
+pub use shared::*;
 pub use verify::*;
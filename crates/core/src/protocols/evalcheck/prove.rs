@@ -4,7 +4,7 @@ use super::{
 	error::Error,
 	evalcheck::{
 		BatchCommittedEvalClaims, CommittedEvalClaim, EvalcheckClaim, EvalcheckMultilinearClaim,
-		EvalcheckProof,
+		EvalcheckProof, RingSwitchBatchProof,
 	},
 	subclaims::{
 		packed_sumcheck_meta, packed_sumcheck_witness, projected_bivariate_claim,
@@ -13,15 +13,24 @@ use super::{
 	},
 };
 use crate::{
-	oracle::{MultilinearOracleSet, MultilinearPolyOracle, ProjectionVariant, ShiftVariant},
+	challenger::{CanObserve, CanSample},
+	oracle::{
+		BatchId, MultilinearOracleSet, MultilinearPolyOracle, ProjectionVariant, ShiftVariant,
+	},
+	polynomial::{composition::BivariateProduct, hypercube_vertex_index, EvaluationDomainFactory},
+	protocols::{
+		ring_switch::{self, RingSwitchProveOutput},
+		sumcheck_v2::{self, prove::RegularSumcheckProver, CompositeSumClaim},
+	},
 	witness::MultilinearExtensionIndex,
 };
 use binius_field::{
-	as_packed_field::PackScalar, underlier::WithUnderlier, PackedField, PackedFieldIndexable,
-	TowerField,
+	as_packed_field::PackScalar, underlier::WithUnderlier, BinaryField1b, ExtensionField, Field,
+	PackedExtension, PackedField, PackedFieldIndexable, TowerField,
 };
+use binius_utils::bail;
 use getset::{Getters, MutGetters};
-use std::mem;
+use std::{iter, mem};
 use tracing::instrument;
 
 /// A mutable prover state.
@@ -47,7 +56,9 @@ where
 
 	#[get = "pub"]
 	new_sumchecks: Vec<BivariateSumcheck<'b, F, PW>>,
-	memoized_queries: MemoizedQueries<PW>,
+	/// Expansions of the evaluation points, which may be shared with other provers.
+	#[getset(get_mut = "pub")]
+	pub(crate) memoized_queries: MemoizedQueries<PW>,
 }
 
 impl<'a, 'b, F, PW> EvalcheckProver<'a, 'b, F, PW>
@@ -314,6 +325,17 @@ where
 
 				EvalcheckProof::ZeroPadded(eval, Box::new(subproof))
 			}
+
+			BitReversed { inner, .. } => {
+				let subclaim = EvalcheckMultilinearClaim {
+					poly: *inner,
+					eval_point: eval_point.into_iter().rev().collect(),
+					eval,
+					is_random_point,
+				};
+
+				self.prove_multilinear(subclaim)?
+			}
 		};
 
 		Ok(proof)
@@ -326,12 +348,33 @@ where
 		wf_eval_point: &[PW::Scalar],
 		is_random_point: bool,
 	) -> Result<(F, EvalcheckProof<F>), Error> {
-		let eval_query = self.memoized_queries.full_query(wf_eval_point)?;
+		// The witness of a bit-reversed oracle is the stored data of its inner oracle, which is
+		// evaluated at the reversed point.
+		if let MultilinearPolyOracle::BitReversed { inner, .. } = poly {
+			let inner_eval_point = eval_point.iter().rev().copied().collect::<Vec<_>>();
+			let wf_inner_eval_point = wf_eval_point.iter().rev().copied().collect::<Vec<_>>();
+			return self.eval_and_proof(
+				*inner,
+				&inner_eval_point,
+				&wf_inner_eval_point,
+				is_random_point,
+			);
+		}
+
 		let witness_poly = self
 			.witness_index
 			.get_multilin_poly(poly.id())
 			.map_err(Error::Witness)?;
-		let eval = witness_poly.evaluate(eval_query)?.into();
+		// Points with boolean coordinates, which are common after projections, select a single
+		// hypercube evaluation and do not need the tensor expansion of the query.
+		let eval = match hypercube_vertex_index(wf_eval_point) {
+			Some(index) => witness_poly.evaluate_on_hypercube(index)?,
+			None => {
+				let eval_query = self.memoized_queries.full_query(wf_eval_point)?;
+				witness_poly.inner_prod_with_expansion(eval_query.expansion())?
+			}
+		}
+		.into();
 		let subclaim = EvalcheckMultilinearClaim {
 			poly,
 			eval_point: eval_point.to_vec(),
@@ -342,3 +385,82 @@ where
 		Ok((eval, subproof))
 	}
 }
+
+impl<'a, 'b, F, PW> EvalcheckProver<'a, 'b, F, PW>
+where
+	F: TowerField,
+	PW: PackedFieldIndexable<Scalar = F> + WithUnderlier,
+	PW::Underlier: PackScalar<F, Packed = PW> + PackScalar<BinaryField1b>,
+{
+	/// Prove the committed evaluation claims on a batch of 1-bit multilinears with ring-switching.
+	///
+	/// The claims accumulated for the batch are taken out of [`BatchCommittedEvalClaims`] and each
+	/// is reduced with [`ring_switch::prove`]. A batched sumcheck then reduces them to evaluation
+	/// claims on the packed multilinears at a common point, which are inserted back under the
+	/// same committed ids. The batch must therefore be committed with a PCS over `F`, as the
+	/// packing of its 1-bit multilinears into `F` elements.
+	pub fn prove_ring_switch<DomainField, Challenger>(
+		&mut self,
+		batch_id: BatchId,
+		domain_factory: impl EvaluationDomainFactory<DomainField>,
+		switchover_fn: impl Fn(usize) -> usize + Clone,
+		mut challenger: Challenger,
+	) -> Result<RingSwitchBatchProof<F>, Error>
+	where
+		F: ExtensionField<DomainField>,
+		PW: PackedExtension<DomainField>,
+		DomainField: Field,
+		Challenger: CanObserve<F> + CanSample<F>,
+	{
+		// Validate before taking the claims out, so that they are kept on error
+		if self.batch_committed_eval_claims.n_claims(batch_id)? == 0 {
+			bail!(Error::EmptyBatch(batch_id));
+		}
+		if self.oracles.committed_batch(batch_id).tower_level != 0 {
+			bail!(Error::RingSwitchNonBinaryBatch(batch_id));
+		}
+		let claims = self.batch_committed_eval_claims.take_claims(batch_id)?;
+
+		let witnesses = claims
+			.iter()
+			.map(|claim| {
+				let oracle_id = self.oracles.committed_oracle_id(claim.id);
+				self.witness_index.get::<BinaryField1b>(oracle_id)
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+
+		let mut ring_switch_proofs = Vec::with_capacity(claims.len());
+		let mut reduced_claims = Vec::with_capacity(claims.len());
+		let mut provers = Vec::with_capacity(claims.len());
+		for (claim, witness) in iter::zip(&claims, &witnesses) {
+			let RingSwitchProveOutput {
+				proof,
+				reduced_claim,
+				multilinears,
+			} = ring_switch::prove::<PW::Underlier, F, _>(claim, witness.evals(), &mut challenger)?;
+			let prover = RegularSumcheckProver::<DomainField, _, _, _>::new(
+				multilinears,
+				[CompositeSumClaim {
+					composition: BivariateProduct,
+					sum: reduced_claim.sum(),
+				}],
+				domain_factory.clone(),
+				switchover_fn.clone(),
+			)?;
+			ring_switch_proofs.push(proof);
+			reduced_claims.push(reduced_claim);
+			provers.push(prover);
+		}
+
+		let (sumcheck_output, sumcheck_proof) =
+			sumcheck_v2::prove::batch_prove(provers, &mut challenger)?;
+		for claim in ring_switch::verify_sumcheck_outputs(&reduced_claims, sumcheck_output)? {
+			self.batch_committed_eval_claims.insert(claim);
+		}
+
+		Ok(RingSwitchBatchProof {
+			ring_switch_proofs,
+			sumcheck_proof,
+		})
+	}
+}
@@ -0,0 +1,112 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Equality links between committed oracles that share their data.
+//!
+//! A committed oracle added with [`MultilinearOracleSet::add_committed_shared`] is committed in
+//! its own batch, so nothing ties it to its source oracle until the statement proves that the two
+//! multilinears are equal. Both are evaluated at one random point, and the claims that they take
+//! the same value there are reduced by evalcheck to the polynomial commitments. Two distinct
+//! multilinears in `n_vars` variables agree on a random point with probability at most
+//! `n_vars / |F|`.
+
+use super::{error::Error, evalcheck::EvalcheckClaim, VerificationError};
+use crate::{
+	challenger::{CanObserve, CanSample},
+	oracle::{MultilinearOracleSet, OracleId},
+	polynomial::MultilinearQuery,
+	witness::MultilinearExtensionIndex,
+};
+use binius_field::{
+	as_packed_field::PackScalar, underlier::WithUnderlier, PackedField, TowerField,
+};
+use binius_utils::bail;
+
+/// The proof of the equality links of the shared committed oracles.
+#[derive(Debug, Clone, Default)]
+pub struct SharedColumnsProof<F> {
+	/// The common evaluation of each shared oracle and its source, in the order of
+	/// [`MultilinearOracleSet::shared_committed`].
+	pub evals: Vec<F>,
+}
+
+/// Proves that every oracle added with [`MultilinearOracleSet::add_committed_shared`] is equal to
+/// its source oracle.
+///
+/// This must be called after the commitments of both batches have been observed by the
+/// challenger. Returns the proof and two evalcheck claims per shared oracle, one on the shared
+/// oracle and one on its source, which the caller proves along with the other evalcheck claims.
+pub fn prove_shared_columns<F, PW, Challenger>(
+	oracles: &MultilinearOracleSet<F>,
+	witness_index: &MultilinearExtensionIndex<PW::Underlier, PW::Scalar>,
+	challenger: &mut Challenger,
+) -> Result<(SharedColumnsProof<F>, Vec<EvalcheckClaim<F>>), Error>
+where
+	F: TowerField + From<PW::Scalar>,
+	PW: PackedField + WithUnderlier,
+	PW::Scalar: From<F>,
+	PW::Underlier: PackScalar<PW::Scalar, Packed = PW>,
+	Challenger: CanObserve<F> + CanSample<F>,
+{
+	let mut proof = SharedColumnsProof::default();
+	let mut claims = Vec::new();
+	for (id, source_id) in oracles.shared_committed() {
+		let eval_point: Vec<F> = challenger.sample_vec(oracles.n_vars(source_id));
+		let query = MultilinearQuery::<PW>::with_full_query(
+			&eval_point
+				.iter()
+				.copied()
+				.map(PW::Scalar::from)
+				.collect::<Vec<_>>(),
+		)?;
+		let eval = F::from(
+			witness_index
+				.get_multilin_poly(source_id)?
+				.evaluate(&query)?,
+		);
+		challenger.observe(eval);
+
+		proof.evals.push(eval);
+		claims.extend(equality_claims(oracles, id, source_id, eval_point, eval));
+	}
+	Ok((proof, claims))
+}
+
+/// Verifies the proof of [`prove_shared_columns`] and returns the same evalcheck claims as the
+/// prover.
+pub fn verify_shared_columns<F, Challenger>(
+	oracles: &MultilinearOracleSet<F>,
+	proof: SharedColumnsProof<F>,
+	challenger: &mut Challenger,
+) -> Result<Vec<EvalcheckClaim<F>>, Error>
+where
+	F: TowerField,
+	Challenger: CanObserve<F> + CanSample<F>,
+{
+	let n_shared = oracles.shared_committed().count();
+	if proof.evals.len() != n_shared {
+		bail!(VerificationError::SharedColumnEvalsMismatch { expected: n_shared });
+	}
+
+	let mut claims = Vec::with_capacity(2 * n_shared);
+	for ((id, source_id), eval) in oracles.shared_committed().zip(proof.evals) {
+		let eval_point: Vec<F> = challenger.sample_vec(oracles.n_vars(source_id));
+		challenger.observe(eval);
+		claims.extend(equality_claims(oracles, id, source_id, eval_point, eval));
+	}
+	Ok(claims)
+}
+
+fn equality_claims<F: TowerField>(
+	oracles: &MultilinearOracleSet<F>,
+	id: OracleId,
+	source_id: OracleId,
+	eval_point: Vec<F>,
+	eval: F,
+) -> [EvalcheckClaim<F>; 2] {
+	[source_id, id].map(|oracle_id| EvalcheckClaim {
+		poly: oracles.oracle(oracle_id).into_composite(),
+		eval_point: eval_point.clone(),
+		eval,
+		is_random_point: true,
+	})
+}
@@ -24,7 +24,7 @@ use crate::{
 		transparent::{
 			eq_ind::EqIndPartialEval, shift_ind::ShiftIndPartialEval, tower_basis::TowerBasis,
 		},
-		MultilinearComposite, MultilinearPoly, MultilinearQuery, MultivariatePoly,
+		MultilinearComposite, MultilinearPoly, MultilinearQueryRef, MultivariatePoly,
 	},
 	protocols::sumcheck::SumcheckClaim,
 	witness::{MultilinearExtensionIndex, MultilinearWitness},
@@ -97,7 +97,7 @@ where
 		memoized_queries,
 		meta,
 		wf_eval_point,
-		|projected_eval_point| {
+		|projected_eval_point, _| {
 			let shift_ind = ShiftIndPartialEval::new(
 				projected_eval_point.len(),
 				shifted.shift_offset(),
@@ -167,7 +167,7 @@ where
 		memoized_queries,
 		meta,
 		wf_eval_point,
-		|_projected_eval_point| {
+		|_projected_eval_point, _| {
 			let tower_basis = TowerBasis::new(log_degree, binary_tower_level)?;
 			Ok(tower_basis
 				.multilinear_extension::<PW>()?
@@ -288,11 +288,10 @@ where
 		memoized_queries,
 		meta.projected_bivariate_meta,
 		&wf_eval_point,
-		|projected_eval_point| {
-			let eq_ind =
-				EqIndPartialEval::new(projected_eval_point.len(), projected_eval_point.to_vec())?;
-
-			Ok(eq_ind.multilinear_extension::<PW>()?.specialize_arc_dyn())
+		|projected_eval_point, memoized_queries| {
+			// The expansion at the projected point is shared with any evaluation at that point.
+			let query = memoized_queries.full_query(projected_eval_point)?;
+			Ok(query.eq_ind_multilinear()?.specialize_arc_dyn())
 		},
 	)
 }
@@ -380,10 +379,7 @@ pub fn projected_bivariate_claim<F: TowerField>(
 	let product =
 		CompositePolyOracle::new(multiplier.n_vars(), vec![inner, multiplier], BivariateProduct)?;
 
-	let sumcheck_claim = SumcheckClaim {
-		poly: product,
-		sum: eval,
-	};
+	let sumcheck_claim = SumcheckClaim::new(product, eval)?;
 
 	Ok(sumcheck_claim)
 }
@@ -393,7 +389,10 @@ fn projected_bivariate_witness<'a, PW>(
 	memoized_queries: &mut MemoizedQueries<PW>,
 	meta: ProjectedBivariateMeta,
 	wf_eval_point: &[PW::Scalar],
-	multiplier_witness_ctr: impl FnOnce(&[PW::Scalar]) -> Result<MultilinearWitness<'a, PW>, Error>,
+	multiplier_witness_ctr: impl FnOnce(
+		&[PW::Scalar],
+		&mut MemoizedQueries<PW>,
+	) -> Result<MultilinearWitness<'a, PW>, Error>,
 ) -> Result<BivariateSumcheckWitness<'a, PW>, Error>
 where
 	PW: PackedField + WithUnderlier,
@@ -414,7 +413,7 @@ where
 		let query = memoized_queries.full_query(&wf_eval_point[projected_n_vars..])?;
 		// upcast_arc_dyn() doesn't compile, but an explicit Arc::new() does compile. Beats me.
 		let projected: Arc<dyn MultilinearPoly<PW> + Send + Sync> =
-			Arc::new(inner_multilin.evaluate_partial_high(query)?);
+			Arc::new(inner_multilin.evaluate_partial_high(&query)?);
 		witness_index.update_multilin_poly(vec![(projected_id, projected.clone())])?;
 
 		(projected, &wf_eval_point[..projected_n_vars])
@@ -425,14 +424,11 @@ where
 	if !witness_index.has(multiplier_id) {
 		witness_index.update_multilin_poly(vec![(
 			multiplier_id,
-			multiplier_witness_ctr(projected_eval_point)?,
+			multiplier_witness_ctr(projected_eval_point, memoized_queries)?,
 		)])?;
 	}
 
-	let multiplier_multilin = witness_index
-		.get_multilin_poly(multiplier_id)
-		.expect("multilinear forcibly created if absent")
-		.clone();
+	let multiplier_multilin = witness_index.get_multilin_poly(multiplier_id)?;
 
 	let witness = MultilinearComposite::new(
 		multiplier_multilin.n_vars(),
@@ -443,8 +439,12 @@ where
 	Ok(witness)
 }
 
+/// A cache of tensor product expansions, keyed by evaluation point.
+///
+/// The cached expansions are shared, so the consumers of an expansion, e.g. the evalcheck prover
+/// and the witnesses of the bivariate sumchecks it creates, hold it without copying.
 pub struct MemoizedQueries<P: PackedField> {
-	memo: Vec<(Vec<P::Scalar>, MultilinearQuery<P>)>,
+	memo: Vec<Arc<MultilinearQueryRef<P>>>,
 }
 
 impl<P: PackedField> MemoizedQueries<P> {
@@ -453,27 +453,41 @@ impl<P: PackedField> MemoizedQueries<P> {
 		Self { memo: Vec::new() }
 	}
 
-	pub fn full_query(&mut self, eval_point: &[P::Scalar]) -> Result<&MultilinearQuery<P>, Error> {
-		if let Some(index) = self
-			.memo
-			.iter()
-			.position(|(memo_eval_point, _)| memo_eval_point.as_slice() == eval_point)
-		{
-			let (_, ref query) = &self.memo[index];
+	/// Returns the expansion at `eval_point`, computing it if it is not cached yet.
+	pub fn full_query(
+		&mut self,
+		eval_point: &[P::Scalar],
+	) -> Result<Arc<MultilinearQueryRef<P>>, Error> {
+		if let Some(query) = self.get(eval_point) {
 			return Ok(query);
 		}
 
-		let wf_query = MultilinearQuery::with_full_query(eval_point)?;
-		self.memo.push((eval_point.to_vec(), wf_query));
-
-		let (_, ref query) = self.memo.last().expect("pushed query immediately above");
+		let query = MultilinearQueryRef::new(eval_point)?;
+		self.memo.push(query.clone());
 		Ok(query)
 	}
+
+	/// Returns the cached expansion at `eval_point`, if any.
+	pub fn get(&self, eval_point: &[P::Scalar]) -> Option<Arc<MultilinearQueryRef<P>>> {
+		self.memo
+			.iter()
+			.find(|query| query.eval_point() == eval_point)
+			.cloned()
+	}
+
+	/// Caches an expansion that was computed elsewhere, so that it is reused rather than
+	/// recomputed. An expansion at a point that is already cached is ignored.
+	pub fn insert(&mut self, query: Arc<MultilinearQueryRef<P>>) {
+		if self.get(query.eval_point()).is_none() {
+			self.memo.push(query);
+		}
+	}
 }
 
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use binius_field::{BinaryField32b, PackedBinaryField4x32b};
 
 	#[test]
 	fn test_compute_common_suffix_len() {
@@ -493,4 +507,23 @@ mod tests {
 			assert_eq!(got, expected);
 		}
 	}
+
+	#[test]
+	fn test_memoized_queries_share_expansions() {
+		let eval_point = [3, 5, 7].map(BinaryField32b::new);
+		let mut memoized_queries = MemoizedQueries::<PackedBinaryField4x32b>::new();
+		assert!(memoized_queries.get(&eval_point).is_none());
+
+		let query = memoized_queries.full_query(&eval_point).unwrap();
+		assert!(Arc::ptr_eq(&query, &memoized_queries.full_query(&eval_point).unwrap()));
+
+		// An expansion computed elsewhere is reused rather than recomputed.
+		let other_point = [11, 13].map(BinaryField32b::new);
+		let other = MultilinearQueryRef::new(&other_point).unwrap();
+		memoized_queries.insert(other.clone());
+		assert!(Arc::ptr_eq(&other, &memoized_queries.full_query(&other_point).unwrap()));
+
+		memoized_queries.insert(MultilinearQueryRef::new(&eval_point).unwrap());
+		assert!(Arc::ptr_eq(&query, &memoized_queries.get(&eval_point).unwrap()));
+	}
 }
@@ -1,18 +1,28 @@
 // Copyright 2024 Ulvetanna Inc.
 
 use crate::{
+	challenger::new_hasher_challenger,
 	oracle::{
 		CompositePolyOracle, MultilinearOracleSet, MultilinearPolyOracle, ProjectionVariant,
 		ShiftVariant,
 	},
 	polynomial::{
 		composition::BivariateProduct, extrapolate_line, transparent::select_row::SelectRow,
-		CompositionPoly, Error as PolynomialError, MultilinearComposite, MultilinearExtension,
-		MultilinearPoly, MultilinearQuery, MultivariatePoly,
+		CompositionPoly, Error as PolynomialError, IsomorphicEvaluationDomainFactory,
+		MultilinearComposite, MultilinearExtension, MultilinearPoly, MultilinearQuery,
+		MultivariatePoly,
 	},
 	protocols::{
-		evalcheck::{EvalcheckClaim, EvalcheckProof, EvalcheckProver, EvalcheckVerifier},
-		sumcheck::SumcheckClaim,
+		evalcheck::{
+			prove_shared_columns, verify_shared_columns, Error, EvalcheckClaim, EvalcheckProof,
+			EvalcheckProver, EvalcheckVerifier, VerificationError,
+		},
+		ring_switch::log_packing_degree,
+		sumcheck_v2::{
+			self,
+			prove::{batch_prove, RegularSumcheckProver},
+			CompositeSumClaim, FoldDirection, SumcheckClaim,
+		},
 	},
 	witness::MultilinearExtensionIndex,
 };
@@ -20,14 +30,16 @@ use assert_matches::assert_matches;
 use binius_field::{
 	packed::{get_packed_slice, len_packed_slice, set_packed_slice},
 	underlier::WithUnderlier,
-	BinaryField128b, Field, PackedBinaryField128x1b, PackedBinaryField16x8b,
-	PackedBinaryField1x128b, PackedBinaryField4x32b, PackedField, TowerField,
+	BinaryField128b, BinaryField1b, BinaryField8b, Field, PackedBinaryField128x1b,
+	PackedBinaryField16x8b, PackedBinaryField1x128b, PackedBinaryField4x32b, PackedField,
+	TowerField,
 };
+use binius_hash::GroestlHasher;
 use binius_utils::bail;
 use bytemuck::cast_slice_mut;
-use itertools::{Either, Itertools};
+use itertools::{izip, Either, Itertools};
 use rand::{rngs::StdRng, SeedableRng};
-use std::iter::repeat_with;
+use std::iter::{self, repeat_with};
 
 type FExtension = BinaryField128b;
 type PExtension = PackedBinaryField1x128b;
@@ -281,13 +293,11 @@ fn test_shifted_evaluation_whole_cube() {
 		1
 	);
 
-	let SumcheckClaim {
-		poly: composite,
-		sum,
-	} = verifier_state.new_sumcheck_claims().first().unwrap();
+	let sumcheck_claim = verifier_state.new_sumcheck_claims().first().unwrap();
+	let composite = sumcheck_claim.poly();
 
 	assert_eq!(composite.inner_polys()[0].id(), poly_id);
-	assert_eq!(*sum, shifted_witness.evaluate(&query).unwrap());
+	assert_eq!(sumcheck_claim.sum(), shifted_witness.evaluate(&query).unwrap());
 }
 
 #[test]
@@ -382,10 +392,8 @@ fn test_shifted_evaluation_subcube() {
 	);
 	assert_eq!(verifier_state.new_sumcheck_claims().len(), 1);
 
-	let SumcheckClaim {
-		poly: composite,
-		sum,
-	} = verifier_state.new_sumcheck_claims().first().unwrap();
+	let sumcheck_claim = verifier_state.new_sumcheck_claims().first().unwrap();
+	let composite = sumcheck_claim.poly();
 
 	match composite.inner_polys()[0] {
 		MultilinearPolyOracle::Projected(_, ref projected) => {
@@ -396,7 +404,7 @@ fn test_shifted_evaluation_subcube() {
 		_ => panic!("expected sumcheck on projection"),
 	}
 
-	assert_eq!(*sum, shifted_witness.evaluate(&query).unwrap());
+	assert_eq!(sumcheck_claim.sum(), shifted_witness.evaluate(&query).unwrap());
 }
 
 #[test]
@@ -486,6 +494,88 @@ fn test_evalcheck_linear_combination() {
 	verifier_state.verify(claim, proof).unwrap();
 }
 
+#[test]
+fn test_evalcheck_hypercube_vertex_point() {
+	let n_vars = 8;
+	let row_id = 5;
+
+	let select_row1 = SelectRow::new(n_vars, 0).unwrap();
+	let select_row2 = SelectRow::new(n_vars, row_id).unwrap();
+
+	let mut oracles = MultilinearOracleSet::new();
+
+	let select_row1_oracle_id = oracles.add_transparent(select_row1.clone()).unwrap();
+	let select_row2_oracle_id = oracles.add_transparent(select_row2.clone()).unwrap();
+
+	let lin_com_id = oracles
+		.add_linear_combination(
+			n_vars,
+			[
+				(select_row1_oracle_id, FExtension::new(2)),
+				(select_row2_oracle_id, FExtension::new(3)),
+			],
+		)
+		.unwrap();
+	let lin_com = oracles.oracle(lin_com_id);
+
+	// The point is the hypercube vertex of the selected row of the second oracle.
+	let eval_point = (0..n_vars)
+		.map(|i| {
+			if (row_id >> i) & 1 == 1 {
+				FExtension::ONE
+			} else {
+				FExtension::ZERO
+			}
+		})
+		.collect::<Vec<_>>();
+	let eval = FExtension::new(3);
+
+	let select_row1_witness = select_row1
+		.multilinear_extension::<PackedBinaryField128x1b>()
+		.unwrap();
+	let select_row2_witness = select_row2
+		.multilinear_extension::<PackedBinaryField128x1b>()
+		.unwrap();
+
+	let lin_com_values = (0..1 << n_vars)
+		.map(|i| {
+			select_row1_witness.evaluate_on_hypercube(i).unwrap() * FExtension::new(2)
+				+ select_row2_witness.evaluate_on_hypercube(i).unwrap() * FExtension::new(3)
+		})
+		.collect();
+	let lin_com_witness = MultilinearExtension::from_values(lin_com_values).unwrap();
+
+	let claim = EvalcheckClaim {
+		poly: lin_com.into_composite(),
+		eval_point,
+		eval,
+		is_random_point: false,
+	};
+
+	let mut witness_index = MultilinearExtensionIndex::<U, FExtension>::new();
+	witness_index
+		.update_multilin_poly(vec![
+			(select_row1_oracle_id, select_row1_witness.specialize_arc_dyn()),
+			(select_row2_oracle_id, select_row2_witness.specialize_arc_dyn()),
+			(lin_com_id, lin_com_witness.specialize_arc_dyn()),
+		])
+		.unwrap();
+
+	let mut prover_state =
+		EvalcheckProver::<FExtension, PExtension>::new(&mut oracles, &mut witness_index);
+	let proof = prover_state.prove(claim.clone()).unwrap();
+
+	if let EvalcheckProof::Composite { ref subproofs } = proof {
+		assert_eq!(subproofs.len(), 1);
+		assert_eq!(subproofs[0].0, eval);
+	} else {
+		panic!("Proof should be Composite.");
+	}
+
+	let mut verifier_state = EvalcheckVerifier::new(&mut oracles);
+	verifier_state.verify(claim, proof).unwrap();
+}
+
 #[test]
 fn test_evalcheck_repeating() {
 	let n_vars = 7;
@@ -819,3 +909,313 @@ fn test_evalcheck_zero_padded() {
 	let mut verifier_state = EvalcheckVerifier::new(&mut oracles);
 	verifier_state.verify(claim, proof).unwrap();
 }
+
+#[test]
+fn test_evalcheck_ring_switch_committed_1b() {
+	let n_vars = 10;
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let mut oracles = MultilinearOracleSet::<FExtension>::new();
+	let batch_id = oracles.add_committed_batch(n_vars, BinaryField1b::TOWER_LEVEL);
+	let ids = oracles.add_committed_multiple::<2>(batch_id);
+
+	let witnesses = repeat_with(|| {
+		repeat_with(|| PackedBinaryField128x1b::random(&mut rng))
+			.take(1 << (n_vars - PackedBinaryField128x1b::LOG_WIDTH))
+			.collect::<Vec<_>>()
+	})
+	.take(2)
+	.collect::<Vec<_>>();
+	let mut witness_index = MultilinearExtensionIndex::<U, FExtension>::new()
+		.update_owned::<BinaryField1b, _>(iter::zip(ids, &witnesses).map(|(id, witness)| {
+			(id, PackedBinaryField128x1b::to_underliers_ref(witness).to_vec())
+		}))
+		.unwrap();
+
+	// Each committed polynomial is claimed at a different point
+	let claims = iter::zip(ids, &witnesses)
+		.map(|(id, witness)| {
+			let eval_point = repeat_with(|| <FExtension as Field>::random(&mut rng))
+				.take(n_vars)
+				.collect::<Vec<_>>();
+			let query = MultilinearQuery::<FExtension>::with_full_query(&eval_point).unwrap();
+			let eval = MultilinearExtension::from_values_slice(witness.as_slice())
+				.unwrap()
+				.evaluate(&query)
+				.unwrap();
+			EvalcheckClaim {
+				poly: oracles.oracle(id).into_composite(),
+				eval_point,
+				eval,
+				is_random_point: true,
+			}
+		})
+		.collect::<Vec<_>>();
+
+	let mut prover_challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	let mut verifier_challenger = prover_challenger.clone();
+	let domain_factory = IsomorphicEvaluationDomainFactory::<BinaryField8b>::default();
+
+	let mut verifier_oracles = oracles.clone();
+	let mut prover_state =
+		EvalcheckProver::<FExtension, PExtension>::new(&mut oracles, &mut witness_index);
+	let proofs = claims
+		.iter()
+		.map(|claim| prover_state.prove(claim.clone()).unwrap())
+		.collect::<Vec<_>>();
+	let batch_claims = prover_state.batch_committed_eval_claims();
+	assert!(batch_claims
+		.try_extract_same_query_pcs_claim(batch_id)
+		.unwrap()
+		.is_none());
+
+	let ring_switch_proof = prover_state
+		.prove_ring_switch(batch_id, domain_factory, |_| 1, &mut prover_challenger)
+		.unwrap();
+	let prover_pcs_claim = prover_state
+		.batch_committed_eval_claims()
+		.try_extract_same_query_pcs_claim(batch_id)
+		.unwrap()
+		.expect("ring switching reduces the batch to a same query claim");
+
+	let mut verifier_state = EvalcheckVerifier::new(&mut verifier_oracles);
+	for (claim, proof) in iter::zip(claims, proofs) {
+		verifier_state.verify(claim, proof).unwrap();
+	}
+
+	// A malformed proof is rejected without consuming the claims of the batch
+	let mut truncated_proof = ring_switch_proof.clone();
+	truncated_proof.ring_switch_proofs.pop();
+	assert_matches!(
+		verifier_state.verify_ring_switch(
+			batch_id,
+			truncated_proof,
+			&mut verifier_challenger.clone()
+		),
+		Err(Error::Verification(VerificationError::RingSwitchProofMismatch))
+	);
+	assert_eq!(
+		verifier_state
+			.batch_committed_eval_claims()
+			.n_claims(batch_id)
+			.unwrap(),
+		2
+	);
+
+	verifier_state
+		.verify_ring_switch(batch_id, ring_switch_proof, &mut verifier_challenger)
+		.unwrap();
+	let verifier_pcs_claim = verifier_state
+		.batch_committed_eval_claims()
+		.try_extract_same_query_pcs_claim(batch_id)
+		.unwrap()
+		.expect("ring switching reduces the batch to a same query claim");
+
+	assert_eq!(prover_pcs_claim.eval_point, verifier_pcs_claim.eval_point);
+	assert_eq!(prover_pcs_claim.evals, verifier_pcs_claim.evals);
+	assert_eq!(prover_pcs_claim.eval_point.len(), n_vars - log_packing_degree::<FExtension>());
+
+	// The claims are on the witnesses reinterpreted as elements of the large field
+	let query =
+		MultilinearQuery::<FExtension>::with_full_query(&prover_pcs_claim.eval_point).unwrap();
+	for (witness, eval) in iter::zip(&witnesses, prover_pcs_claim.evals) {
+		let packed =
+			PExtension::from_underliers_ref(PackedBinaryField128x1b::to_underliers_ref(witness));
+		let packed_eval = MultilinearExtension::from_values_slice(packed)
+			.unwrap()
+			.evaluate(&query)
+			.unwrap();
+		assert_eq!(packed_eval, eval);
+	}
+}
+
+#[test]
+fn test_shared_columns_equality_link() {
+	let n_vars = 6;
+	let mut rng = StdRng::seed_from_u64(0);
+
+	// A column of the CPU table is reused by the hash table, which is committed separately.
+	let mut oracles = MultilinearOracleSet::<FExtension>::new();
+	let cpu_batch_id = oracles.add_committed_batch(n_vars, BinaryField8b::TOWER_LEVEL);
+	let hash_batch_id = oracles.add_committed_batch(n_vars, BinaryField8b::TOWER_LEVEL);
+	let cpu_column = oracles.add_committed(cpu_batch_id);
+	let hash_column = oracles
+		.add_committed_shared(hash_batch_id, cpu_column)
+		.unwrap();
+
+	let random_column = |rng: &mut StdRng| {
+		let packed = repeat_with(|| PackedBinaryField16x8b::random(&mut *rng))
+			.take(1 << (n_vars - PackedBinaryField16x8b::LOG_WIDTH))
+			.collect::<Vec<_>>();
+		PackedBinaryField16x8b::to_underliers_ref(&packed).to_vec()
+	};
+	let column = random_column(&mut rng);
+	let mut witness_index = MultilinearExtensionIndex::<U, FExtension>::new()
+		.update_owned::<BinaryField8b, _>([(cpu_column, column.clone())])
+		.unwrap();
+	witness_index
+		.update_shared([(hash_column, cpu_column)])
+		.unwrap();
+
+	let mut prover_challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	let mut verifier_challenger = prover_challenger.clone();
+
+	let (proof, prover_claims) =
+		prove_shared_columns::<_, PExtension, _>(&oracles, &witness_index, &mut prover_challenger)
+			.unwrap();
+	assert_eq!(proof.evals.len(), 1);
+
+	let mut bad_proof = proof.clone();
+	bad_proof.evals.push(FExtension::ONE);
+	assert_matches!(
+		verify_shared_columns(&oracles, bad_proof, &mut verifier_challenger.clone()),
+		Err(Error::Verification(VerificationError::SharedColumnEvalsMismatch { expected: 1 }))
+	);
+
+	let verifier_claims = verify_shared_columns(&oracles, proof, &mut verifier_challenger).unwrap();
+	assert_eq!(prover_claims.len(), 2);
+	assert_eq!(verifier_claims.len(), 2);
+
+	// Both oracles are claimed at the same point with the same value, which their witnesses match.
+	for (prover_claim, verifier_claim, id) in
+		izip!(&prover_claims, &verifier_claims, [cpu_column, hash_column])
+	{
+		assert_eq!(prover_claim.poly.inner_polys()[0].id(), id);
+		assert_eq!(verifier_claim.poly.inner_polys()[0].id(), id);
+		assert_eq!(prover_claim.eval_point, verifier_claim.eval_point);
+		assert_eq!(prover_claim.eval, verifier_claim.eval);
+		assert_eq!(prover_claim.eval_point, prover_claims[0].eval_point);
+		assert_eq!(prover_claim.eval, prover_claims[0].eval);
+
+		let query =
+			MultilinearQuery::<PExtension>::with_full_query(&prover_claim.eval_point).unwrap();
+		let witness_eval = witness_index
+			.get_multilin_poly(id)
+			.unwrap()
+			.evaluate(&query)
+			.unwrap();
+		assert_eq!(witness_eval, prover_claim.eval);
+	}
+
+	// If the hash table committed different data, its claim would not hold.
+	let tampered_index = MultilinearExtensionIndex::<U, FExtension>::new()
+		.update_owned::<BinaryField8b, _>([
+			(cpu_column, column),
+			(hash_column, random_column(&mut rng)),
+		])
+		.unwrap();
+	let (_, tampered_claims) = prove_shared_columns::<_, PExtension, _>(
+		&oracles,
+		&tampered_index,
+		&mut new_hasher_challenger::<_, GroestlHasher<_>>(),
+	)
+	.unwrap();
+	let query =
+		MultilinearQuery::<PExtension>::with_full_query(&tampered_claims[1].eval_point).unwrap();
+	let hash_eval = tampered_index
+		.get_multilin_poly(hash_column)
+		.unwrap()
+		.evaluate(&query)
+		.unwrap();
+	assert_ne!(hash_eval, tampered_claims[1].eval);
+}
+
+#[test]
+fn test_bit_reversed_sumcheck_and_evalcheck() {
+	let n_vars = 6;
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let mut oracles = MultilinearOracleSet::<FExtension>::new();
+	let batch_id = oracles.add_committed_batch(n_vars, BinaryField8b::TOWER_LEVEL);
+	let stored = oracles.add_committed(batch_id);
+	let column = oracles.add_bit_reversed(stored).unwrap();
+
+	// The column is produced in bit-reversed order and committed as it is stored.
+	let packed = repeat_with(|| PackedBinaryField16x8b::random(&mut rng))
+		.take(1 << (n_vars - PackedBinaryField16x8b::LOG_WIDTH))
+		.collect::<Vec<_>>();
+	let mut witness_index = MultilinearExtensionIndex::<U, FExtension>::new()
+		.update_owned::<BinaryField8b, _>([(
+			stored,
+			PackedBinaryField16x8b::to_underliers_ref(&packed).to_vec(),
+		)])
+		.unwrap();
+	witness_index.update_shared([(column, stored)]).unwrap();
+
+	// A sumcheck over the column folds the stored data high-to-low and is verified low-to-high.
+	let witness = witness_index.get_multilin_poly(column).unwrap();
+	let sum = (0..1 << n_vars)
+		.map(|i| witness.evaluate_on_hypercube(i).unwrap().square())
+		.sum::<FExtension>();
+	let composite_sums = vec![CompositeSumClaim {
+		composition: BivariateProduct,
+		sum,
+	}];
+	let claim = SumcheckClaim::new(n_vars, 2, composite_sums.clone()).unwrap();
+	let prover = RegularSumcheckProver::<BinaryField8b, _, _, _>::new_with_fold_direction(
+		vec![witness.clone(), witness],
+		composite_sums,
+		IsomorphicEvaluationDomainFactory::<BinaryField8b>::default(),
+		|_| 1,
+		FoldDirection::HighToLow,
+	)
+	.unwrap();
+
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	let (_, proof) = batch_prove(vec![prover], challenger.clone()).unwrap();
+	let output = sumcheck_v2::batch_verify(&[claim], proof, challenger).unwrap();
+	let eval_point = output.challenges;
+	let eval = output.multilinear_evals[0][0];
+
+	let lexicographic_values = (0..1 << n_vars)
+		.map(|i: usize| {
+			let stored_index = i.reverse_bits() >> (usize::BITS as usize - n_vars);
+			get_packed_slice(&packed, stored_index)
+		})
+		.collect::<Vec<_>>();
+	let query = MultilinearQuery::<FExtension>::with_full_query(&eval_point).unwrap();
+	assert_eq!(
+		MultilinearExtension::from_values(lexicographic_values)
+			.unwrap()
+			.evaluate(&query)
+			.unwrap(),
+		eval
+	);
+
+	// Evalcheck reduces the claim on the column to the stored data at the reversed point.
+	let claim = EvalcheckClaim {
+		poly: oracles.oracle(column).into_composite(),
+		eval_point: eval_point.clone(),
+		eval,
+		is_random_point: true,
+	};
+	let mut verifier_oracles = oracles.clone();
+	let mut prover_state =
+		EvalcheckProver::<FExtension, PExtension>::new(&mut oracles, &mut witness_index);
+	let proof = prover_state.prove(claim.clone()).unwrap();
+	let prover_claim = prover_state
+		.batch_committed_eval_claims()
+		.try_extract_same_query_pcs_claim(batch_id)
+		.unwrap()
+		.unwrap();
+
+	let mut verifier_state = EvalcheckVerifier::new(&mut verifier_oracles);
+	verifier_state.verify(claim, proof).unwrap();
+	let verifier_claim = verifier_state
+		.batch_committed_eval_claims()
+		.try_extract_same_query_pcs_claim(batch_id)
+		.unwrap()
+		.unwrap();
+
+	let reversed_point = eval_point.iter().rev().copied().collect::<Vec<_>>();
+	assert_eq!(prover_claim.eval_point, reversed_point);
+	assert_eq!(verifier_claim.eval_point, reversed_point);
+	assert_eq!(verifier_claim.evals, vec![eval]);
+
+	let query = MultilinearQuery::<FExtension>::with_full_query(&reversed_point).unwrap();
+	let stored_eval = MultilinearExtension::from_values_slice(packed.as_slice())
+		.unwrap()
+		.evaluate(&query)
+		.unwrap();
+	assert_eq!(stored_eval, eval);
+}
@@ -4,18 +4,20 @@ use super::{
 	error::{Error, VerificationError},
 	evalcheck::{
 		BatchCommittedEvalClaims, CommittedEvalClaim, EvalcheckClaim, EvalcheckMultilinearClaim,
-		EvalcheckProof,
+		EvalcheckProof, RingSwitchBatchProof,
 	},
 	subclaims::{packed_sumcheck_meta, projected_bivariate_claim, shifted_sumcheck_meta},
 };
 use crate::{
-	oracle::{MultilinearOracleSet, MultilinearPolyOracle, ProjectionVariant},
+	challenger::{CanObserve, CanSample},
+	oracle::{BatchId, MultilinearOracleSet, MultilinearPolyOracle, ProjectionVariant},
 	polynomial::extrapolate_line_scalar,
-	protocols::sumcheck::SumcheckClaim,
+	protocols::{ring_switch, sumcheck::SumcheckClaim, sumcheck_v2},
 };
 use binius_field::{util::inner_product_unchecked, TowerField};
 use binius_utils::bail;
 use getset::{Getters, MutGetters};
+use std::iter;
 use tracing::instrument;
 
 /// A mutable verifier state.
@@ -103,6 +105,51 @@ impl<'a, F: TowerField> EvalcheckVerifier<'a, F> {
 		Ok(())
 	}
 
+	/// Verify the ring-switching reduction of the committed evaluation claims on a batch of 1-bit
+	/// multilinears.
+	///
+	/// See [`EvalcheckProver::prove_ring_switch`](`super::prove::EvalcheckProver::prove_ring_switch`)
+	/// docs for comments.
+	pub fn verify_ring_switch<Challenger>(
+		&mut self,
+		batch_id: BatchId,
+		proof: RingSwitchBatchProof<F>,
+		mut challenger: Challenger,
+	) -> Result<(), Error>
+	where
+		Challenger: CanObserve<F> + CanSample<F>,
+	{
+		// Validate before taking the claims out, so that they are kept on error
+		let n_claims = self.batch_committed_eval_claims.n_claims(batch_id)?;
+		if n_claims == 0 {
+			bail!(Error::EmptyBatch(batch_id));
+		}
+		if self.oracles.committed_batch(batch_id).tower_level != 0 {
+			bail!(Error::RingSwitchNonBinaryBatch(batch_id));
+		}
+
+		let RingSwitchBatchProof {
+			ring_switch_proofs,
+			sumcheck_proof,
+		} = proof;
+		if ring_switch_proofs.len() != n_claims {
+			bail!(VerificationError::RingSwitchProofMismatch);
+		}
+		let claims = self.batch_committed_eval_claims.take_claims(batch_id)?;
+
+		let reduced_claims = iter::zip(&claims, ring_switch_proofs)
+			.map(|(claim, proof)| ring_switch::verify(claim, proof, &mut challenger))
+			.collect::<Result<Vec<_>, _>>()?;
+		let sumcheck_claims = ring_switch::reduce_to_sumchecks(&reduced_claims)?;
+		let sumcheck_output =
+			sumcheck_v2::batch_verify(&sumcheck_claims, sumcheck_proof, &mut challenger)?;
+		for claim in ring_switch::verify_sumcheck_outputs(&reduced_claims, sumcheck_output)? {
+			self.batch_committed_eval_claims.insert(claim);
+		}
+
+		Ok(())
+	}
+
 	fn verify_multilinear(
 		&mut self,
 		evalcheck_claim: EvalcheckMultilinearClaim<F>,
@@ -336,6 +383,17 @@ impl<'a, F: TowerField> EvalcheckVerifier<'a, F> {
 					is_random_point,
 				)?;
 			}
+
+			MultilinearPolyOracle::BitReversed { inner, .. } => {
+				let new_claim = EvalcheckMultilinearClaim {
+					poly: *inner,
+					eval_point: eval_point.into_iter().rev().collect(),
+					eval,
+					is_random_point,
+				};
+
+				self.verify_multilinear(new_claim, evalcheck_proof)?;
+			}
 		}
 
 		Ok(())
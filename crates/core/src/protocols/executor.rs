@@ -0,0 +1,613 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Interleaved execution of independent protocol instances over one transcript.
+//!
+//! A batched sumcheck already interleaves the rounds of claims with different numbers of
+//! variables, but mixes them into one round polynomial with random coefficients. Independent
+//! batches, for example sumchecks whose claims only become known at different points of the
+//! proof, would otherwise run one after another, each starting when the previous one finished.
+//! The [`ProtocolExecutor`] runs them round by round over a single challenger instead, aligned by
+//! the number of variables, so that the rounds of all batches over the same number of variables
+//! happen in the same step.
+//!
+//! In each step, the executor executes the round of every active batch and observes the round
+//! proofs in the order the batches were added, and then samples the challenge of every active
+//! batch in the same order. A batch is active from the step where the number of remaining
+//! variables equals the number of rounds of the batch. After the last step, the batches sample the
+//! batching coefficients of their 0-variate claims and observe their multilinear evaluations, in
+//! order. [`verify_interleaved`] replays the same schedule.
+//!
+//! Each batch yields its own [`Proof`] without protocol features or claim labels. Claim labels are
+//! not observed.
+//!
+//! All batches of an executor fold in the same [`FoldDirection`]. On both the prover and the
+//! verifier side, the challenges of each output are ordered by the variables they bind, and the
+//! evaluation point of a claim is [`FoldDirection::eval_point`] of the challenges.
+
+use crate::{
+	challenger::{CanObserve, CanSample},
+	polynomial::{evaluate_univariate, CompositionPoly},
+	protocols::sumcheck_v2::{
+		batch_weighted_value, compute_expected_batch_composite_evaluation,
+		prove::{execute_provers, SumcheckProver},
+		transparent_eval_point, BatchSumcheckOutput, Error, FoldDirection, Proof, ProtocolFeatures,
+		RoundProof, SumcheckClaim, VerificationError,
+	},
+};
+use binius_field::Field;
+use binius_utils::{bail, sorting::is_sorted_ascending};
+use std::marker::PhantomData;
+use tracing::{debug_span, instrument};
+
+/// Drives independent batched sumcheck proofs round by round over one transcript.
+///
+/// See the [module documentation](self) for the order of the transcript.
+#[derive(Debug)]
+pub struct ProtocolExecutor<F: Field, Prover> {
+	batches: Vec<Vec<Prover>>,
+	_marker: PhantomData<F>,
+}
+
+impl<F: Field, Prover> Default for ProtocolExecutor<F, Prover> {
+	fn default() -> Self {
+		Self {
+			batches: Vec::new(),
+			_marker: PhantomData,
+		}
+	}
+}
+
+impl<F, Prover> ProtocolExecutor<F, Prover>
+where
+	F: Field,
+	Prover: SumcheckProver<F>,
+{
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Adds a batch of sumcheck provers and returns the index of the batch.
+	///
+	/// As for [`crate::protocols::sumcheck_v2::prove::batch_prove`], the provers must be in
+	/// descending order by number of variables. They must all fold in the same direction as the
+	/// provers of the batches added before.
+	pub fn add_sumcheck_batch(&mut self, provers: Vec<Prover>) -> Result<usize, Error> {
+		if !is_sorted_ascending(provers.iter().map(|prover| prover.n_vars()).rev()) {
+			bail!(Error::ClaimsOutOfOrder);
+		}
+		if let Some(first) = self.batches.iter().flatten().chain(&provers).next() {
+			let fold_direction = first.fold_direction();
+			if provers
+				.iter()
+				.any(|prover| prover.fold_direction() != fold_direction)
+			{
+				bail!(Error::FoldDirectionMismatch);
+			}
+		}
+
+		self.batches.push(provers);
+		Ok(self.batches.len() - 1)
+	}
+
+	/// The number of steps of the interleaved execution, which is the largest number of rounds of
+	/// a batch.
+	pub fn n_rounds(&self) -> usize {
+		self.batches
+			.iter()
+			.map(|provers| batch_n_rounds(provers.iter().map(|prover| prover.n_vars())))
+			.max()
+			.unwrap_or(0)
+	}
+
+	/// Proves all batches, returning the output and proof of each batch in the order they were
+	/// added.
+	#[instrument(skip_all, name = "protocol_executor::prove", level = "debug")]
+	pub fn prove<Challenger>(
+		self,
+		mut challenger: Challenger,
+	) -> Result<Vec<(BatchSumcheckOutput<F>, Proof<F>)>, Error>
+	where
+		Challenger: CanSample<F> + CanObserve<F>,
+	{
+		let n_rounds = self.n_rounds();
+		let mut states = self
+			.batches
+			.into_iter()
+			.map(ProverBatchState::new)
+			.collect::<Vec<_>>();
+
+		for step in 0..n_rounds {
+			let n_vars = n_rounds - step;
+			let _span = debug_span!("protocol_executor::step", step).entered();
+
+			for state in states.iter_mut().filter(|state| state.n_rounds >= n_vars) {
+				while let Some(prover) = state.provers.get(state.batch_coeffs.len()) {
+					if prover.n_vars() != n_vars {
+						break;
+					}
+					state.batch_coeffs.push(challenger.sample());
+				}
+
+				let n_active = state.batch_coeffs.len();
+				let round_proof =
+					execute_provers(&mut state.provers[..n_active], &state.batch_coeffs)?
+						.truncate();
+				challenger.observe_slice(round_proof.coeffs());
+				state.rounds.push(round_proof);
+			}
+
+			for state in states.iter_mut().filter(|state| state.n_rounds >= n_vars) {
+				let challenge = challenger.sample();
+				state.challenges.push(challenge);
+
+				let n_active = state.batch_coeffs.len();
+				state.provers[..n_active]
+					.iter_mut()
+					.try_for_each(|prover| prover.fold(challenge))?;
+			}
+		}
+
+		// Batch in the provers for 0-variate polynomials, as the verifier does.
+		for state in states.iter_mut() {
+			while state.batch_coeffs.len() < state.provers.len() {
+				state.batch_coeffs.push(challenger.sample());
+			}
+		}
+
+		states
+			.into_iter()
+			.map(|state| {
+				let fold_direction = state
+					.provers
+					.first()
+					.map(|prover| prover.fold_direction())
+					.unwrap_or_default();
+				let multilinear_evals = state
+					.provers
+					.into_iter()
+					.map(|prover| prover.finish())
+					.collect::<Result<Vec<_>, _>>()?;
+				for multilinear_evals in multilinear_evals.iter() {
+					challenger.observe_slice(multilinear_evals);
+				}
+
+				let output = BatchSumcheckOutput {
+					challenges: state.challenges,
+					multilinear_evals: multilinear_evals.clone(),
+				}
+				.with_fold_direction(fold_direction);
+				let proof = Proof {
+					features: ProtocolFeatures::empty(),
+					multilinear_evals,
+					rounds: state.rounds,
+					claim_labels: None,
+				};
+				Ok((output, proof))
+			})
+			.collect()
+	}
+}
+
+struct ProverBatchState<F: Field, Prover> {
+	n_rounds: usize,
+	provers: Vec<Prover>,
+	/// The batching coefficients of the active provers, which are the first provers of the batch.
+	batch_coeffs: Vec<F>,
+	challenges: Vec<F>,
+	rounds: Vec<RoundProof<F>>,
+}
+
+impl<F: Field, Prover: SumcheckProver<F>> ProverBatchState<F, Prover> {
+	fn new(provers: Vec<Prover>) -> Self {
+		Self {
+			n_rounds: batch_n_rounds(provers.iter().map(|prover| prover.n_vars())),
+			provers,
+			batch_coeffs: Vec::new(),
+			challenges: Vec::new(),
+			rounds: Vec::new(),
+		}
+	}
+}
+
+fn batch_n_rounds(n_vars: impl Iterator<Item = usize>) -> usize {
+	n_vars.max().unwrap_or(0)
+}
+
+/// Verifies the proofs of batched sumchecks executed by a [`ProtocolExecutor`].
+///
+/// The batches of claims and their proofs must be in the order the batches of provers were added
+/// to the executor, and `fold_direction` must be the direction the provers folded in. Returns the
+/// output of each batch, with the challenges ordered by the variables they bind, as returned by
+/// [`ProtocolExecutor::prove`]. The transparent multilinears of the claims are evaluated at the
+/// same points.
+#[instrument(skip_all, name = "protocol_executor::verify", level = "debug")]
+pub fn verify_interleaved<F, Composition, Challenger>(
+	batches: &[Vec<SumcheckClaim<F, Composition>>],
+	proofs: Vec<Proof<F>>,
+	fold_direction: FoldDirection,
+	mut challenger: Challenger,
+) -> Result<Vec<BatchSumcheckOutput<F>>, Error>
+where
+	F: Field,
+	Composition: CompositionPoly<F>,
+	Challenger: CanObserve<F> + CanSample<F>,
+{
+	if proofs.len() != batches.len() {
+		bail!(VerificationError::NumberOfBatchProofs {
+			expected: batches.len(),
+		});
+	}
+
+	let mut states = Vec::with_capacity(batches.len());
+	for (claims, proof) in batches.iter().zip(proofs) {
+		if !is_sorted_ascending(claims.iter().map(|claim| claim.n_vars()).rev()) {
+			bail!(Error::ClaimsOutOfOrder);
+		}
+		if !proof.features.is_empty() {
+			bail!(Error::UnsupportedProtocolFeatures {
+				unsupported: proof.features,
+			});
+		}
+
+		let n_rounds = batch_n_rounds(claims.iter().map(|claim| claim.n_vars()));
+		if proof.rounds.len() != n_rounds {
+			bail!(VerificationError::NumberOfRounds);
+		}
+		states.push(VerifierBatchState {
+			claims,
+			n_rounds,
+			round_proofs: proof.rounds.into_iter(),
+			multilinear_evals: proof.multilinear_evals,
+			batch_coeffs: Vec::with_capacity(claims.len()),
+			challenges: Vec::with_capacity(n_rounds),
+			round_coeffs: Vec::new(),
+			sum: F::ZERO,
+			max_degree: 0,
+		});
+	}
+
+	let n_rounds = states.iter().map(|state| state.n_rounds).max().unwrap_or(0);
+	for step in 0..n_rounds {
+		let n_vars = n_rounds - step;
+		let _span = debug_span!("protocol_executor::step", step).entered();
+
+		for state in states.iter_mut().filter(|state| state.n_rounds >= n_vars) {
+			state.batch_in_claims(n_vars, &mut challenger);
+
+			let round = state.challenges.len();
+			let round_proof = state
+				.round_proofs
+				.next()
+				.expect("the number of round proofs is checked above");
+			if round_proof.coeffs().len() != state.max_degree {
+				bail!(VerificationError::NumberOfCoefficients {
+					round,
+					expected: state.max_degree,
+				});
+			}
+			challenger.observe_slice(round_proof.coeffs());
+			state.round_coeffs = round_proof.recover(state.sum).0;
+		}
+
+		for state in states.iter_mut().filter(|state| state.n_rounds >= n_vars) {
+			let challenge = challenger.sample();
+			state.challenges.push(challenge);
+			state.sum = evaluate_univariate(&state.round_coeffs, challenge);
+		}
+	}
+
+	for state in states.iter_mut() {
+		state.batch_in_claims(0, &mut challenger);
+	}
+
+	states
+		.into_iter()
+		.map(|state| {
+			let VerifierBatchState {
+				claims,
+				multilinear_evals,
+				batch_coeffs,
+				challenges,
+				sum,
+				..
+			} = state;
+
+			if multilinear_evals.len() != claims.len() {
+				bail!(VerificationError::NumberOfFinalEvaluations);
+			}
+			let multilinear_evals = claims
+				.iter()
+				.zip(multilinear_evals)
+				.enumerate()
+				.map(|(claim_index, (claim, multilinear_evals))| {
+					if claim.n_committed_multilinears() != multilinear_evals.len() {
+						bail!(VerificationError::NumberOfClaimEvaluations {
+							claim_index,
+							expected: claim.n_committed_multilinears(),
+						});
+					}
+					let point = transparent_eval_point(&challenges, claim.n_vars(), fold_direction);
+					let multilinear_evals =
+						claim.insert_transparent_evals(multilinear_evals, &point)?;
+					challenger.observe_slice(&multilinear_evals);
+					Ok(multilinear_evals)
+				})
+				.collect::<Result<Vec<_>, Error>>()?;
+
+			let expected_sum = compute_expected_batch_composite_evaluation(
+				batch_coeffs,
+				claims,
+				&multilinear_evals,
+			)?;
+			if sum != expected_sum {
+				bail!(VerificationError::IncorrectBatchEvaluation);
+			}
+
+			let output = BatchSumcheckOutput {
+				challenges,
+				multilinear_evals,
+			};
+			Ok(output.with_fold_direction(fold_direction))
+		})
+		.collect()
+}
+
+struct VerifierBatchState<'a, F: Field, Composition> {
+	claims: &'a [SumcheckClaim<F, Composition>],
+	n_rounds: usize,
+	round_proofs: std::vec::IntoIter<RoundProof<F>>,
+	multilinear_evals: Vec<Vec<F>>,
+	/// The batching coefficients of the active claims, which are the first claims of the batch.
+	batch_coeffs: Vec<F>,
+	challenges: Vec<F>,
+	/// The round polynomial of the current step.
+	round_coeffs: Vec<F>,
+	sum: F,
+	/// The maximum individual degree of the active claims.
+	max_degree: usize,
+}
+
+impl<'a, F, Composition> VerifierBatchState<'a, F, Composition>
+where
+	F: Field,
+	Composition: CompositionPoly<F>,
+{
+	/// Samples the batching coefficients of the claims over `n_vars` variables and batches their
+	/// sums into the batched sum.
+	fn batch_in_claims<Challenger: CanSample<F>>(
+		&mut self,
+		n_vars: usize,
+		challenger: &mut Challenger,
+	) {
+		while let Some(claim) = self.claims.get(self.batch_coeffs.len()) {
+			if claim.n_vars() != n_vars {
+				break;
+			}
+
+			let batch_coeff = challenger.sample();
+			self.batch_coeffs.push(batch_coeff);
+			self.sum += batch_weighted_value(
+				batch_coeff,
+				claim
+					.composite_sums()
+					.iter()
+					.map(|inner_claim| inner_claim.sum),
+			);
+			self.max_degree = self.max_degree.max(claim.max_individual_degree());
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		challenger::new_hasher_challenger,
+		polynomial::{
+			IsomorphicEvaluationDomainFactory, MultilinearExtension,
+			MultilinearExtensionSpecialized, MultilinearQuery,
+		},
+		protocols::{
+			sumcheck_v2::{
+				prove::{batch_prove, RegularSumcheckProver},
+				CompositeSumClaim,
+			},
+			test_utils::TestProductComposition,
+		},
+	};
+	use assert_matches::assert_matches;
+	use binius_field::{BinaryField128b, BinaryField8b};
+	use binius_hash::GroestlHasher;
+	use rand::{rngs::StdRng, SeedableRng};
+	use std::iter;
+
+	type FDomain = BinaryField8b;
+	type FE = BinaryField128b;
+
+	type Witness = (Vec<MultilinearExtension<FE>>, FE);
+
+	fn generate_witness(rng: &mut StdRng, n_vars: usize, n_multilinears: usize) -> Witness {
+		let multilins = iter::repeat_with(|| {
+			let values = iter::repeat_with(|| <FE as Field>::random(&mut *rng))
+				.take(1 << n_vars)
+				.collect();
+			MultilinearExtension::from_values(values).unwrap()
+		})
+		.take(n_multilinears)
+		.collect::<Vec<_>>();
+		let sum = (0..1 << n_vars)
+			.map(|index| {
+				multilins
+					.iter()
+					.map(|multilin| multilin.evaluate_on_hypercube(index).unwrap())
+					.product::<FE>()
+			})
+			.sum();
+		(multilins, sum)
+	}
+
+	fn make_provers(
+		witnesses: &[Witness],
+		fold_direction: FoldDirection,
+	) -> Vec<
+		RegularSumcheckProver<
+			FDomain,
+			FE,
+			TestProductComposition,
+			MultilinearExtensionSpecialized<FE, FE>,
+		>,
+	> {
+		let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+		witnesses
+			.iter()
+			.map(|(multilins, sum)| {
+				RegularSumcheckProver::new_with_fold_direction(
+					multilins
+						.iter()
+						.map(|multilin| multilin.clone().specialize())
+						.collect(),
+					[CompositeSumClaim {
+						composition: TestProductComposition::new(multilins.len()),
+						sum: *sum,
+					}],
+					domain_factory.clone(),
+					|_| 1,
+					fold_direction,
+				)
+				.unwrap()
+			})
+			.collect()
+	}
+
+	fn make_claims(witnesses: &[Witness]) -> Vec<SumcheckClaim<FE, TestProductComposition>> {
+		witnesses
+			.iter()
+			.map(|(multilins, sum)| {
+				SumcheckClaim::new(
+					multilins[0].n_vars(),
+					multilins.len(),
+					vec![CompositeSumClaim {
+						composition: TestProductComposition::new(multilins.len()),
+						sum: *sum,
+					}],
+				)
+				.unwrap()
+			})
+			.collect()
+	}
+
+	fn prove_verify_interleaved_batches(fold_direction: FoldDirection) {
+		let mut rng = StdRng::seed_from_u64(0);
+		let batches = [vec![(6, 2), (4, 3)], vec![(5, 2), (5, 1)], vec![(0, 2)]].map(|shapes| {
+			shapes
+				.into_iter()
+				.map(|(n_vars, n_multilinears)| generate_witness(&mut rng, n_vars, n_multilinears))
+				.collect::<Vec<_>>()
+		});
+
+		let mut executor = ProtocolExecutor::new();
+		for witnesses in batches.iter() {
+			executor
+				.add_sumcheck_batch(make_provers(witnesses, fold_direction))
+				.unwrap();
+		}
+		assert_eq!(executor.n_rounds(), 6);
+
+		let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+		let mut prover_challenger = challenger.clone();
+		let (prover_outputs, proofs) = executor
+			.prove(&mut prover_challenger)
+			.unwrap()
+			.into_iter()
+			.unzip::<_, _, Vec<_>, Vec<_>>();
+
+		let claims = batches
+			.iter()
+			.map(|witnesses| make_claims(witnesses))
+			.collect::<Vec<_>>();
+		let mut verifier_challenger = challenger.clone();
+		let verifier_outputs =
+			verify_interleaved(&claims, proofs.clone(), fold_direction, &mut verifier_challenger)
+				.unwrap();
+		assert_eq!(prover_outputs, verifier_outputs);
+		assert_eq!(
+			CanSample::<FE>::sample(&mut prover_challenger),
+			CanSample::<FE>::sample(&mut verifier_challenger)
+		);
+
+		// The evaluations are those of the multilinears at the challenges ordered by variable.
+		for (witnesses, output) in iter::zip(&batches, &verifier_outputs) {
+			for ((multilins, _), evals) in iter::zip(witnesses, &output.multilinear_evals) {
+				let n_vars = multilins[0].n_vars();
+				let eval_point = fold_direction.eval_point(&output.challenges, n_vars);
+				let query = MultilinearQuery::with_full_query(eval_point).unwrap();
+				for (multilin, &expected) in iter::zip(multilins, evals) {
+					assert_eq!(multilin.evaluate(&query).unwrap(), expected);
+				}
+			}
+		}
+
+		// The proofs are bound to their batches.
+		let mut swapped = proofs.clone();
+		swapped.swap(0, 1);
+		assert!(verify_interleaved(&claims, swapped, fold_direction, challenger.clone()).is_err());
+		assert_matches!(
+			verify_interleaved(&claims, proofs[..2].to_vec(), fold_direction, challenger),
+			Err(Error::Verification(VerificationError::NumberOfBatchProofs { expected: 3 }))
+		);
+	}
+
+	#[test]
+	fn test_prove_verify_interleaved_batches() {
+		prove_verify_interleaved_batches(FoldDirection::LowToHigh);
+	}
+
+	#[test]
+	fn test_prove_verify_interleaved_batches_high_to_low() {
+		prove_verify_interleaved_batches(FoldDirection::HighToLow);
+	}
+
+	#[test]
+	fn test_rejects_mixed_fold_directions() {
+		let mut rng = StdRng::seed_from_u64(0);
+		let witnesses = [generate_witness(&mut rng, 3, 1)];
+		let mut executor = ProtocolExecutor::new();
+		executor
+			.add_sumcheck_batch(make_provers(&witnesses, FoldDirection::LowToHigh))
+			.unwrap();
+		assert_matches!(
+			executor.add_sumcheck_batch(make_provers(&witnesses, FoldDirection::HighToLow)),
+			Err(Error::FoldDirectionMismatch)
+		);
+	}
+
+	#[test]
+	fn test_single_batch_matches_batch_prove() {
+		let mut rng = StdRng::seed_from_u64(0);
+		let witnesses = [(5, 2), (3, 2)]
+			.map(|(n_vars, n_multilinears)| generate_witness(&mut rng, n_vars, n_multilinears));
+
+		let mut executor = ProtocolExecutor::new();
+		executor
+			.add_sumcheck_batch(make_provers(&witnesses, FoldDirection::LowToHigh))
+			.unwrap();
+		let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+		let mut results = executor.prove(challenger.clone()).unwrap();
+
+		let (expected_output, expected_proof) =
+			batch_prove(make_provers(&witnesses, FoldDirection::LowToHigh), challenger).unwrap();
+		assert_eq!(results.pop().unwrap(), (expected_output, expected_proof));
+	}
+
+	#[test]
+	fn test_rejects_unsorted_batch() {
+		let mut rng = StdRng::seed_from_u64(0);
+		let witnesses = [(3, 1), (4, 1)]
+			.map(|(n_vars, n_multilinears)| generate_witness(&mut rng, n_vars, n_multilinears));
+		let mut executor = ProtocolExecutor::new();
+		assert_matches!(
+			executor.add_sumcheck_batch(make_provers(&witnesses, FoldDirection::LowToHigh)),
+			Err(Error::ClaimsOutOfOrder)
+		);
+	}
+}
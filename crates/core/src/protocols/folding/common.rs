@@ -0,0 +1,98 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::error::Error;
+use crate::{
+	polynomial::CompositionPoly,
+	protocols::sumcheck_v2::{CompositeSumClaim, SumcheckClaim},
+};
+use binius_field::Field;
+use binius_utils::bail;
+
+/// A proof that folds two sumcheck claims into one.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FoldingProof<F: Field> {
+	/// The monomial coefficients of the folding polynomial of each composite.
+	pub composite_coeffs: Vec<Vec<F>>,
+}
+
+/// The result of folding two sumcheck claims.
+#[derive(Debug)]
+pub struct FoldingOutput<F: Field, Composition> {
+	/// The challenge the witnesses were folded with.
+	pub challenge: F,
+	/// The folded claim.
+	pub claim: SumcheckClaim<F, Composition>,
+}
+
+/// Folds the multilinear evaluations of two witnesses at a common point.
+///
+/// The result is the evaluation of the folded witness at the same point, so the evaluations a
+/// sumcheck over a folded claim reduces to can be checked against the original witnesses.
+pub fn fold_evals<F: Field>(challenge: F, evals: [&[F]; 2]) -> Result<Vec<F>, Error> {
+	let [evals_0, evals_1] = evals;
+	if evals_0.len() != evals_1.len() {
+		bail!(Error::IncorrectNumberOfEvaluations {
+			expected: evals_0.len(),
+		});
+	}
+	Ok(evals_0
+		.iter()
+		.zip(evals_1)
+		.map(|(&eval_0, &eval_1)| eval_0 + (eval_1 - eval_0) * challenge)
+		.collect())
+}
+
+pub(super) fn check_claim_shapes<F, Composition>(
+	claims: [&SumcheckClaim<F, Composition>; 2],
+) -> Result<(), Error>
+where
+	F: Field,
+	Composition: CompositionPoly<F>,
+{
+	let [claim_0, claim_1] = claims;
+	if claim_0.n_vars() != claim_1.n_vars()
+		|| claim_0.n_multilinears() != claim_1.n_multilinears()
+		|| claim_0.composite_degrees() != claim_1.composite_degrees()
+	{
+		bail!(Error::ClaimShapeMismatch);
+	}
+	Ok(())
+}
+
+pub(super) fn folded_claim<F, Composition>(
+	claim: &SumcheckClaim<F, Composition>,
+	sums: impl IntoIterator<Item = F>,
+) -> Result<SumcheckClaim<F, Composition>, Error>
+where
+	F: Field,
+	Composition: CompositionPoly<F> + Clone,
+{
+	let composite_sums = claim
+		.composite_sums()
+		.iter()
+		.zip(sums)
+		.map(|(composite_sum, sum)| CompositeSumClaim {
+			composition: composite_sum.composition.clone(),
+			sum,
+		})
+		.collect();
+	let claim = SumcheckClaim::with_composite_degrees(
+		claim.n_vars(),
+		claim.n_multilinears(),
+		composite_sums,
+		claim.composite_degrees().to_vec(),
+	)?;
+	Ok(claim)
+}
+
+pub(super) fn composite_sums<F, Composition>(claim: &SumcheckClaim<F, Composition>) -> Vec<F>
+where
+	F: Field,
+	Composition: CompositionPoly<F>,
+{
+	claim
+		.composite_sums()
+		.iter()
+		.map(|composite_sum| composite_sum.sum)
+		.collect()
+}
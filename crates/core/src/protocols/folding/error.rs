@@ -0,0 +1,31 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use crate::{polynomial::Error as PolynomialError, protocols::sumcheck_v2};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	#[error("the folded claims must have the same number of variables, multilinears and composite degrees")]
+	ClaimShapeMismatch,
+	#[error("incorrect number of witness multilinears, expected {expected}")]
+	IncorrectNumberOfMultilinears { expected: usize },
+	#[error("witness multilinears must have {expected} variables")]
+	IncorrectNumberOfVariables { expected: usize },
+	#[error("incorrect number of multilinear evaluations, expected {expected}")]
+	IncorrectNumberOfEvaluations { expected: usize },
+	#[error("polynomial error: {0}")]
+	Polynomial(#[from] PolynomialError),
+	#[error("sumcheck error: {0}")]
+	Sumcheck(#[from] sumcheck_v2::Error),
+	#[error("verification failure: {0}")]
+	Verification(#[from] VerificationError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum VerificationError {
+	#[error("the proof must contain one folding polynomial per composite")]
+	NumberOfComposites,
+	#[error("number of coefficients for composite {index} is incorrect, expected {expected}")]
+	NumberOfCoefficients { index: usize, expected: usize },
+	#[error("folding polynomial for composite {index} does not match the claimed sums")]
+	SumMismatch { index: usize },
+}
@@ -0,0 +1,37 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Experimental folding scheme for sumcheck claims.
+//!
+//! Folding, as introduced by Nova [KST22], reduces two claims of the same shape to a single claim
+//! with one random challenge, deferring the expensive verification. Applied repeatedly, an
+//! accumulated claim absorbs one new claim per step, which amortizes proving across the steps of
+//! an incrementally verifiable computation.
+//!
+//! Two [`SumcheckClaim`]s over the same compositions and multilinear shape, with witnesses
+//! $m_0$ and $m_1$, are folded along the line $m(t) = m_0 + t (m_1 - m_0)$. For each composite
+//! $C$ of degree $d$, the prover sends the univariate polynomial
+//! $g(t) = \sum_{x \in \{0, 1\}^n} C(m(t)(x))$, which has degree at most $d$. The verifier checks
+//! $g(0)$ and $g(1)$ against the claimed sums, samples a challenge $r$, and the folded claim is
+//! that the composite over $m(r)$ sums to $g(r)$.
+//!
+//! Unlike Nova, the witnesses are not folded under a homomorphic commitment. The folded claim is
+//! instead over virtual multilinears, and the multilinear evaluations it eventually reduces to are
+//! recovered from evaluations of the original witnesses with [`fold_evals`].
+//!
+//! This module is experimental and its proof format is not stable.
+//!
+//! [KST22]: <https://eprint.iacr.org/2021/370>
+//!
+//! [`SumcheckClaim`]: crate::protocols::sumcheck_v2::SumcheckClaim
+
+mod common;
+mod error;
+mod prove;
+#[cfg(test)]
+mod tests;
+mod verify;
+
+pub use common::*;
+pub use error::*;
+pub use prove::*;
+pub use verify::*;
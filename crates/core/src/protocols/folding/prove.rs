@@ -0,0 +1,147 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::{
+	common::{check_claim_shapes, composite_sums, folded_claim, FoldingOutput, FoldingProof},
+	error::Error,
+};
+use crate::{
+	challenger::{CanObserve, CanSample},
+	polynomial::{
+		evaluate_univariate, CompositionPoly, Error as PolynomialError, EvaluationDomainFactory,
+		MultilinearExtension,
+	},
+	protocols::sumcheck_v2::SumcheckClaim,
+};
+use binius_field::{ExtensionField, Field, PackedField};
+use binius_utils::bail;
+use itertools::izip;
+use rayon::prelude::*;
+use std::ops::Deref;
+
+/// Prove the folding of two sumcheck claims into one.
+///
+/// Returns the folded claim, the folded witness multilinears, and the folding proof. The claims
+/// must have the same shape and the same composition polynomials, and `witnesses` must hold the
+/// multilinears of each claim in order.
+pub fn fold_prove<F, FDomain, P, Data, Composition, Challenger>(
+	claims: [&SumcheckClaim<F, Composition>; 2],
+	witnesses: [&[MultilinearExtension<P, Data>]; 2],
+	domain_factory: impl EvaluationDomainFactory<FDomain>,
+	mut challenger: Challenger,
+) -> Result<(FoldingOutput<F, Composition>, Vec<MultilinearExtension<P>>, FoldingProof<F>), Error>
+where
+	F: Field + ExtensionField<FDomain>,
+	FDomain: Field,
+	P: PackedField<Scalar = F>,
+	Data: Deref<Target = [P]> + Send + Sync,
+	Composition: CompositionPoly<F> + CompositionPoly<P> + Clone,
+	Challenger: CanObserve<F> + CanSample<F>,
+{
+	check_claim_shapes(claims)?;
+
+	let [claim_0, _] = claims;
+	let n_vars = claim_0.n_vars();
+	for witness in witnesses {
+		if witness.len() != claim_0.n_multilinears() {
+			bail!(Error::IncorrectNumberOfMultilinears {
+				expected: claim_0.n_multilinears(),
+			});
+		}
+		if witness.iter().any(|multilin| multilin.n_vars() != n_vars) {
+			bail!(Error::IncorrectNumberOfVariables { expected: n_vars });
+		}
+	}
+	let [witness_0, witness_1] = witnesses;
+
+	let domain = domain_factory.create(claim_0.max_individual_degree() + 1)?;
+	let points = domain
+		.points()
+		.iter()
+		.map(|&point| P::broadcast(point.into()))
+		.collect::<Vec<_>>();
+
+	// Evaluate each folding polynomial on the domain by summing the composites over the line
+	// between the two witnesses.
+	let n_composites = claim_0.composite_sums().len();
+	let n_packed = witness_0
+		.first()
+		.map_or(0, |multilin| multilin.evals().len());
+	let packed_sums = (0..n_packed)
+		.into_par_iter()
+		.try_fold(
+			|| vec![vec![P::zero(); points.len()]; n_composites],
+			|mut sums, i| -> Result<_, Error> {
+				let mut query = vec![P::zero(); witness_0.len()];
+				for (point_index, &point) in points.iter().enumerate() {
+					for (query_k, multilin_0, multilin_1) in izip!(&mut query, witness_0, witness_1)
+					{
+						let eval_0 = multilin_0.evals()[i];
+						*query_k = eval_0 + (multilin_1.evals()[i] - eval_0) * point;
+					}
+					for (composite_sums, composite_sum) in
+						sums.iter_mut().zip(claim_0.composite_sums())
+					{
+						composite_sums[point_index] +=
+							CompositionPoly::<P>::evaluate(&composite_sum.composition, &query)?;
+					}
+				}
+				Ok(sums)
+			},
+		)
+		.try_reduce(
+			|| vec![vec![P::zero(); points.len()]; n_composites],
+			|mut lhs, rhs| {
+				for (lhs, rhs) in lhs.iter_mut().zip(rhs) {
+					for (lhs, rhs) in lhs.iter_mut().zip(rhs) {
+						*lhs += rhs;
+					}
+				}
+				Ok(lhs)
+			},
+		)?;
+
+	let composite_coeffs = packed_sums
+		.into_iter()
+		.zip(claim_0.composite_degrees())
+		.map(|(packed_evals, &degree)| -> Result<_, Error> {
+			// Multilinears with fewer variables than the packing width leave the remaining
+			// scalars unused.
+			let evals = packed_evals
+				.iter()
+				.take(degree + 1)
+				.map(|packed| packed.iter().take(1 << n_vars).sum())
+				.collect::<Vec<F>>();
+			let domain = domain_factory.create(degree + 1)?;
+			Ok(domain.interpolate(&evals)?)
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+
+	challenger.observe_slice(&composite_sums(claims[0]));
+	challenger.observe_slice(&composite_sums(claims[1]));
+	for coeffs in &composite_coeffs {
+		challenger.observe_slice(coeffs);
+	}
+	let challenge = challenger.sample();
+
+	let folded_witness = witness_0
+		.par_iter()
+		.zip(witness_1)
+		.map(|(multilin_0, multilin_1)| {
+			let evals = multilin_0
+				.evals()
+				.iter()
+				.zip(multilin_1.evals())
+				.map(|(&eval_0, &eval_1)| eval_0 + (eval_1 - eval_0) * challenge)
+				.collect();
+			MultilinearExtension::from_values(evals)
+		})
+		.collect::<Result<Vec<_>, PolynomialError>>()?;
+
+	let claim = folded_claim(
+		claim_0,
+		composite_coeffs
+			.iter()
+			.map(|coeffs| evaluate_univariate(coeffs, challenge)),
+	)?;
+	Ok((FoldingOutput { challenge, claim }, folded_witness, FoldingProof { composite_coeffs }))
+}
@@ -0,0 +1,162 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::{
+	error::{Error, VerificationError},
+	fold_evals, fold_prove, fold_verify, FoldingProof,
+};
+use crate::{
+	challenger::new_hasher_challenger,
+	polynomial::{
+		CompositionPoly, IsomorphicEvaluationDomainFactory, MultilinearExtension, MultilinearQuery,
+	},
+	protocols::{
+		sumcheck_v2::{CompositeSumClaim, SumcheckClaim},
+		test_utils::TestProductComposition,
+	},
+};
+use assert_matches::assert_matches;
+use binius_field::{BinaryField128b, BinaryField8b, Field};
+use binius_hash::GroestlHasher;
+use rand::{rngs::StdRng, SeedableRng};
+use std::iter::repeat_with;
+
+type F = BinaryField128b;
+type FDomain = BinaryField8b;
+
+fn random_witness(
+	rng: &mut StdRng,
+	n_vars: usize,
+	n_multilinears: usize,
+) -> Vec<MultilinearExtension<F>> {
+	repeat_with(|| {
+		let values = repeat_with(|| <F as Field>::random(&mut *rng))
+			.take(1 << n_vars)
+			.collect();
+		MultilinearExtension::from_values(values).unwrap()
+	})
+	.take(n_multilinears)
+	.collect()
+}
+
+fn product_claim(
+	n_vars: usize,
+	witness: &[MultilinearExtension<F>],
+) -> SumcheckClaim<F, TestProductComposition> {
+	let composition = TestProductComposition::new(witness.len());
+	let sum = (0..1 << n_vars)
+		.map(|i| {
+			let query = witness
+				.iter()
+				.map(|multilin| multilin.evaluate_on_hypercube(i).unwrap())
+				.collect::<Vec<_>>();
+			CompositionPoly::<F>::evaluate(&composition, &query).unwrap()
+		})
+		.sum();
+	SumcheckClaim::new(n_vars, witness.len(), vec![CompositeSumClaim { composition, sum }]).unwrap()
+}
+
+#[test]
+fn test_fold_prove_verify_accumulates_claims() {
+	let n_vars = 5;
+	let n_multilinears = 3;
+	let mut rng = StdRng::seed_from_u64(0);
+	let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+
+	let mut acc_witness = random_witness(&mut rng, n_vars, n_multilinears);
+	let mut acc_claim = product_claim(n_vars, &acc_witness);
+	for _ in 0..3 {
+		let witness = random_witness(&mut rng, n_vars, n_multilinears);
+		let claim = product_claim(n_vars, &witness);
+
+		let (prover_output, folded_witness, proof) = fold_prove::<_, FDomain, _, _, _, _>(
+			[&acc_claim, &claim],
+			[acc_witness.as_slice(), witness.as_slice()],
+			domain_factory.clone(),
+			challenger.clone(),
+		)
+		.unwrap();
+		assert_eq!(proof.composite_coeffs[0].len(), n_multilinears + 1);
+
+		let verifier_output = fold_verify([&acc_claim, &claim], proof, challenger.clone()).unwrap();
+		assert_eq!(verifier_output.challenge, prover_output.challenge);
+		assert_eq!(
+			verifier_output.claim.composite_sums()[0].sum,
+			prover_output.claim.composite_sums()[0].sum
+		);
+
+		// The folded witness satisfies the folded claim.
+		let expected_claim = product_claim(n_vars, &folded_witness);
+		assert_eq!(
+			verifier_output.claim.composite_sums()[0].sum,
+			expected_claim.composite_sums()[0].sum
+		);
+
+		// Evaluations of the folded witness follow from evaluations of the original witnesses.
+		let point = repeat_with(|| <F as Field>::random(&mut rng))
+			.take(n_vars)
+			.collect::<Vec<_>>();
+		let query = MultilinearQuery::<F>::with_full_query(&point).unwrap();
+		let evals = |witness: &[MultilinearExtension<F>]| {
+			witness
+				.iter()
+				.map(|multilin| multilin.evaluate(&query).unwrap())
+				.collect::<Vec<F>>()
+		};
+		let (acc_evals, new_evals) = (evals(&acc_witness), evals(&witness));
+		assert_eq!(
+			fold_evals(verifier_output.challenge, [&acc_evals, &new_evals]).unwrap(),
+			evals(&folded_witness)
+		);
+
+		acc_witness = folded_witness;
+		acc_claim = verifier_output.claim;
+	}
+}
+
+#[test]
+fn test_fold_verify_rejects_incorrect_sum() {
+	let n_vars = 4;
+	let mut rng = StdRng::seed_from_u64(0);
+	let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+
+	let witnesses = [
+		random_witness(&mut rng, n_vars, 2),
+		random_witness(&mut rng, n_vars, 2),
+	];
+	let claim_0 = product_claim(n_vars, &witnesses[0]);
+	let claim_1 = product_claim(n_vars, &witnesses[1]);
+	let (_, _, proof) = fold_prove::<_, FDomain, _, _, _, _>(
+		[&claim_0, &claim_1],
+		[witnesses[0].as_slice(), witnesses[1].as_slice()],
+		domain_factory,
+		challenger.clone(),
+	)
+	.unwrap();
+
+	let bad_claim = SumcheckClaim::new(
+		n_vars,
+		2,
+		vec![CompositeSumClaim {
+			composition: TestProductComposition::new(2),
+			sum: claim_1.composite_sums()[0].sum + F::ONE,
+		}],
+	)
+	.unwrap();
+	assert_matches!(
+		fold_verify([&claim_0, &bad_claim], proof.clone(), challenger.clone()),
+		Err(Error::Verification(VerificationError::SumMismatch { index: 0 }))
+	);
+
+	let truncated = FoldingProof {
+		composite_coeffs: vec![proof.composite_coeffs[0][..2].to_vec()],
+	};
+	assert_matches!(
+		fold_verify([&claim_0, &claim_1], truncated, challenger),
+		Err(Error::Verification(VerificationError::NumberOfCoefficients {
+			index: 0,
+			expected: 3
+		}))
+	);
+}
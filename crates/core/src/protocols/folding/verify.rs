@@ -0,0 +1,68 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::{
+	common::{check_claim_shapes, composite_sums, folded_claim, FoldingOutput, FoldingProof},
+	error::{Error, VerificationError},
+};
+use crate::{
+	challenger::{CanObserve, CanSample},
+	polynomial::{evaluate_univariate, CompositionPoly},
+	protocols::sumcheck_v2::SumcheckClaim,
+};
+use binius_field::Field;
+use binius_utils::bail;
+use itertools::izip;
+
+/// Verify the folding of two sumcheck claims into one.
+///
+/// The claims must have the same shape and the same composition polynomials, which the verifier
+/// cannot check. The folded claim uses the compositions of the first claim.
+pub fn fold_verify<F, Composition, Challenger>(
+	claims: [&SumcheckClaim<F, Composition>; 2],
+	proof: FoldingProof<F>,
+	mut challenger: Challenger,
+) -> Result<FoldingOutput<F, Composition>, Error>
+where
+	F: Field,
+	Composition: CompositionPoly<F> + Clone,
+	Challenger: CanObserve<F> + CanSample<F>,
+{
+	check_claim_shapes(claims)?;
+
+	let FoldingProof { composite_coeffs } = proof;
+	let [claim_0, claim_1] = claims;
+	if composite_coeffs.len() != claim_0.composite_sums().len() {
+		bail!(VerificationError::NumberOfComposites);
+	}
+
+	let sums_0 = composite_sums(claim_0);
+	let sums_1 = composite_sums(claim_1);
+	for (index, (coeffs, &degree, &sum_0, &sum_1)) in
+		izip!(&composite_coeffs, claim_0.composite_degrees(), &sums_0, &sums_1).enumerate()
+	{
+		if coeffs.len() != degree + 1 {
+			bail!(VerificationError::NumberOfCoefficients {
+				index,
+				expected: degree + 1,
+			});
+		}
+		if coeffs[0] != sum_0 || coeffs.iter().copied().sum::<F>() != sum_1 {
+			bail!(VerificationError::SumMismatch { index });
+		}
+	}
+
+	challenger.observe_slice(&sums_0);
+	challenger.observe_slice(&sums_1);
+	for coeffs in &composite_coeffs {
+		challenger.observe_slice(coeffs);
+	}
+	let challenge = challenger.sample();
+
+	let claim = folded_claim(
+		claim_0,
+		composite_coeffs
+			.iter()
+			.map(|coeffs| evaluate_univariate(coeffs, challenge)),
+	)?;
+	Ok(FoldingOutput { challenge, claim })
+}
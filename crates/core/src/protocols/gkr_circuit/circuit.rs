@@ -0,0 +1,219 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::Error;
+use crate::{
+	oracle::MultilinearPolyOracle,
+	polynomial::CompositionPoly,
+	protocols::{
+		abstract_sumcheck::ReducedClaim, evalcheck::EvalcheckMultilinearClaim,
+		gkr_sumcheck::GkrSumcheckBatchProof,
+	},
+	witness::MultilinearWitness,
+};
+use binius_field::{Field, PackedField};
+use binius_utils::bail;
+use rayon::prelude::*;
+use std::marker::PhantomData;
+
+type LayerEvals<'a, FW> = &'a [Vec<FW>];
+
+/// A data-parallel layered circuit of fan-in-2 gates, with one gate per wire.
+///
+/// See the [module documentation](super) for the wiring of the gates.
+#[derive(Debug, Clone)]
+pub struct LayeredCircuit<C> {
+	gates: Vec<C>,
+}
+
+impl<C> LayeredCircuit<C> {
+	pub fn new(gates: Vec<C>) -> Self {
+		Self { gates }
+	}
+
+	/// Returns the number of wires in each layer of the circuit.
+	pub fn n_wires(&self) -> usize {
+		self.gates.len()
+	}
+
+	/// Returns the gate computing each wire, in wire order.
+	pub fn gates(&self) -> &[C] {
+		&self.gates
+	}
+
+	pub(super) fn check_gates<P: PackedField>(&self) -> Result<(), Error>
+	where
+		C: CompositionPoly<P>,
+	{
+		if self.gates.is_empty() {
+			bail!(Error::EmptyCircuit);
+		}
+		for (index, gate) in self.gates.iter().enumerate() {
+			if gate.n_vars() != 2 * self.n_wires() {
+				bail!(Error::InvalidGate { index });
+			}
+		}
+		Ok(())
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct LayeredCircuitClaim<F: Field> {
+	/// Oracles to the input wires of the circuit, in wire order
+	pub inputs: Vec<MultilinearPolyOracle<F>>,
+	/// Claimed values of the output wires, in wire order
+	pub outputs: Vec<F>,
+}
+
+impl<F: Field> LayeredCircuitClaim<F> {
+	/// Returns the base-two log of the number of inputs on each wire.
+	pub fn n_vars(&self) -> usize {
+		self.inputs.first().map_or(0, |input| input.n_vars())
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct LayeredCircuitWitness<'a, PW: PackedField> {
+	n_vars: usize,
+	// The ith element holds the values of each wire on the ith layer of the circuit
+	circuit_evals: Vec<Vec<Vec<PW::Scalar>>>,
+	_marker: PhantomData<MultilinearWitness<'a, PW>>,
+}
+
+impl<'a, PW: PackedField> LayeredCircuitWitness<'a, PW> {
+	/// Evaluates the circuit on the given input wires.
+	pub fn new<C>(
+		circuit: &LayeredCircuit<C>,
+		inputs: &[MultilinearWitness<'a, PW>],
+	) -> Result<Self, Error>
+	where
+		C: CompositionPoly<PW::Scalar>,
+	{
+		let n_vars = inputs.first().map_or(0, |input| input.n_vars());
+		if inputs.iter().any(|input| input.n_vars() != n_vars) {
+			bail!(Error::NumberOfVariablesMismatch);
+		}
+
+		let input_layer = inputs
+			.iter()
+			.map(|input| {
+				(0..1 << n_vars)
+					.into_par_iter()
+					.map(|i| input.evaluate_on_hypercube(i))
+					.collect::<Result<Vec<_>, _>>()
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+		Self::from_input_layer(circuit, n_vars, input_layer)
+	}
+
+	/// Evaluates the circuit on input wires given by their values over the hypercube.
+	///
+	/// This lets callers populate the input layer directly, without materializing the inputs as
+	/// multilinears first.
+	pub fn from_input_layer<C>(
+		circuit: &LayeredCircuit<C>,
+		n_vars: usize,
+		input_layer: Vec<Vec<PW::Scalar>>,
+	) -> Result<Self, Error>
+	where
+		C: CompositionPoly<PW::Scalar>,
+	{
+		circuit.check_gates::<PW::Scalar>()?;
+		if input_layer.len() != circuit.n_wires() {
+			bail!(Error::IncorrectNumberOfWires {
+				expected: circuit.n_wires(),
+			});
+		}
+		if input_layer.iter().any(|wire| wire.len() != 1 << n_vars) {
+			bail!(Error::NumberOfVariablesMismatch);
+		}
+
+		// Compute the circuit layers from bottom to top
+		let mut all_layers = vec![input_layer];
+		for curr_n_vars in (0..n_vars).rev() {
+			let layer_below = all_layers.last().expect("layers is not empty by invariant");
+			let new_layer = circuit
+				.gates
+				.iter()
+				.map(|gate| {
+					(0..1 << curr_n_vars)
+						.into_par_iter()
+						.map_init(
+							|| Vec::with_capacity(2 * layer_below.len()),
+							|query, i| {
+								query.clear();
+								query.extend(layer_below.iter().map(|wire| wire[i]));
+								query.extend(
+									layer_below.iter().map(|wire| wire[i + (1 << curr_n_vars)]),
+								);
+								gate.evaluate(query)
+							},
+						)
+						.collect::<Result<Vec<_>, _>>()
+				})
+				.collect::<Result<Vec<_>, _>>()?;
+			all_layers.push(new_layer);
+		}
+
+		// Reverse the layers
+		all_layers.reverse();
+		Ok(Self {
+			n_vars,
+			circuit_evals: all_layers,
+			_marker: PhantomData,
+		})
+	}
+
+	/// Returns the base-two log of the number of inputs on each wire
+	pub fn n_vars(&self) -> usize {
+		self.n_vars
+	}
+
+	/// Returns the values of the output wires, in wire order
+	pub fn outputs(&self) -> Vec<PW::Scalar> {
+		// By invariant, we will have n_vars + 1 layers, and the ith layer will have 2^i elements
+		// on each wire. Therefore, this array access is safe.
+		self.circuit_evals[0].iter().map(|wire| wire[0]).collect()
+	}
+
+	pub fn ith_layer_evals(&self, i: usize) -> Result<LayerEvals<'_, PW::Scalar>, Error> {
+		if i > self.n_vars() {
+			bail!(Error::InvalidLayerIndex);
+		}
+		Ok(&self.circuit_evals[i])
+	}
+}
+
+/// LayerClaim is a claim about the evaluation of one wire of the kth layer-multilinear at a
+/// specific evaluation point
+pub type LayerClaim<F> = ReducedClaim<F>;
+
+/// BatchLayerProof is the proof that reduces the kth layer of a batch of circuits to the (k+1)th
+/// layer
+///
+/// Notation:
+/// * $r'_k$ is challenge generated during the k-variate sumcheck reduction from layer k to layer
+/// k+1
+#[derive(Debug, Clone)]
+pub struct BatchLayerProof<F: Field> {
+	/// The proof of the batched sumcheck reduction (on $k$ variables) over all wires
+	pub gkr_sumcheck_batch_proof: GkrSumcheckBatchProof<F>,
+	/// For each circuit, the evaluations of each wire of the (k+1)th layer-multilinear at
+	/// evaluation point $(r'_k, 0)$
+	pub zero_evals: Vec<Vec<F>>,
+	/// For each circuit, the evaluations of each wire of the (k+1)th layer-multilinear at
+	/// evaluation point $(r'_k, 1)$
+	pub one_evals: Vec<Vec<F>>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LayeredCircuitBatchProof<F: Field> {
+	pub batch_layer_proofs: Vec<BatchLayerProof<F>>,
+}
+
+#[derive(Debug, Default)]
+pub struct LayeredCircuitBatchProveOutput<F: Field> {
+	// Reduced evalcheck claims on the input wires of each of the circuit claims
+	pub evalcheck_multilinear_claims: Vec<Vec<EvalcheckMultilinearClaim<F>>>,
+	// The batch proof
+	pub proof: LayeredCircuitBatchProof<F>,
+}
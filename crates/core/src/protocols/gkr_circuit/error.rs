@@ -0,0 +1,54 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use crate::{
+	polynomial::Error as PolynomialError,
+	protocols::{
+		abstract_sumcheck::Error as AbstractSumcheckError, gkr_sumcheck::Error as GkrSumcheckError,
+	},
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	#[error("the circuit must have at least one wire")]
+	EmptyCircuit,
+	#[error("gate {index} must have twice as many variables as the circuit has wires")]
+	InvalidGate { index: usize },
+	#[error("incorrect number of circuit wires, expected {expected}")]
+	IncorrectNumberOfWires { expected: usize },
+	#[error("circuit input wires must all have the same number of variables")]
+	NumberOfVariablesMismatch,
+	#[error("prover has mismatch between claim and witness")]
+	ProverClaimWitnessMismatch,
+	#[error("number of batch layer proofs does not match maximum claim n_vars")]
+	MismatchedClaimsAndProofs,
+	#[error("witneses and claims have mismatched lengths")]
+	MismatchedWitnessClaimLength,
+	#[error("empty claims array")]
+	EmptyClaimsArray,
+	#[error("too many rounds")]
+	TooManyRounds,
+	#[error("finalize called prematurely")]
+	PrematureFinalize,
+	#[error("all layer claims in a batch should be for the same layer")]
+	MismatchedEvalPointLength,
+	#[error("proof has invalid zero-one eval advice")]
+	InvalidZeroOneEvalAdvice,
+	#[error("the inputted layer index was too high")]
+	InvalidLayerIndex,
+	#[error("polynomial error: {0}")]
+	Polynomial(#[from] PolynomialError),
+	#[error("abstract sumcheck failure: {0}")]
+	AbstractSumcheck(#[from] AbstractSumcheckError),
+	#[error("gkr sumcheck failure: {0}")]
+	GkrSumcheckError(#[from] GkrSumcheckError),
+	#[error("verification error: {0}")]
+	Verification(#[from] VerificationError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum VerificationError {
+	#[error("number of zero evals in batch proof does not match number of claims and wires")]
+	MismatchedZeroEvals,
+	#[error("number of one evals in batch proof does not match number of claims and wires")]
+	MismatchedOneEvals,
+}
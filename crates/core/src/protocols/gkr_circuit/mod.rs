@@ -0,0 +1,40 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! A GKR protocol for data-parallel layered circuits of fan-in-2 gates.
+//!
+//! The grand product argument in [`crate::protocols::gkr_gpa`] is the instance of this protocol
+//! for the grand product circuit. Every layer of a [`LayeredCircuit`] has the same number of
+//! wires and each wire is computed from the wires of the layer below by a gate, given as a
+//! composition polynomial. Layer $k$ holds $2^k$ values per wire and the input layer holds $2^n$
+//! values per wire.
+//!
+//! Gate $j$ computes the value of wire $j$ at index $i$ of layer $k$ from the values of all wires
+//! of layer $k + 1$ at indices $i$ and $i + 2^k$. The gate inputs are ordered as
+//! $(w_0(i), \ldots, w_{m-1}(i), w_0(i + 2^k), \ldots, w_{m-1}(i + 2^k))$, so a gate has $2m$
+//! variables for a circuit with $m$ wires. For example, the grand product circuit has one wire
+//! with a bivariate product gate, and the fractional sum circuit used by LogUp-GKR has a numerator
+//! and a denominator wire with gates $p_0 q_1 + p_1 q_0$ and $q_0 q_1$.
+//!
+//! The protocol reduces claims on the output layer to multilinear evalcheck claims on the input
+//! wires, one GKR sumcheck per layer, batched over all wires and circuit instances. The naming
+//! convention for challenges follows [`crate::protocols::gkr_gpa`].
+//!
+//! See [Thaler13] Section 5.3.1 for background on the GKR polynomial identities for a binary tree
+//! circuit.
+//!
+//! [Thaler13]: <https://eprint.iacr.org/2013/351>
+
+mod circuit;
+mod error;
+mod prove;
+#[cfg(test)]
+mod tests;
+mod verify;
+
+pub use circuit::{
+	LayeredCircuit, LayeredCircuitBatchProof, LayeredCircuitBatchProveOutput, LayeredCircuitClaim,
+	LayeredCircuitWitness,
+};
+pub use error::*;
+pub use prove::*;
+pub use verify::*;
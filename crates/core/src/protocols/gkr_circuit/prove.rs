@@ -0,0 +1,434 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::{
+	circuit::{
+		BatchLayerProof, LayerClaim, LayeredCircuit, LayeredCircuitBatchProof,
+		LayeredCircuitBatchProveOutput, LayeredCircuitClaim, LayeredCircuitWitness,
+	},
+	Error,
+};
+use crate::{
+	oracle::MultilinearPolyOracle,
+	polynomial::{
+		extrapolate_line_scalar, CompositionPoly, EvaluationDomainFactory, MultilinearComposite,
+		MultilinearExtension, MultilinearPoly, MultilinearQuery,
+	},
+	protocols::{
+		evalcheck::EvalcheckMultilinearClaim,
+		gkr_sumcheck::{
+			self, GkrSumcheckBatchProof, GkrSumcheckBatchProveOutput, GkrSumcheckClaim,
+			GkrSumcheckWitness,
+		},
+	},
+	witness::MultilinearWitness,
+};
+use binius_field::{ExtensionField, Field, PackedExtension, PackedField, TowerField};
+use binius_utils::{
+	bail,
+	sorting::{stable_sort, unsort},
+};
+use itertools::izip;
+use p3_challenger::{CanObserve, CanSample};
+use std::sync::Arc;
+use tracing::instrument;
+
+type MultilinWitnessPair<'a, P> = (MultilinearWitness<'a, P>, MultilinearWitness<'a, P>);
+
+/// Proves batch reduction turning each LayeredCircuitClaim into EvalcheckMultilinearClaims on its
+/// input wires
+///
+/// REQUIRES:
+/// * witnesses and claims are of the same length
+/// * The ith witness corresponds to the ith claim
+#[instrument(skip_all, name = "gkr_circuit::batch_prove", level = "debug")]
+pub fn batch_prove<'a, F, PW, C, DomainField, Challenger>(
+	circuit: &LayeredCircuit<C>,
+	witnesses: impl IntoIterator<Item = LayeredCircuitWitness<'a, PW>>,
+	claims: impl IntoIterator<Item = LayeredCircuitClaim<F>>,
+	evaluation_domain_factory: impl EvaluationDomainFactory<DomainField>,
+	mut challenger: Challenger,
+) -> Result<LayeredCircuitBatchProveOutput<F>, Error>
+where
+	F: TowerField + From<PW::Scalar>,
+	PW: PackedExtension<DomainField>,
+	DomainField: Field,
+	PW::Scalar: Field + From<F> + ExtensionField<DomainField>,
+	C: CompositionPoly<PW> + Clone,
+	Challenger: CanSample<F> + CanObserve<F>,
+{
+	circuit.check_gates::<PW>()?;
+
+	let witness_vec = witnesses.into_iter().collect::<Vec<_>>();
+	let claim_vec = claims.into_iter().collect::<Vec<_>>();
+
+	let n_claims = claim_vec.len();
+	if n_claims == 0 {
+		return Ok(LayeredCircuitBatchProveOutput::default());
+	}
+	if witness_vec.len() != n_claims {
+		bail!(Error::MismatchedWitnessClaimLength);
+	}
+
+	let provers_vec = witness_vec
+		.into_iter()
+		.zip(claim_vec)
+		.map(|(witness, claim)| LayeredCircuitProverState::new(circuit, &claim, witness))
+		.collect::<Result<Vec<_>, _>>()?;
+
+	let (original_indices, mut sorted_provers) =
+		stable_sort(provers_vec, |prover| prover.input_vars(), true);
+
+	let max_n_vars = sorted_provers
+		.first()
+		.expect("sorted_provers is not empty by invariant")
+		.input_vars();
+
+	let mut batch_layer_proofs = Vec::with_capacity(max_n_vars);
+	let mut reverse_sorted_evalcheck_multilinear_claims = Vec::with_capacity(n_claims);
+
+	for layer_no in 0..max_n_vars {
+		// Step 1: Process finished provers
+		process_finished_provers(
+			n_claims,
+			layer_no,
+			&mut sorted_provers,
+			&mut reverse_sorted_evalcheck_multilinear_claims,
+		)?;
+
+		// Step 2: Create sumcheck batch proof over all wires of all circuits
+		let (gkr_sumcheck_batch_proof, sumcheck_challenge) = if layer_no == 0 {
+			let sorted_evals = sorted_provers
+				.iter()
+				.flat_map(|prover| prover.current_layer_claims.iter().map(|claim| claim.eval))
+				.collect::<Vec<F>>();
+			// Need to sample batching coefficients for prover-verifier challenger consistency
+			let _batching_coeffs = challenger.sample_vec(sorted_evals.len() - 1);
+			let proof = GkrSumcheckBatchProof {
+				rounds: vec![],
+				sorted_evals,
+			};
+			(proof, vec![])
+		} else {
+			let mut sumcheck_claims_and_witnesses = Vec::new();
+			for prover in &sorted_provers {
+				sumcheck_claims_and_witnesses
+					.extend(prover.stage_gkr_sumcheck_claims_and_witnesses(circuit)?);
+			}
+			let GkrSumcheckBatchProveOutput {
+				proof,
+				reduced_claims,
+			} = gkr_sumcheck::batch_prove(
+				sumcheck_claims_and_witnesses,
+				evaluation_domain_factory.clone(),
+				|_| 1,
+				&mut challenger,
+			)?;
+			let sumcheck_challenge = reduced_claims[0].eval_point.clone();
+			(proof, sumcheck_challenge)
+		};
+
+		// Step 3: Get (and observe) zero and one evaluations of the (k+1)th layer-multilinears
+		let (zero_evals, one_evals) = sorted_provers
+			.iter()
+			.map(|p| p.advise_sumcheck_prove(&sumcheck_challenge))
+			.collect::<Result<Vec<_>, _>>()?
+			.into_iter()
+			.unzip::<_, _, Vec<_>, Vec<_>>();
+		challenger.observe_slice(&zero_evals.concat());
+		challenger.observe_slice(&one_evals.concat());
+
+		// Step 4: Sample a challenge for the next layer
+		let gkr_challenge = challenger.sample();
+
+		// Step 5: Finalize each prover to update its internal current_layer_claims
+		for (prover, zero_evals, one_evals) in izip!(&mut sorted_provers, &zero_evals, &one_evals) {
+			prover.finalize_batch_layer_proof(
+				zero_evals,
+				one_evals,
+				&sumcheck_challenge,
+				gkr_challenge,
+			)?;
+		}
+
+		batch_layer_proofs.push(BatchLayerProof {
+			gkr_sumcheck_batch_proof,
+			zero_evals,
+			one_evals,
+		});
+	}
+	process_finished_provers(
+		n_claims,
+		max_n_vars,
+		&mut sorted_provers,
+		&mut reverse_sorted_evalcheck_multilinear_claims,
+	)?;
+
+	debug_assert!(sorted_provers.is_empty());
+	debug_assert_eq!(reverse_sorted_evalcheck_multilinear_claims.len(), n_claims);
+
+	reverse_sorted_evalcheck_multilinear_claims.reverse();
+	let sorted_evalcheck_multilinear_claims = reverse_sorted_evalcheck_multilinear_claims;
+
+	let evalcheck_multilinear_claims =
+		unsort(original_indices, sorted_evalcheck_multilinear_claims);
+
+	Ok(LayeredCircuitBatchProveOutput {
+		evalcheck_multilinear_claims,
+		proof: LayeredCircuitBatchProof { batch_layer_proofs },
+	})
+}
+
+fn process_finished_provers<F, PW>(
+	n_claims: usize,
+	layer_no: usize,
+	sorted_provers: &mut Vec<LayeredCircuitProverState<'_, F, PW>>,
+	reverse_sorted_evalcheck_multilinear_claims: &mut Vec<Vec<EvalcheckMultilinearClaim<F>>>,
+) -> Result<(), Error>
+where
+	PW: PackedField,
+	F: Field + From<PW::Scalar>,
+	PW::Scalar: Field + From<F>,
+{
+	while !sorted_provers.is_empty() && sorted_provers.last().unwrap().input_vars() == layer_no {
+		debug_assert!(layer_no > 0);
+		let finished_prover = sorted_provers.pop().unwrap();
+		let evalcheck_claims = finished_prover.finalize()?;
+		reverse_sorted_evalcheck_multilinear_claims.push(evalcheck_claims);
+		debug_assert_eq!(
+			sorted_provers.len() + reverse_sorted_evalcheck_multilinear_claims.len(),
+			n_claims
+		);
+	}
+	Ok(())
+}
+
+/// Layered circuit GKR protocol prover state
+///
+/// Coordinates the proving of a layered circuit claim before and after the sumcheck-based layer
+/// reductions.
+#[derive(Debug)]
+struct LayeredCircuitProverState<'a, F, PW>
+where
+	F: Field + From<PW::Scalar>,
+	PW: PackedField,
+	PW::Scalar: Field + From<F>,
+{
+	// Input wire oracles
+	inputs: Vec<MultilinearPolyOracle<F>>,
+	// The ith element holds the wires of the ith layer of the circuit as multilinear polynomials
+	layers: Vec<Vec<MultilinearWitness<'a, PW>>>,
+	// The ith element holds the first and second halves of each wire of the (i+1)th layer
+	next_layer_halves: Vec<Vec<MultilinWitnessPair<'a, PW>>>,
+	// The current claims about each wire of a layer multilinear of the circuit
+	current_layer_claims: Vec<LayerClaim<F>>,
+}
+
+impl<'a, F, PW> LayeredCircuitProverState<'a, F, PW>
+where
+	F: Field + From<PW::Scalar>,
+	PW: PackedField,
+	PW::Scalar: Field + From<F>,
+{
+	fn new<C>(
+		circuit: &LayeredCircuit<C>,
+		claim: &LayeredCircuitClaim<F>,
+		witness: LayeredCircuitWitness<'a, PW>,
+	) -> Result<Self, Error> {
+		let n_vars = claim.n_vars();
+		let outputs = witness.outputs();
+		if claim.inputs.len() != circuit.n_wires()
+			|| claim.outputs.len() != circuit.n_wires()
+			|| claim.inputs.iter().any(|input| input.n_vars() != n_vars)
+			|| n_vars != witness.n_vars()
+			|| outputs
+				.iter()
+				.zip(&claim.outputs)
+				.any(|(&output, &claimed)| output != claimed.into())
+		{
+			bail!(Error::ProverClaimWitnessMismatch);
+		}
+
+		// Build multilinear polynomials from circuit evaluations
+		let n_layers = n_vars + 1;
+		let next_layer_halves = (1..n_layers)
+			.map(|i| {
+				witness
+					.ith_layer_evals(i)?
+					.iter()
+					.map(|wire_evals| {
+						let half = wire_evals.len() / 2;
+						debug_assert_eq!(half, 1 << (i - 1));
+						let left = MultilinearExtension::from_values_generic(Arc::from(
+							&wire_evals[..half],
+						))?
+						.specialize_arc_dyn();
+						let right = MultilinearExtension::from_values_generic(Arc::from(
+							&wire_evals[half..],
+						))?
+						.specialize_arc_dyn();
+						Ok((left, right))
+					})
+					.collect::<Result<Vec<_>, Error>>()
+			})
+			.collect::<Result<Vec<_>, Error>>()?;
+
+		let layers = (0..n_layers)
+			.map(|i| {
+				witness
+					.ith_layer_evals(i)?
+					.iter()
+					.map(|wire_evals| {
+						let mle = MultilinearExtension::from_values_generic(Arc::from(
+							wire_evals.as_slice(),
+						))?
+						.specialize_arc_dyn();
+						Ok(mle)
+					})
+					.collect::<Result<Vec<_>, Error>>()
+			})
+			.collect::<Result<Vec<_>, Error>>()?;
+
+		debug_assert_eq!(next_layer_halves.len(), n_vars);
+		debug_assert_eq!(layers.len(), n_vars + 1);
+
+		let current_layer_claims = claim
+			.outputs
+			.iter()
+			.map(|&output| LayerClaim {
+				eval_point: vec![],
+				eval: output,
+			})
+			.collect();
+
+		Ok(Self {
+			inputs: claim.inputs.clone(),
+			layers,
+			next_layer_halves,
+			current_layer_claims,
+		})
+	}
+
+	fn input_vars(&self) -> usize {
+		self.inputs.first().map_or(0, |input| input.n_vars())
+	}
+
+	fn current_layer_no(&self) -> usize {
+		self.current_layer_claims
+			.first()
+			.map_or(0, |claim| claim.eval_point.len())
+	}
+
+	// Create GKR sumcheck claims and witnesses, one per wire
+	#[allow(clippy::type_complexity)]
+	fn stage_gkr_sumcheck_claims_and_witnesses<C>(
+		&self,
+		circuit: &LayeredCircuit<C>,
+	) -> Result<
+		Vec<(GkrSumcheckClaim<F>, GkrSumcheckWitness<PW, C, MultilinearWitness<'a, PW>>)>,
+		Error,
+	>
+	where
+		C: CompositionPoly<PW> + Clone,
+	{
+		let layer_no = self.current_layer_no();
+		if layer_no >= self.input_vars() {
+			bail!(Error::TooManyRounds);
+		}
+
+		let halves = &self.next_layer_halves[layer_no];
+		let gate_inputs = halves
+			.iter()
+			.map(|(left, _)| left.clone())
+			.chain(halves.iter().map(|(_, right)| right.clone()))
+			.collect::<Vec<_>>();
+
+		izip!(circuit.gates(), &self.layers[layer_no], &self.current_layer_claims)
+			.map(|(gate, current_layer, layer_claim)| {
+				let poly = MultilinearComposite::<PW, _, _>::new(
+					layer_no,
+					gate.clone(),
+					gate_inputs.clone(),
+				)?;
+				let witness = GkrSumcheckWitness {
+					poly,
+					current_layer: current_layer.clone(),
+				};
+				let claim = GkrSumcheckClaim {
+					n_vars: layer_no,
+					degree: gate.degree(),
+					sum: layer_claim.eval,
+					r: layer_claim.eval_point.clone(),
+				};
+				Ok((claim, witness))
+			})
+			.collect()
+	}
+
+	// Give the evaluations of each wire of the (k+1)th layer at the evaluation points (r'_k, 0)
+	// and (r'_k, 1)
+	fn advise_sumcheck_prove(&self, sumcheck_eval_point: &[F]) -> Result<(Vec<F>, Vec<F>), Error> {
+		let layer_no = self.current_layer_no();
+		if layer_no >= self.input_vars() {
+			bail!(Error::TooManyRounds);
+		}
+
+		let query = sumcheck_eval_point
+			.iter()
+			.cloned()
+			.map(Into::into)
+			.collect::<Vec<_>>();
+		let multilinear_query = MultilinearQuery::with_full_query(&query)?;
+
+		let mut zero_evals = Vec::with_capacity(self.next_layer_halves[layer_no].len());
+		let mut one_evals = Vec::with_capacity(self.next_layer_halves[layer_no].len());
+		for (left, right) in &self.next_layer_halves[layer_no] {
+			zero_evals.push(left.evaluate(&multilinear_query)?.into());
+			one_evals.push(right.evaluate(&multilinear_query)?.into());
+		}
+		Ok((zero_evals, one_evals))
+	}
+
+	fn finalize_batch_layer_proof(
+		&mut self,
+		zero_evals: &[F],
+		one_evals: &[F],
+		sumcheck_challenge: &[F],
+		gkr_challenge: F,
+	) -> Result<(), Error> {
+		if self.current_layer_no() >= self.input_vars() {
+			bail!(Error::TooManyRounds);
+		}
+
+		let mut layer_challenge = sumcheck_challenge.to_vec();
+		layer_challenge.push(gkr_challenge);
+
+		self.current_layer_claims = zero_evals
+			.iter()
+			.zip(one_evals)
+			.map(|(&zero_eval, &one_eval)| LayerClaim {
+				eval_point: layer_challenge.clone(),
+				eval: extrapolate_line_scalar(zero_eval, one_eval, gkr_challenge),
+			})
+			.collect();
+		Ok(())
+	}
+
+	fn finalize(self) -> Result<Vec<EvalcheckMultilinearClaim<F>>, Error> {
+		if self.current_layer_no() != self.input_vars() {
+			bail!(Error::PrematureFinalize);
+		}
+
+		let evalcheck_multilinear_claims = self
+			.inputs
+			.into_iter()
+			.zip(self.current_layer_claims)
+			.map(|(poly, layer_claim)| EvalcheckMultilinearClaim {
+				poly,
+				eval_point: layer_claim.eval_point,
+				eval: layer_claim.eval,
+				is_random_point: true,
+			})
+			.collect();
+		Ok(evalcheck_multilinear_claims)
+	}
+}
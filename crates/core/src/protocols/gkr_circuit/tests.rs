@@ -0,0 +1,186 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::{
+	batch_prove, batch_verify, Error, LayeredCircuit, LayeredCircuitBatchProveOutput,
+	LayeredCircuitClaim, LayeredCircuitWitness,
+};
+use crate::{
+	challenger::new_hasher_challenger,
+	oracle::MultilinearOracleSet,
+	polynomial::{
+		composition::BivariateProduct, CompositionPoly, Error as PolynomialError,
+		IsomorphicEvaluationDomainFactory, MultilinearExtension, MultilinearPoly, MultilinearQuery,
+	},
+	witness::MultilinearWitness,
+};
+use assert_matches::assert_matches;
+use binius_field::{BinaryField128b, BinaryField32b, Field, PackedField, TowerField};
+use binius_hash::GroestlHasher;
+use binius_utils::bail;
+use itertools::izip;
+use rand::{rngs::StdRng, SeedableRng};
+use std::iter::repeat_with;
+
+type F = BinaryField128b;
+type FS = BinaryField32b;
+
+/// The gates of the fractional sum circuit, adding fractions $p_0 / q_0 + p_1 / q_1$.
+#[derive(Debug, Clone, Copy)]
+enum FractionSumGate {
+	Numerator,
+	Denominator,
+}
+
+impl<P: PackedField> CompositionPoly<P> for FractionSumGate {
+	fn n_vars(&self) -> usize {
+		4
+	}
+
+	fn degree(&self) -> usize {
+		2
+	}
+
+	fn evaluate(&self, query: &[P]) -> Result<P, PolynomialError> {
+		if query.len() != 4 {
+			bail!(PolynomialError::IncorrectQuerySize { expected: 4 });
+		}
+		let (p_0, q_0, p_1, q_1) = (query[0], query[1], query[2], query[3]);
+		Ok(match self {
+			Self::Numerator => p_0 * q_1 + p_1 * q_0,
+			Self::Denominator => q_0 * q_1,
+		})
+	}
+
+	fn binary_tower_level(&self) -> usize {
+		0
+	}
+}
+
+struct CircuitInstance<'a> {
+	claim: LayeredCircuitClaim<F>,
+	witness: LayeredCircuitWitness<'a, F>,
+	inputs: Vec<MultilinearWitness<'a, F>>,
+}
+
+fn create_instance<'a, C: CompositionPoly<F>>(
+	rng: &mut StdRng,
+	oracles: &mut MultilinearOracleSet<F>,
+	circuit: &LayeredCircuit<C>,
+	n_vars: usize,
+) -> CircuitInstance<'a> {
+	let batch_id = oracles.add_committed_batch(n_vars, F::TOWER_LEVEL);
+	let input_oracles = (0..circuit.n_wires())
+		.map(|_| {
+			let id = oracles.add_committed(batch_id);
+			oracles.oracle(id)
+		})
+		.collect::<Vec<_>>();
+	let inputs = repeat_with(|| {
+		let values = repeat_with(|| <F as Field>::random(&mut *rng))
+			.take(1 << n_vars)
+			.collect();
+		MultilinearExtension::from_values(values)
+			.unwrap()
+			.specialize_arc_dyn()
+	})
+	.take(circuit.n_wires())
+	.collect::<Vec<MultilinearWitness<F>>>();
+
+	let witness = LayeredCircuitWitness::new(circuit, &inputs).unwrap();
+	let claim = LayeredCircuitClaim {
+		inputs: input_oracles,
+		outputs: witness.outputs(),
+	};
+	CircuitInstance {
+		claim,
+		witness,
+		inputs,
+	}
+}
+
+fn test_prove_verify_helper<C>(circuit: LayeredCircuit<C>, n_vars: &[usize])
+where
+	C: CompositionPoly<F> + Clone,
+{
+	let mut rng = StdRng::seed_from_u64(0);
+	let mut oracles = MultilinearOracleSet::new();
+	let instances = n_vars
+		.iter()
+		.map(|&n_vars| create_instance(&mut rng, &mut oracles, &circuit, n_vars))
+		.collect::<Vec<_>>();
+	let claims = instances
+		.iter()
+		.map(|instance| instance.claim.clone())
+		.collect::<Vec<_>>();
+
+	let prover_challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	let verifier_challenger = prover_challenger.clone();
+	let domain_factory = IsomorphicEvaluationDomainFactory::<FS>::default();
+
+	let LayeredCircuitBatchProveOutput {
+		evalcheck_multilinear_claims,
+		proof,
+	} = batch_prove::<_, _, _, FS, _>(
+		&circuit,
+		instances.iter().map(|instance| instance.witness.clone()),
+		claims.clone(),
+		domain_factory,
+		prover_challenger,
+	)
+	.unwrap();
+
+	let verified_evalcheck_multilinear_claims =
+		batch_verify(&circuit, claims.clone(), proof.clone(), verifier_challenger.clone()).unwrap();
+	assert_eq!(evalcheck_multilinear_claims.len(), instances.len());
+	assert_eq!(verified_evalcheck_multilinear_claims.len(), instances.len());
+
+	for (proved_claims, verified_claims, instance) in
+		izip!(&evalcheck_multilinear_claims, &verified_evalcheck_multilinear_claims, &instances)
+	{
+		assert_eq!(proved_claims.len(), circuit.n_wires());
+		for (proved, verified, input_oracle, input) in
+			izip!(proved_claims, verified_claims, &instance.claim.inputs, &instance.inputs)
+		{
+			assert_eq!(proved.eval, verified.eval);
+			assert_eq!(proved.eval_point, verified.eval_point);
+			assert_eq!(&verified.poly, input_oracle);
+			assert!(verified.is_random_point);
+
+			// The reduced claims are true evaluations of the input wires.
+			let query = MultilinearQuery::<F>::with_full_query(&verified.eval_point).unwrap();
+			assert_eq!(input.evaluate(&query).unwrap(), verified.eval);
+		}
+	}
+
+	// Incorrect zero-one evaluation advice is rejected.
+	let mut bad_proof = proof;
+	bad_proof.batch_layer_proofs[0].zero_evals[0][0] += F::ONE;
+	assert_matches!(
+		batch_verify(&circuit, claims, bad_proof, verifier_challenger),
+		Err(Error::InvalidZeroOneEvalAdvice)
+	);
+}
+
+#[test]
+fn test_prove_verify_grand_product_circuit() {
+	test_prove_verify_helper(LayeredCircuit::new(vec![BivariateProduct]), &[5, 4, 7]);
+}
+
+#[test]
+fn test_prove_verify_fraction_sum_circuit() {
+	let circuit =
+		LayeredCircuit::new(vec![FractionSumGate::Numerator, FractionSumGate::Denominator]);
+	test_prove_verify_helper(circuit, &[6, 3, 6]);
+}
+
+#[test]
+fn test_invalid_gate_arity() {
+	let circuit = LayeredCircuit::new(vec![BivariateProduct, BivariateProduct]);
+	let input = MultilinearExtension::from_values(vec![F::ONE; 4])
+		.unwrap()
+		.specialize_arc_dyn();
+	assert_matches!(
+		LayeredCircuitWitness::<F>::new(&circuit, &[input.clone(), input]),
+		Err(Error::InvalidGate { index: 0 })
+	);
+}
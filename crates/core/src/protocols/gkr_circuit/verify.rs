@@ -0,0 +1,250 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::{
+	circuit::{BatchLayerProof, LayerClaim, LayeredCircuit, LayeredCircuitBatchProof},
+	Error, LayeredCircuitClaim, VerificationError,
+};
+use crate::{
+	polynomial::{extrapolate_line_scalar, CompositionPoly},
+	protocols::{
+		evalcheck::EvalcheckMultilinearClaim,
+		gkr_sumcheck::{self, GkrSumcheckClaim},
+	},
+};
+use binius_field::{Field, TowerField};
+use binius_utils::{
+	bail,
+	sorting::{stable_sort, unsort},
+};
+use itertools::izip;
+use p3_challenger::{CanObserve, CanSample};
+use tracing::instrument;
+
+/// Verifies batch reduction turning each LayeredCircuitClaim into EvalcheckMultilinearClaims on
+/// its input wires
+#[instrument(skip_all, name = "gkr_circuit::batch_verify", level = "debug")]
+pub fn batch_verify<F, C, Challenger>(
+	circuit: &LayeredCircuit<C>,
+	claims: impl IntoIterator<Item = LayeredCircuitClaim<F>>,
+	proof: LayeredCircuitBatchProof<F>,
+	mut challenger: Challenger,
+) -> Result<Vec<Vec<EvalcheckMultilinearClaim<F>>>, Error>
+where
+	F: TowerField,
+	C: CompositionPoly<F>,
+	Challenger: CanSample<F> + CanObserve<F>,
+{
+	circuit.check_gates::<F>()?;
+
+	let LayeredCircuitBatchProof { batch_layer_proofs } = proof;
+
+	let (original_indices, mut sorted_claims) = stable_sort(claims, |claim| claim.n_vars(), true);
+	let max_n_vars = sorted_claims
+		.first()
+		.map(|claim| claim.n_vars())
+		.ok_or(Error::EmptyClaimsArray)?;
+
+	if max_n_vars != batch_layer_proofs.len() {
+		bail!(Error::MismatchedClaimsAndProofs);
+	}
+
+	for claim in &sorted_claims {
+		if claim.inputs.len() != circuit.n_wires() || claim.outputs.len() != circuit.n_wires() {
+			bail!(Error::IncorrectNumberOfWires {
+				expected: circuit.n_wires(),
+			});
+		}
+		if claim
+			.inputs
+			.iter()
+			.any(|input| input.n_vars() != claim.n_vars())
+		{
+			bail!(Error::NumberOfVariablesMismatch);
+		}
+	}
+
+	// Create the LayerClaims on the output wires of each of the claims
+	let mut layer_claims = sorted_claims
+		.iter()
+		.map(|claim| {
+			claim
+				.outputs
+				.iter()
+				.map(|&output| LayerClaim {
+					eval_point: vec![],
+					eval: output,
+				})
+				.collect::<Vec<_>>()
+		})
+		.collect::<Vec<_>>();
+
+	let n_claims = sorted_claims.len();
+	let mut reverse_sorted_evalcheck_claims = Vec::with_capacity(n_claims);
+
+	for (layer_no, batch_layer_proof) in batch_layer_proofs.into_iter().enumerate() {
+		process_finished_claims(
+			n_claims,
+			layer_no,
+			&mut layer_claims,
+			&mut sorted_claims,
+			&mut reverse_sorted_evalcheck_claims,
+		);
+
+		layer_claims =
+			reduce_layer_claim_batch(circuit, layer_claims, batch_layer_proof, &mut challenger)?;
+	}
+	process_finished_claims(
+		n_claims,
+		max_n_vars,
+		&mut layer_claims,
+		&mut sorted_claims,
+		&mut reverse_sorted_evalcheck_claims,
+	);
+
+	debug_assert!(layer_claims.is_empty());
+	debug_assert_eq!(reverse_sorted_evalcheck_claims.len(), n_claims);
+
+	reverse_sorted_evalcheck_claims.reverse();
+	let sorted_evalcheck_claims = reverse_sorted_evalcheck_claims;
+
+	let evalcheck_multilinear_claims = unsort(original_indices, sorted_evalcheck_claims);
+	Ok(evalcheck_multilinear_claims)
+}
+
+fn process_finished_claims<F: Field>(
+	n_claims: usize,
+	layer_no: usize,
+	layer_claims: &mut Vec<Vec<LayerClaim<F>>>,
+	sorted_claims: &mut Vec<LayeredCircuitClaim<F>>,
+	reverse_sorted_evalcheck_multilinear_claims: &mut Vec<Vec<EvalcheckMultilinearClaim<F>>>,
+) {
+	while !sorted_claims.is_empty() && sorted_claims.last().unwrap().n_vars() == layer_no {
+		debug_assert!(layer_no > 0);
+		debug_assert_eq!(sorted_claims.len(), layer_claims.len());
+		let finished_layer_claims = layer_claims.pop().unwrap();
+		let finished_original_claim = sorted_claims.pop().unwrap();
+		let evalcheck_multilinear_claims = finished_original_claim
+			.inputs
+			.into_iter()
+			.zip(finished_layer_claims)
+			.map(|(poly, layer_claim)| EvalcheckMultilinearClaim {
+				poly,
+				eval: layer_claim.eval,
+				eval_point: layer_claim.eval_point,
+				is_random_point: true,
+			})
+			.collect();
+		reverse_sorted_evalcheck_multilinear_claims.push(evalcheck_multilinear_claims);
+		debug_assert_eq!(
+			sorted_claims.len() + reverse_sorted_evalcheck_multilinear_claims.len(),
+			n_claims
+		);
+	}
+}
+
+/// Reduces the kth layer LayerClaims of n circuits to their (k+1)th layer LayerClaims
+///
+/// Arguments
+/// * `circuit` - The circuit whose gates relate the layers
+/// * `claims` - The kth layer LayerClaims, for each circuit and wire
+/// * `proof` - The batch layer proof that reduces the kth layer claims of the circuits to the
+///   (k+1)th
+/// * `challenger` - The verifier challenger
+fn reduce_layer_claim_batch<F, C, CH>(
+	circuit: &LayeredCircuit<C>,
+	claims: Vec<Vec<LayerClaim<F>>>,
+	proof: BatchLayerProof<F>,
+	mut challenger: CH,
+) -> Result<Vec<Vec<LayerClaim<F>>>, Error>
+where
+	F: Field,
+	C: CompositionPoly<F>,
+	CH: CanSample<F> + CanObserve<F>,
+{
+	let BatchLayerProof {
+		gkr_sumcheck_batch_proof,
+		zero_evals,
+		one_evals,
+	} = proof;
+
+	// Validation
+	if claims.is_empty() {
+		return Ok(vec![]);
+	} else if zero_evals.len() != claims.len()
+		|| zero_evals
+			.iter()
+			.any(|evals| evals.len() != circuit.n_wires())
+	{
+		bail!(VerificationError::MismatchedZeroEvals);
+	} else if one_evals.len() != claims.len()
+		|| one_evals
+			.iter()
+			.any(|evals| evals.len() != circuit.n_wires())
+	{
+		bail!(VerificationError::MismatchedOneEvals);
+	}
+
+	let curr_layer_challenge = &claims[0][0].eval_point[..];
+	if !claims
+		.iter()
+		.flatten()
+		.all(|claim| claim.eval_point == curr_layer_challenge)
+	{
+		bail!(Error::MismatchedEvalPointLength);
+	}
+
+	// Verify the gkr sumcheck batch proof and receive the corresponding reduced claims
+	let gkr_sumcheck_claims = claims.iter().flat_map(|wire_claims| {
+		wire_claims
+			.iter()
+			.zip(circuit.gates())
+			.map(|(claim, gate)| GkrSumcheckClaim {
+				sum: claim.eval,
+				r: claim.eval_point.clone(),
+				n_vars: claim.eval_point.len(),
+				degree: gate.degree(),
+			})
+	});
+	let reduced_claims =
+		gkr_sumcheck::batch_verify(gkr_sumcheck_claims, gkr_sumcheck_batch_proof, &mut challenger)?;
+
+	debug_assert_eq!(reduced_claims.len(), claims.len() * circuit.n_wires());
+	challenger.observe_slice(&zero_evals.concat());
+	challenger.observe_slice(&one_evals.concat());
+
+	// Validate the relationship between zero_evals, one_evals, and the reduced evals of each gate
+	for (zero_evals, one_evals, reduced_claims) in
+		izip!(&zero_evals, &one_evals, reduced_claims.chunks_exact(circuit.n_wires()))
+	{
+		let query = [zero_evals.as_slice(), one_evals.as_slice()].concat();
+		for (gate, reduced_claim) in circuit.gates().iter().zip(reduced_claims) {
+			if gate.evaluate(&query)? != reduced_claim.eval {
+				bail!(Error::InvalidZeroOneEvalAdvice);
+			}
+		}
+	}
+
+	// Create the new (k+1)th layer LayerClaims for each circuit
+	let sumcheck_challenge = reduced_claims[0].eval_point.clone();
+	let gkr_challenge = challenger.sample();
+	let new_layer_challenge = sumcheck_challenge
+		.into_iter()
+		.chain(Some(gkr_challenge))
+		.collect::<Vec<_>>();
+	let new_layer_claims = zero_evals
+		.into_iter()
+		.zip(one_evals)
+		.map(|(zero_evals, one_evals)| {
+			zero_evals
+				.into_iter()
+				.zip(one_evals)
+				.map(|(zero_eval, one_eval)| LayerClaim {
+					eval_point: new_layer_challenge.clone(),
+					eval: extrapolate_line_scalar(zero_eval, one_eval, gkr_challenge),
+				})
+				.collect()
+		})
+		.collect::<Vec<_>>();
+
+	Ok(new_layer_claims)
+}
@@ -3,9 +3,12 @@
 use super::Error;
 use crate::{
 	oracle::MultilinearPolyOracle,
+	polynomial::composition::BivariateProduct,
 	protocols::{
-		abstract_sumcheck::ReducedClaim, evalcheck::EvalcheckMultilinearClaim,
-		gkr_sumcheck::GkrSumcheckBatchProof,
+		evalcheck::EvalcheckMultilinearClaim,
+		gkr_circuit::{
+			LayeredCircuit, LayeredCircuitBatchProof, LayeredCircuitClaim, LayeredCircuitWitness,
+		},
 	},
 	witness::MultilinearWitness,
 };
@@ -16,6 +19,11 @@ use rayon::prelude::*;
 type LayerEvals<'a, FW> = &'a [FW];
 type LayerHalfEvals<'a, FW> = (&'a [FW], &'a [FW]);
 
+/// Returns the grand product circuit, which has one wire computed by a fan-in-2 multiplication gate.
+pub(super) fn grand_product_circuit() -> LayeredCircuit<BivariateProduct> {
+	LayeredCircuit::new(vec![BivariateProduct])
+}
+
 #[derive(Debug, Clone)]
 pub struct GrandProductClaim<F: Field> {
 	/// Oracle to the multilinear polynomial
@@ -24,59 +32,54 @@ pub struct GrandProductClaim<F: Field> {
 	pub product: F,
 }
 
+impl<F: Field> From<GrandProductClaim<F>> for LayeredCircuitClaim<F> {
+	fn from(claim: GrandProductClaim<F>) -> Self {
+		Self {
+			inputs: vec![claim.poly],
+			outputs: vec![claim.product],
+		}
+	}
+}
+
 #[derive(Debug, Clone)]
 pub struct GrandProductWitness<'a, PW: PackedField> {
-	poly: MultilinearWitness<'a, PW>,
-	circuit_evals: Vec<Vec<PW::Scalar>>,
+	circuit_witness: LayeredCircuitWitness<'a, PW>,
 }
 
 impl<'a, PW: PackedField> GrandProductWitness<'a, PW> {
 	pub fn new(poly: MultilinearWitness<'a, PW>) -> Result<Self, Error> {
-		// Compute the circuit layers from bottom to top
-		let input_layer = (0..1 << poly.n_vars())
-			.into_par_iter()
-			.map(|i| poly.evaluate_on_hypercube(i))
-			.collect::<Result<Vec<_>, _>>()?;
-		let mut all_layers = vec![input_layer];
-		for curr_n_vars in (0..poly.n_vars()).rev() {
-			let layer_below = all_layers.last().expect("layers is not empty by invariant");
-			let new_layer = (0..1 << curr_n_vars)
-				.into_par_iter()
-				.map(|i| {
-					let left = layer_below[i];
-					let right = layer_below[i + (1 << curr_n_vars)];
-					left * right
-				})
-				.collect();
-			all_layers.push(new_layer);
-		}
+		let circuit_witness = LayeredCircuitWitness::new(&grand_product_circuit(), &[poly])?;
+		Ok(Self { circuit_witness })
+	}
 
-		// Reverse the layers
-		all_layers.reverse();
-		Ok(Self {
-			poly,
-			circuit_evals: all_layers,
-		})
+	/// Constructs the witness from a function returning the input values by hypercube index.
+	///
+	/// The input layer of the product circuit is populated directly from `eval`, so the inputs
+	/// never need to be materialized as a separate multilinear.
+	pub fn from_fn(n_vars: usize, eval: impl Fn(usize) -> PW::Scalar + Send + Sync) -> Self {
+		let input_layer = (0..1 << n_vars).into_par_iter().map(eval).collect();
+		let circuit_witness = LayeredCircuitWitness::from_input_layer(
+			&grand_product_circuit(),
+			n_vars,
+			vec![input_layer],
+		)
+		.expect("the input layer has one wire with 2^n_vars values");
+		Self { circuit_witness }
 	}
 
 	/// Returns the base-two log of the number of inputs to the GKR Grand Product Circuit
 	pub fn n_vars(&self) -> usize {
-		self.poly.n_vars()
+		self.circuit_witness.n_vars()
 	}
 
 	/// Returns the evaluation of the GKR Grand Product Circuit
 	pub fn grand_product_evaluation(&self) -> PW::Scalar {
-		// By invariant, we will have n_vars + 1 layers, and the ith layer will have 2^i elements.
-		// Therefore, this 2-D array access is safe.
-		self.circuit_evals[0][0]
+		self.circuit_witness.outputs()[0]
 	}
 
 	pub fn ith_layer_evals(&self, i: usize) -> Result<LayerEvals<'_, PW::Scalar>, Error> {
-		let max_layer_idx = self.n_vars();
-		if i > max_layer_idx {
-			bail!(Error::InvalidLayerIndex);
-		}
-		Ok(&self.circuit_evals[i])
+		// The product circuit has exactly one wire.
+		Ok(&self.circuit_witness.ith_layer_evals(i)?[0])
 	}
 
 	/// Returns the evaluations of the ith layer of the GKR Grand Product Circuit, split into two halves
@@ -92,38 +95,15 @@ impl<'a, PW: PackedField> GrandProductWitness<'a, PW> {
 	}
 }
 
-/// LayerClaim is a claim about the evaluation of the kth layer-multilinear at a specific evaluation point
-///
-/// Notation:
-/// * The kth layer-multilinear is the multilinear polynomial whose evaluations are the intermediate values of the kth
-/// layer of the evaluated product circuit.
-pub type LayerClaim<F> = ReducedClaim<F>;
-
-/// BatchLayerProof is the proof that reduces the kth layer of a batch
-/// of product circuits to the (k+1)th layer
-///
-/// Notation:
-/// * The kth layer-multilinear is the multilinear polynomial whose evaluations are the intermediate values of the kth
-/// layer of the evaluated product circuit.
-/// * $r'_k$ is challenge generated during the k-variate sumcheck reduction from layer k to layer k+1
-#[derive(Debug, Clone)]
-pub struct BatchLayerProof<F: Field> {
-	/// The proof of the batched sumcheck reduction (on $k$ variables)
-	/// None for the zeroth to first layer reduction
-	/// Some for the subsequent reductions
-	pub gkr_sumcheck_batch_proof: GkrSumcheckBatchProof<F>,
-	/// The evaluations of the appropriate (k+1)th layer-multilinear at
-	/// evaluation point $(r'_k, 0)$
-	pub zero_evals: Vec<F>,
-	/// The evaluations of the appropriate (k+1)th layer-multilinear at
-	/// evaluation point $(r'_k, 1)$
-	pub one_evals: Vec<F>,
+impl<'a, PW: PackedField> From<GrandProductWitness<'a, PW>> for LayeredCircuitWitness<'a, PW> {
+	fn from(witness: GrandProductWitness<'a, PW>) -> Self {
+		witness.circuit_witness
+	}
 }
 
-#[derive(Debug, Clone, Default)]
-pub struct GrandProductBatchProof<F: Field> {
-	pub batch_layer_proofs: Vec<BatchLayerProof<F>>,
-}
+/// The grand product batch proof is a layered circuit batch proof for the grand product circuit,
+/// where each layer proof holds a single zero and one evaluation per claim.
+pub type GrandProductBatchProof<F> = LayeredCircuitBatchProof<F>;
 
 #[derive(Debug, Default)]
 pub struct GrandProductBatchProveOutput<F: Field> {
@@ -8,6 +8,8 @@
 //!
 //! The GKR circuit used here has only one gate type: the fan-in-2 multiplication gate.
 //! In the natural way, the 2^n input wires are multiplied together in n layers to produce the output.
+//! The protocol is the layered circuit GKR protocol in [`crate::protocols::gkr_circuit`], instantiated
+//! with a single wire whose gate is [`BivariateProduct`](crate::polynomial::composition::BivariateProduct).
 //!
 //! Naming Convention for challenges:
 //! 1) Sumcheck challenge: $r'_k$
@@ -164,6 +164,15 @@ fn test_prove_verify_batch() {
 	} = batch_prove::<_, _, FS, _>(witnesses, claims.clone(), domain_factory, prover_challenger)
 		.unwrap();
 
+	// The grand product circuit has one wire, so each layer proof has one evaluation per claim
+	for batch_layer_proof in &proof.batch_layer_proofs {
+		assert!(batch_layer_proof
+			.zero_evals
+			.iter()
+			.chain(&batch_layer_proof.one_evals)
+			.all(|evals| evals.len() == 1));
+	}
+
 	let verified_evalcheck_multilinear_claims =
 		batch_verify(claims.clone(), proof, verifier_challenger).unwrap();
 
@@ -185,3 +194,22 @@ fn test_prove_verify_batch() {
 		assert!(verified_eval_claim.is_random_point);
 	}
 }
+
+#[test]
+fn test_witness_from_fn_matches_multilinear() {
+	type F = BinaryField128b;
+	let n_vars = 5;
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let (poly, product) = generate_poly_helper::<F>(&mut rng, n_vars, 1).remove(0);
+	let values = poly.evals().to_vec();
+
+	let from_poly = GrandProductWitness::<F>::new(poly.specialize_arc_dyn()).unwrap();
+	let from_fn = GrandProductWitness::<F>::from_fn(n_vars, |i| values[i]);
+
+	assert_eq!(from_fn.n_vars(), n_vars);
+	assert_eq!(from_fn.grand_product_evaluation(), product);
+	for i in 0..=n_vars {
+		assert_eq!(from_fn.ith_layer_evals(i).unwrap(), from_poly.ith_layer_evals(i).unwrap());
+	}
+}
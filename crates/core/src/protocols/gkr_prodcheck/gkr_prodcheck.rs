@@ -2,10 +2,18 @@
 
 use crate::{
 	oracle::MultilinearPolyOracle,
-	protocols::gkr_gpa::{GrandProductClaim, GrandProductWitness},
+	polynomial::Error as PolynomialError,
+	protocols::{
+		evalcheck::EvalcheckMultilinearClaim,
+		gkr_gpa::{
+			Error as GrandProductError, GrandProductBatchProof, GrandProductClaim,
+			GrandProductWitness,
+		},
+	},
 	witness::MultilinearWitness,
 };
 use binius_field::{Field, PackedField};
+use std::{fmt, sync::Arc};
 
 #[derive(Debug, Clone)]
 pub struct ProdcheckClaim<F: Field> {
@@ -17,11 +25,100 @@ pub struct ProdcheckClaim<F: Field> {
 
 #[derive(Debug, Clone)]
 pub struct ProdcheckWitness<'a, PW: PackedField> {
-	pub t_poly: MultilinearWitness<'a, PW>,
-	pub u_poly: MultilinearWitness<'a, PW>,
+	pub t_poly: ProdcheckInput<'a, PW>,
+	pub u_poly: ProdcheckInput<'a, PW>,
 }
 
-#[derive(Debug, Default)]
+impl<'a, PW: PackedField> ProdcheckWitness<'a, PW> {
+	pub fn new(t_poly: MultilinearWitness<'a, PW>, u_poly: MultilinearWitness<'a, PW>) -> Self {
+		Self {
+			t_poly: t_poly.into(),
+			u_poly: u_poly.into(),
+		}
+	}
+
+	/// Constructs a witness from functions returning the values of $T$ and $U$ by hypercube index.
+	///
+	/// This avoids materializing $T$ and $U$ when they are cheap to compute on the fly, for example
+	/// when they are linear combinations of other multilinears shifted by a challenge.
+	pub fn from_fns(
+		n_vars: usize,
+		t_eval: impl Fn(usize) -> PW::Scalar + Send + Sync + 'a,
+		u_eval: impl Fn(usize) -> PW::Scalar + Send + Sync + 'a,
+	) -> Self {
+		Self {
+			t_poly: ProdcheckInput::Fn {
+				n_vars,
+				eval: Arc::new(t_eval),
+			},
+			u_poly: ProdcheckInput::Fn {
+				n_vars,
+				eval: Arc::new(u_eval),
+			},
+		}
+	}
+}
+
+/// Function returning the value of a multilinear at a hypercube vertex, by index.
+pub type HypercubeEvalFn<'a, F> = Arc<dyn Fn(usize) -> F + Send + Sync + 'a>;
+
+/// One side of a product check witness.
+///
+/// The values are either read from a multilinear witness or generated on the fly by index.
+#[derive(Clone)]
+pub enum ProdcheckInput<'a, PW: PackedField> {
+	Multilinear(MultilinearWitness<'a, PW>),
+	Fn {
+		n_vars: usize,
+		eval: HypercubeEvalFn<'a, PW::Scalar>,
+	},
+}
+
+impl<'a, PW: PackedField> ProdcheckInput<'a, PW> {
+	pub fn n_vars(&self) -> usize {
+		match self {
+			Self::Multilinear(poly) => poly.n_vars(),
+			Self::Fn { n_vars, .. } => *n_vars,
+		}
+	}
+
+	pub fn evaluate_on_hypercube(&self, index: usize) -> Result<PW::Scalar, PolynomialError> {
+		match self {
+			Self::Multilinear(poly) => poly.evaluate_on_hypercube(index),
+			Self::Fn { eval, .. } => Ok(eval(index)),
+		}
+	}
+
+	/// Builds the grand product circuit witness over the input values.
+	pub fn into_grand_product_witness(
+		self,
+	) -> Result<GrandProductWitness<'a, PW>, GrandProductError> {
+		match self {
+			Self::Multilinear(poly) => GrandProductWitness::new(poly),
+			Self::Fn { n_vars, eval } => Ok(GrandProductWitness::from_fn(n_vars, &*eval)),
+		}
+	}
+}
+
+impl<'a, PW: PackedField> From<MultilinearWitness<'a, PW>> for ProdcheckInput<'a, PW> {
+	fn from(poly: MultilinearWitness<'a, PW>) -> Self {
+		Self::Multilinear(poly)
+	}
+}
+
+impl<'a, PW: PackedField> fmt::Debug for ProdcheckInput<'a, PW> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Multilinear(poly) => f.debug_tuple("Multilinear").field(poly).finish(),
+			Self::Fn { n_vars, .. } => f
+				.debug_struct("Fn")
+				.field("n_vars", n_vars)
+				.finish_non_exhaustive(),
+		}
+	}
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct ProdcheckBatchProof<F: Field> {
 	pub products: Vec<F>,
 }
@@ -37,3 +134,19 @@ where
 	pub reduced_claims: Vec<GrandProductClaim<F>>,
 	pub batch_proof: ProdcheckBatchProof<F>,
 }
+
+/// The proof of a batch of product checks with the GKR grand product argument.
+#[derive(Debug, Clone, Default)]
+pub struct ProdcheckGrandProductProof<F: Field> {
+	/// The grand products of $T$ and $U$ of each claim
+	pub batch_proof: ProdcheckBatchProof<F>,
+	/// The proof of the grand products
+	pub grand_product_proof: GrandProductBatchProof<F>,
+}
+
+#[derive(Debug, Default)]
+pub struct ProdcheckGrandProductProveOutput<F: Field> {
+	/// Evalcheck claims on $T$ and $U$ of each claim, in that order
+	pub evalcheck_multilinear_claims: Vec<EvalcheckMultilinearClaim<F>>,
+	pub proof: ProdcheckGrandProductProof<F>,
+}
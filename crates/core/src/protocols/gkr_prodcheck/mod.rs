@@ -8,6 +8,11 @@
 //! This protocol simply reduces the Product Check Claim to two Grand Product Claims over the same
 //! claimed product.
 //!
+//! [`prove_grand_product`] proves the Grand Product Claims in the same call with the GKR-based grand
+//! product argument of [`crate::protocols::gkr_gpa`], which reduces them to evalcheck claims on
+//! $T$ and $U$ with one batched sumcheck per layer of the product circuits and no auxiliary
+//! committed columns.
+//!
 //! The naming of this protocol as gkr_prodcheck is to distinguish it from the other prodcheck.
 //! This will be renamed to prodcheck in the future when it becomes the default.
 mod error;
@@ -18,7 +23,8 @@ mod verify;
 
 pub use error::*;
 pub use gkr_prodcheck::{
-	ProdcheckBatchProof, ProdcheckBatchProveOutput, ProdcheckClaim, ProdcheckWitness,
+	HypercubeEvalFn, ProdcheckBatchProof, ProdcheckBatchProveOutput, ProdcheckClaim,
+	ProdcheckGrandProductProof, ProdcheckGrandProductProveOutput, ProdcheckInput, ProdcheckWitness,
 };
 pub use prove::*;
 pub use verify::*;
@@ -1,11 +1,16 @@
 // Copyright 2024 Ulvetanna Inc.
 
 use super::{
-	Error, ProdcheckBatchProof, ProdcheckBatchProveOutput, ProdcheckClaim, ProdcheckWitness,
+	Error, ProdcheckBatchProof, ProdcheckBatchProveOutput, ProdcheckClaim,
+	ProdcheckGrandProductProof, ProdcheckGrandProductProveOutput, ProdcheckWitness,
 };
-use crate::protocols::gkr_gpa::{GrandProductClaim, GrandProductWitness};
-use binius_field::{Field, PackedField, TowerField};
+use crate::{
+	polynomial::EvaluationDomainFactory,
+	protocols::gkr_gpa::{self, GrandProductBatchProveOutput, GrandProductClaim},
+};
+use binius_field::{ExtensionField, Field, PackedExtension, PackedField, TowerField};
 use binius_utils::bail;
+use p3_challenger::{CanObserve, CanSample};
 use tracing::instrument;
 
 /// Proves batch reduction splitting each ProductCheckClaim into two GrandProductClaims
@@ -60,8 +65,8 @@ where
 		}
 
 		// Calculate the products of both T, U polynomials and enforce equal
-		let t_witness = GrandProductWitness::new(t_poly)?;
-		let u_witness = GrandProductWitness::new(u_poly)?;
+		let t_witness = t_poly.into_grand_product_witness()?;
+		let u_witness = u_poly.into_grand_product_witness()?;
 
 		// Create the two GrandProductClaims and the proof
 		let t_product = t_witness.grand_product_evaluation().into();
@@ -99,3 +104,54 @@ where
 		batch_proof,
 	})
 }
+
+/// Proves a batch of product checks with the GKR grand product argument.
+///
+/// Each ProductCheckClaim is split into two GrandProductClaims as in [`batch_prove`], the
+/// products are observed by the challenger, and the GrandProductClaims are reduced to evalcheck
+/// claims on $T$ and $U$ with [`gkr_gpa::batch_prove`]. The proof has one layer per variable of
+/// the largest claim, and no auxiliary columns are committed.
+///
+/// REQUIRES:
+/// * witnesses and claims are of the same length
+/// * The ith witness corresponds to the ith claim
+#[instrument(skip_all, name = "gkr_prodcheck::prove_grand_product", level = "debug")]
+pub fn prove_grand_product<'a, F, PW, DomainField, Challenger>(
+	witnesses: impl IntoIterator<Item = ProdcheckWitness<'a, PW>>,
+	claims: impl IntoIterator<Item = ProdcheckClaim<F>>,
+	evaluation_domain_factory: impl EvaluationDomainFactory<DomainField>,
+	mut challenger: Challenger,
+) -> Result<ProdcheckGrandProductProveOutput<F>, Error>
+where
+	F: TowerField + From<PW::Scalar>,
+	PW: PackedExtension<DomainField>,
+	DomainField: Field,
+	PW::Scalar: Field + From<F> + Into<F> + ExtensionField<DomainField>,
+	Challenger: CanSample<F> + CanObserve<F>,
+{
+	let ProdcheckBatchProveOutput {
+		reduced_witnesses,
+		reduced_claims,
+		batch_proof,
+	} = batch_prove(witnesses, claims)?;
+
+	challenger.observe_slice(&batch_proof.products);
+
+	let GrandProductBatchProveOutput {
+		evalcheck_multilinear_claims,
+		proof: grand_product_proof,
+	} = gkr_gpa::batch_prove(
+		reduced_witnesses,
+		reduced_claims,
+		evaluation_domain_factory,
+		&mut challenger,
+	)?;
+
+	Ok(ProdcheckGrandProductProveOutput {
+		evalcheck_multilinear_claims,
+		proof: ProdcheckGrandProductProof {
+			batch_proof,
+			grand_product_proof,
+		},
+	})
+}
@@ -1,9 +1,16 @@
 // Copyright 2024 Ulvetanna Inc.
 
-use super::{gkr_prodcheck::ProdcheckBatchProof, Error, ProdcheckClaim, VerificationError};
-use crate::protocols::gkr_gpa::GrandProductClaim;
+use super::{
+	gkr_prodcheck::{ProdcheckBatchProof, ProdcheckGrandProductProof},
+	Error, ProdcheckClaim, VerificationError,
+};
+use crate::protocols::{
+	evalcheck::EvalcheckMultilinearClaim,
+	gkr_gpa::{self, GrandProductClaim},
+};
 use binius_field::TowerField;
 use binius_utils::bail;
+use p3_challenger::{CanObserve, CanSample};
 use tracing::instrument;
 
 /// Batch Verify product check instance reductions to two grand product claims.
@@ -54,3 +61,38 @@ pub fn batch_verify<F: TowerField>(
 	debug_assert_eq!(grand_product_claims.len(), n_products);
 	Ok(grand_product_claims)
 }
+
+/// Verifies a batch of product checks proven with [`super::prove_grand_product`].
+///
+/// Returns the evalcheck claims on $T$ and $U$ of each claim, in that order.
+#[instrument(
+	skip_all,
+	name = "gkr_prodcheck::verify_grand_product",
+	level = "debug"
+)]
+pub fn verify_grand_product<F, Challenger>(
+	claims: impl IntoIterator<Item = ProdcheckClaim<F>>,
+	proof: ProdcheckGrandProductProof<F>,
+	mut challenger: Challenger,
+) -> Result<Vec<EvalcheckMultilinearClaim<F>>, Error>
+where
+	F: TowerField,
+	Challenger: CanSample<F> + CanObserve<F>,
+{
+	let ProdcheckGrandProductProof {
+		batch_proof,
+		grand_product_proof,
+	} = proof;
+
+	challenger.observe_slice(&batch_proof.products);
+	let grand_product_claims = batch_verify(claims, batch_proof)?;
+
+	// The grand product argument rejects empty batches, which the prover proves trivially.
+	if grand_product_claims.is_empty() {
+		return Ok(Vec::new());
+	}
+
+	let evalcheck_claims =
+		gkr_gpa::batch_verify(grand_product_claims, grand_product_proof, &mut challenger)?;
+	Ok(evalcheck_claims)
+}
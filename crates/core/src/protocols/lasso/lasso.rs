@@ -3,8 +3,8 @@
 use super::error::Error;
 use crate::{
 	oracle::{
-		BatchId, CompositePolyOracle, MultilinearOracleSet, MultilinearPolyOracle, OracleId,
-		ShiftVariant,
+		BatchId, CompositePolyOracle, Error as OracleError, MultilinearOracleSet,
+		MultilinearPolyOracle, OracleId, ShiftVariant,
 	},
 	polynomial::{transparent::step_down::StepDown, CompositionPoly, Error as PolynomialError},
 	protocols::{
@@ -115,9 +115,30 @@ impl<F: Field> LassoClaim<F> {
 			u_oracles,
 		})
 	}
+
+	/// Constructs a claim from the IDs of the table and the looked-up oracles in `oracles`.
+	pub fn from_oracle_ids(
+		oracles: &MultilinearOracleSet<F>,
+		t_id: OracleId,
+		u_ids: impl IntoIterator<Item = OracleId>,
+	) -> Result<Self, Error>
+	where
+		F: TowerField,
+	{
+		let oracle = |id: OracleId| -> Result<MultilinearPolyOracle<F>, Error> {
+			if id >= oracles.size() {
+				bail!(OracleError::InvalidOracleId(id));
+			}
+			Ok(oracles.oracle(id))
+		};
+
+		let t_oracle = oracle(t_id)?;
+		let u_oracles = u_ids.into_iter().map(oracle).collect::<Result<_, _>>()?;
+		Self::new(t_oracle, u_oracles)
+	}
 }
 
-#[derive(Debug, Getters)]
+#[derive(Debug, Clone, Getters)]
 pub struct LassoWitness<'a, PW: PackedField, L: AsRef<[usize]>> {
 	#[get = "pub"]
 	t_polynomial: MultilinearWitness<'a, PW>,
@@ -318,9 +339,7 @@ pub fn reduce_lasso_claim<C: LassoCount, F: TowerField>(
 				UnaryCarryConstraint,
 			)?;
 
-			zerocheck_claims.push(ZerocheckClaim {
-				poly: unary_carry_constraint_oracle,
-			});
+			zerocheck_claims.push(ZerocheckClaim::new(unary_carry_constraint_oracle)?);
 
 			// Packed oracles for the multiset check
 			packed_counts_oracle_id.push(oracles.add_packed(*counts_oracle_id, C::TOWER_LEVEL)?);
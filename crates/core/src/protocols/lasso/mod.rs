@@ -6,7 +6,8 @@
 //! set of hypercube evaluations of the virtual polynomial $T$ (the "table"). The protocol itself is based on
 //! offline memory checking and described in the Section 4.4 of [DP23], with the important distinction that
 //! this implementation relies on addition gadget in place of multiplicative group for the "counts".
-//! See [`prove`](self::prove::prove()) for in-depth details.
+//! See [`prove`](self::prove::prove()) for in-depth details. Structured tables with succinct
+//! multilinear extensions, such as range and bitwise operation tables, are provided in [`tables`].
 //!
 //! [DP23]: <https://eprint.iacr.org/2023/1784>
 
@@ -14,6 +15,7 @@ mod error;
 #[allow(clippy::module_inception)]
 mod lasso;
 mod prove;
+pub mod tables;
 #[cfg(test)]
 mod tests;
 mod verify;
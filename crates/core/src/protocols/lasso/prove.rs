@@ -8,8 +8,8 @@ use super::{
 	},
 };
 use crate::{
-	oracle::MultilinearOracleSet,
-	polynomial::{Error as PolynomialError, MultilinearComposite, MultilinearPoly},
+	oracle::{MultilinearOracleSet, OracleId},
+	polynomial::{util::fill_linear_combination, MultilinearComposite, MultilinearPoly},
 	protocols::gkr_prodcheck::ProdcheckWitness,
 	witness::{MultilinearExtensionIndex, MultilinearWitness},
 };
@@ -20,7 +20,6 @@ use binius_field::{
 };
 use binius_utils::bail;
 use itertools::izip;
-use rayon::iter::{IndexedParallelIterator, IntoParallelRefMutIterator, ParallelIterator};
 use std::{array, sync::Arc};
 use tracing::instrument;
 
@@ -57,6 +56,10 @@ use tracing::instrument;
 /// proofs. Please note that the implemented addition gadget approach differs from the one described in the
 /// paper (which uses multiplicative group to represent counts), as well as uses slightly different notation.
 ///
+/// The committed columns $C$, $O$ and $F$ are computed by [`prove_counts`] and read from the witness index.
+/// They must be committed and observed by the challenger before $\gamma$ and $\alpha$ are sampled, as the
+/// multiset check is only sound for counts fixed before the challenges.
+///
 /// [DP23]: <https://eprint.iacr.org/2023/1784>
 #[instrument(skip_all, name = "lasso::prove", level = "debug")]
 pub fn prove<'a, FC, U, F, FW, L>(
@@ -85,16 +88,9 @@ where
 	}
 
 	let bit_packing_log_width = PackedType::<U, BinaryField1b>::LOG_WIDTH;
-
-	let mut final_counts_underlier_vecs =
-		vec![U::default(); 1 << (t_n_vars_gf2 - bit_packing_log_width)];
-
-	let final_counts = {
-		let packed_slice = PackedType::<U, FC>::from_underliers_ref_mut(
-			final_counts_underlier_vecs.as_mut_slice(),
-		);
-		PackedType::<U, FC>::unpack_scalars_mut(packed_slice)
-	};
+	if t_n_vars_gf2 < bit_packing_log_width {
+		bail!(Error::WitnessSmallerThanUnderlier);
+	}
 
 	// Check that counts actually fit into the chosen data type
 	// NB. Need one more bit because 1 << n_vars is a valid count.
@@ -102,9 +98,8 @@ where
 		bail!(Error::LassoCountTypeTooSmall);
 	}
 
-	if t_n_vars_gf2 < bit_packing_log_width {
-		bail!(Error::WitnessSmallerThanUnderlier);
-	}
+	let final_counts_underlier_vecs =
+		committed_underliers(&witness_index, lasso_batches.final_counts)?;
 
 	let (reduced_lasso_claims, reduced_claim_oracle_ids) =
 		reduce_lasso_claim::<FC, _>(oracles, lasso_claim, lasso_batches, gamma, alpha)?;
@@ -140,46 +135,40 @@ where
 			bail!(Error::WitnessSmallerThanUnderlier);
 		}
 
-		for (i, (u_polynomial, u_to_t_mapping)) in lasso_witness
-			.u_polynomials()
-			.iter()
-			.zip(lasso_witness.u_to_t_mappings())
-			.enumerate()
-		{
-			// underliers for read counts vectors, a total of seven
-			let mut underlier_vecs: [_; 5] =
+		for (i, u_polynomial) in lasso_witness.u_polynomials().iter().enumerate() {
+			let counts = committed_underliers(&witness_index, lasso_batches.counts[i])?;
+
+			// underliers for the virtual columns of the addition gadget
+			let mut underlier_vecs: [_; 3] =
 				array::from_fn(|_| vec![U::default(); 1 << (u_n_vars_gf2 - bit_packing_log_width)]);
 
 			// cast underliers into Lasso counts for addition gadget computations
-			let [counts, counts_plus_one, carry_in, carry_out, carry_out_shifted] =
+			let [counts_plus_one, carry_in, carry_out_shifted] =
 				underlier_vecs.each_mut().map(|underliers| {
 					let packed_slice =
 						PackedType::<U, FC>::from_underliers_ref_mut(underliers.as_mut_slice());
 					PackedType::<U, FC>::unpack_scalars_mut(packed_slice)
 				});
 
-			let t_indice = u_to_t_mapping.as_ref();
+			let count_scalars = PackedType::<U, FC>::unpack_scalars(
+				PackedType::<U, FC>::from_underliers_ref(&counts),
+			);
 			// addition gadget computing count+1 via a carry_in XORed with 1 at the lowest bit
-			for (&t_index, counts, counts_plus_one, carry_in, carry_out, carry_out_shifted) in
-				izip!(t_indice, counts, counts_plus_one, carry_in, carry_out, carry_out_shifted)
+			for (&count, counts_plus_one, carry_in, carry_out_shifted) in
+				izip!(count_scalars, counts_plus_one, carry_in, carry_out_shifted)
 			{
-				let count = final_counts[t_index];
 				let (count_plus_one, overflow) = count.overflowing_add(FC::ONE);
 				assert!(!overflow, "Lasso count overflowed!");
 
-				final_counts[t_index] = count_plus_one;
-
 				// u32 addition gadget with y = 1
-				*counts = count;
 				*counts_plus_one = count_plus_one;
 				*carry_in = count + count_plus_one;
 				*carry_out_shifted = *carry_in + FC::ONE;
-				*carry_out = (*carry_in).shr1();
 			}
 
 			// construct virtual polynomial oracles
 
-			let [counts, counts_plus_one, carry_in, carry_out, carry_out_shifted] = underlier_vecs;
+			let [counts_plus_one, carry_in, carry_out_shifted] = underlier_vecs;
 
 			// add 1-bit witnesses to the index
 			let counts_oracle_id = &lasso_batches.counts;
@@ -189,10 +178,8 @@ where
 			let counts_plus_one = Arc::<[U]>::from(counts_plus_one);
 
 			witness_index = witness_index.update_owned::<BinaryField1b, _>([
-				(counts_oracle_id[i], counts.clone()),
 				(counts_plus_one_oracle_id[i], counts_plus_one.clone()),
 				(carry_in_oracle_id[i], carry_in.into()),
-				(carry_out_oracle_id[i], carry_out.into()),
 				(carry_out_shifted_oracle_id[i], carry_out_shifted.into()),
 			])?;
 
@@ -220,10 +207,8 @@ where
 			let mixed_u_counts_plus_one_poly =
 				witness_index.get_multilin_poly(mixed_u_counts_plus_one_oracle_id[i])?;
 
-			prodcheck_witnesses.push(ProdcheckWitness {
-				t_poly: mixed_u_counts_poly,
-				u_poly: mixed_u_counts_plus_one_poly,
-			});
+			prodcheck_witnesses
+				.push(ProdcheckWitness::new(mixed_u_counts_poly, mixed_u_counts_plus_one_poly));
 
 			let counts_poly = witness_index.get_multilin_poly(counts_oracle_id[i])?;
 			let carry_in_poly = witness_index.get_multilin_poly(carry_in_oracle_id[i])?;
@@ -253,17 +238,8 @@ where
 		)])?;
 	}
 
-	let final_counts_oracle_id = lasso_batches.final_counts;
-
-	witness_index = witness_index.update_owned::<BinaryField1b, _>([(
-		final_counts_oracle_id,
-		final_counts_underlier_vecs.clone(),
-	)])?;
-
-	witness_index = witness_index.update_owned::<FC, _>([(
-		packed_final_counts_oracle_id,
-		final_counts_underlier_vecs.clone(),
-	)])?;
+	witness_index = witness_index
+		.update_owned::<FC, _>([(packed_final_counts_oracle_id, final_counts_underlier_vecs)])?;
 
 	let packed_final_counts_poly =
 		witness_index.get_multilin_poly(packed_final_counts_oracle_id)?;
@@ -281,10 +257,7 @@ where
 		witness_index.get_multilin_poly(mixed_t_final_counts_oracle_id)?;
 	let mixed_t_zero_poly = witness_index.get_multilin_poly(mixed_t_zero_oracle_id)?;
 
-	prodcheck_witnesses.push(ProdcheckWitness {
-		t_poly: mixed_t_final_counts_poly,
-		u_poly: mixed_t_zero_poly,
-	});
+	prodcheck_witnesses.push(ProdcheckWitness::new(mixed_t_final_counts_poly, mixed_t_zero_poly));
 
 	Ok(LassoProveOutput {
 		reduced_lasso_claims,
@@ -294,47 +267,144 @@ where
 	})
 }
 
-fn linciom<'a, U, FW, PW, F>(
-	trace: &MultilinearWitness<'a, PW>,
-	counts: Option<&MultilinearWitness<'a, PW>>,
+/// Computes the committed columns of a Lasso instance.
+///
+/// Runs the counting algorithm described in [`prove`] and adds the counts $C$, the carries $O$ out of
+/// the addition gadget and the final counts $F$ to the witness index, under the committed oracles of
+/// `lasso_batches`. These columns do not depend on the challenges, and the caller commits them before
+/// sampling $\gamma$ and $\alpha$ for [`prove`].
+#[instrument(skip_all, name = "lasso::prove_counts", level = "debug")]
+pub fn prove_counts<'a, FC, U, FW, L>(
+	witness_index: MultilinearExtensionIndex<'a, U, FW>,
+	lasso_witness: &LassoWitness<'_, PackedType<U, FW>, L>,
+	lasso_batches: &LassoBatches,
+) -> Result<MultilinearExtensionIndex<'a, U, FW>, Error>
+where
+	U: UnderlierType + PackScalar<FW> + PackScalar<FC> + PackScalar<BinaryField1b>,
+	FC: LassoCount,
+	PackedType<U, FC>: PackedFieldIndexable,
+	FW: TowerField + ExtensionField<FC>,
+	L: AsRef<[usize]>,
+{
+	let t_n_vars = lasso_witness.t_polynomial().n_vars();
+	let t_n_vars_gf2 = t_n_vars + FC::TOWER_LEVEL;
+
+	if lasso_witness.u_polynomials().len() > lasso_batches.counts.len() {
+		bail!(Error::ClaimWitnessTablesLenMismatch);
+	}
+
+	let bit_packing_log_width = PackedType::<U, BinaryField1b>::LOG_WIDTH;
+	if t_n_vars_gf2 < bit_packing_log_width {
+		bail!(Error::WitnessSmallerThanUnderlier);
+	}
+
+	// Check that counts actually fit into the chosen data type
+	// NB. Need one more bit because 1 << n_vars is a valid count.
+	if t_n_vars >= FC::N_BITS {
+		bail!(Error::LassoCountTypeTooSmall);
+	}
+
+	let mut final_counts_underlier_vecs =
+		vec![U::default(); 1 << (t_n_vars_gf2 - bit_packing_log_width)];
+
+	let final_counts = {
+		let packed_slice = PackedType::<U, FC>::from_underliers_ref_mut(
+			final_counts_underlier_vecs.as_mut_slice(),
+		);
+		PackedType::<U, FC>::unpack_scalars_mut(packed_slice)
+	};
+
+	let mut witness_index = witness_index;
+
+	if let Some(first) = lasso_witness.u_polynomials().first() {
+		let u_n_vars = first.n_vars();
+		let u_n_vars_gf2 = u_n_vars + FC::TOWER_LEVEL;
+
+		if u_n_vars >= FC::N_BITS {
+			bail!(Error::LassoCountTypeTooSmall);
+		}
+
+		if u_n_vars_gf2 < bit_packing_log_width {
+			bail!(Error::WitnessSmallerThanUnderlier);
+		}
+
+		for (i, u_to_t_mapping) in lasso_witness.u_to_t_mappings().iter().enumerate() {
+			let mut underlier_vecs: [_; 2] =
+				array::from_fn(|_| vec![U::default(); 1 << (u_n_vars_gf2 - bit_packing_log_width)]);
+
+			let [counts, carry_out] = underlier_vecs.each_mut().map(|underliers| {
+				let packed_slice =
+					PackedType::<U, FC>::from_underliers_ref_mut(underliers.as_mut_slice());
+				PackedType::<U, FC>::unpack_scalars_mut(packed_slice)
+			});
+
+			for (&t_index, counts, carry_out) in izip!(u_to_t_mapping.as_ref(), counts, carry_out) {
+				let count = final_counts[t_index];
+				let (count_plus_one, overflow) = count.overflowing_add(FC::ONE);
+				assert!(!overflow, "Lasso count overflowed!");
+
+				final_counts[t_index] = count_plus_one;
+
+				*counts = count;
+				*carry_out = (count + count_plus_one).shr1();
+			}
+
+			let [counts, carry_out] = underlier_vecs;
+			witness_index = witness_index.update_owned::<BinaryField1b, _>([
+				(lasso_batches.counts[i], counts),
+				(lasso_batches.carry_out[i], carry_out),
+			])?;
+		}
+	}
+
+	witness_index = witness_index.update_owned::<BinaryField1b, _>([(
+		lasso_batches.final_counts,
+		final_counts_underlier_vecs,
+	)])?;
+
+	Ok(witness_index)
+}
+
+/// Copies out the underliers of a committed 1-bit Lasso column added by [`prove_counts`].
+fn committed_underliers<U, FW>(
+	witness_index: &MultilinearExtensionIndex<'_, U, FW>,
+	id: OracleId,
+) -> Result<Vec<U>, Error>
+where
+	U: UnderlierType + PackScalar<FW> + PackScalar<BinaryField1b>,
+	FW: TowerField,
+{
+	let column = witness_index.get::<BinaryField1b>(id)?;
+	Ok(PackedType::<U, BinaryField1b>::to_underliers_ref(column.evals()).to_vec())
+}
+
+fn linciom<'a, U, FW, F>(
+	trace: &MultilinearWitness<'a, PackedType<U, FW>>,
+	counts: Option<&MultilinearWitness<'a, PackedType<U, FW>>>,
 	gamma: F,
 	alpha: F,
 ) -> Result<Arc<[U]>, Error>
 where
 	U: UnderlierType + PackScalar<FW>,
-	PackedType<U, FW>: PackedFieldIndexable,
-	PW: PackedField<Scalar = FW>,
 	FW: Field + From<F>,
 {
 	let n_vars = trace.n_vars();
 
 	let packing_log_width = PackedType::<U, FW>::LOG_WIDTH;
+	if n_vars < packing_log_width {
+		bail!(Error::WitnessSmallerThanUnderlier);
+	}
 
 	let mut underliers = vec![U::default(); 1 << (n_vars - packing_log_width)];
-	let values = PackedType::<U, FW>::unpack_scalars_mut(
-		PackedType::<U, FW>::from_underliers_ref_mut(underliers.as_mut_slice()),
-	);
-
-	values.fill(FW::from(gamma));
+	let packed = PackedType::<U, FW>::from_underliers_ref_mut(underliers.as_mut_slice());
 
 	// first dimension of the relation is not weighted
-	values.par_iter_mut().enumerate().try_for_each(
-		|(i, values_i)| -> Result<_, PolynomialError> {
-			*values_i += trace.evaluate_on_hypercube(i)?;
-			Ok(())
-		},
-	)?;
-
-	let alpha = FW::from(alpha);
-
+	let mut inner = vec![(trace.clone(), FW::ONE)];
 	if let Some(counts) = counts {
-		values.par_iter_mut().enumerate().try_for_each(
-			|(i, values_i)| -> Result<_, PolynomialError> {
-				*values_i += counts.evaluate_on_hypercube_and_scale(i, alpha)?;
-				Ok(())
-			},
-		)?;
+		inner.push((counts.clone(), FW::from(alpha)));
 	}
 
+	fill_linear_combination(n_vars, FW::from(gamma), &inner, packed)?;
+
 	Ok(underliers.into())
 }
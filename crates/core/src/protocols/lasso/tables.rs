@@ -0,0 +1,336 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Structured lookup tables.
+//!
+//! The tables in this module have a succinct multilinear extension, so they are added to the oracle
+//! set as transparent polynomials with [`MultilinearOracleSet::add_transparent`] and the verifier
+//! evaluates them directly instead of checking a commitment. The $i$-th entry of each table is the
+//! field element whose $\mathbb{F}_2$-basis coordinates, see [`TowerField::basis`], are the bits
+//! of an integer value, and the multilinear extension is linear in these coordinates.
+//!
+//! [`MultilinearOracleSet::add_transparent`]: crate::oracle::MultilinearOracleSet::add_transparent
+
+use crate::polynomial::{Error, MultilinearExtension, MultivariatePoly};
+use binius_field::{PackedField, TowerField};
+use binius_utils::bail;
+use p3_util::log2_ceil_usize;
+
+/// Returns the field element with the bits of `value` as $\mathbb{F}_2$-basis coordinates.
+pub fn value_to_field<F: TowerField>(value: u128) -> Result<F, Error> {
+	let n_bits = 128 - value.leading_zeros() as usize;
+	if n_bits > F::N_BITS {
+		bail!(Error::ArgumentRangeError {
+			arg: "value bits".into(),
+			range: 0..F::N_BITS + 1,
+		});
+	}
+
+	(0..n_bits)
+		.filter(|bit| (value >> bit) & 1 == 1)
+		.try_fold(F::ZERO, |acc, bit| Ok(acc + F::basis(0, bit)?))
+}
+
+fn bits_inner_product<F: TowerField>(
+	coords: impl IntoIterator<Item = F>,
+	offset: usize,
+) -> Result<F, Error> {
+	coords
+		.into_iter()
+		.enumerate()
+		.try_fold(F::ZERO, |acc, (bit, coord)| Ok(acc + coord * F::basis(0, offset + bit)?))
+}
+
+fn table_multilinear_extension<P: PackedField>(
+	n_vars: usize,
+	entry: impl Fn(usize) -> Result<P::Scalar, Error>,
+) -> Result<MultilinearExtension<P>, Error>
+where
+	P::Scalar: TowerField,
+{
+	let n_packed = (1 << n_vars) / P::WIDTH;
+	if n_packed == 0 {
+		bail!(Error::PackedFieldNotFilled {
+			length: 1 << n_vars,
+			packed_width: P::WIDTH,
+		});
+	}
+
+	let values = (0..n_packed)
+		.map(|i| {
+			let mut packed_value = P::default();
+			for j in 0..P::WIDTH {
+				packed_value.set(j, entry(i * P::WIDTH + j)?);
+			}
+			Ok(packed_value)
+		})
+		.collect::<Result<Vec<_>, Error>>()?;
+	MultilinearExtension::from_values(values)
+}
+
+/// The table of all `n_bits`-bit integers, used for range checks.
+///
+/// The entry at index $i$ is the value $i$ itself, and the multilinear extension is
+/// $T(x) = \sum_j x_j \beta_j$ for the $\mathbb{F}_2$-basis elements $\beta_j$.
+#[derive(Debug, Copy, Clone)]
+pub struct RangeTable {
+	n_bits: usize,
+}
+
+impl RangeTable {
+	/// Constructs the table of `n_bits`-bit integers.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::ArgumentRangeError`] if the table size $2^{n}$ does not fit into a `usize`
+	pub fn new(n_bits: usize) -> Result<Self, Error> {
+		if n_bits >= usize::BITS as usize {
+			bail!(Error::ArgumentRangeError {
+				arg: "n_bits".into(),
+				range: 0..usize::BITS as usize,
+			});
+		}
+		Ok(Self { n_bits })
+	}
+
+	/// The table index of `value`, if it is in range.
+	pub fn index(&self, value: u128) -> Option<usize> {
+		(value >> self.n_bits == 0).then_some(value as usize)
+	}
+
+	pub fn multilinear_extension<P>(&self) -> Result<MultilinearExtension<P>, Error>
+	where
+		P: PackedField,
+		P::Scalar: TowerField,
+	{
+		table_multilinear_extension(self.n_bits, |index| value_to_field(index as u128))
+	}
+}
+
+impl<F: TowerField> MultivariatePoly<F> for RangeTable {
+	fn n_vars(&self) -> usize {
+		self.n_bits
+	}
+
+	fn degree(&self) -> usize {
+		1
+	}
+
+	fn evaluate(&self, query: &[F]) -> Result<F, Error> {
+		if query.len() != self.n_bits {
+			bail!(Error::IncorrectQuerySize {
+				expected: self.n_bits,
+			});
+		}
+
+		bits_inner_product(query.iter().copied(), 0)
+	}
+
+	fn binary_tower_level(&self) -> usize {
+		log2_ceil_usize(self.n_bits)
+	}
+}
+
+/// A bitwise operation on two operands.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BitwiseOp {
+	And,
+	Or,
+	Xor,
+}
+
+impl BitwiseOp {
+	pub fn apply(&self, a: u128, b: u128) -> u128 {
+		match self {
+			Self::And => a & b,
+			Self::Or => a | b,
+			Self::Xor => a ^ b,
+		}
+	}
+
+	/// The multilinear extension of the operation on a single bit.
+	///
+	/// The fields have characteristic 2, so $x \oplus y = x + y$ and $x \lor y = x + y + xy$.
+	fn evaluate_bit<F: TowerField>(&self, x: F, y: F) -> F {
+		match self {
+			Self::And => x * y,
+			Self::Or => x + y + x * y,
+			Self::Xor => x + y,
+		}
+	}
+}
+
+/// The table of a bitwise operation on two `n_bits`-bit operands.
+///
+/// The entry at index $a + 2^n b$ is the concatenation $a + 2^n b + 2^{2n} \mathrm{op}(a, b)$, so
+/// a lookup into the table proves that the high third of each looked-up value is the result of
+/// the operation on its two lower thirds. The entries occupy $3n$ bits and must fit into the
+/// field.
+#[derive(Debug, Copy, Clone)]
+pub struct BitwiseTable {
+	op: BitwiseOp,
+	n_bits: usize,
+}
+
+impl BitwiseTable {
+	/// Constructs the table of `op` on `n_bits`-bit operands.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::ArgumentRangeError`] if the table size $2^{2n}$ does not fit into a `usize`
+	pub fn new(op: BitwiseOp, n_bits: usize) -> Result<Self, Error> {
+		if 2 * n_bits >= usize::BITS as usize {
+			bail!(Error::ArgumentRangeError {
+				arg: "n_bits".into(),
+				range: 0..usize::BITS as usize / 2,
+			});
+		}
+		Ok(Self { op, n_bits })
+	}
+
+	/// The table index of the operands `a` and `b`, if both are in range.
+	pub fn index(&self, a: u128, b: u128) -> Option<usize> {
+		if a >> self.n_bits != 0 || b >> self.n_bits != 0 {
+			return None;
+		}
+		Some((a | b << self.n_bits) as usize)
+	}
+
+	/// The table entry of the operands `a` and `b`, as an integer.
+	pub fn entry(&self, a: u128, b: u128) -> u128 {
+		a | b << self.n_bits | self.op.apply(a, b) << (2 * self.n_bits)
+	}
+
+	pub fn multilinear_extension<P>(&self) -> Result<MultilinearExtension<P>, Error>
+	where
+		P: PackedField,
+		P::Scalar: TowerField,
+	{
+		let mask = (1 << self.n_bits) - 1;
+		table_multilinear_extension(2 * self.n_bits, |index| {
+			let index = index as u128;
+			value_to_field(self.entry(index & mask, index >> self.n_bits))
+		})
+	}
+}
+
+impl<F: TowerField> MultivariatePoly<F> for BitwiseTable {
+	fn n_vars(&self) -> usize {
+		2 * self.n_bits
+	}
+
+	fn degree(&self) -> usize {
+		match self.op {
+			BitwiseOp::Xor => 1,
+			BitwiseOp::And | BitwiseOp::Or => 2,
+		}
+	}
+
+	fn evaluate(&self, query: &[F]) -> Result<F, Error> {
+		let n_vars = 2 * self.n_bits;
+		if query.len() != n_vars {
+			bail!(Error::IncorrectQuerySize { expected: n_vars });
+		}
+
+		let (a, b) = query.split_at(self.n_bits);
+		let result = a.iter().zip(b).map(|(&x, &y)| self.op.evaluate_bit(x, y));
+		Ok(bits_inner_product(query.iter().copied(), 0)? + bits_inner_product(result, n_vars)?)
+	}
+
+	fn binary_tower_level(&self) -> usize {
+		log2_ceil_usize(3 * self.n_bits)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::polynomial::MultilinearQuery;
+	use assert_matches::assert_matches;
+	use binius_field::{BinaryField128b, BinaryField32b, Field, PackedBinaryField4x32b};
+	use rand::{rngs::StdRng, SeedableRng};
+	use std::iter::repeat_with;
+
+	fn assert_consistent<F, P>(table: &impl MultivariatePoly<F>, mle: MultilinearExtension<P>)
+	where
+		F: TowerField,
+		P: PackedField<Scalar = F>,
+	{
+		let mut rng = StdRng::seed_from_u64(0);
+		let challenge = repeat_with(|| <F as Field>::random(&mut rng))
+			.take(table.n_vars())
+			.collect::<Vec<_>>();
+
+		let multilin_query = MultilinearQuery::<F>::with_full_query(&challenge).unwrap();
+		let eval: F = mle.evaluate(&multilin_query).unwrap();
+		assert_eq!(table.evaluate(&challenge).unwrap(), eval);
+	}
+
+	#[test]
+	fn test_range_table_entries() {
+		type F = BinaryField32b;
+		let table = RangeTable::new(8).unwrap();
+		let mle = table.multilinear_extension::<F>().unwrap();
+
+		for value in [0, 1, 37, 255] {
+			let index = table.index(value).unwrap();
+			assert_eq!(mle.evaluate_on_hypercube(index).unwrap(), F::new(value as u32));
+		}
+		assert_eq!(table.index(256), None);
+	}
+
+	#[test]
+	fn test_range_table_consistency() {
+		let table = RangeTable::new(6).unwrap();
+		assert_consistent(&table, table.multilinear_extension::<BinaryField128b>().unwrap());
+		assert_consistent(
+			&table,
+			table
+				.multilinear_extension::<PackedBinaryField4x32b>()
+				.unwrap(),
+		);
+	}
+
+	#[test]
+	fn test_range_table_rejects_wide_indices() {
+		assert!(RangeTable::new(usize::BITS as usize - 1).is_ok());
+		assert_matches!(
+			RangeTable::new(usize::BITS as usize),
+			Err(Error::ArgumentRangeError { .. })
+		);
+		assert!(BitwiseTable::new(BitwiseOp::And, usize::BITS as usize / 2 - 1).is_ok());
+		assert_matches!(
+			BitwiseTable::new(BitwiseOp::And, usize::BITS as usize / 2),
+			Err(Error::ArgumentRangeError { .. })
+		);
+	}
+
+	#[test]
+	fn test_bitwise_table_entries() {
+		type F = BinaryField32b;
+		for op in [BitwiseOp::And, BitwiseOp::Or, BitwiseOp::Xor] {
+			let table = BitwiseTable::new(op, 4).unwrap();
+			let mle = table.multilinear_extension::<F>().unwrap();
+
+			for (a, b) in [(0, 0), (5, 3), (15, 9), (12, 10)] {
+				let index = table.index(a, b).unwrap();
+				let expected = a | b << 4 | op.apply(a, b) << 8;
+				assert_eq!(mle.evaluate_on_hypercube(index).unwrap(), F::new(expected as u32));
+			}
+			assert_eq!(table.index(16, 0), None);
+		}
+	}
+
+	#[test]
+	fn test_bitwise_table_consistency() {
+		for op in [BitwiseOp::And, BitwiseOp::Or, BitwiseOp::Xor] {
+			let table = BitwiseTable::new(op, 3).unwrap();
+			assert_consistent(&table, table.multilinear_extension::<BinaryField128b>().unwrap());
+			assert_consistent(
+				&table,
+				table
+					.multilinear_extension::<PackedBinaryField4x32b>()
+					.unwrap(),
+			);
+		}
+	}
+}
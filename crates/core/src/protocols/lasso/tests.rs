@@ -3,9 +3,14 @@
 use crate::{
 	oracle::MultilinearOracleSet,
 	polynomial::MultilinearExtension,
-	protocols::lasso::{prove, verify, LassoBatches, LassoClaim, LassoWitness},
-	witness::MultilinearExtensionIndex,
+	protocols::lasso::{
+		prove, prove_counts,
+		tables::{value_to_field, BitwiseOp, BitwiseTable, RangeTable},
+		verify, Error, LassoBatches, LassoClaim, LassoWitness,
+	},
+	witness::{Error as WitnessError, MultilinearExtensionIndex},
 };
+use assert_matches::assert_matches;
 use binius_field::{
 	as_packed_field::PackedType, underlier::WithUnderlier, BinaryField128b, BinaryField16b,
 	BinaryField64b, Field, PackedBinaryField128x1b, PackedFieldIndexable, TowerField,
@@ -80,7 +85,9 @@ fn test_prove_verify_interaction() {
 	.unwrap();
 
 	// PROVER
-	let witness_index = MultilinearExtensionIndex::new();
+	let witness_index =
+		prove_counts::<C, U, F, _>(MultilinearExtensionIndex::new(), &witness, &lasso_batches)
+			.unwrap();
 
 	let gamma = F::new(137_u128);
 
@@ -101,3 +108,102 @@ fn test_prove_verify_interaction() {
 	let _verified_reduced_claim =
 		verify::<C, _>(&mut oracles.clone(), &claim, &lasso_batches, gamma, alpha).unwrap();
 }
+
+#[test]
+fn test_prove_verify_bitwise_table() {
+	type F = BinaryField128b;
+	type C = BinaryField16b;
+	type U = <PackedBinaryField128x1b as WithUnderlier>::Underlier;
+
+	let n_bits = 4;
+	let n_vars = 2 * n_bits;
+	let table = BitwiseTable::new(BitwiseOp::Xor, n_bits).unwrap();
+
+	// Setup witness
+
+	let operands = (0..1 << n_vars)
+		.map(|i: u128| ((i * 7) % 16, (i * 13 + 5) % 16))
+		.collect::<Vec<_>>();
+	let u_to_t_mapping = operands
+		.iter()
+		.map(|&(a, b)| table.index(a, b).unwrap())
+		.collect::<Vec<_>>();
+	let u_values = operands
+		.iter()
+		.map(|&(a, b)| value_to_field::<F>(table.entry(a, b)).unwrap())
+		.collect::<Vec<_>>();
+
+	let t_polynomial = table
+		.multilinear_extension::<PackedType<U, F>>()
+		.unwrap()
+		.specialize_arc_dyn();
+	let u_polynomial = MultilinearExtension::from_values(u_values)
+		.unwrap()
+		.specialize_arc_dyn();
+
+	let witness = LassoWitness::<PackedType<U, F>, _>::new(
+		t_polynomial,
+		vec![u_polynomial],
+		vec![u_to_t_mapping],
+	)
+	.unwrap();
+
+	// Setup claim
+	let mut oracles = MultilinearOracleSet::<F>::new();
+
+	let t = oracles.add_transparent(table).unwrap();
+	let lookup_batch = oracles.add_committed_batch(n_vars, F::TOWER_LEVEL);
+	let u = oracles.add_committed(lookup_batch);
+
+	let lasso_batches = LassoBatches::new_in::<C, _>(&mut oracles, n_vars, n_vars, 1);
+
+	let claim = LassoClaim::from_oracle_ids(&oracles, t, [u]).unwrap();
+
+	// The counts are committed before the challenges are sampled, so proving without them fails.
+	assert_matches!(
+		prove::<C, U, F, F, _>(
+			&mut oracles.clone(),
+			MultilinearExtensionIndex::new(),
+			&claim,
+			witness.clone(),
+			&lasso_batches,
+			F::ONE,
+			F::ONE,
+		),
+		Err(Error::Witness(WitnessError::MissingWitness { .. }))
+	);
+
+	// PROVER
+	let witness_index =
+		prove_counts::<C, U, F, _>(MultilinearExtensionIndex::new(), &witness, &lasso_batches)
+			.unwrap();
+
+	let gamma = F::new(137_u128);
+
+	let alpha = F::ONE;
+
+	let _prove_output = prove::<C, U, F, F, _>(
+		&mut oracles.clone(),
+		witness_index,
+		&claim,
+		witness,
+		&lasso_batches,
+		gamma,
+		alpha,
+	)
+	.unwrap();
+
+	// VERIFIER
+	let _verified_reduced_claim =
+		verify::<C, _>(&mut oracles.clone(), &claim, &lasso_batches, gamma, alpha).unwrap();
+}
+
+#[test]
+fn test_claim_from_invalid_oracle_id() {
+	let mut oracles = MultilinearOracleSet::<BinaryField128b>::new();
+	let t = oracles
+		.add_transparent(RangeTable::new(8).unwrap())
+		.unwrap();
+
+	assert!(LassoClaim::from_oracle_ids(&oracles, t, [t + 1]).is_err());
+}
@@ -29,14 +29,20 @@
 
 pub mod abstract_sumcheck;
 pub mod evalcheck;
+pub mod executor;
+pub mod folding;
 pub mod fri;
+pub mod gkr_circuit;
 pub mod gkr_gpa;
 pub mod gkr_prodcheck;
 pub mod gkr_sumcheck;
 pub mod greedy_evalcheck;
 pub mod lasso;
 pub mod msetcheck;
+pub mod opening;
+pub mod permcheck;
 pub mod prodcheck;
+pub mod ring_switch;
 pub mod sumcheck;
 pub mod sumcheck_v2;
 #[allow(dead_code)]
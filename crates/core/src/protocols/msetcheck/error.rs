@@ -13,7 +13,7 @@ pub enum Error {
 	NullaryRelation,
 	#[error("multiplicative alpha challenge only makes sense for dimensionality two and above")]
 	IncorrectAlpha,
-	#[error("the number of variables in some multilinear oracle is not equal to the rest")]
+	#[error("the number of variables in some multilinear oracle is not equal to the rest of its relation")]
 	NumVariablesMismatch,
 	#[error("the challenge vector length does not equal multiset dimensionality")]
 	IncorrectChallengeLength,
@@ -21,6 +21,8 @@ pub enum Error {
 	WitnessDimensionalityMismatch,
 	#[error("the number of variables in some witness multilinear does not match the claim")]
 	WitnessNumVariablesMismatch,
+	#[error("witnesses smaller than the underlier are not supported")]
+	WitnessSmallerThanUnderlier,
 	#[error("iopolynomial error: {0}")]
 	Oracle(#[from] OracleError),
 	#[error("polynomial error: {0}")]
@@ -4,6 +4,10 @@
 //!
 //! Multiset check provides a deterministic reduction of multiset equality claims to product check claims
 //! (see [`prove`](self::prove::prove()) for an in-depth description)
+//!
+//! The product check claim is in turn proven with
+//! [`gkr_prodcheck::prove_grand_product`](crate::protocols::gkr_prodcheck::prove_grand_product),
+//! which leaves evalcheck claims on the linear combinations of the relation columns.
 
 mod error;
 #[allow(clippy::module_inception)]
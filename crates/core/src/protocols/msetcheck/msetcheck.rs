@@ -2,7 +2,7 @@
 
 use super::error::Error;
 use crate::{
-	oracle::{MultilinearOracleSet, MultilinearPolyOracle},
+	oracle::{MultilinearOracleSet, MultilinearPolyOracle, OracleId},
 	protocols::gkr_prodcheck::{ProdcheckClaim, ProdcheckWitness},
 	witness::{MultilinearExtensionIndex, MultilinearWitness},
 };
@@ -12,10 +12,14 @@ use binius_field::{
 	Field, PackedField, TowerField,
 };
 use binius_utils::bail;
-use getset::Getters;
+use getset::{CopyGetters, Getters};
 use std::iter;
 
-#[derive(Debug, Getters)]
+/// A claim that two relations are equal as multisets.
+///
+/// The T and U relations may have different heights, in which case the smaller relation is padded
+/// with all-zero rows to the height of the larger one.
+#[derive(Debug, Getters, CopyGetters)]
 pub struct MsetcheckClaim<F: Field> {
 	/// Oracles to the T polynomials
 	#[get = "pub"]
@@ -23,6 +27,12 @@ pub struct MsetcheckClaim<F: Field> {
 	/// Oracles to the U polynomials
 	#[get = "pub"]
 	u_oracles: Vec<MultilinearPolyOracle<F>>,
+	/// Number of variables in each of the T oracles
+	#[get_copy = "pub"]
+	t_n_vars: usize,
+	/// Number of variables in each of the U oracles
+	#[get_copy = "pub"]
+	u_n_vars: usize,
 }
 
 impl<F: Field> MsetcheckClaim<F> {
@@ -34,11 +44,14 @@ impl<F: Field> MsetcheckClaim<F> {
 		let t_oracles = t_oracles.into_iter().collect::<Vec<_>>();
 		let u_oracles = u_oracles.into_iter().collect::<Vec<_>>();
 
-		relation_sanity_checks(&t_oracles, &u_oracles, |oracle| oracle.n_vars())?;
+		let (t_n_vars, u_n_vars) =
+			relation_sanity_checks(&t_oracles, &u_oracles, |oracle| oracle.n_vars())?;
 
 		Ok(Self {
 			t_oracles,
 			u_oracles,
+			t_n_vars,
+			u_n_vars,
 		})
 	}
 
@@ -47,13 +60,13 @@ impl<F: Field> MsetcheckClaim<F> {
 		self.t_oracles.len()
 	}
 
-	/// Number of variables in each of the multilinear oracles.
+	/// Number of variables of the relations after padding, the larger of the T and U heights.
 	pub fn n_vars(&self) -> usize {
-		self.t_oracles.first().expect("non nullary").n_vars()
+		self.t_n_vars.max(self.u_n_vars)
 	}
 }
 
-#[derive(Debug, Getters)]
+#[derive(Debug, Getters, CopyGetters)]
 pub struct MsetcheckWitness<'a, PW: PackedField> {
 	/// Witnesses to the T polynomials
 	#[get = "pub"]
@@ -61,6 +74,12 @@ pub struct MsetcheckWitness<'a, PW: PackedField> {
 	/// Witnesses to the U polynomials
 	#[get = "pub"]
 	u_polynomials: Vec<MultilinearWitness<'a, PW>>,
+	/// Number of variables in each of the T witness multilinears
+	#[get_copy = "pub"]
+	t_n_vars: usize,
+	/// Number of variables in each of the U witness multilinears
+	#[get_copy = "pub"]
+	u_n_vars: usize,
 }
 
 impl<'a, PW: PackedField> MsetcheckWitness<'a, PW> {
@@ -72,11 +91,14 @@ impl<'a, PW: PackedField> MsetcheckWitness<'a, PW> {
 		let t_polynomials = t_polynomials.into_iter().collect::<Vec<_>>();
 		let u_polynomials = u_polynomials.into_iter().collect::<Vec<_>>();
 
-		relation_sanity_checks(&t_polynomials, &u_polynomials, |witness| witness.n_vars())?;
+		let (t_n_vars, u_n_vars) =
+			relation_sanity_checks(&t_polynomials, &u_polynomials, |witness| witness.n_vars())?;
 
 		Ok(Self {
 			t_polynomials,
 			u_polynomials,
+			t_n_vars,
+			u_n_vars,
 		})
 	}
 
@@ -85,9 +107,9 @@ impl<'a, PW: PackedField> MsetcheckWitness<'a, PW> {
 		self.t_polynomials.len()
 	}
 
-	/// Number of variables in each of the witness multilinears.
+	/// Number of variables of the relations after padding, the larger of the T and U heights.
 	pub fn n_vars(&self) -> usize {
-		return self.t_polynomials.first().expect("non nullary").n_vars();
+		self.t_n_vars.max(self.u_n_vars)
 	}
 }
 
@@ -98,12 +120,41 @@ pub struct MsetcheckProveOutput<'a, U: UnderlierType + PackScalar<FW>, F: Field,
 	pub witness_index: MultilinearExtensionIndex<'a, U, FW>,
 }
 
+/// The oracles added for the linear combination of the columns of one relation.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct RelationOracleIds {
+	/// The linear combination at the padded height, offset by $\gamma$
+	pub lincom: OracleId,
+	/// For a relation smaller than the padded height, the linear combination at its own height
+	/// without the offset, and its zero padding to the padded height
+	pub padding: Option<(OracleId, OracleId)>,
+}
+
+/// Reduces a multiset check claim to a product check claim over the linear combinations of the
+/// relation columns, adding the linear combination oracles to `oracles`.
+///
+/// This only transforms the claim. Provers should use [`prove`](super::prove()) instead, which
+/// performs the same reduction and also computes the linear combination witnesses in parallel
+/// and adds them to the witness index.
 pub fn reduce_msetcheck_claim<F: TowerField>(
 	oracles: &mut MultilinearOracleSet<F>,
 	msetcheck_claim: &MsetcheckClaim<F>,
 	gamma: F,
 	alpha: Option<F>,
 ) -> Result<ProdcheckClaim<F>, Error> {
+	let (prodcheck_claim, _) =
+		reduce_msetcheck_claim_with_oracle_ids(oracles, msetcheck_claim, gamma, alpha)?;
+	Ok(prodcheck_claim)
+}
+
+/// Reduces a multiset check claim as [`reduce_msetcheck_claim`], also returning the oracles added
+/// for the T and U relations.
+pub(super) fn reduce_msetcheck_claim_with_oracle_ids<F: TowerField>(
+	oracles: &mut MultilinearOracleSet<F>,
+	msetcheck_claim: &MsetcheckClaim<F>,
+	gamma: F,
+	alpha: Option<F>,
+) -> Result<(ProdcheckClaim<F>, [RelationOracleIds; 2]), Error> {
 	// Claim sanity checks
 	let dimensions = msetcheck_claim.dimensions();
 	let n_vars = msetcheck_claim.n_vars();
@@ -114,28 +165,51 @@ pub fn reduce_msetcheck_claim<F: TowerField>(
 
 	// for a relation represented by the polynomials (T1, .., Tn) and challenges (gamma, alpha),
 	// construct a linear combination oracle for gamma + T1 + alpha * T2 + ... + alpha^(n-1) * Tn
-	let mut lincom_oracle = |relation_oracles: &[MultilinearPolyOracle<F>]| -> Result<_, Error> {
+	let mut lincom_oracle = |relation_oracles: &[MultilinearPolyOracle<F>],
+	                         relation_n_vars: usize|
+	 -> Result<_, Error> {
 		let inner_coeffs = iter::successors(Some(F::ONE), |coeff| alpha.map(|alpha| alpha * coeff));
 		let inner = inner_coeffs
 			.zip(relation_oracles)
 			.map(|(coeff, oracle)| (oracle.id(), coeff));
-		let oracle_id = oracles.add_linear_combination_with_offset(n_vars, gamma, inner)?;
-		Ok(oracles.oracle(oracle_id))
+
+		if relation_n_vars == n_vars {
+			let lincom = oracles.add_linear_combination_with_offset(n_vars, gamma, inner)?;
+			return Ok(RelationOracleIds {
+				lincom,
+				padding: None,
+			});
+		}
+
+		// the padding rows of a smaller relation are all zero, so their linear combination is gamma
+		let unpadded = oracles.add_linear_combination(relation_n_vars, inner)?;
+		let zero_padded = oracles.add_zero_padded(unpadded, n_vars)?;
+		let lincom =
+			oracles.add_linear_combination_with_offset(n_vars, gamma, [(zero_padded, F::ONE)])?;
+		Ok(RelationOracleIds {
+			lincom,
+			padding: Some((unpadded, zero_padded)),
+		})
 	};
 
-	let t_oracle = lincom_oracle(&msetcheck_claim.t_oracles)?;
-	let u_oracle = lincom_oracle(&msetcheck_claim.u_oracles)?;
+	let t_oracle_ids = lincom_oracle(&msetcheck_claim.t_oracles, msetcheck_claim.t_n_vars)?;
+	let u_oracle_ids = lincom_oracle(&msetcheck_claim.u_oracles, msetcheck_claim.u_n_vars)?;
 
-	let prodcheck_claim = ProdcheckClaim { t_oracle, u_oracle };
+	let prodcheck_claim = ProdcheckClaim {
+		t_oracle: oracles.oracle(t_oracle_ids.lincom),
+		u_oracle: oracles.oracle(u_oracle_ids.lincom),
+	};
 
-	Ok(prodcheck_claim)
+	Ok((prodcheck_claim, [t_oracle_ids, u_oracle_ids]))
 }
 
+/// Checks that the relations are well formed and returns the number of variables of the T and U
+/// columns.
 fn relation_sanity_checks<Column>(
 	t: &[Column],
 	u: &[Column],
 	n_vars: impl Fn(&Column) -> usize,
-) -> Result<(), Error> {
+) -> Result<(usize, usize), Error> {
 	// same dimensionality
 	if t.len() != u.len() {
 		bail!(Error::IncorrectDimensions);
@@ -146,16 +220,17 @@ fn relation_sanity_checks<Column>(
 		bail!(Error::NullaryRelation);
 	}
 
-	// same n_vars
-	let first_n_vars = n_vars(t.first().expect("non nullary"));
-	let equal_n_vars = t
-		.iter()
-		.chain(u)
-		.all(|column| n_vars(column) == first_n_vars);
+	// same n_vars within each relation
+	let relation_n_vars = |relation: &[Column]| -> Result<usize, Error> {
+		let first_n_vars = n_vars(&relation[0]);
+		let equal_n_vars = relation.iter().all(|column| n_vars(column) == first_n_vars);
 
-	if !equal_n_vars {
-		bail!(Error::NumVariablesMismatch);
-	}
+		if !equal_n_vars {
+			bail!(Error::NumVariablesMismatch);
+		}
+
+		Ok(first_n_vars)
+	};
 
-	Ok(())
+	Ok((relation_n_vars(t)?, relation_n_vars(u)?))
 }
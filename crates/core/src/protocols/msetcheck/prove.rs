@@ -2,11 +2,14 @@
 
 use super::{
 	error::Error,
-	msetcheck::{reduce_msetcheck_claim, MsetcheckClaim, MsetcheckProveOutput, MsetcheckWitness},
+	msetcheck::{
+		reduce_msetcheck_claim_with_oracle_ids, MsetcheckClaim, MsetcheckProveOutput,
+		MsetcheckWitness,
+	},
 };
 use crate::{
 	oracle::MultilinearOracleSet,
-	polynomial::Error as PolynomialError,
+	polynomial::util::fill_linear_combination,
 	protocols::gkr_prodcheck::ProdcheckWitness,
 	witness::{MultilinearExtensionIndex, MultilinearWitness},
 };
@@ -16,8 +19,7 @@ use binius_field::{
 	PackedField, PackedFieldIndexable, TowerField,
 };
 use binius_utils::bail;
-use rayon::prelude::*;
-use std::sync::Arc;
+use std::{iter, sync::Arc};
 use tracing::instrument;
 
 /// Prove a multiset check instance reduction.
@@ -33,6 +35,14 @@ use tracing::instrument;
 ///
 /// where $\gamma$ and $\alpha$ are some large field challenges sampled via Fiat-Shamir
 /// (`alpha` is non-`None` if $n \ge 2$).
+///
+/// When one relation has fewer variables than the other, it is padded with all-zero rows: its
+/// linear combination without $\gamma$ is zero padded to the larger number of variables, so that
+/// $\gamma$ is the value of $T'$ or $U'$ at the padding rows. The witnesses of the linear
+/// combinations without $\gamma$ and of their zero padding are added to the witness index.
+///
+/// $T'$ and $U'$ are not materialized: the product check witness computes their values from the
+/// relation columns by hypercube index.
 #[instrument(skip_all, name = "msetcheck::prove", level = "debug")]
 pub fn prove<'a, U, F, FW>(
 	oracles: &mut MultilinearOracleSet<F>,
@@ -48,7 +58,8 @@ where
 	FW: TowerField + From<F>,
 	PackedType<U, FW>: PackedFieldIndexable,
 {
-	let prodcheck_claim = reduce_msetcheck_claim(oracles, msetcheck_claim, gamma, alpha)?;
+	let (prodcheck_claim, [t_oracle_ids, u_oracle_ids]) =
+		reduce_msetcheck_claim_with_oracle_ids(oracles, msetcheck_claim, gamma, alpha)?;
 
 	let dimensions = msetcheck_claim.dimensions();
 	let n_vars = msetcheck_claim.n_vars();
@@ -57,64 +68,84 @@ where
 		bail!(Error::WitnessDimensionalityMismatch);
 	}
 
-	if msetcheck_witness.n_vars() != n_vars {
+	if msetcheck_witness.t_n_vars() != msetcheck_claim.t_n_vars()
+		|| msetcheck_witness.u_n_vars() != msetcheck_claim.u_n_vars()
+	{
 		bail!(Error::WitnessNumVariablesMismatch);
 	}
 
 	let packing_log_width = PackedType::<U, FW>::LOG_WIDTH;
+	if msetcheck_claim.t_n_vars().min(msetcheck_claim.u_n_vars()) < packing_log_width {
+		bail!(Error::WitnessSmallerThanUnderlier);
+	}
 
-	let lincom_witness = |relation_witnesses: &[MultilinearWitness<'a, PackedType<U, FW>>]| -> Result<Arc<[U]>, Error> {
-		// TODO: preallocate values
-		// Populate the accumulator vector with additive challenge term
-        let mut underliers = vec![U::default(); 1 << (n_vars - packing_log_width)];
-        let values = PackedType::<U, FW>::unpack_scalars_mut(
-            PackedType::<U, FW>::from_underliers_ref_mut(underliers.as_mut_slice()));
-
-        values.fill(FW::from(gamma));
-
-		let (first_witness, rest_witnesses) = relation_witnesses
-			.split_first()
-			.expect("dimensionality checked above");
-
-		// first dimension of the relation is not weighted
-		values.par_iter_mut().enumerate().try_for_each(
-			|(i, values_i)| -> Result<_, PolynomialError> {
-				*values_i += first_witness.evaluate_on_hypercube(i)?;
-				Ok(())
-			},
-		)?;
-
-		let fw_alpha = FW::from(alpha.expect("dimensionality checked on reduction"));
-		let mut fw_coeff = fw_alpha;
-
-		// the rest are weighted - apply small field optimization where possible
-		for rest_witness in rest_witnesses {
-			values.par_iter_mut().enumerate().try_for_each(
-				|(i, values_i)| -> Result<_, PolynomialError> {
-					*values_i += rest_witness.evaluate_on_hypercube_and_scale(i, fw_coeff)?;
-					Ok(())
-				},
-			)?;
+	let gamma = FW::from(gamma);
 
-			fw_coeff *= fw_alpha;
-		}
+	// first dimension of the relation is not weighted, the rest are weighted by powers of alpha
+	let weighted = |relation_witnesses: &[MultilinearWitness<'a, PackedType<U, FW>>]| {
+		let coeffs =
+			iter::successors(Some(FW::ONE), |coeff| alpha.map(|alpha| FW::from(alpha) * coeff));
+		let inner = iter::zip(relation_witnesses.iter().cloned(), coeffs).collect::<Vec<_>>();
+		debug_assert_eq!(inner.len(), relation_witnesses.len());
+		inner
+	};
 
+	let lincom_witness = |relation_witnesses: &[MultilinearWitness<'a, PackedType<U, FW>>],
+	                      relation_n_vars: usize|
+	 -> Result<Arc<[U]>, Error> {
+		let mut underliers = vec![U::default(); 1 << (relation_n_vars - packing_log_width)];
+		let packed = PackedType::<U, FW>::from_underliers_ref_mut(underliers.as_mut_slice());
+		fill_linear_combination(relation_n_vars, FW::ZERO, &weighted(relation_witnesses), packed)?;
 		Ok(underliers.into())
 	};
 
-	let t_oracle_id = prodcheck_claim.t_oracle.id();
-	let u_oracle_id = prodcheck_claim.u_oracle.id();
+	// pads the values of a smaller relation's linear combination with zeros to n_vars variables
+	let zero_padded_witness = |values: &[U]| -> Arc<[U]> {
+		let mut underliers = vec![U::default(); 1 << (n_vars - packing_log_width)];
+		underliers[..values.len()].copy_from_slice(values);
+		underliers.into()
+	};
 
-	let t_polynomial = lincom_witness(msetcheck_witness.t_polynomials())?;
-	let u_polynomial = lincom_witness(msetcheck_witness.u_polynomials())?;
+	// The gamma-shifted linear combinations are only read by the grand product circuit, which
+	// computes their values by index instead of materializing them. The rows past the height of a
+	// smaller relation are padding, where the linear combination is gamma.
+	let lincom_fn = |relation_witnesses: &[MultilinearWitness<'a, PackedType<U, FW>>],
+	                 relation_n_vars: usize| {
+		let inner = weighted(relation_witnesses);
+		let offset = gamma;
+		move |index: usize| {
+			if index >> relation_n_vars != 0 {
+				return offset;
+			}
+			inner.iter().fold(offset, |acc, (poly, coeff)| {
+				let value = poly
+					.evaluate_on_hypercube(index)
+					.expect("index is within the relation hypercube");
+				acc + value * *coeff
+			})
+		}
+	};
 
-	let witness_index = witness_index
-		.update_owned::<FW, _>([(t_oracle_id, t_polynomial), (u_oracle_id, u_polynomial)])?;
+	// the linear combinations without gamma of padded relations are needed to evaluate the
+	// zero padding oracles
+	let mut padding_witnesses = Vec::new();
+	for (relation_witnesses, relation_n_vars, oracle_ids) in [
+		(msetcheck_witness.t_polynomials(), msetcheck_witness.t_n_vars(), t_oracle_ids),
+		(msetcheck_witness.u_polynomials(), msetcheck_witness.u_n_vars(), u_oracle_ids),
+	] {
+		if let Some((unpadded_id, zero_padded_id)) = oracle_ids.padding {
+			let unpadded = lincom_witness(relation_witnesses, relation_n_vars)?;
+			padding_witnesses.push((zero_padded_id, zero_padded_witness(&unpadded)));
+			padding_witnesses.push((unpadded_id, unpadded));
+		}
+	}
+	let witness_index = witness_index.update_owned::<FW, _>(padding_witnesses)?;
 
-	let prodcheck_witness = ProdcheckWitness {
-		t_poly: witness_index.get_multilin_poly(t_oracle_id)?,
-		u_poly: witness_index.get_multilin_poly(u_oracle_id)?,
-	};
+	let prodcheck_witness = ProdcheckWitness::from_fns(
+		n_vars,
+		lincom_fn(msetcheck_witness.t_polynomials(), msetcheck_witness.t_n_vars()),
+		lincom_fn(msetcheck_witness.u_polynomials(), msetcheck_witness.u_n_vars()),
+	);
 
 	Ok(MsetcheckProveOutput {
 		prodcheck_claim,
@@ -1,17 +1,42 @@
 // Copyright 2024 Ulvetanna Inc.
 
 use crate::{
+	challenger::new_hasher_challenger,
 	oracle::MultilinearOracleSet,
-	polynomial::MultilinearExtension,
-	protocols::msetcheck::{prove, verify, MsetcheckClaim, MsetcheckWitness},
+	polynomial::{IsomorphicEvaluationDomainFactory, MultilinearExtension, MultilinearQuery},
+	protocols::{
+		gkr_prodcheck::{
+			prove_grand_product, verify_grand_product, ProdcheckGrandProductProveOutput,
+			ProdcheckInput,
+		},
+		msetcheck::{prove, verify, Error, MsetcheckClaim, MsetcheckWitness},
+	},
 	witness::{MultilinearExtensionIndex, MultilinearWitness},
 };
+use assert_matches::assert_matches;
 use binius_field::{
 	underlier::WithUnderlier, BinaryField128b, BinaryField16b, BinaryField32b, BinaryField64b,
-	ExtensionField, Field, PackedBinaryField1x128b, PackedField, TowerField,
+	ExtensionField, Field, PackedBinaryField1x128b, PackedBinaryField4x32b, PackedField,
+	TowerField,
 };
+use binius_hash::GroestlHasher;
+use itertools::izip;
 use std::iter::{successors, Step};
 
+fn evaluate_input(
+	input: &ProdcheckInput<PackedBinaryField1x128b>,
+	point: &[BinaryField128b],
+) -> BinaryField128b {
+	let values = (0..1 << input.n_vars())
+		.map(|i| input.evaluate_on_hypercube(i).unwrap())
+		.collect::<Vec<_>>();
+	let query = MultilinearQuery::<BinaryField128b>::with_full_query(point).unwrap();
+	MultilinearExtension::from_values(values)
+		.unwrap()
+		.evaluate(&query)
+		.unwrap()
+}
+
 fn create_polynomial<F: Field + Step, PW>(
 	n_vars: usize,
 	stride: usize,
@@ -126,3 +151,192 @@ fn test_prove_verify_interaction() {
 	assert_eq!(verified_reduced_claim.t_oracle.n_vars(), n_vars);
 	assert_eq!(verified_reduced_claim.u_oracle.n_vars(), n_vars);
 }
+
+#[test]
+fn test_prove_rejects_malformed_inputs() {
+	type P = PackedBinaryField4x32b;
+	type F = BinaryField32b;
+	type U = <P as WithUnderlier>::Underlier;
+
+	assert_matches!(MsetcheckWitness::<P>::new([], []), Err(Error::NullaryRelation));
+
+	// The witness does not fill a single underlier.
+	let n_vars = 1;
+	let t_polynomial = create_polynomial::<F, P>(n_vars, 3, false);
+	let u_polynomial = create_polynomial::<F, P>(n_vars, 3, true);
+	let witness = MsetcheckWitness::new([t_polynomial], [u_polynomial]).unwrap();
+
+	let mut oracles = MultilinearOracleSet::<F>::new();
+	let batch_id = oracles.add_committed_batch(n_vars, F::TOWER_LEVEL);
+	let [t, u] = oracles.add_committed_multiple(batch_id);
+	let claim = MsetcheckClaim::new([oracles.oracle(t)], [oracles.oracle(u)]).unwrap();
+
+	let witness_index = MultilinearExtensionIndex::<U, F>::new();
+	assert_matches!(
+		prove(&mut oracles, witness_index, &claim, witness, F::new(0x123), None),
+		Err(Error::WitnessSmallerThanUnderlier)
+	);
+}
+
+#[test]
+fn test_prove_verify_with_grand_product() {
+	type P = PackedBinaryField1x128b;
+	type F = BinaryField128b;
+	type U = <P as WithUnderlier>::Underlier;
+	type FDomain = BinaryField32b;
+
+	let n_vars = 6;
+	let t_polynomial = create_polynomial::<BinaryField16b, P>(n_vars, 7, false);
+	let u_polynomial = create_polynomial::<BinaryField16b, P>(n_vars, 7, true);
+	let witness = MsetcheckWitness::new([t_polynomial], [u_polynomial]).unwrap();
+
+	let mut oracles = MultilinearOracleSet::<F>::new();
+	let batch_id = oracles.add_committed_batch(n_vars, BinaryField16b::TOWER_LEVEL);
+	let [t, u] = oracles.add_committed_multiple(batch_id);
+	let claim = MsetcheckClaim::new([oracles.oracle(t)], [oracles.oracle(u)]).unwrap();
+	let gamma = F::new(0x123);
+
+	let witness_index = MultilinearExtensionIndex::<U, F>::new();
+	let prove_output =
+		prove(&mut oracles.clone(), witness_index, &claim, witness, gamma, None).unwrap();
+	let prodcheck_claim = verify(&mut oracles.clone(), &claim, gamma, None).unwrap();
+
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	let ProdcheckGrandProductProveOutput {
+		evalcheck_multilinear_claims,
+		proof,
+	} = prove_grand_product::<_, _, FDomain, _>(
+		[prove_output.prodcheck_witness.clone()],
+		[prove_output.prodcheck_claim],
+		IsomorphicEvaluationDomainFactory::<FDomain>::default(),
+		challenger.clone(),
+	)
+	.unwrap();
+	let verified_claims = verify_grand_product([prodcheck_claim], proof, challenger).unwrap();
+
+	let witness = prove_output.prodcheck_witness;
+	assert_eq!(verified_claims.len(), 2);
+	for (proved, verified, poly) in
+		izip!(evalcheck_multilinear_claims, verified_claims, [witness.t_poly, witness.u_poly])
+	{
+		assert_eq!(proved.poly, verified.poly);
+		assert_eq!(proved.eval_point, verified.eval_point);
+		assert_eq!(proved.eval, verified.eval);
+
+		assert_matches!(poly, ProdcheckInput::Fn { .. });
+		assert_eq!(evaluate_input(&poly, &verified.eval_point), verified.eval);
+	}
+}
+
+#[test]
+fn test_prove_verify_differing_n_vars() {
+	type P = PackedBinaryField1x128b;
+	type F = BinaryField128b;
+	type U = <P as WithUnderlier>::Underlier;
+	type FDomain = BinaryField32b;
+
+	// The U relation is a lookup table, and the T relation holds its rows in reverse order at every
+	// fourth row, with all-zero rows in between.
+	let t_n_vars = 6;
+	let u_n_vars = 4;
+	let u_columns = [1, 100].map(|start| {
+		(0..1 << u_n_vars)
+			.map(|i| BinaryField16b::new(start + i))
+			.collect::<Vec<_>>()
+	});
+	let t_columns = u_columns.clone().map(|column| {
+		(0..1 << t_n_vars)
+			.map(|i| match i % 4 {
+				0 => column[column.len() - 1 - i / 4],
+				_ => BinaryField16b::ZERO,
+			})
+			.collect::<Vec<_>>()
+	});
+	let to_witness = |column: Vec<BinaryField16b>| -> MultilinearWitness<'static, P> {
+		MultilinearExtension::from_values(column)
+			.unwrap()
+			.specialize_arc_dyn()
+	};
+	let t_polynomials = t_columns.map(to_witness);
+	let u_polynomials = u_columns.map(to_witness);
+	let witness = MsetcheckWitness::new(t_polynomials.clone(), u_polynomials.clone()).unwrap();
+	assert_eq!(witness.n_vars(), t_n_vars);
+
+	let mut oracles = MultilinearOracleSet::<F>::new();
+	let t_batch_id = oracles.add_committed_batch(t_n_vars, BinaryField16b::TOWER_LEVEL);
+	let u_batch_id = oracles.add_committed_batch(u_n_vars, BinaryField16b::TOWER_LEVEL);
+	let t_oracle_ids = oracles.add_committed_multiple::<2>(t_batch_id);
+	let u_oracle_ids = oracles.add_committed_multiple::<2>(u_batch_id);
+	let claim = MsetcheckClaim::new(
+		t_oracle_ids.map(|id| oracles.oracle(id)),
+		u_oracle_ids.map(|id| oracles.oracle(id)),
+	)
+	.unwrap();
+	assert_eq!((claim.t_n_vars(), claim.u_n_vars()), (t_n_vars, u_n_vars));
+	assert_eq!(claim.n_vars(), t_n_vars);
+
+	let gamma = F::new(0x123);
+	let alpha = F::new(0x346);
+
+	let mut prover_oracles = oracles.clone();
+	let n_committed = prover_oracles.size();
+	let prove_output = prove(
+		&mut prover_oracles,
+		MultilinearExtensionIndex::<U, F>::new(),
+		&claim,
+		witness,
+		gamma,
+		Some(alpha),
+	)
+	.unwrap();
+	let prodcheck_claim = verify(&mut oracles.clone(), &claim, gamma, Some(alpha)).unwrap();
+	assert_eq!(prodcheck_claim.t_oracle, prove_output.prodcheck_claim.t_oracle);
+	assert_eq!(prodcheck_claim.u_oracle, prove_output.prodcheck_claim.u_oracle);
+	assert_eq!(prodcheck_claim.u_oracle.n_vars(), t_n_vars);
+
+	// The witnesses of the oracles added for the zero padding are in the witness index, and the
+	// gamma-shifted linear combinations are not materialized.
+	let lincom_ids = [
+		prove_output.prodcheck_claim.t_oracle.id(),
+		prove_output.prodcheck_claim.u_oracle.id(),
+	];
+	for id in n_committed..prover_oracles.size() {
+		let poly = prove_output.witness_index.get_multilin_poly(id);
+		if lincom_ids.contains(&id) {
+			assert!(poly.is_err());
+		} else {
+			assert_eq!(poly.unwrap().n_vars(), prover_oracles.n_vars(id));
+		}
+	}
+
+	let u_witness = &prove_output.prodcheck_witness.u_poly;
+	for i in 0..1 << t_n_vars {
+		let expected = if i < 1 << u_n_vars {
+			let vu1 = u_polynomials[0].evaluate_on_hypercube(i).unwrap();
+			let vu2 = u_polynomials[1].evaluate_on_hypercube(i).unwrap();
+			gamma + vu1 + alpha * vu2
+		} else {
+			gamma
+		};
+		assert_eq!(u_witness.evaluate_on_hypercube(i).unwrap(), expected);
+	}
+
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	let proof = prove_grand_product::<_, _, FDomain, _>(
+		[prove_output.prodcheck_witness],
+		[prove_output.prodcheck_claim],
+		IsomorphicEvaluationDomainFactory::<FDomain>::default(),
+		challenger.clone(),
+	)
+	.unwrap()
+	.proof;
+	let verified_claims = verify_grand_product([prodcheck_claim], proof, challenger).unwrap();
+	assert_eq!(verified_claims.len(), 2);
+
+	// The columns of each relation must still have the same number of variables.
+	let mixed_u_oracles = [u_oracle_ids[0], t_oracle_ids[1]].map(|id| oracles.oracle(id));
+	assert_matches!(
+		MsetcheckClaim::new(t_oracle_ids.map(|id| oracles.oracle(id)), mixed_u_oracles),
+		Err(Error::NumVariablesMismatch)
+	);
+}
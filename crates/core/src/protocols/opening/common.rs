@@ -0,0 +1,75 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::error::Error;
+use crate::{
+	challenger::CanObserve,
+	polynomial::{composition::BivariateProduct, Error as PolynomialError},
+	protocols::sumcheck_v2::{self, CompositeSumClaim, SumcheckClaim},
+};
+use binius_field::Field;
+use binius_utils::bail;
+
+/// A query for the evaluation of a committed polynomial at an arbitrary point.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpeningQuery<F: Field> {
+	/// The index of the polynomial in its committed batch.
+	pub index: usize,
+	pub eval_point: Vec<F>,
+}
+
+#[derive(Debug, Clone)]
+pub struct OpeningProof<F: Field, PCSProof> {
+	pub sumcheck_proof: sumcheck_v2::Proof<F>,
+	/// The evaluations of all polynomials in the committed batch at the sumcheck challenge point.
+	pub batch_evals: Vec<F>,
+	pub pcs_proof: PCSProof,
+}
+
+/// Checks the shape of the queries and observes them with their claimed evaluations.
+pub(super) fn observe_queries<F, Challenger>(
+	n_vars: usize,
+	queries: &[OpeningQuery<F>],
+	evals: &[F],
+	challenger: &mut Challenger,
+) -> Result<(), Error>
+where
+	F: Field,
+	Challenger: CanObserve<F>,
+{
+	if queries.is_empty() {
+		bail!(Error::NoQueries);
+	}
+	if evals.len() != queries.len() {
+		bail!(Error::NumberOfEvaluationsMismatch);
+	}
+	for (query, &eval) in queries.iter().zip(evals) {
+		if query.eval_point.len() != n_vars {
+			bail!(PolynomialError::IncorrectQuerySize { expected: n_vars });
+		}
+		challenger.observe_slice(&query.eval_point);
+		challenger.observe(eval);
+	}
+	Ok(())
+}
+
+/// Constructs the sumcheck claim on the product of the queried polynomial and the eq indicator at
+/// the query point, for each query.
+pub(super) fn opening_sumcheck_claims<F: Field>(
+	n_vars: usize,
+	evals: &[F],
+) -> Result<Vec<SumcheckClaim<F, BivariateProduct>>, Error> {
+	let claims = evals
+		.iter()
+		.map(|&eval| {
+			SumcheckClaim::new(
+				n_vars,
+				2,
+				vec![CompositeSumClaim {
+					composition: BivariateProduct,
+					sum: eval,
+				}],
+			)
+		})
+		.collect::<Result<_, _>>()?;
+	Ok(claims)
+}
@@ -0,0 +1,29 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use crate::{polynomial::Error as PolynomialError, protocols::sumcheck_v2::Error as SumcheckError};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	#[error("at least one opening query is required")]
+	NoQueries,
+	#[error("query refers to polynomial {index}, which is not in the committed batch")]
+	InvalidPolynomialIndex { index: usize },
+	#[error("the number of evaluations does not match the number of queries")]
+	NumberOfEvaluationsMismatch,
+	#[error("polynomial error: {0}")]
+	Polynomial(#[from] PolynomialError),
+	#[error("sumcheck error: {0}")]
+	Sumcheck(#[from] SumcheckError),
+	#[error("polynomial commitment error: {0}")]
+	PolyCommit(#[source] Box<dyn std::error::Error + Send + Sync>),
+	#[error("verification failure: {0}")]
+	Verification(#[from] VerificationError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum VerificationError {
+	#[error("the proof contains an incorrect evaluation of the eq indicator for query {query}")]
+	IncorrectEqIndEvaluation { query: usize },
+	#[error("the sumcheck evaluation for query {query} does not match the batch evaluation")]
+	BatchEvaluationMismatch { query: usize },
+}
@@ -0,0 +1,27 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Standalone evaluation proofs for committed polynomials at arbitrary points.
+//!
+//! The polynomial commitment schemes only prove evaluations at points sampled by the verifier. An
+//! application that wants to answer follow-up queries about committed polynomials after the main
+//! proof, at points of its choosing, first reduces each evaluation claim $t(z) = v$ to the
+//! sumcheck claim
+//!
+//! $$
+//! \sum_{x \in \{0, 1\}^{\ell}} t(x) \cdot \widetilde{eq}(z, x) = v.
+//! $$
+//!
+//! The sumcheck over all queries against one committed batch reduces them to evaluations of the
+//! batch at a single random point, which the polynomial commitment scheme then proves.
+
+mod common;
+mod error;
+mod prove;
+#[cfg(test)]
+mod tests;
+mod verify;
+
+pub use common::*;
+pub use error::*;
+pub use prove::*;
+pub use verify::*;
@@ -0,0 +1,110 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::{
+	common::{observe_queries, OpeningProof, OpeningQuery},
+	error::Error,
+};
+use crate::{
+	challenger::{CanObserve, CanSample, CanSampleBits},
+	poly_commit::PolyCommitScheme,
+	polynomial::{
+		composition::BivariateProduct, transparent::eq_ind::EqIndPartialEval,
+		EvaluationDomainFactory, MultilinearExtension, MultilinearQuery,
+	},
+	protocols::sumcheck_v2::{
+		prove::{batch_prove, RegularSumcheckProver},
+		BatchSumcheckOutput, CompositeSumClaim,
+	},
+	witness::MultilinearWitness,
+};
+use binius_field::{ExtensionField, Field, PackedExtension, PackedField, TowerField};
+use binius_utils::bail;
+use std::{fmt::Debug, ops::Deref};
+use tracing::instrument;
+
+/// Prove the evaluations of polynomials in a committed batch at arbitrary points.
+///
+/// This can be run at any time after the polynomials are committed, for example after the main
+/// proof, with the challenger in the state the verifier will reproduce. Returns the evaluations
+/// for the queries, in order, together with the proof.
+#[instrument(skip_all, name = "opening::prove", level = "debug")]
+pub fn prove<P, FE, FDomain, PCS, Data, Challenger>(
+	pcs: &PCS,
+	committed: &PCS::Committed,
+	polys: &[MultilinearExtension<P, Data>],
+	queries: &[OpeningQuery<FE>],
+	domain_factory: impl EvaluationDomainFactory<FDomain>,
+	switchover_fn: impl Fn(usize) -> usize + Copy,
+	mut challenger: Challenger,
+) -> Result<(Vec<FE>, OpeningProof<FE, PCS::Proof>), Error>
+where
+	P: PackedField + Debug,
+	FE: TowerField + ExtensionField<P::Scalar> + ExtensionField<FDomain> + PackedExtension<FDomain>,
+	FDomain: Field,
+	PCS: PolyCommitScheme<P, FE>,
+	Data: Deref<Target = [P]> + Send + Sync + Debug,
+	Challenger: CanObserve<FE> + CanSample<FE> + CanSampleBits<usize>,
+{
+	let n_vars = pcs.n_vars();
+	if let Some(query) = queries.iter().find(|query| query.index >= polys.len()) {
+		bail!(Error::InvalidPolynomialIndex { index: query.index });
+	}
+
+	let evals = queries
+		.iter()
+		.map(|query| {
+			let multilin_query = MultilinearQuery::<FE>::with_full_query(&query.eval_point)?;
+			polys[query.index].evaluate(&multilin_query)
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+	observe_queries(n_vars, queries, &evals, &mut challenger)?;
+
+	let provers = queries
+		.iter()
+		.zip(evals.iter())
+		.map(|(query, &eval)| {
+			let eq_ind = EqIndPartialEval::new(n_vars, query.eval_point.clone())?
+				.multilinear_extension::<FE>()?;
+			let multilinears: Vec<MultilinearWitness<FE>> = vec![
+				polys[query.index].to_ref().specialize_arc_dyn(),
+				eq_ind.specialize_arc_dyn(),
+			];
+			RegularSumcheckProver::<FDomain, _, _, _>::new(
+				multilinears,
+				[CompositeSumClaim {
+					composition: BivariateProduct,
+					sum: eval,
+				}],
+				domain_factory.clone(),
+				switchover_fn,
+			)
+			.map_err(Error::from)
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+
+	let (
+		BatchSumcheckOutput {
+			challenges: eval_point,
+			..
+		},
+		sumcheck_proof,
+	) = batch_prove(provers, &mut challenger)?;
+
+	let multilin_query = MultilinearQuery::<FE>::with_full_query(&eval_point)?;
+	let batch_evals = polys
+		.iter()
+		.map(|poly| poly.evaluate(&multilin_query))
+		.collect::<Result<Vec<_>, _>>()?;
+	challenger.observe_slice(&batch_evals);
+
+	let pcs_proof = pcs
+		.prove_evaluation(&mut challenger, committed, polys, &eval_point)
+		.map_err(|err| Error::PolyCommit(Box::new(err)))?;
+
+	let proof = OpeningProof {
+		sumcheck_proof,
+		batch_evals,
+		pcs_proof,
+	};
+	Ok((evals, proof))
+}
@@ -0,0 +1,184 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::*;
+use crate::{
+	challenger::{new_hasher_challenger, CanSample},
+	poly_commit::{tensor_pcs::calculate_n_test_queries_reed_solomon, BasicTensorPCS},
+	polynomial::{IsomorphicEvaluationDomainFactory, MultilinearExtension, MultilinearQuery},
+	reed_solomon::reed_solomon::ReedSolomonCode,
+};
+use assert_matches::assert_matches;
+use binius_field::{
+	arch::OptimalUnderlier128b, BinaryField128b, BinaryField8b, Field, PackedBinaryField16x8b,
+	PackedField,
+};
+use binius_hash::GroestlHasher;
+use binius_ntt::NTTOptions;
+use binius_utils::cost_report::with_cost_report;
+use rand::{rngs::StdRng, SeedableRng};
+use std::{iter::repeat_with, mem};
+
+type Packed = PackedBinaryField16x8b;
+type FE = BinaryField128b;
+
+#[test]
+fn test_prove_verify_openings() {
+	let rs_code = ReedSolomonCode::new(5, 2, NTTOptions::default()).unwrap();
+	let n_test_queries =
+		calculate_n_test_queries_reed_solomon::<_, FE, _>(100, 4, &rs_code).unwrap();
+	let pcs = <BasicTensorPCS<OptimalUnderlier128b, BinaryField8b, BinaryField8b, FE, _, _, _>>::new_using_groestl_merkle_tree(
+		4,
+		rs_code,
+		n_test_queries,
+	)
+	.unwrap();
+	let n_vars = pcs.n_vars();
+
+	let mut rng = StdRng::seed_from_u64(0);
+	let polys = repeat_with(|| {
+		let evals = repeat_with(|| Packed::random(&mut rng))
+			.take((1 << n_vars) / Packed::WIDTH)
+			.collect::<Vec<_>>();
+		MultilinearExtension::from_values(evals).unwrap()
+	})
+	.take(3)
+	.collect::<Vec<_>>();
+	let (commitment, committed) = pcs.commit(&polys).unwrap();
+
+	// The points are chosen by the application rather than sampled by the verifier.
+	let queries = vec![
+		OpeningQuery {
+			index: 0,
+			eval_point: (0..n_vars).map(|i| FE::new(i as u128)).collect(),
+		},
+		OpeningQuery {
+			index: 2,
+			eval_point: vec![FE::ONE; n_vars],
+		},
+		OpeningQuery {
+			index: 0,
+			eval_point: repeat_with(|| FE::random(&mut rng)).take(n_vars).collect(),
+		},
+	];
+
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	let prove_openings = |queries: &[OpeningQuery<FE>]| {
+		prove(
+			&pcs,
+			&committed,
+			&polys,
+			queries,
+			IsomorphicEvaluationDomainFactory::<BinaryField8b>::default(),
+			|_| 1,
+			challenger.clone(),
+		)
+	};
+
+	let (evals, proof) = prove_openings(&queries).unwrap();
+	for (query, &eval) in queries.iter().zip(evals.iter()) {
+		let multilin_query = MultilinearQuery::<FE>::with_full_query(&query.eval_point).unwrap();
+		assert_eq!(polys[query.index].evaluate(&multilin_query).unwrap(), eval);
+	}
+
+	let mut verifier_challenger = challenger.clone();
+	verify::<Packed, _, _, _>(&pcs, &commitment, &queries, &evals, proof, &mut verifier_challenger)
+		.unwrap();
+
+	// The report accounts for every sub-protocol of an accepted verification.
+	let (_, proof) = prove_openings(&queries).unwrap();
+	let report = verify_with_report::<Packed, _, _, _>(
+		&pcs,
+		&commitment,
+		&queries,
+		&evals,
+		proof,
+		challenger.clone(),
+	)
+	.unwrap();
+	let names = report
+		.subprotocols()
+		.iter()
+		.map(|subprotocol| subprotocol.name())
+		.collect::<Vec<_>>();
+	assert_eq!(names, ["queries", "sumcheck", "pcs"]);
+	assert_eq!(report.n_claims_checked(), queries.len());
+	assert_eq!(report.subprotocol("queries").unwrap().n_challenges(), 0);
+	assert!(report.subprotocol("sumcheck").unwrap().n_challenges() >= n_vars);
+	assert!(report.subprotocol("pcs").unwrap().observed_bytes() > 0);
+	assert_eq!(report.pcs_proof_bytes(), pcs.proof_size(polys.len()));
+
+	// Only the data written by the prover counts toward the proof bytes, while the observed bytes
+	// also include the queries and claimed evaluations the verifier knows itself.
+	let (_, proof) = prove_openings(&queries).unwrap();
+	let sumcheck_proof_bytes = proof
+		.sumcheck_proof
+		.rounds
+		.iter()
+		.map(|round| round.coeffs().len())
+		.chain(proof.sumcheck_proof.multilinear_evals.iter().map(Vec::len))
+		.sum::<usize>()
+		* mem::size_of::<FE>();
+	let pcs_proof_bytes =
+		mem::size_of_val(proof.batch_evals.as_slice()) + pcs.proof_size(polys.len());
+	let (report, costs) = with_cost_report(|| {
+		verify_with_report::<Packed, _, _, _>(
+			&pcs,
+			&commitment,
+			&queries,
+			&evals,
+			proof,
+			challenger.clone(),
+		)
+	});
+	let report = report.unwrap();
+	assert_eq!(
+		costs
+			.section("sumcheck_v2::batch_verify")
+			.unwrap()
+			.proof_bytes,
+		sumcheck_proof_bytes as u64
+	);
+	assert_eq!(costs.section("opening::verify").unwrap().proof_bytes, pcs_proof_bytes as u64);
+	assert_eq!(costs.total().proof_bytes, (sumcheck_proof_bytes + pcs_proof_bytes) as u64);
+	assert!(report.subprotocol("queries").unwrap().observed_bytes() > 0);
+
+	// The prover and verifier transcripts stay in sync after the openings.
+	let mut prover_challenger = challenger.clone();
+	prove(
+		&pcs,
+		&committed,
+		&polys,
+		&queries,
+		IsomorphicEvaluationDomainFactory::<BinaryField8b>::default(),
+		|_| 1,
+		&mut prover_challenger,
+	)
+	.unwrap();
+	assert_eq!(
+		CanSample::<FE>::sample(&mut prover_challenger),
+		CanSample::<FE>::sample(&mut verifier_challenger)
+	);
+
+	// A wrong evaluation is rejected.
+	let (mut wrong_evals, proof) = prove_openings(&queries).unwrap();
+	wrong_evals[1] += FE::ONE;
+	assert!(verify::<Packed, _, _, _>(
+		&pcs,
+		&commitment,
+		&queries,
+		&wrong_evals,
+		proof,
+		challenger.clone()
+	)
+	.is_err());
+
+	let invalid_index = [OpeningQuery {
+		index: 3,
+		eval_point: vec![FE::ZERO; n_vars],
+	}];
+	assert_matches!(
+		prove_openings(&invalid_index),
+		Err(Error::InvalidPolynomialIndex { index: 3 })
+	);
+	assert_matches!(prove_openings(&[]), Err(Error::NoQueries));
+}
@@ -0,0 +1,181 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::{
+	common::{observe_queries, opening_sumcheck_claims, OpeningProof, OpeningQuery},
+	error::{Error, VerificationError},
+};
+use crate::{
+	challenger::{CanObserve, CanSample, CanSampleBits, InstrumentedChallenger},
+	poly_commit::PolyCommitScheme,
+	polynomial::{transparent::eq_ind::EqIndPartialEval, MultivariatePoly},
+	protocols::sumcheck_v2::{batch_verify, BatchSumcheckOutput},
+};
+use binius_field::{ExtensionField, PackedField, TowerField};
+use binius_utils::{bail, cost_report::record_proof_bytes};
+use getset::{CopyGetters, Getters};
+use std::{
+	mem,
+	time::{Duration, Instant},
+};
+use tracing::instrument;
+
+/// The costs of one sub-protocol of a verification, see [`VerificationReport`].
+#[derive(Debug, Clone, Copy, CopyGetters)]
+#[getset(get_copy = "pub")]
+pub struct SubprotocolReport {
+	name: &'static str,
+	elapsed: Duration,
+	/// The number of bytes of claims and proof observed by the challenger.
+	observed_bytes: usize,
+	/// The number of challenges sampled from the challenger.
+	n_challenges: usize,
+}
+
+/// A structured account of an accepted verification, see [`verify_with_report`].
+///
+/// The sub-protocols are `"queries"`, which observes the queried points and evaluations,
+/// `"sumcheck"`, which reduces the queries to an evaluation of the batch at one point, and `"pcs"`,
+/// which verifies the evaluation proof of the polynomial commitment scheme.
+#[derive(Debug, Clone, Default, Getters, CopyGetters)]
+pub struct VerificationReport {
+	#[getset(get = "pub")]
+	subprotocols: Vec<SubprotocolReport>,
+	/// The number of evaluation claims checked, which is the number of queries.
+	#[getset(get_copy = "pub")]
+	n_claims_checked: usize,
+	/// The size of the evaluation proof of the polynomial commitment scheme. Openings such as
+	/// Merkle paths are read by the verifier without being observed, so they are not counted in the
+	/// observed bytes of the `"pcs"` sub-protocol.
+	#[getset(get_copy = "pub")]
+	pcs_proof_bytes: usize,
+}
+
+impl VerificationReport {
+	/// The report of the sub-protocol named `name`.
+	pub fn subprotocol(&self, name: &str) -> Option<&SubprotocolReport> {
+		self.subprotocols.iter().find(|report| report.name == name)
+	}
+
+	pub fn total_elapsed(&self) -> Duration {
+		self.subprotocols.iter().map(|report| report.elapsed).sum()
+	}
+
+	pub fn total_observed_bytes(&self) -> usize {
+		self.subprotocols
+			.iter()
+			.map(|report| report.observed_bytes)
+			.sum()
+	}
+
+	fn record<Challenger, T>(
+		&mut self,
+		name: &'static str,
+		challenger: &mut InstrumentedChallenger<Challenger>,
+		subprotocol: impl FnOnce(&mut InstrumentedChallenger<Challenger>) -> Result<T, Error>,
+	) -> Result<T, Error> {
+		let observed_bytes = challenger.observed_bytes();
+		let n_samples = challenger.n_samples();
+		let start = Instant::now();
+		let output = subprotocol(challenger)?;
+		self.subprotocols.push(SubprotocolReport {
+			name,
+			elapsed: start.elapsed(),
+			observed_bytes: challenger.observed_bytes() - observed_bytes,
+			n_challenges: challenger.n_samples() - n_samples,
+		});
+		Ok(output)
+	}
+}
+
+/// Verify the evaluations of polynomials in a committed batch at arbitrary points.
+///
+/// The challenger must be in the state the prover started from, see [`super::prove()`].
+pub fn verify<P, FE, PCS, Challenger>(
+	pcs: &PCS,
+	commitment: &PCS::Commitment,
+	queries: &[OpeningQuery<FE>],
+	evals: &[FE],
+	proof: OpeningProof<FE, PCS::Proof>,
+	challenger: Challenger,
+) -> Result<(), Error>
+where
+	P: PackedField,
+	FE: TowerField + ExtensionField<P::Scalar>,
+	PCS: PolyCommitScheme<P, FE>,
+	Challenger: CanObserve<FE> + CanSample<FE> + CanSampleBits<usize>,
+{
+	verify_with_report(pcs, commitment, queries, evals, proof, challenger).map(|_| ())
+}
+
+/// Verify the evaluations of polynomials in a committed batch at arbitrary points, returning a
+/// report of the costs of each sub-protocol.
+///
+/// This accepts and rejects the same proofs as [`verify`], so integrators can opt into the report
+/// to log and monitor verification in production.
+#[instrument(skip_all, name = "opening::verify", level = "debug")]
+pub fn verify_with_report<P, FE, PCS, Challenger>(
+	pcs: &PCS,
+	commitment: &PCS::Commitment,
+	queries: &[OpeningQuery<FE>],
+	evals: &[FE],
+	proof: OpeningProof<FE, PCS::Proof>,
+	challenger: Challenger,
+) -> Result<VerificationReport, Error>
+where
+	P: PackedField,
+	FE: TowerField + ExtensionField<P::Scalar>,
+	PCS: PolyCommitScheme<P, FE>,
+	Challenger: CanObserve<FE> + CanSample<FE> + CanSampleBits<usize>,
+{
+	let OpeningProof {
+		sumcheck_proof,
+		batch_evals,
+		pcs_proof,
+	} = proof;
+
+	let n_vars = pcs.n_vars();
+	if let Some(query) = queries
+		.iter()
+		.find(|query| query.index >= batch_evals.len())
+	{
+		bail!(Error::InvalidPolynomialIndex { index: query.index });
+	}
+
+	let mut challenger = InstrumentedChallenger::new(challenger);
+	let pcs_proof_bytes = pcs.proof_size(batch_evals.len());
+	let mut report = VerificationReport {
+		n_claims_checked: queries.len(),
+		pcs_proof_bytes: pcs_proof_bytes,
+		..Default::default()
+	};
+	report.record("queries", &mut challenger, |challenger| {
+		observe_queries(n_vars, queries, evals, challenger)
+	})?;
+
+	let eval_point = report.record("sumcheck", &mut challenger, |challenger| {
+		let claims = opening_sumcheck_claims(n_vars, evals)?;
+		let BatchSumcheckOutput {
+			challenges: eval_point,
+			multilinear_evals,
+		} = batch_verify(&claims, sumcheck_proof, challenger)?;
+
+		for (i, (query, multilinear_evals)) in queries.iter().zip(multilinear_evals).enumerate() {
+			let eq_ind = EqIndPartialEval::new(n_vars, query.eval_point.clone())?;
+			if eq_ind.evaluate(&eval_point)? != multilinear_evals[1] {
+				bail!(VerificationError::IncorrectEqIndEvaluation { query: i });
+			}
+			if batch_evals[query.index] != multilinear_evals[0] {
+				bail!(VerificationError::BatchEvaluationMismatch { query: i });
+			}
+		}
+		Ok(eval_point)
+	})?;
+
+	report.record("pcs", &mut challenger, |challenger| {
+		record_proof_bytes(mem::size_of_val(batch_evals.as_slice()) + pcs_proof_bytes);
+		challenger.observe_slice(&batch_evals);
+		pcs.verify_evaluation(challenger, commitment, &eval_point, pcs_proof, &batch_evals)
+			.map_err(|err| Error::PolyCommit(Box::new(err)))
+	})?;
+	Ok(report)
+}
@@ -0,0 +1,22 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! The permutation check polynomial protocol.
+//!
+//! Permutation check proves that the hypercube evaluations of a multilinear $B$ are a permutation
+//! of those of a multilinear $A$. It is the single-dimension case of the multiset check in
+//! [`crate::protocols::msetcheck`], with the $\gamma$ challenge sampled from the challenger, and
+//! reduces the claim to a product check claim on $\gamma + A$ and $\gamma + B$.
+//!
+//! The columns must be committed and observed by the challenger before the reduction, so that
+//! $\gamma$ is sampled after them.
+
+#[allow(clippy::module_inception)]
+mod permcheck;
+mod prove;
+#[cfg(test)]
+mod tests;
+mod verify;
+
+pub use permcheck::{PermcheckClaim, PermcheckWitness};
+pub use prove::*;
+pub use verify::*;
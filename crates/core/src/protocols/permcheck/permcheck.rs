@@ -0,0 +1,79 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use crate::{
+	oracle::MultilinearPolyOracle,
+	protocols::msetcheck::{Error, MsetcheckClaim, MsetcheckWitness},
+	witness::MultilinearWitness,
+};
+use binius_field::{Field, PackedField};
+use binius_utils::bail;
+use getset::Getters;
+
+/// A claim that the hypercube evaluations of one multilinear are a permutation of another's.
+#[derive(Debug, Getters)]
+pub struct PermcheckClaim<F: Field> {
+	/// The equivalent single-dimension multiset check claim
+	#[get = "pub"]
+	msetcheck_claim: MsetcheckClaim<F>,
+}
+
+impl<F: Field> PermcheckClaim<F> {
+	/// Claim constructor, for the claim that `b_oracle` is a permutation of `a_oracle`.
+	///
+	/// Unlike multiset check claims, both oracles must have the same number of variables.
+	pub fn new(
+		a_oracle: MultilinearPolyOracle<F>,
+		b_oracle: MultilinearPolyOracle<F>,
+	) -> Result<Self, Error> {
+		if a_oracle.n_vars() != b_oracle.n_vars() {
+			bail!(Error::NumVariablesMismatch);
+		}
+		let msetcheck_claim = MsetcheckClaim::new([a_oracle], [b_oracle])?;
+		Ok(Self { msetcheck_claim })
+	}
+
+	/// Oracle to the A polynomial.
+	pub fn a_oracle(&self) -> &MultilinearPolyOracle<F> {
+		&self.msetcheck_claim.t_oracles()[0]
+	}
+
+	/// Oracle to the B polynomial.
+	pub fn b_oracle(&self) -> &MultilinearPolyOracle<F> {
+		&self.msetcheck_claim.u_oracles()[0]
+	}
+
+	/// Number of variables in each of the multilinear oracles.
+	pub fn n_vars(&self) -> usize {
+		self.msetcheck_claim.n_vars()
+	}
+}
+
+#[derive(Debug, Getters)]
+pub struct PermcheckWitness<'a, PW: PackedField> {
+	/// The equivalent single-dimension multiset check witness
+	#[get = "pub"]
+	msetcheck_witness: MsetcheckWitness<'a, PW>,
+}
+
+impl<'a, PW: PackedField> PermcheckWitness<'a, PW> {
+	/// Witness constructor
+	pub fn new(
+		a_polynomial: MultilinearWitness<'a, PW>,
+		b_polynomial: MultilinearWitness<'a, PW>,
+	) -> Result<Self, Error> {
+		if a_polynomial.n_vars() != b_polynomial.n_vars() {
+			bail!(Error::NumVariablesMismatch);
+		}
+		let msetcheck_witness = MsetcheckWitness::new([a_polynomial], [b_polynomial])?;
+		Ok(Self { msetcheck_witness })
+	}
+
+	pub fn into_msetcheck_witness(self) -> MsetcheckWitness<'a, PW> {
+		self.msetcheck_witness
+	}
+
+	/// Number of variables in each of the witness multilinears.
+	pub fn n_vars(&self) -> usize {
+		self.msetcheck_witness.n_vars()
+	}
+}
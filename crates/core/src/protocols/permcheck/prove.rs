@@ -0,0 +1,46 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::permcheck::{PermcheckClaim, PermcheckWitness};
+use crate::{
+	oracle::MultilinearOracleSet,
+	protocols::msetcheck::{self, Error, MsetcheckProveOutput},
+	witness::MultilinearExtensionIndex,
+};
+use binius_field::{
+	as_packed_field::{PackScalar, PackedType},
+	underlier::UnderlierType,
+	PackedFieldIndexable, TowerField,
+};
+use p3_challenger::CanSample;
+use tracing::instrument;
+
+/// Prove a permutation check instance reduction.
+///
+/// Samples the challenge $\gamma$ from the challenger and reduces the claim that $B$ is a
+/// permutation of $A$ to a grand product check of $\gamma + A(x)$ and $\gamma + B(x)$, as in
+/// [`msetcheck::prove`].
+#[instrument(skip_all, name = "permcheck::prove", level = "debug")]
+pub fn prove<'a, U, F, FW, Challenger>(
+	oracles: &mut MultilinearOracleSet<F>,
+	witness_index: MultilinearExtensionIndex<'a, U, FW>,
+	permcheck_claim: &PermcheckClaim<F>,
+	permcheck_witness: PermcheckWitness<'a, PackedType<U, FW>>,
+	mut challenger: Challenger,
+) -> Result<MsetcheckProveOutput<'a, U, F, FW>, Error>
+where
+	U: UnderlierType + PackScalar<FW>,
+	F: TowerField + From<FW>,
+	FW: TowerField + From<F>,
+	PackedType<U, FW>: PackedFieldIndexable,
+	Challenger: CanSample<F>,
+{
+	let gamma = challenger.sample();
+	msetcheck::prove(
+		oracles,
+		witness_index,
+		permcheck_claim.msetcheck_claim(),
+		permcheck_witness.into_msetcheck_witness(),
+		gamma,
+		None,
+	)
+}
@@ -0,0 +1,116 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use crate::{
+	challenger::new_hasher_challenger,
+	oracle::MultilinearOracleSet,
+	polynomial::{IsomorphicEvaluationDomainFactory, MultilinearExtension},
+	protocols::{
+		gkr_prodcheck::{self, prove_grand_product, verify_grand_product},
+		permcheck::{prove, verify, PermcheckClaim, PermcheckWitness},
+	},
+	witness::{MultilinearExtensionIndex, MultilinearWitness},
+};
+use assert_matches::assert_matches;
+use binius_field::{
+	underlier::WithUnderlier, BinaryField128b, BinaryField16b, BinaryField32b,
+	PackedBinaryField1x128b, TowerField,
+};
+use binius_hash::GroestlHasher;
+use p3_challenger::CanSample;
+
+type P = PackedBinaryField1x128b;
+type F = BinaryField128b;
+type U = <P as WithUnderlier>::Underlier;
+type FDomain = BinaryField32b;
+
+fn create_polynomial(values: impl IntoIterator<Item = u16>) -> MultilinearWitness<'static, P> {
+	let values = values
+		.into_iter()
+		.map(BinaryField16b::new)
+		.collect::<Vec<_>>();
+	MultilinearExtension::from_values(values)
+		.unwrap()
+		.specialize_arc_dyn()
+}
+
+fn setup_claim(n_vars: usize) -> (MultilinearOracleSet<F>, PermcheckClaim<F>) {
+	let mut oracles = MultilinearOracleSet::<F>::new();
+	let batch_id = oracles.add_committed_batch(n_vars, BinaryField16b::TOWER_LEVEL);
+	let [a, b] = oracles.add_committed_multiple(batch_id);
+	let claim = PermcheckClaim::new(oracles.oracle(a), oracles.oracle(b)).unwrap();
+	(oracles, claim)
+}
+
+#[test]
+fn test_prove_verify_permutation() {
+	let n_vars = 6;
+	let (oracles, claim) = setup_claim(n_vars);
+	let a_polynomial = create_polynomial((0..1 << n_vars).map(|i| 3 * i + 1));
+	let b_polynomial = create_polynomial((0..1 << n_vars).map(|i| 3 * (i ^ 0b101101) + 1));
+	let witness = PermcheckWitness::new(a_polynomial.clone(), b_polynomial).unwrap();
+
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	let gamma: F = challenger.clone().sample();
+
+	let prove_output = prove(
+		&mut oracles.clone(),
+		MultilinearExtensionIndex::<U, F>::new(),
+		&claim,
+		witness,
+		challenger.clone(),
+	)
+	.unwrap();
+	let prodcheck_claim = verify(&mut oracles.clone(), &claim, challenger.clone()).unwrap();
+	assert_eq!(prodcheck_claim.t_oracle, prove_output.prodcheck_claim.t_oracle);
+	assert_eq!(prodcheck_claim.u_oracle, prove_output.prodcheck_claim.u_oracle);
+
+	for i in 0..1 << n_vars {
+		assert_eq!(
+			prove_output
+				.prodcheck_witness
+				.t_poly
+				.evaluate_on_hypercube(i)
+				.unwrap(),
+			gamma + a_polynomial.evaluate_on_hypercube(i).unwrap()
+		);
+	}
+
+	let proof = prove_grand_product::<_, _, FDomain, _>(
+		[prove_output.prodcheck_witness],
+		[prove_output.prodcheck_claim],
+		IsomorphicEvaluationDomainFactory::<FDomain>::default(),
+		challenger.clone(),
+	)
+	.unwrap()
+	.proof;
+	let evalcheck_claims = verify_grand_product([prodcheck_claim], proof, challenger).unwrap();
+	assert_eq!(evalcheck_claims.len(), 2);
+}
+
+#[test]
+fn test_prove_rejects_non_permutation() {
+	let n_vars = 4;
+	let (mut oracles, claim) = setup_claim(n_vars);
+	let a_polynomial = create_polynomial(0..1 << n_vars);
+	let b_polynomial = create_polynomial((0..1 << n_vars).map(|i| i.max(1)));
+	let witness = PermcheckWitness::new(a_polynomial, b_polynomial).unwrap();
+
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	let prove_output = prove(
+		&mut oracles,
+		MultilinearExtensionIndex::<U, F>::new(),
+		&claim,
+		witness,
+		challenger.clone(),
+	)
+	.unwrap();
+	assert_matches!(
+		prove_grand_product::<_, _, FDomain, _>(
+			[prove_output.prodcheck_witness],
+			[prove_output.prodcheck_claim],
+			IsomorphicEvaluationDomainFactory::<FDomain>::default(),
+			challenger,
+		),
+		Err(gkr_prodcheck::Error::ProductsDiffer)
+	);
+}
@@ -0,0 +1,28 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::permcheck::PermcheckClaim;
+use crate::{
+	oracle::MultilinearOracleSet,
+	protocols::{
+		gkr_prodcheck::ProdcheckClaim,
+		msetcheck::{self, Error},
+	},
+};
+use binius_field::TowerField;
+use p3_challenger::CanSample;
+use tracing::instrument;
+
+/// Verify a permutation check instance reduction.
+#[instrument(skip_all, name = "permcheck::verify", level = "debug")]
+pub fn verify<F, Challenger>(
+	oracles: &mut MultilinearOracleSet<F>,
+	claim: &PermcheckClaim<F>,
+	mut challenger: Challenger,
+) -> Result<ProdcheckClaim<F>, Error>
+where
+	F: TowerField,
+	Challenger: CanSample<F>,
+{
+	let gamma = challenger.sample();
+	msetcheck::verify(oracles, claim.msetcheck_claim(), gamma, None)
+}
@@ -157,9 +157,7 @@ pub fn reduce_prodcheck_claim<F: TowerField>(
 	)?;
 
 	// Construct ReducedProductCheckClaims
-	let t_prime_claim = ZerocheckClaim {
-		poly: t_prime_oracle,
-	};
+	let t_prime_claim = ZerocheckClaim::new(t_prime_oracle)?;
 	let mut grand_prod_eval_point = vec![F::ONE; n_vars + 1];
 	grand_prod_eval_point[0] = F::ZERO;
 	let grand_product_poly_claim = EvalcheckClaim {
@@ -24,7 +24,9 @@ use tracing::instrument;
 fn multilin_poly_to_underliers_vec<U: UnderlierType + PackScalar<F>, F: Field>(
 	poly: impl MultilinearPoly<PackedType<U, F>>,
 ) -> Result<Vec<U>, Error> {
-	debug_assert!(poly.n_vars() >= PackedType::<U, F>::LOG_WIDTH);
+	if poly.n_vars() < PackedType::<U, F>::LOG_WIDTH {
+		bail!(Error::WitnessSmallerThanUnderlier);
+	}
 	let mut underliers = vec![U::default(); 1 << (poly.n_vars() - PackedType::<U, F>::LOG_WIDTH)];
 	let packed = PackedType::<U, F>::from_underliers_ref_mut(underliers.as_mut_slice());
 	poly.subcube_evals(poly.n_vars(), 0, packed)?;
@@ -81,6 +83,9 @@ where
 		bail!(Error::NumVariablesMismatch);
 	}
 	let n_vars = t_polynomial.n_vars();
+	if prodcheck_claim.t_oracle.n_vars() != n_vars || prodcheck_claim.u_oracle.n_vars() != n_vars {
+		bail!(Error::NumVariablesMismatch);
+	}
 	let packing_log_width = PackedType::<U, F>::LOG_WIDTH;
 	if n_vars < packing_log_width {
 		bail!(Error::WitnessSmallerThanUnderlier);
@@ -304,7 +309,7 @@ mod tests {
 			n_vars + 1
 		);
 
-		assert_eq!(reduced_claims.t_prime_claim.poly.n_vars(), n_vars + 1);
-		assert_eq!(verified_reduced_claims.t_prime_claim.poly.n_vars(), n_vars + 1);
+		assert_eq!(reduced_claims.t_prime_claim.poly().n_vars(), n_vars + 1);
+		assert_eq!(verified_reduced_claims.t_prime_claim.poly().n_vars(), n_vars + 1);
 	}
 }
@@ -0,0 +1,29 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use crate::{polynomial::Error as PolynomialError, protocols::sumcheck_v2::Error as SumcheckError};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	#[error("the claim has {n_vars} variables, fewer than the {log_degree} packed into each field element")]
+	NotEnoughVariables { n_vars: usize, log_degree: usize },
+	#[error("the number of variables in the witness does not match the claim")]
+	WitnessNumVariablesMismatch,
+	#[error("polynomial error: {0}")]
+	Polynomial(#[from] PolynomialError),
+	#[error("sumcheck error: {0}")]
+	Sumcheck(#[from] SumcheckError),
+	#[error("verification failure: {0}")]
+	Verification(#[from] VerificationError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum VerificationError {
+	#[error("incorrect number of column evaluations, expected {expected}")]
+	NumberOfColumnEvals { expected: usize },
+	#[error("the column evaluations are inconsistent with the claimed evaluation")]
+	ColumnEvalsMismatch,
+	#[error("the number of final evaluations must match the number of instances")]
+	NumberOfFinalEvaluations,
+	#[error("the proof contains an incorrect evaluation of the ring-switching eq indicator")]
+	IncorrectEqIndEvaluation,
+}
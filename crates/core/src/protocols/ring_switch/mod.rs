@@ -0,0 +1,44 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! The ring-switching reduction from evaluation claims on 1-bit multilinears to evaluation claims
+//! on their packings into a large field.
+//!
+//! Committing a 1-bit multilinear with a large-field polynomial commitment scheme by embedding
+//! each bit into a field element inflates the committed data by the field's extension degree.
+//! Instead, the bits can be packed $d = 2^{\kappa}$ at a time into the coordinates of field
+//! elements, which is the same data reinterpreted and costs nothing to commit. The packed
+//! multilinear $t'$ over $\ell - \kappa$ variables is related to the 1-bit multilinear $t$ over
+//! $\ell$ variables by
+//!
+//! $$
+//! t'(w) = \sum_{u \in \{0, 1\}^{\kappa}} t(u, w) \cdot \beta_u,
+//! $$
+//!
+//! where $\beta_u$ is the canonical $\mathbb{F}_2$-basis of the field. An evaluation of $t$ is
+//! not an evaluation of $t'$, so ring-switching reduces the claim $t(r) = s$ to a sumcheck claim
+//! on the product of $t'$ with a transparent multilinear, see
+//! [`RingSwitchEqInd`](crate::polynomial::transparent::ring_switch_eq_ind::RingSwitchEqInd).
+//! The sumcheck in turn reduces to an evaluation of $t'$, which is a committed evaluation claim
+//! for the large-field PCS. See [DP24] Section 3 for the protocol and its security analysis.
+//!
+//! The committed evaluation claims that evalcheck accumulates for a batch of 1-bit multilinears
+//! are ring-switched with
+//! [`EvalcheckProver::prove_ring_switch`](crate::protocols::evalcheck::EvalcheckProver::prove_ring_switch)
+//! and
+//! [`EvalcheckVerifier::verify_ring_switch`](crate::protocols::evalcheck::EvalcheckVerifier::verify_ring_switch),
+//! which leave a same query claim on the packed multilinears of the batch.
+//!
+//! [DP24]: <https://eprint.iacr.org/2024/504>
+
+mod error;
+mod prove;
+#[allow(clippy::module_inception)]
+mod ring_switch;
+#[cfg(test)]
+mod tests;
+mod verify;
+
+pub use error::*;
+pub use prove::*;
+pub use ring_switch::*;
+pub use verify::*;
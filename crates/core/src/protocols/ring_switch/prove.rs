@@ -0,0 +1,105 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::{
+	error::Error,
+	ring_switch::{reduce_claim, split_eval_point, RingSwitchProof, RingSwitchReducedClaim},
+};
+use crate::{
+	challenger::{CanObserve, CanSample},
+	polynomial::{MultilinearExtension, MultilinearQuery},
+	protocols::evalcheck::CommittedEvalClaim,
+	witness::MultilinearWitness,
+};
+use binius_field::{
+	as_packed_field::{PackScalar, PackedType},
+	underlier::WithUnderlier,
+	BinaryField1b, ExtensionField, Field, PackedField, TowerField,
+};
+use binius_utils::bail;
+use rayon::prelude::*;
+use tracing::instrument;
+
+#[derive(Debug)]
+pub struct RingSwitchProveOutput<'a, P: PackedField> {
+	pub proof: RingSwitchProof<P::Scalar>,
+	pub reduced_claim: RingSwitchReducedClaim<P::Scalar>,
+	/// The packed multilinear and the ring-switching eq indicator, in the order expected by the
+	/// sumcheck claim of [`super::reduce_to_sumchecks`].
+	pub multilinears: Vec<MultilinearWitness<'a, P>>,
+}
+
+/// Prove the ring-switching reduction of an evaluation claim on a committed 1-bit multilinear.
+///
+/// The witness is given as the packed evaluations of the 1-bit multilinear. The packed
+/// multilinear is the witness data reinterpreted as elements of `F`, so no data is
+/// copied. The prover sends the partial evaluations of the witness at the high coordinates of the
+/// evaluation point for every assignment of the packed variables, which takes one pass over the
+/// packed witness.
+#[instrument(skip_all, name = "ring_switch::prove", level = "debug")]
+pub fn prove<'a, U, F, Challenger>(
+	claim: &CommittedEvalClaim<F>,
+	witness: &'a [PackedType<U, BinaryField1b>],
+	mut challenger: Challenger,
+) -> Result<RingSwitchProveOutput<'a, PackedType<U, F>>, Error>
+where
+	U: PackScalar<BinaryField1b> + PackScalar<F>,
+	F: TowerField,
+	Challenger: CanObserve<F> + CanSample<F>,
+{
+	let (_, eval_point_high) = split_eval_point(claim)?;
+	if MultilinearExtension::from_values_slice(witness)?.n_vars() != claim.eval_point.len() {
+		bail!(Error::WitnessNumVariablesMismatch);
+	}
+
+	let packed = MultilinearExtension::from_values_slice(PackedType::<U, F>::from_underliers_ref(
+		PackedType::<U, BinaryField1b>::to_underliers_ref(witness),
+	))?;
+	if packed.n_vars() != eval_point_high.len() {
+		bail!(Error::WitnessNumVariablesMismatch);
+	}
+
+	let eq_ind = MultilinearQuery::<F>::with_full_query(eval_point_high)?.into_expansion();
+	let column_evals = eq_ind
+		.par_iter()
+		.enumerate()
+		.fold(
+			|| vec![F::ZERO; F::DEGREE],
+			|mut column_evals, (i, &eq_ind_i)| {
+				let value = packed
+					.evaluate_on_hypercube(i)
+					.expect("i is less than the size of the packed multilinear");
+				for (column_eval, bit) in column_evals
+					.iter_mut()
+					.zip(ExtensionField::<BinaryField1b>::iter_bases(&value))
+				{
+					if bit == BinaryField1b::ONE {
+						*column_eval += eq_ind_i;
+					}
+				}
+				column_evals
+			},
+		)
+		.reduce(
+			|| vec![F::ZERO; F::DEGREE],
+			|mut lhs, rhs| {
+				lhs.iter_mut()
+					.zip(rhs)
+					.for_each(|(lhs_i, rhs_i)| *lhs_i += rhs_i);
+				lhs
+			},
+		);
+
+	let reduced_claim = reduce_claim(claim, &column_evals, &mut challenger)?;
+	let eq_ind_multilin = reduced_claim
+		.eq_ind()
+		.multilinear_extension::<PackedType<U, F>>()?;
+
+	Ok(RingSwitchProveOutput {
+		proof: RingSwitchProof { column_evals },
+		reduced_claim,
+		multilinears: vec![
+			packed.specialize_arc_dyn(),
+			eq_ind_multilin.specialize_arc_dyn(),
+		],
+	})
+}
@@ -0,0 +1,181 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::error::{Error, VerificationError};
+use crate::{
+	challenger::{CanObserve, CanSample},
+	oracle::CommittedId,
+	polynomial::{
+		composition::BivariateProduct, transparent::ring_switch_eq_ind::RingSwitchEqInd,
+		MultilinearQuery, MultivariatePoly,
+	},
+	protocols::{
+		evalcheck::CommittedEvalClaim,
+		sumcheck_v2::{BatchSumcheckOutput, CompositeSumClaim, SumcheckClaim},
+	},
+};
+use binius_field::{BinaryField1b, ExtensionField, Field, TowerField};
+use binius_utils::bail;
+use getset::{CopyGetters, Getters};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RingSwitchProof<F: Field> {
+	/// The partial evaluations $t(u, r_{\kappa}, \ldots, r_{\ell - 1})$ of the 1-bit multilinear
+	/// at each vertex $u$ of the packed variables.
+	pub column_evals: Vec<F>,
+}
+
+/// An evaluation claim on a committed 1-bit multilinear after ring-switching, which is to be
+/// proven with a sumcheck on the product of its packing and a [`RingSwitchEqInd`].
+#[derive(Debug, Clone, Getters, CopyGetters)]
+pub struct RingSwitchReducedClaim<F: Field> {
+	#[getset(get_copy = "pub")]
+	committed_id: CommittedId,
+	#[getset(get = "pub")]
+	eq_ind: RingSwitchEqInd<F>,
+	#[getset(get_copy = "pub")]
+	sum: F,
+}
+
+impl<F: TowerField> RingSwitchReducedClaim<F> {
+	/// The number of variables of the packed multilinear.
+	pub fn n_vars(&self) -> usize {
+		MultivariatePoly::<F>::n_vars(&self.eq_ind)
+	}
+}
+
+/// The base-2 logarithm of the number of bits packed into each field element.
+pub fn log_packing_degree<F: TowerField>() -> usize {
+	F::DEGREE.ilog2() as usize
+}
+
+/// Splits the evaluation point of a claim into the packed and the remaining coordinates.
+pub(super) fn split_eval_point<F: TowerField>(
+	claim: &CommittedEvalClaim<F>,
+) -> Result<(&[F], &[F]), Error> {
+	let log_degree = log_packing_degree::<F>();
+	let n_vars = claim.eval_point.len();
+	if n_vars < log_degree {
+		bail!(Error::NotEnoughVariables { n_vars, log_degree });
+	}
+	Ok(claim.eval_point.split_at(log_degree))
+}
+
+/// Observes the column evaluations, samples the row batching challenges, and computes the reduced
+/// claim.
+///
+/// The column evaluations must have been checked against the claim by the verifier.
+pub(super) fn reduce_claim<F, Challenger>(
+	claim: &CommittedEvalClaim<F>,
+	column_evals: &[F],
+	challenger: &mut Challenger,
+) -> Result<RingSwitchReducedClaim<F>, Error>
+where
+	F: TowerField,
+	Challenger: CanObserve<F> + CanSample<F>,
+{
+	let (_, eval_point_high) = split_eval_point(claim)?;
+
+	challenger.observe_slice(column_evals);
+	let row_batch_challenges = challenger.sample_vec(log_packing_degree::<F>());
+
+	// The sum of the reduced claim batches the rows of the transposed column evaluations.
+	let row_batch_coeffs =
+		MultilinearQuery::<F>::with_full_query(&row_batch_challenges)?.into_expansion();
+	let sum = transpose_bits(column_evals)
+		.into_iter()
+		.zip(row_batch_coeffs)
+		.map(|(row_eval, coeff)| row_eval * coeff)
+		.sum();
+
+	let eq_ind = RingSwitchEqInd::new(eval_point_high.to_vec(), row_batch_challenges)?;
+	Ok(RingSwitchReducedClaim {
+		committed_id: claim.id,
+		eq_ind,
+		sum,
+	})
+}
+
+/// Transposes a square matrix of bits, given as the coordinates of field elements.
+///
+/// Returns the elements whose coordinate $u$ is coordinate $v$ of `values[u]`, for each $v$.
+fn transpose_bits<F: TowerField>(values: &[F]) -> Vec<F> {
+	let mut bits = vec![vec![BinaryField1b::ZERO; values.len()]; F::DEGREE];
+	for (u, value) in values.iter().enumerate() {
+		for (v, bit) in ExtensionField::<BinaryField1b>::iter_bases(value).enumerate() {
+			bits[v][u] = bit;
+		}
+	}
+	bits.into_iter()
+		.map(|row| {
+			ExtensionField::<BinaryField1b>::from_bases(&row)
+				.expect("values has length equal to the extension degree")
+		})
+		.collect()
+}
+
+/// Constructs the sumcheck claims for the reduced claims.
+///
+/// Each sumcheck claim is over the packed multilinear and the [`RingSwitchEqInd`] multilinear, in
+/// that order.
+pub fn reduce_to_sumchecks<F: TowerField>(
+	claims: &[RingSwitchReducedClaim<F>],
+) -> Result<Vec<SumcheckClaim<F, BivariateProduct>>, Error> {
+	let sumcheck_claims = claims
+		.iter()
+		.map(|claim| {
+			SumcheckClaim::new(
+				claim.n_vars(),
+				2,
+				vec![CompositeSumClaim {
+					composition: BivariateProduct,
+					sum: claim.sum,
+				}],
+			)
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+	Ok(sumcheck_claims)
+}
+
+/// Verify the validity of the sumcheck outputs for the reduced claims.
+///
+/// This checks the evaluations of the [`RingSwitchEqInd`] multilinears and returns the evaluation
+/// claims on the packed multilinears, which are committed with the same identifiers as the 1-bit
+/// multilinears.
+pub fn verify_sumcheck_outputs<F: TowerField>(
+	claims: &[RingSwitchReducedClaim<F>],
+	sumcheck_output: BatchSumcheckOutput<F>,
+) -> Result<Vec<CommittedEvalClaim<F>>, Error> {
+	let BatchSumcheckOutput {
+		challenges,
+		multilinear_evals,
+	} = sumcheck_output;
+
+	if multilinear_evals.len() != claims.len() {
+		bail!(VerificationError::NumberOfFinalEvaluations);
+	}
+
+	let n_rounds = challenges.len();
+	claims
+		.iter()
+		.zip(multilinear_evals)
+		.map(|(claim, evals)| {
+			let n_vars = claim.n_vars();
+			if evals.len() != 2 || n_vars > n_rounds {
+				bail!(VerificationError::NumberOfFinalEvaluations);
+			}
+
+			// Claims with fewer variables join the batched sumcheck in later rounds.
+			let eval_point = &challenges[n_rounds - n_vars..];
+			if claim.eq_ind.evaluate(eval_point)? != evals[1] {
+				bail!(VerificationError::IncorrectEqIndEvaluation);
+			}
+
+			Ok(CommittedEvalClaim {
+				id: claim.committed_id,
+				eval_point: eval_point.to_vec(),
+				eval: evals[0],
+				is_random_point: true,
+			})
+		})
+		.collect()
+}
@@ -0,0 +1,154 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::*;
+use crate::{
+	challenger::new_hasher_challenger,
+	oracle::CommittedId,
+	polynomial::{
+		composition::BivariateProduct, IsomorphicEvaluationDomainFactory, MultilinearExtension,
+		MultilinearQuery,
+	},
+	protocols::{
+		evalcheck::CommittedEvalClaim,
+		sumcheck_v2::{
+			batch_verify,
+			prove::{batch_prove, RegularSumcheckProver},
+			CompositeSumClaim,
+		},
+	},
+};
+use assert_matches::assert_matches;
+use binius_field::{
+	as_packed_field::{PackScalar, PackedType},
+	underlier::WithUnderlier,
+	BinaryField128b, BinaryField32b, BinaryField8b, ExtensionField, Field, PackedBinaryField128x1b,
+	PackedExtension, PackedField, TowerField,
+};
+use binius_hash::GroestlHasher;
+use rand::{rngs::StdRng, SeedableRng};
+use std::iter::repeat_with;
+
+fn generate_claim<F: TowerField>(
+	n_vars: usize,
+) -> (Vec<PackedBinaryField128x1b>, CommittedEvalClaim<F>) {
+	let mut rng = StdRng::seed_from_u64(0);
+	let witness = repeat_with(|| PackedBinaryField128x1b::random(&mut rng))
+		.take(1 << (n_vars - PackedBinaryField128x1b::LOG_WIDTH))
+		.collect::<Vec<_>>();
+	let eval_point = repeat_with(|| F::random(&mut rng))
+		.take(n_vars)
+		.collect::<Vec<_>>();
+
+	let query = MultilinearQuery::<F>::with_full_query(&eval_point).unwrap();
+	let eval = MultilinearExtension::from_values_slice(&witness)
+		.unwrap()
+		.evaluate(&query)
+		.unwrap();
+
+	let claim = CommittedEvalClaim {
+		id: CommittedId {
+			batch_id: 0,
+			index: 0,
+		},
+		eval_point,
+		eval,
+		is_random_point: true,
+	};
+	(witness, claim)
+}
+
+fn test_prove_verify_ring_switch_helper<F>(n_vars: usize)
+where
+	F: TowerField + ExtensionField<BinaryField8b>,
+	u128: PackScalar<F>,
+	PackedType<u128, F>: PackedExtension<BinaryField8b>,
+{
+	let (witness, claim) = generate_claim::<F>(n_vars);
+
+	let mut prover_challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	let mut verifier_challenger = prover_challenger.clone();
+
+	let output = prove::<u128, F, _>(&claim, &witness, &mut prover_challenger).unwrap();
+	let reduced_claim = verify(&claim, output.proof, &mut verifier_challenger).unwrap();
+	assert_eq!(reduced_claim.sum(), output.reduced_claim.sum());
+	assert_eq!(reduced_claim.n_vars(), n_vars - log_packing_degree::<F>());
+
+	let prover = RegularSumcheckProver::<BinaryField8b, _, _, _>::new(
+		output.multilinears,
+		[CompositeSumClaim {
+			composition: BivariateProduct,
+			sum: output.reduced_claim.sum(),
+		}],
+		IsomorphicEvaluationDomainFactory::<BinaryField8b>::default(),
+		|_| 1,
+	)
+	.unwrap();
+	let (_, sumcheck_proof) = batch_prove(vec![prover], &mut prover_challenger).unwrap();
+
+	let reduced_claims = [reduced_claim];
+	let sumcheck_claims = reduce_to_sumchecks(&reduced_claims).unwrap();
+	let sumcheck_output =
+		batch_verify(&sumcheck_claims, sumcheck_proof, &mut verifier_challenger).unwrap();
+	let pcs_claims = verify_sumcheck_outputs(&reduced_claims, sumcheck_output).unwrap();
+
+	assert_eq!(pcs_claims.len(), 1);
+	let pcs_claim = &pcs_claims[0];
+	assert_eq!(pcs_claim.id, claim.id);
+
+	// The packed multilinear is the witness reinterpreted as elements of the large field.
+	let packed_witness = PackedType::<u128, F>::from_underliers_ref(
+		PackedBinaryField128x1b::to_underliers_ref(&witness),
+	);
+	let query = MultilinearQuery::<F>::with_full_query(&pcs_claim.eval_point).unwrap();
+	let packed_eval = MultilinearExtension::from_values_slice(packed_witness)
+		.unwrap()
+		.evaluate(&query)
+		.unwrap();
+	assert_eq!(pcs_claim.eval, packed_eval);
+}
+
+#[test]
+fn test_prove_verify_ring_switch_128b() {
+	test_prove_verify_ring_switch_helper::<BinaryField128b>(12);
+}
+
+#[test]
+fn test_prove_verify_ring_switch_32b() {
+	test_prove_verify_ring_switch_helper::<BinaryField32b>(10);
+}
+
+#[test]
+fn test_ring_switch_rejects_inconsistent_column_evals() {
+	type F = BinaryField128b;
+
+	let (witness, claim) = generate_claim::<F>(10);
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+
+	let mut proof = prove::<u128, F, _>(&claim, &witness, challenger.clone())
+		.unwrap()
+		.proof;
+	proof.column_evals[3] += F::ONE;
+	assert_matches!(
+		verify(&claim, proof.clone(), challenger.clone()),
+		Err(Error::Verification(VerificationError::ColumnEvalsMismatch))
+	);
+
+	proof.column_evals.pop();
+	assert_matches!(
+		verify(&claim, proof, challenger),
+		Err(Error::Verification(VerificationError::NumberOfColumnEvals { .. }))
+	);
+
+	let small_claim = CommittedEvalClaim {
+		eval_point: claim.eval_point[..5].to_vec(),
+		..claim
+	};
+	assert_matches!(
+		prove::<u128, F, _>(
+			&small_claim,
+			&witness[..1],
+			new_hasher_challenger::<_, GroestlHasher<_>>()
+		),
+		Err(Error::NotEnoughVariables { .. })
+	);
+}
@@ -0,0 +1,54 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::{
+	error::{Error, VerificationError},
+	ring_switch::{
+		log_packing_degree, reduce_claim, split_eval_point, RingSwitchProof, RingSwitchReducedClaim,
+	},
+};
+use crate::{
+	challenger::{CanObserve, CanSample},
+	polynomial::MultilinearQuery,
+	protocols::evalcheck::CommittedEvalClaim,
+};
+use binius_field::TowerField;
+use binius_utils::bail;
+use tracing::instrument;
+
+/// Verify the ring-switching reduction of an evaluation claim on a committed 1-bit multilinear.
+///
+/// The verifier checks that the column evaluations recombine to the claimed evaluation, then
+/// samples the row batching challenges and returns the claim to be proven with a sumcheck, see
+/// [`super::reduce_to_sumchecks`].
+#[instrument(skip_all, name = "ring_switch::verify", level = "debug")]
+pub fn verify<F, Challenger>(
+	claim: &CommittedEvalClaim<F>,
+	proof: RingSwitchProof<F>,
+	mut challenger: Challenger,
+) -> Result<RingSwitchReducedClaim<F>, Error>
+where
+	F: TowerField,
+	Challenger: CanObserve<F> + CanSample<F>,
+{
+	let RingSwitchProof { column_evals } = proof;
+
+	let log_degree = log_packing_degree::<F>();
+	if column_evals.len() != 1 << log_degree {
+		bail!(VerificationError::NumberOfColumnEvals {
+			expected: 1 << log_degree,
+		});
+	}
+
+	let (eval_point_low, _) = split_eval_point(claim)?;
+	let expected_eval = MultilinearQuery::<F>::with_full_query(eval_point_low)?
+		.into_expansion()
+		.into_iter()
+		.zip(column_evals.iter())
+		.map(|(coeff, &column_eval)| coeff * column_eval)
+		.sum::<F>();
+	if expected_eval != claim.eval {
+		bail!(VerificationError::ColumnEvalsMismatch);
+	}
+
+	reduce_claim(claim, &column_evals, &mut challenger)
+}
@@ -64,7 +64,7 @@ where
 
 	let oracles = sumchecks
 		.iter()
-		.map(|(claim, _)| claim.poly.clone())
+		.map(|(claim, _)| claim.poly().clone())
 		.collect::<Vec<_>>();
 
 	let AbstractSumcheckBatchProveOutput {
@@ -113,7 +113,7 @@ where
 	let evalcheck_claims = reduced_claims
 		.into_iter()
 		.zip(claims_vec)
-		.map(|(rc, c)| finalize_evalcheck_claim(&c.poly, rc))
+		.map(|(rc, c)| finalize_evalcheck_claim(c.poly(), rc))
 		.collect::<Result<_, _>>()?;
 
 	Ok(evalcheck_claims)
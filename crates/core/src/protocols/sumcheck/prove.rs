@@ -128,12 +128,12 @@ where
 		witness: W,
 		seq_id: usize,
 	) -> Result<Self::Prover, Error> {
-		let ids = claim.poly.inner_polys_oracle_ids().collect::<Vec<_>>();
+		let ids = claim.poly().inner_polys_oracle_ids().collect::<Vec<_>>();
 		self.common
 			.extend(witness.multilinears(seq_id, ids.as_slice())?)?;
 		let domain = self
 			.evaluation_domain_factory
-			.create(claim.poly.max_individual_degree() + 1)?;
+			.create(claim.poly().max_individual_degree() + 1)?;
 		let prover = SumcheckProver::new(claim, witness, domain)?;
 		Ok(prover)
 	}
@@ -217,17 +217,17 @@ where
 		#[cfg(feature = "debug_validate_sumcheck")]
 		validate_witness(&claim, &witness)?;
 
-		if claim.poly.max_individual_degree() == 0 {
+		if claim.poly().max_individual_degree() == 0 {
 			bail!(Error::PolynomialDegreeIsZero);
 		}
 
-		check_evaluation_domain(claim.poly.max_individual_degree(), &domain)?;
+		check_evaluation_domain(claim.poly().max_individual_degree(), &domain)?;
 
-		let oracle_ids = claim.poly.inner_polys_oracle_ids().collect::<Vec<_>>();
+		let oracle_ids = claim.poly().inner_polys_oracle_ids().collect::<Vec<_>>();
 
 		let round_claim = SumcheckRoundClaim {
 			partial_point: Vec::new(),
-			current_round_sum: claim.sum,
+			current_round_sum: claim.sum(),
 		};
 
 		let sumcheck_prover = SumcheckProver {
@@ -284,7 +284,7 @@ where
 			self.reduce_claim(prev_rd_challenge)?;
 		}
 
-		let degree = self.claim.poly.max_individual_degree();
+		let degree = self.claim.poly().max_individual_degree();
 		let evaluator = SumcheckEvaluator {
 			degree,
 			composition: self.witness.composition(),
@@ -2,7 +2,7 @@
 
 use super::{Error, VerificationError};
 use crate::{
-	oracle::{CompositePolyOracle, OracleId},
+	oracle::{CompositePolyOracle, Error as OracleError, OracleId},
 	polynomial::{evaluate_univariate, MultilinearComposite},
 	protocols::{
 		abstract_sumcheck::{
@@ -12,8 +12,9 @@ use crate::{
 		evalcheck::EvalcheckClaim,
 	},
 };
-use binius_field::{Field, PackedField};
+use binius_field::{Field, PackedField, TowerField};
 use binius_utils::bail;
+use getset::{CopyGetters, Getters};
 
 pub type SumcheckRound<F> = AbstractSumcheckRound<F>;
 pub type SumcheckProof<F> = AbstractSumcheckProof<F>;
@@ -24,10 +25,25 @@ pub struct SumcheckProveOutput<F: Field> {
 	pub sumcheck_proof: SumcheckProof<F>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Getters, CopyGetters)]
 pub struct SumcheckClaim<F: Field> {
-	pub poly: CompositePolyOracle<F>,
-	pub sum: F,
+	#[getset(get = "pub")]
+	poly: CompositePolyOracle<F>,
+	#[getset(get_copy = "pub")]
+	sum: F,
+}
+
+impl<F: TowerField> SumcheckClaim<F> {
+	/// Constructs a new sumcheck claim.
+	///
+	/// ## Throws
+	///
+	/// * [`OracleError::CompositionTowerLevelTooHigh`] if the composition has a tower level
+	///   exceeding that of all the composed oracles, other than the tower level of `F`
+	pub fn new(poly: CompositePolyOracle<F>, sum: F) -> Result<Self, OracleError> {
+		poly.check_tower_levels(F::TOWER_LEVEL)?;
+		Ok(Self { poly, sum })
+	}
 }
 
 impl<F: Field> AbstractSumcheckClaim<F> for SumcheckClaim<F> {
@@ -130,7 +146,7 @@ where
 	W: AbstractSumcheckWitness<PW, MultilinearId = OracleId>,
 {
 	let log_size = claim.n_vars();
-	let oracle_ids = claim.poly.inner_polys_oracle_ids().collect::<Vec<_>>();
+	let oracle_ids = claim.poly().inner_polys_oracle_ids().collect::<Vec<_>>();
 	let multilinears = witness
 		.multilinears(0, oracle_ids.as_slice())?
 		.into_iter()
@@ -96,10 +96,7 @@ fn test_prove_verify_interaction_helper(
 	let composite_poly =
 		CompositePolyOracle::new(n_vars, h, TestProductComposition::new(n_multilinears)).unwrap();
 
-	let sumcheck_claim = SumcheckClaim {
-		sum: sum.into(),
-		poly: composite_poly,
-	};
+	let sumcheck_claim = SumcheckClaim::new(composite_poly, sum.into()).unwrap();
 
 	// Setup evaluation domain
 	let domain_factory = IsomorphicEvaluationDomainFactory::<F>::default();
@@ -170,10 +167,7 @@ fn test_prove_verify_interaction_with_monomial_basis_conversion_helper(
 		CompositePolyOracle::new(n_vars, h, TestProductComposition::new(n_multilinears)).unwrap();
 	let poly_oracle = composite_poly;
 
-	let sumcheck_claim = SumcheckClaim {
-		sum,
-		poly: poly_oracle,
-	};
+	let sumcheck_claim = SumcheckClaim::new(poly_oracle, sum).unwrap();
 
 	// Setup evaluation domain
 	let domain_factory = IsomorphicEvaluationDomainFactory::<F>::default();
@@ -348,7 +342,7 @@ fn test_prove_verify_batch() {
 	let sumcheck_claims = composites
 		.into_iter()
 		.zip(composite_sums)
-		.map(|(poly, sum)| SumcheckClaim { poly, sum })
+		.map(|(poly, sum)| SumcheckClaim::new(poly, sum).unwrap())
 		.collect::<Vec<_>>();
 
 	let domain_factory = IsomorphicEvaluationDomainFactory::<BinaryField32b>::default();
@@ -447,7 +441,7 @@ fn test_packed_sumcheck() {
 	let sumcheck_claims = composites
 		.into_iter()
 		.zip(composite_sums)
-		.map(|(poly, sum)| SumcheckClaim { poly, sum })
+		.map(|(poly, sum)| SumcheckClaim::new(poly, sum).unwrap())
 		.collect::<Vec<_>>();
 
 	let domain_factory = IsomorphicEvaluationDomainFactory::<BinaryField32b>::default();
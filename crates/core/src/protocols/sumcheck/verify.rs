@@ -27,7 +27,7 @@ where
 	F: Field,
 	CH: CanSample<F> + CanObserve<F>,
 {
-	let n_vars = claim.poly.n_vars();
+	let n_vars = claim.poly().n_vars();
 	let n_rounds = proof.rounds.len();
 	if n_rounds != n_vars {
 		bail!(VerificationError::NumberOfRounds);
@@ -38,5 +38,5 @@ where
 	};
 	let reduced_claim = abstract_sumcheck::verify(claim, proof, reductor, challenger)?;
 
-	finalize_evalcheck_claim(&claim.poly, reduced_claim).map_err(Into::into)
+	finalize_evalcheck_claim(claim.poly(), reduced_claim).map_err(Into::into)
 }
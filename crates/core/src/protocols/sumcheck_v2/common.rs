@@ -1,11 +1,23 @@
 // Copyright 2024 Ulvetanna Inc.
 
-use super::error::Error;
-use crate::polynomial::CompositionPoly;
-use binius_field::Field;
+use super::{
+	error::{Error, VerificationError},
+	features::ProtocolFeatures,
+	labels::ClaimLabel,
+};
+use crate::polynomial::{
+	evaluate_univariate, CompositionPoly, Error as PolynomialError, EvaluationDomain,
+	MultivariatePoly,
+};
+use binius_field::{ExtensionField, Field, PackedExtension};
 use binius_utils::bail;
 use getset::{CopyGetters, Getters};
-use std::ops::{Add, AddAssign, Mul, MulAssign};
+use std::{
+	cmp::Reverse,
+	iter,
+	ops::{Add, AddAssign, Mul, MulAssign},
+	sync::Arc,
+};
 
 /// A claim about the sum of the values of a multilinear composite polynomial over the boolean
 /// hypercube.
@@ -28,13 +40,22 @@ pub struct CompositeSumClaim<F: Field, Composition> {
 /// efficient batch proving and verification and reduce to a set of multilinear evaluations of the
 /// same polynomials. In other words, this grouping deduplicates prover work and proof data that
 /// would be redundant in a more naive implementation.
-#[derive(Debug, CopyGetters)]
+///
+/// Multilinears that the verifier can evaluate itself, such as equality indicators or step
+/// functions, may be declared transparent with [`SumcheckClaim::with_transparent`]. Their
+/// evaluations are omitted from the proof and computed by the verifier instead.
+#[derive(Debug, Clone, CopyGetters)]
 pub struct SumcheckClaim<F: Field, C> {
 	#[getset(get_copy = "pub")]
 	n_vars: usize,
 	#[getset(get_copy = "pub")]
 	n_multilinears: usize,
 	composite_sums: Vec<CompositeSumClaim<F, C>>,
+	/// The total degree of each composition polynomial, in the order of `composite_sums`.
+	composite_degrees: Vec<usize>,
+	label: Option<ClaimLabel>,
+	/// The transparent multilinears with their indices, in ascending order by index.
+	transparents: Vec<(usize, Arc<dyn MultivariatePoly<F>>)>,
 }
 
 impl<F: Field, Composition> SumcheckClaim<F, Composition>
@@ -43,6 +64,9 @@ where
 {
 	/// Constructs a new sumcheck claim.
 	///
+	/// The degree of each composite is read from [`CompositionPoly::degree`] and recorded in the
+	/// claim.
+	///
 	/// ## Throws
 	///
 	/// * [`Error::InvalidComposition`] if any of the composition polynomials in the composite
@@ -52,35 +76,174 @@ where
 		n_multilinears: usize,
 		composite_sums: Vec<CompositeSumClaim<F, Composition>>,
 	) -> Result<Self, Error> {
-		for CompositeSumClaim {
-			ref composition, ..
-		} in composite_sums.iter()
+		let composite_degrees = composite_sums
+			.iter()
+			.map(|composite_sum| composite_sum.composition.degree())
+			.collect();
+		Self::with_composite_degrees(n_vars, n_multilinears, composite_sums, composite_degrees)
+	}
+
+	/// Constructs a new sumcheck claim with explicit degrees for the composite polynomials.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::InvalidComposition`] if any of the composition polynomials in the composite
+	///   claims vector do not have their number of variables equal to `n_multilinears`
+	/// * [`Error::IncorrectNumberOfCompositeDegrees`] if there is not exactly one degree per
+	///   composite claim
+	/// * [`Error::InvalidCompositionDegree`] if a degree does not match the degree of the
+	///   corresponding composition polynomial
+	pub fn with_composite_degrees(
+		n_vars: usize,
+		n_multilinears: usize,
+		composite_sums: Vec<CompositeSumClaim<F, Composition>>,
+		composite_degrees: Vec<usize>,
+	) -> Result<Self, Error> {
+		if composite_degrees.len() != composite_sums.len() {
+			bail!(Error::IncorrectNumberOfCompositeDegrees {
+				expected: composite_sums.len(),
+			});
+		}
+		for (index, (composite_sum, &degree)) in composite_sums
+			.iter()
+			.zip(composite_degrees.iter())
+			.enumerate()
 		{
+			let composition = &composite_sum.composition;
 			if composition.n_vars() != n_multilinears {
 				bail!(Error::InvalidComposition {
 					expected_n_vars: n_multilinears,
 				});
 			}
+			if composition.degree() != degree {
+				bail!(Error::InvalidCompositionDegree {
+					index,
+					expected: composition.degree(),
+				});
+			}
 		}
 		Ok(Self {
 			n_vars,
 			n_multilinears,
 			composite_sums,
+			composite_degrees,
+			label: None,
+			transparents: Vec::new(),
 		})
 	}
 
+	/// Attaches an application-level label to the claim.
+	///
+	/// See [`ClaimLabel`] for how labels are bound into the transcript.
+	pub fn with_label(mut self, label: impl Into<ClaimLabel>) -> Self {
+		self.label = Some(label.into());
+		self
+	}
+
 	/// Returns the maximum individual degree of all composite polynomials.
+	///
+	/// The composites are compositions of multilinears, so the individual degree of a composite
+	/// in any variable is at most the total degree of its composition polynomial.
 	pub fn max_individual_degree(&self) -> usize {
-		self.composite_sums
-			.iter()
-			.map(|composite_sum| composite_sum.composition.n_vars())
-			.max()
-			.unwrap_or(0)
+		self.composite_degrees.iter().copied().max().unwrap_or(0)
 	}
 
 	pub fn composite_sums(&self) -> &[CompositeSumClaim<F, Composition>] {
 		&self.composite_sums
 	}
+
+	/// The total degree of each composition polynomial, in the order of [`Self::composite_sums`].
+	pub fn composite_degrees(&self) -> &[usize] {
+		&self.composite_degrees
+	}
+
+	pub fn label(&self) -> Option<&ClaimLabel> {
+		self.label.as_ref()
+	}
+
+	/// Declares the multilinear at `index` to be the transparent polynomial `poly`.
+	///
+	/// The verifier evaluates `poly` at the challenge point instead of reading the evaluation from
+	/// the proof, so the prover cannot equivocate on it. Proofs for claims with transparent
+	/// multilinears must omit their evaluations, see [`Proof::omit_transparent_evals`]. Declaring
+	/// the same index again replaces the polynomial.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::TransparentIndexOutOfRange`] if `index` is not less than the number of
+	///   multilinears
+	/// * [`Error::TransparentNumberOfVariables`] if `poly` does not have the number of variables of
+	///   the claim
+	pub fn with_transparent(
+		mut self,
+		index: usize,
+		poly: Arc<dyn MultivariatePoly<F>>,
+	) -> Result<Self, Error> {
+		if index >= self.n_multilinears {
+			bail!(Error::TransparentIndexOutOfRange {
+				index,
+				n_multilinears: self.n_multilinears,
+			});
+		}
+		if poly.n_vars() != self.n_vars {
+			bail!(Error::TransparentNumberOfVariables {
+				index,
+				expected: self.n_vars,
+			});
+		}
+		match self
+			.transparents
+			.binary_search_by_key(&index, |(transparent_index, _)| *transparent_index)
+		{
+			Ok(position) => self.transparents[position].1 = poly,
+			Err(position) => self.transparents.insert(position, (index, poly)),
+		}
+		Ok(self)
+	}
+
+	/// Whether the multilinear at `index` is transparent.
+	pub fn is_transparent(&self, index: usize) -> bool {
+		self.transparents
+			.binary_search_by_key(&index, |(transparent_index, _)| *transparent_index)
+			.is_ok()
+	}
+
+	/// The number of multilinears whose evaluations are included in proofs, which are those that
+	/// are not transparent.
+	pub fn n_committed_multilinears(&self) -> usize {
+		self.n_multilinears - self.transparents.len()
+	}
+
+	/// Interleaves the evaluations of the committed multilinears read from a proof with the
+	/// evaluations of the transparent multilinears at `point`.
+	///
+	/// `committed_evals` must have [`Self::n_committed_multilinears`] entries, and `point` is the
+	/// evaluation point of the claim ordered by variable.
+	pub(super) fn insert_transparent_evals(
+		&self,
+		committed_evals: Vec<F>,
+		point: &[F],
+	) -> Result<Vec<F>, Error> {
+		debug_assert_eq!(committed_evals.len(), self.n_committed_multilinears());
+		if self.transparents.is_empty() {
+			return Ok(committed_evals);
+		}
+
+		let mut committed_evals = committed_evals.into_iter();
+		let mut transparents = self.transparents.iter().peekable();
+		let mut evals = Vec::with_capacity(self.n_multilinears);
+		for index in 0..self.n_multilinears {
+			let eval =
+				match transparents.next_if(|(transparent_index, _)| *transparent_index == index) {
+					Some((_, poly)) => poly.evaluate(point)?,
+					None => committed_evals
+						.next()
+						.expect("committed_evals has an entry for every committed multilinear"),
+				};
+			evals.push(eval);
+		}
+		Ok(evals)
+	}
 }
 
 /// A univariate polynomial in monomial basis.
@@ -96,6 +259,24 @@ impl<F: Field> RoundCoeffs<F> {
 		self.0.truncate(new_len);
 		RoundProof(self)
 	}
+
+	/// Drops the trailing zero coefficients of the polynomial, keeping at least the constant and
+	/// linear coefficients.
+	///
+	/// This is the canonical form of round polynomials with
+	/// [`ProtocolFeatures::TRIMMED_ROUND_PROOFS`]. The linear coefficient is kept because the
+	/// constant term of a constant polynomial cannot be recovered from a truncated round proof.
+	pub fn trim(mut self) -> Self {
+		while self.0.len() > 2 && self.0.last() == Some(&F::ZERO) {
+			self.0.pop();
+		}
+		self
+	}
+
+	/// Whether the polynomial is in the canonical form produced by [`Self::trim`].
+	pub fn is_trimmed(&self) -> bool {
+		self.0.len() <= 2 || self.0.last() != Some(&F::ZERO)
+	}
 }
 
 impl<F: Field> Add<&Self> for RoundCoeffs<F> {
@@ -136,6 +317,71 @@ impl<F: Field> MulAssign<F> for RoundCoeffs<F> {
 	}
 }
 
+/// A univariate polynomial in evaluation form.
+///
+/// The value at position `i` in the inner vector is the evaluation of the polynomial at the `i`-th
+/// point of an [`EvaluationDomain`]. The domains created by
+/// [`crate::polynomial::IsomorphicEvaluationDomainFactory`] consist of the points
+/// $0, 1, \ldots, d$, so that the first two values are the evaluations at 0 and 1 that a round
+/// polynomial must sum to the claimed sum of the round.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RoundEvals<F: Field>(pub Vec<F>);
+
+impl<F: Field> RoundEvals<F> {
+	/// Evaluates a polynomial in monomial basis at all points of the domain.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::DomainTooSmall`] if the domain has fewer points than the polynomial has
+	///   coefficients
+	pub fn from_coeffs<FDomain>(
+		coeffs: &RoundCoeffs<F>,
+		domain: &EvaluationDomain<FDomain>,
+	) -> Result<Self, Error>
+	where
+		FDomain: Field,
+		F: ExtensionField<FDomain>,
+	{
+		if coeffs.0.len() > domain.size() {
+			bail!(Error::DomainTooSmall {
+				n_coeffs: coeffs.0.len(),
+			});
+		}
+		let evals = domain
+			.points()
+			.iter()
+			.map(|&point| evaluate_univariate(&coeffs.0, point.into()))
+			.collect();
+		Ok(Self(evals))
+	}
+
+	/// Interpolates the coefficients of the polynomial in monomial basis.
+	///
+	/// The domain must be the one the evaluations are over. The provers compute their round
+	/// polynomials in evaluation form and convert them with this method.
+	pub fn to_coeffs<FDomain>(
+		&self,
+		domain: &EvaluationDomain<FDomain>,
+	) -> Result<RoundCoeffs<F>, PolynomialError>
+	where
+		FDomain: Field,
+		F: ExtensionField<FDomain>,
+	{
+		Ok(RoundCoeffs(domain.interpolate(&self.0)?))
+	}
+
+	/// Evaluates the polynomial at `x` with barycentric extrapolation from the domain points.
+	///
+	/// The domain must be the one the evaluations are over.
+	pub fn evaluate<FDomain>(&self, domain: &EvaluationDomain<FDomain>, x: F) -> Result<F, Error>
+	where
+		FDomain: Field,
+		F: ExtensionField<FDomain> + PackedExtension<FDomain, Scalar = F>,
+	{
+		Ok(domain.extrapolate(&self.0, x)?)
+	}
+}
+
 /// A sumcheck round proof is a univariate polynomial in monomial basis with the coefficient of the
 /// highest-degree term truncated off.
 ///
@@ -174,6 +420,26 @@ impl<F: Field> RoundProof<F> {
 		RoundCoeffs(coeffs)
 	}
 
+	/// Wraps round proof coefficients as they appear in a proof.
+	pub(super) fn from_raw(coeffs: Vec<F>) -> Self {
+		RoundProof(RoundCoeffs(coeffs))
+	}
+
+	/// Wraps all univariate polynomial coefficients into a round proof without truncation.
+	///
+	/// This is the round proof format used with [`ProtocolFeatures::UNCOMPRESSED_ROUND_PROOFS`].
+	pub fn uncompressed(coeffs: RoundCoeffs<F>) -> Self {
+		RoundProof(coeffs)
+	}
+
+	/// Returns the coefficients of an uncompressed round proof, or `None` if the polynomial does
+	/// not satisfy the identity $s = r_i(0) + r_i(1)$ for the claimed sum $s$.
+	pub fn check_uncompressed(self, sum: F) -> Option<RoundCoeffs<F>> {
+		let RoundProof(coeffs) = self;
+		let first_coeff = coeffs.0.first().copied().unwrap_or(F::ZERO);
+		(first_coeff + coeffs.0.iter().sum::<F>() == sum).then_some(coeffs)
+	}
+
 	/// The truncated polynomial coefficients.
 	pub fn coeffs(&self) -> &[F] {
 		&self.0 .0
@@ -183,6 +449,8 @@ impl<F: Field> RoundProof<F> {
 /// A sumcheck batch proof.
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct Proof<F: Field> {
+	/// The optional protocol features the proof was produced with.
+	pub features: ProtocolFeatures,
 	/// The round proofs for each round.
 	pub rounds: Vec<RoundProof<F>>,
 	/// The claimed evaluations of all multilinears at the point defined by the sumcheck verifier
@@ -192,6 +460,95 @@ pub struct Proof<F: Field> {
 	/// corresponds to one [`SumcheckClaim`] in a batch. Each inner vector contains the evaluations
 	/// of the multilinears referenced by that claim.
 	pub multilinear_evals: Vec<Vec<F>>,
+	/// The labels of the claims in the batch, if the prover chose to disclose them.
+	///
+	/// Disclosed labels must match the labels of the claims the verifier checks the proof against.
+	pub claim_labels: Option<Vec<Option<ClaimLabel>>>,
+}
+
+impl<F: Field> Proof<F> {
+	/// Removes the evaluations of the transparent multilinears of the claims from the proof.
+	///
+	/// The provers of a batch return the evaluations of all multilinears, including the transparent
+	/// ones, which the verifier computes itself. The claims must be in the order of the batch.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::IncorrectNumberOfClaims`] if there is not exactly one claim per entry of
+	///   [`Self::multilinear_evals`]
+	/// * [`Error::IncorrectNumberOfClaimEvaluations`] if an entry does not have an evaluation for
+	///   every multilinear of its claim
+	pub fn omit_transparent_evals<Composition>(
+		mut self,
+		claims: &[SumcheckClaim<F, Composition>],
+	) -> Result<Self, Error>
+	where
+		Composition: CompositionPoly<F>,
+	{
+		if claims.len() != self.multilinear_evals.len() {
+			bail!(Error::IncorrectNumberOfClaims {
+				expected: self.multilinear_evals.len(),
+			});
+		}
+		for (claim_index, (claim, evals)) in
+			iter::zip(claims, self.multilinear_evals.iter_mut()).enumerate()
+		{
+			if evals.len() != claim.n_multilinears() {
+				bail!(Error::IncorrectNumberOfClaimEvaluations {
+					claim_index,
+					expected: claim.n_multilinears(),
+				});
+			}
+			let mut index = 0;
+			evals.retain(|_| {
+				let keep = !claim.is_transparent(index);
+				index += 1;
+				keep
+			});
+		}
+		Ok(self)
+	}
+}
+
+/// The order in which the sumcheck rounds bind the variables of the multilinears.
+///
+/// With [`FoldDirection::LowToHigh`], round $i$ binds variable $i$. With
+/// [`FoldDirection::HighToLow`], round $i$ binds variable $n - 1 - i$, which is the order that
+/// FRI-style polynomial commitment schemes fold in, so that the witness does not have to be
+/// transposed for the opening proof. The round polynomials sum over different variables in the
+/// two directions, so the proofs and the transcripts differ, and a proof must be verified with
+/// the direction it was produced with.
+///
+/// A prover folding the stored data of an oracle added with
+/// [`crate::oracle::MultilinearOracleSet::add_bit_reversed`] high-to-low produces the proof of a
+/// prover folding the oracle itself low-to-high, and is verified as such.
+///
+/// In both cases the challenges of a [`BatchSumcheckOutput`] are ordered by variable, see
+/// [`Self::eval_point`] for the evaluation point of a claim in a batch.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FoldDirection {
+	#[default]
+	LowToHigh,
+	HighToLow,
+}
+
+impl FoldDirection {
+	/// Returns the evaluation point of a claim with `n_vars` variables in a batch whose output has
+	/// the given `challenges`.
+	///
+	/// Claims with fewer variables join the batched sumcheck in later rounds, so they are bound to
+	/// the last challenges in round order. These are the highest-indexed challenges when folding
+	/// low-to-high and the lowest-indexed challenges when folding high-to-low.
+	///
+	/// ## Panics
+	///
+	/// Panics if `n_vars` is greater than the number of challenges.
+	pub fn eval_point<F>(self, challenges: &[F], n_vars: usize) -> &[F] {
+		match self {
+			FoldDirection::LowToHigh => &challenges[challenges.len() - n_vars..],
+			FoldDirection::HighToLow => &challenges[..n_vars],
+		}
+	}
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -199,3 +556,67 @@ pub struct BatchSumcheckOutput<F: Field> {
 	pub challenges: Vec<F>,
 	pub multilinear_evals: Vec<Vec<F>>,
 }
+
+impl<F: Field> BatchSumcheckOutput<F> {
+	/// Restores the original order of the multilinear evaluations of a batch whose claims were
+	/// sorted with the permutation returned by [`sort_permutation_by_n_vars`].
+	pub(super) fn unsort(self, permutation: &[usize]) -> Self {
+		let BatchSumcheckOutput {
+			challenges,
+			multilinear_evals,
+		} = self;
+		let mut unsorted = vec![Vec::new(); multilinear_evals.len()];
+		for (&index, evals) in permutation.iter().zip(multilinear_evals) {
+			unsorted[index] = evals;
+		}
+		BatchSumcheckOutput {
+			challenges,
+			multilinear_evals: unsorted,
+		}
+	}
+
+	/// Orders the challenges, which are sampled in round order, by the variables they bind.
+	pub(crate) fn with_fold_direction(mut self, fold_direction: FoldDirection) -> Self {
+		if fold_direction == FoldDirection::HighToLow {
+			self.challenges.reverse();
+		}
+		self
+	}
+
+	/// Converts the output of a batch with a single claim.
+	pub(super) fn into_single(self) -> Result<SumcheckOutput<F>, Error> {
+		let BatchSumcheckOutput {
+			challenges,
+			multilinear_evals,
+		} = self;
+		let Ok([multilinear_evals]) = <[_; 1]>::try_from(multilinear_evals) else {
+			bail!(VerificationError::NumberOfFinalEvaluations);
+		};
+		Ok(SumcheckOutput {
+			challenges,
+			multilinear_evals,
+		})
+	}
+}
+
+/// The output of a sumcheck on a single claim.
+#[derive(Debug, PartialEq, Eq)]
+pub struct SumcheckOutput<F: Field> {
+	/// The evaluation point defined by the sumcheck verifier challenges.
+	pub challenges: Vec<F>,
+	/// The evaluations of the multilinears referenced by the claim at the challenge point.
+	pub multilinear_evals: Vec<F>,
+}
+
+/// Returns the permutation that sorts claims in descending order by number of variables, as the
+/// batched sumcheck requires.
+///
+/// Entry $i$ of the permutation is the original index of the claim placed at position $i$. The
+/// sort is stable, so the prover and the verifier order claims with equal numbers of variables
+/// the same way.
+pub(super) fn sort_permutation_by_n_vars(n_vars: impl IntoIterator<Item = usize>) -> Vec<usize> {
+	let n_vars = n_vars.into_iter().collect::<Vec<_>>();
+	let mut permutation = (0..n_vars.len()).collect::<Vec<_>>();
+	permutation.sort_by_key(|&index| Reverse(n_vars[index]));
+	permutation
+}
@@ -0,0 +1,368 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Experimental sumcheck over subcubes of the boolean hypercube.
+//!
+//! A coset sumcheck claim asserts the sum of a multilinear composite over a subcube, the coset of
+//! the coordinate subspace spanned by the free variables of a [`SubcubeIndicator`]. Such sums
+//! arise in packing and ring-switching constructions, where only the vertices with some
+//! coordinates fixed carry meaningful values.
+//!
+//! The claim is reduced to a regular sumcheck over the product of the composite with the
+//! transparent indicator, so it batches with other sumcheck claims. The verifier evaluates the
+//! indicator itself at the end of the protocol. The [`CosetSumcheckProver`] exploits the product
+//! structure of the indicator to avoid materializing it and to skip the vertices outside the
+//! subcube.
+//!
+//! [`CosetSumcheckProver`]: super::prove::CosetSumcheckProver
+
+use super::{
+	error::{Error, VerificationError},
+	zerocheck::ExtraProduct,
+	BatchSumcheckOutput, CompositeSumClaim, SumcheckClaim,
+};
+use crate::polynomial::{
+	transparent::subcube_ind::SubcubeIndicator, CompositionPoly, MultivariatePoly,
+};
+use binius_field::Field;
+use binius_utils::{bail, sorting::is_sorted_ascending};
+use getset::CopyGetters;
+
+/// A group of claims about the sums of multilinear composites over a subcube of the hypercube.
+#[derive(Debug, CopyGetters)]
+pub struct CosetSumcheckClaim<F: Field, Composition> {
+	#[getset(get_copy = "pub")]
+	n_multilinears: usize,
+	#[getset(get_copy = "pub")]
+	indicator: SubcubeIndicator,
+	composite_sums: Vec<CompositeSumClaim<F, Composition>>,
+}
+
+impl<F: Field, Composition> CosetSumcheckClaim<F, Composition>
+where
+	Composition: CompositionPoly<F>,
+{
+	pub fn new(
+		indicator: SubcubeIndicator,
+		n_multilinears: usize,
+		composite_sums: Vec<CompositeSumClaim<F, Composition>>,
+	) -> Result<Self, Error> {
+		for composite_sum in composite_sums.iter() {
+			if composite_sum.composition.n_vars() != n_multilinears {
+				bail!(Error::InvalidComposition {
+					expected_n_vars: n_multilinears,
+				});
+			}
+		}
+		Ok(Self {
+			n_multilinears,
+			indicator,
+			composite_sums,
+		})
+	}
+
+	pub fn n_vars(&self) -> usize {
+		self.indicator.n_vars()
+	}
+
+	pub fn composite_sums(&self) -> &[CompositeSumClaim<F, Composition>] {
+		&self.composite_sums
+	}
+}
+
+/// Reduces coset sumcheck claims to sumcheck claims over the hypercube.
+///
+/// Each composite is multiplied by the subcube indicator, which is appended as the last
+/// multilinear of the reduced claim.
+pub fn reduce_to_sumchecks<F: Field, Composition: CompositionPoly<F>>(
+	claims: &[CosetSumcheckClaim<F, Composition>],
+) -> Result<Vec<SumcheckClaim<F, ExtraProduct<&Composition>>>, Error> {
+	// Check that the claims are in descending order by n_vars
+	if !is_sorted_ascending(claims.iter().map(|claim| claim.n_vars()).rev()) {
+		bail!(Error::ClaimsOutOfOrder);
+	}
+
+	claims
+		.iter()
+		.map(|claim| {
+			SumcheckClaim::new(
+				claim.n_vars(),
+				claim.n_multilinears() + 1,
+				claim
+					.composite_sums()
+					.iter()
+					.map(|composite_sum| CompositeSumClaim {
+						composition: ExtraProduct::new(&composite_sum.composition),
+						sum: composite_sum.sum,
+					})
+					.collect(),
+			)
+		})
+		.collect()
+}
+
+/// Verify the validity of the sumcheck outputs for reduced coset sumchecks.
+///
+/// This takes in the output of the reduced sumcheck protocol and returns the output for the coset
+/// sumcheck instances. This strips off the multilinear evaluations of the subcube indicators and
+/// verifies that the values are correct.
+pub fn verify_sumcheck_outputs<F: Field, Composition: CompositionPoly<F>>(
+	claims: &[CosetSumcheckClaim<F, Composition>],
+	sumcheck_output: BatchSumcheckOutput<F>,
+) -> Result<BatchSumcheckOutput<F>, Error> {
+	let BatchSumcheckOutput {
+		challenges,
+		mut multilinear_evals,
+	} = sumcheck_output;
+
+	// Check that the claims are in descending order by n_vars
+	if !is_sorted_ascending(claims.iter().map(|claim| claim.n_vars()).rev()) {
+		bail!(Error::ClaimsOutOfOrder);
+	}
+
+	if multilinear_evals.len() != claims.len() {
+		bail!(VerificationError::NumberOfFinalEvaluations);
+	}
+
+	let n_rounds = challenges.len();
+	for (claim, multilinear_evals) in claims.iter().zip(multilinear_evals.iter_mut()) {
+		if claim.n_vars() > n_rounds || multilinear_evals.len() != claim.n_multilinears() + 1 {
+			bail!(VerificationError::NumberOfFinalEvaluations);
+		}
+
+		// Claims over fewer variables are batched into the later rounds.
+		let indicator_eval = claim
+			.indicator()
+			.evaluate(&challenges[n_rounds - claim.n_vars()..])?;
+		let indicator_eval_claimed = multilinear_evals
+			.pop()
+			.expect("checked above that multilinear_evals length is at least 1");
+		if indicator_eval != indicator_eval_claimed {
+			bail!(VerificationError::IncorrectSubcubeIndicatorEvaluation);
+		}
+	}
+
+	Ok(BatchSumcheckOutput {
+		challenges,
+		multilinear_evals,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		challenger::{new_hasher_challenger, CanSample},
+		polynomial::{IsomorphicEvaluationDomainFactory, MultilinearExtension, MultilinearQuery},
+		protocols::{
+			sumcheck_v2::{
+				batch_verify,
+				prove::{batch_prove, coset, CosetSumcheckProver, RegularSumcheckProver},
+			},
+			test_utils::TestProductComposition,
+		},
+	};
+	use binius_field::{BinaryField128b, BinaryField8b};
+	use binius_hash::GroestlHasher;
+	use rand::{prelude::StdRng, SeedableRng};
+	use std::iter;
+
+	type FDomain = BinaryField8b;
+	type FE = BinaryField128b;
+
+	fn generate_multilinears(
+		n_vars: usize,
+		n_multilinears: usize,
+	) -> Vec<MultilinearExtension<FE>> {
+		let mut rng = StdRng::seed_from_u64(0);
+		iter::repeat_with(|| {
+			let values = iter::repeat_with(|| <FE as Field>::random(&mut rng))
+				.take(1 << n_vars)
+				.collect();
+			MultilinearExtension::from_values(values).unwrap()
+		})
+		.take(n_multilinears)
+		.collect()
+	}
+
+	fn subcube_sum(multilins: &[MultilinearExtension<FE>], indicator: &SubcubeIndicator) -> FE {
+		(0..1 << indicator.n_vars())
+			.filter(|&index| indicator.contains(index))
+			.map(|index| {
+				multilins
+					.iter()
+					.map(|multilin| multilin.evaluate_on_hypercube(index).unwrap())
+					.product::<FE>()
+			})
+			.sum()
+	}
+
+	fn test_compare_prover_with_reference(indicator: SubcubeIndicator, n_multilinears: usize) {
+		let multilins = generate_multilinears(indicator.n_vars(), n_multilinears);
+		let sum = subcube_sum(&multilins, &indicator);
+		let composite_sums = || {
+			[CompositeSumClaim {
+				composition: TestProductComposition::new(n_multilinears),
+				sum,
+			}]
+		};
+
+		let specialized = multilins
+			.iter()
+			.map(|multilin| multilin.clone().specialize::<FE>())
+			.collect::<Vec<_>>();
+		coset::validate_witness(&specialized, &indicator, composite_sums()).unwrap();
+
+		let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+		let indicator_multilin = indicator.multilinear_extension::<FE>().unwrap();
+		let reference_prover = RegularSumcheckProver::<FDomain, _, _, _>::new(
+			multilins
+				.iter()
+				.chain([&indicator_multilin])
+				.map(|multilin| multilin.clone().specialize::<FE>())
+				.collect(),
+			composite_sums().map(|claim| CompositeSumClaim {
+				composition: ExtraProduct::new(claim.composition),
+				sum: claim.sum,
+			}),
+			domain_factory.clone(),
+			|_| 1,
+		)
+		.unwrap();
+
+		let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+		let (output1, proof1) = batch_prove(vec![reference_prover], challenger.clone()).unwrap();
+
+		let optimized_prover = CosetSumcheckProver::<FDomain, _, _, _>::new(
+			specialized,
+			indicator,
+			composite_sums(),
+			domain_factory,
+			|_| 1,
+		)
+		.unwrap();
+		let (output2, proof2) = batch_prove(vec![optimized_prover], challenger).unwrap();
+
+		assert_eq!(proof1, proof2);
+		assert_eq!(output1, output2);
+	}
+
+	#[test]
+	fn test_compare_coset_prover_with_reference() {
+		for (n_vars, fixed_vars) in [
+			(7, vec![]),
+			(7, vec![(0, true)]),
+			(7, vec![(0, false), (3, true)]),
+			(8, vec![(2, true), (5, false), (7, true)]),
+			(8, vec![(1, false), (6, true), (7, false)]),
+			(5, (0..5).map(|var| (var, var % 2 == 1)).collect()),
+		] {
+			let indicator = SubcubeIndicator::new(n_vars, fixed_vars).unwrap();
+			test_compare_prover_with_reference(indicator, 3);
+		}
+	}
+
+	#[test]
+	fn test_prove_verify_coset_sumcheck() {
+		let n_multilinears = 2;
+		let indicators = [
+			SubcubeIndicator::new(8, [(1, true), (4, false)]).unwrap(),
+			SubcubeIndicator::new(6, [(5, true)]).unwrap(),
+		];
+
+		let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+		let witnesses = indicators
+			.iter()
+			.map(|indicator| {
+				let multilins = generate_multilinears(indicator.n_vars(), n_multilinears);
+				let sum = subcube_sum(&multilins, indicator);
+				(multilins, sum)
+			})
+			.collect::<Vec<_>>();
+
+		let provers = iter::zip(&indicators, &witnesses)
+			.map(|(&indicator, (multilins, sum))| {
+				CosetSumcheckProver::<FDomain, _, _, _>::new(
+					multilins
+						.iter()
+						.map(|multilin| multilin.clone().specialize::<FE>())
+						.collect(),
+					indicator,
+					[CompositeSumClaim {
+						composition: TestProductComposition::new(n_multilinears),
+						sum: *sum,
+					}],
+					domain_factory.clone(),
+					|_| 2,
+				)
+				.unwrap()
+			})
+			.collect();
+
+		let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+		let mut prover_challenger = challenger.clone();
+		let (prove_output, proof) = batch_prove(provers, &mut prover_challenger).unwrap();
+
+		let claims = iter::zip(&indicators, &witnesses)
+			.map(|(&indicator, (_, sum))| {
+				CosetSumcheckClaim::new(
+					indicator,
+					n_multilinears,
+					vec![CompositeSumClaim {
+						composition: TestProductComposition::new(n_multilinears),
+						sum: *sum,
+					}],
+				)
+				.unwrap()
+			})
+			.collect::<Vec<_>>();
+
+		let prover_output = verify_sumcheck_outputs(&claims, prove_output).unwrap();
+
+		let mut verifier_challenger = challenger.clone();
+		let sumcheck_claims = reduce_to_sumchecks(&claims).unwrap();
+		let verifier_output = verify_sumcheck_outputs(
+			&claims,
+			batch_verify(&sumcheck_claims, proof, &mut verifier_challenger).unwrap(),
+		)
+		.unwrap();
+
+		assert_eq!(
+			CanSample::<FE>::sample(&mut prover_challenger),
+			CanSample::<FE>::sample(&mut verifier_challenger)
+		);
+		assert_eq!(prover_output, verifier_output);
+
+		let n_rounds = verifier_output.challenges.len();
+		for ((multilins, _), evals) in iter::zip(&witnesses, &verifier_output.multilinear_evals) {
+			let n_vars = multilins[0].n_vars();
+			let query =
+				MultilinearQuery::with_full_query(&verifier_output.challenges[n_rounds - n_vars..])
+					.unwrap();
+			for (multilin, &expected) in iter::zip(multilins, evals) {
+				assert_eq!(multilin.evaluate(&query).unwrap(), expected);
+			}
+		}
+	}
+
+	#[test]
+	fn test_verify_rejects_incorrect_indicator_evaluation() {
+		let indicator = SubcubeIndicator::new(3, [(0, true)]).unwrap();
+		let claims = [CosetSumcheckClaim::new(
+			indicator,
+			1,
+			vec![CompositeSumClaim {
+				composition: TestProductComposition::new(1),
+				sum: FE::ONE,
+			}],
+		)
+		.unwrap()];
+		let output = BatchSumcheckOutput {
+			challenges: vec![FE::new(2), FE::new(3), FE::new(4)],
+			multilinear_evals: vec![vec![FE::ONE, FE::new(3)]],
+		};
+		assert!(matches!(
+			verify_sumcheck_outputs(&claims, output),
+			Err(Error::Verification(VerificationError::IncorrectSubcubeIndicatorEvaluation))
+		));
+	}
+}
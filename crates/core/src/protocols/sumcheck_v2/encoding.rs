@@ -0,0 +1,584 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::{
+	common::{BatchSumcheckOutput, Proof, RoundProof},
+	features::ProtocolFeatures,
+	labels::ClaimLabel,
+	prove::{BatchProveCheckpoint, MultilinearSnapshot, ProverSnapshot},
+	transform::TransformError,
+};
+use binius_field::{BinaryField1b, ExtensionField, Field, TowerField};
+use binius_utils::bail;
+use std::mem;
+
+/// The maximum number of sumcheck rounds, one per variable of a multilinear with at most
+/// `2^usize::BITS` hypercube evaluations.
+const MAX_ROUNDS: usize = usize::BITS as usize;
+
+/// The byte encoding of a sumcheck [`Proof`], [`RoundProof`], [`BatchSumcheckOutput`],
+/// [`ProverSnapshot`], or [`BatchProveCheckpoint`].
+///
+/// Both encodings write field elements in tower-canonical form: the coordinates of the element in
+/// the binary tower basis, packed little-endian. The encoding of a value is therefore
+/// deterministic and does not depend on the host or the field representation.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ProofEncoding {
+	/// Every length is written as a fixed-width 64-bit integer, every round proof carries its own
+	/// length prefix, and every field element starts on a byte boundary.
+	#[default]
+	Standard,
+	/// Lengths are written as LEB128 varints, consecutive round proofs with the same number of
+	/// coefficients share a single length prefix, and field elements are bit-packed.
+	///
+	/// Round proofs of a batch typically have the same number of coefficients over long runs of
+	/// rounds, so most of the per-round framing is dropped. The coefficients of a run of round
+	/// proofs, and every other sequence of field elements, are packed into one bit string of
+	/// exactly the extension degree in bits per element, padded with zero bits to a byte boundary
+	/// at its end. This only saves bytes for fields of fewer than 8 bits, since the elements of
+	/// larger tower fields fill whole bytes. The decoding is strict: the encoding of a proof is
+	/// unique and any other byte string, including one with nonzero padding bits, is rejected.
+	Compact,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DecodingError {
+	#[error("unexpected end of input")]
+	UnexpectedEnd,
+	#[error("{0} trailing bytes after the proof")]
+	TrailingBytes(usize),
+	#[error("varint is not minimally encoded or overflows 64 bits")]
+	InvalidVarint,
+	#[error("length {0} does not fit in the remaining input")]
+	LengthOutOfRange(u64),
+	#[error("invalid option tag {0}")]
+	InvalidTag(u8),
+	#[error("claim label is not valid UTF-8")]
+	InvalidLabel,
+	#[error("field element has nonzero bits beyond the extension degree")]
+	NonCanonicalElement,
+	#[error("bit-packed field elements have nonzero padding bits")]
+	NonCanonicalPadding,
+	#[error("round proof runs must be non-empty and have distinct lengths from their predecessor")]
+	NonCanonicalRuns,
+	#[error("unsupported proof envelope version {0}")]
+	UnsupportedEnvelopeVersion(u8),
+	#[error("unknown proof transform {0}")]
+	UnknownTransform(u8),
+	#[error("proof transform {transform_id} failed: {source}")]
+	Transform {
+		transform_id: u8,
+		#[source]
+		source: TransformError,
+	},
+}
+
+impl<F: TowerField> Proof<F> {
+	/// Encodes the proof to bytes.
+	pub fn encode(&self, encoding: ProofEncoding) -> Vec<u8> {
+		let mut writer = Writer {
+			encoding,
+			bytes: Vec::new(),
+		};
+
+		writer.bytes.extend(self.features.bits().to_le_bytes());
+
+		match &self.claim_labels {
+			None => writer.bytes.push(0),
+			Some(labels) => {
+				writer.bytes.push(1);
+				writer.len(labels.len());
+				for label in labels {
+					match label {
+						None => writer.bytes.push(0),
+						Some(label) => {
+							writer.bytes.push(1);
+							writer.len(label.as_str().len());
+							writer.bytes.extend(label.as_str().as_bytes());
+						}
+					}
+				}
+			}
+		}
+
+		match encoding {
+			ProofEncoding::Standard => {
+				writer.len(self.rounds.len());
+				for round in self.rounds.iter() {
+					writer.round(round);
+				}
+			}
+			ProofEncoding::Compact => {
+				let runs = self
+					.rounds
+					.chunk_by(|lhs, rhs| lhs.coeffs().len() == rhs.coeffs().len())
+					.collect::<Vec<_>>();
+				writer.len(runs.len());
+				for run in runs {
+					writer.len(run.len());
+					writer.len(run[0].coeffs().len());
+					let coeffs = run
+						.iter()
+						.flat_map(|round| round.coeffs().iter().copied())
+						.collect::<Vec<_>>();
+					writer.elems(&coeffs);
+				}
+			}
+		}
+
+		writer.evals(&self.multilinear_evals);
+		writer.bytes
+	}
+
+	/// Decodes a proof from bytes produced by [`Self::encode`] with the same encoding.
+	///
+	/// ## Throws
+	///
+	/// * [`DecodingError`] if `bytes` is not a valid encoding of a proof
+	pub fn decode(bytes: &[u8], encoding: ProofEncoding) -> Result<Self, DecodingError> {
+		let mut reader = Reader { encoding, bytes };
+
+		let features = ProtocolFeatures::from_bits(u32::from_le_bytes(
+			reader.take(4)?.try_into().expect("took 4 bytes"),
+		));
+
+		let claim_labels = match reader.tag()? {
+			false => None,
+			true => {
+				let n_labels = reader.len(1)?;
+				let labels = (0..n_labels)
+					.map(|_| -> Result<_, DecodingError> {
+						if !reader.tag()? {
+							return Ok(None);
+						}
+						let len = reader.len(1)?;
+						let label = std::str::from_utf8(reader.take(len)?)
+							.map_err(|_| DecodingError::InvalidLabel)?;
+						Ok(Some(ClaimLabel::new(label)))
+					})
+					.collect::<Result<Vec<_>, _>>()?;
+				Some(labels)
+			}
+		};
+
+		let rounds = match encoding {
+			ProofEncoding::Standard => {
+				let n_rounds = reader.len(mem::size_of::<u64>())?;
+				if n_rounds > MAX_ROUNDS {
+					bail!(DecodingError::LengthOutOfRange(n_rounds as u64));
+				}
+				(0..n_rounds)
+					.map(|_| reader.round())
+					.collect::<Result<Vec<_>, _>>()?
+			}
+			ProofEncoding::Compact => {
+				let n_runs = reader.len(2)?;
+				let mut rounds = Vec::new();
+				let mut last_n_coeffs = None;
+				for _ in 0..n_runs {
+					let run_len = reader.len(0)?;
+					let n_coeffs = reader.len(reader.min_elem_bytes::<F>())?;
+					if run_len == 0 || last_n_coeffs == Some(n_coeffs) {
+						bail!(DecodingError::NonCanonicalRuns);
+					}
+					if run_len > MAX_ROUNDS - rounds.len() {
+						bail!(DecodingError::LengthOutOfRange(run_len as u64));
+					}
+					last_n_coeffs = Some(n_coeffs);
+					let n_run_coeffs = run_len
+						.checked_mul(n_coeffs)
+						.ok_or(DecodingError::LengthOutOfRange(n_coeffs as u64))?;
+					let coeffs = reader.elems::<F>(n_run_coeffs)?;
+					// A run of rounds without coefficients has no elements to chunk.
+					if n_coeffs == 0 {
+						rounds.extend((0..run_len).map(|_| RoundProof::from_raw(Vec::new())));
+					} else {
+						rounds.extend(
+							coeffs
+								.chunks_exact(n_coeffs)
+								.map(|chunk| RoundProof::from_raw(chunk.to_vec())),
+						);
+					}
+				}
+				rounds
+			}
+		};
+
+		let multilinear_evals = reader.evals()?;
+		reader.finish()?;
+
+		Ok(Proof {
+			features,
+			rounds,
+			multilinear_evals,
+			claim_labels,
+		})
+	}
+}
+
+impl<F: TowerField> RoundProof<F> {
+	/// Encodes the round proof to bytes.
+	pub fn encode(&self, encoding: ProofEncoding) -> Vec<u8> {
+		let mut writer = Writer {
+			encoding,
+			bytes: Vec::new(),
+		};
+		writer.round(self);
+		writer.bytes
+	}
+
+	/// Decodes a round proof from bytes produced by [`Self::encode`] with the same encoding.
+	///
+	/// ## Throws
+	///
+	/// * [`DecodingError`] if `bytes` is not a valid encoding of a round proof
+	pub fn decode(bytes: &[u8], encoding: ProofEncoding) -> Result<Self, DecodingError> {
+		let mut reader = Reader { encoding, bytes };
+		let round = reader.round()?;
+		reader.finish()?;
+		Ok(round)
+	}
+}
+
+impl<F: TowerField> BatchSumcheckOutput<F> {
+	/// Encodes the sumcheck output to bytes.
+	pub fn encode(&self, encoding: ProofEncoding) -> Vec<u8> {
+		let mut writer = Writer {
+			encoding,
+			bytes: Vec::new(),
+		};
+		writer.len(self.challenges.len());
+		writer.elems(&self.challenges);
+		writer.evals(&self.multilinear_evals);
+		writer.bytes
+	}
+
+	/// Decodes a sumcheck output from bytes produced by [`Self::encode`] with the same encoding.
+	///
+	/// ## Throws
+	///
+	/// * [`DecodingError`] if `bytes` is not a valid encoding of a sumcheck output
+	pub fn decode(bytes: &[u8], encoding: ProofEncoding) -> Result<Self, DecodingError> {
+		let mut reader = Reader { encoding, bytes };
+		let n_challenges = reader.len(reader.min_elem_bytes::<F>())?;
+		let challenges = reader.elems(n_challenges)?;
+		let multilinear_evals = reader.evals()?;
+		reader.finish()?;
+		Ok(BatchSumcheckOutput {
+			challenges,
+			multilinear_evals,
+		})
+	}
+}
+
+impl<F: TowerField> ProverSnapshot<F> {
+	/// Encodes the snapshot to bytes.
+	pub fn encode(&self, encoding: ProofEncoding) -> Vec<u8> {
+		let mut writer = Writer {
+			encoding,
+			bytes: Vec::new(),
+		};
+		writer.snapshot(self);
+		writer.bytes
+	}
+
+	/// Decodes a snapshot from bytes produced by [`Self::encode`] with the same encoding.
+	///
+	/// ## Throws
+	///
+	/// * [`DecodingError`] if `bytes` is not a valid encoding of a snapshot
+	pub fn decode(bytes: &[u8], encoding: ProofEncoding) -> Result<Self, DecodingError> {
+		let mut reader = Reader { encoding, bytes };
+		let snapshot = reader.snapshot()?;
+		reader.finish()?;
+		Ok(snapshot)
+	}
+}
+
+impl<F: TowerField, Challenger> BatchProveCheckpoint<F, Challenger> {
+	/// Encodes the checkpoint to bytes.
+	///
+	/// The challenger is not encoded. Its state is determined by the messages of the transcript,
+	/// which are replayed when the checkpoint is decoded with [`Self::decode`]. The batching
+	/// coefficients and challenges are sampled again in the replay, so they are not encoded
+	/// either.
+	pub fn encode(&self, encoding: ProofEncoding) -> Vec<u8> {
+		let mut writer = Writer {
+			encoding,
+			bytes: Vec::new(),
+		};
+		writer.bytes.extend(self.features.bits().to_le_bytes());
+		writer.len(self.rounds.len());
+		for round in self.rounds.iter() {
+			writer.round(round);
+		}
+		writer.len(self.provers.len());
+		for snapshot in self.provers.iter() {
+			writer.snapshot(snapshot);
+		}
+		writer.bytes
+	}
+}
+
+/// Decodes the features, round proofs, and prover snapshots of a checkpoint encoded with
+/// [`BatchProveCheckpoint::encode`].
+pub(super) fn decode_checkpoint_parts<F: TowerField>(
+	bytes: &[u8],
+	encoding: ProofEncoding,
+) -> Result<(ProtocolFeatures, Vec<RoundProof<F>>, Vec<ProverSnapshot<F>>), DecodingError> {
+	let mut reader = Reader { encoding, bytes };
+	let features = ProtocolFeatures::from_bits(u32::from_le_bytes(
+		reader.take(4)?.try_into().expect("took 4 bytes"),
+	));
+	let n_rounds = reader.len(1)?;
+	if n_rounds > MAX_ROUNDS {
+		bail!(DecodingError::LengthOutOfRange(n_rounds as u64));
+	}
+	let rounds = (0..n_rounds)
+		.map(|_| reader.round())
+		.collect::<Result<Vec<_>, _>>()?;
+	let n_provers = reader.len(1)?;
+	let provers = (0..n_provers)
+		.map(|_| reader.snapshot())
+		.collect::<Result<Vec<_>, _>>()?;
+	reader.finish()?;
+	Ok((features, rounds, provers))
+}
+
+/// The number of bits between the starts of consecutive field elements in the encoding.
+///
+/// The standard encoding rounds each element up to whole bytes, the compact encoding packs the
+/// elements bit by bit.
+fn elem_stride<F: TowerField>(encoding: ProofEncoding) -> usize {
+	let degree = <F as ExtensionField<BinaryField1b>>::DEGREE;
+	match encoding {
+		ProofEncoding::Standard => degree.next_multiple_of(8),
+		ProofEncoding::Compact => degree,
+	}
+}
+
+struct Writer {
+	encoding: ProofEncoding,
+	bytes: Vec<u8>,
+}
+
+impl Writer {
+	fn len(&mut self, len: usize) {
+		match self.encoding {
+			ProofEncoding::Standard => self.bytes.extend((len as u64).to_le_bytes()),
+			ProofEncoding::Compact => {
+				let mut value = len as u64;
+				while value >= 0x80 {
+					self.bytes.push((value as u8) | 0x80);
+					value >>= 7;
+				}
+				self.bytes.push(value as u8);
+			}
+		}
+	}
+
+	fn elems<F: TowerField>(&mut self, elems: &[F]) {
+		// Zero the space for all elements at once and set their bits in place.
+		let stride = elem_stride::<F>(self.encoding);
+		let start = self.bytes.len();
+		self.bytes
+			.resize(start + (elems.len() * stride).div_ceil(8), 0);
+		let bytes = &mut self.bytes[start..];
+		for (j, elem) in elems.iter().enumerate() {
+			for (i, bit) in ExtensionField::<BinaryField1b>::iter_bases(elem).enumerate() {
+				if bit == BinaryField1b::ONE {
+					let pos = j * stride + i;
+					bytes[pos / 8] |= 1 << (pos % 8);
+				}
+			}
+		}
+	}
+
+	fn round<F: TowerField>(&mut self, round: &RoundProof<F>) {
+		self.len(round.coeffs().len());
+		self.elems(round.coeffs());
+	}
+
+	fn snapshot<F: TowerField>(&mut self, snapshot: &ProverSnapshot<F>) {
+		self.len(snapshot.challenges.len());
+		self.elems(&snapshot.challenges);
+		self.len(snapshot.multilinears.len());
+		for multilinear in snapshot.multilinears.iter() {
+			match multilinear {
+				MultilinearSnapshot::Transparent { switchover_round } => {
+					self.bytes.push(0);
+					self.len(*switchover_round);
+				}
+				MultilinearSnapshot::Folded { evals } => {
+					self.bytes.push(1);
+					self.len(evals.len());
+					self.elems(evals);
+				}
+			}
+		}
+		self.len(snapshot.sums.len());
+		self.elems(&snapshot.sums);
+	}
+
+	fn evals<F: TowerField>(&mut self, multilinear_evals: &[Vec<F>]) {
+		self.len(multilinear_evals.len());
+		for evals in multilinear_evals {
+			self.len(evals.len());
+			self.elems(evals);
+		}
+	}
+}
+
+struct Reader<'a> {
+	encoding: ProofEncoding,
+	bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+	fn take(&mut self, n: usize) -> Result<&'a [u8], DecodingError> {
+		if n > self.bytes.len() {
+			bail!(DecodingError::UnexpectedEnd);
+		}
+		let (head, tail) = self.bytes.split_at(n);
+		self.bytes = tail;
+		Ok(head)
+	}
+
+	fn tag(&mut self) -> Result<bool, DecodingError> {
+		match self.take(1)?[0] {
+			0 => Ok(false),
+			1 => Ok(true),
+			tag => bail!(DecodingError::InvalidTag(tag)),
+		}
+	}
+
+	/// Reads a length, checking that the remaining input can hold `len` items of at least
+	/// `min_item_size` bytes each.
+	///
+	/// The check bounds allocations by the input size when decoding untrusted proofs.
+	fn len(&mut self, min_item_size: usize) -> Result<usize, DecodingError> {
+		let value = match self.encoding {
+			ProofEncoding::Standard => {
+				u64::from_le_bytes(self.take(8)?.try_into().expect("took 8 bytes"))
+			}
+			ProofEncoding::Compact => self.varint()?,
+		};
+		if value.saturating_mul(min_item_size as u64) > self.bytes.len() as u64 {
+			bail!(DecodingError::LengthOutOfRange(value));
+		}
+		Ok(value as usize)
+	}
+
+	fn varint(&mut self) -> Result<u64, DecodingError> {
+		let mut value = 0u64;
+		for shift in (0..64).step_by(7) {
+			let byte = self.take(1)?[0];
+			let payload = (byte & 0x7f) as u64;
+			if shift == 63 && payload > 1 {
+				bail!(DecodingError::InvalidVarint);
+			}
+			value |= payload << shift;
+			if byte & 0x80 == 0 {
+				// Reject non-minimal encodings with redundant zero continuation bytes.
+				if byte == 0 && shift != 0 {
+					bail!(DecodingError::InvalidVarint);
+				}
+				return Ok(value);
+			}
+		}
+		bail!(DecodingError::InvalidVarint)
+	}
+
+	/// The number of bytes every encoded field element takes at least, to bound lengths by the
+	/// remaining input.
+	fn min_elem_bytes<F: TowerField>(&self) -> usize {
+		elem_stride::<F>(self.encoding) / 8
+	}
+
+	fn elems<F: TowerField>(&mut self, n: usize) -> Result<Vec<F>, DecodingError> {
+		let degree = <F as ExtensionField<BinaryField1b>>::DEGREE;
+		let stride = elem_stride::<F>(self.encoding);
+		let n_bits = n
+			.checked_mul(stride)
+			.ok_or(DecodingError::LengthOutOfRange(n as u64))?;
+		let bytes = self.take(n_bits.div_ceil(8))?;
+		let bit = |pos: usize| (bytes[pos / 8] >> (pos % 8)) & 1 == 1;
+
+		// The bits past the extension degree of each element, and the bits after the last
+		// element, must be zero.
+		let mut padding_bits = (0..n)
+			.flat_map(|j| j * stride + degree..(j + 1) * stride)
+			.chain(n_bits..bytes.len() * 8);
+		if padding_bits.any(bit) {
+			bail!(match self.encoding {
+				ProofEncoding::Standard => DecodingError::NonCanonicalElement,
+				ProofEncoding::Compact => DecodingError::NonCanonicalPadding,
+			});
+		}
+
+		let mut bits = vec![BinaryField1b::ZERO; degree];
+		Ok((0..n)
+			.map(|j| {
+				for (i, b) in bits.iter_mut().enumerate() {
+					*b = if bit(j * stride + i) {
+						BinaryField1b::ONE
+					} else {
+						BinaryField1b::ZERO
+					};
+				}
+				ExtensionField::<BinaryField1b>::from_bases(&bits)
+					.expect("bits has length equal to the extension degree")
+			})
+			.collect())
+	}
+
+	fn round<F: TowerField>(&mut self) -> Result<RoundProof<F>, DecodingError> {
+		let n_coeffs = self.len(self.min_elem_bytes::<F>())?;
+		Ok(RoundProof::from_raw(self.elems(n_coeffs)?))
+	}
+
+	fn snapshot<F: TowerField>(&mut self) -> Result<ProverSnapshot<F>, DecodingError> {
+		let n_challenges = self.len(self.min_elem_bytes::<F>())?;
+		let challenges = self.elems(n_challenges)?;
+		let n_multilinears = self.len(1)?;
+		let multilinears = (0..n_multilinears)
+			.map(|_| -> Result<_, DecodingError> {
+				let multilinear = match self.tag()? {
+					false => MultilinearSnapshot::Transparent {
+						switchover_round: self.len(0)?,
+					},
+					true => {
+						let n_evals = self.len(self.min_elem_bytes::<F>())?;
+						MultilinearSnapshot::Folded {
+							evals: self.elems(n_evals)?,
+						}
+					}
+				};
+				Ok(multilinear)
+			})
+			.collect::<Result<_, _>>()?;
+		let n_sums = self.len(self.min_elem_bytes::<F>())?;
+		let sums = self.elems(n_sums)?;
+		Ok(ProverSnapshot {
+			challenges,
+			multilinears,
+			sums,
+		})
+	}
+
+	fn evals<F: TowerField>(&mut self) -> Result<Vec<Vec<F>>, DecodingError> {
+		let n_claims = self.len(1)?;
+		(0..n_claims)
+			.map(|_| -> Result<_, DecodingError> {
+				let n_evals = self.len(self.min_elem_bytes::<F>())?;
+				self.elems(n_evals)
+			})
+			.collect()
+	}
+
+	fn finish(self) -> Result<(), DecodingError> {
+		if !self.bytes.is_empty() {
+			bail!(DecodingError::TrailingBytes(self.bytes.len()));
+		}
+		Ok(())
+	}
+}
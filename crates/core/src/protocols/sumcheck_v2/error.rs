@@ -1,6 +1,11 @@
 // Copyright 2024 Ulvetanna Inc.
 
-use crate::polynomial::Error as PolynomialError;
+use super::{encoding::DecodingError, features::ProtocolFeatures};
+use crate::{
+	oracle::Error as OracleError, polynomial::Error as PolynomialError,
+	witness::Error as WitnessError,
+};
+use std::ops::Range;
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -8,6 +13,20 @@ pub enum Error {
 		"composition polynomial has an incorrect number of variables, expected {expected_n_vars}"
 	)]
 	InvalidComposition { expected_n_vars: usize },
+	#[error("incorrect number of composite degrees, expected {expected}")]
+	IncorrectNumberOfCompositeDegrees { expected: usize },
+	#[error("degree of composite {index} does not match its composition, expected {expected}")]
+	InvalidCompositionDegree { index: usize, expected: usize },
+	#[error(
+		"transparent multilinear index {index} is out of range for {n_multilinears} multilinears"
+	)]
+	TransparentIndexOutOfRange { index: usize, n_multilinears: usize },
+	#[error("transparent multilinear {index} must have {expected} variables")]
+	TransparentNumberOfVariables { index: usize, expected: usize },
+	#[error("incorrect number of claims, expected {expected}")]
+	IncorrectNumberOfClaims { expected: usize },
+	#[error("claim {claim_index} has an incorrect number of multilinear evaluations, expected {expected}")]
+	IncorrectNumberOfClaimEvaluations { claim_index: usize, expected: usize },
 	#[error("claims must be sorted in descending order by number of variables")]
 	ClaimsOutOfOrder,
 	#[error("expected call to execute")]
@@ -22,29 +41,113 @@ pub enum Error {
 		"ProverState::execute called with incorrect number of evaluators, expected {expected}"
 	)]
 	IncorrectNumberOfEvaluators { expected: usize },
+	#[error("incorrect number of round evaluations for composite {index}, expected {expected}")]
+	IncorrectNumberOfRoundEvaluations { index: usize, expected: usize },
+	#[error("round polynomial of composite {index} has an incorrect length, expected {expected}")]
+	IncorrectRoundPolynomialLength { index: usize, expected: usize },
+	#[error("protocol features {unsupported} are not supported")]
+	UnsupportedProtocolFeatures { unsupported: ProtocolFeatures },
+	#[error("incorrect number of claim labels, expected {expected}")]
+	IncorrectNumberOfClaimLabels { expected: usize },
+	#[error("claim label of {len} bytes is longer than the maximum of 2^32 - 1")]
+	ClaimLabelTooLong { len: usize },
+	#[error("evaluation domain is too small for a polynomial with {n_coeffs} coefficients")]
+	DomainTooSmall { n_coeffs: usize },
+	#[error("the evaluation domain of a round polynomial must start with the points 0 and 1")]
+	InvalidRoundEvalsDomain,
+	#[error("the univariates of a mask polynomial must all have the same degree")]
+	InvalidMaskPolynomial,
+	#[error("a masked sumcheck claim must have at least one composite")]
+	EmptyMaskedClaim,
+	#[error("the coefficients of the mask do not fit into a commitment over {n_vars} variables")]
+	MaskCommitmentTooSmall { n_vars: usize },
+	#[error("mask commitment error: {0}")]
+	MaskCommitment(#[source] Box<dyn std::error::Error + Send + Sync>),
 	#[error("sumcheck naive witness validation failed: composition index {composition_index}")]
 	SumcheckNaiveValidationFailure { composition_index: usize },
+	#[error("round {round} polynomial of prover {prover_index} does not match its round sum")]
+	RoundSumValidationFailure { round: usize, prover_index: usize },
 	#[error("zerocheck naive witness validation failed: composition index {composition_index}, vertex index {vertex_index}")]
 	ZerocheckNaiveValidationFailure {
 		composition_index: usize,
 		vertex_index: usize,
 	},
+	#[error("the values of the external multilinear do not match its {n_vars} variables")]
+	ExternalOracleSizeMismatch { n_vars: usize },
+	#[error("incorrect number of multilinear origins, expected {expected}")]
+	IncorrectNumberOfMultilinearOrigins { expected: usize },
+	#[error("the provers in a batch must all fold their variables in the same direction")]
+	FoldDirectionMismatch,
+	#[error("the checkpoint does not match the provers it is resumed with")]
+	InvalidCheckpoint,
+	#[error("the provers sharing multilinears must be folded with the same challenges")]
+	SharedFoldMismatch,
+	#[error("remote prover error: {0}")]
+	Remote(#[source] Box<dyn std::error::Error + Send + Sync>),
+	#[error("round {round} polynomial of the remote prover exceeds the degree {max_degree} of the claim")]
+	RemoteRoundDegree { round: usize, max_degree: usize },
+	#[error("round {round} polynomial of the remote prover does not match the claimed sum")]
+	RemoteRoundSumMismatch { round: usize },
+	#[error("the remote prover returned the wrong number of multilinear evaluations, expected {expected}")]
+	RemoteEvaluationCount { expected: usize },
+	#[error("per-claim verification reports require proofs with uncompressed round polynomials")]
+	ReportRequiresUncompressedRoundProofs,
+	#[error("the maximum number of claims in a batch must be at least 1")]
+	InvalidMaxBatchClaims,
+	#[error("window {window:?} is not a range of rows of a multilinear with {n_vars} variables")]
+	InvalidWindow { window: Range<usize>, n_vars: usize },
+	#[error("decoding error: {0}")]
+	Decoding(#[from] DecodingError),
+	#[error("oracle error: {0}")]
+	Oracle(#[from] OracleError),
 	#[error("polynomial error: {0}")]
 	Polynomial(#[from] PolynomialError),
+	#[error("I/O error: {0}")]
+	Io(#[from] std::io::Error),
 	#[error("verification failure: {0}")]
 	Verification(#[from] VerificationError),
+	#[error("witness error: {0}")]
+	Witness(#[from] WitnessError),
 }
 
-#[derive(Debug, thiserror::Error)]
+#[derive(Debug, Clone, thiserror::Error)]
 pub enum VerificationError {
 	#[error("number of coefficients in round {round} proof is incorrect, expected {expected}")]
 	NumberOfCoefficients { round: usize, expected: usize },
 	#[error("incorrect number of rounds")]
 	NumberOfRounds,
+	#[error("incorrect number of interleaved batch proofs, expected {expected}")]
+	NumberOfBatchProofs { expected: usize },
+	#[error("round {round} proof has trailing zero coefficients")]
+	NonCanonicalRoundProof { round: usize },
 	#[error("the number of final evaluations must match the number of instances")]
 	NumberOfFinalEvaluations,
+	#[error("incorrect number of final evaluations for claim {claim_index}, expected {expected}")]
+	NumberOfClaimEvaluations { claim_index: usize, expected: usize },
 	#[error("the final batch composite evaluation is incorrect")]
 	IncorrectBatchEvaluation,
 	#[error("the proof contains an incorrect evaluation of the eq indicator")]
 	IncorrectZerocheckEqIndEvaluation,
+	#[error("the proof contains an incorrect evaluation of the subcube indicator")]
+	IncorrectSubcubeIndicatorEvaluation,
+	#[error("the proof contains an incorrect evaluation of the step-down indicator")]
+	IncorrectStepDownEvaluation,
+	/// The round polynomial does not sum to the batched claimed sum. `claim_indices` are the
+	/// claims batched in at the start of the round, whose claimed sums are checked for the first
+	/// time. The range is empty if the round does not batch in any claims.
+	#[error(
+		"round {round} polynomial does not match the claimed sum, after batching in claims {claim_indices:?}"
+	)]
+	RoundSumMismatch {
+		round: usize,
+		claim_indices: Range<usize>,
+	},
+	#[error("proof uses unsupported protocol features {unsupported}")]
+	UnsupportedProtocolFeatures { unsupported: ProtocolFeatures },
+	#[error("the claim labels disclosed in the proof do not match the claims")]
+	ClaimLabelMismatch,
+	#[error("the number of mask openings must match the number of variables")]
+	NumberOfMaskOpenings,
+	#[error("the openings of the mask do not match its evaluation in the sumcheck")]
+	IncorrectMaskEvaluation,
 }
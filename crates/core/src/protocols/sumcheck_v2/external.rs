@@ -0,0 +1,150 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Sumchecks over multilinears committed by external proof systems.
+//!
+//! A sumcheck claim does not care how its multilinears are committed, only that the evaluation
+//! claims it reduces to are eventually checked. This module lets a multilinear committed by
+//! another proof system, e.g. a KZG-committed multilinear from another library, take part in a
+//! sumcheck batch alongside multilinears from a binius oracle set:
+//!
+//! * the prover adapts it into a sumcheck witness with [`external_multilinear`];
+//! * the verifier describes it with [`MultilinearOrigin::External`] and, after the batch is
+//!   verified, hands the evaluation claims from [`export_external_eval_claims`] back to the
+//!   external system, which proves and verifies them with its own opening protocol.
+//!
+//! The external commitments must be observed by the challenger before the sumcheck batch, in the
+//! same way as binius commitments, so that the sumcheck challenges depend on them.
+
+use super::{
+	common::{BatchSumcheckOutput, SumcheckClaim},
+	error::{Error, VerificationError},
+};
+use crate::{
+	polynomial::{MultilinearExtension, MultilinearPoly},
+	witness::MultilinearWitness,
+};
+use binius_field::{ExtensionField, Field, PackedField};
+use binius_utils::bail;
+use std::fmt::Debug;
+
+/// A multilinear polynomial committed by an external proof system.
+///
+/// This is all the verifier needs to include the polynomial in a sumcheck claim and route the
+/// resulting evaluation claim back to the external system.
+pub trait ExternalOracle {
+	/// The identifier of the polynomial in the external system, e.g. its commitment.
+	type Handle: Clone + Debug;
+
+	fn handle(&self) -> Self::Handle;
+
+	fn n_vars(&self) -> usize;
+
+	fn origin(&self) -> MultilinearOrigin<Self::Handle> {
+		MultilinearOrigin::External(self.handle())
+	}
+}
+
+/// Prover access to the values of a multilinear polynomial committed by an external proof system.
+pub trait ExternalMultilinear<P: PackedField>: ExternalOracle {
+	/// The values of the polynomial over the boolean hypercube, in lexicographic order.
+	///
+	/// The slice must hold exactly $2^{n}$ scalars, where $n$ is [`ExternalOracle::n_vars`].
+	fn evals(&self) -> &[P];
+}
+
+/// Where a multilinear of a sumcheck claim is committed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MultilinearOrigin<H> {
+	/// Committed by binius. The evaluation claim is left to the binius evalcheck.
+	Native,
+	/// Committed by an external proof system, with the given handle.
+	External(H),
+}
+
+/// An evaluation claim on a multilinear committed by an external proof system.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalEvalClaim<F: Field, H> {
+	pub handle: H,
+	pub eval_point: Vec<F>,
+	pub eval: F,
+}
+
+/// Adapts an externally committed multilinear into a witness for the sumcheck provers.
+///
+/// The witness borrows the values of the external multilinear, so they are not copied.
+///
+/// ## Throws
+///
+/// * [`Error::ExternalOracleSizeMismatch`] if the values do not match the number of variables
+pub fn external_multilinear<'a, P, PE, E>(
+	oracle: &'a E,
+) -> Result<MultilinearWitness<'a, PE>, Error>
+where
+	P: PackedField,
+	PE: PackedField,
+	PE::Scalar: ExtensionField<P::Scalar>,
+	E: ExternalMultilinear<P>,
+{
+	let n_vars = oracle.n_vars();
+	let evals = oracle.evals();
+	if evals.len() * P::WIDTH != 1 << n_vars {
+		bail!(Error::ExternalOracleSizeMismatch { n_vars });
+	}
+	let multilinear = MultilinearExtension::from_values_slice(evals)?;
+	debug_assert_eq!(multilinear.n_vars(), n_vars);
+	Ok(multilinear.specialize_arc_dyn())
+}
+
+/// Extracts the evaluation claims on externally committed multilinears from a verified batch.
+///
+/// `origins[i][j]` is the origin of multilinear `j` of `claims[i]`, and `output` is the result of
+/// verifying the batch of `claims`. The claims are returned in claim order, then in multilinear
+/// order. The evaluations of native multilinears remain in `output` for the binius evalcheck.
+pub fn export_external_eval_claims<F, Composition, H>(
+	claims: &[SumcheckClaim<F, Composition>],
+	origins: &[Vec<MultilinearOrigin<H>>],
+	output: &BatchSumcheckOutput<F>,
+) -> Result<Vec<ExternalEvalClaim<F, H>>, Error>
+where
+	F: Field,
+	H: Clone,
+{
+	if origins.len() != claims.len() {
+		bail!(Error::IncorrectNumberOfMultilinearOrigins {
+			expected: claims.len(),
+		});
+	}
+	if output.multilinear_evals.len() != claims.len() {
+		bail!(VerificationError::NumberOfFinalEvaluations);
+	}
+
+	let n_rounds = output.challenges.len();
+	let mut external_claims = Vec::new();
+	for ((claim, claim_origins), evals) in claims
+		.iter()
+		.zip(origins)
+		.zip(output.multilinear_evals.iter())
+	{
+		if claim_origins.len() != claim.n_multilinears() {
+			bail!(Error::IncorrectNumberOfMultilinearOrigins {
+				expected: claim.n_multilinears(),
+			});
+		}
+		if evals.len() != claim.n_multilinears() || claim.n_vars() > n_rounds {
+			bail!(VerificationError::NumberOfFinalEvaluations);
+		}
+
+		// Claims with fewer variables join the batched sumcheck in later rounds.
+		let eval_point = &output.challenges[n_rounds - claim.n_vars()..];
+		for (origin, &eval) in claim_origins.iter().zip(evals) {
+			if let MultilinearOrigin::External(handle) = origin {
+				external_claims.push(ExternalEvalClaim {
+					handle: handle.clone(),
+					eval_point: eval_point.to_vec(),
+					eval,
+				});
+			}
+		}
+	}
+	Ok(external_claims)
+}
@@ -0,0 +1,141 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::{error::VerificationError, labels::bits_to_field_elems};
+use crate::challenger::CanObserve;
+use binius_field::Field;
+use binius_utils::bail;
+use std::{
+	fmt,
+	ops::{BitOr, RangeInclusive},
+};
+
+/// A bitmap of optional protocol features used by the prover, recorded in the proof header.
+///
+/// The features select between variants of the sumcheck protocol that produce proofs in different
+/// formats. The verifier follows the verification path matching the features in the header and
+/// rejects proofs using features it does not know, so that provers can adopt new variants without
+/// breaking deployed verifiers that keep accepting proofs in the formats they support.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ProtocolFeatures(u32);
+
+impl ProtocolFeatures {
+	/// Round proofs contain all coefficients of the round polynomial, instead of omitting the
+	/// highest-degree coefficient.
+	pub const UNCOMPRESSED_ROUND_PROOFS: Self = Self(1 << 0);
+
+	/// Round polynomials are sent with their actual degree rather than the maximum degree of the
+	/// active claims, dropping trailing zero coefficients, which saves proof data when
+	/// compositions are sparse. Round proofs then vary in length, and the number of coefficients
+	/// of each round proof is observed before its coefficients.
+	pub const TRIMMED_ROUND_PROOFS: Self = Self(1 << 1);
+
+	/// All features supported by this implementation.
+	pub const SUPPORTED: Self =
+		Self(Self::UNCOMPRESSED_ROUND_PROOFS.0 | Self::TRIMMED_ROUND_PROOFS.0);
+
+	pub const fn empty() -> Self {
+		Self(0)
+	}
+
+	/// Reconstructs a feature set from its bit representation, including unknown bits.
+	pub const fn from_bits(bits: u32) -> Self {
+		Self(bits)
+	}
+
+	pub const fn bits(self) -> u32 {
+		self.0
+	}
+
+	pub const fn is_empty(self) -> bool {
+		self.0 == 0
+	}
+
+	pub const fn contains(self, other: Self) -> bool {
+		self.0 & other.0 == other.0
+	}
+
+	/// Returns the features that are not supported by this implementation.
+	pub const fn unsupported(self) -> Self {
+		Self(self.0 & !Self::SUPPORTED.0)
+	}
+
+	/// The accepted numbers of coefficients of a round proof whose round polynomial has degree at
+	/// most `degree`.
+	///
+	/// The degree bound is derived by the verifier from the composite degrees of the active
+	/// claims. Without [`Self::TRIMMED_ROUND_PROOFS`], round proofs are always sized for the bound.
+	pub fn round_proof_n_coeffs(self, degree: usize) -> RangeInclusive<usize> {
+		let max_len = degree + 1;
+		let min_len = if self.contains(Self::TRIMMED_ROUND_PROOFS) {
+			max_len.min(2)
+		} else {
+			max_len
+		};
+		let n_truncated = usize::from(!self.contains(Self::UNCOMPRESSED_ROUND_PROOFS));
+		(min_len - n_truncated)..=(max_len - n_truncated)
+	}
+}
+
+impl BitOr for ProtocolFeatures {
+	type Output = Self;
+
+	fn bitor(self, rhs: Self) -> Self::Output {
+		Self(self.0 | rhs.0)
+	}
+}
+
+impl fmt::Display for ProtocolFeatures {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{:#010x}", self.0)
+	}
+}
+
+/// Checks that all protocol features in a proof header are supported.
+pub fn check_protocol_features(features: ProtocolFeatures) -> Result<(), VerificationError> {
+	let unsupported = features.unsupported();
+	if !unsupported.is_empty() {
+		bail!(VerificationError::UnsupportedProtocolFeatures { unsupported });
+	}
+	Ok(())
+}
+
+/// Observes the protocol features with the challenger.
+///
+/// Nothing is observed for the empty feature set, so that the transcript of the baseline protocol
+/// is unaffected. Otherwise, the 32-bit feature bitmap is written as zero or one field elements.
+pub fn observe_protocol_features<F, Challenger>(
+	features: ProtocolFeatures,
+	mut challenger: Challenger,
+) where
+	F: Field,
+	Challenger: CanObserve<F>,
+{
+	if features.is_empty() {
+		return;
+	}
+
+	let encoded = bits_to_field_elems(features.bits().to_le_bytes().iter()).collect::<Vec<F>>();
+	challenger.observe_slice(&encoded);
+}
+
+/// Observes the number of coefficients of a round proof with the challenger.
+///
+/// Nothing is observed unless the proof uses [`ProtocolFeatures::TRIMMED_ROUND_PROOFS`], as the
+/// length of every round proof is otherwise fixed by the claims. With trimmed round proofs, the
+/// length is observed as a 32-bit integer, with every bit written as a zero or one field element,
+/// so that the transcript determines where each round proof ends.
+pub fn observe_round_proof_len<F, Challenger>(
+	features: ProtocolFeatures,
+	n_coeffs: usize,
+	mut challenger: Challenger,
+) where
+	F: Field,
+	Challenger: CanObserve<F>,
+{
+	if !features.contains(ProtocolFeatures::TRIMMED_ROUND_PROOFS) {
+		return;
+	}
+
+	let encoded = bits_to_field_elems((n_coeffs as u32).to_le_bytes().iter()).collect::<Vec<F>>();
+	challenger.observe_slice(&encoded);
+}
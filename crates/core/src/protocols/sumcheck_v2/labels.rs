@@ -0,0 +1,114 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::{
+	common::SumcheckClaim,
+	error::{Error, VerificationError},
+};
+use crate::challenger::CanObserve;
+use binius_field::Field;
+use binius_utils::bail;
+use std::{fmt, iter};
+
+/// An application-level label attached to a [`SumcheckClaim`].
+///
+/// Labels are bound into the Fiat-Shamir transcript, so a proof only verifies against claims
+/// carrying exactly the same labels. When the prover discloses the labels in the proof, an auditor
+/// can read which constraints a proof covers without access to the circuit construction code.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ClaimLabel(String);
+
+impl ClaimLabel {
+	pub fn new(label: impl Into<String>) -> Self {
+		Self(label.into())
+	}
+
+	pub fn as_str(&self) -> &str {
+		&self.0
+	}
+}
+
+impl fmt::Display for ClaimLabel {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(&self.0)
+	}
+}
+
+impl From<&str> for ClaimLabel {
+	fn from(label: &str) -> Self {
+		Self::new(label)
+	}
+}
+
+/// Returns the labels of a batch of claims, in claim order.
+pub fn claim_labels<F: Field, Composition>(
+	claims: &[SumcheckClaim<F, Composition>],
+) -> Vec<Option<ClaimLabel>> {
+	claims.iter().map(|claim| claim.label().cloned()).collect()
+}
+
+/// Observes the labels of a batch of claims with the challenger.
+///
+/// Nothing is observed if none of the claims are labeled, so that the transcript of an unlabeled
+/// batch is unaffected. Otherwise, each label is encoded injectively as a presence flag, a 32-bit
+/// length, and the label bytes, with every bit written as a zero or one field element.
+///
+/// ## Throws
+///
+/// * [`Error::ClaimLabelTooLong`] if a label is longer than `u32::MAX` bytes
+pub fn observe_claim_labels<F, Challenger>(
+	labels: &[Option<ClaimLabel>],
+	mut challenger: Challenger,
+) -> Result<(), Error>
+where
+	F: Field,
+	Challenger: CanObserve<F>,
+{
+	if labels.iter().all(Option::is_none) {
+		return Ok(());
+	}
+
+	for label in labels {
+		let encoded = match label {
+			Some(label) => {
+				let bytes = label.as_str().as_bytes();
+				let Ok(len) = u32::try_from(bytes.len()) else {
+					bail!(Error::ClaimLabelTooLong { len: bytes.len() });
+				};
+				iter::once(F::ONE)
+					.chain(bits_to_field_elems(len.to_le_bytes().iter()))
+					.chain(bits_to_field_elems(bytes.iter()))
+					.collect::<Vec<_>>()
+			}
+			None => vec![F::ZERO],
+		};
+		challenger.observe_slice(&encoded);
+	}
+	Ok(())
+}
+
+/// Checks labels disclosed in a proof against the labels of the claims being verified.
+pub fn check_disclosed_claim_labels(
+	expected: &[Option<ClaimLabel>],
+	disclosed: Option<&[Option<ClaimLabel>]>,
+) -> Result<(), VerificationError> {
+	if let Some(disclosed) = disclosed {
+		if disclosed != expected {
+			bail!(VerificationError::ClaimLabelMismatch);
+		}
+	}
+	Ok(())
+}
+
+pub(super) fn bits_to_field_elems<'a, F: Field>(
+	bytes: impl Iterator<Item = &'a u8> + 'a,
+) -> impl Iterator<Item = F> + 'a {
+	bytes.flat_map(|&byte| {
+		(0..8).map(move |i| {
+			if (byte >> i) & 1 == 1 {
+				F::ONE
+			} else {
+				F::ZERO
+			}
+		})
+	})
+}
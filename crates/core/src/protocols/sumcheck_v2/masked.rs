@@ -0,0 +1,359 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Sumcheck over a prefix of the boolean hypercube.
+//!
+//! A masked sumcheck claim asserts the sum of a multilinear composite over the first `n_points`
+//! vertices of the hypercube, in the usual order where the lowest variable is the least
+//! significant bit of the vertex index. Such sums arise for traces whose last rows are padding.
+//!
+//! The claim is reduced to a regular sumcheck over the product of the composite with the
+//! [`StepDown`] indicator of the prefix, so it batches with other sumcheck claims. The verifier
+//! evaluates the indicator itself at the end of the protocol. The [`MaskedSumcheckProver`] tracks
+//! the indicator in closed form as it is folded, without materializing it, and skips the vertices
+//! past the prefix.
+//!
+//! [`MaskedSumcheckProver`]: super::prove::MaskedSumcheckProver
+
+use super::{
+	error::{Error, VerificationError},
+	zerocheck::ExtraProduct,
+	BatchSumcheckOutput, CompositeSumClaim, SumcheckClaim,
+};
+use crate::polynomial::{transparent::step_down::StepDown, CompositionPoly, MultivariatePoly};
+use binius_field::Field;
+use binius_utils::{bail, sorting::is_sorted_ascending};
+use getset::CopyGetters;
+
+/// A group of claims about the sums of multilinear composites over a prefix of the hypercube.
+#[derive(Debug, CopyGetters)]
+pub struct MaskedSumcheckClaim<F: Field, Composition> {
+	#[getset(get_copy = "pub")]
+	n_vars: usize,
+	#[getset(get_copy = "pub")]
+	n_multilinears: usize,
+	/// The number of vertices at the start of the hypercube the composites are summed over.
+	#[getset(get_copy = "pub")]
+	n_points: usize,
+	composite_sums: Vec<CompositeSumClaim<F, Composition>>,
+}
+
+impl<F: Field, Composition> MaskedSumcheckClaim<F, Composition>
+where
+	Composition: CompositionPoly<F>,
+{
+	pub fn new(
+		n_vars: usize,
+		n_points: usize,
+		n_multilinears: usize,
+		composite_sums: Vec<CompositeSumClaim<F, Composition>>,
+	) -> Result<Self, Error> {
+		// Validates that n_points is at most the size of the hypercube.
+		StepDown::new(n_vars, n_points)?;
+		for composite_sum in composite_sums.iter() {
+			if composite_sum.composition.n_vars() != n_multilinears {
+				bail!(Error::InvalidComposition {
+					expected_n_vars: n_multilinears,
+				});
+			}
+		}
+		Ok(Self {
+			n_vars,
+			n_multilinears,
+			n_points,
+			composite_sums,
+		})
+	}
+
+	/// The indicator of the vertices the composites are summed over.
+	pub fn indicator(&self) -> StepDown {
+		StepDown::new(self.n_vars, self.n_points).expect("n_points is validated in the constructor")
+	}
+
+	pub fn composite_sums(&self) -> &[CompositeSumClaim<F, Composition>] {
+		&self.composite_sums
+	}
+}
+
+/// Reduces masked sumcheck claims to sumcheck claims over the hypercube.
+///
+/// Each composite is multiplied by the step-down indicator, which is appended as the last
+/// multilinear of the reduced claim.
+pub fn reduce_to_sumchecks<F: Field, Composition: CompositionPoly<F>>(
+	claims: &[MaskedSumcheckClaim<F, Composition>],
+) -> Result<Vec<SumcheckClaim<F, ExtraProduct<&Composition>>>, Error> {
+	// Check that the claims are in descending order by n_vars
+	if !is_sorted_ascending(claims.iter().map(|claim| claim.n_vars()).rev()) {
+		bail!(Error::ClaimsOutOfOrder);
+	}
+
+	claims
+		.iter()
+		.map(|claim| {
+			SumcheckClaim::new(
+				claim.n_vars(),
+				claim.n_multilinears() + 1,
+				claim
+					.composite_sums()
+					.iter()
+					.map(|composite_sum| CompositeSumClaim {
+						composition: ExtraProduct::new(&composite_sum.composition),
+						sum: composite_sum.sum,
+					})
+					.collect(),
+			)
+		})
+		.collect()
+}
+
+/// Verify the validity of the sumcheck outputs for reduced masked sumchecks.
+///
+/// This takes in the output of the reduced sumcheck protocol and returns the output for the masked
+/// sumcheck instances. This strips off the multilinear evaluations of the step-down indicators and
+/// verifies that the values are correct.
+pub fn verify_sumcheck_outputs<F: Field, Composition: CompositionPoly<F>>(
+	claims: &[MaskedSumcheckClaim<F, Composition>],
+	sumcheck_output: BatchSumcheckOutput<F>,
+) -> Result<BatchSumcheckOutput<F>, Error> {
+	let BatchSumcheckOutput {
+		challenges,
+		mut multilinear_evals,
+	} = sumcheck_output;
+
+	// Check that the claims are in descending order by n_vars
+	if !is_sorted_ascending(claims.iter().map(|claim| claim.n_vars()).rev()) {
+		bail!(Error::ClaimsOutOfOrder);
+	}
+
+	if multilinear_evals.len() != claims.len() {
+		bail!(VerificationError::NumberOfFinalEvaluations);
+	}
+
+	let n_rounds = challenges.len();
+	for (claim, multilinear_evals) in claims.iter().zip(multilinear_evals.iter_mut()) {
+		if claim.n_vars() > n_rounds || multilinear_evals.len() != claim.n_multilinears() + 1 {
+			bail!(VerificationError::NumberOfFinalEvaluations);
+		}
+
+		// Claims over fewer variables are batched into the later rounds.
+		let indicator_eval = claim
+			.indicator()
+			.evaluate(&challenges[n_rounds - claim.n_vars()..])?;
+		let indicator_eval_claimed = multilinear_evals
+			.pop()
+			.expect("checked above that multilinear_evals length is at least 1");
+		if indicator_eval != indicator_eval_claimed {
+			bail!(VerificationError::IncorrectStepDownEvaluation);
+		}
+	}
+
+	Ok(BatchSumcheckOutput {
+		challenges,
+		multilinear_evals,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		challenger::{new_hasher_challenger, CanSample},
+		polynomial::{IsomorphicEvaluationDomainFactory, MultilinearExtension, MultilinearQuery},
+		protocols::{
+			sumcheck_v2::{
+				batch_verify,
+				prove::{batch_prove, masked, MaskedSumcheckProver, RegularSumcheckProver},
+			},
+			test_utils::TestProductComposition,
+		},
+	};
+	use binius_field::{BinaryField128b, BinaryField8b};
+	use binius_hash::GroestlHasher;
+	use rand::{prelude::StdRng, SeedableRng};
+	use std::iter;
+
+	type FDomain = BinaryField8b;
+	type FE = BinaryField128b;
+
+	fn generate_multilinears(
+		n_vars: usize,
+		n_multilinears: usize,
+	) -> Vec<MultilinearExtension<FE>> {
+		let mut rng = StdRng::seed_from_u64(0);
+		iter::repeat_with(|| {
+			let values = iter::repeat_with(|| <FE as Field>::random(&mut rng))
+				.take(1 << n_vars)
+				.collect();
+			MultilinearExtension::from_values(values).unwrap()
+		})
+		.take(n_multilinears)
+		.collect()
+	}
+
+	fn prefix_sum(multilins: &[MultilinearExtension<FE>], n_points: usize) -> FE {
+		(0..n_points)
+			.map(|index| {
+				multilins
+					.iter()
+					.map(|multilin| multilin.evaluate_on_hypercube(index).unwrap())
+					.product::<FE>()
+			})
+			.sum()
+	}
+
+	fn test_compare_prover_with_reference(n_vars: usize, n_points: usize, n_multilinears: usize) {
+		let multilins = generate_multilinears(n_vars, n_multilinears);
+		let sum = prefix_sum(&multilins, n_points);
+		let composite_sums = || {
+			[CompositeSumClaim {
+				composition: TestProductComposition::new(n_multilinears),
+				sum,
+			}]
+		};
+
+		let specialized = multilins
+			.iter()
+			.map(|multilin| multilin.clone().specialize::<FE>())
+			.collect::<Vec<_>>();
+		masked::validate_witness(&specialized, n_points, composite_sums()).unwrap();
+
+		let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+		let indicator_multilin = MultilinearExtension::from_values(
+			(0..1 << n_vars)
+				.map(|index| if index < n_points { FE::ONE } else { FE::ZERO })
+				.collect(),
+		)
+		.unwrap();
+		let reference_prover = RegularSumcheckProver::<FDomain, _, _, _>::new(
+			multilins
+				.iter()
+				.chain([&indicator_multilin])
+				.map(|multilin| multilin.clone().specialize::<FE>())
+				.collect(),
+			composite_sums().map(|claim| CompositeSumClaim {
+				composition: ExtraProduct::new(claim.composition),
+				sum: claim.sum,
+			}),
+			domain_factory.clone(),
+			|_| 1,
+		)
+		.unwrap();
+
+		let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+		let (output1, proof1) = batch_prove(vec![reference_prover], challenger.clone()).unwrap();
+
+		let optimized_prover = MaskedSumcheckProver::<FDomain, _, _, _>::new(
+			specialized,
+			n_points,
+			composite_sums(),
+			domain_factory,
+			|_| 1,
+		)
+		.unwrap();
+		let (output2, proof2) = batch_prove(vec![optimized_prover], challenger).unwrap();
+
+		assert_eq!(proof1, proof2);
+		assert_eq!(output1, output2);
+	}
+
+	#[test]
+	fn test_compare_masked_prover_with_reference() {
+		for (n_vars, n_points) in [(7, 0), (7, 1), (7, 77), (7, 64), (7, 127), (7, 128), (4, 5)] {
+			test_compare_prover_with_reference(n_vars, n_points, 3);
+		}
+	}
+
+	#[test]
+	fn test_prove_verify_masked_sumcheck() {
+		let n_multilinears = 2;
+		let shapes = [(8, 200), (6, 37)];
+
+		let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+		let witnesses = shapes
+			.iter()
+			.map(|&(n_vars, n_points)| {
+				let multilins = generate_multilinears(n_vars, n_multilinears);
+				let sum = prefix_sum(&multilins, n_points);
+				(multilins, sum)
+			})
+			.collect::<Vec<_>>();
+
+		let provers = iter::zip(&shapes, &witnesses)
+			.map(|(&(_, n_points), (multilins, sum))| {
+				MaskedSumcheckProver::<FDomain, _, _, _>::new(
+					multilins
+						.iter()
+						.map(|multilin| multilin.clone().specialize::<FE>())
+						.collect(),
+					n_points,
+					[CompositeSumClaim {
+						composition: TestProductComposition::new(n_multilinears),
+						sum: *sum,
+					}],
+					domain_factory.clone(),
+					|_| 2,
+				)
+				.unwrap()
+			})
+			.collect();
+
+		let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+		let mut prover_challenger = challenger.clone();
+		let (prove_output, proof) = batch_prove(provers, &mut prover_challenger).unwrap();
+
+		let claims = iter::zip(&shapes, &witnesses)
+			.map(|(&(n_vars, n_points), (_, sum))| {
+				MaskedSumcheckClaim::new(
+					n_vars,
+					n_points,
+					n_multilinears,
+					vec![CompositeSumClaim {
+						composition: TestProductComposition::new(n_multilinears),
+						sum: *sum,
+					}],
+				)
+				.unwrap()
+			})
+			.collect::<Vec<_>>();
+
+		let prover_output = verify_sumcheck_outputs(&claims, prove_output).unwrap();
+
+		let mut verifier_challenger = challenger.clone();
+		let sumcheck_claims = reduce_to_sumchecks(&claims).unwrap();
+		let verifier_output = verify_sumcheck_outputs(
+			&claims,
+			batch_verify(&sumcheck_claims, proof, &mut verifier_challenger).unwrap(),
+		)
+		.unwrap();
+
+		assert_eq!(
+			CanSample::<FE>::sample(&mut prover_challenger),
+			CanSample::<FE>::sample(&mut verifier_challenger)
+		);
+		assert_eq!(prover_output, verifier_output);
+
+		let n_rounds = verifier_output.challenges.len();
+		for ((multilins, _), evals) in iter::zip(&witnesses, &verifier_output.multilinear_evals) {
+			let n_vars = multilins[0].n_vars();
+			let query =
+				MultilinearQuery::with_full_query(&verifier_output.challenges[n_rounds - n_vars..])
+					.unwrap();
+			for (multilin, &expected) in iter::zip(multilins, evals) {
+				assert_eq!(multilin.evaluate(&query).unwrap(), expected);
+			}
+		}
+	}
+
+	#[test]
+	fn test_masked_claim_rejects_too_many_points() {
+		assert!(MaskedSumcheckClaim::<FE, _>::new(
+			3,
+			9,
+			1,
+			vec![CompositeSumClaim {
+				composition: TestProductComposition::new(1),
+				sum: FE::ONE,
+			}],
+		)
+		.is_err());
+	}
+}
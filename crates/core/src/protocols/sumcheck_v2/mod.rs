@@ -11,14 +11,41 @@
 //! [`crate::protocols::zerocheck`] modules.
 
 mod common;
+pub mod coset;
+mod encoding;
 mod error;
+mod external;
+mod features;
+mod labels;
+pub mod masked;
+mod oracles;
 pub mod prove;
+mod strict;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod testing;
 #[cfg(test)]
 mod tests;
+mod transform;
 mod verify;
+mod window;
 pub mod zerocheck;
+pub mod zk;
 
 pub use common::*;
+pub use coset::CosetSumcheckClaim;
+pub use encoding::*;
 pub use error::*;
+pub use external::*;
+pub use features::*;
+pub use labels::*;
+pub use masked::MaskedSumcheckClaim;
+pub use oracles::*;
+pub use strict::*;
+pub use transform::*;
 pub use verify::*;
+pub(crate) use verify::{
+	batch_weighted_value, compute_expected_batch_composite_evaluation, transparent_eval_point,
+};
+pub use window::*;
 pub use zerocheck::ZerocheckClaim;
+pub use zk::{mask_opening_point, MaskPolynomial, ZkProof, ZkSumcheckOutput};
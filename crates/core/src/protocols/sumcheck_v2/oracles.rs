@@ -0,0 +1,130 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Reduction of verified sumcheck claims over multilinear oracles to evalcheck claims.
+
+use super::{
+	common::{BatchSumcheckOutput, FoldDirection},
+	error::{Error, VerificationError},
+};
+use crate::{
+	oracle::{Error as OracleError, MultilinearOracleSet, OracleId},
+	protocols::evalcheck::EvalcheckMultilinearClaim,
+};
+use binius_field::TowerField;
+use binius_utils::bail;
+use std::iter;
+
+/// The multilinear oracles a sumcheck claim is over, in the order of its multilinears.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OracleClaimMeta {
+	pub n_vars: usize,
+	pub oracle_ids: Vec<OracleId>,
+}
+
+/// Turns the output of a verified sumcheck batch into evalcheck claims on the multilinear oracles.
+///
+/// `metas[i]` describes the oracles of claim `i` of the batch, and `output` is the result of
+/// verifying the batch with provers that fold low-to-high. Each multilinear evaluation becomes an
+/// evalcheck claim on its oracle at the evaluation point of its claim, in claim order, then in
+/// multilinear order.
+///
+/// ## Throws
+///
+/// * [`VerificationError::NumberOfFinalEvaluations`] if there is not one claim description per
+///   claim in the output, or a claim has more variables than there are challenges
+/// * [`VerificationError::NumberOfClaimEvaluations`] if a claim does not have one evaluation per
+///   oracle
+/// * [`OracleError::InvalidOracleId`] if an oracle is not in `oracles`
+/// * [`OracleError::IncorrectNumberOfVariables`] if an oracle does not have the number of
+///   variables of its claim
+pub fn make_eval_claims<F: TowerField>(
+	oracles: &MultilinearOracleSet<F>,
+	metas: impl IntoIterator<Item = OracleClaimMeta>,
+	output: BatchSumcheckOutput<F>,
+) -> Result<Vec<EvalcheckMultilinearClaim<F>>, Error> {
+	make_eval_claims_with_fold_direction(oracles, metas, output, FoldDirection::LowToHigh)
+}
+
+/// Turns the output of a verified sumcheck batch into evalcheck claims on the multilinear oracles,
+/// for provers that fold in the given direction.
+///
+/// See [`make_eval_claims`].
+pub fn make_eval_claims_with_fold_direction<F: TowerField>(
+	oracles: &MultilinearOracleSet<F>,
+	metas: impl IntoIterator<Item = OracleClaimMeta>,
+	output: BatchSumcheckOutput<F>,
+	fold_direction: FoldDirection,
+) -> Result<Vec<EvalcheckMultilinearClaim<F>>, Error> {
+	let BatchSumcheckOutput {
+		challenges,
+		multilinear_evals,
+	} = output;
+
+	let metas = metas.into_iter().collect::<Vec<_>>();
+	if metas.len() != multilinear_evals.len() {
+		bail!(VerificationError::NumberOfFinalEvaluations);
+	}
+
+	let mut eval_claims = Vec::new();
+	for (claim_index, (meta, evals)) in iter::zip(metas, multilinear_evals).enumerate() {
+		let OracleClaimMeta { n_vars, oracle_ids } = meta;
+		if oracle_ids.len() != evals.len() {
+			bail!(VerificationError::NumberOfClaimEvaluations {
+				claim_index,
+				expected: oracle_ids.len(),
+			});
+		}
+		if n_vars > challenges.len() {
+			bail!(VerificationError::NumberOfFinalEvaluations);
+		}
+
+		let eval_point = fold_direction.eval_point(&challenges, n_vars);
+		for (oracle_id, eval) in iter::zip(oracle_ids, evals) {
+			if oracle_id >= oracles.size() {
+				bail!(OracleError::InvalidOracleId(oracle_id));
+			}
+			if oracles.n_vars(oracle_id) != n_vars {
+				bail!(OracleError::IncorrectNumberOfVariables { expected: n_vars });
+			}
+			eval_claims.push(EvalcheckMultilinearClaim {
+				poly: oracles.oracle(oracle_id),
+				eval_point: eval_point.to_vec(),
+				eval,
+				is_random_point: true,
+			});
+		}
+	}
+	Ok(eval_claims)
+}
+
+/// Turns the outputs of a sumcheck split into several batches into evalcheck claims on the
+/// multilinear oracles.
+///
+/// `outputs` are the outputs of [`super::batch_verify_split`], and `metas` describe the oracles of
+/// all the claims, in the order of the claims. The evalcheck claims of the batches are merged in
+/// the same order, as if the claims had been proven in one batch.
+///
+/// ## Throws
+///
+/// * [`VerificationError::NumberOfFinalEvaluations`] if the batches do not cover the claim
+///   descriptions exactly
+/// * the errors of [`make_eval_claims`] for each batch
+pub fn make_split_eval_claims<F: TowerField>(
+	oracles: &MultilinearOracleSet<F>,
+	metas: impl IntoIterator<Item = OracleClaimMeta>,
+	outputs: impl IntoIterator<Item = BatchSumcheckOutput<F>>,
+) -> Result<Vec<EvalcheckMultilinearClaim<F>>, Error> {
+	let mut metas = metas.into_iter();
+	let mut eval_claims = Vec::new();
+	for output in outputs {
+		let batch_metas = metas
+			.by_ref()
+			.take(output.multilinear_evals.len())
+			.collect::<Vec<_>>();
+		eval_claims.extend(make_eval_claims(oracles, batch_metas, output)?);
+	}
+	if metas.next().is_some() {
+		bail!(VerificationError::NumberOfFinalEvaluations);
+	}
+	Ok(eval_claims)
+}
@@ -0,0 +1,372 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::{
+	memory::{MemoryBudget, ScratchArena},
+	prover_state::SumcheckEvaluator,
+};
+use crate::{
+	polynomial::MultilinearExtension,
+	protocols::{
+		sumcheck_v2::{common::FoldDirection, error::Error},
+		utils::packed_from_fn_with_offset,
+	},
+};
+use binius_field::{packed::get_packed_slice, Field, PackedField};
+use binius_utils::{alloc::zeroed_std_vec, array_2d::Array2D};
+use rayon::{current_num_threads, prelude::*};
+use std::{array, fmt::Debug, iter, mem, ops::Range};
+
+/// The hardware backend that runs the hypercube loops of the sumcheck provers.
+///
+/// Nearly all of the prover work in a round is spent in two loops over the hypercube: summing
+/// the composite evaluations that make up the round polynomial, and folding the large field
+/// multilinears with the round challenge. The provers in this module run both loops through a
+/// backend, so that they can be offloaded to an accelerator such as a GPU without changing the
+/// provers. [`CpuBackend`] runs them on the CPU with rayon.
+pub trait ComputeBackend: Debug + Send + Sync {
+	/// Folds one variable of a large field multilinear with a challenge.
+	///
+	/// The multilinear has `n_vars` variables. When it has fewer than `P::WIDTH` evaluations, the
+	/// rest of its single packed element is zero padding, as in
+	/// [`MultilinearExtension::evaluate_partial_low`]. Folding binds the lowest variable to the
+	/// challenge for [`FoldDirection::LowToHigh`] and the highest for
+	/// [`FoldDirection::HighToLow`], and returns the multilinear in the remaining `n_vars - 1`
+	/// variables, padded in the same way.
+	fn fold_multilinear<P: PackedField>(
+		&self,
+		multilinear: &MultilinearExtension<P>,
+		n_vars: usize,
+		challenge: P::Scalar,
+		fold_direction: FoldDirection,
+	) -> Result<MultilinearExtension<P>, Error>;
+
+	/// Sums the round evaluations of the composites over the hypercube.
+	///
+	/// The round polynomial of a state with `n_vars` variables is a sum over the $2^{n - 1}$ pairs
+	/// of vertices that differ in the variable folded in the round, grouped into packed indices.
+	/// `sample` writes the evaluations of the `n_multilinears` multilinears at a range of packed
+	/// indices into the rows of `evals_0` and `evals_1`, one column per multilinear, and each
+	/// evaluator accumulates its round evaluations from a row with
+	/// [`SumcheckEvaluator::process_vertex`].
+	///
+	/// The scratch memory the loop holds at once should stay within `memory_budget`, and the
+	/// backend records it with [`MemoryBudget::record_scratch_bytes`]. Host scratch buffers
+	/// should be taken from and returned to [`MemoryBudget::scratch_arena`].
+	///
+	/// Returns the round evaluations of each evaluator, summed over all vertices and all the
+	/// scalars of the packed accumulators, or the first error returned by `sample`.
+	fn sum_composition_evals<P, Evaluator>(
+		&self,
+		n_vars: usize,
+		n_multilinears: usize,
+		sample: &(dyn Fn(Range<usize>, &mut Array2D<P>, &mut Array2D<P>) -> Result<(), Error>
+		      + Sync),
+		evaluators: &[Evaluator],
+		memory_budget: &MemoryBudget,
+	) -> Result<Vec<Vec<P::Scalar>>, Error>
+	where
+		P: PackedField,
+		Evaluator: SumcheckEvaluator<P> + Sync;
+
+	/// The scratch memory in bytes held at once by [`Self::sum_composition_evals`] on the host.
+	///
+	/// `n_round_evals` are the numbers of round evaluations of the evaluators. Backends that keep
+	/// their scratch memory on a device report zero.
+	fn sum_composition_scratch_bytes<P: PackedField>(
+		&self,
+		_n_vars: usize,
+		_n_multilinears: usize,
+		_n_round_evals: &[usize],
+	) -> usize {
+		0
+	}
+}
+
+/// The base-2 logarithm of the largest number of vertices processed at once by a thread.
+const MAX_LOG_BATCH_SIZE: usize = 6;
+
+/// The default backend, which runs the sumcheck loops on the CPU in parallel with rayon.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CpuBackend;
+
+impl ComputeBackend for CpuBackend {
+	fn fold_multilinear<P: PackedField>(
+		&self,
+		multilinear: &MultilinearExtension<P>,
+		n_vars: usize,
+		challenge: P::Scalar,
+		fold_direction: FoldDirection,
+	) -> Result<MultilinearExtension<P>, Error> {
+		match fold_direction {
+			FoldDirection::LowToHigh => Ok(multilinear.partial_evaluate_low(&[challenge])?),
+			FoldDirection::HighToLow if multilinear.n_vars() == n_vars => {
+				Ok(multilinear.partial_evaluate_high(&[challenge])?)
+			}
+			FoldDirection::HighToLow => fold_highest_variable(multilinear, n_vars, challenge),
+		}
+	}
+
+	fn sum_composition_evals<P, Evaluator>(
+		&self,
+		n_vars: usize,
+		n_multilinears: usize,
+		sample: &(dyn Fn(Range<usize>, &mut Array2D<P>, &mut Array2D<P>) -> Result<(), Error>
+		      + Sync),
+		evaluators: &[Evaluator],
+		memory_budget: &MemoryBudget,
+	) -> Result<Vec<Vec<P::Scalar>>, Error>
+	where
+		P: PackedField,
+		Evaluator: SumcheckEvaluator<P> + Sync,
+	{
+		let n_round_evals = evaluators.iter().map(|evaluator| evaluator.n_round_evals());
+		let scratch_arena = memory_budget.scratch_arena();
+		let state_bytes = |batch_size: usize| {
+			ParFoldStates::<P>::scratch_bytes(n_multilinears, n_round_evals.clone(), batch_size)
+		};
+
+		// Process batches of vertices in parallel, accumulating the round evaluations.
+		let mut log_batch_size = (n_vars - 1).min(MAX_LOG_BATCH_SIZE);
+		let n_par_states = current_num_threads().min(1 << (n_vars - 1 - log_batch_size));
+
+		let packed_accumulators = match memory_budget.max_scratch_bytes() {
+			Some(max_scratch_bytes)
+				if n_par_states * state_bytes(1 << log_batch_size) > max_scratch_bytes =>
+			{
+				// Over budget, shrink the batches until a single state fits and run as many states
+				// as the budget allows, each over a contiguous chunk of the batches.
+				while log_batch_size > 0 && state_bytes(1 << log_batch_size) > max_scratch_bytes {
+					log_batch_size -= 1;
+				}
+				let batch_size = 1 << log_batch_size;
+				let n_batches = 1 << (n_vars - 1 - log_batch_size);
+				let n_states = (max_scratch_bytes / state_bytes(batch_size)).clamp(1, n_batches);
+				let chunk_size = n_batches.div_ceil(n_states);
+				memory_budget.record_scratch_bytes(n_states * state_bytes(batch_size));
+				// Idle buffers of other shapes would be held on top of the budget, so drop them.
+				scratch_arena.retain(|scratch: &FoldScratch<P>| {
+					ParFoldStates::scratch_has_shape(scratch, n_multilinears, batch_size)
+				});
+
+				(0..n_states)
+					.into_par_iter()
+					.map(|chunk| {
+						let mut par_fold_states = ParFoldStates::new(
+							n_multilinears,
+							n_round_evals.clone(),
+							batch_size,
+							scratch_arena,
+						);
+						let batches = chunk * chunk_size..((chunk + 1) * chunk_size).min(n_batches);
+						for batch in batches {
+							par_fold_states.process_batch(
+								batch << log_batch_size,
+								sample,
+								evaluators,
+							)?;
+						}
+						Ok::<_, Error>(par_fold_states.into_round_evals(scratch_arena))
+					})
+					.try_reduce_with(|lhs, rhs| Ok(add_round_evals(lhs, rhs)))
+					.expect("there is at least one chunk")?
+			}
+			_ => {
+				let batch_size = 1 << log_batch_size;
+				memory_budget.record_scratch_bytes(n_par_states * state_bytes(batch_size));
+
+				(0..(1 << (n_vars - 1 - log_batch_size)))
+					.into_par_iter()
+					.try_fold(
+						|| {
+							ParFoldStates::new(
+								n_multilinears,
+								n_round_evals.clone(),
+								batch_size,
+								scratch_arena,
+							)
+						},
+						|mut par_fold_states, batch| {
+							par_fold_states.process_batch(
+								batch << log_batch_size,
+								sample,
+								evaluators,
+							)?;
+							Ok::<_, Error>(par_fold_states)
+						},
+					)
+					.map(|states| states.map(|states| states.into_round_evals(scratch_arena)))
+					// Simply sum up the fold partitions.
+					.try_reduce(
+						|| {
+							evaluators
+								.iter()
+								.map(|evaluator| vec![P::zero(); evaluator.n_round_evals()])
+								.collect()
+						},
+						|lhs, rhs| Ok(add_round_evals(lhs, rhs)),
+					)?
+			}
+		};
+
+		let evals = packed_accumulators
+			.into_iter()
+			.map(|vals| {
+				vals.into_iter()
+					.map(|packed_val| packed_val.iter().sum())
+					.collect()
+			})
+			.collect();
+		Ok(evals)
+	}
+
+	fn sum_composition_scratch_bytes<P: PackedField>(
+		&self,
+		n_vars: usize,
+		n_multilinears: usize,
+		n_round_evals: &[usize],
+	) -> usize {
+		let log_batch_size = (n_vars - 1).min(MAX_LOG_BATCH_SIZE);
+		let batch_size = 1 << log_batch_size;
+		let n_batches = 1 << (n_vars - 1 - log_batch_size);
+
+		// There is at most one fold state per thread.
+		n_batches.min(current_num_threads())
+			* ParFoldStates::<P>::scratch_bytes(
+				n_multilinears,
+				n_round_evals.iter().copied(),
+				batch_size,
+			)
+	}
+}
+
+/// Adds the round evaluations accumulated by two parallel fold states.
+fn add_round_evals<P: PackedField>(lhs: Vec<Vec<P>>, rhs: Vec<Vec<P>>) -> Vec<Vec<P>> {
+	iter::zip(lhs, rhs)
+		.map(|(mut lhs_vals, rhs_vals)| {
+			for (lhs_val, rhs_val) in lhs_vals.iter_mut().zip(rhs_vals) {
+				*lhs_val += rhs_val;
+			}
+			lhs_vals
+		})
+		.collect()
+}
+
+/// The scratch buffers of a [`ParFoldStates`], kept in a [`ScratchArena`] between rounds.
+type FoldScratch<P> = [Array2D<P>; 3];
+
+/// Parallel fold state, consisting of scratch area and result accumulator.
+#[derive(Debug)]
+struct ParFoldStates<P: PackedField> {
+	// Evaluations at 0, 1 and domain points, per MLE. Scratch space.
+	evals_0: Array2D<P>,
+	evals_1: Array2D<P>,
+	evals_z: Array2D<P>,
+
+	/// Accumulated sums of evaluations over univariate domain.
+	///
+	/// Each element of the outer vector corresponds to one composite polynomial. Each element of
+	/// an inner vector contains the evaluations at different points.
+	round_evals: Vec<Vec<P>>,
+}
+
+impl<P: PackedField> ParFoldStates<P> {
+	/// Creates a fold state, reusing scratch buffers of the same shape from the arena.
+	///
+	/// The scratch buffers are overwritten by every batch before they are read, so buffers left
+	/// over from earlier rounds need no clearing.
+	fn new(
+		n_multilinears: usize,
+		n_round_evals: impl Iterator<Item = usize>,
+		n_states: usize,
+		scratch_arena: &ScratchArena,
+	) -> Self {
+		let [evals_0, evals_1, evals_z] = scratch_arena
+			.take(|scratch: &FoldScratch<P>| {
+				Self::scratch_has_shape(scratch, n_multilinears, n_states)
+			})
+			.unwrap_or_else(|| array::from_fn(|_| Array2D::zeroes(n_states, n_multilinears)));
+		Self {
+			evals_0,
+			evals_1,
+			evals_z,
+			round_evals: n_round_evals
+				.map(|n_round_evals| zeroed_std_vec(n_round_evals))
+				.collect(),
+		}
+	}
+
+	fn scratch_has_shape(scratch: &FoldScratch<P>, n_multilinears: usize, n_states: usize) -> bool {
+		scratch[0].rows() == n_states && scratch[0].cols() == n_multilinears
+	}
+
+	/// Puts the scratch buffers back into the arena and returns the accumulated round evaluations.
+	fn into_round_evals(self, scratch_arena: &ScratchArena) -> Vec<Vec<P>> {
+		let scratch: FoldScratch<P> = [self.evals_0, self.evals_1, self.evals_z];
+		scratch_arena.put(scratch);
+		self.round_evals
+	}
+
+	/// The size in bytes of a fold state with the given dimensions.
+	fn scratch_bytes(
+		n_multilinears: usize,
+		n_round_evals: impl Iterator<Item = usize>,
+		batch_size: usize,
+	) -> usize {
+		let n_elems = 3 * batch_size * n_multilinears + n_round_evals.sum::<usize>();
+		n_elems * mem::size_of::<P>()
+	}
+
+	/// Samples the batch of vertices starting at `begin` and accumulates their round evaluations.
+	fn process_batch<Evaluator: SumcheckEvaluator<P>>(
+		&mut self,
+		begin: usize,
+		sample: &(dyn Fn(Range<usize>, &mut Array2D<P>, &mut Array2D<P>) -> Result<(), Error>
+		      + Sync),
+		evaluators: &[Evaluator],
+	) -> Result<(), Error> {
+		let batch_size = self.evals_0.rows();
+		sample(begin..begin + batch_size, &mut self.evals_0, &mut self.evals_1)?;
+
+		for (evaluator, round_evals) in iter::zip(evaluators, self.round_evals.iter_mut()) {
+			for k in 0..batch_size {
+				evaluator.process_vertex(
+					begin + k,
+					self.evals_0.get_row(k),
+					self.evals_1.get_row(k),
+					self.evals_z.get_row_mut(k),
+					round_evals,
+				);
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Folds the highest of the `n_vars` variables of a large field multilinear with a challenge.
+///
+/// The evaluations past the first $2^{n - 1}$ are zero padding, as in
+/// `MultilinearExtension::evaluate_partial_low`. This is only needed when the number of variables
+/// of the multilinear extension itself exceeds `n_vars` due to the padding, otherwise the highest
+/// variable is folded with `MultilinearExtension::partial_evaluate_high`.
+fn fold_highest_variable<P: PackedField>(
+	multilinear: &MultilinearExtension<P>,
+	n_vars: usize,
+	challenge: P::Scalar,
+) -> Result<MultilinearExtension<P>, Error> {
+	let evals = multilinear.evals();
+	let half = 1 << (n_vars - 1);
+	let values = (0..(half / P::WIDTH).max(1))
+		.into_par_iter()
+		.map(|i| {
+			packed_from_fn_with_offset(i, |index| {
+				if index >= half {
+					return P::Scalar::ZERO;
+				}
+				let eval_0 = get_packed_slice(evals, index);
+				let eval_1 = get_packed_slice(evals, index | half);
+				eval_0 + (eval_1 - eval_0) * challenge
+			})
+		})
+		.collect();
+	Ok(MultilinearExtension::from_values(values)?)
+}
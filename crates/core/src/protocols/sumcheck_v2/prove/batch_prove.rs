@@ -1,16 +1,30 @@
 // Copyright 2024 Ulvetanna Inc.
 
+use super::{
+	memory::MemoryBudget,
+	metrics::{RoundMetrics, SumcheckMetrics},
+};
+#[cfg(feature = "debug_validate_round")]
+use crate::polynomial::evaluate_univariate;
 use crate::{
 	challenger::CanSample,
 	protocols::sumcheck_v2::{
-		common::{BatchSumcheckOutput, Proof, RoundCoeffs},
+		common::{
+			sort_permutation_by_n_vars, BatchSumcheckOutput, FoldDirection, Proof, RoundCoeffs,
+			RoundProof, SumcheckOutput,
+		},
 		error::Error,
+		features::{observe_protocol_features, observe_round_proof_len, ProtocolFeatures},
+		labels::{observe_claim_labels, ClaimLabel},
 	},
 };
 use binius_field::Field;
 use binius_utils::{bail, sorting::is_sorted_ascending};
 use p3_challenger::CanObserve;
-use std::iter;
+#[cfg(feature = "parallel_batch_prove")]
+use rayon::prelude::*;
+use std::{iter, time::Instant};
+use tracing::debug_span;
 
 /// A sumcheck prover with a round-by-round execution interface.
 ///
@@ -18,7 +32,8 @@ use std::iter;
 /// depending on the structure of the multivariate polynomial that the protocol targets. For
 /// example, [Gruen24] observes a significant optimization available to the sumcheck prover when
 /// the multivariate is the product of a multilinear composite and an equality indicator
-/// polynomial, which arises in the zerocheck protocol.
+/// polynomial, which arises in the zerocheck protocol, and is implemented by
+/// [`super::ZerocheckProver`].
 ///
 /// The trait exposes a round-by-round interface so that protocol execution logic that drives the
 /// prover can interleave the executions of the interactive protocol, for example in the case of
@@ -29,7 +44,8 @@ use std::iter;
 /// times, and finally call [`Self::finish`]. If the calls aren't made in that order, the caller
 /// will get an error result.
 ///
-/// This trait is object-safe.
+/// This trait is object-safe. Boxed trait objects are provers themselves, so provers of different
+/// kinds can be batched together with [`batch_prove_dyn`].
 ///
 /// [Gruen24]: <https://eprint.iacr.org/2024/108>
 pub trait SumcheckProver<F: Field> {
@@ -54,9 +70,72 @@ pub trait SumcheckProver<F: Field> {
 	/// Folds the sumcheck multilinears with a new verifier challenge.
 	fn fold(&mut self, challenge: F) -> Result<(), Error>;
 
+	/// The scratch memory in bytes held at once by the last call to [`Self::execute`].
+	///
+	/// This is reported in the [`SumcheckMetrics`] for profiling. Provers that do not track their
+	/// scratch memory report zero.
+	fn scratch_bytes(&self) -> usize {
+		0
+	}
+
+	/// The order in which the prover binds the variables to the challenges.
+	///
+	/// The description of [`Self::execute`] is for provers that fold low-to-high. A prover that
+	/// folds high-to-low binds the challenges $r_0, ..., r_{k-1}$ to the variables in reverse order,
+	/// starting from the highest.
+	fn fold_direction(&self) -> FoldDirection {
+		FoldDirection::LowToHigh
+	}
+
+	/// Caps the scratch memory the prover allocates in each round, see [`MemoryBudget`].
+	///
+	/// Provers that do not allocate scratch memory in proportion to the hypercube ignore the
+	/// budget.
+	fn set_memory_budget(&mut self, _memory_budget: MemoryBudget) {}
+
 	/// Finishes the sumcheck proving protocol and returns the evaluations of all multilinears at
 	/// the challenge point.
 	fn finish(self) -> Result<Vec<F>, Error>;
+
+	/// Finishes a boxed prover, see [`Self::finish`].
+	///
+	/// Unlike [`Self::finish`], this can be called on a trait object. Implementations call
+	/// [`Self::finish`] on the unboxed prover.
+	fn finish_boxed(self: Box<Self>) -> Result<Vec<F>, Error>;
+}
+
+impl<'a, F: Field> SumcheckProver<F> for Box<dyn SumcheckProver<F> + 'a> {
+	fn n_vars(&self) -> usize {
+		(**self).n_vars()
+	}
+
+	fn execute(&mut self, batch_coeff: F) -> Result<RoundCoeffs<F>, Error> {
+		(**self).execute(batch_coeff)
+	}
+
+	fn fold(&mut self, challenge: F) -> Result<(), Error> {
+		(**self).fold(challenge)
+	}
+
+	fn scratch_bytes(&self) -> usize {
+		(**self).scratch_bytes()
+	}
+
+	fn fold_direction(&self) -> FoldDirection {
+		(**self).fold_direction()
+	}
+
+	fn set_memory_budget(&mut self, memory_budget: MemoryBudget) {
+		(**self).set_memory_budget(memory_budget)
+	}
+
+	fn finish(self) -> Result<Vec<F>, Error> {
+		self.finish_boxed()
+	}
+
+	fn finish_boxed(self: Box<Self>) -> Result<Vec<F>, Error> {
+		(*self).finish()
+	}
 }
 
 /// Prove a batched sumcheck protocol execution.
@@ -67,8 +146,198 @@ pub trait SumcheckProver<F: Field> {
 ///
 /// The provers in the `provers` parameter must in the same order as the corresponding claims
 /// provided to [`crate::protocols::sumcheck_v2::batch_verify`] during proof verification.
+///
+/// Each prover interpolates the round polynomial of each of its composites over an evaluation
+/// domain sized for the degree of that composite, so the round proofs only have as many
+/// coefficients as the highest degree composite of the active provers.
+///
+/// The provers must all fold in the same [`FoldDirection`], which determines the order of the
+/// output challenges. Proofs from provers that fold high-to-low are verified with
+/// [`crate::protocols::sumcheck_v2::batch_verify_with_fold_direction`].
 pub fn batch_prove<F, Prover, Challenger>(
+	provers: Vec<Prover>,
+	challenger: Challenger,
+) -> Result<(BatchSumcheckOutput<F>, Proof<F>), Error>
+where
+	F: Field,
+	Prover: SumcheckProver<F>,
+	Challenger: CanSample<F> + CanObserve<F>,
+{
+	batch_prove_with_features(provers, ProtocolFeatures::empty(), challenger)
+}
+
+/// Prove a batched sumcheck protocol execution with provers of different kinds.
+///
+/// This is [`batch_prove`] over boxed provers, for example to batch regular sumcheck provers with
+/// zerocheck provers.
+pub fn batch_prove_dyn<'a, F, Challenger>(
+	provers: Vec<Box<dyn SumcheckProver<F> + 'a>>,
+	challenger: Challenger,
+) -> Result<(BatchSumcheckOutput<F>, Proof<F>), Error>
+where
+	F: Field,
+	Challenger: CanSample<F> + CanObserve<F>,
+{
+	batch_prove(provers, challenger)
+}
+
+/// Prove a batched sumcheck protocol execution with the provers in any order.
+///
+/// The provers are sorted in descending order by number of variables before proving, and the
+/// multilinear evaluations of the output are returned in the original order of the provers. The
+/// proof is in sorted order and is verified with
+/// [`crate::protocols::sumcheck_v2::batch_verify_unsorted`], given the claims in the original
+/// order.
+pub fn batch_prove_unsorted<F, Prover, Challenger>(
+	provers: Vec<Prover>,
+	challenger: Challenger,
+) -> Result<(BatchSumcheckOutput<F>, Proof<F>), Error>
+where
+	F: Field,
+	Prover: SumcheckProver<F>,
+	Challenger: CanSample<F> + CanObserve<F>,
+{
+	let permutation = sort_permutation_by_n_vars(provers.iter().map(|prover| prover.n_vars()));
+	let mut provers = provers.into_iter().map(Some).collect::<Vec<_>>();
+	let sorted_provers = permutation
+		.iter()
+		.map(|&index| {
+			provers[index]
+				.take()
+				.expect("permutation indices are distinct")
+		})
+		.collect();
+
+	let (output, proof) = batch_prove(sorted_provers, challenger)?;
+	Ok((output.unsort(&permutation), proof))
+}
+
+/// Prove a batched sumcheck protocol execution, split into batches of at most `max_batch_claims`
+/// provers.
+///
+/// A single batch over many claims has long round proofs and keeps every prover in memory until
+/// the last round. The provers are instead grouped in order into consecutive batches of
+/// `max_batch_claims`, with the last batch possibly smaller, and the batches are proven one after
+/// the other with the same challenger. If `max_batch_claims` is `None`, the provers are proven in a
+/// single batch as with [`batch_prove`].
+///
+/// The provers of each batch must be in descending order by number of variables, which holds when
+/// all the provers are. Returns the output and the proof of each batch. The proofs are verified with
+/// [`crate::protocols::sumcheck_v2::batch_verify_split`] given the same `max_batch_claims`, and
+/// the outputs are turned into evalcheck claims with
+/// [`crate::protocols::sumcheck_v2::make_split_eval_claims`].
+///
+/// ## Throws
+///
+/// * [`Error::InvalidMaxBatchClaims`] if `max_batch_claims` is zero
+pub fn batch_prove_split<F, Prover, Challenger>(
+	provers: Vec<Prover>,
+	max_batch_claims: Option<usize>,
+	mut challenger: Challenger,
+) -> Result<(Vec<BatchSumcheckOutput<F>>, Vec<Proof<F>>), Error>
+where
+	F: Field,
+	Prover: SumcheckProver<F>,
+	Challenger: CanSample<F> + CanObserve<F>,
+{
+	let max_batch_claims = match max_batch_claims {
+		Some(0) => bail!(Error::InvalidMaxBatchClaims),
+		Some(max_batch_claims) => max_batch_claims,
+		None => provers.len().max(1),
+	};
+
+	let mut outputs = Vec::new();
+	let mut proofs = Vec::new();
+	let mut provers = provers.into_iter().peekable();
+	while provers.peek().is_some() {
+		let batch = provers.by_ref().take(max_batch_claims).collect();
+		let (output, proof) = batch_prove(batch, &mut challenger)?;
+		outputs.push(output);
+		proofs.push(proof);
+	}
+	Ok((outputs, proofs))
+}
+
+/// Prove a sumcheck protocol execution for a single claim.
+///
+/// This is [`batch_prove`] with a batch of one prover, whose output is unwrapped. The proof is
+/// verified with [`crate::protocols::sumcheck_v2::verify_single`].
+pub fn prove_single<F, Prover, Challenger>(
+	prover: Prover,
+	challenger: Challenger,
+) -> Result<(SumcheckOutput<F>, Proof<F>), Error>
+where
+	F: Field,
+	Prover: SumcheckProver<F>,
+	Challenger: CanSample<F> + CanObserve<F>,
+{
+	let (output, proof) = batch_prove(vec![prover], challenger)?;
+	Ok((output.into_single()?, proof))
+}
+
+/// Prove a batched sumcheck protocol execution and measure the rounds.
+///
+/// The proof is the same as from [`batch_prove`]. The [`SumcheckMetrics`] record the durations
+/// of the round computations and folds, the number of active provers and the scratch memory of
+/// each round, so that large proofs can be profiled without instrumenting the provers.
+pub fn batch_prove_with_metrics<F, Prover, Challenger>(
+	provers: Vec<Prover>,
+	mut challenger: Challenger,
+) -> Result<(BatchSumcheckOutput<F>, Proof<F>, SumcheckMetrics), Error>
+where
+	F: Field,
+	Prover: SumcheckProver<F>,
+	Challenger: CanSample<F> + CanObserve<F>,
+{
+	let features = ProtocolFeatures::empty();
+	observe_features(features, &mut challenger)?;
+
+	let mut metrics = SumcheckMetrics::default();
+	let (output, proof) =
+		prove_rounds(provers, features, BatchProgress::default(), challenger, |_, progress, _| {
+			metrics
+				.rounds
+				.extend(progress.metrics.rounds.last().cloned());
+			Ok(())
+		})?;
+	Ok((output, proof, metrics))
+}
+
+/// Prove a batched sumcheck protocol execution within a scratch memory budget.
+///
+/// Each prover is given a clone of `memory_budget` before proving, which caps the scratch memory
+/// of its hypercube loops. The provers run one at a time, so the budget applies to each prover in
+/// turn rather than to their sum. The proof is the same as from [`batch_prove`].
+///
+/// Returns the peak scratch memory in bytes held at once by any prover, along with the output and
+/// the proof, for tuning the budget.
+pub fn batch_prove_with_memory_budget<F, Prover, Challenger>(
 	mut provers: Vec<Prover>,
+	memory_budget: MemoryBudget,
+	challenger: Challenger,
+) -> Result<(BatchSumcheckOutput<F>, Proof<F>, usize), Error>
+where
+	F: Field,
+	Prover: SumcheckProver<F>,
+	Challenger: CanSample<F> + CanObserve<F>,
+{
+	for prover in provers.iter_mut() {
+		prover.set_memory_budget(memory_budget.clone());
+	}
+	let (output, proof) = batch_prove(provers, challenger)?;
+	Ok((output, proof, memory_budget.peak_scratch_bytes()))
+}
+
+/// Prove a batched sumcheck protocol execution using optional protocol features.
+///
+/// The features are recorded in the proof header, see [`ProtocolFeatures`].
+///
+/// ## Throws
+///
+/// * [`Error::UnsupportedProtocolFeatures`] if `features` contains unsupported features
+pub fn batch_prove_with_features<F, Prover, Challenger>(
+	provers: Vec<Prover>,
+	features: ProtocolFeatures,
 	mut challenger: Challenger,
 ) -> Result<(BatchSumcheckOutput<F>, Proof<F>), Error>
 where
@@ -76,34 +345,150 @@ where
 	Prover: SumcheckProver<F>,
 	Challenger: CanSample<F> + CanObserve<F>,
 {
-	if provers.is_empty() {
-		return Ok((
-			BatchSumcheckOutput {
-				challenges: Vec::new(),
-				multilinear_evals: Vec::new(),
-			},
-			Proof::default(),
-		));
+	observe_features(features, &mut challenger)?;
+	prove_rounds(provers, features, BatchProgress::default(), challenger, |_, _, _| Ok(()))
+}
+
+/// Prove a batched sumcheck protocol execution, executing the active provers of each round in
+/// parallel.
+///
+/// The rounds of the provers are independent, so their round polynomials are computed on the rayon
+/// thread pool and summed with a parallel reduction. The proof is the same as from
+/// [`batch_prove`].
+#[cfg(feature = "parallel_batch_prove")]
+pub fn batch_prove_parallel<F, Prover, Challenger>(
+	provers: Vec<Prover>,
+	mut challenger: Challenger,
+) -> Result<(BatchSumcheckOutput<F>, Proof<F>), Error>
+where
+	F: Field,
+	Prover: SumcheckProver<F> + Send,
+	Challenger: CanSample<F> + CanObserve<F>,
+{
+	let features = ProtocolFeatures::empty();
+	observe_features(features, &mut challenger)?;
+	prove_rounds_with(
+		provers,
+		features,
+		BatchProgress::default(),
+		challenger,
+		execute_provers_parallel,
+		|_, _, _| Ok(()),
+	)
+}
+
+/// Checks that the protocol features are supported and observes them with the challenger.
+pub(super) fn observe_features<F, Challenger>(
+	features: ProtocolFeatures,
+	challenger: &mut Challenger,
+) -> Result<(), Error>
+where
+	F: Field,
+	Challenger: CanObserve<F>,
+{
+	let unsupported = features.unsupported();
+	if !unsupported.is_empty() {
+		bail!(Error::UnsupportedProtocolFeatures { unsupported });
 	}
 
+	observe_protocol_features::<F, _>(features, challenger);
+	Ok(())
+}
+
+/// The rounds of a batched sumcheck proof executed so far.
+#[derive(Debug, Default)]
+pub(super) struct BatchProgress<F: Field> {
+	pub batch_coeffs: Vec<F>,
+	pub challenges: Vec<F>,
+	pub rounds: Vec<RoundProof<F>>,
+	/// The measurements of the rounds executed since the proof was started or resumed.
+	pub metrics: SumcheckMetrics,
+}
+
+/// Executes the remaining rounds of a batched sumcheck proof and finishes the provers.
+///
+/// `on_round` is called after each round, once the provers have been folded.
+pub(super) fn prove_rounds<F, Prover, Challenger>(
+	provers: Vec<Prover>,
+	features: ProtocolFeatures,
+	progress: BatchProgress<F>,
+	challenger: Challenger,
+	on_round: impl FnMut(&[Prover], &BatchProgress<F>, &Challenger) -> Result<(), Error>,
+) -> Result<(BatchSumcheckOutput<F>, Proof<F>), Error>
+where
+	F: Field,
+	Prover: SumcheckProver<F>,
+	Challenger: CanSample<F> + CanObserve<F>,
+{
+	prove_rounds_with(provers, features, progress, challenger, execute_provers, on_round)
+}
+
+/// Executes the remaining rounds of a batched sumcheck proof with `execute` computing the mixed
+/// round polynomial of the active provers, see [`prove_rounds`].
+///
+/// With the `debug_validate_round` feature, the provers are executed one by one to validate their
+/// round polynomials, and `execute` is not used.
+fn prove_rounds_with<F, Prover, Challenger, Execute>(
+	mut provers: Vec<Prover>,
+	features: ProtocolFeatures,
+	mut progress: BatchProgress<F>,
+	mut challenger: Challenger,
+	#[cfg_attr(feature = "debug_validate_round", allow(unused_variables))] execute: Execute,
+	mut on_round: impl FnMut(&[Prover], &BatchProgress<F>, &Challenger) -> Result<(), Error>,
+) -> Result<(BatchSumcheckOutput<F>, Proof<F>), Error>
+where
+	F: Field,
+	Prover: SumcheckProver<F>,
+	Challenger: CanSample<F> + CanObserve<F>,
+	Execute: Fn(&mut [Prover], &[F]) -> Result<RoundCoeffs<F>, Error>,
+{
+	let compressed = !features.contains(ProtocolFeatures::UNCOMPRESSED_ROUND_PROOFS);
+	let trimmed = features.contains(ProtocolFeatures::TRIMMED_ROUND_PROOFS);
+
 	// Check that the provers are in descending order by n_vars
 	if !is_sorted_ascending(provers.iter().map(|prover| prover.n_vars()).rev()) {
 		bail!(Error::ClaimsOutOfOrder);
 	}
 
+	let fold_direction = provers
+		.first()
+		.map(|prover| prover.fold_direction())
+		.unwrap_or_default();
+	if provers
+		.iter()
+		.any(|prover| prover.fold_direction() != fold_direction)
+	{
+		bail!(Error::FoldDirectionMismatch);
+	}
+
 	let n_rounds = provers
 		.iter()
 		.map(|prover| prover.n_vars())
 		.max()
 		.unwrap_or(0);
 
+	// A resumed proof must have activated exactly the provers with more variables than remain.
+	let first_round = progress.challenges.len();
+	if progress.rounds.len() != first_round
+		|| first_round > n_rounds
+		|| progress.batch_coeffs.len()
+			!= provers
+				.iter()
+				.filter(|prover| prover.n_vars() + first_round > n_rounds)
+				.count()
+	{
+		bail!(Error::InvalidCheckpoint);
+	}
+
+	// The round sum of each prover, known from the second round it takes part in.
+	#[cfg(feature = "debug_validate_round")]
+	let mut round_sums = vec![None; provers.len()];
+
 	// active_index is an index into the provers slice.
-	let mut active_index = 0;
-	let mut batch_coeffs = Vec::with_capacity(provers.len());
-	let mut challenges = Vec::with_capacity(n_rounds);
-	let mut rounds = Vec::with_capacity(n_rounds);
-	for round_no in 0..n_rounds {
+	let mut active_index = progress.batch_coeffs.len();
+	for round_no in first_round..n_rounds {
 		let n_vars = n_rounds - round_no;
+		let _span = debug_span!("sumcheck_v2::round", round = round_no).entered();
 
 		// Activate new provers
 		while let Some(prover) = provers.get(active_index) {
@@ -112,29 +497,74 @@ where
 			}
 
 			let next_batch_coeff = challenger.sample();
-			batch_coeffs.push(next_batch_coeff);
+			progress.batch_coeffs.push(next_batch_coeff);
 			active_index += 1;
 		}
 
 		// Process the active provers
-		let mut round_coeffs = RoundCoeffs::default();
-		for (&batch_coeff, prover) in
-			iter::zip(batch_coeffs.iter(), provers[..active_index].iter_mut())
-		{
-			let prover_coeffs = prover.execute(batch_coeff)?;
-			round_coeffs += &(prover_coeffs * batch_coeff);
+		let execute_start = Instant::now();
+		#[cfg(not(feature = "debug_validate_round"))]
+		let mut round_coeffs = debug_span!("execute", n_active_provers = active_index)
+			.in_scope(|| execute(&mut provers[..active_index], &progress.batch_coeffs))?;
+		// The round polynomials of the provers are kept to validate each against its round sum.
+		#[cfg(feature = "debug_validate_round")]
+		let (mut round_coeffs, prover_round_coeffs) =
+			debug_span!("execute", n_active_provers = active_index).in_scope(|| {
+				execute_provers_separately(&mut provers[..active_index], &progress.batch_coeffs)
+			})?;
+		let execute_duration = execute_start.elapsed();
+
+		#[cfg(feature = "debug_validate_round")]
+		validate_round_sums(round_no, &prover_round_coeffs, &round_sums)?;
+
+		if trimmed {
+			round_coeffs = round_coeffs.trim();
 		}
 
-		let round_proof = round_coeffs.truncate();
+		let round_proof = if compressed {
+			round_coeffs.truncate()
+		} else {
+			RoundProof::uncompressed(round_coeffs)
+		};
+		observe_round_proof_len::<F, _>(features, round_proof.coeffs().len(), &mut challenger);
 		challenger.observe_slice(round_proof.coeffs());
-		rounds.push(round_proof);
+		progress.rounds.push(round_proof);
 
 		let challenge = challenger.sample();
-		challenges.push(challenge);
+		progress.challenges.push(challenge);
 
-		for prover in provers[..active_index].iter_mut() {
-			prover.fold(challenge)?;
+		#[cfg(feature = "debug_validate_round")]
+		for (round_sum, coeffs) in iter::zip(round_sums.iter_mut(), prover_round_coeffs) {
+			*round_sum = Some(evaluate_univariate(&coeffs.0, challenge));
 		}
+
+		let fold_start = Instant::now();
+		debug_span!("fold", n_active_provers = active_index).in_scope(|| {
+			provers[..active_index]
+				.iter_mut()
+				.try_for_each(|prover| prover.fold(challenge))
+		})?;
+		let fold_duration = fold_start.elapsed();
+
+		let scratch_bytes = provers[..active_index]
+			.iter()
+			.map(|prover| prover.scratch_bytes())
+			.sum::<usize>();
+		tracing::debug!(
+			n_active_provers = active_index,
+			?execute_duration,
+			?fold_duration,
+			scratch_bytes,
+			"sumcheck round metrics"
+		);
+		progress.metrics.rounds.push(RoundMetrics {
+			n_active_provers: active_index,
+			execute_duration,
+			fold_duration,
+			scratch_bytes,
+		});
+
+		on_round(&provers, &progress, &challenger)?;
 	}
 
 	let multilinear_evals = provers
@@ -147,13 +577,136 @@ where
 	}
 
 	let output = BatchSumcheckOutput {
-		challenges,
+		challenges: progress.challenges,
 		multilinear_evals: multilinear_evals.clone(),
-	};
+	}
+	.with_fold_direction(fold_direction);
 	let proof = Proof {
+		features,
 		multilinear_evals,
-		rounds,
+		rounds: progress.rounds,
+		claim_labels: None,
 	};
 
 	Ok((output, proof))
 }
+
+/// Executes the round of the active provers and mixes their round polynomials with the batching
+/// coefficients.
+pub(crate) fn execute_provers<F, Prover>(
+	provers: &mut [Prover],
+	batch_coeffs: &[F],
+) -> Result<RoundCoeffs<F>, Error>
+where
+	F: Field,
+	Prover: SumcheckProver<F>,
+{
+	let mut round_coeffs = RoundCoeffs::default();
+	for (&batch_coeff, prover) in iter::zip(batch_coeffs, provers) {
+		let prover_coeffs = prover.execute(batch_coeff)?;
+		round_coeffs += &(prover_coeffs * batch_coeff);
+	}
+	Ok(round_coeffs)
+}
+
+/// Executes the round of the active provers in parallel, see [`execute_provers`].
+///
+/// The rounds of the provers are independent, so they are executed in parallel, and their round
+/// polynomials are summed with a parallel reduction.
+#[cfg(feature = "parallel_batch_prove")]
+fn execute_provers_parallel<F, Prover>(
+	provers: &mut [Prover],
+	batch_coeffs: &[F],
+) -> Result<RoundCoeffs<F>, Error>
+where
+	F: Field,
+	Prover: SumcheckProver<F> + Send,
+{
+	provers
+		.par_iter_mut()
+		.zip(batch_coeffs)
+		.map(|(prover, &batch_coeff)| Ok(prover.execute(batch_coeff)? * batch_coeff))
+		.try_reduce(RoundCoeffs::default, |mut round_coeffs, prover_coeffs| {
+			round_coeffs += &prover_coeffs;
+			Ok(round_coeffs)
+		})
+}
+
+/// Executes the round of the active provers sequentially, returning the mixed round polynomial
+/// along with the round polynomial of each prover.
+#[cfg(feature = "debug_validate_round")]
+fn execute_provers_separately<F, Prover>(
+	provers: &mut [Prover],
+	batch_coeffs: &[F],
+) -> Result<(RoundCoeffs<F>, Vec<RoundCoeffs<F>>), Error>
+where
+	F: Field,
+	Prover: SumcheckProver<F>,
+{
+	let mut round_coeffs = RoundCoeffs::default();
+	let mut prover_round_coeffs = Vec::with_capacity(provers.len());
+	for (&batch_coeff, prover) in iter::zip(batch_coeffs, provers) {
+		let prover_coeffs = prover.execute(batch_coeff)?;
+		round_coeffs += &(prover_coeffs.clone() * batch_coeff);
+		prover_round_coeffs.push(prover_coeffs);
+	}
+	Ok((round_coeffs, prover_round_coeffs))
+}
+
+/// Checks that the round polynomial of each prover sums to its round sum over $\{0, 1\}$.
+///
+/// The round sum of a prover is the evaluation of its previous round polynomial at the previous
+/// challenge, so the check starts from the second round each prover takes part in.
+#[cfg(feature = "debug_validate_round")]
+fn validate_round_sums<F: Field>(
+	round: usize,
+	prover_round_coeffs: &[RoundCoeffs<F>],
+	round_sums: &[Option<F>],
+) -> Result<(), Error> {
+	for (prover_index, (coeffs, round_sum)) in
+		iter::zip(prover_round_coeffs, round_sums).enumerate()
+	{
+		let Some(round_sum) = *round_sum else {
+			continue;
+		};
+		let eval_0 = evaluate_univariate(&coeffs.0, F::ZERO);
+		let eval_1 = evaluate_univariate(&coeffs.0, F::ONE);
+		if eval_0 + eval_1 != round_sum {
+			bail!(Error::RoundSumValidationFailure {
+				round,
+				prover_index,
+			});
+		}
+	}
+	Ok(())
+}
+
+/// Prove a batched sumcheck protocol execution over labeled claims.
+///
+/// The `labels` must be in the same order as the provers and match the labels of the claims
+/// provided to [`crate::protocols::sumcheck_v2::batch_verify`]. If `disclose` is true, the labels
+/// are included in the proof.
+pub fn batch_prove_labeled<F, Prover, Challenger>(
+	provers: Vec<Prover>,
+	labels: Vec<Option<ClaimLabel>>,
+	disclose: bool,
+	mut challenger: Challenger,
+) -> Result<(BatchSumcheckOutput<F>, Proof<F>), Error>
+where
+	F: Field,
+	Prover: SumcheckProver<F>,
+	Challenger: CanSample<F> + CanObserve<F>,
+{
+	if labels.len() != provers.len() {
+		bail!(Error::IncorrectNumberOfClaimLabels {
+			expected: provers.len(),
+		});
+	}
+
+	observe_claim_labels::<F, _>(&labels, &mut challenger)?;
+	let (output, mut proof) = batch_prove(provers, challenger)?;
+	if disclose {
+		proof.claim_labels = Some(labels);
+	}
+	Ok((output, proof))
+}
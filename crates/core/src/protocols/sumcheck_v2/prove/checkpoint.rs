@@ -0,0 +1,257 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::batch_prove::{observe_features, prove_rounds, BatchProgress, SumcheckProver};
+use crate::{
+	challenger::CanSample,
+	protocols::sumcheck_v2::{
+		common::{BatchSumcheckOutput, Proof, RoundProof},
+		encoding::{decode_checkpoint_parts, ProofEncoding},
+		error::Error,
+		features::{observe_round_proof_len, ProtocolFeatures},
+	},
+};
+use binius_field::{Field, TowerField};
+use binius_utils::bail;
+use p3_challenger::CanObserve;
+
+/// The state of one multilinear of a sumcheck prover between two rounds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MultilinearSnapshot<F: Field> {
+	/// The multilinear has not been folded into the large field yet, which it will be after
+	/// `switchover_round` more rounds.
+	///
+	/// The multilinear itself is not part of the snapshot, the original witness is passed back in
+	/// when the prover is restored.
+	Transparent { switchover_round: usize },
+	/// The evaluations of the multilinear folded with all challenges so far.
+	Folded { evals: Vec<F> },
+}
+
+/// The state of a sumcheck prover between two rounds.
+///
+/// Together with the original witness, a snapshot determines the prover state, so a prover can be
+/// restored from it and continue the protocol where the snapshot was taken. Snapshots are encoded
+/// to bytes with [`Self::encode`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProverSnapshot<F: Field> {
+	/// The challenges the prover has been folded with.
+	pub challenges: Vec<F>,
+	/// The state of each multilinear, in the order the prover was constructed with.
+	pub multilinears: Vec<MultilinearSnapshot<F>>,
+	/// The claimed sums of the composites over the remaining variables.
+	pub sums: Vec<F>,
+}
+
+/// A sumcheck prover whose state can be snapshotted between rounds.
+///
+/// Provers are restored from a [`ProverSnapshot`] with a constructor of the concrete prover type,
+/// since restoring requires the original witness.
+pub trait ResumableProver<F: Field>: SumcheckProver<F> {
+	/// Takes a snapshot of the prover state.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::ExpectedFold`] if the prover has executed a round but not been folded yet
+	fn snapshot(&self) -> Result<ProverSnapshot<F>, Error>;
+}
+
+/// The state of a batched sumcheck proof between two rounds, from which proving can be resumed
+/// with [`resume_batch_prove`].
+///
+/// The checkpoint includes the challenger, so the transcript continues exactly where the
+/// checkpoint was taken. Checkpoints are encoded to bytes with [`Self::encode`] and decoded with
+/// [`Self::decode`].
+#[derive(Debug, Clone)]
+pub struct BatchProveCheckpoint<F: Field, Challenger> {
+	pub features: ProtocolFeatures,
+	/// The batching coefficients of the provers that have been activated so far.
+	pub batch_coeffs: Vec<F>,
+	/// The challenges sampled so far, one per round.
+	pub challenges: Vec<F>,
+	/// The round proofs sent so far.
+	pub rounds: Vec<RoundProof<F>>,
+	/// The snapshots of all provers in the batch.
+	pub provers: Vec<ProverSnapshot<F>>,
+	pub challenger: Challenger,
+}
+
+impl<F, Challenger> BatchProveCheckpoint<F, Challenger>
+where
+	F: TowerField,
+	Challenger: CanSample<F> + CanObserve<F>,
+{
+	/// Decodes a checkpoint from bytes produced by [`Self::encode`] with the same encoding.
+	///
+	/// `challenger` must be in the state it was in when it was passed to
+	/// [`batch_prove_with_checkpoints`]. The transcript up to the checkpoint is replayed into it
+	/// from the round proofs, which samples the batching coefficients and challenges again.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::Decoding`] if `bytes` is not a valid encoding of a checkpoint
+	/// * [`Error::InvalidCheckpoint`] if the prover snapshots were not folded with the replayed
+	///   challenges
+	pub fn decode(
+		bytes: &[u8],
+		encoding: ProofEncoding,
+		mut challenger: Challenger,
+	) -> Result<Self, Error> {
+		let (features, rounds, provers) = decode_checkpoint_parts::<F>(bytes, encoding)?;
+		observe_features(features, &mut challenger)?;
+
+		// The provers are activated in order, each in the round from which it has been folded
+		// with every challenge. Provers that have not been activated have not been folded.
+		let n_rounds = rounds.len();
+		let n_active = provers
+			.iter()
+			.take_while(|snapshot| !snapshot.challenges.is_empty())
+			.count();
+		if provers[n_active..]
+			.iter()
+			.any(|snapshot| !snapshot.challenges.is_empty())
+			|| provers[..n_active]
+				.iter()
+				.any(|snapshot| snapshot.challenges.len() > n_rounds)
+		{
+			bail!(Error::InvalidCheckpoint);
+		}
+		let mut activation_rounds = provers[..n_active]
+			.iter()
+			.map(|snapshot| n_rounds - snapshot.challenges.len())
+			.peekable();
+
+		let mut batch_coeffs = Vec::with_capacity(n_active);
+		let mut challenges = Vec::with_capacity(n_rounds);
+		for (round_no, round) in rounds.iter().enumerate() {
+			while activation_rounds.next_if_eq(&round_no).is_some() {
+				batch_coeffs.push(challenger.sample());
+			}
+			observe_round_proof_len::<F, _>(features, round.coeffs().len(), &mut challenger);
+			challenger.observe_slice(round.coeffs());
+			challenges.push(challenger.sample());
+		}
+
+		if batch_coeffs.len() != n_active
+			|| provers
+				.iter()
+				.any(|snapshot| !challenges.ends_with(&snapshot.challenges))
+		{
+			bail!(Error::InvalidCheckpoint);
+		}
+
+		Ok(Self {
+			features,
+			batch_coeffs,
+			challenges,
+			rounds,
+			provers,
+			challenger,
+		})
+	}
+}
+
+/// Prove a batched sumcheck protocol execution, taking a checkpoint every `checkpoint_interval`
+/// rounds.
+///
+/// This is [`super::batch_prove_with_features`], except that `on_checkpoint` is called with a
+/// [`BatchProveCheckpoint`] after every `checkpoint_interval` rounds. A checkpoint interval of
+/// zero disables checkpoints.
+pub fn batch_prove_with_checkpoints<F, Prover, Challenger>(
+	provers: Vec<Prover>,
+	features: ProtocolFeatures,
+	mut challenger: Challenger,
+	checkpoint_interval: usize,
+	on_checkpoint: impl FnMut(BatchProveCheckpoint<F, Challenger>),
+) -> Result<(BatchSumcheckOutput<F>, Proof<F>), Error>
+where
+	F: Field,
+	Prover: ResumableProver<F>,
+	Challenger: CanSample<F> + CanObserve<F> + Clone,
+{
+	observe_features(features, &mut challenger)?;
+	prove_rounds(
+		provers,
+		features,
+		BatchProgress::default(),
+		challenger,
+		checkpoint_fn(features, checkpoint_interval, on_checkpoint),
+	)
+}
+
+/// Resumes a batched sumcheck protocol execution from a checkpoint.
+///
+/// The provers must be restored from the prover snapshots in the checkpoint. Further checkpoints
+/// are taken as in [`batch_prove_with_checkpoints`]. The proof is identical to the proof produced
+/// without interruption.
+///
+/// ## Throws
+///
+/// * [`Error::InvalidCheckpoint`] if the checkpoint does not match the provers
+pub fn resume_batch_prove<F, Prover, Challenger>(
+	provers: Vec<Prover>,
+	checkpoint: BatchProveCheckpoint<F, Challenger>,
+	checkpoint_interval: usize,
+	on_checkpoint: impl FnMut(BatchProveCheckpoint<F, Challenger>),
+) -> Result<(BatchSumcheckOutput<F>, Proof<F>), Error>
+where
+	F: Field,
+	Prover: ResumableProver<F>,
+	Challenger: CanSample<F> + CanObserve<F> + Clone,
+{
+	let BatchProveCheckpoint {
+		features,
+		batch_coeffs,
+		challenges,
+		rounds,
+		provers: snapshots,
+		challenger,
+	} = checkpoint;
+
+	if snapshots.len() != provers.len() {
+		bail!(Error::InvalidCheckpoint);
+	}
+
+	let progress = BatchProgress {
+		batch_coeffs,
+		challenges,
+		rounds,
+		..Default::default()
+	};
+	prove_rounds(
+		provers,
+		features,
+		progress,
+		challenger,
+		checkpoint_fn(features, checkpoint_interval, on_checkpoint),
+	)
+}
+
+fn checkpoint_fn<F, Prover, Challenger>(
+	features: ProtocolFeatures,
+	checkpoint_interval: usize,
+	mut on_checkpoint: impl FnMut(BatchProveCheckpoint<F, Challenger>),
+) -> impl FnMut(&[Prover], &BatchProgress<F>, &Challenger) -> Result<(), Error>
+where
+	F: Field,
+	Prover: ResumableProver<F>,
+	Challenger: Clone,
+{
+	move |provers, progress, challenger| {
+		if checkpoint_interval == 0 || progress.challenges.len() % checkpoint_interval != 0 {
+			return Ok(());
+		}
+		let provers = provers
+			.iter()
+			.map(|prover| prover.snapshot())
+			.collect::<Result<_, _>>()?;
+		on_checkpoint(BatchProveCheckpoint {
+			features,
+			batch_coeffs: progress.batch_coeffs.clone(),
+			challenges: progress.challenges.clone(),
+			rounds: progress.rounds.clone(),
+			provers,
+			challenger: challenger.clone(),
+		});
+		Ok(())
+	}
+}
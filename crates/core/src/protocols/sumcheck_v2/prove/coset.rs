@@ -0,0 +1,399 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use crate::{
+	polynomial::{
+		extrapolate_line, transparent::subcube_ind::SubcubeIndicator, CompositionPoly,
+		Error as PolynomialError, EvaluationDomain, EvaluationDomainFactory, MultilinearComposite,
+		MultilinearPoly,
+	},
+	protocols::sumcheck_v2::{
+		common::{CompositeSumClaim, FoldDirection, RoundCoeffs, RoundEvals},
+		error::Error,
+		prove::{
+			backend::{ComputeBackend, CpuBackend},
+			memory::MemoryBudget,
+			prover_state::{ProverState, SumcheckEvaluator},
+			SumcheckProver,
+		},
+	},
+};
+use binius_field::{ExtensionField, Field, PackedExtension, PackedField};
+use binius_utils::bail;
+use itertools::izip;
+use rayon::prelude::*;
+
+pub fn validate_witness<F, P, M, Composition>(
+	multilinears: &[M],
+	indicator: &SubcubeIndicator,
+	sum_claims: impl IntoIterator<Item = CompositeSumClaim<F, Composition>>,
+) -> Result<(), Error>
+where
+	F: Field,
+	P: PackedField<Scalar = F>,
+	M: MultilinearPoly<P> + Send + Sync,
+	Composition: CompositionPoly<P>,
+{
+	let n_vars = indicator.n_vars();
+	for multilinear in multilinears.iter() {
+		if multilinear.n_vars() != n_vars {
+			bail!(Error::NumberOfVariablesMismatch);
+		}
+	}
+
+	let multilinears = multilinears.iter().collect::<Vec<_>>();
+
+	for (i, claim) in sum_claims.into_iter().enumerate() {
+		let CompositeSumClaim {
+			composition,
+			sum: expected_sum,
+		} = claim;
+		let witness = MultilinearComposite::new(n_vars, composition, multilinears.clone())?;
+		let sum = (0..(1 << n_vars))
+			.into_par_iter()
+			.filter(|&j| indicator.contains(j))
+			.map(|j| witness.evaluate_on_hypercube(j))
+			.try_reduce(|| F::ZERO, |a, b| Ok(a + b))?;
+
+		if sum != expected_sum {
+			bail!(Error::SumcheckNaiveValidationFailure {
+				composition_index: i,
+			});
+		}
+	}
+	Ok(())
+}
+
+/// A prover for sums of multilinear composites over a subcube of the hypercube.
+///
+/// The prover produces the same transcript as a [`super::RegularSumcheckProver`] for the
+/// composites multiplied by the subcube indicator, without materializing the indicator. The
+/// indicator factors as a product over the fixed variables, so in each round the prover sums the
+/// composites only over the vertices of the remaining subcube and multiplies the resulting round
+/// polynomial by the indicator factor of the current variable and the evaluation of the
+/// indicator factors of the previous variables at the challenges.
+#[derive(Debug)]
+pub struct CosetSumcheckProver<FDomain, P, Composition, M, Backend = CpuBackend>
+where
+	FDomain: Field,
+	P: PackedField,
+	M: MultilinearPoly<P> + Send + Sync,
+	Backend: ComputeBackend,
+{
+	n_vars: usize,
+	state: ProverState<P, M, Backend>,
+	indicator: SubcubeIndicator,
+	indicator_eval: P::Scalar,
+	compositions: Vec<Composition>,
+	domains: Vec<EvaluationDomain<FDomain>>,
+}
+
+impl<F, FDomain, P, Composition, M> CosetSumcheckProver<FDomain, P, Composition, M>
+where
+	F: Field + ExtensionField<FDomain>,
+	FDomain: Field,
+	P: PackedField<Scalar = F>,
+	Composition: CompositionPoly<P>,
+	M: MultilinearPoly<P> + Send + Sync,
+{
+	pub fn new(
+		multilinears: Vec<M>,
+		indicator: SubcubeIndicator,
+		composite_claims: impl IntoIterator<Item = CompositeSumClaim<F, Composition>>,
+		evaluation_domain_factory: impl EvaluationDomainFactory<FDomain>,
+		switchover_fn: impl Fn(usize) -> usize,
+	) -> Result<Self, Error> {
+		Self::new_with_backend(
+			multilinears,
+			indicator,
+			composite_claims,
+			evaluation_domain_factory,
+			switchover_fn,
+			CpuBackend,
+		)
+	}
+}
+
+impl<F, FDomain, P, Composition, M, Backend>
+	CosetSumcheckProver<FDomain, P, Composition, M, Backend>
+where
+	F: Field + ExtensionField<FDomain>,
+	FDomain: Field,
+	P: PackedField<Scalar = F>,
+	Composition: CompositionPoly<P>,
+	M: MultilinearPoly<P> + Send + Sync,
+	Backend: ComputeBackend,
+{
+	/// Constructs a prover that runs its hypercube loops on the given backend.
+	pub fn new_with_backend(
+		multilinears: Vec<M>,
+		indicator: SubcubeIndicator,
+		composite_claims: impl IntoIterator<Item = CompositeSumClaim<F, Composition>>,
+		evaluation_domain_factory: impl EvaluationDomainFactory<FDomain>,
+		switchover_fn: impl Fn(usize) -> usize,
+		backend: Backend,
+	) -> Result<Self, Error> {
+		let composite_claims = composite_claims.into_iter().collect::<Vec<_>>();
+		for claim in composite_claims.iter() {
+			if claim.composition.n_vars() != multilinears.len() {
+				bail!(Error::InvalidComposition {
+					expected_n_vars: multilinears.len(),
+				});
+			}
+		}
+
+		let claimed_sums = composite_claims
+			.iter()
+			.map(|composite_claim| composite_claim.sum)
+			.collect();
+		let state = ProverState::new(
+			multilinears,
+			claimed_sums,
+			switchover_fn,
+			FoldDirection::LowToHigh,
+			backend,
+		)?;
+		let n_vars = state.n_vars();
+		if indicator.n_vars() != n_vars {
+			bail!(Error::NumberOfVariablesMismatch);
+		}
+
+		let domains = composite_claims
+			.iter()
+			.map(|composite_claim| {
+				let degree = composite_claim.composition.degree();
+				evaluation_domain_factory.create(degree + 1)
+			})
+			.collect::<Result<_, _>>()?;
+
+		let compositions = composite_claims
+			.into_iter()
+			.map(|claim| claim.composition)
+			.collect();
+
+		Ok(Self {
+			n_vars,
+			state,
+			indicator,
+			indicator_eval: F::ONE,
+			compositions,
+			domains,
+		})
+	}
+
+	fn round(&self) -> usize {
+		self.n_vars - self.state.n_vars()
+	}
+}
+
+impl<F, FDomain, P, Composition, M, Backend> SumcheckProver<F>
+	for CosetSumcheckProver<FDomain, P, Composition, M, Backend>
+where
+	F: Field + ExtensionField<FDomain>,
+	FDomain: Field,
+	P: PackedField<Scalar = F> + PackedExtension<FDomain>,
+	Composition: CompositionPoly<P>,
+	M: MultilinearPoly<P> + Send + Sync,
+	Backend: ComputeBackend,
+{
+	fn n_vars(&self) -> usize {
+		self.n_vars
+	}
+
+	fn fold(&mut self, challenge: F) -> Result<(), Error> {
+		match self.indicator.fixed_value(self.round()) {
+			Some(true) => self.indicator_eval *= challenge,
+			Some(false) => self.indicator_eval *= F::ONE - challenge,
+			None => {}
+		}
+		self.state.fold(challenge)?;
+		Ok(())
+	}
+
+	fn execute(&mut self, batch_coeff: F) -> Result<RoundCoeffs<F>, Error> {
+		let round = self.round();
+		let fixed_value = self.indicator.fixed_value(round);
+
+		// The subcube of the variables after the current one, which index the vertices.
+		let vertex_fixed_mask = self.indicator.fixed_mask() >> (round + 1);
+		let vertex_fixed_values = self.indicator.fixed_values() >> (round + 1);
+		let n_vertices = 1 << (self.state.n_vars() - 1);
+
+		let evaluators = izip!(&self.compositions, &self.domains)
+			.map(|(composition, evaluation_domain)| CosetSumcheckEvaluator {
+				composition,
+				evaluation_domain,
+				domain_points: evaluation_domain.points(),
+				fixed_value,
+				vertex_fixed_mask,
+				vertex_fixed_values,
+				n_vertices,
+			})
+			.collect::<Vec<_>>();
+		let mut coeffs = self
+			.state
+			.calculate_round_coeffs(&evaluators, batch_coeff)?;
+
+		// Multiply by the indicator factor of the current variable, which is $X$ or $1 - X$ if it
+		// is fixed and $1$ otherwise. The round polynomial must have the degree of the reduced
+		// claim, one more than the composition degree, so it is padded with a zero coefficient
+		// for free variables.
+		let max_degree = self
+			.compositions
+			.iter()
+			.map(|composition| composition.degree())
+			.max()
+			.unwrap_or(0);
+		match fixed_value {
+			Some(true) => coeffs.0.insert(0, F::ZERO),
+			Some(false) => {
+				let mut coeffs_scaled_by_linear_term = coeffs.clone() * -F::ONE;
+				coeffs_scaled_by_linear_term.0.insert(0, F::ZERO);
+				coeffs += &coeffs_scaled_by_linear_term;
+			}
+			None => {}
+		}
+		coeffs.0.resize(max_degree + 2, F::ZERO);
+
+		Ok(coeffs * self.indicator_eval)
+	}
+
+	fn scratch_bytes(&self) -> usize {
+		self.state.scratch_bytes()
+	}
+
+	fn set_memory_budget(&mut self, memory_budget: MemoryBudget) {
+		self.state.set_memory_budget(memory_budget);
+	}
+
+	fn finish(self) -> Result<Vec<F>, Error> {
+		let mut evals = self.state.finish()?;
+		evals.push(self.indicator_eval);
+		Ok(evals)
+	}
+
+	fn finish_boxed(self: Box<Self>) -> Result<Vec<F>, Error> {
+		(*self).finish()
+	}
+}
+
+struct CosetSumcheckEvaluator<'a, FDomain, Composition>
+where
+	FDomain: Field,
+{
+	composition: &'a Composition,
+	evaluation_domain: &'a EvaluationDomain<FDomain>,
+	domain_points: &'a [FDomain],
+	/// The value the current variable is fixed to, or `None` if it is free.
+	fixed_value: Option<bool>,
+	vertex_fixed_mask: usize,
+	vertex_fixed_values: usize,
+	n_vertices: usize,
+}
+
+impl<'a, FDomain, Composition> CosetSumcheckEvaluator<'a, FDomain, Composition>
+where
+	FDomain: Field,
+{
+	/// Returns the subcube indicator over the packed vertices at index `i`, `None` if all of them
+	/// lie outside the subcube, or one if all of them lie inside.
+	fn vertex_mask<P: PackedField>(&self, i: usize) -> Option<P> {
+		let begin = i << P::LOG_WIDTH;
+		let high_mask = self.vertex_fixed_mask & !(P::WIDTH - 1);
+		if begin & high_mask != self.vertex_fixed_values & high_mask {
+			return None;
+		}
+		if self.vertex_fixed_mask & (P::WIDTH - 1) == 0 && begin + P::WIDTH <= self.n_vertices {
+			return Some(P::one());
+		}
+		Some(P::from_fn(|k| {
+			let vertex = begin + k;
+			if vertex < self.n_vertices
+				&& vertex & self.vertex_fixed_mask == self.vertex_fixed_values
+			{
+				P::Scalar::ONE
+			} else {
+				P::Scalar::ZERO
+			}
+		}))
+	}
+}
+
+impl<'a, F, P, FDomain, Composition> SumcheckEvaluator<P>
+	for CosetSumcheckEvaluator<'a, FDomain, Composition>
+where
+	F: Field + ExtensionField<FDomain>,
+	P: PackedField<Scalar = F> + PackedExtension<FDomain>,
+	FDomain: Field,
+	Composition: CompositionPoly<P>,
+{
+	fn degree(&self) -> usize {
+		self.composition.degree()
+	}
+
+	fn n_round_evals(&self) -> usize {
+		// We skip evaluation of $r(X)$ at the one point in $\{0, 1\}$ that is derivable from the
+		// current round's claimed sum.
+		self.composition.degree()
+	}
+
+	fn process_vertex(
+		&self,
+		i: usize,
+		evals_0: &[P],
+		evals_1: &[P],
+		evals_z: &mut [P],
+		round_evals: &mut [P],
+	) {
+		let Some(vertex_mask) = self.vertex_mask::<P>(i) else {
+			return;
+		};
+
+		// When the current variable is fixed to 1, the evaluation at 1 is the claimed sum and the
+		// evaluation at 0 is computed instead.
+		let evals_boolean = if self.fixed_value == Some(true) {
+			evals_0
+		} else {
+			evals_1
+		};
+		round_evals[0] += self
+			.composition
+			.evaluate(evals_boolean)
+			.expect("evals_boolean is initialized with a length of poly.composition.n_vars()")
+			* vertex_mask;
+
+		// The rest require interpolation.
+		for d in 2..=self.composition.degree() {
+			evals_0
+				.iter()
+				.zip(evals_1.iter())
+				.zip(evals_z.iter_mut())
+				.for_each(|((&evals_0_j, &evals_1_j), evals_z_j)| {
+					*evals_z_j = extrapolate_line(evals_0_j, evals_1_j, self.domain_points[d]);
+				});
+
+			round_evals[d - 1] += self
+				.composition
+				.evaluate(evals_z)
+				.expect("evals_z is initialized with a length of poly.composition.n_vars()")
+				* vertex_mask;
+		}
+	}
+
+	fn round_evals_to_coeffs(
+		&self,
+		last_round_sum: F,
+		mut round_evals: Vec<F>,
+	) -> Result<Vec<F>, PolynomialError> {
+		// Letting $s$ be the current round's claimed sum and $h$ the sum of the composite over the
+		// remaining subcube, $s = h(0) + h(1)$ if the current variable is free and $s = h(b)$ if
+		// it is fixed to $b$.
+		match self.fixed_value {
+			None => round_evals.insert(0, last_round_sum - round_evals[0]),
+			Some(false) => round_evals.insert(0, last_round_sum),
+			Some(true) => round_evals.insert(1, last_round_sum),
+		}
+
+		let round_evals = RoundEvals(round_evals);
+		Ok(round_evals.to_coeffs(self.evaluation_domain)?.0)
+	}
+}
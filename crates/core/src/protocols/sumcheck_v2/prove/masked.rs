@@ -0,0 +1,382 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use crate::{
+	polynomial::{
+		extrapolate_line, transparent::step_down::StepDown, CompositionPoly,
+		Error as PolynomialError, EvaluationDomain, EvaluationDomainFactory, MultilinearComposite,
+		MultilinearPoly,
+	},
+	protocols::sumcheck_v2::{
+		common::{CompositeSumClaim, FoldDirection, RoundCoeffs, RoundEvals},
+		error::Error,
+		prove::{
+			memory::MemoryBudget,
+			prover_state::{ProverState, SumcheckEvaluator},
+			SumcheckProver,
+		},
+	},
+};
+use binius_field::{ExtensionField, Field, PackedExtension, PackedField};
+use binius_utils::bail;
+use itertools::izip;
+use rayon::prelude::*;
+
+pub fn validate_witness<F, P, M, Composition>(
+	multilinears: &[M],
+	n_points: usize,
+	sum_claims: impl IntoIterator<Item = CompositeSumClaim<F, Composition>>,
+) -> Result<(), Error>
+where
+	F: Field,
+	P: PackedField<Scalar = F>,
+	M: MultilinearPoly<P> + Send + Sync,
+	Composition: CompositionPoly<P>,
+{
+	let n_vars = multilinears
+		.first()
+		.map(|multilinear| multilinear.n_vars())
+		.unwrap_or(0);
+	for multilinear in multilinears.iter() {
+		if multilinear.n_vars() != n_vars {
+			bail!(Error::NumberOfVariablesMismatch);
+		}
+	}
+	StepDown::new(n_vars, n_points)?;
+
+	let multilinears = multilinears.iter().collect::<Vec<_>>();
+
+	for (i, claim) in sum_claims.into_iter().enumerate() {
+		let CompositeSumClaim {
+			composition,
+			sum: expected_sum,
+		} = claim;
+		let witness = MultilinearComposite::new(n_vars, composition, multilinears.clone())?;
+		let sum = (0..n_points)
+			.into_par_iter()
+			.map(|j| witness.evaluate_on_hypercube(j))
+			.try_reduce(|| F::ZERO, |a, b| Ok(a + b))?;
+
+		if sum != expected_sum {
+			bail!(Error::SumcheckNaiveValidationFailure {
+				composition_index: i,
+			});
+		}
+	}
+	Ok(())
+}
+
+/// A prover for sums of multilinear composites over a prefix of the hypercube.
+///
+/// The prover produces the same transcript as a [`super::RegularSumcheckProver`] for the
+/// composites multiplied by the [`StepDown`] indicator of the prefix, without materializing the
+/// indicator. After folding the first variables, the folded indicator is one on the vertices
+/// before the prefix length shifted down by the number of rounds, zero after it, and takes a
+/// single value at it, which the prover updates with every challenge. In each round the prover
+/// evaluates the composites only over the vertices up to that boundary, multiplying the boundary
+/// vertex by the linear factor of the indicator.
+#[derive(Debug)]
+pub struct MaskedSumcheckProver<FDomain, P, Composition, M, Backend = CpuBackend>
+where
+	FDomain: Field,
+	P: PackedField,
+	M: MultilinearPoly<P> + Send + Sync,
+	Backend: ComputeBackend,
+{
+	n_vars: usize,
+	state: ProverState<P, M, Backend>,
+	n_points: usize,
+	/// The value of the folded indicator at the vertex `n_points >> round`.
+	boundary_eval: P::Scalar,
+	compositions: Vec<Composition>,
+	domains: Vec<EvaluationDomain<FDomain>>,
+}
+
+impl<F, FDomain, P, Composition, M> MaskedSumcheckProver<FDomain, P, Composition, M>
+where
+	F: Field + ExtensionField<FDomain>,
+	FDomain: Field,
+	P: PackedField<Scalar = F>,
+	Composition: CompositionPoly<P>,
+	M: MultilinearPoly<P> + Send + Sync,
+{
+	pub fn new(
+		multilinears: Vec<M>,
+		n_points: usize,
+		composite_claims: impl IntoIterator<Item = CompositeSumClaim<F, Composition>>,
+		evaluation_domain_factory: impl EvaluationDomainFactory<FDomain>,
+		switchover_fn: impl Fn(usize) -> usize,
+	) -> Result<Self, Error> {
+		Self::new_with_backend(
+			multilinears,
+			n_points,
+			composite_claims,
+			evaluation_domain_factory,
+			switchover_fn,
+			CpuBackend,
+		)
+	}
+}
+
+impl<F, FDomain, P, Composition, M, Backend>
+	MaskedSumcheckProver<FDomain, P, Composition, M, Backend>
+where
+	F: Field + ExtensionField<FDomain>,
+	FDomain: Field,
+	P: PackedField<Scalar = F>,
+	Composition: CompositionPoly<P>,
+	M: MultilinearPoly<P> + Send + Sync,
+	Backend: ComputeBackend,
+{
+	/// Constructs a prover that runs its hypercube loops on the given backend.
+	pub fn new_with_backend(
+		multilinears: Vec<M>,
+		n_points: usize,
+		composite_claims: impl IntoIterator<Item = CompositeSumClaim<F, Composition>>,
+		evaluation_domain_factory: impl EvaluationDomainFactory<FDomain>,
+		switchover_fn: impl Fn(usize) -> usize,
+		backend: Backend,
+	) -> Result<Self, Error> {
+		let composite_claims = composite_claims.into_iter().collect::<Vec<_>>();
+		for claim in composite_claims.iter() {
+			if claim.composition.n_vars() != multilinears.len() {
+				bail!(Error::InvalidComposition {
+					expected_n_vars: multilinears.len(),
+				});
+			}
+		}
+
+		let claimed_sums = composite_claims
+			.iter()
+			.map(|composite_claim| composite_claim.sum)
+			.collect();
+		let state = ProverState::new(
+			multilinears,
+			claimed_sums,
+			switchover_fn,
+			FoldDirection::LowToHigh,
+			backend,
+		)?;
+		let n_vars = state.n_vars();
+		StepDown::new(n_vars, n_points)?;
+
+		// The round polynomials have the degree of the reduced claim, one more than the
+		// composition degree.
+		let domains = composite_claims
+			.iter()
+			.map(|composite_claim| {
+				let degree = composite_claim.composition.degree();
+				evaluation_domain_factory.create(degree + 2)
+			})
+			.collect::<Result<_, _>>()?;
+
+		let compositions = composite_claims
+			.into_iter()
+			.map(|claim| claim.composition)
+			.collect();
+
+		Ok(Self {
+			n_vars,
+			state,
+			n_points,
+			// The indicator is zero at the vertex n_points.
+			boundary_eval: F::ZERO,
+			compositions,
+			domains,
+		})
+	}
+
+	fn round(&self) -> usize {
+		self.n_vars - self.state.n_vars()
+	}
+}
+
+impl<F, FDomain, P, Composition, M, Backend> SumcheckProver<F>
+	for MaskedSumcheckProver<FDomain, P, Composition, M, Backend>
+where
+	F: Field + ExtensionField<FDomain>,
+	FDomain: Field,
+	P: PackedField<Scalar = F> + PackedExtension<FDomain>,
+	Composition: CompositionPoly<P>,
+	M: MultilinearPoly<P> + Send + Sync,
+	Backend: ComputeBackend,
+{
+	fn n_vars(&self) -> usize {
+		self.n_vars
+	}
+
+	fn fold(&mut self, challenge: F) -> Result<(), Error> {
+		let boundary = self.n_points >> self.round();
+		self.state.fold(challenge)?;
+
+		// The boundary vertex of the next round pairs the vertices 2b and 2b + 1 of this round.
+		// If the boundary is even, these are the boundary and a vertex past it, and otherwise
+		// a vertex before it and the boundary.
+		self.boundary_eval = if boundary & 1 == 0 {
+			(F::ONE - challenge) * self.boundary_eval
+		} else {
+			F::ONE - challenge + challenge * self.boundary_eval
+		};
+		Ok(())
+	}
+
+	fn execute(&mut self, batch_coeff: F) -> Result<RoundCoeffs<F>, Error> {
+		let boundary = self.n_points >> self.round();
+		let boundary_factor = if boundary & 1 == 0 {
+			(self.boundary_eval, F::ZERO)
+		} else {
+			(F::ONE, self.boundary_eval)
+		};
+		let n_vertices = 1 << (self.state.n_vars() - 1);
+
+		let evaluators = izip!(&self.compositions, &self.domains)
+			.map(|(composition, evaluation_domain)| MaskedSumcheckEvaluator {
+				composition,
+				evaluation_domain,
+				domain_points: evaluation_domain.points(),
+				boundary_vertex: boundary >> 1,
+				boundary_factor,
+				n_vertices,
+			})
+			.collect::<Vec<_>>();
+		self.state.calculate_round_coeffs(&evaluators, batch_coeff)
+	}
+
+	fn scratch_bytes(&self) -> usize {
+		self.state.scratch_bytes()
+	}
+
+	fn set_memory_budget(&mut self, memory_budget: MemoryBudget) {
+		self.state.set_memory_budget(memory_budget);
+	}
+
+	fn finish(self) -> Result<Vec<F>, Error> {
+		let indicator_eval = if self.n_points == 1 << self.n_vars {
+			F::ONE
+		} else {
+			self.boundary_eval
+		};
+		let mut evals = self.state.finish()?;
+		evals.push(indicator_eval);
+		Ok(evals)
+	}
+
+	fn finish_boxed(self: Box<Self>) -> Result<Vec<F>, Error> {
+		(*self).finish()
+	}
+}
+
+struct MaskedSumcheckEvaluator<'a, F, FDomain, Composition>
+where
+	F: Field,
+	FDomain: Field,
+{
+	composition: &'a Composition,
+	evaluation_domain: &'a EvaluationDomain<FDomain>,
+	domain_points: &'a [FDomain],
+	/// The vertex pairing the boundary of the folded indicator with one of its neighbours.
+	boundary_vertex: usize,
+	/// The values of the indicator at the boundary vertex when the current variable is 0 and 1.
+	boundary_factor: (F, F),
+	n_vertices: usize,
+}
+
+impl<'a, F, FDomain, Composition> MaskedSumcheckEvaluator<'a, F, FDomain, Composition>
+where
+	F: Field + ExtensionField<FDomain>,
+	FDomain: Field,
+{
+	/// Returns the indicator over the packed vertices at index `i` with the current variable at
+	/// `z`, `None` if all of them lie past the prefix, or one if all of them lie inside.
+	fn vertex_mask<P: PackedField<Scalar = F>>(&self, i: usize, z: F) -> Option<P> {
+		let begin = i << P::LOG_WIDTH;
+		if begin > self.boundary_vertex || begin >= self.n_vertices {
+			return None;
+		}
+		if begin + P::WIDTH <= self.boundary_vertex.min(self.n_vertices) {
+			return Some(P::one());
+		}
+		let (eval_0, eval_1) = self.boundary_factor;
+		let boundary_eval = eval_0 + (eval_1 - eval_0) * z;
+		Some(P::from_fn(|k| {
+			let vertex = begin + k;
+			if vertex >= self.n_vertices || vertex > self.boundary_vertex {
+				F::ZERO
+			} else if vertex == self.boundary_vertex {
+				boundary_eval
+			} else {
+				F::ONE
+			}
+		}))
+	}
+}
+
+impl<'a, F, P, FDomain, Composition> SumcheckEvaluator<P>
+	for MaskedSumcheckEvaluator<'a, F, FDomain, Composition>
+where
+	F: Field + ExtensionField<FDomain>,
+	P: PackedField<Scalar = F> + PackedExtension<FDomain>,
+	FDomain: Field,
+	Composition: CompositionPoly<P>,
+{
+	fn degree(&self) -> usize {
+		// The indicator is multilinear, so it raises the degree of the composite by one.
+		self.composition.degree() + 1
+	}
+
+	fn n_round_evals(&self) -> usize {
+		// The round polynomial has degree one more than the composition, and we skip evaluation
+		// of $r(0)$, which is derivable from the current round's claimed sum.
+		self.composition.degree() + 1
+	}
+
+	fn process_vertex(
+		&self,
+		i: usize,
+		evals_0: &[P],
+		evals_1: &[P],
+		evals_z: &mut [P],
+		round_evals: &mut [P],
+	) {
+		let Some(mask_1) = self.vertex_mask::<P>(i, F::ONE) else {
+			return;
+		};
+		round_evals[0] += self
+			.composition
+			.evaluate(evals_1)
+			.expect("evals_1 is initialized with a length of poly.composition.n_vars()")
+			* mask_1;
+
+		// The rest require interpolation.
+		for d in 2..=self.composition.degree() + 1 {
+			evals_0
+				.iter()
+				.zip(evals_1.iter())
+				.zip(evals_z.iter_mut())
+				.for_each(|((&evals_0_j, &evals_1_j), evals_z_j)| {
+					*evals_z_j = extrapolate_line(evals_0_j, evals_1_j, self.domain_points[d]);
+				});
+
+			let mask_z = self
+				.vertex_mask::<P>(i, self.domain_points[d].into())
+				.expect("the vertex is inside the prefix, as checked above");
+			round_evals[d - 1] += self
+				.composition
+				.evaluate(evals_z)
+				.expect("evals_z is initialized with a length of poly.composition.n_vars()")
+				* mask_z;
+		}
+	}
+
+	fn round_evals_to_coeffs(
+		&self,
+		last_round_sum: F,
+		mut round_evals: Vec<F>,
+	) -> Result<Vec<F>, PolynomialError> {
+		// Given $r(1), \ldots, r(d+1)$, letting $s$ be the current round's claimed sum,
+		// we can compute $r(0)$ using the identity $r(0) = s - r(1)$
+		round_evals.insert(0, last_round_sum - round_evals[0]);
+
+		let round_evals = RoundEvals(round_evals);
+		Ok(round_evals.to_coeffs(self.evaluation_domain)?.0)
+	}
+}
@@ -0,0 +1,139 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use std::{
+	any::Any,
+	sync::{
+		atomic::{AtomicUsize, Ordering},
+		Arc, Mutex, MutexGuard,
+	},
+};
+
+/// The largest number of buffers a [`ScratchArena`] keeps for reuse.
+const MAX_SCRATCH_BUFFERS: usize = 256;
+
+/// A cap on the scratch memory the sumcheck provers allocate in their hypercube loops.
+///
+/// The scratch memory of a round holds the multilinear evaluations sampled at a batch of vertices
+/// and the round evaluations accumulated for each composite, once per parallel task. Under a
+/// budget, the provers run fewer tasks over larger chunks of the hypercube and with smaller
+/// batches of vertices, reusing the buffers of a task across its whole chunk. A budget smaller
+/// than the scratch memory of a single vertex cannot be met, and the provers then process one
+/// vertex at a time on a single task.
+///
+/// Clones of a budget share the peak usage they record, so the provers of a batch can be given
+/// clones of one budget and report the peak of the whole batch through it. Clones also share a
+/// [`ScratchArena`], through which the scratch buffers of a round are reused by the next rounds
+/// and by the other provers run under the budget.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryBudget {
+	max_scratch_bytes: Option<usize>,
+	peak_scratch_bytes: Arc<AtomicUsize>,
+	scratch_arena: ScratchArena,
+}
+
+impl MemoryBudget {
+	/// A budget of at most `max_scratch_bytes` bytes of scratch memory at once.
+	pub fn new(max_scratch_bytes: usize) -> Self {
+		Self {
+			max_scratch_bytes: Some(max_scratch_bytes),
+			peak_scratch_bytes: Arc::default(),
+			scratch_arena: ScratchArena::default(),
+		}
+	}
+
+	/// A budget without a cap, which only records the peak usage.
+	pub fn unlimited() -> Self {
+		Self::default()
+	}
+
+	pub fn max_scratch_bytes(&self) -> Option<usize> {
+		self.max_scratch_bytes
+	}
+
+	/// The largest amount of scratch memory, in bytes, held at once by the loops run under this
+	/// budget or its clones.
+	pub fn peak_scratch_bytes(&self) -> usize {
+		self.peak_scratch_bytes.load(Ordering::Relaxed)
+	}
+
+	/// Records that a loop held `scratch_bytes` bytes of scratch memory at once.
+	pub fn record_scratch_bytes(&self, scratch_bytes: usize) {
+		self.peak_scratch_bytes
+			.fetch_max(scratch_bytes, Ordering::Relaxed);
+	}
+
+	/// The arena the hypercube loops run under this budget take their scratch buffers from.
+	pub fn scratch_arena(&self) -> &ScratchArena {
+		&self.scratch_arena
+	}
+
+	/// Replaces the scratch arena of the budget, so that the buffers of `scratch_arena` are reused.
+	pub fn with_scratch_arena(mut self, scratch_arena: ScratchArena) -> Self {
+		self.scratch_arena = scratch_arena;
+		self
+	}
+}
+
+/// A pool of scratch buffers that outlive the round that allocated them.
+///
+/// A hypercube loop takes its buffers from the arena and returns them when the round is done, so
+/// that the following rounds, and the following proofs of a long-running prover, skip the
+/// allocation and zeroing of buffers of the same shape. Buffers of any type are kept, and clones
+/// of an arena share its buffers. The arena keeps at most a fixed number of buffers and drops the
+/// buffers returned beyond it.
+#[derive(Debug, Clone, Default)]
+pub struct ScratchArena {
+	buffers: Arc<Mutex<Vec<Box<dyn Any + Send>>>>,
+}
+
+impl ScratchArena {
+	/// Takes a buffer of type `T` for which `matches` holds, if the arena has one.
+	pub fn take<T: Any + Send>(&self, matches: impl Fn(&T) -> bool) -> Option<T> {
+		let mut buffers = self.lock();
+		let position = buffers
+			.iter()
+			.position(|buffer| buffer.downcast_ref::<T>().is_some_and(&matches))?;
+		let buffer = buffers
+			.swap_remove(position)
+			.downcast()
+			.expect("the buffer has type T");
+		Some(*buffer)
+	}
+
+	/// Drops the buffers of type `T` for which `keep` does not hold.
+	pub fn retain<T: Any + Send>(&self, keep: impl Fn(&T) -> bool) {
+		self.lock()
+			.retain(|buffer| match buffer.downcast_ref::<T>() {
+				Some(buffer) => keep(buffer),
+				None => true,
+			});
+	}
+
+	/// Returns a buffer to the arena for reuse.
+	pub fn put<T: Any + Send>(&self, buffer: T) {
+		let mut buffers = self.lock();
+		if buffers.len() < MAX_SCRATCH_BUFFERS {
+			buffers.push(Box::new(buffer));
+		}
+	}
+
+	/// The number of buffers held by the arena.
+	pub fn len(&self) -> usize {
+		self.lock().len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Drops all the buffers held by the arena.
+	pub fn clear(&self) {
+		self.lock().clear();
+	}
+
+	fn lock(&self) -> MutexGuard<'_, Vec<Box<dyn Any + Send>>> {
+		self.buffers
+			.lock()
+			.expect("scratch arena lock is not poisoned")
+	}
+}
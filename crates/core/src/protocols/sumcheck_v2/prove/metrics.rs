@@ -0,0 +1,52 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use std::time::Duration;
+
+/// Profiling measurements of one round of a batched sumcheck proof.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RoundMetrics {
+	/// The number of provers active in the round.
+	pub n_active_provers: usize,
+	/// The time spent computing the round polynomials of the active provers.
+	pub execute_duration: Duration,
+	/// The time spent folding the active provers with the round challenge.
+	pub fold_duration: Duration,
+	/// The scratch memory in bytes reported by the active provers for the round, see
+	/// [`super::SumcheckProver::scratch_bytes`].
+	///
+	/// This is the sum over the provers, which bounds the scratch memory held at once when the
+	/// provers are executed in parallel.
+	pub scratch_bytes: usize,
+}
+
+/// Profiling measurements of a batched sumcheck proof, returned by
+/// [`super::batch_prove_with_metrics`].
+///
+/// The same measurements are emitted as `tracing` events at the debug level, so that they can be
+/// collected by a subscriber instead.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SumcheckMetrics {
+	/// The measurements of each round, in order.
+	pub rounds: Vec<RoundMetrics>,
+}
+
+impl SumcheckMetrics {
+	/// The largest scratch memory in bytes of any round.
+	pub fn peak_scratch_bytes(&self) -> usize {
+		self.rounds
+			.iter()
+			.map(|round| round.scratch_bytes)
+			.max()
+			.unwrap_or(0)
+	}
+
+	/// The total time spent computing round polynomials.
+	pub fn execute_duration(&self) -> Duration {
+		self.rounds.iter().map(|round| round.execute_duration).sum()
+	}
+
+	/// The total time spent folding the provers.
+	pub fn fold_duration(&self) -> Duration {
+		self.rounds.iter().map(|round| round.fold_duration).sum()
+	}
+}
@@ -1,10 +1,43 @@
 // Copyright 2024 Ulvetanna Inc.
 
+mod backend;
 mod batch_prove;
+mod checkpoint;
+pub mod coset;
+pub mod masked;
+mod memory;
+mod metrics;
 mod prover_state;
 pub mod regular_sumcheck;
+mod remote;
+mod shared;
+mod streaming;
+mod window;
 pub mod zerocheck;
+mod zk;
 
-pub use batch_prove::{batch_prove, SumcheckProver};
+pub use backend::{ComputeBackend, CpuBackend};
+#[cfg(feature = "parallel_batch_prove")]
+pub use batch_prove::batch_prove_parallel;
+pub(crate) use batch_prove::execute_provers;
+pub use batch_prove::{
+	batch_prove, batch_prove_dyn, batch_prove_labeled, batch_prove_split, batch_prove_unsorted,
+	batch_prove_with_features, batch_prove_with_memory_budget, batch_prove_with_metrics,
+	prove_single, SumcheckProver,
+};
+pub use checkpoint::{
+	batch_prove_with_checkpoints, resume_batch_prove, BatchProveCheckpoint, MultilinearSnapshot,
+	ProverSnapshot, ResumableProver,
+};
+pub use coset::CosetSumcheckProver;
+pub use masked::MaskedSumcheckProver;
+pub use memory::{MemoryBudget, ScratchArena};
+pub use metrics::{RoundMetrics, SumcheckMetrics};
+pub use prover_state::SumcheckEvaluator;
 pub use regular_sumcheck::RegularSumcheckProver;
-pub use zerocheck::ZerocheckProver;
+pub use remote::{RemoteError, RemoteRoundProver, RemoteSumcheckProver};
+pub use shared::SharedSumcheckProver;
+pub use streaming::{ReadSource, StreamingSource, StreamingSumcheckProver};
+pub use window::{windowed_sum_prover, windowed_sum_witness};
+pub use zerocheck::{EqIndStorage, ZerocheckProver};
+pub use zk::{prove_zk, ZkSumcheckProver};
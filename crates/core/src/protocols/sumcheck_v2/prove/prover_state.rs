@@ -1,18 +1,25 @@
 // Copyright 2024 Ulvetanna Inc.
 
+use super::{
+	backend::ComputeBackend,
+	checkpoint::{MultilinearSnapshot, ProverSnapshot},
+	memory::MemoryBudget,
+};
 use crate::{
 	polynomial::{
-		evaluate_univariate, Error as PolynomialError, MultilinearExtensionSpecialized,
-		MultilinearPoly, MultilinearQuery,
+		evaluate_univariate, Error as PolynomialError, MultilinearExtension,
+		MultilinearExtensionSpecialized, MultilinearPoly, MultilinearQuery,
 	},
 	protocols::{
-		sumcheck_v2::{common::RoundCoeffs, error::Error},
+		sumcheck_v2::{
+			common::{FoldDirection, RoundCoeffs},
+			error::Error,
+		},
 		utils::packed_from_fn_with_offset,
 	},
 };
-use binius_field::{util::powers, Field, PackedField};
+use binius_field::{packed::iter_packed_slice, util::powers, Field, PackedField};
 use binius_utils::{array_2d::Array2D, bail};
-use bytemuck::zeroed_vec;
 use getset::CopyGetters;
 use itertools::izip;
 use rayon::prelude::*;
@@ -35,9 +42,15 @@ where
 	Folded {
 		large_field_folded_multilinear: MultilinearExtensionSpecialized<P, P>,
 	},
+	/// Polynomial that is constant over the hypercube, such as an all-zero selector column.
+	/// Folding leaves it unchanged, so it is never folded and its round evaluations are the value.
+	Constant { value: P::Scalar },
 }
 
 pub trait SumcheckEvaluator<P: PackedField> {
+	/// The declared degree of the composite, which is the degree of its round polynomial.
+	fn degree(&self) -> usize;
+
 	/// The number of points to evaluate at.
 	fn n_round_evals(&self) -> usize;
 
@@ -72,38 +85,6 @@ pub trait SumcheckEvaluator<P: PackedField> {
 	) -> Result<Vec<P::Scalar>, PolynomialError>;
 }
 
-/// Parallel fold state, consisting of scratch area and result accumulator.
-#[derive(Debug)]
-struct ParFoldStates<P: PackedField> {
-	// Evaluations at 0, 1 and domain points, per MLE. Scratch space.
-	evals_0: Array2D<P>,
-	evals_1: Array2D<P>,
-	evals_z: Array2D<P>,
-
-	/// Accumulated sums of evaluations over univariate domain.
-	///
-	/// Each element of the outer vector corresponds to one composite polynomial. Each element of
-	/// an inner vector contains the evaluations at different points.
-	round_evals: Vec<Vec<P>>,
-}
-
-impl<P: PackedField> ParFoldStates<P> {
-	fn new(
-		n_multilinears: usize,
-		n_round_evals: impl Iterator<Item = usize>,
-		n_states: usize,
-	) -> Self {
-		Self {
-			evals_0: Array2D::zeroes(n_states, n_multilinears),
-			evals_1: Array2D::zeroes(n_states, n_multilinears),
-			evals_z: Array2D::zeroes(n_states, n_multilinears),
-			round_evals: n_round_evals
-				.map(|n_round_evals| zeroed_vec(n_round_evals))
-				.collect(),
-		}
-	}
-}
-
 #[derive(Debug)]
 enum ProverStateCoeffsOrSums<F: Field> {
 	Coeffs(Vec<RoundCoeffs<F>>),
@@ -114,32 +95,48 @@ enum ProverStateCoeffsOrSums<F: Field> {
 ///
 /// We expect that CPU sumcheck provers will internally maintain a [`ProverState`] instance and
 /// customize the sumcheck logic through different [`SumcheckEvaluator`] implementations passed to
-/// the common state object.
+/// the common state object. The hypercube loops are run by the [`ComputeBackend`].
 #[derive(Debug, CopyGetters)]
-pub struct ProverState<P, M>
+pub struct ProverState<P, M, Backend>
 where
 	P: PackedField,
 	M: MultilinearPoly<P> + Send + Sync,
+	Backend: ComputeBackend,
 {
 	/// The number of variables in the folded multilinears. This value decrements each round the
 	/// state is folded.
 	#[getset(get_copy = "pub")]
 	n_vars: usize,
+	/// The order in which the variables are folded.
+	#[getset(get_copy = "pub")]
+	fold_direction: FoldDirection,
 	multilinears: Vec<SumcheckMultilinear<P, M>>,
+	/// The tensor product expansion of the challenges the transparent multilinears have been
+	/// folded with, ordered by the variables they bind.
 	tensor_query: Option<MultilinearQuery<P>>,
 	last_coeffs_or_sums: ProverStateCoeffsOrSums<P::Scalar>,
+	/// The challenges the state has been folded with, kept to take snapshots.
+	challenges: Vec<P::Scalar>,
+	/// The scratch memory in bytes held at once by the hypercube loop of the last round.
+	#[getset(get_copy = "pub")]
+	scratch_bytes: usize,
+	backend: Backend,
+	memory_budget: MemoryBudget,
 }
 
-impl<F, P, M> ProverState<P, M>
+impl<F, P, M, Backend> ProverState<P, M, Backend>
 where
 	F: Field,
 	P: PackedField<Scalar = F>,
 	M: MultilinearPoly<P> + Send + Sync,
+	Backend: ComputeBackend,
 {
 	pub fn new(
 		multilinears: Vec<M>,
 		claimed_sums: Vec<F>,
 		switchover_fn: impl Fn(usize) -> usize,
+		fold_direction: FoldDirection,
+		backend: Backend,
 	) -> Result<Self, Error> {
 		let n_vars = multilinears
 			.first()
@@ -151,26 +148,173 @@ where
 			}
 		}
 
-		let switchover_rounds = multilinears
-			.iter()
-			.map(|multilinear| switchover_fn(multilinear.extension_degree()))
+		// Witnesses from a witness index compute their constant value once, see
+		// `ConstantValueCached`, so constructing several provers over them does not rescan them.
+		let multilinears = multilinears
+			.into_iter()
+			.map(|multilinear| match multilinear.constant_value() {
+				Some(value) => SumcheckMultilinear::Constant { value },
+				None => SumcheckMultilinear::Transparent {
+					switchover_round: switchover_fn(multilinear.extension_degree()),
+					multilinear,
+				},
+			})
 			.collect::<Vec<_>>();
-		let max_switchover_round = switchover_rounds.iter().copied().max().unwrap_or_default();
-
-		let multilinears = iter::zip(multilinears, switchover_rounds)
-			.map(|(multilinear, switchover_round)| SumcheckMultilinear::Transparent {
-				multilinear,
-				switchover_round,
+		let max_switchover_round = multilinears
+			.iter()
+			.filter_map(|multilinear| match multilinear {
+				SumcheckMultilinear::Transparent {
+					switchover_round, ..
+				} => Some(*switchover_round),
+				_ => None,
 			})
-			.collect();
+			.max()
+			.unwrap_or_default();
 
 		let tensor_query = MultilinearQuery::new(max_switchover_round)?;
 
 		Ok(Self {
 			n_vars,
+			fold_direction,
 			multilinears,
 			tensor_query: Some(tensor_query),
 			last_coeffs_or_sums: ProverStateCoeffsOrSums::Sums(claimed_sums),
+			challenges: Vec::new(),
+			scratch_bytes: 0,
+			backend,
+			memory_budget: MemoryBudget::default(),
+		})
+	}
+
+	/// Restores the state from a snapshot taken with [`Self::snapshot`], given the multilinears
+	/// the original state was constructed with.
+	///
+	/// The fold direction is not part of the snapshot and must be the one the original state was
+	/// constructed with.
+	pub fn restore(
+		multilinears: Vec<M>,
+		snapshot: ProverSnapshot<F>,
+		fold_direction: FoldDirection,
+		backend: Backend,
+	) -> Result<Self, Error> {
+		let ProverSnapshot {
+			challenges,
+			multilinears: multilinear_snapshots,
+			sums,
+		} = snapshot;
+
+		let n_vars = multilinears
+			.first()
+			.map(|multilin| multilin.n_vars())
+			.unwrap_or(0);
+		for multilinear in multilinears.iter() {
+			if multilinear.n_vars() != n_vars {
+				bail!(Error::NumberOfVariablesMismatch);
+			}
+		}
+		if multilinear_snapshots.len() != multilinears.len() || challenges.len() > n_vars {
+			bail!(Error::InvalidCheckpoint);
+		}
+		let n_vars_left = n_vars - challenges.len();
+
+		let mut max_switchover_round = None;
+		let multilinears = iter::zip(multilinears, multilinear_snapshots)
+			.map(|(multilinear, multilinear_snapshot)| match multilinear_snapshot {
+				MultilinearSnapshot::Transparent { switchover_round } => {
+					max_switchover_round = max_switchover_round.max(Some(switchover_round));
+					Ok(SumcheckMultilinear::Transparent {
+						multilinear,
+						switchover_round,
+					})
+				}
+				MultilinearSnapshot::Folded { evals } => {
+					if evals.len() != 1 << n_vars_left {
+						bail!(Error::InvalidCheckpoint);
+					}
+					if evals.iter().all(|&eval| eval == evals[0]) {
+						return Ok(SumcheckMultilinear::Constant { value: evals[0] });
+					}
+					// Folded multilinears with fewer evaluations than the packing width occupy a
+					// single packed element, as in `MultilinearExtension::evaluate_partial_low`.
+					let values = evals
+						.par_chunks(P::WIDTH)
+						.map(|chunk| P::from_fn(|i| chunk.get(i).copied().unwrap_or(F::ZERO)))
+						.collect();
+					Ok(SumcheckMultilinear::Folded {
+						large_field_folded_multilinear: MultilinearExtension::from_values(values)?
+							.specialize(),
+					})
+				}
+			})
+			.collect::<Result<_, Error>>()?;
+
+		// The tensor query is kept until the last transparent multilinear is folded.
+		let tensor_query = match max_switchover_round {
+			Some(max_switchover_round) => Some(max_switchover_round),
+			None if challenges.is_empty() => Some(0),
+			None => None,
+		}
+		.map(|max_switchover_round| {
+			let query = MultilinearQuery::new(challenges.len() + max_switchover_round)?;
+			match fold_direction {
+				FoldDirection::LowToHigh => query.update(&challenges),
+				FoldDirection::HighToLow => query.update_low(&challenges),
+			}
+		})
+		.transpose()?;
+
+		Ok(Self {
+			n_vars: n_vars_left,
+			fold_direction,
+			multilinears,
+			tensor_query,
+			last_coeffs_or_sums: ProverStateCoeffsOrSums::Sums(sums),
+			challenges,
+			scratch_bytes: 0,
+			backend,
+			memory_budget: MemoryBudget::default(),
+		})
+	}
+
+	/// Caps the scratch memory of the hypercube loops, see [`MemoryBudget`].
+	pub fn set_memory_budget(&mut self, memory_budget: MemoryBudget) {
+		self.memory_budget = memory_budget;
+	}
+
+	/// Takes a snapshot of the state between two rounds.
+	pub fn snapshot(&self) -> Result<ProverSnapshot<F>, Error> {
+		let ProverStateCoeffsOrSums::Sums(ref sums) = self.last_coeffs_or_sums else {
+			bail!(Error::ExpectedFold);
+		};
+
+		let multilinears = self
+			.multilinears
+			.iter()
+			.map(|multilinear| match multilinear {
+				SumcheckMultilinear::Transparent {
+					switchover_round, ..
+				} => MultilinearSnapshot::Transparent {
+					switchover_round: *switchover_round,
+				},
+				SumcheckMultilinear::Folded {
+					large_field_folded_multilinear,
+				} => {
+					let mut evals = large_field_folded_multilinear.as_ref().to_scalars();
+					evals.truncate(1 << self.n_vars);
+					MultilinearSnapshot::Folded { evals }
+				}
+				// Constant multilinears are snapshotted as folded ones, and detected again when
+				// the snapshot is restored.
+				SumcheckMultilinear::Constant { value } => MultilinearSnapshot::Folded {
+					evals: vec![*value; 1 << self.n_vars],
+				},
+			})
+			.collect();
+
+		Ok(ProverSnapshot {
+			challenges: self.challenges.clone(),
+			multilinears,
+			sums: sums.clone(),
 		})
 	}
 
@@ -193,14 +337,15 @@ where
 			}
 		}
 
-		// Update the tensor query.
+		// Update the tensor query. When folding high-to-low, the new challenge binds the lowest of
+		// the folded variables, so it is prepended to the expansion rather than appended.
 		if let Some(tensor_query) = self.tensor_query.take() {
-			self.tensor_query = Some(tensor_query.update(&[challenge])?);
+			self.tensor_query = Some(match self.fold_direction {
+				FoldDirection::LowToHigh => tensor_query.update(&[challenge])?,
+				FoldDirection::HighToLow => tensor_query.update_low(&[challenge])?,
+			});
 		}
 
-		// Partial query for folding
-		let single_variable_partial_query = MultilinearQuery::with_full_query(&[challenge])?;
-
 		let mut any_transparent_left = false;
 		for multilinear in self.multilinears.iter_mut() {
 			match multilinear {
@@ -218,8 +363,14 @@ where
 					if *switchover_round == 0 {
 						// At switchover, perform inner products in large field and save them in a
 						// newly created MLE.
-						let large_field_folded_multilinear =
-							inner_multilinear.evaluate_partial_low(tensor_query)?;
+						let large_field_folded_multilinear = match self.fold_direction {
+							FoldDirection::LowToHigh => {
+								inner_multilinear.evaluate_partial_low(tensor_query)?
+							}
+							FoldDirection::HighToLow => {
+								inner_multilinear.evaluate_partial_high(tensor_query)?
+							}
+						};
 
 						*multilinear = SumcheckMultilinear::Folded {
 							large_field_folded_multilinear,
@@ -232,9 +383,17 @@ where
 					ref mut large_field_folded_multilinear,
 				} => {
 					// Post-switchover, simply halve large field MLE.
-					*large_field_folded_multilinear = large_field_folded_multilinear
-						.evaluate_partial_low(&single_variable_partial_query)?;
+					*large_field_folded_multilinear = self
+						.backend
+						.fold_multilinear(
+							large_field_folded_multilinear.as_ref(),
+							self.n_vars,
+							challenge,
+							self.fold_direction,
+						)?
+						.specialize();
 				}
+				SumcheckMultilinear::Constant { .. } => {}
 			}
 		}
 
@@ -242,6 +401,7 @@ where
 			self.tensor_query = None;
 		}
 
+		self.challenges.push(challenge);
 		self.n_vars -= 1;
 		Ok(())
 	}
@@ -257,7 +417,6 @@ where
 			},
 		};
 
-		let empty_query = MultilinearQuery::new(0).expect("constructing an empty query");
 		self.multilinears
 			.into_iter()
 			.map(|multilinear| {
@@ -270,11 +429,12 @@ where
 							.expect(
 								"tensor_query is guaranteed to be Some while there is still a transparent multilinear"
 							);
-						inner_multilinear.evaluate(tensor_query)
+						inner_multilinear.inner_prod_with_expansion(tensor_query.expansion())
 					}
 					SumcheckMultilinear::Folded {
 						large_field_folded_multilinear,
-					} => large_field_folded_multilinear.evaluate(&empty_query),
+					} => large_field_folded_multilinear.evaluate_on_hypercube(0),
+					SumcheckMultilinear::Constant { value } => Ok(value),
 				};
 				result.map_err(Error::Polynomial)
 			})
@@ -286,6 +446,22 @@ where
 		evaluators: &[Evaluator],
 		batch_coeff: F,
 	) -> Result<RoundCoeffs<F>, Error> {
+		let coeffs = self.calculate_composite_round_coeffs(evaluators)?;
+
+		let batched_coeffs = coeffs
+			.into_iter()
+			.zip(powers(batch_coeff))
+			.map(|(coeffs, scalar)| coeffs * scalar)
+			.fold(RoundCoeffs::default(), |accum, coeffs| accum + &coeffs);
+
+		Ok(batched_coeffs)
+	}
+
+	/// Computes the round polynomials of each evaluator's composite, without mixing them.
+	pub fn calculate_composite_round_coeffs<Evaluator: SumcheckEvaluator<P> + Sync>(
+		&mut self,
+		evaluators: &[Evaluator],
+	) -> Result<Vec<RoundCoeffs<F>>, Error> {
 		let evals = self.calculate_round_evals(evaluators)?;
 
 		let coeffs = match self.last_coeffs_or_sums {
@@ -299,10 +475,19 @@ where
 					});
 				}
 
+				// The length of each round polynomial is checked against the declared degree of its
+				// composite, so that an evaluator bug fails here rather than in the verifier.
 				let coeffs = izip!(evaluators, sums, evals)
-					.map(|(evaluator, &sum, RoundCoeffs(evals))| {
+					.enumerate()
+					.map(|(index, (evaluator, &sum, RoundCoeffs(evals)))| {
 						let coeffs = evaluator.round_evals_to_coeffs(sum, evals)?;
-						Ok::<_, Error>(RoundCoeffs(coeffs))
+						if coeffs.len() != evaluator.degree() + 1 {
+							bail!(Error::IncorrectRoundPolynomialLength {
+								index,
+								expected: evaluator.degree() + 1,
+							});
+						}
+						Ok(RoundCoeffs(coeffs))
 					})
 					.collect::<Result<Vec<_>, _>>()?;
 				self.last_coeffs_or_sums = ProverStateCoeffsOrSums::Coeffs(coeffs.clone());
@@ -310,98 +495,60 @@ where
 			}
 		};
 
-		let batched_coeffs = coeffs
-			.into_iter()
-			.zip(powers(batch_coeff))
-			.map(|(coeffs, scalar)| coeffs * scalar)
-			.fold(RoundCoeffs::default(), |accum, coeffs| accum + &coeffs);
-
-		Ok(batched_coeffs)
+		Ok(coeffs)
 	}
 
 	fn calculate_round_evals<Evaluator: SumcheckEvaluator<P> + Sync>(
-		&self,
+		&mut self,
 		evaluators: &[Evaluator],
 	) -> Result<Vec<RoundCoeffs<F>>, Error> {
-		let n_multilinears = self.multilinears.len();
-		let n_round_evals = evaluators.iter().map(|evaluator| evaluator.n_round_evals());
-
 		let empty_query = MultilinearQuery::new(0).expect("constructing an empty query");
 		let query = self.tensor_query.as_ref().unwrap_or(&empty_query);
+		let fold_direction = self.fold_direction;
+		let n_vars = self.n_vars;
+
+		let sample = |indices: Range<usize>, evals_0: &mut Array2D<P>, evals_1: &mut Array2D<P>| {
+			for (j, multilinear) in self.multilinears.iter().enumerate() {
+				Self::eval01(
+					query,
+					multilinear,
+					fold_direction,
+					n_vars,
+					indices.clone(),
+					evals_0,
+					evals_1,
+					j,
+				)?;
+			}
+			Ok(())
+		};
 
-		/// Process batches of vertices in parallel, accumulating the round evaluations.
-		const MAX_LOG_BATCH_SIZE: usize = 6;
-		let log_batch_size = (self.n_vars - 1).min(MAX_LOG_BATCH_SIZE);
-		let batch_size = 1 << log_batch_size;
-
-		let packed_accumulators = (0..(1 << (self.n_vars - 1 - log_batch_size)))
-			.into_par_iter()
-			.fold(
-				|| ParFoldStates::new(n_multilinears, n_round_evals.clone(), batch_size),
-				|mut par_fold_states, vertex| {
-					let begin = vertex << log_batch_size;
-					let end = begin + batch_size;
-					for (j, multilinear) in self.multilinears.iter().enumerate() {
-						Self::eval01(
-							query,
-							multilinear,
-							begin..end,
-							&mut par_fold_states.evals_0,
-							&mut par_fold_states.evals_1,
-							j,
-						);
-					}
-
-					for (evaluator, round_evals) in
-						iter::zip(evaluators.iter(), par_fold_states.round_evals.iter_mut())
-					{
-						for k in 0..batch_size {
-							evaluator.process_vertex(
-								begin + k,
-								par_fold_states.evals_0.get_row(k),
-								par_fold_states.evals_1.get_row(k),
-								par_fold_states.evals_z.get_row_mut(k),
-								round_evals,
-							);
-						}
-					}
-
-					par_fold_states
-				},
-			)
-			.map(|states| states.round_evals)
-			// Simply sum up the fold partitions.
-			.reduce(
-				|| {
-					evaluators
-						.iter()
-						.map(|evaluator| vec![P::zero(); evaluator.n_round_evals()])
-						.collect()
-				},
-				|lhs, rhs| {
-					iter::zip(lhs, rhs)
-						.map(|(mut lhs_vals, rhs_vals)| {
-							for (lhs_val, rhs_val) in lhs_vals.iter_mut().zip(rhs_vals) {
-								*lhs_val += rhs_val;
-							}
-							lhs_vals
-						})
-						.collect()
-				},
-			);
+		let n_round_evals = evaluators
+			.iter()
+			.map(|evaluator| evaluator.n_round_evals())
+			.collect::<Vec<_>>();
+		self.scratch_bytes = self.backend.sum_composition_scratch_bytes::<P>(
+			n_vars,
+			self.multilinears.len(),
+			&n_round_evals,
+		);
 
-		let evals = packed_accumulators
-			.into_iter()
-			.map(|vals| {
-				RoundCoeffs(
-					vals.into_iter()
-						.map(|packed_val| packed_val.iter().sum())
-						.collect(),
-				)
-			})
-			.collect();
+		let evals = self.backend.sum_composition_evals(
+			n_vars,
+			self.multilinears.len(),
+			&sample,
+			evaluators,
+			&self.memory_budget,
+		)?;
+
+		for (index, evaluator) in evaluators.iter().enumerate() {
+			let expected = evaluator.n_round_evals();
+			if evals.get(index).map(|evals| evals.len()) != Some(expected) {
+				bail!(Error::IncorrectNumberOfRoundEvaluations { index, expected });
+			}
+		}
 
-		Ok(evals)
+		Ok(evals.into_iter().map(RoundCoeffs).collect())
 	}
 
 	// Note the generic parameter - this method samples small field in first round and
@@ -429,6 +576,76 @@ where
 		}
 	}
 
+	/// Samples the evaluations at the pairs of vertices that differ in the highest remaining
+	/// variable, which is the next one folded when folding high-to-low.
+	///
+	/// The `n_query_vars` variables above the `n_vars` remaining ones have already been folded, and
+	/// are summed over with their tensor product expansion `query_expansion`.
+	#[allow(clippy::too_many_arguments)]
+	#[inline]
+	fn sample_high<MD>(
+		multilin: MD,
+		query_expansion: &[P],
+		n_query_vars: usize,
+		n_vars: usize,
+		indices: Range<usize>,
+		evals_0: &mut Array2D<P>,
+		evals_1: &mut Array2D<P>,
+		col_index: usize,
+	) -> Result<(), Error>
+	where
+		MD: MultilinearPoly<P>,
+	{
+		let half = 1 << (n_vars - 1);
+		let eval = |index: usize| -> Result<F, Error> {
+			let mut sum = F::ZERO;
+			for (j, coeff) in iter_packed_slice(query_expansion)
+				.take(1 << n_query_vars)
+				.enumerate()
+			{
+				sum += multilin.evaluate_on_hypercube_and_scale(index | (j << n_vars), coeff)?;
+			}
+			Ok(sum)
+		};
+		for (k, i) in indices.enumerate() {
+			let mut packed_0 = P::zero();
+			let mut packed_1 = P::zero();
+			for offset in 0..P::WIDTH {
+				let index = i * P::WIDTH + offset;
+				if index < half {
+					packed_0.set(offset, eval(index)?);
+					packed_1.set(offset, eval(index | half)?);
+				}
+			}
+			evals_0[(k, col_index)] = packed_0;
+			evals_1[(k, col_index)] = packed_1;
+		}
+		Ok(())
+	}
+
+	/// Samples a constant multilinear, whose evaluations are the same at every vertex.
+	#[inline]
+	fn constant_sample(
+		value: F,
+		n_vars: usize,
+		indices: Range<usize>,
+		evals_0: &mut Array2D<P>,
+		evals_1: &mut Array2D<P>,
+		col_index: usize,
+	) {
+		let half = 1 << (n_vars - 1);
+		let broadcast = P::broadcast(value);
+		for (k, i) in indices.enumerate() {
+			let packed = if (i + 1) * P::WIDTH <= half {
+				broadcast
+			} else {
+				packed_from_fn_with_offset(i, |idx| if idx < half { value } else { F::ZERO })
+			};
+			evals_0[(k, col_index)] = packed;
+			evals_1[(k, col_index)] = packed;
+		}
+	}
+
 	#[inline]
 	fn subcube_inner_product(
 		query: &MultilinearQuery<P>,
@@ -443,14 +660,48 @@ where
 			.expect("indices within range");
 	}
 
+	#[allow(clippy::too_many_arguments)]
 	fn eval01(
 		query: &MultilinearQuery<P>,
 		multilin: &SumcheckMultilinear<P, M>,
+		fold_direction: FoldDirection,
+		n_vars: usize,
 		indices: Range<usize>,
 		evals_0: &mut Array2D<P>,
 		evals_1: &mut Array2D<P>,
 		col_index: usize,
-	) {
+	) -> Result<(), Error> {
+		if fold_direction == FoldDirection::HighToLow {
+			return match multilin {
+				SumcheckMultilinear::Transparent { multilinear, .. } => Self::sample_high(
+					multilinear,
+					query.expansion(),
+					query.n_vars(),
+					n_vars,
+					indices,
+					evals_0,
+					evals_1,
+					col_index,
+				),
+				SumcheckMultilinear::Folded {
+					large_field_folded_multilinear,
+				} => Self::sample_high(
+					large_field_folded_multilinear,
+					&[P::set_single(F::ONE)],
+					0,
+					n_vars,
+					indices,
+					evals_0,
+					evals_1,
+					col_index,
+				),
+				SumcheckMultilinear::Constant { value } => {
+					Self::constant_sample(*value, n_vars, indices, evals_0, evals_1, col_index);
+					Ok(())
+				}
+			};
+		}
+
 		match multilin {
 			SumcheckMultilinear::Transparent { multilinear, .. } => Self::subcube_inner_product(
 				query,
@@ -470,6 +721,11 @@ where
 				evals_1,
 				col_index,
 			),
+
+			SumcheckMultilinear::Constant { value } => {
+				Self::constant_sample(*value, n_vars, indices, evals_0, evals_1, col_index)
+			}
 		}
+		Ok(())
 	}
 }
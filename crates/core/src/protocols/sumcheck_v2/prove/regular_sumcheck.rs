@@ -1,7 +1,10 @@
 // Copyright 2024 Ulvetanna Inc.
 
 use super::{
+	backend::{ComputeBackend, CpuBackend},
 	batch_prove::SumcheckProver,
+	checkpoint::{ProverSnapshot, ResumableProver},
+	memory::MemoryBudget,
 	prover_state::{ProverState, SumcheckEvaluator},
 };
 use crate::{
@@ -10,7 +13,7 @@ use crate::{
 		EvaluationDomainFactory, MultilinearComposite, MultilinearPoly,
 	},
 	protocols::sumcheck_v2::{
-		common::{CompositeSumClaim, RoundCoeffs},
+		common::{CompositeSumClaim, FoldDirection, RoundCoeffs, RoundEvals},
 		error::Error,
 	},
 };
@@ -62,14 +65,15 @@ where
 	Ok(())
 }
 
-pub struct RegularSumcheckProver<FDomain, P, Composition, M>
+pub struct RegularSumcheckProver<FDomain, P, Composition, M, Backend = CpuBackend>
 where
 	FDomain: Field,
 	P: PackedField,
 	M: MultilinearPoly<P> + Send + Sync,
+	Backend: ComputeBackend,
 {
 	n_vars: usize,
-	state: ProverState<P, M>,
+	state: ProverState<P, M, Backend>,
 	compositions: Vec<Composition>,
 	domains: Vec<EvaluationDomain<FDomain>>,
 }
@@ -87,8 +91,131 @@ where
 		composite_claims: impl IntoIterator<Item = CompositeSumClaim<F, Composition>>,
 		evaluation_domain_factory: impl EvaluationDomainFactory<FDomain>,
 		switchover_fn: impl Fn(usize) -> usize,
+	) -> Result<Self, Error> {
+		Self::new_with_fold_direction(
+			multilinears,
+			composite_claims,
+			evaluation_domain_factory,
+			switchover_fn,
+			FoldDirection::LowToHigh,
+		)
+	}
+
+	/// Constructs a prover that folds the variables in the given direction.
+	pub fn new_with_fold_direction(
+		multilinears: Vec<M>,
+		composite_claims: impl IntoIterator<Item = CompositeSumClaim<F, Composition>>,
+		evaluation_domain_factory: impl EvaluationDomainFactory<FDomain>,
+		switchover_fn: impl Fn(usize) -> usize,
+		fold_direction: FoldDirection,
+	) -> Result<Self, Error> {
+		Self::new_with_backend(
+			multilinears,
+			composite_claims,
+			evaluation_domain_factory,
+			switchover_fn,
+			fold_direction,
+			CpuBackend,
+		)
+	}
+
+	/// Restores a prover from a snapshot taken with [`ResumableProver::snapshot`].
+	///
+	/// The multilinears and composite claims must be the ones the original prover was constructed
+	/// with.
+	pub fn restore(
+		multilinears: Vec<M>,
+		composite_claims: impl IntoIterator<Item = CompositeSumClaim<F, Composition>>,
+		evaluation_domain_factory: impl EvaluationDomainFactory<FDomain>,
+		snapshot: ProverSnapshot<F>,
+	) -> Result<Self, Error> {
+		Self::restore_with_fold_direction(
+			multilinears,
+			composite_claims,
+			evaluation_domain_factory,
+			snapshot,
+			FoldDirection::LowToHigh,
+		)
+	}
+
+	/// Restores a prover constructed with [`Self::new_with_fold_direction`] from a snapshot.
+	pub fn restore_with_fold_direction(
+		multilinears: Vec<M>,
+		composite_claims: impl IntoIterator<Item = CompositeSumClaim<F, Composition>>,
+		evaluation_domain_factory: impl EvaluationDomainFactory<FDomain>,
+		snapshot: ProverSnapshot<F>,
+		fold_direction: FoldDirection,
+	) -> Result<Self, Error> {
+		Self::restore_with_backend(
+			multilinears,
+			composite_claims,
+			evaluation_domain_factory,
+			snapshot,
+			fold_direction,
+			CpuBackend,
+		)
+	}
+}
+
+impl<F, FDomain, P, Composition, M, Backend>
+	RegularSumcheckProver<FDomain, P, Composition, M, Backend>
+where
+	F: Field + ExtensionField<FDomain>,
+	FDomain: Field,
+	P: PackedField<Scalar = F>,
+	Composition: CompositionPoly<P>,
+	M: MultilinearPoly<P> + Send + Sync,
+	Backend: ComputeBackend,
+{
+	/// Constructs a prover that runs its hypercube loops on the given backend.
+	pub fn new_with_backend(
+		multilinears: Vec<M>,
+		composite_claims: impl IntoIterator<Item = CompositeSumClaim<F, Composition>>,
+		evaluation_domain_factory: impl EvaluationDomainFactory<FDomain>,
+		switchover_fn: impl Fn(usize) -> usize,
+		fold_direction: FoldDirection,
+		backend: Backend,
 	) -> Result<Self, Error> {
 		let composite_claims = composite_claims.into_iter().collect::<Vec<_>>();
+		Self::check_compositions(&multilinears, &composite_claims)?;
+
+		let claimed_sums = composite_claims
+			.iter()
+			.map(|composite_claim| composite_claim.sum)
+			.collect();
+		let state =
+			ProverState::new(multilinears, claimed_sums, switchover_fn, fold_direction, backend)?;
+		let n_vars = state.n_vars();
+		Self::with_state(n_vars, state, composite_claims, evaluation_domain_factory)
+	}
+
+	/// Restores a prover constructed with [`Self::new_with_backend`] from a snapshot.
+	pub fn restore_with_backend(
+		multilinears: Vec<M>,
+		composite_claims: impl IntoIterator<Item = CompositeSumClaim<F, Composition>>,
+		evaluation_domain_factory: impl EvaluationDomainFactory<FDomain>,
+		snapshot: ProverSnapshot<F>,
+		fold_direction: FoldDirection,
+		backend: Backend,
+	) -> Result<Self, Error> {
+		let composite_claims = composite_claims.into_iter().collect::<Vec<_>>();
+		Self::check_compositions(&multilinears, &composite_claims)?;
+		if snapshot.sums.len() != composite_claims.len() {
+			bail!(Error::InvalidCheckpoint);
+		}
+
+		let n_vars = multilinears
+			.first()
+			.map(|multilinear| multilinear.n_vars())
+			.unwrap_or_default();
+		let state = ProverState::restore(multilinears, snapshot, fold_direction, backend)?;
+		Self::with_state(n_vars, state, composite_claims, evaluation_domain_factory)
+	}
+
+	fn check_compositions(
+		multilinears: &[M],
+		composite_claims: &[CompositeSumClaim<F, Composition>],
+	) -> Result<(), Error> {
 		for claim in composite_claims.iter() {
 			if claim.composition.n_vars() != multilinears.len() {
 				bail!(Error::InvalidComposition {
@@ -96,14 +223,15 @@ where
 				});
 			}
 		}
+		Ok(())
+	}
 
-		let claimed_sums = composite_claims
-			.iter()
-			.map(|composite_claim| composite_claim.sum)
-			.collect();
-		let state = ProverState::new(multilinears, claimed_sums, switchover_fn)?;
-		let n_vars = state.n_vars();
-
+	fn with_state(
+		n_vars: usize,
+		state: ProverState<P, M, Backend>,
+		composite_claims: Vec<CompositeSumClaim<F, Composition>>,
+		evaluation_domain_factory: impl EvaluationDomainFactory<FDomain>,
+	) -> Result<Self, Error> {
 		let domains = composite_claims
 			.iter()
 			.map(|composite_claim| {
@@ -126,14 +254,15 @@ where
 	}
 }
 
-impl<F, FDomain, P, Composition, M> SumcheckProver<F>
-	for RegularSumcheckProver<FDomain, P, Composition, M>
+impl<F, FDomain, P, Composition, M, Backend> SumcheckProver<F>
+	for RegularSumcheckProver<FDomain, P, Composition, M, Backend>
 where
 	F: Field + ExtensionField<FDomain>,
 	FDomain: Field,
 	P: PackedField<Scalar = F> + PackedExtension<FDomain>,
 	Composition: CompositionPoly<P>,
 	M: MultilinearPoly<P> + Send + Sync,
+	Backend: ComputeBackend,
 {
 	fn n_vars(&self) -> usize {
 		self.n_vars
@@ -144,25 +273,53 @@ where
 		Ok(())
 	}
 
+	fn fold_direction(&self) -> FoldDirection {
+		self.state.fold_direction()
+	}
+
 	fn execute(&mut self, batch_coeff: F) -> Result<RoundCoeffs<F>, Error> {
 		let evaluators = izip!(&self.compositions, &self.domains)
-			.map(|(composition, evaluation_domain)| RegularSumcheckEvaluator {
-				composition,
-				evaluation_domain,
-				domain_points: evaluation_domain.points(),
-				_marker: PhantomData,
+			.map(|(composition, evaluation_domain)| {
+				RegularSumcheckEvaluator::new(composition, evaluation_domain)
 			})
 			.collect::<Vec<_>>();
 
 		self.state.calculate_round_coeffs(&evaluators, batch_coeff)
 	}
 
+	fn scratch_bytes(&self) -> usize {
+		self.state.scratch_bytes()
+	}
+
+	fn set_memory_budget(&mut self, memory_budget: MemoryBudget) {
+		self.state.set_memory_budget(memory_budget);
+	}
+
 	fn finish(self) -> Result<Vec<F>, Error> {
 		self.state.finish()
 	}
+
+	fn finish_boxed(self: Box<Self>) -> Result<Vec<F>, Error> {
+		(*self).finish()
+	}
 }
 
-struct RegularSumcheckEvaluator<'a, P, FDomain, Composition>
+impl<F, FDomain, P, Composition, M, Backend> ResumableProver<F>
+	for RegularSumcheckProver<FDomain, P, Composition, M, Backend>
+where
+	F: Field + ExtensionField<FDomain>,
+	FDomain: Field,
+	P: PackedField<Scalar = F> + PackedExtension<FDomain>,
+	Composition: CompositionPoly<P>,
+	M: MultilinearPoly<P> + Send + Sync,
+	Backend: ComputeBackend,
+{
+	fn snapshot(&self) -> Result<ProverSnapshot<F>, Error> {
+		self.state.snapshot()
+	}
+}
+
+pub(super) struct RegularSumcheckEvaluator<'a, P, FDomain, Composition>
 where
 	P: PackedField,
 	FDomain: Field,
@@ -173,6 +330,24 @@ where
 	_marker: PhantomData<P>,
 }
 
+impl<'a, P, FDomain, Composition> RegularSumcheckEvaluator<'a, P, FDomain, Composition>
+where
+	P: PackedField,
+	FDomain: Field,
+{
+	pub(super) fn new(
+		composition: &'a Composition,
+		evaluation_domain: &'a EvaluationDomain<FDomain>,
+	) -> Self {
+		Self {
+			composition,
+			evaluation_domain,
+			domain_points: evaluation_domain.points(),
+			_marker: PhantomData,
+		}
+	}
+}
+
 impl<'a, F, P, FDomain, Composition> SumcheckEvaluator<P>
 	for RegularSumcheckEvaluator<'a, P, FDomain, Composition>
 where
@@ -181,6 +356,10 @@ where
 	FDomain: Field,
 	Composition: CompositionPoly<P>,
 {
+	fn degree(&self) -> usize {
+		self.composition.degree()
+	}
+
 	fn n_round_evals(&self) -> usize {
 		// NB: We skip evaluation of $r(X)$ at $X = 0$ as it is derivable from the
 		// current_round_sum - $r(1)$.
@@ -230,7 +409,7 @@ where
 		// we can compute $r(0)$ using the identity $r(0) = s - r(1)$
 		round_evals.insert(0, last_round_sum - round_evals[0]);
 
-		let coeffs = self.evaluation_domain.interpolate(&round_evals)?;
-		Ok(coeffs)
+		let round_evals = RoundEvals(round_evals);
+		Ok(round_evals.to_coeffs(self.evaluation_domain)?.0)
 	}
 }
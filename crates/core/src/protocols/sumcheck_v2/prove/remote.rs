@@ -0,0 +1,164 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use crate::{
+	polynomial::{evaluate_univariate, CompositionPoly},
+	protocols::sumcheck_v2::{
+		common::{FoldDirection, RoundCoeffs, SumcheckClaim},
+		error::Error,
+		prove::SumcheckProver,
+	},
+};
+use binius_field::{
+	util::{inner_product_unchecked, powers},
+	Field,
+};
+use binius_utils::bail;
+
+/// Error type returned by a [`RemoteRoundProver`].
+pub type RemoteError = Box<dyn std::error::Error + Send + Sync>;
+
+/// The rounds of a sumcheck prover that runs outside this process, such as on a remote worker or
+/// a GPU service.
+///
+/// The methods have the semantics of the corresponding methods of [`SumcheckProver`], and are
+/// called in the same order.
+pub trait RemoteRoundProver<F: Field> {
+	/// Computes the round polynomial of the composites mixed with the powers of `batch_coeff`, see
+	/// [`SumcheckProver::execute`].
+	fn execute(&mut self, batch_coeff: F) -> Result<RoundCoeffs<F>, RemoteError>;
+
+	/// Folds the multilinears with the round challenge.
+	fn fold(&mut self, challenge: F) -> Result<(), RemoteError>;
+
+	/// Returns the evaluations of the multilinears at the challenge point.
+	fn finish(&mut self) -> Result<Vec<F>, RemoteError>;
+}
+
+/// A sumcheck prover whose round polynomials are computed by a [`RemoteRoundProver`].
+///
+/// The adapter handles the interaction with the transcript through [`super::batch_prove`] and only
+/// forwards the batching coefficient and the challenges to the remote side, so out-of-process
+/// provers can be batched with local ones. The adapter knows the claim, and checks that each round
+/// polynomial has at most the degree of the claim and is consistent with the claimed sum of the
+/// round, so that a faulty remote prover is reported in the round it fails rather than as a proof
+/// that does not verify.
+#[derive(Debug)]
+pub struct RemoteSumcheckProver<F: Field, Remote> {
+	remote: Remote,
+	n_vars: usize,
+	n_multilinears: usize,
+	max_degree: usize,
+	fold_direction: FoldDirection,
+	claimed_sums: Vec<F>,
+	round: usize,
+	/// The mixed claimed sum of the current round, once the batching coefficient is known.
+	sum: Option<F>,
+	/// The round polynomial of the current round, once executed.
+	round_coeffs: Option<RoundCoeffs<F>>,
+}
+
+impl<F: Field, Remote: RemoteRoundProver<F>> RemoteSumcheckProver<F, Remote> {
+	pub fn new<Composition: CompositionPoly<F>>(
+		remote: Remote,
+		claim: &SumcheckClaim<F, Composition>,
+	) -> Self {
+		Self::new_with_fold_direction(remote, claim, FoldDirection::LowToHigh)
+	}
+
+	/// Constructs an adapter for a remote prover that folds in the given direction.
+	pub fn new_with_fold_direction<Composition: CompositionPoly<F>>(
+		remote: Remote,
+		claim: &SumcheckClaim<F, Composition>,
+		fold_direction: FoldDirection,
+	) -> Self {
+		Self {
+			remote,
+			n_vars: claim.n_vars(),
+			n_multilinears: claim.n_multilinears(),
+			max_degree: claim.max_individual_degree(),
+			fold_direction,
+			claimed_sums: claim
+				.composite_sums()
+				.iter()
+				.map(|composite_sum| composite_sum.sum)
+				.collect(),
+			round: 0,
+			sum: None,
+			round_coeffs: None,
+		}
+	}
+}
+
+impl<F, Remote> SumcheckProver<F> for RemoteSumcheckProver<F, Remote>
+where
+	F: Field,
+	Remote: RemoteRoundProver<F>,
+{
+	fn n_vars(&self) -> usize {
+		self.n_vars
+	}
+
+	fn execute(&mut self, batch_coeff: F) -> Result<RoundCoeffs<F>, Error> {
+		if self.round_coeffs.is_some() {
+			bail!(Error::ExpectedFold);
+		}
+		if self.round == self.n_vars {
+			bail!(Error::ExpectedFinish);
+		}
+
+		let round_coeffs = self.remote.execute(batch_coeff).map_err(Error::Remote)?;
+		if round_coeffs.0.len() > self.max_degree + 1 {
+			bail!(Error::RemoteRoundDegree {
+				round: self.round,
+				max_degree: self.max_degree,
+			});
+		}
+
+		let sum = *self.sum.get_or_insert_with(|| {
+			inner_product_unchecked(powers(batch_coeff), self.claimed_sums.iter().copied())
+		});
+		let round_sum = evaluate_univariate(&round_coeffs.0, F::ZERO)
+			+ evaluate_univariate(&round_coeffs.0, F::ONE);
+		if round_sum != sum {
+			bail!(Error::RemoteRoundSumMismatch { round: self.round });
+		}
+
+		self.round_coeffs = Some(round_coeffs.clone());
+		Ok(round_coeffs)
+	}
+
+	fn fold(&mut self, challenge: F) -> Result<(), Error> {
+		let Some(round_coeffs) = self.round_coeffs.take() else {
+			bail!(Error::ExpectedExecution);
+		};
+		self.remote.fold(challenge).map_err(Error::Remote)?;
+		self.sum = Some(evaluate_univariate(&round_coeffs.0, challenge));
+		self.round += 1;
+		Ok(())
+	}
+
+	fn fold_direction(&self) -> FoldDirection {
+		self.fold_direction
+	}
+
+	fn finish(mut self) -> Result<Vec<F>, Error> {
+		if self.round_coeffs.is_some() {
+			bail!(Error::ExpectedFold);
+		}
+		if self.round != self.n_vars {
+			bail!(Error::ExpectedExecution);
+		}
+
+		let multilinear_evals = self.remote.finish().map_err(Error::Remote)?;
+		if multilinear_evals.len() != self.n_multilinears {
+			bail!(Error::RemoteEvaluationCount {
+				expected: self.n_multilinears,
+			});
+		}
+		Ok(multilinear_evals)
+	}
+
+	fn finish_boxed(self: Box<Self>) -> Result<Vec<F>, Error> {
+		(*self).finish()
+	}
+}
@@ -0,0 +1,298 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::{
+	backend::CpuBackend, batch_prove::SumcheckProver, prover_state::ProverState,
+	regular_sumcheck::RegularSumcheckEvaluator,
+};
+use crate::{
+	polynomial::{
+		composition::IndexComposition, CompositionPoly, EvaluationDomain, EvaluationDomainFactory,
+		MultilinearPoly,
+	},
+	protocols::sumcheck_v2::{
+		common::{CompositeSumClaim, FoldDirection, RoundCoeffs},
+		error::Error,
+	},
+};
+use binius_field::{util::powers, ExtensionField, Field, PackedExtension, PackedField};
+use binius_utils::bail;
+use std::{
+	ops::Range,
+	sync::{Arc, Mutex},
+};
+
+/// The claims of a batch over a shared set of multilinears, and the multilinears they reference.
+#[derive(Debug)]
+struct SharedState<FDomain, P, Composition, M, const N: usize>
+where
+	FDomain: Field,
+	P: PackedField,
+	M: MultilinearPoly<P> + Send + Sync,
+{
+	/// The state over all the multilinears, until it is finished.
+	state: Option<ProverState<P, M>>,
+	/// The compositions of all claims lifted to the shared multilinears, claim by claim.
+	compositions: Vec<IndexComposition<Composition, N>>,
+	domains: Vec<EvaluationDomain<FDomain>>,
+	/// The round polynomials of all compositions, once computed in the current round.
+	round_coeffs: Option<Vec<RoundCoeffs<P::Scalar>>>,
+	/// The challenges the state has been folded with.
+	challenges: Vec<P::Scalar>,
+	/// The evaluations of all multilinears, once the state is finished.
+	multilinear_evals: Option<Vec<P::Scalar>>,
+}
+
+/// A prover for one claim of a batch whose claims reference overlapping multilinears.
+///
+/// The provers of a batch are constructed together with [`Self::new_batch`] from a single set of
+/// multilinears and the indices of the multilinears each claim references, so that each
+/// multilinear is folded once per round rather than once per claim referencing it. The round
+/// polynomials of all claims are computed in a single pass over the hypercube by the first
+/// prover executed in a round, and each prover selects the polynomials of its own claim. The
+/// multilinear evaluations are split back per claim when the provers finish, in the order of
+/// the claim's indices.
+///
+/// The provers are run as a batch with [`super::batch_prove`], which executes all provers of a
+/// round before folding any of them. The claims are verified as usual, each claim having the
+/// multilinears of its indices.
+#[derive(Debug)]
+pub struct SharedSumcheckProver<FDomain, P, Composition, M, const N: usize>
+where
+	FDomain: Field,
+	P: PackedField,
+	M: MultilinearPoly<P> + Send + Sync,
+{
+	shared: Arc<Mutex<SharedState<FDomain, P, Composition, M, N>>>,
+	n_vars: usize,
+	/// The position of the claim in the batch.
+	claim_index: usize,
+	/// The indices of the multilinears of the claim in the shared set.
+	indices: [usize; N],
+	/// The positions of the claim's compositions among the compositions of all claims.
+	compositions: Range<usize>,
+	/// The number of times the prover has been folded.
+	round: usize,
+	executed: bool,
+}
+
+impl<F, FDomain, P, Composition, M, const N: usize>
+	SharedSumcheckProver<FDomain, P, Composition, M, N>
+where
+	F: Field + ExtensionField<FDomain>,
+	FDomain: Field,
+	P: PackedField<Scalar = F>,
+	Composition: CompositionPoly<P>,
+	M: MultilinearPoly<P> + Send + Sync,
+{
+	/// Constructs the provers of a batch of claims over a shared set of multilinears.
+	///
+	/// Each claim is given by the indices of its multilinears in `multilinears` and its composite
+	/// sum claims, whose compositions are over the claim's multilinears in the order of the
+	/// indices. The provers are returned in the order of the claims.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::InvalidComposition`] if a composition does not have `N` variables
+	/// * [`Error::NumberOfVariablesMismatch`] if the multilinears have different numbers of
+	///   variables
+	pub fn new_batch(
+		multilinears: Vec<M>,
+		claims: impl IntoIterator<Item = ([usize; N], Vec<CompositeSumClaim<F, Composition>>)>,
+		evaluation_domain_factory: impl EvaluationDomainFactory<FDomain>,
+		switchover_fn: impl Fn(usize) -> usize,
+	) -> Result<Vec<Self>, Error> {
+		let n_multilinears = multilinears.len();
+
+		let mut claim_indices = Vec::new();
+		let mut claim_compositions = Vec::new();
+		let mut compositions = Vec::new();
+		let mut claimed_sums = Vec::new();
+		for (indices, composite_claims) in claims {
+			let start = compositions.len();
+			for CompositeSumClaim { composition, sum } in composite_claims {
+				if composition.n_vars() != N {
+					bail!(Error::InvalidComposition { expected_n_vars: N });
+				}
+				compositions.push(IndexComposition::new(n_multilinears, indices, composition)?);
+				claimed_sums.push(sum);
+			}
+			claim_indices.push(indices);
+			claim_compositions.push(start..compositions.len());
+		}
+
+		let domains = compositions
+			.iter()
+			.map(|composition| {
+				let degree = CompositionPoly::<P>::degree(composition);
+				evaluation_domain_factory.create(degree + 1)
+			})
+			.collect::<Result<_, _>>()?;
+
+		let state = ProverState::new(
+			multilinears,
+			claimed_sums,
+			switchover_fn,
+			FoldDirection::LowToHigh,
+			CpuBackend,
+		)?;
+		let n_vars = state.n_vars();
+
+		let shared = Arc::new(Mutex::new(SharedState {
+			state: Some(state),
+			compositions,
+			domains,
+			round_coeffs: None,
+			challenges: Vec::new(),
+			multilinear_evals: None,
+		}));
+
+		let provers = claim_indices
+			.into_iter()
+			.zip(claim_compositions)
+			.enumerate()
+			.map(|(claim_index, (indices, compositions))| Self {
+				shared: shared.clone(),
+				n_vars,
+				claim_index,
+				indices,
+				compositions,
+				round: 0,
+				executed: false,
+			})
+			.collect();
+		Ok(provers)
+	}
+}
+
+impl<F, FDomain, P, Composition, M, const N: usize> SumcheckProver<F>
+	for SharedSumcheckProver<FDomain, P, Composition, M, N>
+where
+	F: Field + ExtensionField<FDomain>,
+	FDomain: Field,
+	P: PackedField<Scalar = F> + PackedExtension<FDomain>,
+	Composition: CompositionPoly<P>,
+	M: MultilinearPoly<P> + Send + Sync,
+{
+	fn n_vars(&self) -> usize {
+		self.n_vars
+	}
+
+	fn execute(&mut self, batch_coeff: F) -> Result<RoundCoeffs<F>, Error> {
+		if self.executed {
+			bail!(Error::ExpectedFold);
+		}
+
+		let mut shared = self
+			.shared
+			.lock()
+			.expect("shared state lock is not poisoned");
+		// Another prover of the batch has already been folded in this round.
+		if self.round != shared.challenges.len() {
+			bail!(Error::ExpectedFold);
+		}
+
+		if shared.round_coeffs.is_none() {
+			let SharedState {
+				state,
+				compositions,
+				domains,
+				round_coeffs,
+				..
+			} = &mut *shared;
+			let state = state.as_mut().ok_or(Error::ExpectedFinish)?;
+			let evaluators = compositions
+				.iter()
+				.zip(domains.iter())
+				.map(|(composition, evaluation_domain)| {
+					RegularSumcheckEvaluator::new(composition, evaluation_domain)
+				})
+				.collect::<Vec<_>>();
+			*round_coeffs = Some(state.calculate_composite_round_coeffs(&evaluators)?);
+		}
+
+		let round_coeffs = shared
+			.round_coeffs
+			.as_ref()
+			.expect("round_coeffs is computed above");
+		let batched_coeffs = round_coeffs[self.compositions.clone()]
+			.iter()
+			.zip(powers(batch_coeff))
+			.map(|(coeffs, scalar)| coeffs.clone() * scalar)
+			.fold(RoundCoeffs::default(), |accum, coeffs| accum + &coeffs);
+
+		self.executed = true;
+		Ok(batched_coeffs)
+	}
+
+	fn fold(&mut self, challenge: F) -> Result<(), Error> {
+		if !self.executed {
+			bail!(Error::ExpectedExecution);
+		}
+
+		let mut shared = self
+			.shared
+			.lock()
+			.expect("shared state lock is not poisoned");
+		// The first prover of the batch to be folded in a round folds the shared state.
+		if self.round == shared.challenges.len() {
+			shared
+				.state
+				.as_mut()
+				.ok_or(Error::ExpectedFinish)?
+				.fold(challenge)?;
+			shared.challenges.push(challenge);
+			shared.round_coeffs = None;
+		} else if shared.challenges[self.round] != challenge {
+			bail!(Error::SharedFoldMismatch);
+		}
+
+		self.round += 1;
+		self.executed = false;
+		Ok(())
+	}
+
+	fn scratch_bytes(&self) -> usize {
+		// The scratch memory is held once for the whole batch, and reported by its first claim.
+		if self.claim_index != 0 {
+			return 0;
+		}
+		let shared = self
+			.shared
+			.lock()
+			.expect("shared state lock is not poisoned");
+		shared
+			.state
+			.as_ref()
+			.map(|state| state.scratch_bytes())
+			.unwrap_or_default()
+	}
+
+	fn finish(self) -> Result<Vec<F>, Error> {
+		if self.round != self.n_vars {
+			bail!(Error::ExpectedExecution);
+		}
+
+		let mut shared = self
+			.shared
+			.lock()
+			.expect("shared state lock is not poisoned");
+		if shared.multilinear_evals.is_none() {
+			let state = shared.state.take().ok_or(Error::ExpectedFinish)?;
+			shared.multilinear_evals = Some(state.finish()?);
+		}
+
+		let multilinear_evals = shared
+			.multilinear_evals
+			.as_ref()
+			.expect("multilinear_evals is computed above");
+		Ok(self
+			.indices
+			.iter()
+			.map(|&index| multilinear_evals[index])
+			.collect())
+	}
+
+	fn finish_boxed(self: Box<Self>) -> Result<Vec<F>, Error> {
+		(*self).finish()
+	}
+}
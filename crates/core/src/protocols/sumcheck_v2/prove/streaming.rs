@@ -0,0 +1,516 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::{
+	batch_prove::SumcheckProver,
+	prover_state::SumcheckEvaluator,
+	regular_sumcheck::{RegularSumcheckEvaluator, RegularSumcheckProver},
+};
+use crate::{
+	polynomial::{
+		evaluate_univariate, CompositionPoly, Error as PolynomialError, EvaluationDomain,
+		EvaluationDomainFactory, MultilinearExtension, MultilinearExtensionSpecialized,
+	},
+	protocols::sumcheck_v2::{
+		common::{CompositeSumClaim, FoldDirection, RoundCoeffs},
+		error::Error,
+	},
+};
+use binius_field::{util::powers, ExtensionField, Field, PackedExtension, PackedField};
+use binius_utils::bail;
+use bytemuck::Pod;
+use p3_util::log2_strict_usize;
+use rayon::prelude::*;
+use std::{
+	io::{self, Read, Seek, SeekFrom, Write},
+	iter, mem,
+};
+
+/// The hypercube evaluations of a multilinear, read in chunks by a [`StreamingSumcheckProver`].
+pub trait StreamingSource<P: PackedField> {
+	/// The number of variables of the multilinear.
+	fn n_vars(&self) -> usize;
+
+	/// Reads the packed evaluations starting at the packed index `offset` into `out`.
+	fn read_at(&mut self, offset: usize, out: &mut [P]) -> io::Result<()>;
+}
+
+/// A [`StreamingSource`] reading the packed evaluations from a reader.
+///
+/// The reader holds the packed evaluations in lexicographic order, in the in-memory
+/// representation of the packed field.
+#[derive(Debug)]
+pub struct ReadSource<R> {
+	reader: R,
+	n_vars: usize,
+}
+
+impl<R> ReadSource<R> {
+	pub fn new(reader: R, n_vars: usize) -> Self {
+		Self { reader, n_vars }
+	}
+}
+
+impl<P, R> StreamingSource<P> for ReadSource<R>
+where
+	P: PackedField + Pod,
+	R: Read + Seek,
+{
+	fn n_vars(&self) -> usize {
+		self.n_vars
+	}
+
+	fn read_at(&mut self, offset: usize, out: &mut [P]) -> io::Result<()> {
+		read_packed(&mut self.reader, (offset * mem::size_of::<P>()) as u64, out)
+	}
+}
+
+/// Packed evaluations in a slice, for example of a memory-mapped file, are read directly.
+impl<P: PackedField> StreamingSource<P> for &[P] {
+	fn n_vars(&self) -> usize {
+		log2_strict_usize(self.len() * P::WIDTH)
+	}
+
+	fn read_at(&mut self, offset: usize, out: &mut [P]) -> io::Result<()> {
+		let evals = self
+			.get(offset..offset + out.len())
+			.ok_or(io::ErrorKind::UnexpectedEof)?;
+		out.copy_from_slice(evals);
+		Ok(())
+	}
+}
+
+/// Where the evaluations of the multilinears in the current round are stored.
+#[derive(Debug, Clone, Copy)]
+enum DataLocation {
+	/// The multilinears have not been folded yet and are read from the sources.
+	Sources,
+	/// The folded multilinears are stored one after the other at a byte offset in the spill.
+	Spill { offset: u64 },
+}
+
+/// A sumcheck prover for multilinears that do not fit in memory.
+///
+/// The prover reads the evaluations of the multilinears in chunks of `chunk_len` packed elements
+/// from [`StreamingSource`]s and writes the folded multilinears to a spill, for example a
+/// temporary file. It folds the variables high-to-low, so that the two evaluations of each
+/// vertex pair are at the same offsets of the two halves of a multilinear and are processed with
+/// packed arithmetic. The fold with the challenge of a round is deferred to the next round, so
+/// each round makes a single pass over the current multilinears that both folds them and
+/// computes the round polynomial of the folded multilinears.
+///
+/// Once the current multilinears are small enough, they are loaded into memory and the
+/// remaining rounds are proven by a [`RegularSumcheckProver`]. At most four chunks of each
+/// multilinear are held in memory until then.
+///
+/// The spill holds at most one and a half times the size of the multilinears folded once. Since
+/// the prover folds high-to-low, the proof is verified with
+/// [`crate::protocols::sumcheck_v2::batch_verify_with_fold_direction`].
+pub struct StreamingSumcheckProver<FDomain, P, Composition, S, Spill, DomainFactory>
+where
+	FDomain: Field,
+	P: PackedField,
+{
+	n_vars: usize,
+	sources: Vec<S>,
+	spill: Spill,
+	compositions: Vec<Composition>,
+	domains: Vec<EvaluationDomain<FDomain>>,
+	domain_factory: DomainFactory,
+	chunk_len: usize,
+	/// The number of variables of the multilinears at the current location, one more than
+	/// the remaining rounds while a fold is pending.
+	data_n_vars: usize,
+	location: DataLocation,
+	/// The byte offset of the second region of the spill, once it is known.
+	spill_region: Option<u64>,
+	/// The sums of the composites over the remaining hypercube.
+	sums: Vec<P::Scalar>,
+	/// The round polynomials of the composites, between execution and folding.
+	round_coeffs: Option<Vec<RoundCoeffs<P::Scalar>>>,
+	/// The challenge of the last round, which the multilinears are not folded with yet.
+	pending_challenge: Option<P::Scalar>,
+	in_memory: Option<
+		RegularSumcheckProver<FDomain, P, Composition, MultilinearExtensionSpecialized<P, P>>,
+	>,
+}
+
+impl<F, FDomain, P, Composition, S, Spill, DomainFactory>
+	StreamingSumcheckProver<FDomain, P, Composition, S, Spill, DomainFactory>
+where
+	F: Field + ExtensionField<FDomain>,
+	FDomain: Field,
+	P: PackedField<Scalar = F> + PackedExtension<FDomain> + Pod,
+	Composition: CompositionPoly<P>,
+	S: StreamingSource<P>,
+	Spill: Read + Write + Seek,
+	DomainFactory: EvaluationDomainFactory<FDomain>,
+{
+	/// Constructs a prover reading the multilinears from `sources` and spilling to `spill`.
+	///
+	/// `chunk_len` is the number of packed elements of each multilinear that are read at once.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::NumberOfVariablesMismatch`] if the sources have different numbers of variables
+	/// * [`Error::InvalidComposition`] if a composition does not have one variable per source
+	/// * [`PolynomialError::PackedFieldNotFilled`] if the multilinears have fewer evaluations than
+	///   the packing width
+	pub fn new(
+		sources: Vec<S>,
+		spill: Spill,
+		composite_claims: impl IntoIterator<Item = CompositeSumClaim<F, Composition>>,
+		domain_factory: DomainFactory,
+		chunk_len: usize,
+	) -> Result<Self, Error> {
+		let n_vars = sources
+			.first()
+			.map(|source| source.n_vars())
+			.unwrap_or_default();
+		if sources.iter().any(|source| source.n_vars() != n_vars) {
+			bail!(Error::NumberOfVariablesMismatch);
+		}
+		if n_vars < P::LOG_WIDTH {
+			bail!(PolynomialError::PackedFieldNotFilled {
+				length: 1 << n_vars,
+				packed_width: P::WIDTH,
+			});
+		}
+		if !chunk_len.is_power_of_two() {
+			bail!(PolynomialError::PowerOfTwoLengthRequired);
+		}
+
+		let (compositions, sums): (Vec<_>, Vec<_>) = composite_claims
+			.into_iter()
+			.map(|claim| (claim.composition, claim.sum))
+			.unzip();
+		if compositions
+			.iter()
+			.any(|composition| composition.n_vars() != sources.len())
+		{
+			bail!(Error::InvalidComposition {
+				expected_n_vars: sources.len(),
+			});
+		}
+
+		let domains = compositions
+			.iter()
+			.map(|composition| domain_factory.create(composition.degree() + 1))
+			.collect::<Result<_, _>>()?;
+
+		Ok(Self {
+			n_vars,
+			sources,
+			spill,
+			compositions,
+			domains,
+			domain_factory,
+			chunk_len,
+			data_n_vars: n_vars,
+			location: DataLocation::Sources,
+			spill_region: None,
+			sums,
+			round_coeffs: None,
+			pending_challenge: None,
+			in_memory: None,
+		})
+	}
+
+	/// The number of packed elements of each multilinear at the current location.
+	fn data_len(&self) -> usize {
+		1 << (self.data_n_vars - P::LOG_WIDTH)
+	}
+
+	/// Reads a chunk of the multilinear `index` at the current location.
+	fn read(&mut self, index: usize, offset: usize, out: &mut [P]) -> Result<(), Error> {
+		match self.location {
+			DataLocation::Sources => self.sources[index].read_at(offset, out)?,
+			DataLocation::Spill { offset: base } => {
+				let offset = (index * self.data_len() + offset) * mem::size_of::<P>();
+				read_packed(&mut self.spill, base + offset as u64, out)?
+			}
+		}
+		Ok(())
+	}
+
+	/// Computes the round polynomials of the composites in a single pass over the multilinears,
+	/// first folding them with the pending challenge.
+	fn stream_round(&mut self) -> Result<Vec<RoundCoeffs<F>>, Error> {
+		let n_multilinears = self.sources.len();
+		let chunk_len = self.chunk_len;
+		let data_len = self.data_len();
+
+		let mut evals_0 = vec![vec![P::zero(); chunk_len]; n_multilinears];
+		let mut evals_1 = vec![vec![P::zero(); chunk_len]; n_multilinears];
+		let mut round_evals = self
+			.compositions
+			.iter()
+			.map(|composition| vec![P::zero(); composition.degree()])
+			.collect::<Vec<_>>();
+
+		match self.pending_challenge.take() {
+			None => {
+				let half = data_len / 2;
+				for offset in (0..half).step_by(chunk_len) {
+					for j in 0..n_multilinears {
+						self.read(j, offset, &mut evals_0[j])?;
+						self.read(j, half + offset, &mut evals_1[j])?;
+					}
+					self.accumulate(&evals_0, &evals_1, &mut round_evals);
+				}
+			}
+			Some(challenge) => {
+				// The folded multilinears are written to the region of the spill that does not
+				// hold the current ones.
+				let folded_len = data_len / 2;
+				let write_base = match (self.location, self.spill_region) {
+					(DataLocation::Spill { offset: 0 }, Some(region)) => region,
+					_ => 0,
+				};
+				let quarter = data_len / 4;
+				let mut upper = vec![P::zero(); chunk_len];
+				for offset in (0..quarter).step_by(chunk_len) {
+					for j in 0..n_multilinears {
+						for (evals, start) in [
+							(&mut evals_0[j], offset),
+							(&mut evals_1[j], quarter + offset),
+						] {
+							self.read(j, start, evals)?;
+							self.read(j, folded_len + start, &mut upper)?;
+							fold_halves(evals, &upper, challenge);
+
+							let byte_offset = (j * folded_len + start) * mem::size_of::<P>();
+							write_packed(&mut self.spill, write_base + byte_offset as u64, evals)?;
+						}
+					}
+					self.accumulate(&evals_0, &evals_1, &mut round_evals);
+				}
+
+				if self.spill_region.is_none() {
+					self.spill_region =
+						Some((n_multilinears * folded_len * mem::size_of::<P>()) as u64);
+				}
+				self.location = DataLocation::Spill { offset: write_base };
+				self.data_n_vars -= 1;
+			}
+		}
+
+		regular_evaluators(&self.compositions, &self.domains)
+			.zip(round_evals)
+			.zip(&self.sums)
+			.map(|((evaluator, round_evals), &sum)| {
+				let round_evals = round_evals
+					.into_iter()
+					.map(|packed_eval| packed_eval.iter().sum())
+					.collect();
+				Ok(RoundCoeffs(evaluator.round_evals_to_coeffs(sum, round_evals)?))
+			})
+			.collect()
+	}
+
+	/// Adds the round evaluations of the vertex pairs of a chunk.
+	fn accumulate(&self, evals_0: &[Vec<P>], evals_1: &[Vec<P>], round_evals: &mut [Vec<P>]) {
+		let n_multilinears = evals_0.len();
+		let evaluators = regular_evaluators(&self.compositions, &self.domains).collect::<Vec<_>>();
+		let new_round_evals = || {
+			evaluators
+				.iter()
+				.map(|evaluator| vec![P::zero(); evaluator.n_round_evals()])
+				.collect::<Vec<_>>()
+		};
+
+		let chunk_round_evals = (0..self.chunk_len)
+			.into_par_iter()
+			.fold(
+				|| {
+					let vertex = vec![P::zero(); n_multilinears];
+					(vertex.clone(), vertex.clone(), vertex, new_round_evals())
+				},
+				|(mut vertex_0, mut vertex_1, mut vertex_z, mut round_evals), i| {
+					for j in 0..n_multilinears {
+						vertex_0[j] = evals_0[j][i];
+						vertex_1[j] = evals_1[j][i];
+					}
+					for (evaluator, round_evals) in iter::zip(&evaluators, &mut round_evals) {
+						evaluator.process_vertex(
+							i,
+							&vertex_0,
+							&vertex_1,
+							&mut vertex_z,
+							round_evals,
+						);
+					}
+					(vertex_0, vertex_1, vertex_z, round_evals)
+				},
+			)
+			.map(|(_, _, _, round_evals)| round_evals)
+			.reduce(new_round_evals, add_round_evals);
+
+		for (accum, chunk_evals) in iter::zip(round_evals, chunk_round_evals) {
+			for (accum, chunk_eval) in iter::zip(accum, chunk_evals) {
+				*accum += chunk_eval;
+			}
+		}
+	}
+
+	/// Loads the current multilinears into memory, folded with the pending challenge, and hands
+	/// the remaining rounds over to an in-memory prover.
+	fn load_in_memory(&mut self) -> Result<(), Error> {
+		let data_len = self.data_len();
+		let pending_challenge = self.pending_challenge.take();
+
+		let mut multilinears = Vec::with_capacity(self.sources.len());
+		for j in 0..self.sources.len() {
+			let mut evals = vec![P::zero(); data_len];
+			self.read(j, 0, &mut evals)?;
+			if let Some(challenge) = pending_challenge {
+				let (lower, upper) = evals.split_at_mut(data_len / 2);
+				fold_halves(lower, upper, challenge);
+				evals.truncate(data_len / 2);
+			}
+			multilinears.push(MultilinearExtension::from_values(evals)?.specialize());
+		}
+
+		let composite_claims = iter::zip(mem::take(&mut self.compositions), &self.sums)
+			.map(|(composition, &sum)| CompositeSumClaim { composition, sum });
+		self.in_memory = Some(RegularSumcheckProver::new_with_fold_direction(
+			multilinears,
+			composite_claims,
+			self.domain_factory.clone(),
+			|_| 1,
+			FoldDirection::HighToLow,
+		)?);
+		Ok(())
+	}
+}
+
+impl<F, FDomain, P, Composition, S, Spill, DomainFactory> SumcheckProver<F>
+	for StreamingSumcheckProver<FDomain, P, Composition, S, Spill, DomainFactory>
+where
+	F: Field + ExtensionField<FDomain>,
+	FDomain: Field,
+	P: PackedField<Scalar = F> + PackedExtension<FDomain> + Pod,
+	Composition: CompositionPoly<P>,
+	S: StreamingSource<P>,
+	Spill: Read + Write + Seek,
+	DomainFactory: EvaluationDomainFactory<FDomain>,
+{
+	fn n_vars(&self) -> usize {
+		self.n_vars
+	}
+
+	fn fold_direction(&self) -> FoldDirection {
+		FoldDirection::HighToLow
+	}
+
+	fn execute(&mut self, batch_coeff: F) -> Result<RoundCoeffs<F>, Error> {
+		if let Some(in_memory) = self.in_memory.as_mut() {
+			return in_memory.execute(batch_coeff);
+		}
+		if self.round_coeffs.is_some() {
+			bail!(Error::ExpectedFold);
+		}
+
+		// A round streams quarters of the multilinears while a fold is pending and halves
+		// otherwise, which must span whole chunks.
+		let min_streamed_len = match self.pending_challenge {
+			Some(_) => 4 * self.chunk_len,
+			None => 2 * self.chunk_len,
+		};
+		if self.data_len() < min_streamed_len {
+			self.load_in_memory()?;
+			return self.execute(batch_coeff);
+		}
+
+		let round_coeffs = self.stream_round()?;
+		let batched_coeffs = round_coeffs
+			.iter()
+			.zip(powers(batch_coeff))
+			.map(|(coeffs, scalar)| coeffs.clone() * scalar)
+			.fold(RoundCoeffs::default(), |accum, coeffs| accum + &coeffs);
+		self.round_coeffs = Some(round_coeffs);
+		Ok(batched_coeffs)
+	}
+
+	fn fold(&mut self, challenge: F) -> Result<(), Error> {
+		if let Some(in_memory) = self.in_memory.as_mut() {
+			return in_memory.fold(challenge);
+		}
+
+		let Some(round_coeffs) = self.round_coeffs.take() else {
+			bail!(Error::ExpectedExecution);
+		};
+		for (sum, coeffs) in iter::zip(&mut self.sums, round_coeffs) {
+			*sum = evaluate_univariate(&coeffs.0, challenge);
+		}
+		self.pending_challenge = Some(challenge);
+		Ok(())
+	}
+
+	fn finish(mut self) -> Result<Vec<F>, Error> {
+		if self.in_memory.is_none() {
+			if self.round_coeffs.is_some() {
+				bail!(Error::ExpectedFold);
+			}
+			if self.data_n_vars - usize::from(self.pending_challenge.is_some()) != 0 {
+				bail!(Error::ExpectedExecution);
+			}
+			self.load_in_memory()?;
+		}
+
+		self.in_memory
+			.expect("the multilinears are loaded into memory above")
+			.finish()
+	}
+
+	fn finish_boxed(self: Box<Self>) -> Result<Vec<F>, Error> {
+		(*self).finish()
+	}
+}
+
+fn regular_evaluators<'a, P, FDomain, Composition>(
+	compositions: &'a [Composition],
+	domains: &'a [EvaluationDomain<FDomain>],
+) -> impl Iterator<Item = RegularSumcheckEvaluator<'a, P, FDomain, Composition>>
+where
+	P: PackedField,
+	FDomain: Field,
+{
+	iter::zip(compositions, domains)
+		.map(|(composition, domain)| RegularSumcheckEvaluator::new(composition, domain))
+}
+
+fn add_round_evals<P: PackedField>(mut lhs: Vec<Vec<P>>, rhs: Vec<Vec<P>>) -> Vec<Vec<P>> {
+	for (lhs, rhs) in iter::zip(&mut lhs, rhs) {
+		for (lhs, rhs) in iter::zip(lhs, rhs) {
+			*lhs += rhs;
+		}
+	}
+	lhs
+}
+
+/// Folds the highest variable of a multilinear given the two halves of its evaluations,
+/// overwriting the lower half.
+fn fold_halves<P: PackedField>(lower: &mut [P], upper: &[P], challenge: P::Scalar) {
+	lower
+		.par_iter_mut()
+		.zip(upper.par_iter())
+		.for_each(|(eval_0, &eval_1)| *eval_0 += (eval_1 - *eval_0) * challenge);
+}
+
+fn read_packed<P: Pod>(
+	reader: &mut (impl Read + Seek),
+	offset: u64,
+	out: &mut [P],
+) -> io::Result<()> {
+	reader.seek(SeekFrom::Start(offset))?;
+	reader.read_exact(bytemuck::cast_slice_mut(out))
+}
+
+fn write_packed<P: Pod>(
+	writer: &mut (impl Write + Seek),
+	offset: u64,
+	data: &[P],
+) -> io::Result<()> {
+	writer.seek(SeekFrom::Start(offset))?;
+	writer.write_all(bytemuck::cast_slice(data))
+}
@@ -0,0 +1,96 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::regular_sumcheck::RegularSumcheckProver;
+use crate::{
+	polynomial::{
+		transparent::{step_down::StepDown, step_up::StepUp},
+		EvaluationDomainFactory, MultilinearExtension,
+	},
+	protocols::sumcheck_v2::{
+		common::CompositeSumClaim,
+		error::Error,
+		window::{WindowedSumClaim, WindowedSumComposition},
+	},
+	witness::{MultilinearExtensionIndex, MultilinearWitness},
+};
+use binius_field::{
+	as_packed_field::{PackScalar, PackedType},
+	underlier::{UnderlierType, WithUnderlier},
+	BinaryField1b, ExtensionField, Field, TowerField,
+};
+use std::fmt::Debug;
+
+/// Adds the witnesses of the window selectors of a windowed sum claim to the witness index.
+///
+/// ## Throws
+///
+/// * [`crate::polynomial::Error::PackedFieldNotFilled`] if the selectors have fewer values than
+///   the underlier packs
+pub fn windowed_sum_witness<'a, U, FW>(
+	claim: &WindowedSumClaim<FW>,
+	witness_index: MultilinearExtensionIndex<'a, U, FW>,
+) -> Result<MultilinearExtensionIndex<'a, U, FW>, Error>
+where
+	U: UnderlierType + PackScalar<FW> + PackScalar<BinaryField1b> + Debug,
+	FW: TowerField + ExtensionField<BinaryField1b>,
+{
+	let n_vars = claim.meta().n_vars;
+	let window = claim.window();
+	let [_, step_down_id, step_up_id] = claim.meta().oracle_ids[..] else {
+		unreachable!("windowed sum claims are over three oracles");
+	};
+
+	let step_down = StepDown::new(n_vars, window.end)?
+		.multilinear_extension::<PackedType<U, BinaryField1b>>()?;
+	let step_up = StepUp::new(n_vars, window.start)?
+		.multilinear_extension::<PackedType<U, BinaryField1b>>()?;
+	let underliers = |selector: &MultilinearExtension<PackedType<U, BinaryField1b>>| {
+		PackedType::<U, BinaryField1b>::to_underliers_ref(selector.evals()).to_vec()
+	};
+
+	Ok(witness_index.update_owned::<BinaryField1b, _>([
+		(step_down_id, underliers(&step_down)),
+		(step_up_id, underliers(&step_up)),
+	])?)
+}
+
+/// Constructs a sumcheck prover for a windowed sum claim.
+///
+/// The witness index must contain the summed oracle and the window selectors, see
+/// [`windowed_sum_witness`].
+pub fn windowed_sum_prover<'a, U, FW, FDomain>(
+	claim: &WindowedSumClaim<FW>,
+	witness_index: &MultilinearExtensionIndex<'a, U, FW>,
+	evaluation_domain_factory: impl EvaluationDomainFactory<FDomain>,
+	switchover_fn: impl Fn(usize) -> usize,
+) -> Result<
+	RegularSumcheckProver<
+		FDomain,
+		PackedType<U, FW>,
+		WindowedSumComposition,
+		MultilinearWitness<'a, PackedType<U, FW>>,
+	>,
+	Error,
+>
+where
+	U: UnderlierType + PackScalar<FW>,
+	FW: TowerField + ExtensionField<FDomain>,
+	FDomain: Field,
+{
+	let multilinears = claim
+		.meta()
+		.oracle_ids
+		.iter()
+		.map(|&oracle_id| witness_index.get_multilin_poly(oracle_id))
+		.collect::<Result<Vec<_>, _>>()?;
+
+	RegularSumcheckProver::new(
+		multilinears,
+		[CompositeSumClaim {
+			composition: WindowedSumComposition,
+			sum: claim.sum(),
+		}],
+		evaluation_domain_factory,
+		switchover_fn,
+	)
+}
@@ -8,10 +8,12 @@ use crate::{
 	protocols::{
 		sumcheck_v2::{
 			prove::{
+				backend::{ComputeBackend, CpuBackend},
+				memory::MemoryBudget,
 				prover_state::{ProverState, SumcheckEvaluator},
 				SumcheckProver,
 			},
-			Error, RoundCoeffs,
+			Error, FoldDirection, RoundCoeffs, RoundEvals,
 		},
 		utils::packed_from_fn_with_offset,
 	},
@@ -23,6 +25,7 @@ use binius_field::{
 use binius_utils::bail;
 use itertools::izip;
 use rayon::prelude::*;
+use std::mem;
 
 pub fn validate_witness<F, P, M, Composition>(
 	multilinears: &[M],
@@ -61,16 +64,73 @@ where
 	Ok(())
 }
 
+/// How a [`ZerocheckProver`] stores the equality indicator of the variables after the current
+/// round.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EqIndStorage {
+	/// The equality indicator is expanded over the hypercube once and folded every round, taking
+	/// $2^{n-1}$ field elements for $n$ variables.
+	#[default]
+	Expanded,
+	/// The equality indicator is recomputed at every vertex it is needed, and only its expansions
+	/// over the lower and the upper half of the variables are stored, taking $O(2^{n/2})$ field
+	/// elements.
+	///
+	/// The equality indicator at a vertex is the product of the two expansions at the halves of
+	/// the vertex index, so each vertex costs one multiplication. The expansions are rebuilt from
+	/// the zerocheck challenges every round.
+	Recomputed,
+}
+
+impl EqIndStorage {
+	/// Selects the storage for a zerocheck over `n_vars` variables in the field `F`, recomputing
+	/// the equality indicator if its expansion takes more than `max_bytes`.
+	///
+	/// [`ZerocheckProver`] applies this to the memory budget it is given with
+	/// [`SumcheckProver::set_memory_budget`].
+	pub fn with_memory_limit<F: Field>(n_vars: usize, max_bytes: usize) -> Self {
+		let expansion_bytes = mem::size_of::<F>() << n_vars.saturating_sub(1);
+		if expansion_bytes > max_bytes {
+			Self::Recomputed
+		} else {
+			Self::Expanded
+		}
+	}
+}
+
+/// A sumcheck prover for the sumcheck claims that a zerocheck claim reduces to.
+///
+/// The zerocheck claim that the compositions vanish on the hypercube reduces, given the zerocheck
+/// challenges $\alpha$, to the claim that the compositions multiplied by the equality indicator
+/// $\mathrm{eq}(X, \alpha)$ sum to zero, see
+/// [`crate::protocols::sumcheck_v2::zerocheck::reduce_to_sumchecks`]. Following [Gruen24], the
+/// prover never materializes the product with the equality indicator:
+///
+/// * the equality indicator of the current round variable is factored out of the round
+///   polynomial and multiplied back in as a linear polynomial, and the equality indicator of the
+///   folded variables as a running scalar, so only the equality indicator of the remaining
+///   variables is expanded;
+/// * in the first round the round polynomial vanishes at 0 and 1, and in later rounds its value at
+///   0 follows from the claimed sum and its value at 1, so fewer evaluations are computed.
+///
+/// The evaluation of the equality indicator is appended to the multilinear evaluations returned
+/// by [`SumcheckProver::finish`], where the verifier checks it with
+/// [`crate::protocols::sumcheck_v2::zerocheck::verify_sumcheck_outputs`].
+///
+/// [Gruen24]: <https://eprint.iacr.org/2024/108>
 #[derive(Debug)]
-pub struct ZerocheckProver<FDomain, P, Composition, M>
+pub struct ZerocheckProver<FDomain, P, Composition, M, Backend = CpuBackend>
 where
 	FDomain: Field,
 	P: PackedField,
 	M: MultilinearPoly<P> + Send + Sync,
+	Backend: ComputeBackend,
 {
 	n_vars: usize,
-	state: ProverState<P, M>,
+	state: ProverState<P, M, Backend>,
 	eq_ind_eval: P::Scalar,
+	eq_ind_storage: EqIndStorage,
+	/// The expansion of the equality indicator, empty if it is recomputed.
 	partial_eq_ind_evals: Vec<P>,
 	zerocheck_challenges: Vec<P::Scalar>,
 	compositions: Vec<Composition>,
@@ -91,6 +151,62 @@ where
 		challenges: &[F],
 		evaluation_domain_factory: impl EvaluationDomainFactory<FDomain>,
 		switchover_fn: impl Fn(usize) -> usize,
+	) -> Result<Self, Error> {
+		Self::new_with_eq_ind_storage(
+			multilinears,
+			zero_claims,
+			challenges,
+			evaluation_domain_factory,
+			switchover_fn,
+			EqIndStorage::Expanded,
+		)
+	}
+
+	/// Constructs a prover storing the equality indicator as given.
+	///
+	/// Recomputing the equality indicator trades prover time for the memory of its expansion,
+	/// which is as large as a multilinear in the extension field. See
+	/// [`EqIndStorage::with_memory_limit`] to select the storage from a memory limit. The storage
+	/// also switches to [`EqIndStorage::Recomputed`] when the prover is given a memory budget too
+	/// small for the expansion.
+	pub fn new_with_eq_ind_storage(
+		multilinears: Vec<M>,
+		zero_claims: impl IntoIterator<Item = Composition>,
+		challenges: &[F],
+		evaluation_domain_factory: impl EvaluationDomainFactory<FDomain>,
+		switchover_fn: impl Fn(usize) -> usize,
+		eq_ind_storage: EqIndStorage,
+	) -> Result<Self, Error> {
+		Self::new_with_backend(
+			multilinears,
+			zero_claims,
+			challenges,
+			evaluation_domain_factory,
+			switchover_fn,
+			eq_ind_storage,
+			CpuBackend,
+		)
+	}
+}
+
+impl<F, FDomain, P, Composition, M, Backend> ZerocheckProver<FDomain, P, Composition, M, Backend>
+where
+	F: Field + ExtensionField<FDomain>,
+	FDomain: Field,
+	P: PackedFieldIndexable<Scalar = F>,
+	Composition: CompositionPoly<P>,
+	M: MultilinearPoly<P> + Send + Sync,
+	Backend: ComputeBackend,
+{
+	/// Constructs a prover that runs its hypercube loops on the given backend.
+	pub fn new_with_backend(
+		multilinears: Vec<M>,
+		zero_claims: impl IntoIterator<Item = Composition>,
+		challenges: &[F],
+		evaluation_domain_factory: impl EvaluationDomainFactory<FDomain>,
+		switchover_fn: impl Fn(usize) -> usize,
+		eq_ind_storage: EqIndStorage,
+		backend: Backend,
 	) -> Result<Self, Error> {
 		let compositions = zero_claims.into_iter().collect::<Vec<_>>();
 		for composition in compositions.iter() {
@@ -102,7 +218,14 @@ where
 		}
 
 		let claimed_sums = vec![F::ZERO; compositions.len()];
-		let state = ProverState::new(multilinears, claimed_sums, switchover_fn)?;
+		// The Gruen optimization folds the equality indicator low-to-high.
+		let state = ProverState::new(
+			multilinears,
+			claimed_sums,
+			switchover_fn,
+			FoldDirection::LowToHigh,
+			backend,
+		)?;
 		let n_vars = state.n_vars();
 
 		let domains = compositions
@@ -113,13 +236,18 @@ where
 			})
 			.collect::<Result<_, _>>()?;
 
-		let partial_eq_ind_evals =
-			MultilinearQuery::with_full_query(&challenges[1..])?.into_expansion();
+		let partial_eq_ind_evals = match eq_ind_storage {
+			EqIndStorage::Expanded => {
+				MultilinearQuery::with_full_query(&challenges[1..])?.into_expansion()
+			}
+			EqIndStorage::Recomputed => Vec::new(),
+		};
 
 		Ok(Self {
 			n_vars,
 			state,
 			eq_ind_eval: F::ONE,
+			eq_ind_storage,
 			partial_eq_ind_evals,
 			zerocheck_challenges: challenges.to_vec(),
 			compositions,
@@ -146,7 +274,7 @@ where
 
 	fn fold_partial_eq_ind(&mut self) {
 		let n_rounds_remaining = self.n_rounds_remaining();
-		if n_rounds_remaining == 0 {
+		if n_rounds_remaining == 0 || self.eq_ind_storage == EqIndStorage::Recomputed {
 			return;
 		}
 		if self.partial_eq_ind_evals.len() == 1 {
@@ -171,14 +299,15 @@ where
 	}
 }
 
-impl<F, FDomain, P, Composition, M> SumcheckProver<F>
-	for ZerocheckProver<FDomain, P, Composition, M>
+impl<F, FDomain, P, Composition, M, Backend> SumcheckProver<F>
+	for ZerocheckProver<FDomain, P, Composition, M, Backend>
 where
 	F: Field + ExtensionField<FDomain>,
 	FDomain: Field,
 	P: PackedFieldIndexable<Scalar = F> + PackedExtension<FDomain>,
 	Composition: CompositionPoly<P>,
 	M: MultilinearPoly<P> + Send + Sync,
+	Backend: ComputeBackend,
 {
 	fn n_vars(&self) -> usize {
 		self.n_vars
@@ -196,13 +325,30 @@ where
 
 	fn execute(&mut self, batch_coeff: F) -> Result<RoundCoeffs<F>, Error> {
 		let round = self.round();
+		let (low_evals, high_evals);
+		let partial_eq_ind = match self.eq_ind_storage {
+			EqIndStorage::Expanded => PartialEqInd::Expanded(&self.partial_eq_ind_evals),
+			EqIndStorage::Recomputed => {
+				let challenges = &self.zerocheck_challenges[round + 1..];
+				let n_low_vars = challenges.len() / 2;
+				low_evals = MultilinearQuery::<F>::with_full_query(&challenges[..n_low_vars])?
+					.into_expansion();
+				high_evals = MultilinearQuery::<F>::with_full_query(&challenges[n_low_vars..])?
+					.into_expansion();
+				PartialEqInd::Recomputed {
+					n_low_vars,
+					low_evals: &low_evals,
+					high_evals: &high_evals,
+				}
+			}
+		};
 		let coeffs = if round == 0 {
 			let evaluators = izip!(&self.compositions, &self.domains)
 				.map(|(composition, evaluation_domain)| ZerocheckFirstRoundEvaluator {
 					composition,
 					evaluation_domain,
 					domain_points: evaluation_domain.points(),
-					partial_eq_ind_evals: &self.partial_eq_ind_evals,
+					partial_eq_ind,
 				})
 				.collect::<Vec<_>>();
 			self.state
@@ -213,7 +359,7 @@ where
 					composition,
 					evaluation_domain,
 					domain_points: evaluation_domain.points(),
-					partial_eq_ind_evals: &self.partial_eq_ind_evals,
+					partial_eq_ind,
 					round_zerocheck_challenge: self.zerocheck_challenges[round],
 				})
 				.collect::<Vec<_>>();
@@ -238,11 +384,67 @@ where
 		Ok(sumcheck_coeffs * self.eq_ind_eval)
 	}
 
+	fn scratch_bytes(&self) -> usize {
+		self.state.scratch_bytes()
+	}
+
+	fn set_memory_budget(&mut self, memory_budget: MemoryBudget) {
+		// The expansion is dropped if it does not fit in the budget.
+		if let Some(max_bytes) = memory_budget.max_scratch_bytes() {
+			let n_rounds_remaining = self.n_rounds_remaining();
+			if EqIndStorage::with_memory_limit::<F>(n_rounds_remaining, max_bytes)
+				== EqIndStorage::Recomputed
+			{
+				self.eq_ind_storage = EqIndStorage::Recomputed;
+				self.partial_eq_ind_evals = Vec::new();
+			}
+		}
+		self.state.set_memory_budget(memory_budget);
+	}
+
 	fn finish(self) -> Result<Vec<F>, Error> {
 		let mut evals = self.state.finish()?;
 		evals.push(self.eq_ind_eval);
 		Ok(evals)
 	}
+
+	fn finish_boxed(self: Box<Self>) -> Result<Vec<F>, Error> {
+		(*self).finish()
+	}
+}
+
+/// The equality indicator of the variables after the current round, at the zerocheck challenges
+/// of those variables.
+#[derive(Clone, Copy)]
+enum PartialEqInd<'a, P: PackedField> {
+	Expanded(&'a [P]),
+	/// The expansions of the equality indicator over the lowest `n_low_vars` variables and over
+	/// the other variables.
+	Recomputed {
+		n_low_vars: usize,
+		low_evals: &'a [P::Scalar],
+		high_evals: &'a [P::Scalar],
+	},
+}
+
+impl<'a, P: PackedField> PartialEqInd<'a, P> {
+	/// The packed evaluations of the equality indicator at the `i`-th packed hypercube offset.
+	fn packed_eval(&self, i: usize) -> P {
+		match self {
+			Self::Expanded(evals) => evals[i],
+			Self::Recomputed {
+				n_low_vars,
+				low_evals,
+				high_evals,
+			} => packed_from_fn_with_offset(i, |index| {
+				// The masks keep the padding lanes of a packed element past the end of the
+				// hypercube in bounds.
+				let low = index & (low_evals.len() - 1);
+				let high = (index >> n_low_vars) & (high_evals.len() - 1);
+				low_evals[low] * high_evals[high]
+			}),
+		}
+	}
 }
 
 struct ZerocheckFirstRoundEvaluator<'a, P, FDomain, Composition>
@@ -253,7 +455,7 @@ where
 	composition: &'a Composition,
 	evaluation_domain: &'a EvaluationDomain<FDomain>,
 	domain_points: &'a [FDomain],
-	partial_eq_ind_evals: &'a [P],
+	partial_eq_ind: PartialEqInd<'a, P>,
 }
 
 impl<'a, F, P, FDomain, Composition> SumcheckEvaluator<P>
@@ -264,6 +466,10 @@ where
 	FDomain: Field,
 	Composition: CompositionPoly<P>,
 {
+	fn degree(&self) -> usize {
+		self.composition.degree()
+	}
+
 	fn n_round_evals(&self) -> usize {
 		// In the first round of zerocheck we can uniquely determine the degree d
 		// univariate round polynomial $R(X)$ with evaluations at X = 2, ..., d
@@ -279,7 +485,7 @@ where
 		evals_z: &mut [P],
 		round_evals: &mut [P],
 	) {
-		let eq_ind_factor = self.partial_eq_ind_evals[i];
+		let eq_ind_factor = self.partial_eq_ind.packed_eval(i);
 
 		for d in 2..=self.composition.degree() {
 			evals_0
@@ -310,8 +516,8 @@ where
 		round_evals.insert(0, P::Scalar::ZERO);
 		round_evals.insert(0, P::Scalar::ZERO);
 
-		let coeffs = self.evaluation_domain.interpolate(&round_evals)?;
-		Ok(coeffs)
+		let round_evals = RoundEvals(round_evals);
+		Ok(round_evals.to_coeffs(self.evaluation_domain)?.0)
 	}
 }
 
@@ -323,7 +529,7 @@ where
 	composition: &'a Composition,
 	evaluation_domain: &'a EvaluationDomain<FDomain>,
 	domain_points: &'a [FDomain],
-	partial_eq_ind_evals: &'a [P],
+	partial_eq_ind: PartialEqInd<'a, P>,
 	round_zerocheck_challenge: P::Scalar,
 }
 
@@ -335,6 +541,10 @@ where
 	FDomain: Field,
 	Composition: CompositionPoly<P>,
 {
+	fn degree(&self) -> usize {
+		self.composition.degree()
+	}
+
 	fn n_round_evals(&self) -> usize {
 		// We can uniquely derive the degree d univariate round polynomial r from evaluations at
 		// X = 1, ..., d because we have an identity that relates r(0), r(1), and the current
@@ -350,7 +560,7 @@ where
 		evals_z: &mut [P],
 		round_evals: &mut [P],
 	) {
-		let eq_ind_factor = self.partial_eq_ind_evals[i];
+		let eq_ind_factor = self.partial_eq_ind.packed_eval(i);
 
 		let composite_value = self
 			.composition
@@ -394,7 +604,7 @@ where
 
 		round_evals.insert(0, zero_evaluation);
 
-		let coeffs = self.evaluation_domain.interpolate(&round_evals)?;
-		Ok(coeffs)
+		let round_evals = RoundEvals(round_evals);
+		Ok(round_evals.to_coeffs(self.evaluation_domain)?.0)
 	}
 }
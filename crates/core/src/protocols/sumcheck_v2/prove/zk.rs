@@ -0,0 +1,209 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use crate::{
+	challenger::{CanObserve, CanSample, CanSampleBits},
+	poly_commit::PolyCommitScheme,
+	polynomial::evaluate_univariate,
+	protocols::sumcheck_v2::{
+		common::RoundCoeffs,
+		error::Error,
+		prove::{prove_single, MemoryBudget, SumcheckProver},
+		zk::{mask_opening_point, MaskPolynomial, ZkProof, ZkSumcheckOutput},
+	},
+};
+use binius_field::{Field, PackedField};
+use binius_utils::bail;
+use getset::Getters;
+use rand::RngCore;
+
+/// A prover for the zero-knowledge sumcheck, see [`crate::protocols::sumcheck_v2::zk`].
+///
+/// The prover wraps the prover of the unmasked claim and adds the round polynomials of the scaled
+/// mask to its round polynomials. The mask has the product structure described in
+/// [`MaskPolynomial`], so its round polynomials are computed in closed form from the evaluation
+/// of the mask restricted to the bound variables and the sums of the univariates of the free
+/// variables, without ever materializing the mask over the hypercube.
+///
+/// The degree of the mask must be [`crate::protocols::sumcheck_v2::zk::mask_degree`] of the
+/// claim, so that the mask does not change the number of coefficients of the round polynomials.
+#[derive(Debug, Getters)]
+pub struct ZkSumcheckProver<F: Field, Prover> {
+	inner: Prover,
+	#[getset(get = "pub")]
+	mask: MaskPolynomial<F>,
+	mask_coeff: F,
+	/// The sums $\sum_{i > j} g_i(0) + g_i(1)$ of the univariates after each variable $j$.
+	suffix_sums: Vec<F>,
+	/// The evaluation of the mask restricted to the bound variables at the challenges.
+	prefix_eval: F,
+	/// The product of the challenges.
+	prefix_prod: F,
+	round: usize,
+}
+
+impl<F, Prover> ZkSumcheckProver<F, Prover>
+where
+	F: Field,
+	Prover: SumcheckProver<F>,
+{
+	/// Wraps the prover of the unmasked claim, sampling a random mask of degree `degree`.
+	pub fn new(inner: Prover, degree: usize, rng: impl RngCore) -> Result<Self, Error> {
+		let mask = MaskPolynomial::random(inner.n_vars(), degree, rng);
+		Self::with_mask(inner, mask)
+	}
+
+	/// Wraps the prover of the unmasked claim with a given mask.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::NumberOfVariablesMismatch`] if the mask and the prover have different numbers
+	///   of variables
+	pub fn with_mask(inner: Prover, mask: MaskPolynomial<F>) -> Result<Self, Error> {
+		if mask.n_vars() != inner.n_vars() {
+			bail!(Error::NumberOfVariablesMismatch);
+		}
+
+		let mut suffix_sums = vec![F::ZERO; mask.n_vars()];
+		for i in (1..mask.n_vars()).rev() {
+			let coeffs = &mask.univariates()[i];
+			suffix_sums[i - 1] = suffix_sums[i]
+				+ evaluate_univariate(coeffs, F::ZERO)
+				+ evaluate_univariate(coeffs, F::ONE);
+		}
+
+		Ok(Self {
+			inner,
+			mask,
+			mask_coeff: F::ZERO,
+			suffix_sums,
+			prefix_eval: F::ZERO,
+			prefix_prod: F::ONE,
+			round: 0,
+		})
+	}
+
+	/// The round polynomial of the mask, $P g_j(X) + X (E + P S_j)$, where $P$ is the product of
+	/// the challenges, $E$ is the restricted evaluation of the mask and $S_j$ is the suffix sum.
+	fn mask_round_coeffs(&self) -> RoundCoeffs<F> {
+		let univariate = &self.mask.univariates()[self.round];
+		let mut coeffs = RoundCoeffs(univariate.clone()) * self.prefix_prod;
+		if coeffs.0.len() < 2 {
+			coeffs.0.resize(2, F::ZERO);
+		}
+		coeffs.0[1] += self.prefix_eval + self.prefix_prod * self.suffix_sums[self.round];
+		coeffs
+	}
+}
+
+impl<F, Prover> SumcheckProver<F> for ZkSumcheckProver<F, Prover>
+where
+	F: Field,
+	Prover: SumcheckProver<F>,
+{
+	fn n_vars(&self) -> usize {
+		self.inner.n_vars()
+	}
+
+	fn execute(&mut self, batch_coeff: F) -> Result<RoundCoeffs<F>, Error> {
+		let coeffs = self.inner.execute(batch_coeff)?;
+		// The mask is added to the first composite, which has weight one in the batch.
+		Ok(coeffs + &(self.mask_round_coeffs() * self.mask_coeff))
+	}
+
+	fn fold(&mut self, challenge: F) -> Result<(), Error> {
+		self.inner.fold(challenge)?;
+
+		let univariate = &self.mask.univariates()[self.round];
+		self.prefix_eval = self.prefix_eval * challenge
+			+ evaluate_univariate(univariate, challenge) * self.prefix_prod;
+		self.prefix_prod *= challenge;
+		self.round += 1;
+		Ok(())
+	}
+
+	fn scratch_bytes(&self) -> usize {
+		self.inner.scratch_bytes()
+	}
+
+	fn set_memory_budget(&mut self, memory_budget: MemoryBudget) {
+		self.inner.set_memory_budget(memory_budget);
+	}
+
+	fn finish(self) -> Result<Vec<F>, Error> {
+		let mut multilinear_evals = self.inner.finish()?;
+		multilinear_evals.push(self.prefix_eval);
+		Ok(multilinear_evals)
+	}
+
+	fn finish_boxed(self: Box<Self>) -> Result<Vec<F>, Error> {
+		(*self).finish()
+	}
+}
+
+/// Prove a zero-knowledge sumcheck protocol execution for a single claim.
+///
+/// The mask of the prover is committed with `pcs` and the commitment is observed with the
+/// challenger before the mixing coefficient is sampled. After the sumcheck, the univariates of the
+/// mask are opened at their challenges. The proof is verified with
+/// [`crate::protocols::sumcheck_v2::zk::verify_zk`].
+///
+/// ## Throws
+///
+/// * [`Error::MaskCommitmentTooSmall`] if the mask does not fit into `pcs`
+/// * [`Error::MaskCommitment`] if committing to or opening the mask fails
+pub fn prove_zk<F, P, Prover, PCS, Challenger>(
+	mut prover: ZkSumcheckProver<F, Prover>,
+	pcs: &PCS,
+	mut challenger: Challenger,
+) -> Result<(ZkSumcheckOutput<F>, ZkProof<F, PCS::Commitment, PCS::Proof>), Error>
+where
+	F: Field,
+	P: PackedField<Scalar = F>,
+	Prover: SumcheckProver<F>,
+	PCS: PolyCommitScheme<P, F>,
+	Challenger: CanObserve<F> + CanObserve<PCS::Commitment> + CanSample<F> + CanSampleBits<usize>,
+{
+	let mask = prover.mask.clone();
+	let mask_polys = [mask.coeffs_multilinear::<P>(pcs.n_vars())?];
+	let (mask_commitment, mask_committed) = pcs
+		.commit(&mask_polys)
+		.map_err(|err| Error::MaskCommitment(Box::new(err)))?;
+
+	let mask_sum = mask.sum();
+	challenger.observe(mask_commitment.clone());
+	challenger.observe(mask_sum);
+	prover.mask_coeff = challenger.sample();
+
+	let (output, proof) = prove_single(prover, &mut challenger)?;
+
+	let mut multilinear_evals = output.multilinear_evals;
+	multilinear_evals
+		.pop()
+		.expect("the masked prover appends the mask evaluation");
+
+	let univariate_evals = mask.univariate_evals(&output.challenges)?;
+	let opening_proofs = output
+		.challenges
+		.iter()
+		.enumerate()
+		.map(|(index, &challenge)| {
+			let point =
+				mask_opening_point(mask.n_vars(), mask.degree(), pcs.n_vars(), index, challenge)?;
+			pcs.prove_evaluation(&mut challenger, &mask_committed, &mask_polys, &point)
+				.map_err(|err| Error::MaskCommitment(Box::new(err)))
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+
+	let output = ZkSumcheckOutput {
+		challenges: output.challenges,
+		multilinear_evals,
+	};
+	let proof = ZkProof {
+		mask_commitment,
+		mask_sum,
+		proof,
+		univariate_evals,
+		opening_proofs,
+	};
+	Ok((output, proof))
+}
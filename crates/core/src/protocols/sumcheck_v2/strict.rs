@@ -0,0 +1,228 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Strict verification of batched sumcheck proofs.
+//!
+//! [`batch_verify`] checks the structure of a proof as it goes, so a malformed proof is rejected
+//! at the first inconsistency it happens to reach. [`batch_verify_strict`] is an audit mode for
+//! high-assurance deployments. It first derives the complete [`ProofShape`] from the claims alone,
+//! re-validating the claims themselves instead of trusting their cached metadata, and checks every
+//! field of the proof against the shape before any proof data reaches the challenger. Only then is
+//! the proof verified, and the output is cross-checked against the shape once more.
+
+use super::{
+	common::{BatchSumcheckOutput, Proof, SumcheckClaim},
+	error::{Error, VerificationError},
+	features::check_protocol_features,
+	verify::batch_verify,
+};
+use crate::{
+	challenger::{CanObserve, CanSample},
+	polynomial::CompositionPoly,
+};
+use binius_field::Field;
+use binius_utils::{bail, sorting::is_sorted_ascending};
+
+/// The shape of a well-formed proof for a batch of sumcheck claims, derived from the claims alone.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofShape {
+	/// The degree of the round polynomial in each round, which is the maximum individual degree of
+	/// the claims that are active in the round.
+	pub round_degrees: Vec<usize>,
+	/// The number of multilinear evaluations of each claim in the proof, which excludes the
+	/// transparent multilinears.
+	pub n_multilinear_evals: Vec<usize>,
+	/// The number of claims, which disclosed claim labels must match.
+	pub n_claims: usize,
+}
+
+impl ProofShape {
+	/// Derives the proof shape from the claims of a batch.
+	///
+	/// The claims are re-validated rather than trusted: the ordering of the batch, the number of
+	/// variables of every composition, and every recorded composite degree are recomputed from the
+	/// composition polynomials.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::ClaimsOutOfOrder`] if the claims are not in descending order by number of
+	///   variables
+	/// * [`Error::IncorrectNumberOfCompositeDegrees`] if a claim does not record one degree per
+	///   composite
+	/// * [`Error::InvalidComposition`] if a composition is not over the multilinears of its claim
+	/// * [`Error::InvalidCompositionDegree`] if a recorded degree does not match its composition
+	pub fn from_claims<F, Composition>(
+		claims: &[SumcheckClaim<F, Composition>],
+	) -> Result<Self, Error>
+	where
+		F: Field,
+		Composition: CompositionPoly<F>,
+	{
+		if !is_sorted_ascending(claims.iter().map(|claim| claim.n_vars()).rev()) {
+			bail!(Error::ClaimsOutOfOrder);
+		}
+
+		let mut max_degrees = Vec::with_capacity(claims.len());
+		for claim in claims.iter() {
+			let composite_sums = claim.composite_sums();
+			if claim.composite_degrees().len() != composite_sums.len() {
+				bail!(Error::IncorrectNumberOfCompositeDegrees {
+					expected: composite_sums.len(),
+				});
+			}
+
+			let mut max_degree = 0;
+			for (index, (composite_sum, &recorded_degree)) in composite_sums
+				.iter()
+				.zip(claim.composite_degrees())
+				.enumerate()
+			{
+				let composition = &composite_sum.composition;
+				if composition.n_vars() != claim.n_multilinears() {
+					bail!(Error::InvalidComposition {
+						expected_n_vars: claim.n_multilinears(),
+					});
+				}
+				let degree = composition.degree();
+				if recorded_degree != degree {
+					bail!(Error::InvalidCompositionDegree {
+						index,
+						expected: degree,
+					});
+				}
+				max_degree = max_degree.max(degree);
+			}
+			max_degrees.push(max_degree);
+		}
+
+		let n_rounds = claims.first().map(|claim| claim.n_vars()).unwrap_or(0);
+		let round_degrees = (0..n_rounds)
+			.map(|round_no| {
+				let n_vars = n_rounds - round_no;
+				claims
+					.iter()
+					.zip(max_degrees.iter())
+					.take_while(|(claim, _)| claim.n_vars() >= n_vars)
+					.map(|(_, &max_degree)| max_degree)
+					.max()
+					.unwrap_or(0)
+			})
+			.collect();
+
+		Ok(Self {
+			round_degrees,
+			n_multilinear_evals: claims
+				.iter()
+				.map(|claim| claim.n_committed_multilinears())
+				.collect(),
+			n_claims: claims.len(),
+		})
+	}
+
+	pub fn n_rounds(&self) -> usize {
+		self.round_degrees.len()
+	}
+
+	/// Checks every field of a proof against the shape.
+	///
+	/// ## Throws
+	///
+	/// * [`VerificationError::UnsupportedProtocolFeatures`] if the proof uses unknown features
+	/// * [`VerificationError::NumberOfRounds`] if the proof has the wrong number of rounds
+	/// * [`VerificationError::NumberOfCoefficients`] if a round proof has the wrong number of
+	///   coefficients for the degree of the round and the round proof format, see
+	///   [`super::ProtocolFeatures::round_proof_n_coeffs`]
+	/// * [`VerificationError::NumberOfFinalEvaluations`] if the proof does not have evaluations
+	///   for every claim
+	/// * [`VerificationError::NumberOfClaimEvaluations`] if the evaluations of a claim do not
+	///   match its multilinears
+	/// * [`VerificationError::ClaimLabelMismatch`] if the proof discloses a different number of
+	///   labels than there are claims
+	pub fn check<F: Field>(&self, proof: &Proof<F>) -> Result<(), VerificationError> {
+		let Proof {
+			features,
+			rounds,
+			multilinear_evals,
+			claim_labels,
+		} = proof;
+
+		check_protocol_features(*features)?;
+
+		if rounds.len() != self.n_rounds() {
+			bail!(VerificationError::NumberOfRounds);
+		}
+		for (round, (round_proof, &degree)) in rounds.iter().zip(&self.round_degrees).enumerate() {
+			let n_coeffs = features.round_proof_n_coeffs(degree);
+			if !n_coeffs.contains(&round_proof.coeffs().len()) {
+				bail!(VerificationError::NumberOfCoefficients {
+					round,
+					expected: *n_coeffs.end(),
+				});
+			}
+		}
+
+		if multilinear_evals.len() != self.n_multilinear_evals.len() {
+			bail!(VerificationError::NumberOfFinalEvaluations);
+		}
+		for (claim_index, (evals, &n_evals)) in multilinear_evals
+			.iter()
+			.zip(&self.n_multilinear_evals)
+			.enumerate()
+		{
+			if evals.len() != n_evals {
+				bail!(VerificationError::NumberOfClaimEvaluations {
+					claim_index,
+					expected: n_evals,
+				});
+			}
+		}
+
+		if let Some(labels) = claim_labels {
+			if labels.len() != self.n_claims {
+				bail!(VerificationError::ClaimLabelMismatch);
+			}
+		}
+		Ok(())
+	}
+
+	/// Checks the output of a verified batch against the shape.
+	fn check_output<F: Field>(&self, output: &BatchSumcheckOutput<F>) -> Result<(), Error> {
+		if output.challenges.len() != self.n_rounds() {
+			bail!(VerificationError::NumberOfRounds);
+		}
+		if output.multilinear_evals.len() != self.n_multilinear_evals.len()
+			|| output
+				.multilinear_evals
+				.iter()
+				.zip(&self.n_multilinear_evals)
+				.any(|(evals, &n_evals)| evals.len() != n_evals)
+		{
+			bail!(VerificationError::NumberOfFinalEvaluations);
+		}
+		Ok(())
+	}
+}
+
+/// Verify a batched sumcheck protocol execution in audit mode.
+///
+/// This accepts exactly the proofs that [`batch_verify`] accepts. Instead of checking the
+/// structure of the proof as verification reaches it, the [`ProofShape`] is derived from the claims
+/// alone and the whole proof is checked against it before any proof data is observed by the
+/// challenger. The claims are re-validated in the process, and the output is checked against the
+/// shape after verification. This costs extra verifier work, which is small next to the
+/// verification itself.
+pub fn batch_verify_strict<F, Composition, Challenger>(
+	claims: &[SumcheckClaim<F, Composition>],
+	proof: Proof<F>,
+	challenger: Challenger,
+) -> Result<BatchSumcheckOutput<F>, Error>
+where
+	F: Field,
+	Composition: CompositionPoly<F>,
+	Challenger: CanObserve<F> + CanSample<F>,
+{
+	let shape = ProofShape::from_claims(claims)?;
+	shape.check(&proof)?;
+	let output = batch_verify(claims, proof, challenger)?;
+	shape.check_output(&output)?;
+	Ok(output)
+}
@@ -0,0 +1,269 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! A reference sumcheck prover for testing optimized prover implementations.
+//!
+//! [`NaiveSumcheckProver`] computes every round polynomial directly from the definition in
+//! $O(d \cdot 2^n)$ time per composite of degree $d$, without switchover, packing or parallelism.
+//! [`assert_prover_matches_reference`] drives a prover under test and the reference prover through
+//! the same rounds and checks that they agree on every round polynomial and on the final
+//! multilinear evaluations, so that a new prover optimization fails in the round it diverges.
+
+use super::{
+	common::{CompositeSumClaim, FoldDirection, RoundCoeffs, RoundEvals},
+	error::Error,
+	prove::SumcheckProver,
+};
+use crate::polynomial::{
+	CompositionPoly, EvaluationDomain, EvaluationDomainFactory, MultilinearPoly,
+};
+use binius_field::{util::powers, ExtensionField, Field, PackedField};
+use binius_utils::bail;
+use rand::Rng;
+use std::marker::PhantomData;
+
+/// A sumcheck prover that computes the round polynomials naively over the scalar evaluations of
+/// the multilinears.
+#[derive(Debug)]
+pub struct NaiveSumcheckProver<FDomain, P, Composition>
+where
+	FDomain: Field,
+	P: PackedField,
+{
+	n_vars: usize,
+	/// The evaluations of the partially folded multilinears over the remaining hypercube.
+	multilinears: Vec<Vec<P::Scalar>>,
+	compositions: Vec<Composition>,
+	domains: Vec<EvaluationDomain<FDomain>>,
+	fold_direction: FoldDirection,
+	round: usize,
+	executed: bool,
+	_p_marker: PhantomData<P>,
+}
+
+impl<F, FDomain, P, Composition> NaiveSumcheckProver<FDomain, P, Composition>
+where
+	F: Field + ExtensionField<FDomain>,
+	FDomain: Field,
+	P: PackedField<Scalar = F>,
+	Composition: CompositionPoly<P>,
+{
+	pub fn new<M: MultilinearPoly<P>>(
+		multilinears: &[M],
+		composite_claims: impl IntoIterator<Item = CompositeSumClaim<F, Composition>>,
+		evaluation_domain_factory: impl EvaluationDomainFactory<FDomain>,
+	) -> Result<Self, Error> {
+		Self::new_with_fold_direction(
+			multilinears,
+			composite_claims,
+			evaluation_domain_factory,
+			FoldDirection::LowToHigh,
+		)
+	}
+
+	/// Constructs a reference prover that folds the variables in the given direction.
+	///
+	/// The claimed sums are not used, the reference prover only computes the round polynomials of
+	/// the witness.
+	pub fn new_with_fold_direction<M: MultilinearPoly<P>>(
+		multilinears: &[M],
+		composite_claims: impl IntoIterator<Item = CompositeSumClaim<F, Composition>>,
+		evaluation_domain_factory: impl EvaluationDomainFactory<FDomain>,
+		fold_direction: FoldDirection,
+	) -> Result<Self, Error> {
+		let n_vars = multilinears
+			.first()
+			.map(|multilinear| multilinear.n_vars())
+			.unwrap_or_default();
+		let multilinears = multilinears
+			.iter()
+			.map(|multilinear| {
+				if multilinear.n_vars() != n_vars {
+					bail!(Error::NumberOfVariablesMismatch);
+				}
+				(0..1 << n_vars)
+					.map(|index| multilinear.evaluate_on_hypercube(index))
+					.collect::<Result<Vec<_>, _>>()
+					.map_err(Error::from)
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+
+		let compositions = composite_claims
+			.into_iter()
+			.map(|composite_claim| composite_claim.composition)
+			.collect::<Vec<_>>();
+		for composition in compositions.iter() {
+			if composition.n_vars() != multilinears.len() {
+				bail!(Error::InvalidComposition {
+					expected_n_vars: multilinears.len(),
+				});
+			}
+		}
+		let domains = compositions
+			.iter()
+			.map(|composition| evaluation_domain_factory.create(composition.degree() + 1))
+			.collect::<Result<Vec<_>, _>>()?;
+
+		Ok(Self {
+			n_vars,
+			multilinears,
+			compositions,
+			domains,
+			fold_direction,
+			round: 0,
+			executed: false,
+			_p_marker: PhantomData,
+		})
+	}
+
+	/// Returns the pairs of hypercube indices that differ only in the variable of this round.
+	fn vertex_pairs(&self) -> impl Iterator<Item = (usize, usize)> {
+		let half = 1 << (self.n_vars - self.round - 1);
+		let fold_direction = self.fold_direction;
+		(0..half).map(move |i| match fold_direction {
+			FoldDirection::LowToHigh => (2 * i, 2 * i + 1),
+			FoldDirection::HighToLow => (i, i + half),
+		})
+	}
+
+	fn composite_round_coeffs(
+		&self,
+		composition: &Composition,
+		domain: &EvaluationDomain<FDomain>,
+	) -> Result<RoundCoeffs<F>, Error> {
+		let mut query = vec![P::zero(); self.multilinears.len()];
+		let round_evals = domain
+			.points()
+			.iter()
+			.map(|&point| {
+				let mut sum = F::ZERO;
+				for (index_0, index_1) in self.vertex_pairs() {
+					for (value, multilinear) in query.iter_mut().zip(&self.multilinears) {
+						let (eval_0, eval_1) = (multilinear[index_0], multilinear[index_1]);
+						*value = P::broadcast(eval_0 + (eval_1 - eval_0) * point);
+					}
+					sum += composition.evaluate(&query)?.get(0);
+				}
+				Ok(sum)
+			})
+			.collect::<Result<Vec<_>, Error>>()?;
+		Ok(RoundEvals(round_evals).to_coeffs(domain)?)
+	}
+}
+
+impl<F, FDomain, P, Composition> SumcheckProver<F> for NaiveSumcheckProver<FDomain, P, Composition>
+where
+	F: Field + ExtensionField<FDomain>,
+	FDomain: Field,
+	P: PackedField<Scalar = F>,
+	Composition: CompositionPoly<P>,
+{
+	fn n_vars(&self) -> usize {
+		self.n_vars
+	}
+
+	fn execute(&mut self, batch_coeff: F) -> Result<RoundCoeffs<F>, Error> {
+		if self.executed {
+			bail!(Error::ExpectedFold);
+		}
+		if self.round == self.n_vars {
+			bail!(Error::ExpectedFinish);
+		}
+
+		let mut round_coeffs = RoundCoeffs::default();
+		for ((composition, domain), scalar) in self
+			.compositions
+			.iter()
+			.zip(&self.domains)
+			.zip(powers(batch_coeff))
+		{
+			round_coeffs += &(self.composite_round_coeffs(composition, domain)? * scalar);
+		}
+		self.executed = true;
+		Ok(round_coeffs)
+	}
+
+	fn fold(&mut self, challenge: F) -> Result<(), Error> {
+		if !self.executed {
+			bail!(Error::ExpectedExecution);
+		}
+
+		let pairs = self.vertex_pairs().collect::<Vec<_>>();
+		for multilinear in self.multilinears.iter_mut() {
+			*multilinear = pairs
+				.iter()
+				.map(|&(index_0, index_1)| {
+					let (eval_0, eval_1) = (multilinear[index_0], multilinear[index_1]);
+					eval_0 + (eval_1 - eval_0) * challenge
+				})
+				.collect();
+		}
+		self.round += 1;
+		self.executed = false;
+		Ok(())
+	}
+
+	fn fold_direction(&self) -> FoldDirection {
+		self.fold_direction
+	}
+
+	fn finish(self) -> Result<Vec<F>, Error> {
+		if self.executed {
+			bail!(Error::ExpectedFold);
+		}
+		if self.round != self.n_vars {
+			bail!(Error::ExpectedExecution);
+		}
+		Ok(self
+			.multilinears
+			.into_iter()
+			.map(|multilinear| multilinear[0])
+			.collect())
+	}
+
+	fn finish_boxed(self: Box<Self>) -> Result<Vec<F>, Error> {
+		(*self).finish()
+	}
+}
+
+/// Runs `prover` and `reference` on the same random batching coefficient and challenges, and
+/// asserts that they agree on every round.
+///
+/// The round polynomials are compared after [`RoundCoeffs::trim`], so a prover may return round
+/// polynomials padded with zero coefficients.
+///
+/// ## Panics
+///
+/// Panics if either prover fails, or if the provers disagree on the number of variables, the
+/// fold direction, a round polynomial or the final multilinear evaluations.
+pub fn assert_prover_matches_reference<F, Prover, Reference>(
+	mut prover: Prover,
+	mut reference: Reference,
+	mut rng: impl Rng,
+) where
+	F: Field,
+	Prover: SumcheckProver<F>,
+	Reference: SumcheckProver<F>,
+{
+	assert_eq!(prover.n_vars(), reference.n_vars(), "number of variables differs");
+	assert_eq!(prover.fold_direction(), reference.fold_direction(), "fold direction differs");
+
+	let batch_coeff = F::random(&mut rng);
+	for round in 0..reference.n_vars() {
+		let round_coeffs = prover
+			.execute(batch_coeff)
+			.unwrap_or_else(|err| panic!("prover failed to execute round {round}: {err}"));
+		let expected = reference.execute(batch_coeff).unwrap();
+		assert_eq!(round_coeffs.trim(), expected.trim(), "round {round} polynomial differs");
+
+		let challenge = F::random(&mut rng);
+		prover
+			.fold(challenge)
+			.unwrap_or_else(|err| panic!("prover failed to fold round {round}: {err}"));
+		reference.fold(challenge).unwrap();
+	}
+
+	let multilinear_evals = prover
+		.finish()
+		.unwrap_or_else(|err| panic!("prover failed to finish: {err}"));
+	assert_eq!(multilinear_evals, reference.finish().unwrap(), "multilinear evaluations differ");
+}
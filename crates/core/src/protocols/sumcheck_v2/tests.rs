@@ -1,28 +1,69 @@
 // Copyright 2024 Ulvetanna Inc.
 
 use super::{
+	batch_verify_split, batch_verify_strict, batch_verify_with_observer, batch_verify_with_report,
 	common::CompositeSumClaim,
-	prove::{batch_prove, RegularSumcheckProver},
-	verify::batch_verify,
-	BatchSumcheckOutput, SumcheckClaim,
+	error::{Error, VerificationError},
+	export_external_eval_claims, external_multilinear, make_eval_claims, make_split_eval_claims,
+	prove::{
+		batch_prove, batch_prove_dyn, batch_prove_labeled, batch_prove_split, batch_prove_unsorted,
+		batch_prove_with_checkpoints, batch_prove_with_features, batch_prove_with_memory_budget,
+		batch_prove_with_metrics, prove_single, resume_batch_prove, windowed_sum_prover,
+		windowed_sum_witness, BatchProveCheckpoint, ComputeBackend, CpuBackend, MemoryBudget,
+		ProverSnapshot, ReadSource, RegularSumcheckProver, RemoteError, RemoteRoundProver,
+		RemoteSumcheckProver, ScratchArena, SharedSumcheckProver, StreamingSumcheckProver,
+		SumcheckEvaluator, SumcheckProver, ZerocheckProver,
+	},
+	testing::{assert_prover_matches_reference, NaiveSumcheckProver},
+	verify::{
+		batch_verify, batch_verify_unsorted, batch_verify_with_fold_direction, verify_round_evals,
+		verify_single,
+	},
+	windowed_sum_claim,
+	zerocheck::ExtraProduct,
+	BatchSumcheckOutput, ClaimLabel, ClaimVerdict, DecodingError, EnvelopeHeader,
+	ExternalMultilinear, ExternalOracle, FoldDirection, IdentityTransform, MultilinearOrigin,
+	OracleClaimMeta, Proof, ProofEncoding, ProofShape, ProofTransform, ProtocolFeatures,
+	RoundCoeffs, RoundEvals, RoundProof, SumcheckClaim, SumcheckOutput, TransformError,
+	ENVELOPE_VERSION,
 };
 use crate::{
 	challenger::{new_hasher_challenger, CanSample},
+	oracle::{Error as OracleError, MultilinearOracleSet},
+	params::ProveParams,
 	polynomial::{
-		composition::index_composition, CompositionPoly, Error as PolynomialError,
+		composition::index_composition,
+		evaluate_univariate,
+		transparent::{step_down::StepDown, step_up::StepUp},
+		CompositionPoly, Error as PolynomialError, EvaluationDomain, EvaluationDomainFactory,
 		IdentityCompositionPoly, IsomorphicEvaluationDomainFactory, MultilinearComposite,
 		MultilinearExtension, MultilinearExtensionSpecialized, MultilinearPoly, MultilinearQuery,
+		MultivariatePoly,
 	},
 	protocols::test_utils::TestProductComposition,
+	witness::{MultilinearExtensionIndex, MultilinearWitness},
 };
+use assert_matches::assert_matches;
 use binius_field::{
-	BinaryField128b, BinaryField32b, BinaryField8b, ExtensionField, Field, PackedField,
+	underlier::WithUnderlier, BinaryField128b, BinaryField32b, BinaryField4b, BinaryField8b,
+	ExtensionField, Field, PackedBinaryField1x128b, PackedBinaryField4x32b, PackedField,
+	TowerField,
 };
 use binius_hash::GroestlHasher;
+use binius_utils::array_2d::Array2D;
 use p3_util::log2_ceil_usize;
 use rand::{rngs::StdRng, Rng, SeedableRng};
 use rayon::{current_num_threads, prelude::*};
-use std::{iter, iter::repeat_with, sync::Arc};
+use std::{
+	io::Cursor,
+	iter,
+	iter::repeat_with,
+	ops::Range,
+	sync::{
+		atomic::{AtomicUsize, Ordering},
+		Arc,
+	},
+};
 
 #[derive(Debug, Clone)]
 struct SquareComposition;
@@ -183,6 +224,53 @@ fn test_prove_verify_interaction_pigeonhole_cores() {
 	}
 }
 
+#[test]
+fn test_prove_verify_single() {
+	type F = BinaryField32b;
+	type FDomain = BinaryField8b;
+	type FE = BinaryField128b;
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let n_vars = 6;
+	let multilins = generate_random_multilinears::<F, FE>(&mut rng, n_vars, 3);
+	let composition = TestProductComposition::new(3);
+	let composite_sums = vec![CompositeSumClaim {
+		composition: &composition,
+		sum: compute_composite_sum(&multilins, &composition),
+	}];
+	let claim = SumcheckClaim::new(n_vars, 3, composite_sums.clone()).unwrap();
+
+	let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+	let new_prover = || {
+		RegularSumcheckProver::<FDomain, _, _, _>::new(
+			multilins.iter().collect(),
+			composite_sums.clone(),
+			domain_factory.clone(),
+			|_| 1,
+		)
+		.unwrap()
+	};
+
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	let (output, proof) = prove_single(new_prover(), challenger.clone()).unwrap();
+	let (batch_output, batch_proof) = batch_prove(vec![new_prover()], challenger.clone()).unwrap();
+	assert_eq!(proof, batch_proof);
+	assert_eq!(output.challenges, batch_output.challenges);
+	assert_eq!(vec![output.multilinear_evals.clone()], batch_output.multilinear_evals);
+
+	let SumcheckOutput {
+		challenges,
+		multilinear_evals,
+	} = verify_single(&claim, proof, challenger).unwrap();
+	assert_eq!(challenges, output.challenges);
+	assert_eq!(multilinear_evals, output.multilinear_evals);
+
+	let multilin_query = MultilinearQuery::with_full_query(&challenges).unwrap();
+	for (multilinear, &expected) in iter::zip(multilins, multilinear_evals.iter()) {
+		assert_eq!(multilinear.evaluate(&multilin_query).unwrap(), expected);
+	}
+}
+
 #[test]
 fn test_prove_verify_batch() {
 	type F = BinaryField32b;
@@ -260,3 +348,2605 @@ fn test_prove_verify_batch() {
 		CanSample::<FE>::sample(&mut verifier_challenger)
 	);
 }
+
+#[test]
+fn test_prove_verify_unsorted_batch() {
+	type F = BinaryField32b;
+	type FDomain = BinaryField8b;
+	type FE = BinaryField128b;
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let composition = TestProductComposition::new(2);
+	let n_vars = [4, 8, 6, 8];
+	let witnesses = n_vars.map(|n_vars| {
+		let multilins = generate_random_multilinears::<F, FE>(&mut rng, n_vars, 2);
+		let composite_sums = vec![CompositeSumClaim {
+			composition: &composition,
+			sum: compute_composite_sum(&multilins, &composition),
+		}];
+		(multilins, composite_sums)
+	});
+	let claims = iter::zip(n_vars, &witnesses)
+		.map(|(n_vars, (_, composite_sums))| {
+			SumcheckClaim::new(n_vars, 2, composite_sums.clone()).unwrap()
+		})
+		.collect::<Vec<_>>();
+
+	let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+	let new_prover = |index: usize| {
+		let (multilins, composite_sums) = &witnesses[index];
+		RegularSumcheckProver::<FDomain, _, _, _>::new(
+			multilins.iter().collect(),
+			composite_sums.clone(),
+			domain_factory.clone(),
+			|_| 1,
+		)
+		.unwrap()
+	};
+
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	let (output, proof) =
+		batch_prove_unsorted((0..4).map(&new_prover).collect(), challenger.clone()).unwrap();
+
+	// Equal numbers of variables keep their relative order.
+	let (_, sorted_proof) =
+		batch_prove([1, 3, 2, 0].map(&new_prover).to_vec(), challenger.clone()).unwrap();
+	assert_eq!(proof, sorted_proof);
+	assert_matches!(
+		batch_verify(&claims, proof.clone(), challenger.clone()),
+		Err(Error::ClaimsOutOfOrder)
+	);
+
+	let verifier_output = batch_verify_unsorted(&claims, proof, challenger).unwrap();
+	assert_eq!(verifier_output, output);
+
+	let BatchSumcheckOutput {
+		challenges,
+		multilinear_evals,
+	} = output;
+	for ((multilins, _), evals) in iter::zip(&witnesses, multilinear_evals) {
+		let n_vars = multilins[0].n_vars();
+		let query =
+			MultilinearQuery::with_full_query(&challenges[challenges.len() - n_vars..]).unwrap();
+		for (multilinear, expected) in iter::zip(multilins, evals) {
+			assert_eq!(multilinear.evaluate(&query).unwrap(), expected);
+		}
+	}
+}
+
+#[test]
+fn test_prove_verify_degree_differs_from_arity() {
+	type F = BinaryField32b;
+	type FDomain = BinaryField8b;
+	type FE = BinaryField128b;
+
+	let n_vars = 5;
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let multilins = generate_random_multilinears::<F, FE>(&mut rng, n_vars, 3);
+	// A composition of 3 multilinears with degree 2.
+	let square_composition = index_composition(&[0, 1, 2], [1], SquareComposition).unwrap();
+	let square_sum = compute_composite_sum(&multilins, &square_composition);
+
+	let claim = SumcheckClaim::new(
+		n_vars,
+		3,
+		vec![CompositeSumClaim {
+			composition: &square_composition,
+			sum: square_sum,
+		}],
+	)
+	.unwrap();
+	assert_eq!(claim.composite_degrees(), &[2]);
+	assert_eq!(claim.max_individual_degree(), 2);
+
+	assert_matches!(
+		SumcheckClaim::with_composite_degrees(
+			n_vars,
+			3,
+			vec![CompositeSumClaim {
+				composition: &square_composition,
+				sum: square_sum,
+			}],
+			vec![3],
+		),
+		Err(Error::InvalidCompositionDegree {
+			index: 0,
+			expected: 2
+		})
+	);
+
+	let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+	let prover = RegularSumcheckProver::<FDomain, _, _, _>::new(
+		multilins.iter().collect(),
+		claim.composite_sums().iter().cloned(),
+		domain_factory,
+		|_| 1,
+	)
+	.unwrap();
+
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	let (prover_output, proof) =
+		batch_prove(vec![prover], challenger.clone()).expect("failed to prove sumcheck");
+	assert!(proof.rounds.iter().all(|round| round.coeffs().len() == 2));
+
+	let verifier_output = batch_verify(&[claim], proof, challenger).unwrap();
+	assert_eq!(prover_output, verifier_output);
+}
+
+#[test]
+fn test_prove_verify_labeled_claims() {
+	type F = BinaryField32b;
+	type FDomain = BinaryField8b;
+	type FE = BinaryField128b;
+
+	let n_vars = 4;
+	let n_multilinears = 2;
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let multilins = generate_random_multilinears::<F, FE>(&mut rng, n_vars, n_multilinears);
+	let composition = TestProductComposition::new(n_multilinears);
+	let sum = compute_composite_sum(&multilins, &composition);
+	let make_claim = |label: &str| {
+		SumcheckClaim::new(
+			n_vars,
+			n_multilinears,
+			vec![CompositeSumClaim {
+				composition: &composition,
+				sum,
+			}],
+		)
+		.unwrap()
+		.with_label(label)
+	};
+
+	let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+	let prover = RegularSumcheckProver::<FDomain, _, _, _>::new(
+		multilins.iter().collect(),
+		[CompositeSumClaim {
+			composition: &composition,
+			sum,
+		}],
+		domain_factory,
+		|_| 1,
+	)
+	.unwrap();
+
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	let (_, proof) = batch_prove_labeled(
+		vec![prover],
+		vec![Some(ClaimLabel::new("product"))],
+		true,
+		challenger.clone(),
+	)
+	.expect("failed to prove sumcheck");
+
+	assert_eq!(proof.claim_labels, Some(vec![Some(ClaimLabel::new("product"))]));
+	batch_verify(&[make_claim("product")], proof.clone(), challenger.clone()).unwrap();
+
+	// Disclosed labels must match the claims.
+	assert_matches!(
+		batch_verify(&[make_claim("other")], proof.clone(), challenger.clone()),
+		Err(Error::Verification(VerificationError::ClaimLabelMismatch))
+	);
+
+	// Labels are bound into the transcript even when they are not disclosed.
+	let undisclosed = super::Proof {
+		claim_labels: None,
+		..proof
+	};
+	assert!(batch_verify(&[make_claim("other")], undisclosed, challenger).is_err());
+}
+
+#[test]
+fn test_prove_verify_with_protocol_features() {
+	type F = BinaryField32b;
+	type FDomain = BinaryField8b;
+	type FE = BinaryField128b;
+
+	let n_vars = 4;
+	let n_multilinears = 3;
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let multilins = generate_random_multilinears::<F, FE>(&mut rng, n_vars, n_multilinears);
+	let composition = TestProductComposition::new(n_multilinears);
+	let sum = compute_composite_sum(&multilins, &composition);
+	let make_claim = || {
+		SumcheckClaim::new(
+			n_vars,
+			n_multilinears,
+			vec![CompositeSumClaim {
+				composition: &composition,
+				sum,
+			}],
+		)
+		.unwrap()
+	};
+
+	let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+	let prover = RegularSumcheckProver::<FDomain, _, _, _>::new(
+		multilins.iter().collect(),
+		[CompositeSumClaim {
+			composition: &composition,
+			sum,
+		}],
+		domain_factory,
+		|_| 1,
+	)
+	.unwrap();
+
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	let (prover_output, proof) = batch_prove_with_features(
+		vec![prover],
+		ProtocolFeatures::UNCOMPRESSED_ROUND_PROOFS,
+		challenger.clone(),
+	)
+	.expect("failed to prove sumcheck");
+
+	assert_eq!(proof.features, ProtocolFeatures::UNCOMPRESSED_ROUND_PROOFS);
+	assert!(proof
+		.rounds
+		.iter()
+		.all(|round| round.coeffs().len() == n_multilinears + 1));
+
+	let verifier_output = batch_verify(&[make_claim()], proof.clone(), challenger.clone()).unwrap();
+	assert_eq!(prover_output, verifier_output);
+
+	// The verifier follows the path selected by the header, so a proof relabeled as compressed
+	// is rejected.
+	let relabeled = super::Proof {
+		features: ProtocolFeatures::empty(),
+		..proof.clone()
+	};
+	assert_matches!(
+		batch_verify(&[make_claim()], relabeled, challenger.clone()),
+		Err(Error::Verification(VerificationError::NumberOfCoefficients { .. }))
+	);
+
+	// Unknown features are rejected.
+	let unknown = ProtocolFeatures::from_bits(1 << 31);
+	let from_future_prover = super::Proof {
+		features: ProtocolFeatures::UNCOMPRESSED_ROUND_PROOFS | unknown,
+		..proof
+	};
+	assert_matches!(
+		batch_verify(&[make_claim()], from_future_prover, challenger),
+		Err(Error::Verification(VerificationError::UnsupportedProtocolFeatures { unsupported }))
+			if unsupported == unknown
+	);
+}
+
+/// The product of two multilinears, declared with a higher degree than it has.
+#[derive(Debug, Clone)]
+struct LooseDegreeProduct;
+
+impl<P: PackedField> CompositionPoly<P> for LooseDegreeProduct {
+	fn n_vars(&self) -> usize {
+		2
+	}
+
+	fn degree(&self) -> usize {
+		4
+	}
+
+	fn evaluate(&self, query: &[P]) -> Result<P, PolynomialError> {
+		Ok(query[0] * query[1])
+	}
+
+	fn binary_tower_level(&self) -> usize {
+		0
+	}
+}
+
+#[test]
+fn test_prove_verify_with_trimmed_round_proofs() {
+	type F = BinaryField32b;
+	type FDomain = BinaryField8b;
+	type FE = BinaryField128b;
+
+	let n_vars = 5;
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let multilins = generate_random_multilinears::<F, FE>(&mut rng, n_vars, 2);
+	let sum = compute_composite_sum(&multilins, LooseDegreeProduct);
+	let composite_sums = vec![CompositeSumClaim {
+		composition: LooseDegreeProduct,
+		sum,
+	}];
+	let claim = SumcheckClaim::new(n_vars, 2, composite_sums.clone()).unwrap();
+
+	let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	let prove = |features| {
+		let prover = RegularSumcheckProver::<FDomain, _, _, _>::new(
+			multilins.iter().collect(),
+			composite_sums.clone(),
+			domain_factory.clone(),
+			|_| 1,
+		)
+		.unwrap();
+		batch_prove_with_features(vec![prover], features, challenger.clone()).unwrap()
+	};
+
+	// Untrimmed round proofs are sized for the declared degree 4.
+	let (_, proof) = prove(ProtocolFeatures::empty());
+	assert!(proof.rounds.iter().all(|round| round.coeffs().len() == 4));
+
+	for (features, n_coeffs) in [
+		(ProtocolFeatures::TRIMMED_ROUND_PROOFS, 2),
+		(ProtocolFeatures::TRIMMED_ROUND_PROOFS | ProtocolFeatures::UNCOMPRESSED_ROUND_PROOFS, 3),
+	] {
+		// Trimmed round proofs have the actual degree 2 of the round polynomials.
+		let (prover_output, proof) = prove(features);
+		assert!(proof
+			.rounds
+			.iter()
+			.all(|round| round.coeffs().len() == n_coeffs));
+
+		let verifier_output =
+			batch_verify(&[claim.clone()], proof.clone(), challenger.clone()).unwrap();
+		assert_eq!(prover_output, verifier_output);
+		let strict_output =
+			batch_verify_strict(&[claim.clone()], proof, challenger.clone()).unwrap();
+		assert_eq!(prover_output, strict_output);
+	}
+
+	// Round proofs with trailing zero coefficients are not canonical and are rejected, even though
+	// they are within the degree bound.
+	let features =
+		ProtocolFeatures::TRIMMED_ROUND_PROOFS | ProtocolFeatures::UNCOMPRESSED_ROUND_PROOFS;
+	let (_, mut proof) = prove(features);
+	let mut coeffs = proof.rounds[0].coeffs().to_vec();
+	coeffs.push(FE::ZERO);
+	proof.rounds[0] = RoundProof::from_raw(coeffs);
+	assert_matches!(
+		batch_verify(&[claim.clone()], proof, challenger.clone()),
+		Err(Error::Verification(VerificationError::NonCanonicalRoundProof { round: 0 }))
+	);
+
+	// Without the feature, the verifier only accepts round proofs sized for the declared degree.
+	let (_, proof) = prove(features);
+	let relabeled = super::Proof {
+		features: ProtocolFeatures::UNCOMPRESSED_ROUND_PROOFS,
+		..proof
+	};
+	assert_matches!(
+		batch_verify(&[claim], relabeled, challenger),
+		Err(Error::Verification(VerificationError::NumberOfCoefficients {
+			round: 0,
+			expected: 5
+		}))
+	);
+}
+
+#[test]
+fn test_proof_encoding_roundtrip() {
+	type F = BinaryField32b;
+	type FDomain = BinaryField8b;
+	type FE = BinaryField128b;
+
+	let n_vars = 8;
+	let n_multilinears = 2;
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let multilins = generate_random_multilinears::<F, FE>(&mut rng, n_vars, n_multilinears);
+	let composition = TestProductComposition::new(n_multilinears);
+	let sum = compute_composite_sum(&multilins, &composition);
+	let claim = SumcheckClaim::new(
+		n_vars,
+		n_multilinears,
+		vec![CompositeSumClaim {
+			composition: &composition,
+			sum,
+		}],
+	)
+	.unwrap()
+	.with_label("product");
+
+	let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+	let prover = RegularSumcheckProver::<FDomain, _, _, _>::new(
+		multilins.iter().collect(),
+		claim.composite_sums().iter().cloned(),
+		domain_factory,
+		|_| 1,
+	)
+	.unwrap();
+
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	let (_, proof) =
+		batch_prove_labeled(vec![prover], vec![claim.label().cloned()], true, challenger.clone())
+			.expect("failed to prove sumcheck");
+
+	let standard = proof.encode(ProofEncoding::Standard);
+	let compact = proof.encode(ProofEncoding::Compact);
+	assert!(compact.len() < standard.len());
+
+	for (bytes, encoding) in [
+		(&standard, ProofEncoding::Standard),
+		(&compact, ProofEncoding::Compact),
+	] {
+		let decoded = Proof::<FE>::decode(bytes, encoding).unwrap();
+		assert_eq!(decoded, proof);
+	}
+	batch_verify(&[claim], Proof::decode(&compact, ProofEncoding::Compact).unwrap(), challenger)
+		.unwrap();
+
+	// Decoding is strict.
+	let mut trailing = compact.clone();
+	trailing.push(0);
+	assert_matches!(
+		Proof::<FE>::decode(&trailing, ProofEncoding::Compact),
+		Err(DecodingError::TrailingBytes(1))
+	);
+	assert_matches!(
+		Proof::<FE>::decode(&compact[..compact.len() - 1], ProofEncoding::Compact),
+		Err(DecodingError::UnexpectedEnd)
+	);
+	assert!(Proof::<FE>::decode(&compact, ProofEncoding::Standard).is_err());
+}
+
+/// Compresses runs of zero bytes, which are frequent in the fixed-width lengths of the standard
+/// encoding, into a zero byte followed by the run length.
+struct ZeroRunTransform;
+
+impl ProofTransform for ZeroRunTransform {
+	fn id(&self) -> u8 {
+		7
+	}
+
+	fn compress(&self, bytes: &[u8]) -> Vec<u8> {
+		let mut compressed = Vec::new();
+		for run in bytes.chunk_by(|lhs, rhs| *lhs == 0 && *rhs == 0) {
+			if run[0] == 0 {
+				for chunk in run.chunks(u8::MAX as usize) {
+					compressed.extend([0, chunk.len() as u8]);
+				}
+			} else {
+				compressed.extend(run);
+			}
+		}
+		compressed
+	}
+
+	fn decompress(&self, bytes: &[u8]) -> Result<Vec<u8>, TransformError> {
+		let mut decompressed = Vec::new();
+		let mut iter = bytes.iter();
+		while let Some(&byte) = iter.next() {
+			if byte == 0 {
+				let run_len = *iter.next().ok_or("truncated zero run")?;
+				decompressed.extend(iter::repeat(0).take(run_len as usize));
+			} else {
+				decompressed.push(byte);
+			}
+		}
+		Ok(decompressed)
+	}
+}
+
+#[test]
+fn test_proof_envelope_transforms() {
+	type F = BinaryField32b;
+	type FDomain = BinaryField8b;
+	type FE = BinaryField128b;
+
+	let n_vars = 6;
+	let n_multilinears = 2;
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let multilins = generate_random_multilinears::<F, FE>(&mut rng, n_vars, n_multilinears);
+	let composition = TestProductComposition::new(n_multilinears);
+	let sum = compute_composite_sum(&multilins, &composition);
+	let claim = SumcheckClaim::new(
+		n_vars,
+		n_multilinears,
+		vec![CompositeSumClaim {
+			composition: &composition,
+			sum,
+		}],
+	)
+	.unwrap();
+
+	let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+	let prover = RegularSumcheckProver::<FDomain, _, _, _>::new(
+		multilins.iter().collect(),
+		claim.composite_sums().iter().cloned(),
+		domain_factory,
+		|_| 1,
+	)
+	.unwrap();
+
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	let (_, proof) =
+		batch_prove(vec![prover], challenger.clone()).expect("failed to prove sumcheck");
+
+	let transform = ZeroRunTransform;
+	let standard = proof.encode(ProofEncoding::Standard);
+	let envelope = proof.encode_envelope(ProofEncoding::Standard, &transform);
+	assert!(envelope.len() < standard.len());
+
+	let (header, _) = EnvelopeHeader::parse(&envelope).unwrap();
+	assert_eq!(
+		header,
+		EnvelopeHeader {
+			encoding: ProofEncoding::Standard,
+			transform_id: 7,
+		}
+	);
+
+	let decoded = Proof::<FE>::decode_envelope(&envelope, &[&transform]).unwrap();
+	assert_eq!(decoded, proof);
+	batch_verify(&[claim], decoded, challenger).unwrap();
+
+	// The identity transform is always supported.
+	let envelope = proof.encode_envelope(ProofEncoding::Compact, &IdentityTransform);
+	assert_eq!(Proof::<FE>::decode_envelope(&envelope, &[]).unwrap(), proof);
+
+	// The verifier rejects transforms it does not know and failures to decompress.
+	let envelope = proof.encode_envelope(ProofEncoding::Standard, &transform);
+	assert_matches!(
+		Proof::<FE>::decode_envelope(&envelope, &[]),
+		Err(DecodingError::UnknownTransform(7))
+	);
+	let mut truncated = envelope.clone();
+	truncated.push(0);
+	assert_matches!(
+		Proof::<FE>::decode_envelope(&truncated, &[&transform]),
+		Err(DecodingError::Transform {
+			transform_id: 7,
+			..
+		})
+	);
+	let mut unversioned = envelope;
+	unversioned[0] = ENVELOPE_VERSION + 1;
+	assert_matches!(
+		Proof::<FE>::decode_envelope(&unversioned, &[&transform]),
+		Err(DecodingError::UnsupportedEnvelopeVersion(_))
+	);
+}
+
+#[test]
+fn test_encode_decode_round_proof_and_output() {
+	let round = RoundProof::uncompressed(RoundCoeffs(vec![
+		BinaryField32b::new(0x0403_0201),
+		BinaryField32b::ONE,
+	]));
+	let standard = round.encode(ProofEncoding::Standard);
+	// Field elements are written little-endian in the tower basis, independent of the host.
+	assert_eq!(standard, [2, 0, 0, 0, 0, 0, 0, 0, 1, 2, 3, 4, 1, 0, 0, 0]);
+	assert_eq!(round.encode(ProofEncoding::Compact), [2, 1, 2, 3, 4, 1, 0, 0, 0]);
+	assert_eq!(RoundProof::decode(&standard, ProofEncoding::Standard).unwrap(), round);
+
+	let mut rng = StdRng::seed_from_u64(0);
+	let output = BatchSumcheckOutput {
+		challenges: repeat_with(|| BinaryField128b::random(&mut rng))
+			.take(5)
+			.collect(),
+		multilinear_evals: vec![
+			repeat_with(|| BinaryField128b::random(&mut rng))
+				.take(3)
+				.collect(),
+			vec![],
+		],
+	};
+	for encoding in [ProofEncoding::Standard, ProofEncoding::Compact] {
+		let bytes = output.encode(encoding);
+		assert_eq!(BatchSumcheckOutput::decode(&bytes, encoding).unwrap(), output);
+		assert_matches!(
+			BatchSumcheckOutput::<BinaryField128b>::decode(&bytes[1..], encoding),
+			Err(_)
+		);
+	}
+
+	// Sub-byte fields take a byte per element in the standard encoding and are bit-packed in the
+	// compact encoding, and both reject nonzero padding bits.
+	let round =
+		RoundProof::uncompressed(RoundCoeffs([9u8, 5, 3].map(BinaryField4b::from).to_vec()));
+	let mut bytes = round.encode(ProofEncoding::Standard);
+	assert_eq!(bytes, [3, 0, 0, 0, 0, 0, 0, 0, 9, 5, 3]);
+	assert_eq!(RoundProof::decode(&bytes, ProofEncoding::Standard).unwrap(), round);
+	bytes[8] |= 0x10;
+	assert_matches!(
+		RoundProof::<BinaryField4b>::decode(&bytes, ProofEncoding::Standard),
+		Err(DecodingError::NonCanonicalElement)
+	);
+
+	let mut bytes = round.encode(ProofEncoding::Compact);
+	assert_eq!(bytes, [3, 0x59, 0x03]);
+	assert_eq!(RoundProof::decode(&bytes, ProofEncoding::Compact).unwrap(), round);
+	bytes[2] |= 0x10;
+	assert_matches!(
+		RoundProof::<BinaryField4b>::decode(&bytes, ProofEncoding::Compact),
+		Err(DecodingError::NonCanonicalPadding)
+	);
+}
+
+#[test]
+fn test_verify_round_evals() {
+	type FDomain = BinaryField8b;
+	type FE = BinaryField128b;
+
+	let mut rng = StdRng::seed_from_u64(0);
+	let degree = 3;
+	let domain = IsomorphicEvaluationDomainFactory::<FDomain>::default()
+		.create(degree + 1)
+		.unwrap();
+
+	let coeffs = RoundCoeffs(
+		repeat_with(|| FE::random(&mut rng))
+			.take(degree + 1)
+			.collect::<Vec<_>>(),
+	);
+	let round_evals = RoundEvals::from_coeffs(&coeffs, &domain).unwrap();
+	assert_eq!(round_evals.to_coeffs(&domain).unwrap(), coeffs);
+
+	let sum = evaluate_univariate(&coeffs.0, FE::ZERO) + evaluate_univariate(&coeffs.0, FE::ONE);
+	let challenge = FE::random(&mut rng);
+	assert_eq!(
+		verify_round_evals(0, &round_evals, &domain, sum, challenge).unwrap(),
+		evaluate_univariate(&coeffs.0, challenge)
+	);
+
+	assert_matches!(
+		verify_round_evals(0, &round_evals, &domain, sum + FE::ONE, challenge),
+		Err(Error::Verification(VerificationError::RoundSumMismatch { round: 0, .. }))
+	);
+	let short = RoundEvals(round_evals.0[..degree].to_vec());
+	assert_matches!(
+		verify_round_evals(1, &short, &domain, sum, challenge),
+		Err(Error::Verification(VerificationError::NumberOfCoefficients {
+			round: 1,
+			expected: 4
+		}))
+	);
+
+	// A polynomial of higher degree than the domain supports cannot be converted.
+	let too_long = RoundCoeffs(vec![FE::ONE; degree + 2]);
+	assert_matches!(
+		RoundEvals::from_coeffs(&too_long, &domain),
+		Err(Error::DomainTooSmall { n_coeffs: 5 })
+	);
+
+	// The sum over 0 and 1 is only read off the evaluations of a domain starting with them.
+	let reversed_domain =
+		EvaluationDomain::from_points(domain.points().iter().rev().copied().collect()).unwrap();
+	assert_matches!(
+		verify_round_evals(0, &round_evals, &reversed_domain, sum, challenge),
+		Err(Error::InvalidRoundEvalsDomain)
+	);
+}
+
+#[test]
+fn test_resume_batch_prove_from_checkpoints() {
+	type F = BinaryField32b;
+	type FDomain = BinaryField8b;
+	type FE = BinaryField128b;
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let composition = TestProductComposition::new(2);
+	let witnesses = [6, 4]
+		.into_iter()
+		.map(|n_vars| {
+			let multilins = generate_random_multilinears::<F, FE>(&mut rng, n_vars, 2);
+			let composite_sums = vec![CompositeSumClaim {
+				composition: &composition,
+				sum: compute_composite_sum(&multilins, &composition),
+			}];
+			(multilins, composite_sums)
+		})
+		.collect::<Vec<_>>();
+
+	let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+	let new_provers = || {
+		witnesses
+			.iter()
+			.map(|(multilins, composite_sums)| {
+				RegularSumcheckProver::<FDomain, _, _, _>::new(
+					multilins.iter().collect(),
+					composite_sums.clone(),
+					domain_factory.clone(),
+					|_| 3,
+				)
+				.unwrap()
+			})
+			.collect::<Vec<_>>()
+	};
+	let restore_provers = |snapshots: &[ProverSnapshot<FE>]| {
+		iter::zip(&witnesses, snapshots)
+			.map(|((multilins, composite_sums), snapshot)| {
+				RegularSumcheckProver::<FDomain, _, _, _>::restore(
+					multilins.iter().collect(),
+					composite_sums.clone(),
+					domain_factory.clone(),
+					snapshot.clone(),
+				)
+				.unwrap()
+			})
+			.collect::<Vec<_>>()
+	};
+
+	let features = ProtocolFeatures::empty();
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	let expected = batch_prove_with_features(new_provers(), features, challenger.clone()).unwrap();
+
+	let mut checkpoints = Vec::new();
+	let proved = batch_prove_with_checkpoints(
+		new_provers(),
+		features,
+		challenger.clone(),
+		1,
+		|checkpoint| checkpoints.push(checkpoint),
+	)
+	.unwrap();
+	assert_eq!(proved, expected);
+	assert_eq!(checkpoints.len(), 6);
+
+	// Resuming from any round, including rounds where the multilinears are partially folded into
+	// the large field and before the second prover is activated, reproduces the proof. The
+	// checkpoints survive a round trip through their byte encoding, which replays the transcript
+	// into a fresh challenger.
+	for checkpoint in checkpoints {
+		for encoding in [ProofEncoding::Standard, ProofEncoding::Compact] {
+			let bytes = checkpoint.encode(encoding);
+			let decoded =
+				BatchProveCheckpoint::decode(&bytes, encoding, challenger.clone()).unwrap();
+			assert_eq!(decoded.batch_coeffs, checkpoint.batch_coeffs);
+			assert_eq!(decoded.challenges, checkpoint.challenges);
+			assert_eq!(decoded.rounds, checkpoint.rounds);
+			assert_eq!(decoded.provers, checkpoint.provers);
+
+			let provers = restore_provers(&decoded.provers);
+			let resumed = resume_batch_prove(provers, decoded, 0, |_| {}).unwrap();
+			assert_eq!(resumed, expected);
+		}
+	}
+}
+
+#[test]
+fn test_decode_checkpoint_rejects_tampered_rounds() {
+	type F = BinaryField32b;
+	type FDomain = BinaryField8b;
+	type FE = BinaryField128b;
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let composition = TestProductComposition::new(2);
+	let multilins = generate_random_multilinears::<F, FE>(&mut rng, 4, 2);
+	let composite_sums = vec![CompositeSumClaim {
+		composition: &composition,
+		sum: compute_composite_sum(&multilins, &composition),
+	}];
+	let prover = RegularSumcheckProver::<FDomain, _, _, _>::new(
+		multilins.iter().collect(),
+		composite_sums,
+		IsomorphicEvaluationDomainFactory::<FDomain>::default(),
+		|_| 3,
+	)
+	.unwrap();
+
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	let mut checkpoints = Vec::new();
+	batch_prove_with_checkpoints(
+		vec![prover],
+		ProtocolFeatures::empty(),
+		challenger.clone(),
+		2,
+		|checkpoint| checkpoints.push(checkpoint),
+	)
+	.unwrap();
+
+	// Changing a round proof changes the replayed challenges, which no longer match the
+	// challenges the prover snapshot was folded with.
+	let mut checkpoint = checkpoints.remove(0);
+	checkpoint.rounds[0] = RoundProof::from_raw(
+		checkpoint.rounds[0]
+			.coeffs()
+			.iter()
+			.map(|&coeff| coeff + FE::ONE)
+			.collect(),
+	);
+	let bytes = checkpoint.encode(ProofEncoding::Standard);
+	assert_matches!(
+		BatchProveCheckpoint::decode(&bytes, ProofEncoding::Standard, challenger.clone()),
+		Err(Error::InvalidCheckpoint)
+	);
+
+	// Truncated input is a decoding error.
+	let bytes = checkpoints[0].encode(ProofEncoding::Standard);
+	assert_matches!(
+		BatchProveCheckpoint::<FE, _>::decode(
+			&bytes[..bytes.len() - 1],
+			ProofEncoding::Standard,
+			challenger
+		),
+		Err(Error::Decoding(_))
+	);
+}
+
+#[test]
+fn test_make_eval_claims() {
+	type F = BinaryField32b;
+	type FDomain = BinaryField8b;
+	type FE = BinaryField128b;
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let mut oracles = MultilinearOracleSet::<FE>::new();
+	let composition = TestProductComposition::new(2);
+	let witnesses = [6, 4]
+		.into_iter()
+		.map(|n_vars| {
+			let batch_id = oracles.add_committed_batch(n_vars, F::TOWER_LEVEL);
+			let oracle_ids = oracles.add_committed_multiple::<2>(batch_id).to_vec();
+			let multilins = generate_random_multilinears::<F, FE>(&mut rng, n_vars, 2);
+			let sum = compute_composite_sum(&multilins, &composition);
+			(oracle_ids, multilins, sum)
+		})
+		.collect::<Vec<_>>();
+	let claims = witnesses
+		.iter()
+		.map(|(_, multilins, sum)| {
+			SumcheckClaim::new(
+				multilins[0].n_vars(),
+				2,
+				vec![CompositeSumClaim {
+					composition: &composition,
+					sum: *sum,
+				}],
+			)
+			.unwrap()
+		})
+		.collect::<Vec<_>>();
+	let metas = witnesses
+		.iter()
+		.map(|(oracle_ids, multilins, _)| OracleClaimMeta {
+			n_vars: multilins[0].n_vars(),
+			oracle_ids: oracle_ids.clone(),
+		})
+		.collect::<Vec<_>>();
+
+	let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+	let provers = witnesses
+		.iter()
+		.map(|(_, multilins, sum)| {
+			RegularSumcheckProver::<FDomain, _, _, _>::new(
+				multilins.iter().collect(),
+				[CompositeSumClaim {
+					composition: &composition,
+					sum: *sum,
+				}],
+				domain_factory.clone(),
+				|_| 3,
+			)
+			.unwrap()
+		})
+		.collect();
+
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	let (_, proof) = batch_prove(provers, &mut challenger.clone()).unwrap();
+	let output = batch_verify(&claims, proof, &mut challenger.clone()).unwrap();
+	let challenges = output.challenges.clone();
+
+	let eval_claims = make_eval_claims(&oracles, metas.clone(), output).unwrap();
+	let expected_claims = witnesses
+		.iter()
+		.flat_map(|(oracle_ids, multilins, _)| iter::zip(oracle_ids, multilins));
+	assert_eq!(eval_claims.len(), 4);
+	for (eval_claim, (&oracle_id, multilin)) in iter::zip(&eval_claims, expected_claims) {
+		let n_vars = multilin.n_vars();
+		let eval_point = &challenges[challenges.len() - n_vars..];
+		let query = MultilinearQuery::with_full_query(eval_point).unwrap();
+		assert_eq!(eval_claim.poly.id(), oracle_id);
+		assert_eq!(eval_claim.eval_point, eval_point);
+		assert_eq!(eval_claim.eval, multilin.evaluate(&query).unwrap());
+		assert!(eval_claim.is_random_point);
+	}
+
+	let output = || BatchSumcheckOutput {
+		challenges: challenges.clone(),
+		multilinear_evals: vec![vec![FE::ZERO; 2]; 2],
+	};
+	let mut wrong_n_vars = metas.clone();
+	wrong_n_vars[1].n_vars = 5;
+	assert_matches!(
+		make_eval_claims(&oracles, wrong_n_vars, output()),
+		Err(Error::Oracle(OracleError::IncorrectNumberOfVariables { expected: 5 }))
+	);
+	let mut unknown_oracle = metas.clone();
+	unknown_oracle[0].oracle_ids[1] = oracles.size();
+	assert_matches!(
+		make_eval_claims(&oracles, unknown_oracle, output()),
+		Err(Error::Oracle(OracleError::InvalidOracleId(_)))
+	);
+	assert_matches!(
+		make_eval_claims(&oracles, metas[..1].to_vec(), output()),
+		Err(Error::Verification(VerificationError::NumberOfFinalEvaluations))
+	);
+}
+
+#[test]
+fn test_prove_verify_split() {
+	type F = BinaryField32b;
+	type FDomain = BinaryField8b;
+	type FE = BinaryField128b;
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let params = ProveParams::builder().max_batch_claims(2).build().unwrap();
+	let mut oracles = MultilinearOracleSet::<FE>::new();
+	let composition = TestProductComposition::new(2);
+	let witnesses = [7, 6, 6, 4, 3]
+		.into_iter()
+		.map(|n_vars| {
+			let batch_id = oracles.add_committed_batch(n_vars, F::TOWER_LEVEL);
+			let oracle_ids = oracles.add_committed_multiple::<2>(batch_id).to_vec();
+			let multilins = generate_random_multilinears::<F, FE>(&mut rng, n_vars, 2);
+			let sum = compute_composite_sum(&multilins, &composition);
+			(oracle_ids, multilins, sum)
+		})
+		.collect::<Vec<_>>();
+	let claims = witnesses
+		.iter()
+		.map(|(_, multilins, sum)| {
+			SumcheckClaim::new(
+				multilins[0].n_vars(),
+				2,
+				vec![CompositeSumClaim {
+					composition: &composition,
+					sum: *sum,
+				}],
+			)
+			.unwrap()
+		})
+		.collect::<Vec<_>>();
+	let metas = witnesses
+		.iter()
+		.map(|(oracle_ids, multilins, _)| OracleClaimMeta {
+			n_vars: multilins[0].n_vars(),
+			oracle_ids: oracle_ids.clone(),
+		})
+		.collect::<Vec<_>>();
+
+	let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+	let provers = || {
+		witnesses
+			.iter()
+			.map(|(_, multilins, sum)| {
+				RegularSumcheckProver::<FDomain, _, _, _>::new(
+					multilins.iter().collect(),
+					[CompositeSumClaim {
+						composition: &composition,
+						sum: *sum,
+					}],
+					domain_factory.clone(),
+					params.switchover_fn(),
+				)
+				.unwrap()
+			})
+			.collect::<Vec<_>>()
+	};
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+
+	let (prover_outputs, proofs) =
+		batch_prove_split(provers(), params.max_batch_claims(), challenger.clone()).unwrap();
+	assert_eq!(
+		proofs
+			.iter()
+			.map(|proof| proof.multilinear_evals.len())
+			.collect::<Vec<_>>(),
+		[2, 2, 1]
+	);
+
+	let verify_params = params.verify_params();
+	let verifier_outputs = batch_verify_split(
+		&claims,
+		proofs.clone(),
+		verify_params.max_batch_claims(),
+		challenger.clone(),
+	)
+	.unwrap();
+	assert_eq!(verifier_outputs, prover_outputs);
+
+	let eval_claims = make_split_eval_claims(&oracles, metas, verifier_outputs).unwrap();
+	let expected_claims = witnesses
+		.iter()
+		.flat_map(|(oracle_ids, multilins, _)| iter::zip(oracle_ids, multilins));
+	assert_eq!(eval_claims.len(), 10);
+	for (eval_claim, (&oracle_id, multilin)) in iter::zip(&eval_claims, expected_claims) {
+		let query = MultilinearQuery::with_full_query(&eval_claim.eval_point).unwrap();
+		assert_eq!(eval_claim.poly.id(), oracle_id);
+		assert_eq!(eval_claim.eval_point.len(), multilin.n_vars());
+		assert_eq!(eval_claim.eval, multilin.evaluate(&query).unwrap());
+	}
+
+	// Without a limit, the claims are proven in one batch.
+	let (_, unsplit_proofs) = batch_prove_split(provers(), None, challenger.clone()).unwrap();
+	let (_, expected_proof) = batch_prove(provers(), challenger.clone()).unwrap();
+	assert_eq!(unsplit_proofs, [expected_proof]);
+
+	assert_matches!(
+		batch_verify_split(&claims, proofs[..2].to_vec(), Some(2), challenger.clone()),
+		Err(Error::Verification(VerificationError::NumberOfBatchProofs { expected: 3 }))
+	);
+
+	// The batches are derived from the limit, not from the proofs. Proofs of a different split
+	// of the same claims are rejected.
+	let (_, other_proofs) = batch_prove_split(provers(), Some(3), challenger.clone()).unwrap();
+	assert_matches!(
+		batch_verify_split(&claims, other_proofs, Some(2), challenger.clone()),
+		Err(Error::Verification(VerificationError::NumberOfBatchProofs { expected: 3 }))
+	);
+	let (_, other_proofs) = batch_prove_split(provers(), Some(4), challenger.clone()).unwrap();
+	assert_matches!(
+		batch_verify_split(&claims, other_proofs, Some(3), challenger.clone()),
+		Err(Error::Verification(VerificationError::NumberOfFinalEvaluations))
+	);
+	assert_matches!(
+		batch_verify_split(&claims, proofs, Some(0), challenger.clone()),
+		Err(Error::InvalidMaxBatchClaims)
+	);
+	assert_matches!(
+		batch_prove_split(provers(), Some(0), challenger),
+		Err(Error::InvalidMaxBatchClaims)
+	);
+}
+
+/// A multilinear committed by an external proof system, identified by an integer handle.
+#[derive(Debug)]
+struct MockExternalOracle<F> {
+	handle: u64,
+	n_vars: usize,
+	evals: Vec<F>,
+}
+
+impl<F> ExternalOracle for MockExternalOracle<F> {
+	type Handle = u64;
+
+	fn handle(&self) -> u64 {
+		self.handle
+	}
+
+	fn n_vars(&self) -> usize {
+		self.n_vars
+	}
+}
+
+impl<F: Field> ExternalMultilinear<F> for MockExternalOracle<F> {
+	fn evals(&self) -> &[F] {
+		&self.evals
+	}
+}
+
+#[test]
+fn test_prove_verify_with_external_multilinears() {
+	type F = BinaryField32b;
+	type FDomain = BinaryField8b;
+	type FE = BinaryField128b;
+
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let n_vars = [6, 4];
+	let external_oracles = n_vars
+		.iter()
+		.enumerate()
+		.map(|(handle, &n_vars)| MockExternalOracle {
+			handle: handle as u64,
+			n_vars,
+			evals: repeat_with(|| F::random(&mut rng))
+				.take(1 << n_vars)
+				.collect(),
+		})
+		.collect::<Vec<_>>();
+
+	let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+	let composition = TestProductComposition::new(2);
+	let (claims, provers) = iter::zip(n_vars, &external_oracles)
+		.map(|(n_vars, external_oracle)| {
+			let native = MultilinearExtension::from_values(
+				repeat_with(|| F::random(&mut rng))
+					.take(1 << n_vars)
+					.collect(),
+			)
+			.unwrap()
+			.specialize_arc_dyn();
+			let external = external_multilinear::<F, FE, _>(external_oracle).unwrap();
+			let multilins: Vec<MultilinearWitness<FE>> = vec![native, external];
+
+			let composite_sums = vec![CompositeSumClaim {
+				composition: &composition,
+				sum: compute_composite_sum(&multilins, &composition),
+			}];
+			let claim = SumcheckClaim::new(n_vars, 2, composite_sums.clone()).unwrap();
+			let prover = RegularSumcheckProver::<FDomain, _, _, _>::new(
+				multilins,
+				composite_sums,
+				domain_factory.clone(),
+				|_| 1,
+			)
+			.unwrap();
+			(claim, prover)
+		})
+		.unzip::<_, _, Vec<_>, Vec<_>>();
+
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	let (_, proof) = batch_prove(provers, &mut challenger.clone()).unwrap();
+	let output = batch_verify(&claims, proof, &mut challenger.clone()).unwrap();
+
+	let origins = external_oracles
+		.iter()
+		.map(|external_oracle| vec![MultilinearOrigin::Native, external_oracle.origin()])
+		.collect::<Vec<_>>();
+	let external_claims = export_external_eval_claims(&claims, &origins, &output).unwrap();
+	assert_eq!(external_claims.len(), 2);
+	for (external_claim, external_oracle) in iter::zip(&external_claims, &external_oracles) {
+		assert_eq!(external_claim.handle, external_oracle.handle);
+		assert_eq!(external_claim.eval_point.len(), external_oracle.n_vars);
+
+		// The external system checks the claim against its own copy of the multilinear.
+		let multilin = MultilinearExtension::from_values_slice(&external_oracle.evals).unwrap();
+		let query = MultilinearQuery::<FE>::with_full_query(&external_claim.eval_point).unwrap();
+		assert_eq!(multilin.evaluate(&query).unwrap(), external_claim.eval);
+	}
+
+	assert_matches!(
+		export_external_eval_claims(&claims, &origins[..1], &output),
+		Err(Error::IncorrectNumberOfMultilinearOrigins { expected: 2 })
+	);
+
+	let truncated = MockExternalOracle {
+		handle: 2,
+		n_vars: 5,
+		evals: external_oracles[1].evals.clone(),
+	};
+	assert_matches!(
+		external_multilinear::<F, FE, _>(&truncated),
+		Err(Error::ExternalOracleSizeMismatch { n_vars: 5 })
+	);
+}
+
+#[test]
+fn test_prove_with_metrics() {
+	type F = BinaryField32b;
+	type FDomain = BinaryField8b;
+	type FE = BinaryField128b;
+
+	let mut rng = StdRng::seed_from_u64(0);
+	let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+	let witnesses = [6, 4].map(|n_vars| {
+		let multilins = generate_random_multilinears::<F, FE>(&mut rng, n_vars, 2);
+		let sum = compute_composite_sum(&multilins, TestProductComposition::new(2));
+		(multilins, sum)
+	});
+	let make_provers = || {
+		witnesses
+			.iter()
+			.map(|(multilins, sum)| {
+				RegularSumcheckProver::<FDomain, _, _, _>::new(
+					multilins.clone(),
+					[CompositeSumClaim {
+						composition: TestProductComposition::new(2),
+						sum: *sum,
+					}],
+					domain_factory.clone(),
+					|_| 1,
+				)
+				.unwrap()
+			})
+			.collect::<Vec<_>>()
+	};
+
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	let (output, proof) = batch_prove(make_provers(), challenger.clone()).unwrap();
+	let (metrics_output, metrics_proof, metrics) =
+		batch_prove_with_metrics(make_provers(), challenger).unwrap();
+	assert_eq!(metrics_output, output);
+	assert_eq!(metrics_proof, proof);
+
+	let n_active_provers = metrics
+		.rounds
+		.iter()
+		.map(|round| round.n_active_provers)
+		.collect::<Vec<_>>();
+	assert_eq!(n_active_provers, [1, 1, 2, 2, 2, 2]);
+	assert!(metrics.rounds.iter().all(|round| round.scratch_bytes > 0));
+	assert_eq!(metrics.peak_scratch_bytes(), metrics.rounds[0].scratch_bytes);
+}
+
+#[test]
+fn test_prove_verify_high_to_low() {
+	type F = BinaryField32b;
+	type FDomain = BinaryField8b;
+	type FE = BinaryField128b;
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let composition = TestProductComposition::new(2);
+	let witnesses = [6, 4]
+		.into_iter()
+		.map(|n_vars| {
+			let multilins = generate_random_multilinears::<F, FE>(&mut rng, n_vars, 2);
+			let sum = compute_composite_sum(&multilins, &composition);
+			(multilins, sum)
+		})
+		.collect::<Vec<_>>();
+	let claims = witnesses
+		.iter()
+		.map(|(multilins, sum)| {
+			SumcheckClaim::new(
+				multilins[0].n_vars(),
+				2,
+				vec![CompositeSumClaim {
+					composition: &composition,
+					sum: *sum,
+				}],
+			)
+			.unwrap()
+		})
+		.collect::<Vec<_>>();
+
+	let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+	let new_provers = |fold_directions: [FoldDirection; 2]| {
+		iter::zip(&witnesses, fold_directions)
+			.map(|((multilins, sum), fold_direction)| {
+				// The switchover happens midway through the first claim.
+				RegularSumcheckProver::<FDomain, _, _, _>::new_with_fold_direction(
+					multilins.iter().collect(),
+					[CompositeSumClaim {
+						composition: &composition,
+						sum: *sum,
+					}],
+					domain_factory.clone(),
+					|_| 3,
+					fold_direction,
+				)
+				.unwrap()
+			})
+			.collect::<Vec<_>>()
+	};
+
+	// Each direction yields a proof that verifies, with the evaluations at the challenges ordered
+	// by variable.
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	for fold_direction in [FoldDirection::LowToHigh, FoldDirection::HighToLow] {
+		let (prover_output, proof) =
+			batch_prove(new_provers([fold_direction; 2]), challenger.clone()).unwrap();
+		let verifier_output =
+			batch_verify_with_fold_direction(&claims, proof, fold_direction, challenger.clone())
+				.unwrap();
+		assert_eq!(prover_output, verifier_output);
+
+		for ((multilins, _), evals) in iter::zip(&witnesses, &verifier_output.multilinear_evals) {
+			let n_vars = multilins[0].n_vars();
+			let eval_point = fold_direction.eval_point(&verifier_output.challenges, n_vars);
+			let query = MultilinearQuery::with_full_query(eval_point).unwrap();
+			for (multilin, &eval) in iter::zip(multilins, evals) {
+				assert_eq!(multilin.evaluate(&query).unwrap(), eval);
+			}
+		}
+	}
+
+	assert_matches!(
+		batch_prove(new_provers([FoldDirection::HighToLow, FoldDirection::LowToHigh]), challenger),
+		Err(Error::FoldDirectionMismatch)
+	);
+}
+
+#[test]
+fn test_prove_verify_with_constant_multilinears() {
+	type F = BinaryField32b;
+	type FDomain = BinaryField8b;
+	type FE = BinaryField128b;
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let n_vars = 6;
+	let constant = <F as Field>::random(&mut rng);
+	let constant_multilinear = |value: F| {
+		MultilinearExtension::from_values(vec![value; 1 << n_vars])
+			.unwrap()
+			.specialize::<FE>()
+	};
+
+	// A random multilinear, a constant selector and an all-zero selector.
+	let mut multilins = generate_random_multilinears::<F, FE>(&mut rng, n_vars, 1);
+	multilins.push(constant_multilinear(constant));
+	multilins.push(constant_multilinear(F::ZERO));
+
+	let compositions: [Arc<dyn CompositionPoly<FE>>; 3] = [
+		Arc::new(index_composition(&[0, 1, 2], [0, 1], TestProductComposition::new(2)).unwrap()),
+		Arc::new(index_composition(&[0, 1, 2], [1], SquareComposition).unwrap()),
+		Arc::new(TestProductComposition::new(3)),
+	];
+	let composite_sums = compositions
+		.into_iter()
+		.map(|composition| CompositeSumClaim {
+			sum: compute_composite_sum(&multilins, &composition),
+			composition,
+		})
+		.collect::<Vec<_>>();
+	let claim = SumcheckClaim::new(n_vars, 3, composite_sums.clone()).unwrap();
+
+	let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	for fold_direction in [FoldDirection::LowToHigh, FoldDirection::HighToLow] {
+		let prover = RegularSumcheckProver::<FDomain, _, _, _>::new_with_fold_direction(
+			multilins.iter().collect(),
+			composite_sums.iter().cloned(),
+			domain_factory.clone(),
+			|_| 3,
+			fold_direction,
+		)
+		.unwrap();
+
+		let (prover_output, proof) = batch_prove(vec![prover], challenger.clone()).unwrap();
+		let verifier_output = batch_verify_with_fold_direction(
+			&[claim.clone()],
+			proof,
+			fold_direction,
+			challenger.clone(),
+		)
+		.unwrap();
+		assert_eq!(prover_output, verifier_output);
+
+		let eval_point = fold_direction.eval_point(&verifier_output.challenges, n_vars);
+		let query = MultilinearQuery::with_full_query(eval_point).unwrap();
+		for (multilin, &eval) in iter::zip(&multilins, &verifier_output.multilinear_evals[0]) {
+			assert_eq!(multilin.evaluate(&query).unwrap(), eval);
+		}
+		assert_eq!(verifier_output.multilinear_evals[0][1..], [FE::from(constant), FE::ZERO]);
+	}
+}
+
+#[test]
+fn test_batch_verify_strict() {
+	type F = BinaryField32b;
+	type FDomain = BinaryField8b;
+	type FE = BinaryField128b;
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let compositions = [
+		TestProductComposition::new(2),
+		TestProductComposition::new(3),
+	];
+	let witnesses = iter::zip([(6, 2), (4, 3)], &compositions)
+		.map(|((n_vars, n_multilinears), composition)| {
+			let multilins = generate_random_multilinears::<F, FE>(&mut rng, n_vars, n_multilinears);
+			let sum = compute_composite_sum(&multilins, composition);
+			(multilins, composition, sum)
+		})
+		.collect::<Vec<_>>();
+	let claims = witnesses
+		.iter()
+		.map(|(multilins, composition, sum)| {
+			SumcheckClaim::new(
+				multilins[0].n_vars(),
+				multilins.len(),
+				vec![CompositeSumClaim {
+					composition: *composition,
+					sum: *sum,
+				}],
+			)
+			.unwrap()
+		})
+		.collect::<Vec<_>>();
+
+	// The second claim, with a degree 3 composite, joins after two rounds.
+	let shape = ProofShape::from_claims(&claims).unwrap();
+	assert_eq!(shape.round_degrees, vec![2, 2, 3, 3, 3, 3]);
+	assert_eq!(shape.n_multilinear_evals, vec![2, 3]);
+
+	let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+	let provers = witnesses
+		.iter()
+		.map(|(multilins, composition, sum)| {
+			RegularSumcheckProver::<FDomain, _, _, _>::new(
+				multilins.iter().collect(),
+				[CompositeSumClaim {
+					composition: *composition,
+					sum: *sum,
+				}],
+				domain_factory.clone(),
+				|_| 2,
+			)
+			.unwrap()
+		})
+		.collect();
+
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	let (prover_output, proof) = batch_prove(provers, challenger.clone()).unwrap();
+	let verifier_output = batch_verify_strict(&claims, proof.clone(), challenger.clone()).unwrap();
+	assert_eq!(prover_output, verifier_output);
+
+	let mut truncated_round = proof.clone();
+	truncated_round.rounds[5] = RoundProof::from_raw(proof.rounds[5].coeffs()[..2].to_vec());
+	assert_matches!(
+		batch_verify_strict(&claims, truncated_round, challenger.clone()),
+		Err(Error::Verification(VerificationError::NumberOfCoefficients {
+			round: 5,
+			expected: 3
+		}))
+	);
+
+	let mut extra_eval = proof.clone();
+	extra_eval.multilinear_evals[1].push(FE::ONE);
+	assert_matches!(
+		batch_verify_strict(&claims, extra_eval, challenger.clone()),
+		Err(Error::Verification(VerificationError::NumberOfClaimEvaluations {
+			claim_index: 1,
+			expected: 3
+		}))
+	);
+
+	let mut extra_label = proof.clone();
+	extra_label.claim_labels = Some(vec![None; 3]);
+	assert_matches!(
+		batch_verify_strict(&claims, extra_label, challenger.clone()),
+		Err(Error::Verification(VerificationError::ClaimLabelMismatch))
+	);
+
+	let reversed_claims = claims.into_iter().rev().collect::<Vec<_>>();
+	assert_matches!(
+		batch_verify_strict(&reversed_claims, proof, challenger),
+		Err(Error::ClaimsOutOfOrder)
+	);
+}
+
+#[test]
+fn test_prove_verify_shared_multilinears() {
+	type F = BinaryField32b;
+	type FDomain = BinaryField8b;
+	type FE = BinaryField128b;
+
+	let mut rng = StdRng::seed_from_u64(0);
+	let n_vars = 6;
+	let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+	let multilins = generate_random_multilinears::<F, FE>(&mut rng, n_vars, 3);
+
+	// Both claims reference the multilinear at index 1.
+	let claim_indices = [[0, 1], [1, 2]];
+	let claim_sums = claim_indices.map(|indices| {
+		let claim_multilins = indices.map(|index| multilins[index].clone());
+		compute_composite_sum(&claim_multilins, TestProductComposition::new(2))
+	});
+	let claims = claim_sums
+		.iter()
+		.map(|&sum| {
+			SumcheckClaim::new(
+				n_vars,
+				2,
+				vec![CompositeSumClaim {
+					composition: TestProductComposition::new(2),
+					sum,
+				}],
+			)
+			.unwrap()
+		})
+		.collect::<Vec<_>>();
+
+	let shared_provers = SharedSumcheckProver::<FDomain, _, _, _, 2>::new_batch(
+		multilins.clone(),
+		iter::zip(claim_indices, claim_sums).map(|(indices, sum)| {
+			(
+				indices,
+				vec![CompositeSumClaim {
+					composition: TestProductComposition::new(2),
+					sum,
+				}],
+			)
+		}),
+		domain_factory.clone(),
+		|_| 2,
+	)
+	.unwrap();
+
+	let provers = iter::zip(claim_indices, claim_sums)
+		.map(|(indices, sum)| {
+			RegularSumcheckProver::<FDomain, _, _, _>::new(
+				indices.map(|index| multilins[index].clone()).to_vec(),
+				[CompositeSumClaim {
+					composition: TestProductComposition::new(2),
+					sum,
+				}],
+				domain_factory.clone(),
+				|_| 2,
+			)
+			.unwrap()
+		})
+		.collect::<Vec<_>>();
+
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	let (shared_output, shared_proof) = batch_prove(shared_provers, challenger.clone()).unwrap();
+	let (output, proof) = batch_prove(provers, challenger.clone()).unwrap();
+	assert_eq!(shared_output, output);
+	assert_eq!(shared_proof, proof);
+
+	// The evaluation of the shared multilinear is split back to both claims.
+	assert_eq!(shared_output.multilinear_evals[0][1], shared_output.multilinear_evals[1][0]);
+
+	let verifier_output = batch_verify(&claims, shared_proof, challenger).unwrap();
+	assert_eq!(verifier_output, shared_output);
+}
+
+#[test]
+fn test_prove_verify_streaming() {
+	type P = PackedBinaryField4x32b;
+	type FDomain = BinaryField8b;
+
+	let mut rng = StdRng::seed_from_u64(0);
+	let n_vars = 8;
+	let multilins = generate_random_multilinears::<P, P>(&mut rng, n_vars, 2);
+	let composition = TestProductComposition::new(2);
+	let sum = compute_composite_sum(&multilins, &composition);
+	let claim = SumcheckClaim::new(
+		n_vars,
+		2,
+		vec![CompositeSumClaim {
+			composition: &composition,
+			sum,
+		}],
+	)
+	.unwrap();
+
+	let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+	let sources = multilins
+		.iter()
+		.map(|multilin| {
+			let bytes = bytemuck::cast_slice::<P, u8>(multilin.as_ref().evals()).to_vec();
+			ReadSource::new(Cursor::new(bytes), n_vars)
+		})
+		.collect();
+	// Four rounds are streamed before the multilinears fit in four chunks.
+	let streaming_prover = StreamingSumcheckProver::<FDomain, P, _, _, _, _>::new(
+		sources,
+		Cursor::new(Vec::new()),
+		[CompositeSumClaim {
+			composition: &composition,
+			sum,
+		}],
+		domain_factory.clone(),
+		4,
+	)
+	.unwrap();
+	let prover = RegularSumcheckProver::<FDomain, _, _, _>::new_with_fold_direction(
+		multilins.iter().collect(),
+		[CompositeSumClaim {
+			composition: &composition,
+			sum,
+		}],
+		domain_factory,
+		|_| 1,
+		FoldDirection::HighToLow,
+	)
+	.unwrap();
+
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	let (streaming_output, streaming_proof) =
+		prove_single(streaming_prover, challenger.clone()).unwrap();
+	let (output, proof) = prove_single(prover, challenger.clone()).unwrap();
+	assert_eq!(streaming_output, output);
+	assert_eq!(streaming_proof, proof);
+
+	let verifier_output = batch_verify_with_fold_direction(
+		&[claim],
+		streaming_proof,
+		FoldDirection::HighToLow,
+		challenger,
+	)
+	.unwrap();
+	let eval_point = FoldDirection::HighToLow.eval_point(&verifier_output.challenges, n_vars);
+	let query = MultilinearQuery::<P>::with_full_query(eval_point).unwrap();
+	for (multilin, &eval) in iter::zip(&multilins, &verifier_output.multilinear_evals[0]) {
+		assert_eq!(multilin.evaluate(&query).unwrap(), eval);
+	}
+}
+
+#[test]
+fn test_batch_verify_with_report() {
+	type F = BinaryField32b;
+	type FDomain = BinaryField8b;
+	type FE = BinaryField128b;
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let composition = TestProductComposition::new(2);
+	let witnesses = [6, 4]
+		.into_iter()
+		.map(|n_vars| {
+			let multilins = generate_random_multilinears::<F, FE>(&mut rng, n_vars, 2);
+			let sum = compute_composite_sum(&multilins, &composition);
+			(multilins, sum)
+		})
+		.collect::<Vec<_>>();
+	let make_claims = |sums: [FE; 2]| {
+		iter::zip(&witnesses, sums)
+			.map(|((multilins, _), sum)| {
+				SumcheckClaim::new(
+					multilins[0].n_vars(),
+					2,
+					vec![CompositeSumClaim {
+						composition: &composition,
+						sum,
+					}],
+				)
+				.unwrap()
+			})
+			.collect::<Vec<_>>()
+	};
+
+	let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+	let provers = witnesses
+		.iter()
+		.map(|(multilins, sum)| {
+			RegularSumcheckProver::<FDomain, _, _, _>::new(
+				multilins.iter().collect(),
+				[CompositeSumClaim {
+					composition: &composition,
+					sum: *sum,
+				}],
+				domain_factory.clone(),
+				|_| 2,
+			)
+			.unwrap()
+		})
+		.collect();
+
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	let (prover_output, proof) = batch_prove_with_features(
+		provers,
+		ProtocolFeatures::UNCOMPRESSED_ROUND_PROOFS,
+		challenger.clone(),
+	)
+	.unwrap();
+
+	let sums = [witnesses[0].1, witnesses[1].1];
+	let report =
+		batch_verify_with_report(&make_claims(sums), proof.clone(), challenger.clone()).unwrap();
+	assert_matches!(report.claims[..], [ClaimVerdict::Passed, ClaimVerdict::Passed]);
+	assert_eq!(report.result.unwrap(), prover_output);
+
+	// A wrong claimed sum is caught in the round that batches the claim in.
+	let wrong_sums = [sums[0], sums[1] + FE::ONE];
+	let report =
+		batch_verify_with_report(&make_claims(wrong_sums), proof.clone(), challenger.clone())
+			.unwrap();
+	assert_matches!(
+		report.claims[..],
+		[
+			ClaimVerdict::Inconclusive,
+			ClaimVerdict::Failed(VerificationError::RoundSumMismatch {
+				round: 2,
+				ref claim_indices
+			})
+		] if *claim_indices == (1..2)
+	);
+	assert_matches!(
+		report.result,
+		Err(Error::Verification(VerificationError::RoundSumMismatch { round: 2, .. }))
+	);
+	assert_matches!(
+		batch_verify(&make_claims(wrong_sums), proof.clone(), challenger.clone()),
+		Err(Error::Verification(VerificationError::RoundSumMismatch { round: 2, .. }))
+	);
+
+	let mut missing_eval = proof;
+	missing_eval.multilinear_evals[0].pop();
+	let report =
+		batch_verify_with_report(&make_claims(sums), missing_eval, challenger.clone()).unwrap();
+	assert_matches!(
+		report.claims[..],
+		[
+			ClaimVerdict::Failed(VerificationError::NumberOfClaimEvaluations {
+				claim_index: 0,
+				expected: 2
+			}),
+			ClaimVerdict::Inconclusive
+		]
+	);
+
+	// Compressed round proofs have no round sum checks to attribute.
+	let provers = witnesses
+		.iter()
+		.map(|(multilins, sum)| {
+			RegularSumcheckProver::<FDomain, _, _, _>::new(
+				multilins.iter().collect(),
+				[CompositeSumClaim {
+					composition: &composition,
+					sum: *sum,
+				}],
+				domain_factory.clone(),
+				|_| 2,
+			)
+			.unwrap()
+		})
+		.collect();
+	let (_, compressed) = batch_prove(provers, challenger.clone()).unwrap();
+	assert_matches!(
+		batch_verify_with_report(&make_claims(sums), compressed, challenger),
+		Err(Error::ReportRequiresUncompressedRoundProofs)
+	);
+}
+
+#[test]
+fn test_batch_prove_dyn_regular_and_zerocheck() {
+	type F = BinaryField32b;
+	type FDomain = BinaryField8b;
+	type FE = BinaryField128b;
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let composition = TestProductComposition::new(2);
+	let sumcheck_multilins = generate_random_multilinears::<F, FE>(&mut rng, 6, 2);
+	let sum = compute_composite_sum(&sumcheck_multilins, &composition);
+
+	// The product of the zerocheck multilinears vanishes on the hypercube, as one of them is zero
+	// at every vertex.
+	let zerocheck_n_vars = 4;
+	let zerocheck_multilins = (0..2)
+		.map(|j| {
+			let values = (0..1 << zerocheck_n_vars)
+				.map(|i| {
+					if i % 2 == j {
+						F::ZERO
+					} else {
+						<F as Field>::random(&mut rng)
+					}
+				})
+				.collect();
+			MultilinearExtension::from_values(values)
+				.unwrap()
+				.specialize::<FE>()
+		})
+		.collect::<Vec<_>>();
+
+	let mut challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	let zerocheck_challenges = challenger.sample_vec(zerocheck_n_vars);
+
+	let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+	let provers: Vec<Box<dyn SumcheckProver<FE> + '_>> = vec![
+		Box::new(
+			RegularSumcheckProver::<FDomain, _, _, _>::new(
+				sumcheck_multilins.iter().collect(),
+				[CompositeSumClaim {
+					composition: &composition,
+					sum,
+				}],
+				domain_factory.clone(),
+				|_| 2,
+			)
+			.unwrap(),
+		),
+		Box::new(
+			ZerocheckProver::<FDomain, _, _, _>::new(
+				zerocheck_multilins.iter().collect(),
+				[&composition],
+				&zerocheck_challenges,
+				domain_factory,
+				|_| 2,
+			)
+			.unwrap(),
+		),
+	];
+	let (prover_output, proof) = batch_prove_dyn(provers, challenger.clone()).unwrap();
+
+	// The zerocheck claim is verified as a sumcheck claim with the eq indicator as an extra
+	// multilinear.
+	let claims = vec![
+		SumcheckClaim::new(
+			6,
+			2,
+			vec![CompositeSumClaim {
+				composition: Arc::new(&composition) as Arc<dyn CompositionPoly<FE> + '_>,
+				sum,
+			}],
+		)
+		.unwrap(),
+		SumcheckClaim::new(
+			zerocheck_n_vars,
+			3,
+			vec![CompositeSumClaim {
+				composition: Arc::new(ExtraProduct::new(&composition))
+					as Arc<dyn CompositionPoly<FE> + '_>,
+				sum: FE::ZERO,
+			}],
+		)
+		.unwrap(),
+	];
+	let verifier_output = batch_verify(&claims, proof, challenger).unwrap();
+	assert_eq!(prover_output, verifier_output);
+
+	let query = MultilinearQuery::<FE>::with_full_query(&verifier_output.challenges).unwrap();
+	for (multilin, &eval) in iter::zip(&sumcheck_multilins, &verifier_output.multilinear_evals[0]) {
+		assert_eq!(multilin.evaluate(&query).unwrap(), eval);
+	}
+}
+
+#[test]
+fn test_batch_verify_with_observer() {
+	type F = BinaryField32b;
+	type FDomain = BinaryField8b;
+	type FE = BinaryField128b;
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let composition = TestProductComposition::new(2);
+	let witnesses = [6, 4]
+		.into_iter()
+		.map(|n_vars| {
+			let multilins = generate_random_multilinears::<F, FE>(&mut rng, n_vars, 2);
+			let sum = compute_composite_sum(&multilins, &composition);
+			(multilins, sum)
+		})
+		.collect::<Vec<_>>();
+	let make_claims = |sums: [FE; 2]| {
+		iter::zip(&witnesses, sums)
+			.map(|((multilins, _), sum)| {
+				SumcheckClaim::new(
+					multilins[0].n_vars(),
+					2,
+					vec![CompositeSumClaim {
+						composition: &composition,
+						sum,
+					}],
+				)
+				.unwrap()
+			})
+			.collect::<Vec<_>>()
+	};
+
+	let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+	let provers = witnesses
+		.iter()
+		.map(|(multilins, sum)| {
+			RegularSumcheckProver::<FDomain, _, _, _>::new(
+				multilins.iter().collect(),
+				[CompositeSumClaim {
+					composition: &composition,
+					sum: *sum,
+				}],
+				domain_factory.clone(),
+				|_| 2,
+			)
+			.unwrap()
+		})
+		.collect();
+
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	let (_, proof) = batch_prove(provers, challenger.clone()).unwrap();
+
+	// Records the active claims, the weighted sums and the reduced sum of every round.
+	let observe = |sums: [FE; 2]| {
+		let mut states = Vec::new();
+		let result = batch_verify_with_observer(
+			&make_claims(sums),
+			proof.clone(),
+			challenger.clone(),
+			|state| {
+				assert_eq!(state.challenges.len(), state.round + 1);
+				assert_eq!(
+					evaluate_univariate(&state.round_coeffs.0, state.challenges[state.round]),
+					state.sum
+				);
+				states.push((state.n_active_claims, state.weighted_sums.to_vec(), state.sum));
+			},
+		);
+		(result, states)
+	};
+
+	let sums = [witnesses[0].1, witnesses[1].1];
+	let (result, states) = observe(sums);
+	let output = result.unwrap();
+	assert_eq!(states.len(), output.challenges.len());
+	let n_active_claims = states.iter().map(|state| state.0).collect::<Vec<_>>();
+	assert_eq!(n_active_claims, [1, 1, 2, 2, 2, 2]);
+
+	// A wrong claimed sum for the second claim diverges in the round that batches it in.
+	let (result, wrong_states) = observe([sums[0], sums[1] + FE::ONE]);
+	assert_matches!(result, Err(Error::Verification(VerificationError::IncorrectBatchEvaluation)));
+	assert_eq!(wrong_states[..2], states[..2]);
+	assert_eq!(wrong_states[2].1[0], states[2].1[0]);
+	assert_ne!(wrong_states[2].1[1], states[2].1[1]);
+	assert_ne!(wrong_states[2].2, states[2].2);
+}
+
+/// A backend that counts the calls to each hook and delegates to the CPU backend.
+#[derive(Debug, Default)]
+struct CountingBackend {
+	n_folds: AtomicUsize,
+	n_sums: AtomicUsize,
+}
+
+impl ComputeBackend for &CountingBackend {
+	fn fold_multilinear<P: PackedField>(
+		&self,
+		multilinear: &MultilinearExtension<P>,
+		n_vars: usize,
+		challenge: P::Scalar,
+		fold_direction: FoldDirection,
+	) -> Result<MultilinearExtension<P>, Error> {
+		self.n_folds.fetch_add(1, Ordering::Relaxed);
+		CpuBackend.fold_multilinear(multilinear, n_vars, challenge, fold_direction)
+	}
+
+	fn sum_composition_evals<P, Evaluator>(
+		&self,
+		n_vars: usize,
+		n_multilinears: usize,
+		sample: &(dyn Fn(Range<usize>, &mut Array2D<P>, &mut Array2D<P>) -> Result<(), Error>
+		      + Sync),
+		evaluators: &[Evaluator],
+		memory_budget: &MemoryBudget,
+	) -> Result<Vec<Vec<P::Scalar>>, Error>
+	where
+		P: PackedField,
+		Evaluator: SumcheckEvaluator<P> + Sync,
+	{
+		self.n_sums.fetch_add(1, Ordering::Relaxed);
+		CpuBackend.sum_composition_evals(n_vars, n_multilinears, sample, evaluators, memory_budget)
+	}
+}
+
+#[test]
+fn test_prove_with_backend() {
+	type F = BinaryField32b;
+	type FDomain = BinaryField8b;
+	type FE = BinaryField128b;
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let n_vars = 6;
+	let composition = TestProductComposition::new(2);
+	let multilins = generate_random_multilinears::<F, FE>(&mut rng, n_vars, 2);
+	let sum = compute_composite_sum(&multilins, &composition);
+	let claim = SumcheckClaim::new(
+		n_vars,
+		2,
+		vec![CompositeSumClaim {
+			composition: &composition,
+			sum,
+		}],
+	)
+	.unwrap();
+
+	let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	for fold_direction in [FoldDirection::LowToHigh, FoldDirection::HighToLow] {
+		let composite_claims = [CompositeSumClaim {
+			composition: &composition,
+			sum,
+		}];
+		// The multilinears switch over to the large field in the second round.
+		let prover = RegularSumcheckProver::<FDomain, _, _, _>::new_with_fold_direction(
+			multilins.iter().collect(),
+			composite_claims.clone(),
+			domain_factory.clone(),
+			|_| 2,
+			fold_direction,
+		)
+		.unwrap();
+		let (expected_output, expected_proof) =
+			batch_prove(vec![prover], challenger.clone()).unwrap();
+
+		let backend = CountingBackend::default();
+		let prover = RegularSumcheckProver::<FDomain, _, _, _, _>::new_with_backend(
+			multilins.iter().collect(),
+			composite_claims,
+			domain_factory.clone(),
+			|_| 2,
+			fold_direction,
+			&backend,
+		)
+		.unwrap();
+		let (prover_output, proof) = batch_prove(vec![prover], challenger.clone()).unwrap();
+		assert_eq!(prover_output, expected_output);
+		assert_eq!(proof, expected_proof);
+
+		// Every round is summed through the backend, and both multilinears are folded through it in
+		// every round after the switchover.
+		assert_eq!(backend.n_sums.load(Ordering::Relaxed), n_vars);
+		assert_eq!(backend.n_folds.load(Ordering::Relaxed), 2 * (n_vars - 2));
+
+		let verifier_output = batch_verify_with_fold_direction(
+			&[claim.clone()],
+			proof,
+			fold_direction,
+			challenger.clone(),
+		)
+		.unwrap();
+		assert_eq!(prover_output, verifier_output);
+	}
+}
+
+/// A backend that drops the last round evaluation of every composite.
+#[derive(Debug)]
+struct DroppingBackend;
+
+impl ComputeBackend for DroppingBackend {
+	fn fold_multilinear<P: PackedField>(
+		&self,
+		multilinear: &MultilinearExtension<P>,
+		n_vars: usize,
+		challenge: P::Scalar,
+		fold_direction: FoldDirection,
+	) -> Result<MultilinearExtension<P>, Error> {
+		CpuBackend.fold_multilinear(multilinear, n_vars, challenge, fold_direction)
+	}
+
+	fn sum_composition_evals<P, Evaluator>(
+		&self,
+		n_vars: usize,
+		n_multilinears: usize,
+		sample: &(dyn Fn(Range<usize>, &mut Array2D<P>, &mut Array2D<P>) -> Result<(), Error>
+		      + Sync),
+		evaluators: &[Evaluator],
+		memory_budget: &MemoryBudget,
+	) -> Result<Vec<Vec<P::Scalar>>, Error>
+	where
+		P: PackedField,
+		Evaluator: SumcheckEvaluator<P> + Sync,
+	{
+		let mut evals = CpuBackend.sum_composition_evals(
+			n_vars,
+			n_multilinears,
+			sample,
+			evaluators,
+			memory_budget,
+		)?;
+		for evals in evals.iter_mut() {
+			evals.pop();
+		}
+		Ok(evals)
+	}
+}
+
+#[test]
+fn test_prove_checks_round_evaluations() {
+	type F = BinaryField32b;
+	type FDomain = BinaryField8b;
+	type FE = BinaryField128b;
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let n_vars = 4;
+	let composition = TestProductComposition::new(2);
+	let multilins = generate_random_multilinears::<F, FE>(&mut rng, n_vars, 2);
+	let sum = compute_composite_sum(&multilins, &composition);
+
+	let mut prover = RegularSumcheckProver::<FDomain, _, _, _, _>::new_with_backend(
+		multilins.iter().collect(),
+		[CompositeSumClaim {
+			composition: &composition,
+			sum,
+		}],
+		IsomorphicEvaluationDomainFactory::<FDomain>::default(),
+		|_| 2,
+		FoldDirection::LowToHigh,
+		DroppingBackend,
+	)
+	.unwrap();
+	assert_matches!(
+		prover.execute(FE::ONE),
+		Err(Error::IncorrectNumberOfRoundEvaluations {
+			index: 0,
+			expected: 2
+		})
+	);
+}
+
+/// A prover that adds one to the linear coefficient of its round polynomials after the first.
+#[cfg(feature = "debug_validate_round")]
+struct TamperedProver<Prover> {
+	inner: Prover,
+	round: usize,
+}
+
+#[cfg(feature = "debug_validate_round")]
+impl<F: Field, Prover: SumcheckProver<F>> SumcheckProver<F> for TamperedProver<Prover> {
+	fn n_vars(&self) -> usize {
+		self.inner.n_vars()
+	}
+
+	fn execute(&mut self, batch_coeff: F) -> Result<RoundCoeffs<F>, Error> {
+		let mut coeffs = self.inner.execute(batch_coeff)?;
+		if self.round > 0 {
+			coeffs.0[1] += F::ONE;
+		}
+		self.round += 1;
+		Ok(coeffs)
+	}
+
+	fn fold(&mut self, challenge: F) -> Result<(), Error> {
+		self.inner.fold(challenge)
+	}
+
+	fn finish(self) -> Result<Vec<F>, Error> {
+		self.inner.finish()
+	}
+
+	fn finish_boxed(self: Box<Self>) -> Result<Vec<F>, Error> {
+		(*self).finish()
+	}
+}
+
+#[cfg(feature = "debug_validate_round")]
+#[test]
+fn test_prove_validates_round_sums() {
+	type F = BinaryField32b;
+	type FDomain = BinaryField8b;
+	type FE = BinaryField128b;
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let composition = TestProductComposition::new(2);
+	let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+	let witnesses = [5, 3].map(|n_vars| generate_random_multilinears::<F, FE>(&mut rng, n_vars, 2));
+	let provers = || {
+		witnesses
+			.iter()
+			.map(|multilins| {
+				RegularSumcheckProver::<FDomain, _, _, _>::new(
+					multilins.iter().collect(),
+					[CompositeSumClaim {
+						composition: &composition,
+						sum: compute_composite_sum(multilins, &composition),
+					}],
+					domain_factory.clone(),
+					|_| 2,
+				)
+				.unwrap()
+			})
+			.collect::<Vec<_>>()
+	};
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+
+	batch_prove(provers(), challenger.clone()).unwrap();
+
+	// The second prover takes part from round 2, so its round sum is first checked in round 3.
+	let mut provers = provers().into_iter();
+	let tampered: Vec<Box<dyn SumcheckProver<FE> + '_>> = vec![
+		Box::new(provers.next().unwrap()),
+		Box::new(TamperedProver {
+			inner: provers.next().unwrap(),
+			round: 0,
+		}),
+	];
+	assert_matches!(
+		batch_prove_dyn(tampered, challenger.clone()),
+		Err(Error::RoundSumValidationFailure {
+			round: 3,
+			prover_index: 1
+		})
+	);
+}
+
+/// A remote prover that runs a local prover, optionally corrupting the round polynomial of one
+/// round.
+struct LocalRemote<Prover> {
+	prover: Option<Prover>,
+	corrupt_round: Option<usize>,
+	round: usize,
+}
+
+impl<F: Field, Prover: SumcheckProver<F>> RemoteRoundProver<F> for LocalRemote<Prover> {
+	fn execute(&mut self, batch_coeff: F) -> Result<RoundCoeffs<F>, RemoteError> {
+		let prover = self.prover.as_mut().ok_or("the prover is finished")?;
+		let mut round_coeffs = prover.execute(batch_coeff)?;
+		if self.corrupt_round == Some(self.round) {
+			round_coeffs.0[0] += F::ONE;
+		}
+		Ok(round_coeffs)
+	}
+
+	fn fold(&mut self, challenge: F) -> Result<(), RemoteError> {
+		let prover = self.prover.as_mut().ok_or("the prover is finished")?;
+		prover.fold(challenge)?;
+		self.round += 1;
+		Ok(())
+	}
+
+	fn finish(&mut self) -> Result<Vec<F>, RemoteError> {
+		let prover = self.prover.take().ok_or("the prover is finished")?;
+		Ok(prover.finish()?)
+	}
+}
+
+#[test]
+fn test_prove_with_remote_prover() {
+	type F = BinaryField32b;
+	type FDomain = BinaryField8b;
+	type FE = BinaryField128b;
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let n_vars = 6;
+	let composition = TestProductComposition::new(2);
+	let multilins = generate_random_multilinears::<F, FE>(&mut rng, n_vars, 2);
+	let sum = compute_composite_sum(&multilins, &composition);
+	let claim = SumcheckClaim::new(
+		n_vars,
+		2,
+		vec![CompositeSumClaim {
+			composition: &composition,
+			sum,
+		}],
+	)
+	.unwrap();
+
+	let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+	let make_remote = |corrupt_round| LocalRemote {
+		prover: Some(
+			RegularSumcheckProver::<FDomain, _, _, _>::new(
+				multilins.iter().collect(),
+				[CompositeSumClaim {
+					composition: &composition,
+					sum,
+				}],
+				domain_factory.clone(),
+				|_| 2,
+			)
+			.unwrap(),
+		),
+		corrupt_round,
+		round: 0,
+	};
+
+	// The adapter produces the same proof as the prover it forwards to.
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	let local_prover = make_remote(None).prover.unwrap();
+	let (expected_output, expected_proof) =
+		batch_prove(vec![local_prover], challenger.clone()).unwrap();
+
+	let prover = RemoteSumcheckProver::new(make_remote(None), &claim);
+	let (prover_output, proof) = batch_prove(vec![prover], challenger.clone()).unwrap();
+	assert_eq!(prover_output, expected_output);
+	assert_eq!(proof, expected_proof);
+
+	let verifier_output = batch_verify(&[claim.clone()], proof, challenger.clone()).unwrap();
+	assert_eq!(prover_output, verifier_output);
+
+	// A wrong round polynomial is caught in the round it is returned.
+	let prover = RemoteSumcheckProver::new(make_remote(Some(2)), &claim);
+	assert_matches!(
+		batch_prove(vec![prover], challenger),
+		Err(Error::RemoteRoundSumMismatch { round: 2 })
+	);
+}
+
+#[test]
+fn test_prove_verify_with_transparent_multilinear() {
+	type F = BinaryField32b;
+	type FDomain = BinaryField8b;
+	type FE = BinaryField128b;
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let n_vars = 5;
+	let n_points = 19;
+	let composition = TestProductComposition::new(2);
+	let step_down_values = (0..1 << n_vars)
+		.map(|index| if index < n_points { F::ONE } else { F::ZERO })
+		.collect::<Vec<_>>();
+	let mut multilins = generate_random_multilinears::<F, FE>(&mut rng, n_vars, 1);
+	multilins.push(
+		MultilinearExtension::from_values(step_down_values)
+			.unwrap()
+			.specialize(),
+	);
+	let sum = compute_composite_sum(&multilins, &composition);
+	let claim = SumcheckClaim::new(
+		n_vars,
+		2,
+		vec![CompositeSumClaim {
+			composition: &composition,
+			sum,
+		}],
+	)
+	.unwrap()
+	.with_transparent(1, Arc::new(StepDown::new(n_vars, n_points).unwrap()))
+	.unwrap();
+	assert_eq!(claim.n_committed_multilinears(), 1);
+
+	let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+	let prover = RegularSumcheckProver::<FDomain, _, _, _>::new(
+		multilins.iter().collect(),
+		[CompositeSumClaim {
+			composition: &composition,
+			sum,
+		}],
+		domain_factory,
+		|_| 2,
+	)
+	.unwrap();
+
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	let (prover_output, full_proof) = batch_prove(vec![prover], challenger.clone()).unwrap();
+	let proof = full_proof
+		.clone()
+		.omit_transparent_evals(std::slice::from_ref(&claim))
+		.unwrap();
+	assert_eq!(proof.multilinear_evals, vec![vec![prover_output.multilinear_evals[0][0]]]);
+
+	// The verifier computes the evaluation of the step-down multilinear itself.
+	let verifier_output =
+		batch_verify(&[claim.clone()], proof.clone(), challenger.clone()).unwrap();
+	assert_eq!(verifier_output, prover_output);
+
+	// A proof with the evaluations of the transparent multilinears is rejected.
+	assert_matches!(
+		batch_verify(&[claim.clone()], full_proof, challenger.clone()),
+		Err(Error::Verification(VerificationError::NumberOfClaimEvaluations {
+			claim_index: 0,
+			expected: 1
+		}))
+	);
+	assert_matches!(
+		claim
+			.clone()
+			.with_transparent(2, Arc::new(StepDown::new(n_vars, n_points).unwrap())),
+		Err(Error::TransparentIndexOutOfRange { index: 2, .. })
+	);
+	assert_matches!(
+		claim.with_transparent(0, Arc::new(StepDown::new(n_vars - 1, 3).unwrap())),
+		Err(Error::TransparentNumberOfVariables { index: 0, .. })
+	);
+}
+
+#[test]
+fn test_prove_with_memory_budget() {
+	type F = BinaryField32b;
+	type FDomain = BinaryField8b;
+	type FE = BinaryField128b;
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let n_vars = 10;
+	let composition = TestProductComposition::new(3);
+	let multilins = generate_random_multilinears::<F, FE>(&mut rng, n_vars, 3);
+	let sum = compute_composite_sum(&multilins, &composition);
+	let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+
+	let prove = |memory_budget: MemoryBudget| {
+		let prover = RegularSumcheckProver::<FDomain, _, _, _>::new(
+			multilins.iter().collect(),
+			[CompositeSumClaim {
+				composition: &composition,
+				sum,
+			}],
+			domain_factory.clone(),
+			|_| 2,
+		)
+		.unwrap();
+		batch_prove_with_memory_budget(vec![prover], memory_budget, challenger.clone()).unwrap()
+	};
+
+	let (expected_output, expected_proof, unlimited_peak) = prove(MemoryBudget::unlimited());
+
+	// The budget holds a few vertices at a time, so the rounds are chunked into small batches.
+	let max_scratch_bytes = 1024;
+	let (output, proof, peak) = prove(MemoryBudget::new(max_scratch_bytes));
+	assert_eq!(output, expected_output);
+	assert_eq!(proof, expected_proof);
+	assert!(peak > 0);
+	assert!(peak <= max_scratch_bytes);
+	assert!(unlimited_peak > max_scratch_bytes);
+
+	// A budget below the scratch memory of a single vertex still proves, one vertex at a time.
+	let (output, proof, _) = prove(MemoryBudget::new(0));
+	assert_eq!(output, expected_output);
+	assert_eq!(proof, expected_proof);
+}
+
+#[test]
+fn test_prove_reuses_scratch_arena() {
+	type F = BinaryField32b;
+	type FDomain = BinaryField8b;
+	type FE = BinaryField128b;
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let n_vars = 10;
+	let composition = TestProductComposition::new(3);
+	let multilins = generate_random_multilinears::<F, FE>(&mut rng, n_vars, 3);
+	let sum = compute_composite_sum(&multilins, &composition);
+	let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+
+	let prove = |memory_budget: MemoryBudget| {
+		let prover = RegularSumcheckProver::<FDomain, _, _, _>::new(
+			multilins.iter().collect(),
+			[CompositeSumClaim {
+				composition: &composition,
+				sum,
+			}],
+			domain_factory.clone(),
+			|_| 2,
+		)
+		.unwrap();
+		let (output, proof, _) =
+			batch_prove_with_memory_budget(vec![prover], memory_budget, challenger.clone())
+				.unwrap();
+		(output, proof)
+	};
+
+	let expected = prove(MemoryBudget::unlimited());
+
+	// The rounds return their scratch buffers to the arena shared by clones of the budget.
+	let memory_budget = MemoryBudget::unlimited();
+	assert!(memory_budget.scratch_arena().is_empty());
+	assert_eq!(prove(memory_budget.clone()), expected);
+	assert!(!memory_budget.scratch_arena().is_empty());
+
+	// A second proof reuses the buffers, which still hold the values of the first proof.
+	assert_eq!(prove(memory_budget.clone()), expected);
+
+	// Under a cap, buffers of other shapes are dropped rather than held on top of the budget.
+	let capped = MemoryBudget::new(1024).with_scratch_arena(memory_budget.scratch_arena().clone());
+	assert_eq!(prove(capped), expected);
+
+	memory_budget.scratch_arena().clear();
+	assert!(memory_budget.scratch_arena().is_empty());
+}
+
+#[test]
+fn test_scratch_arena_takes_matching_buffers() {
+	let arena = ScratchArena::default();
+	arena.put(vec![0u8; 4]);
+	arena.put(vec![0u8; 8]);
+	arena.put(vec![0u32; 4]);
+
+	assert_eq!(arena.take(|buffer: &Vec<u8>| buffer.len() == 8), Some(vec![0u8; 8]));
+	assert_eq!(arena.take(|buffer: &Vec<u8>| buffer.len() == 8), None);
+	assert_eq!(arena.take(|_: &Vec<u16>| true), None);
+	assert_eq!(arena.len(), 2);
+
+	// Retaining only affects the buffers of the given type.
+	arena.retain(|_: &Vec<u8>| false);
+	assert_eq!(arena.len(), 1);
+	assert_eq!(arena.take(|_: &Vec<u32>| true), Some(vec![0u32; 4]));
+	assert!(arena.is_empty());
+}
+
+#[test]
+fn test_regular_prover_matches_reference() {
+	type F = BinaryField32b;
+	type FDomain = BinaryField8b;
+	type FE = BinaryField128b;
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let n_vars = 7;
+	let multilins = generate_random_multilinears::<F, FE>(&mut rng, n_vars, 3);
+	let composition = TestProductComposition::new(3);
+	let sum = compute_composite_sum(&multilins, &composition);
+	let composite_claims = [CompositeSumClaim {
+		composition: &composition,
+		sum,
+	}];
+
+	let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+	for fold_direction in [FoldDirection::LowToHigh, FoldDirection::HighToLow] {
+		let prover = RegularSumcheckProver::<FDomain, _, _, _>::new_with_fold_direction(
+			multilins.iter().collect(),
+			composite_claims.clone(),
+			domain_factory.clone(),
+			|_| 3,
+			fold_direction,
+		)
+		.unwrap();
+		let reference = NaiveSumcheckProver::new_with_fold_direction(
+			&multilins,
+			composite_claims.clone(),
+			domain_factory.clone(),
+			fold_direction,
+		)
+		.unwrap();
+		assert_prover_matches_reference(prover, reference, &mut rng);
+	}
+}
+
+#[test]
+fn test_prove_verify_windowed_sum() {
+	type F = BinaryField32b;
+	type FDomain = BinaryField8b;
+	type FE = BinaryField128b;
+	type U = <PackedBinaryField1x128b as WithUnderlier>::Underlier;
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let n_vars = 8;
+	let window = 37..201;
+	let mut oracles = MultilinearOracleSet::<FE>::new();
+	let batch_id = oracles.add_committed_batch(n_vars, F::TOWER_LEVEL);
+	let [oracle_id] = oracles.add_committed_multiple(batch_id);
+
+	let values = repeat_with(|| F::random(&mut rng))
+		.take(1 << n_vars)
+		.collect::<Vec<_>>();
+	let sum: FE = values[window.clone()].iter().copied().sum::<F>().into();
+	let multilin: MultilinearWitness<PackedBinaryField1x128b> =
+		MultilinearExtension::from_values(values)
+			.unwrap()
+			.specialize_arc_dyn();
+
+	let claim = windowed_sum_claim(&mut oracles, oracle_id, window.clone(), sum).unwrap();
+	let mut witness_index = MultilinearExtensionIndex::<U, FE>::new();
+	witness_index
+		.update_multilin_poly([(oracle_id, multilin.clone())])
+		.unwrap();
+	let witness_index = windowed_sum_witness(&claim, witness_index).unwrap();
+	let prover = windowed_sum_prover::<_, _, FDomain>(
+		&claim,
+		&witness_index,
+		IsomorphicEvaluationDomainFactory::<FDomain>::default(),
+		|_| 3,
+	)
+	.unwrap();
+
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	let (_, proof) = batch_prove(vec![prover], &mut challenger.clone()).unwrap();
+	let output =
+		batch_verify(&[claim.sumcheck_claim().clone()], proof.clone(), &mut challenger.clone())
+			.unwrap();
+	let challenges = output.challenges.clone();
+
+	let eval_claims = make_eval_claims(&oracles, [claim.meta().clone()], output).unwrap();
+	assert_eq!(eval_claims.len(), 3);
+	let query = MultilinearQuery::with_full_query(&challenges).unwrap();
+	assert_eq!(eval_claims[0].poly.id(), oracle_id);
+	assert_eq!(eval_claims[0].eval, multilin.evaluate(&query).unwrap());
+	// The selectors are transparent, so the verifier evaluates them itself.
+	assert_eq!(
+		eval_claims[1].eval,
+		StepDown::new(n_vars, window.end)
+			.unwrap()
+			.evaluate(&challenges)
+			.unwrap()
+	);
+	assert_eq!(
+		eval_claims[2].eval,
+		StepUp::new(n_vars, window.start)
+			.unwrap()
+			.evaluate(&challenges)
+			.unwrap()
+	);
+
+	// The proof does not verify a different sum over the window.
+	let wrong_claim = windowed_sum_claim(&mut oracles, oracle_id, window, sum + FE::ONE).unwrap();
+	assert_matches!(
+		batch_verify(&[wrong_claim.sumcheck_claim().clone()], proof, &mut challenger.clone()),
+		Err(Error::Verification(VerificationError::RoundSumMismatch { .. }))
+	);
+
+	assert_matches!(
+		windowed_sum_claim(&mut oracles, oracle_id, 5..4, FE::ZERO),
+		Err(Error::InvalidWindow { n_vars: 8, .. })
+	);
+	assert_matches!(
+		windowed_sum_claim(&mut oracles, oracle_id, 0..257, FE::ZERO),
+		Err(Error::InvalidWindow { n_vars: 8, .. })
+	);
+	// Empty windows and the whole hypercube are windows.
+	assert!(windowed_sum_claim(&mut oracles, oracle_id, 9..9, FE::ZERO).is_ok());
+	assert!(windowed_sum_claim(&mut oracles, oracle_id, 0..256, FE::ZERO).is_ok());
+}
+
+#[cfg(feature = "parallel_batch_prove")]
+#[test]
+fn test_batch_prove_parallel_matches_sequential() {
+	use super::prove::batch_prove_parallel;
+
+	type F = BinaryField32b;
+	type FDomain = BinaryField8b;
+	type FE = BinaryField128b;
+	let mut rng = StdRng::seed_from_u64(0);
+
+	let composition = TestProductComposition::new(2);
+	let domain_factory = IsomorphicEvaluationDomainFactory::<FDomain>::default();
+	let multilins = [8, 8, 6, 4]
+		.map(|n_vars| generate_random_multilinears::<F, FE>(&mut rng, n_vars, 2))
+		.to_vec();
+	let provers = || {
+		multilins
+			.iter()
+			.map(|multilins| {
+				let sum = compute_composite_sum(multilins, &composition);
+				RegularSumcheckProver::<FDomain, _, _, _>::new(
+					multilins.iter().collect(),
+					[CompositeSumClaim {
+						composition: &composition,
+						sum,
+					}],
+					domain_factory.clone(),
+					|_| 2,
+				)
+				.unwrap()
+			})
+			.collect::<Vec<_>>()
+	};
+
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	let expected = batch_prove(provers(), challenger.clone()).unwrap();
+	assert_eq!(batch_prove_parallel(provers(), challenger).unwrap(), expected);
+}
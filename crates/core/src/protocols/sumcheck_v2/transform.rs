@@ -0,0 +1,157 @@
+// Copyright 2024 Ulvetanna Inc.
+
+use super::{
+	common::Proof,
+	encoding::{DecodingError, ProofEncoding},
+};
+use binius_field::TowerField;
+use binius_utils::bail;
+
+/// The version of the proof envelope format written by [`Proof::encode_envelope`].
+pub const ENVELOPE_VERSION: u8 = 1;
+
+/// The error type returned by [`ProofTransform::decompress`].
+pub type TransformError = Box<dyn std::error::Error + Send + Sync>;
+
+/// A reversible post-processing step applied to an encoded proof, such as general-purpose or
+/// domain-specific compression.
+///
+/// The prover applies [`Self::compress`] to the encoded proof and the verifier applies
+/// [`Self::decompress`] before decoding it. The envelope records the [`Self::id`] of the transform,
+/// so the verifier can select the matching transform among the ones it supports.
+///
+/// The decompressed bytes are decoded strictly, so a transform need not validate them. It should
+/// bound the size of its output though, as the input of the verifier is untrusted.
+pub trait ProofTransform {
+	/// The identifier of the transform recorded in the envelope.
+	///
+	/// The identifier 0 is reserved for [`IdentityTransform`].
+	fn id(&self) -> u8;
+
+	/// Transforms an encoded proof on the prover side.
+	fn compress(&self, bytes: &[u8]) -> Vec<u8>;
+
+	/// Inverts [`Self::compress`] on the verifier side.
+	fn decompress(&self, bytes: &[u8]) -> Result<Vec<u8>, TransformError>;
+}
+
+/// The transform that leaves the encoded proof unchanged.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct IdentityTransform;
+
+impl ProofTransform for IdentityTransform {
+	fn id(&self) -> u8 {
+		0
+	}
+
+	fn compress(&self, bytes: &[u8]) -> Vec<u8> {
+		bytes.to_vec()
+	}
+
+	fn decompress(&self, bytes: &[u8]) -> Result<Vec<u8>, TransformError> {
+		Ok(bytes.to_vec())
+	}
+}
+
+/// The header of a proof envelope.
+///
+/// An envelope is the header followed by the transformed proof. The header consists of one byte
+/// each for the envelope version, the [`ProofEncoding`] of the proof, and the identifier of the
+/// [`ProofTransform`] that was applied to the encoded proof.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnvelopeHeader {
+	pub encoding: ProofEncoding,
+	pub transform_id: u8,
+}
+
+impl EnvelopeHeader {
+	const LEN: usize = 3;
+
+	/// Parses the header of an envelope and returns it with the transformed proof.
+	///
+	/// ## Throws
+	///
+	/// * [`DecodingError::UnsupportedEnvelopeVersion`] if the envelope has an unknown version
+	/// * [`DecodingError`] if `bytes` is shorter than the header or has an invalid encoding tag
+	pub fn parse(bytes: &[u8]) -> Result<(Self, &[u8]), DecodingError> {
+		if bytes.len() < Self::LEN {
+			bail!(DecodingError::UnexpectedEnd);
+		}
+		let (header, payload) = bytes.split_at(Self::LEN);
+
+		if header[0] != ENVELOPE_VERSION {
+			bail!(DecodingError::UnsupportedEnvelopeVersion(header[0]));
+		}
+		let encoding = match header[1] {
+			0 => ProofEncoding::Standard,
+			1 => ProofEncoding::Compact,
+			tag => bail!(DecodingError::InvalidTag(tag)),
+		};
+
+		let header = Self {
+			encoding,
+			transform_id: header[2],
+		};
+		Ok((header, payload))
+	}
+
+	fn write(&self, bytes: &mut Vec<u8>) {
+		let encoding_tag = match self.encoding {
+			ProofEncoding::Standard => 0,
+			ProofEncoding::Compact => 1,
+		};
+		bytes.extend([ENVELOPE_VERSION, encoding_tag, self.transform_id]);
+	}
+}
+
+impl<F: TowerField> Proof<F> {
+	/// Encodes the proof and wraps it in an envelope after applying `transform`.
+	pub fn encode_envelope(
+		&self,
+		encoding: ProofEncoding,
+		transform: &dyn ProofTransform,
+	) -> Vec<u8> {
+		let header = EnvelopeHeader {
+			encoding,
+			transform_id: transform.id(),
+		};
+
+		let mut bytes = Vec::new();
+		header.write(&mut bytes);
+		bytes.extend(transform.compress(&self.encode(encoding)));
+		bytes
+	}
+
+	/// Decodes a proof from an envelope produced by [`Self::encode_envelope`].
+	///
+	/// The transform recorded in the envelope is inverted with the transform of the same identifier
+	/// in `transforms`. The [`IdentityTransform`] is always supported.
+	///
+	/// ## Throws
+	///
+	/// * [`DecodingError::UnknownTransform`] if no transform in `transforms` has the recorded identifier
+	/// * [`DecodingError::Transform`] if the transform fails to decompress the proof
+	/// * [`DecodingError`] if the envelope or the decompressed proof is malformed
+	pub fn decode_envelope(
+		bytes: &[u8],
+		transforms: &[&dyn ProofTransform],
+	) -> Result<Self, DecodingError> {
+		let (header, payload) = EnvelopeHeader::parse(bytes)?;
+
+		let transform_id = header.transform_id;
+		let transform = transforms
+			.iter()
+			.copied()
+			.chain([&IdentityTransform as &dyn ProofTransform])
+			.find(|transform| transform.id() == transform_id)
+			.ok_or(DecodingError::UnknownTransform(transform_id))?;
+
+		let encoded = transform
+			.decompress(payload)
+			.map_err(|source| DecodingError::Transform {
+				transform_id,
+				source,
+			})?;
+		Self::decode(&encoded, header.encoding)
+	}
+}
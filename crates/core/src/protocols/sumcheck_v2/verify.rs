@@ -1,19 +1,145 @@
 // Copyright 2024 Ulvetanna Inc.
 
 use super::{
-	common::{BatchSumcheckOutput, Proof, RoundProof, SumcheckClaim},
+	common::{
+		sort_permutation_by_n_vars, BatchSumcheckOutput, FoldDirection, Proof, RoundCoeffs,
+		RoundEvals, SumcheckClaim, SumcheckOutput,
+	},
 	error::{Error, VerificationError},
+	features::{
+		check_protocol_features, observe_protocol_features, observe_round_proof_len,
+		ProtocolFeatures,
+	},
+	labels::{check_disclosed_claim_labels, claim_labels, observe_claim_labels},
 };
 use crate::{
 	challenger::{CanObserve, CanSample},
-	polynomial::{evaluate_univariate, CompositionPoly},
+	polynomial::{evaluate_univariate, CompositionPoly, EvaluationDomain},
 };
 use binius_field::{
 	util::{inner_product_unchecked, powers},
-	Field,
+	ExtensionField, Field, PackedExtension,
 };
-use binius_utils::{bail, sorting::is_sorted_ascending};
-use itertools::izip;
+use binius_utils::{bail, cost_report::record_proof_bytes, sorting::is_sorted_ascending};
+use rayon::prelude::*;
+use std::{iter, mem, ops::Range};
+use tracing::{debug_span, instrument};
+
+/// Verify a sumcheck protocol execution for a single claim.
+///
+/// This is [`batch_verify`] with a batch of one claim, whose output is unwrapped. It verifies
+/// proofs produced by [`super::prove::prove_single`].
+pub fn verify_single<F, Composition, Challenger>(
+	claim: &SumcheckClaim<F, Composition>,
+	proof: Proof<F>,
+	challenger: Challenger,
+) -> Result<SumcheckOutput<F>, Error>
+where
+	F: Field,
+	Composition: CompositionPoly<F>,
+	Challenger: CanObserve<F> + CanSample<F>,
+{
+	batch_verify(std::slice::from_ref(claim), proof, challenger)?.into_single()
+}
+
+/// Verify a batched sumcheck protocol execution split into several batches.
+///
+/// This verifies proofs produced by [`super::prove::batch_prove_split`] with the same
+/// `max_batch_claims`. The claims are grouped in order into consecutive batches of
+/// `max_batch_claims` claims, the last one possibly smaller, and the batches are verified one
+/// after the other with the same challenger. The grouping is derived from the claims alone, and
+/// proofs of a different shape are rejected. Returns the output of each batch.
+///
+/// ## Throws
+///
+/// * [`Error::InvalidMaxBatchClaims`] if `max_batch_claims` is zero
+/// * [`VerificationError::NumberOfBatchProofs`] if there is not exactly one proof per batch
+/// * [`VerificationError::NumberOfFinalEvaluations`] if a proof does not have one entry of
+///   multilinear evaluations per claim of its batch
+pub fn batch_verify_split<F, Composition, Challenger>(
+	claims: &[SumcheckClaim<F, Composition>],
+	proofs: Vec<Proof<F>>,
+	max_batch_claims: Option<usize>,
+	mut challenger: Challenger,
+) -> Result<Vec<BatchSumcheckOutput<F>>, Error>
+where
+	F: Field,
+	Composition: CompositionPoly<F>,
+	Challenger: CanObserve<F> + CanSample<F>,
+{
+	let max_batch_claims = match max_batch_claims {
+		Some(0) => bail!(Error::InvalidMaxBatchClaims),
+		Some(max_batch_claims) => max_batch_claims,
+		None => claims.len().max(1),
+	};
+
+	let n_batches = claims.len().div_ceil(max_batch_claims);
+	if proofs.len() != n_batches {
+		bail!(VerificationError::NumberOfBatchProofs {
+			expected: n_batches,
+		});
+	}
+
+	iter::zip(claims.chunks(max_batch_claims), proofs)
+		.map(|(batch_claims, proof)| {
+			if proof.multilinear_evals.len() != batch_claims.len() {
+				bail!(VerificationError::NumberOfFinalEvaluations);
+			}
+			batch_verify(batch_claims, proof, &mut challenger)
+		})
+		.collect()
+}
+
+/// Verify a batched sumcheck protocol execution with the claims in any order.
+///
+/// This verifies proofs produced by [`super::prove::batch_prove_unsorted`]. The claims are sorted
+/// in descending order by number of variables before verifying, and the multilinear evaluations of
+/// the output are returned in the original order of the claims.
+pub fn batch_verify_unsorted<F, Composition, Challenger>(
+	claims: &[SumcheckClaim<F, Composition>],
+	proof: Proof<F>,
+	challenger: Challenger,
+) -> Result<BatchSumcheckOutput<F>, Error>
+where
+	F: Field,
+	Composition: CompositionPoly<F> + Clone,
+	Challenger: CanObserve<F> + CanSample<F>,
+{
+	let permutation = sort_permutation_by_n_vars(claims.iter().map(|claim| claim.n_vars()));
+	let sorted_claims = permutation
+		.iter()
+		.map(|&index| claims[index].clone())
+		.collect::<Vec<_>>();
+	let output = batch_verify(&sorted_claims, proof, challenger)?;
+	Ok(output.unsort(&permutation))
+}
+
+/// Verify a batched sumcheck protocol execution by provers that fold in the given direction.
+///
+/// The round checks are those of [`batch_verify`], which verifies provers folding low-to-high.
+/// The output challenges are ordered by the variables they bind, see [`FoldDirection`], and the
+/// transparent multilinears of the claims are evaluated at the point ordered the same way.
+pub fn batch_verify_with_fold_direction<F, Composition, Challenger>(
+	claims: &[SumcheckClaim<F, Composition>],
+	proof: Proof<F>,
+	fold_direction: FoldDirection,
+	challenger: Challenger,
+) -> Result<BatchSumcheckOutput<F>, Error>
+where
+	F: Field,
+	Composition: CompositionPoly<F>,
+	Challenger: CanObserve<F> + CanSample<F>,
+{
+	let output = batch_verify_reporting(
+		claims,
+		proof,
+		fold_direction,
+		challenger,
+		|error, _| Err(error.into()),
+		|_| {},
+	)?;
+	Ok(output.with_fold_direction(fold_direction))
+}
 
 /// Verify a batched sumcheck protocol execution.
 ///
@@ -27,10 +153,195 @@ use itertools::izip;
 /// For each sumcheck claim, we sample one random mixing coefficient. The multiple composite claims
 /// within each claim over a group of multilinears are mixed using the powers of the mixing
 /// coefficient.
+///
+/// The round polynomial in each round has the maximum degree over the composites of the claims
+/// batched in so far, as recorded in [`SumcheckClaim::composite_degrees`]. Rounds before a
+/// higher degree claim is batched in are not sized for it. With
+/// [`ProtocolFeatures::TRIMMED_ROUND_PROOFS`], this degree is only an upper bound, and the verifier
+/// accepts shorter round proofs in the canonical form of [`RoundCoeffs::trim`].
+///
+/// The proof header records the [`ProtocolFeatures`] the prover used, which select the format of
+/// the round proofs. Proofs using features unknown to this verifier are rejected.
+///
+/// If any of the claims carry a [`super::ClaimLabel`], the labels are observed by the challenger
+/// before the first round, and any labels disclosed in the proof must match those of the claims.
+///
+/// The proof contains no evaluations for the transparent multilinears of the claims, see
+/// [`SumcheckClaim::with_transparent`]. The verifier evaluates them at the challenge point of
+/// provers folding low-to-high, and the output contains the evaluations of all multilinears.
+#[instrument(skip_all, name = "sumcheck_v2::batch_verify", level = "debug")]
 pub fn batch_verify<F, Composition, Challenger>(
 	claims: &[SumcheckClaim<F, Composition>],
 	proof: Proof<F>,
+	challenger: Challenger,
+) -> Result<BatchSumcheckOutput<F>, Error>
+where
+	F: Field,
+	Composition: CompositionPoly<F>,
+	Challenger: CanObserve<F> + CanSample<F>,
+{
+	batch_verify_reporting(
+		claims,
+		proof,
+		FoldDirection::LowToHigh,
+		challenger,
+		|error, _| Err(error.into()),
+		|_| {},
+	)
+}
+
+/// The state of the verifier after a round of [`batch_verify_with_observer`].
+#[derive(Debug)]
+pub struct VerifierRoundState<'a, F: Field> {
+	/// The index of the round.
+	pub round: usize,
+	/// The number of claims batched in so far, which are the first claims of the batch.
+	pub n_active_claims: usize,
+	/// The batching coefficients of the active claims.
+	pub batch_coeffs: &'a [F],
+	/// The batch-weighted sum of the composite sums of each active claim, which is the term it
+	/// contributed to the batched sum when it was batched in.
+	pub weighted_sums: &'a [F],
+	/// The round polynomial, recovered from the round proof.
+	pub round_coeffs: &'a RoundCoeffs<F>,
+	/// The challenges sampled so far, including the challenge of this round.
+	pub challenges: &'a [F],
+	/// The reduced batched sum, which is the round polynomial evaluated at the challenge and the
+	/// claimed sum of the next round.
+	pub sum: F,
+}
+
+/// Verify a batched sumcheck protocol execution, passing the verifier state after each round to
+/// `observer`.
+///
+/// This is [`batch_verify`] for debugging tools. Comparing the states with the values computed by
+/// an honest prover shows the round and the claims at which a proof diverges. The observer is
+/// called for every round that completes, so a proof failing a check in some round is observed
+/// up to the round before.
+pub fn batch_verify_with_observer<F, Composition, Challenger>(
+	claims: &[SumcheckClaim<F, Composition>],
+	proof: Proof<F>,
+	challenger: Challenger,
+	observer: impl FnMut(&VerifierRoundState<F>),
+) -> Result<BatchSumcheckOutput<F>, Error>
+where
+	F: Field,
+	Composition: CompositionPoly<F>,
+	Challenger: CanObserve<F> + CanSample<F>,
+{
+	batch_verify_reporting(
+		claims,
+		proof,
+		FoldDirection::LowToHigh,
+		challenger,
+		|error, _| Err(error.into()),
+		observer,
+	)
+}
+
+/// The verdict on one claim of a batch, see [`batch_verify_with_report`].
+#[derive(Debug)]
+pub enum ClaimVerdict {
+	/// The batch verified.
+	Passed,
+	/// Verification of the batch failed on a check of this claim.
+	Failed(VerificationError),
+	/// Verification of the batch failed, but not on a check that can be attributed to this claim.
+	Inconclusive,
+}
+
+/// The results of verifying a batched sumcheck proof claim by claim, see
+/// [`batch_verify_with_report`].
+#[derive(Debug)]
+pub struct VerificationReport<F: Field> {
+	/// The verdict on each claim, in the order of the claims.
+	pub claims: Vec<ClaimVerdict>,
+	/// The result of verifying the batch, which is what [`batch_verify`] returns.
+	pub result: Result<BatchSumcheckOutput<F>, Error>,
+}
+
+/// Verify a batched sumcheck protocol execution, reporting which claims fail verification.
+///
+/// This is a debugging aid for batched proofs. Where [`batch_verify`] stops at the first failed
+/// check, this continues past checks that can be attributed to individual claims and records a
+/// verdict for every claim. A batch is verified as a random linear combination of its claims, so
+/// only some checks can be attributed:
+///
+/// * the number of multilinear evaluations of each claim;
+/// * the sum of the round polynomial in each round that batches in new claims, which is
+///   attributed to those claims.
+///
+/// The final batched evaluation check cannot be attributed to any claim. Compressed round proofs
+/// omit the coefficient that the round sum determines, so they have no round sum checks to
+/// attribute, and only proofs produced with [`ProtocolFeatures::UNCOMPRESSED_ROUND_PROOFS`] are
+/// accepted.
+///
+/// ## Throws
+///
+/// * [`Error::ReportRequiresUncompressedRoundProofs`] if the proof has compressed round proofs
+pub fn batch_verify_with_report<F, Composition, Challenger>(
+	claims: &[SumcheckClaim<F, Composition>],
+	proof: Proof<F>,
+	challenger: Challenger,
+) -> Result<VerificationReport<F>, Error>
+where
+	F: Field,
+	Composition: CompositionPoly<F>,
+	Challenger: CanObserve<F> + CanSample<F>,
+{
+	if !proof
+		.features
+		.contains(ProtocolFeatures::UNCOMPRESSED_ROUND_PROOFS)
+	{
+		bail!(Error::ReportRequiresUncompressedRoundProofs);
+	}
+
+	let mut failures = vec![None; claims.len()];
+	let mut first_error = None;
+	let result = batch_verify_reporting(
+		claims,
+		proof,
+		FoldDirection::LowToHigh,
+		challenger,
+		|error, claim_indices| {
+			for claim_index in claim_indices {
+				failures[claim_index].get_or_insert_with(|| error.clone());
+			}
+			first_error.get_or_insert(error);
+			Ok(())
+		},
+		|_| {},
+	);
+	let result = match first_error {
+		Some(error) => Err(error.into()),
+		None => result,
+	};
+
+	let claims = failures
+		.into_iter()
+		.map(|failure| match (failure, &result) {
+			(Some(error), _) => ClaimVerdict::Failed(error),
+			(None, Ok(_)) => ClaimVerdict::Passed,
+			(None, Err(_)) => ClaimVerdict::Inconclusive,
+		})
+		.collect();
+	Ok(VerificationReport { claims, result })
+}
+
+/// Verifies a batched sumcheck protocol execution, passing failed checks that can be attributed to
+/// claims to `on_failure` along with the indices of the claims.
+///
+/// Verification stops with the error returned by `on_failure`, or continues if it returns `Ok`.
+/// The state after each round is passed to `on_round`. The fold direction of the provers
+/// determines the point at which the transparent multilinears of the claims are evaluated, and
+/// the challenges of the output are in round order regardless.
+fn batch_verify_reporting<F, Composition, Challenger>(
+	claims: &[SumcheckClaim<F, Composition>],
+	proof: Proof<F>,
+	fold_direction: FoldDirection,
 	mut challenger: Challenger,
+	mut on_failure: impl FnMut(VerificationError, Range<usize>) -> Result<(), Error>,
+	mut on_round: impl FnMut(&VerifierRoundState<F>),
 ) -> Result<BatchSumcheckOutput<F>, Error>
 where
 	F: Field,
@@ -38,15 +349,33 @@ where
 	Challenger: CanObserve<F> + CanSample<F>,
 {
 	let Proof {
+		features,
 		rounds: round_proofs,
 		multilinear_evals,
+		claim_labels: disclosed_labels,
 	} = proof;
 
+	let n_proof_elems = round_proofs
+		.iter()
+		.map(|round_proof| round_proof.coeffs().len())
+		.chain(multilinear_evals.iter().map(Vec::len))
+		.sum::<usize>();
+	record_proof_bytes(n_proof_elems * mem::size_of::<F>());
+
 	// Check that the claims are in descending order by n_vars
 	if !is_sorted_ascending(claims.iter().map(|claim| claim.n_vars()).rev()) {
 		bail!(Error::ClaimsOutOfOrder);
 	}
 
+	let labels = claim_labels(claims);
+	check_disclosed_claim_labels(&labels, disclosed_labels.as_deref())?;
+	observe_claim_labels::<F, _>(&labels, &mut challenger)?;
+
+	check_protocol_features(features)?;
+	observe_protocol_features::<F, _>(features, &mut challenger);
+	let compressed = !features.contains(ProtocolFeatures::UNCOMPRESSED_ROUND_PROOFS);
+	let trimmed = features.contains(ProtocolFeatures::TRIMMED_ROUND_PROOFS);
+
 	let n_rounds = claims.iter().map(|claim| claim.n_vars()).max().unwrap_or(0);
 	if round_proofs.len() != n_rounds {
 		bail!(VerificationError::NumberOfRounds);
@@ -56,12 +385,15 @@ where
 	// been batched into the instance and claims after the index have not.
 	let mut active_index = 0;
 	let mut batch_coeffs = Vec::with_capacity(claims.len());
+	let mut weighted_sums = Vec::with_capacity(claims.len());
 	let mut challenges = Vec::with_capacity(n_rounds);
 	let mut sum = F::ZERO;
 	let mut max_degree = 0; // Maximum individual degree of the active claims
 	for (round_no, round_proof) in round_proofs.into_iter().enumerate() {
 		let n_vars = n_rounds - round_no;
+		let _span = debug_span!("sumcheck_v2::round", round = round_no).entered();
 
+		let first_new_index = active_index;
 		while let Some(claim) = claims.get(active_index) {
 			if claim.n_vars() != n_vars {
 				break;
@@ -71,29 +403,65 @@ where
 			batch_coeffs.push(next_batch_coeff);
 
 			// Batch the next claimed sum into the batched sum.
-			sum += batch_weighted_value(
+			let weighted_sum = batch_weighted_value(
 				next_batch_coeff,
 				claim
 					.composite_sums()
 					.iter()
 					.map(|inner_claim| inner_claim.sum),
 			);
+			weighted_sums.push(weighted_sum);
+			sum += weighted_sum;
 			max_degree = max_degree.max(claim.max_individual_degree());
 			active_index += 1;
 		}
 
-		if round_proof.coeffs().len() != max_degree {
+		let n_coeffs = features.round_proof_n_coeffs(max_degree);
+		if !n_coeffs.contains(&round_proof.coeffs().len()) {
 			bail!(VerificationError::NumberOfCoefficients {
 				round: round_no,
-				expected: max_degree,
+				expected: *n_coeffs.end(),
 			});
 		}
 
+		observe_round_proof_len::<F, _>(features, round_proof.coeffs().len(), &mut challenger);
 		challenger.observe_slice(round_proof.coeffs());
 		let challenge = challenger.sample();
 		challenges.push(challenge);
 
-		sum = interpolate_round_proof(round_proof, sum, challenge);
+		let coeffs = if compressed {
+			round_proof.recover(sum)
+		} else {
+			match round_proof.clone().check_uncompressed(sum) {
+				Some(coeffs) => coeffs,
+				None => {
+					let claim_indices = first_new_index..active_index;
+					on_failure(
+						VerificationError::RoundSumMismatch {
+							round: round_no,
+							claim_indices: claim_indices.clone(),
+						},
+						claim_indices,
+					)?;
+					// Continue with the round polynomial as sent.
+					RoundCoeffs(round_proof.coeffs().to_vec())
+				}
+			}
+		};
+		if trimmed && !coeffs.is_trimmed() {
+			bail!(VerificationError::NonCanonicalRoundProof { round: round_no });
+		}
+		sum = evaluate_univariate(&coeffs.0, challenge);
+
+		on_round(&VerifierRoundState {
+			round: round_no,
+			n_active_claims: active_index,
+			batch_coeffs: &batch_coeffs,
+			weighted_sums: &weighted_sums,
+			round_coeffs: &coeffs,
+			challenges: &challenges,
+			sum,
+		});
 	}
 
 	// Batch in any claims for 0-variate (ie. constant) polynomials.
@@ -117,12 +485,32 @@ where
 	if multilinear_evals.len() != claims.len() {
 		bail!(VerificationError::NumberOfFinalEvaluations);
 	}
-	for (claim, multilinear_evals) in claims.iter().zip(multilinear_evals.iter()) {
-		if claim.n_multilinears() != multilinear_evals.len() {
-			bail!(VerificationError::NumberOfFinalEvaluations);
+	let mut evals_mismatch = false;
+	let mut all_multilinear_evals = Vec::with_capacity(claims.len());
+	for (claim_index, (claim, multilinear_evals)) in
+		claims.iter().zip(multilinear_evals).enumerate()
+	{
+		// The evaluations of transparent multilinears are computed by the verifier.
+		if claim.n_committed_multilinears() != multilinear_evals.len() {
+			on_failure(
+				VerificationError::NumberOfClaimEvaluations {
+					claim_index,
+					expected: claim.n_committed_multilinears(),
+				},
+				claim_index..claim_index + 1,
+			)?;
+			evals_mismatch = true;
+			continue;
 		}
-		challenger.observe_slice(multilinear_evals);
+		let point = transparent_eval_point(&challenges, claim.n_vars(), fold_direction);
+		let multilinear_evals = claim.insert_transparent_evals(multilinear_evals, &point)?;
+		challenger.observe_slice(&multilinear_evals);
+		all_multilinear_evals.push(multilinear_evals);
 	}
+	if evals_mismatch {
+		bail!(VerificationError::NumberOfFinalEvaluations);
+	}
+	let multilinear_evals = all_multilinear_evals;
 
 	let expected_sum =
 		compute_expected_batch_composite_evaluation(batch_coeffs, claims, &multilinear_evals)?;
@@ -136,7 +524,66 @@ where
 	})
 }
 
-fn compute_expected_batch_composite_evaluation<F: Field, Composition>(
+/// Verify a single round polynomial sent in evaluation form.
+///
+/// The round polynomial is given by its evaluations over `domain`, whose first two points must be
+/// 0 and 1, as in the domains created by
+/// [`crate::polynomial::IsomorphicEvaluationDomainFactory`]. The verifier checks the evaluations at
+/// 0 and 1 against the claimed sum of the round directly and returns the evaluation at the round
+/// challenge, which is the claimed sum of the next round. The evaluation is computed with
+/// barycentric extrapolation, so that the coefficients of the polynomial are never interpolated.
+///
+/// ## Throws
+///
+/// * [`Error::InvalidRoundEvalsDomain`] if the domain does not start with the points 0 and 1
+pub fn verify_round_evals<F, FDomain>(
+	round: usize,
+	round_evals: &RoundEvals<F>,
+	domain: &EvaluationDomain<FDomain>,
+	sum: F,
+	challenge: F,
+) -> Result<F, Error>
+where
+	FDomain: Field,
+	F: ExtensionField<FDomain> + PackedExtension<FDomain, Scalar = F>,
+{
+	if !domain.points().starts_with(&[FDomain::ZERO, FDomain::ONE]) {
+		bail!(Error::InvalidRoundEvalsDomain);
+	}
+
+	let evals = &round_evals.0;
+	if evals.len() != domain.size() || evals.len() < 2 {
+		bail!(VerificationError::NumberOfCoefficients {
+			round,
+			expected: domain.size(),
+		});
+	}
+	if evals[0] + evals[1] != sum {
+		// A single round polynomial is not associated with the claims of a batch.
+		bail!(VerificationError::RoundSumMismatch {
+			round,
+			claim_indices: 0..0,
+		});
+	}
+	round_evals.evaluate(domain, challenge)
+}
+
+/// Returns the evaluation point, ordered by variable, of a claim with `n_vars` variables given the
+/// challenges of a batch in round order.
+pub(crate) fn transparent_eval_point<F: Field>(
+	challenges: &[F],
+	n_vars: usize,
+	fold_direction: FoldDirection,
+) -> Vec<F> {
+	// Claims with fewer variables are batched into the later rounds.
+	let point = &challenges[challenges.len() - n_vars..];
+	match fold_direction {
+		FoldDirection::LowToHigh => point.to_vec(),
+		FoldDirection::HighToLow => point.iter().rev().copied().collect(),
+	}
+}
+
+pub(crate) fn compute_expected_batch_composite_evaluation<F: Field, Composition>(
 	batch_coeffs: Vec<F>,
 	claims: &[SumcheckClaim<F, Composition>],
 	multilinear_evals: &[Vec<F>],
@@ -144,7 +591,10 @@ fn compute_expected_batch_composite_evaluation<F: Field, Composition>(
 where
 	Composition: CompositionPoly<F>,
 {
-	izip!(batch_coeffs, claims, multilinear_evals.iter())
+	// The composite evaluations of different claims are independent, so they are computed in
+	// parallel across claims. This dominates the verifier cost of batches with many claims.
+	let terms = (batch_coeffs, claims, multilinear_evals)
+		.into_par_iter()
 		.map(|(batch_coeff, claim, multilinear_evals)| {
 			let composite_evals = claim
 				.composite_sums()
@@ -153,15 +603,11 @@ where
 				.collect::<Result<Vec<_>, _>>()?;
 			Ok::<_, Error>(batch_weighted_value(batch_coeff, composite_evals.into_iter()))
 		})
-		.try_fold(F::ZERO, |sum, term| Ok(sum + term?))
+		.collect::<Result<Vec<_>, _>>()?;
+	Ok(terms.into_iter().sum())
 }
 
-fn batch_weighted_value<F: Field>(batch_coeff: F, values: impl Iterator<Item = F>) -> F {
+pub(crate) fn batch_weighted_value<F: Field>(batch_coeff: F, values: impl Iterator<Item = F>) -> F {
 	// Multiplying by batch_coeff is important for security!
 	batch_coeff * inner_product_unchecked(powers(batch_coeff), values)
 }
-
-fn interpolate_round_proof<F: Field>(round_proof: RoundProof<F>, sum: F, challenge: F) -> F {
-	let coeffs = round_proof.recover(sum);
-	evaluate_univariate(&coeffs.0, challenge)
-}
@@ -0,0 +1,125 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Sumcheck claims on the sum of a multilinear over a window of hypercube rows.
+//!
+//! A claim that the values of a multilinear $C$ at the rows $[a, b)$ of the hypercube sum to $s$
+//! is a sumcheck claim on $C$ multiplied by the indicator of the window. The indicator is
+//! $D_b + U_a + 1$, where $D_b$ is the [`StepDown`] selector of the rows below $b$ and $U_a$ the
+//! [`StepUp`] selector of the rows from $a$ onwards: outside the window exactly one of the two
+//! selectors is one, and inside the window both are, so in characteristic 2 the sum is one exactly
+//! inside the window. As both selectors are transparent, the evaluation claims on them are
+//! checked directly by the verifier.
+//!
+//! [`windowed_sum_claim`] adds the selectors to the oracle set and builds the claim, and the
+//! prover builds its sumcheck prover with [`super::prove::windowed_sum_prover`].
+
+use super::{
+	common::{CompositeSumClaim, SumcheckClaim},
+	error::Error,
+	oracles::OracleClaimMeta,
+};
+use crate::{
+	oracle::{Error as OracleError, MultilinearOracleSet, OracleId},
+	polynomial::{
+		transparent::{step_down::StepDown, step_up::StepUp},
+		CompositionPoly, Error as PolynomialError,
+	},
+};
+use binius_field::{PackedField, TowerField};
+use binius_utils::bail;
+use getset::Getters;
+use std::ops::Range;
+
+/// The composition $C \cdot (D + U + 1)$ of a windowed sum claim.
+///
+/// The query is the summed multilinear $C$, followed by the step down selector $D$ at the end of
+/// the window and the step up selector $U$ at its start.
+#[derive(Debug, Clone, Copy)]
+pub struct WindowedSumComposition;
+
+impl<P: PackedField> CompositionPoly<P> for WindowedSumComposition {
+	fn n_vars(&self) -> usize {
+		3
+	}
+
+	fn degree(&self) -> usize {
+		2
+	}
+
+	fn evaluate(&self, query: &[P]) -> Result<P, PolynomialError> {
+		if query.len() != 3 {
+			bail!(PolynomialError::IncorrectQuerySize { expected: 3 });
+		}
+		Ok(query[0] * (query[1] + query[2] + P::one()))
+	}
+
+	fn binary_tower_level(&self) -> usize {
+		0
+	}
+}
+
+/// A claim that the values of a multilinear oracle over a window of rows sum to a given value.
+#[derive(Debug, Clone, Getters)]
+#[getset(get = "pub")]
+pub struct WindowedSumClaim<F: TowerField> {
+	/// The sumcheck claim over the oracles of [`Self::meta`].
+	sumcheck_claim: SumcheckClaim<F, WindowedSumComposition>,
+	/// The summed oracle, then the step down and step up selectors of the window.
+	meta: OracleClaimMeta,
+	/// The rows of the window.
+	window: Range<usize>,
+}
+
+impl<F: TowerField> WindowedSumClaim<F> {
+	/// The claimed sum over the window.
+	pub fn sum(&self) -> F {
+		self.sumcheck_claim.composite_sums()[0].sum
+	}
+}
+
+/// Claims that the values of the oracle `oracle_id` at the rows `window` sum to `sum`.
+///
+/// Adds the step down and step up selectors of the window to `oracles` as transparent oracles.
+/// The prover and verifier must both call this with the same window, so that their oracle sets
+/// agree. The sumcheck claim can be batched with other claims, and the output of the verified
+/// batch is turned into evalcheck claims with [`super::make_eval_claims`] and
+/// [`WindowedSumClaim::meta`].
+///
+/// ## Throws
+///
+/// * [`OracleError::InvalidOracleId`] if the oracle is not in `oracles`
+/// * [`Error::InvalidWindow`] if the window is decreasing or ends past the last row of the oracle
+pub fn windowed_sum_claim<F: TowerField>(
+	oracles: &mut MultilinearOracleSet<F>,
+	oracle_id: OracleId,
+	window: Range<usize>,
+	sum: F,
+) -> Result<WindowedSumClaim<F>, Error> {
+	if oracle_id >= oracles.size() {
+		bail!(OracleError::InvalidOracleId(oracle_id));
+	}
+	let n_vars = oracles.n_vars(oracle_id);
+	if window.start > window.end || window.end > 1 << n_vars {
+		bail!(Error::InvalidWindow { window, n_vars });
+	}
+
+	let step_down_id = oracles.add_transparent(StepDown::new(n_vars, window.end)?)?;
+	let step_up_id = oracles.add_transparent(StepUp::new(n_vars, window.start)?)?;
+
+	let sumcheck_claim = SumcheckClaim::new(
+		n_vars,
+		3,
+		vec![CompositeSumClaim {
+			composition: WindowedSumComposition,
+			sum,
+		}],
+	)?;
+	Ok(WindowedSumClaim {
+		sumcheck_claim,
+		meta: OracleClaimMeta {
+			n_vars,
+			oracle_ids: vec![oracle_id, step_down_id, step_up_id],
+		},
+		window,
+	})
+}
@@ -146,6 +146,13 @@ pub struct ExtraProduct<Composition> {
 	inner: Composition,
 }
 
+impl<Composition> ExtraProduct<Composition> {
+	/// Wraps a composition, multiplying it by an extra multilinear appended after its inputs.
+	pub fn new(inner: Composition) -> Self {
+		Self { inner }
+	}
+}
+
 impl<P, Composition> CompositionPoly<P> for ExtraProduct<Composition>
 where
 	P: PackedField,
@@ -187,7 +194,10 @@ mod tests {
 		protocols::{
 			sumcheck_v2::{
 				batch_verify,
-				prove::{batch_prove, zerocheck, RegularSumcheckProver, ZerocheckProver},
+				prove::{
+					batch_prove, batch_prove_with_memory_budget, zerocheck, EqIndStorage,
+					MemoryBudget, RegularSumcheckProver, ZerocheckProver,
+				},
 			},
 			test_utils::TestProductComposition,
 		},
@@ -308,10 +318,10 @@ mod tests {
 		) = batch_prove(vec![reference_prover], &mut challenger1).unwrap();
 
 		let optimized_prover = ZerocheckProver::<FDomain, _, _, _>::new(
-			multilins,
+			multilins.clone(),
 			[TestProductComposition::new(n_multilinears)],
 			&challenges,
-			domain_factory,
+			domain_factory.clone(),
 			|_| switchover_rd,
 		)
 		.unwrap();
@@ -328,6 +338,39 @@ mod tests {
 		assert_eq!(proof1, proof2);
 		assert_eq!(multilinear_evals_1, multilinear_evals_2);
 		assert_eq!(sumcheck_challenges_1, sumcheck_challenges_2);
+
+		// Recomputing the equality indicator produces the same proof.
+		let recomputing_prover = ZerocheckProver::<FDomain, _, _, _>::new_with_eq_ind_storage(
+			multilins.clone(),
+			[TestProductComposition::new(n_multilinears)],
+			&challenges,
+			domain_factory.clone(),
+			|_| switchover_rd,
+			EqIndStorage::Recomputed,
+		)
+		.unwrap();
+		let (output3, proof3) =
+			batch_prove(vec![recomputing_prover], &mut challenger.clone()).unwrap();
+		assert_eq!(proof1, proof3);
+		assert_eq!(multilinear_evals_1, output3.multilinear_evals);
+
+		// A memory budget too small for the expansion switches the prover to recomputing it.
+		let budgeted_prover = ZerocheckProver::<FDomain, _, _, _>::new(
+			multilins,
+			[TestProductComposition::new(n_multilinears)],
+			&challenges,
+			domain_factory,
+			|_| switchover_rd,
+		)
+		.unwrap();
+		let (output4, proof4, _) = batch_prove_with_memory_budget(
+			vec![budgeted_prover],
+			MemoryBudget::new(0),
+			&mut challenger.clone(),
+		)
+		.unwrap();
+		assert_eq!(proof1, proof4);
+		assert_eq!(multilinear_evals_1, output4.multilinear_evals);
 	}
 
 	fn test_prove_verify_product_constraint_helper(
@@ -425,6 +468,17 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn test_eq_ind_storage_with_memory_limit() {
+		type FE = BinaryField128b;
+		// The expansion over 9 of 10 variables takes 8 KiB.
+		assert_eq!(EqIndStorage::with_memory_limit::<FE>(10, 1 << 13), EqIndStorage::Expanded);
+		assert_eq!(
+			EqIndStorage::with_memory_limit::<FE>(10, (1 << 13) - 1),
+			EqIndStorage::Recomputed
+		);
+	}
+
 	#[test]
 	fn test_prove_verify_product_basic() {
 		for n_vars in 2..8 {
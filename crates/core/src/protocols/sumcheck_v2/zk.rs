@@ -0,0 +1,593 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Zero-knowledge sumcheck with a random masking polynomial.
+//!
+//! In the plain sumcheck protocol, the round polynomials are partial sums of the witness, so they
+//! leak information about it. In the masked mode, the prover first commits to a random
+//! [`MaskPolynomial`] $g$ and sends its sum $G$ over the hypercube. The verifier samples a mixing
+//! coefficient $\rho$ and the prover runs the sumcheck for $C + \rho g$ with the claimed sum
+//! $s + \rho G$, where $C$ is the first composite of the claim. Each round polynomial of the
+//! masked sum contains a uniformly random univariate of the mask, so the round polynomials reveal
+//! nothing about the witness.
+//!
+//! The mask is committed with a multilinear polynomial commitment scheme, see
+//! [`MaskPolynomial::coeffs_multilinear`], and the commitment is observed before the mixing
+//! coefficient is sampled. After the sumcheck, the prover opens the univariate $g_i$ at the
+//! challenge $r_i$ for each variable, and the verifier checks that the openings combine to the
+//! evaluation of the mask at the challenge point. The protocol reduces to the multilinear
+//! evaluations of the claim, which are not masked and must be checked by the caller as usual.
+//!
+//! The prover is [`super::prove::ZkSumcheckProver`], driven by [`super::prove::prove_zk`].
+
+use super::{
+	error::{Error, VerificationError},
+	verify::verify_single,
+	CompositeSumClaim, Proof, SumcheckClaim,
+};
+use crate::{
+	challenger::{CanObserve, CanSample, CanSampleBits},
+	poly_commit::PolyCommitScheme,
+	polynomial::{
+		evaluate_univariate, CompositionPoly, Error as PolynomialError, MultilinearExtension,
+	},
+};
+use binius_field::{Field, PackedField};
+use binius_utils::bail;
+use getset::Getters;
+use p3_util::log2_ceil_usize;
+use rand::RngCore;
+use std::iter::{self, repeat_with};
+
+/// A masking polynomial for the zero-knowledge sumcheck.
+///
+/// The mask is the $n$-variate polynomial
+///
+/// $$
+/// g(X_0, \ldots, X_{n-1}) = \sum_{i=0}^{n-1} g_i(X_i) \prod_{k \ne i} X_k,
+/// $$
+///
+/// where the $g_i$ are univariate polynomials of degree $d$. The products over the other
+/// variables keep the contribution of every $g_i$ to the round polynomials from vanishing, which
+/// a plain sum of univariates would do over fields of characteristic 2. The sum of the mask over
+/// the hypercube is $\sum_i g_i(0) + g_i(1)$.
+///
+/// The variables are in the order they are bound by the sumcheck rounds.
+#[derive(Debug, Clone, PartialEq, Eq, Getters)]
+pub struct MaskPolynomial<F: Field> {
+	/// The coefficients of the univariates $g_i$ in monomial basis.
+	#[getset(get = "pub")]
+	univariates: Vec<Vec<F>>,
+}
+
+impl<F: Field> MaskPolynomial<F> {
+	/// Constructs a mask from the coefficients of its univariates in monomial basis.
+	///
+	/// ## Throws
+	///
+	/// * [`Error::InvalidMaskPolynomial`] if the univariates do not all have the same number of
+	///   coefficients
+	pub fn new(univariates: Vec<Vec<F>>) -> Result<Self, Error> {
+		if let Some(first) = univariates.first() {
+			if univariates.iter().any(|coeffs| coeffs.len() != first.len()) {
+				bail!(Error::InvalidMaskPolynomial);
+			}
+		}
+		Ok(Self { univariates })
+	}
+
+	/// Samples a random mask over `n_vars` variables with univariates of degree `degree`.
+	pub fn random(n_vars: usize, degree: usize, mut rng: impl RngCore) -> Self {
+		let univariates = repeat_with(|| {
+			repeat_with(|| F::random(&mut rng))
+				.take(degree + 1)
+				.collect()
+		})
+		.take(n_vars)
+		.collect();
+		Self { univariates }
+	}
+
+	pub fn n_vars(&self) -> usize {
+		self.univariates.len()
+	}
+
+	/// The degree of the univariates, which is the individual degree of the mask.
+	pub fn degree(&self) -> usize {
+		self.univariates
+			.first()
+			.map_or(0, |coeffs| coeffs.len().saturating_sub(1))
+	}
+
+	/// The sum of the mask over the boolean hypercube.
+	pub fn sum(&self) -> F {
+		self.univariates
+			.iter()
+			.map(|coeffs| {
+				evaluate_univariate(coeffs, F::ZERO) + evaluate_univariate(coeffs, F::ONE)
+			})
+			.sum()
+	}
+
+	/// Evaluates the mask at a point.
+	pub fn evaluate(&self, point: &[F]) -> Result<F, Error> {
+		Ok(Self::combine_univariate_evals(&self.univariate_evals(point)?, point))
+	}
+
+	/// Evaluates each univariate $g_i$ at the coordinate $i$ of a point.
+	pub fn univariate_evals(&self, point: &[F]) -> Result<Vec<F>, Error> {
+		if point.len() != self.n_vars() {
+			bail!(PolynomialError::IncorrectQuerySize {
+				expected: self.n_vars(),
+			});
+		}
+
+		Ok(iter::zip(&self.univariates, point)
+			.map(|(coeffs, &x)| evaluate_univariate(coeffs, x))
+			.collect())
+	}
+
+	/// Combines the evaluations $g_i(x_i)$ of the univariates into the evaluation of the mask at
+	/// the point $x$.
+	pub fn combine_univariate_evals(univariate_evals: &[F], point: &[F]) -> F {
+		// Accumulate the evaluations of the mask restricted to the first variables, along with
+		// the products of the coordinates.
+		let (eval, _) = iter::zip(univariate_evals, point)
+			.fold((F::ZERO, F::ONE), |(eval, prod), (&univariate_eval, &x)| {
+				(eval * x + univariate_eval * prod, prod * x)
+			});
+		eval
+	}
+
+	/// The multilinear over `n_vars` variables that commits to the mask.
+	///
+	/// The hypercube evaluations of the multilinear are the coefficients of the univariates, with
+	/// the coefficient $j$ of $g_i$ at index $j + 2^k i$, where $k$ is the number of variables left
+	/// after indexing the univariates. The multilinear evaluates to $g_i(x)$ at the point returned
+	/// by [`mask_opening_point`].
+	///
+	/// ## Throws
+	///
+	/// * [`Error::MaskCommitmentTooSmall`] if the coefficients do not fit into `n_vars` variables
+	pub fn coeffs_multilinear<P: PackedField<Scalar = F>>(
+		&self,
+		n_vars: usize,
+	) -> Result<MultilinearExtension<P>, Error> {
+		let log_coeffs = mask_log_coeffs(self.n_vars(), self.degree(), n_vars)?;
+
+		let mut values = vec![F::ZERO; 1 << n_vars];
+		for (i, coeffs) in self.univariates.iter().enumerate() {
+			values[i << log_coeffs..][..coeffs.len()].copy_from_slice(coeffs);
+		}
+		let packed = values
+			.chunks(P::WIDTH)
+			.map(|chunk| P::from_scalars(chunk.iter().copied()))
+			.collect();
+		Ok(MultilinearExtension::from_values(packed)?)
+	}
+}
+
+/// The number of variables indexing the coefficients of a univariate in the multilinear of
+/// [`MaskPolynomial::coeffs_multilinear`].
+fn mask_log_coeffs(n_univariates: usize, degree: usize, n_vars: usize) -> Result<usize, Error> {
+	let log_univariates = log2_ceil_usize(n_univariates);
+	if log_univariates > n_vars || degree >= 1 << (n_vars - log_univariates) {
+		bail!(Error::MaskCommitmentTooSmall { n_vars });
+	}
+	Ok(n_vars - log_univariates)
+}
+
+/// The point at which the multilinear of [`MaskPolynomial::coeffs_multilinear`] over `n_vars`
+/// variables evaluates to the univariate at `index` of a mask with `n_univariates` univariates of
+/// degree `degree`, evaluated at `x`.
+///
+/// The coefficient variables are set to the powers $x, x^2, x^4, \ldots$, whose tensor expansion
+/// is the vector of powers of $x$, and the remaining variables select the univariate.
+pub fn mask_opening_point<F: Field>(
+	n_univariates: usize,
+	degree: usize,
+	n_vars: usize,
+	index: usize,
+	x: F,
+) -> Result<Vec<F>, Error> {
+	let log_coeffs = mask_log_coeffs(n_univariates, degree, n_vars)?;
+	let powers = iter::successors(Some(x), |&power| Some(power.square())).take(log_coeffs);
+	let selector = (0..n_vars - log_coeffs).map(|bit| {
+		if (index >> bit) & 1 == 1 {
+			F::ONE
+		} else {
+			F::ZERO
+		}
+	});
+	Ok(powers.chain(selector).collect())
+}
+
+/// A composition with a scaled mask added to it.
+///
+/// The mask is appended as an extra input after the inputs of the inner composition. Without a
+/// mask coefficient the extra input is ignored, which is used for all composites of a masked
+/// claim but the first.
+#[derive(Debug)]
+pub struct MaskedComposition<F: Field, Composition> {
+	inner: Composition,
+	mask_coeff: Option<F>,
+}
+
+impl<F: Field, Composition> MaskedComposition<F, Composition> {
+	pub fn new(inner: Composition, mask_coeff: Option<F>) -> Self {
+		Self { inner, mask_coeff }
+	}
+}
+
+impl<F, P, Composition> CompositionPoly<P> for MaskedComposition<F, Composition>
+where
+	F: Field,
+	P: PackedField<Scalar = F>,
+	Composition: CompositionPoly<P>,
+{
+	fn n_vars(&self) -> usize {
+		self.inner.n_vars() + 1
+	}
+
+	fn degree(&self) -> usize {
+		match self.mask_coeff {
+			Some(_) => self.inner.degree().max(1),
+			None => self.inner.degree(),
+		}
+	}
+
+	fn evaluate(&self, query: &[P]) -> Result<P, PolynomialError> {
+		let n_vars = self.n_vars();
+		if query.len() != n_vars {
+			bail!(PolynomialError::IncorrectQuerySize { expected: n_vars });
+		}
+
+		let inner_eval = self.inner.evaluate(&query[..n_vars - 1])?;
+		Ok(match self.mask_coeff {
+			Some(mask_coeff) => inner_eval + query[n_vars - 1] * mask_coeff,
+			None => inner_eval,
+		})
+	}
+
+	fn binary_tower_level(&self) -> usize {
+		self.inner.binary_tower_level()
+	}
+}
+
+/// A zero-knowledge sumcheck proof.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZkProof<F: Field, Commitment, OpeningProof> {
+	/// The commitment to the mask.
+	pub mask_commitment: Commitment,
+	/// The sum of the mask over the hypercube.
+	pub mask_sum: F,
+	/// The sumcheck proof for the masked claim.
+	pub proof: Proof<F>,
+	/// The evaluation of each univariate $g_i$ of the mask at the challenge $r_i$.
+	pub univariate_evals: Vec<F>,
+	/// The opening proofs of the univariate evaluations against the mask commitment.
+	pub opening_proofs: Vec<OpeningProof>,
+}
+
+/// The output of a zero-knowledge sumcheck on a single claim.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ZkSumcheckOutput<F: Field> {
+	/// The evaluation point defined by the sumcheck verifier challenges.
+	pub challenges: Vec<F>,
+	/// The evaluations of the multilinears referenced by the claim at the challenge point.
+	pub multilinear_evals: Vec<F>,
+}
+
+/// The individual degree of the mask for a claim.
+///
+/// The mask must not raise the degree of the round polynomials, so its degree is the maximum
+/// individual degree of the claim, and at least one for claims of constant composites.
+pub fn mask_degree<F: Field, Composition: CompositionPoly<F>>(
+	claim: &SumcheckClaim<F, Composition>,
+) -> usize {
+	claim.max_individual_degree().max(1)
+}
+
+/// Reduces a sumcheck claim to the claim for the masked sum.
+///
+/// The mask is appended as the last input of the reduced claim and scaled by `mask_coeff`
+/// in the first composite.
+pub fn reduce_to_masked_sumcheck<F: Field, Composition: CompositionPoly<F>>(
+	claim: &SumcheckClaim<F, Composition>,
+	mask_sum: F,
+	mask_coeff: F,
+) -> Result<SumcheckClaim<F, MaskedComposition<F, &Composition>>, Error> {
+	let composite_sums = claim
+		.composite_sums()
+		.iter()
+		.enumerate()
+		.map(|(index, composite_sum)| {
+			let (mask_coeff, sum) = if index == 0 {
+				(Some(mask_coeff), composite_sum.sum + mask_coeff * mask_sum)
+			} else {
+				(None, composite_sum.sum)
+			};
+			CompositeSumClaim {
+				composition: MaskedComposition::new(&composite_sum.composition, mask_coeff),
+				sum,
+			}
+		})
+		.collect();
+	SumcheckClaim::new(claim.n_vars(), claim.n_multilinears() + 1, composite_sums)
+}
+
+/// Verify a zero-knowledge sumcheck protocol execution for a single claim.
+///
+/// The mask commitment is observed with the challenger before the mixing coefficient is sampled,
+/// and the evaluation of the mask at the challenge point is checked against the commitment with
+/// `pcs`. The claim must have at least one composite.
+///
+/// ## Throws
+///
+/// * [`Error::EmptyMaskedClaim`] if the claim has no composites
+/// * [`Error::MaskCommitmentTooSmall`] if the mask of the claim does not fit into `pcs`
+/// * [`VerificationError::IncorrectMaskEvaluation`] if the openings of the mask do not match the
+///   evaluation of the mask in the sumcheck
+/// * [`Error::MaskCommitment`] if an opening proof is rejected
+pub fn verify_zk<F, P, Composition, PCS, Challenger>(
+	claim: &SumcheckClaim<F, Composition>,
+	proof: ZkProof<F, PCS::Commitment, PCS::Proof>,
+	pcs: &PCS,
+	mut challenger: Challenger,
+) -> Result<ZkSumcheckOutput<F>, Error>
+where
+	F: Field,
+	P: PackedField<Scalar = F>,
+	Composition: CompositionPoly<F>,
+	PCS: PolyCommitScheme<P, F>,
+	Challenger: CanObserve<F> + CanObserve<PCS::Commitment> + CanSample<F> + CanSampleBits<usize>,
+{
+	let ZkProof {
+		mask_commitment,
+		mask_sum,
+		proof,
+		univariate_evals,
+		opening_proofs,
+	} = proof;
+	if claim.composite_sums().is_empty() {
+		bail!(Error::EmptyMaskedClaim);
+	}
+
+	let n_vars = claim.n_vars();
+	let degree = mask_degree(claim);
+	mask_log_coeffs(n_vars, degree, pcs.n_vars())?;
+
+	challenger.observe(mask_commitment.clone());
+	challenger.observe(mask_sum);
+	let mask_coeff = challenger.sample();
+
+	let masked_claim = reduce_to_masked_sumcheck(claim, mask_sum, mask_coeff)?;
+	let output = verify_single(&masked_claim, proof, &mut challenger)?;
+
+	let mut multilinear_evals = output.multilinear_evals;
+	let Some(mask_eval) = multilinear_evals.pop() else {
+		bail!(VerificationError::NumberOfFinalEvaluations);
+	};
+
+	if univariate_evals.len() != n_vars || opening_proofs.len() != n_vars {
+		bail!(VerificationError::NumberOfMaskOpenings);
+	}
+	if MaskPolynomial::combine_univariate_evals(&univariate_evals, &output.challenges) != mask_eval
+	{
+		bail!(VerificationError::IncorrectMaskEvaluation);
+	}
+	for (index, (univariate_eval, opening_proof)) in
+		iter::zip(univariate_evals, opening_proofs).enumerate()
+	{
+		let point =
+			mask_opening_point(n_vars, degree, pcs.n_vars(), index, output.challenges[index])?;
+		pcs.verify_evaluation(
+			&mut challenger,
+			&mask_commitment,
+			&point,
+			opening_proof,
+			&[univariate_eval],
+		)
+		.map_err(|err| Error::MaskCommitment(Box::new(err)))?;
+	}
+
+	Ok(ZkSumcheckOutput {
+		challenges: output.challenges,
+		multilinear_evals,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		challenger::new_hasher_challenger,
+		poly_commit::{tensor_pcs::calculate_n_test_queries_reed_solomon, BasicTensorPCS},
+		polynomial::{
+			IsomorphicEvaluationDomainFactory, MultilinearComposite, MultilinearPoly,
+			MultilinearQuery,
+		},
+		protocols::{
+			sumcheck_v2::prove::{prove_zk, RegularSumcheckProver, ZkSumcheckProver},
+			test_utils::TestProductComposition,
+		},
+		reed_solomon::reed_solomon::ReedSolomonCode,
+	};
+	use assert_matches::assert_matches;
+	use binius_field::{
+		arch::OptimalUnderlier128b, BinaryField128b, BinaryField8b, PackedBinaryField1x128b,
+	};
+	use binius_hash::GroestlHasher;
+	use binius_ntt::NTTOptions;
+	use rand::{rngs::StdRng, SeedableRng};
+
+	type FDomain = BinaryField8b;
+	type FE = BinaryField128b;
+	type P = PackedBinaryField1x128b;
+
+	#[test]
+	fn test_mask_sum_and_evaluation() {
+		let mut rng = StdRng::seed_from_u64(0);
+		let n_vars = 4;
+		let mask = MaskPolynomial::<FE>::random(n_vars, 3, &mut rng);
+		assert_eq!(mask.degree(), 3);
+
+		let hypercube_sum = (0..1 << n_vars)
+			.map(|index| {
+				let point = (0..n_vars)
+					.map(|i| {
+						if (index >> i) & 1 == 1 {
+							FE::ONE
+						} else {
+							FE::ZERO
+						}
+					})
+					.collect::<Vec<_>>();
+				mask.evaluate(&point).unwrap()
+			})
+			.sum::<FE>();
+		assert_eq!(mask.sum(), hypercube_sum);
+
+		let point = repeat_with(|| FE::random(&mut rng))
+			.take(n_vars)
+			.collect::<Vec<_>>();
+		let expected = (0..n_vars)
+			.map(|i| {
+				let others = (0..n_vars)
+					.filter(|&k| k != i)
+					.map(|k| point[k])
+					.product::<FE>();
+				evaluate_univariate(&mask.univariates()[i], point[i]) * others
+			})
+			.sum::<FE>();
+		assert_eq!(mask.evaluate(&point).unwrap(), expected);
+
+		assert!(MaskPolynomial::new(vec![vec![FE::ONE; 2], vec![FE::ONE; 3]]).is_err());
+	}
+
+	#[test]
+	fn test_coeffs_multilinear_opens_univariates() {
+		let mut rng = StdRng::seed_from_u64(0);
+		let (n_univariates, degree, n_vars) = (5, 2, 6);
+		let mask = MaskPolynomial::<FE>::random(n_univariates, degree, &mut rng);
+		let multilin = mask.coeffs_multilinear::<P>(n_vars).unwrap();
+
+		let point = repeat_with(|| FE::random(&mut rng))
+			.take(n_univariates)
+			.collect::<Vec<_>>();
+		let univariate_evals = mask.univariate_evals(&point).unwrap();
+		for (index, (&x, &eval)) in point.iter().zip(&univariate_evals).enumerate() {
+			let opening_point =
+				mask_opening_point(n_univariates, degree, n_vars, index, x).unwrap();
+			let query = MultilinearQuery::<P>::with_full_query(&opening_point).unwrap();
+			assert_eq!(multilin.evaluate(&query).unwrap(), eval);
+		}
+		assert_eq!(
+			MaskPolynomial::combine_univariate_evals(&univariate_evals, &point),
+			mask.evaluate(&point).unwrap()
+		);
+
+		// Eight univariates leave two coefficient variables, too few for degree four.
+		let mask = MaskPolynomial::<FE>::random(8, 4, &mut rng);
+		assert_matches!(
+			mask.coeffs_multilinear::<P>(5),
+			Err(Error::MaskCommitmentTooSmall { n_vars: 5 })
+		);
+	}
+
+	#[test]
+	fn test_prove_verify_zk_sumcheck() {
+		let mut rng = StdRng::seed_from_u64(0);
+		let n_vars = 6;
+		let n_multilinears = 3;
+
+		let multilins = repeat_with(|| {
+			let values = repeat_with(|| FE::random(&mut rng))
+				.take(1 << n_vars)
+				.collect();
+			MultilinearExtension::from_values(values)
+				.unwrap()
+				.specialize::<FE>()
+		})
+		.take(n_multilinears)
+		.collect::<Vec<_>>();
+		let composition = TestProductComposition::new(n_multilinears);
+		let witness =
+			MultilinearComposite::new(n_vars, composition.clone(), multilins.clone()).unwrap();
+		let sum = (0..1 << n_vars)
+			.map(|index| witness.evaluate_on_hypercube(index).unwrap())
+			.sum::<FE>();
+
+		let claim = SumcheckClaim::new(
+			n_vars,
+			n_multilinears,
+			vec![CompositeSumClaim {
+				composition: composition.clone(),
+				sum,
+			}],
+		)
+		.unwrap();
+
+		let rs_code = ReedSolomonCode::new(5, 2, NTTOptions::default()).unwrap();
+		let n_test_queries =
+			calculate_n_test_queries_reed_solomon::<_, FE, _>(100, 4, &rs_code).unwrap();
+		let pcs = <BasicTensorPCS<OptimalUnderlier128b, FE, BinaryField8b, FE, _, _, _>>::new_using_groestl_merkle_tree(
+			4,
+			rs_code,
+			n_test_queries,
+		)
+		.unwrap();
+
+		let inner = RegularSumcheckProver::<FDomain, _, _, _>::new(
+			multilins.clone(),
+			[CompositeSumClaim { composition, sum }],
+			IsomorphicEvaluationDomainFactory::<FDomain>::default(),
+			|_| 1,
+		)
+		.unwrap();
+		let prover = ZkSumcheckProver::new(inner, mask_degree(&claim), &mut rng).unwrap();
+
+		let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+		let (prove_output, proof) =
+			prove_zk::<_, P, _, _, _>(prover, &pcs, challenger.clone()).unwrap();
+		let verify_output =
+			verify_zk::<_, P, _, _, _>(&claim, proof.clone(), &pcs, challenger.clone()).unwrap();
+		assert_eq!(prove_output, verify_output);
+
+		let query = MultilinearQuery::<FE>::with_full_query(&verify_output.challenges).unwrap();
+		for (multilin, eval) in multilins.iter().zip(&verify_output.multilinear_evals) {
+			assert_eq!(multilin.evaluate(&query).unwrap(), *eval);
+		}
+
+		let mut bad_proof = proof.clone();
+		bad_proof.mask_sum += FE::ONE;
+		assert!(verify_zk::<_, P, _, _, _>(&claim, bad_proof, &pcs, challenger.clone()).is_err());
+
+		// Openings that do not match the mask evaluated in the sumcheck are rejected.
+		let mut bad_proof = proof.clone();
+		bad_proof.univariate_evals[0] += FE::ONE;
+		assert_matches!(
+			verify_zk::<_, P, _, _, _>(&claim, bad_proof, &pcs, challenger.clone()),
+			Err(Error::Verification(VerificationError::IncorrectMaskEvaluation))
+		);
+
+		// The univariates of the mask are bound by the commitment. Shift two of the evaluations so
+		// that the combined mask evaluation, and hence the sumcheck, still verifies.
+		let (x0, x1) = (verify_output.challenges[0], verify_output.challenges[1]);
+		let mut bad_proof = proof.clone();
+		bad_proof.univariate_evals[0] += x0 * x1.invert().unwrap();
+		bad_proof.univariate_evals[1] += FE::ONE;
+		assert_matches!(
+			verify_zk::<_, P, _, _, _>(&claim, bad_proof, &pcs, challenger.clone()),
+			Err(Error::MaskCommitment(_))
+		);
+
+		// A commitment to a different mask is rejected.
+		let other_mask = MaskPolynomial::<FE>::random(n_vars, mask_degree(&claim), &mut rng);
+		let (other_commitment, _) = pcs
+			.commit(&[other_mask.coeffs_multilinear::<P>(pcs.n_vars()).unwrap()])
+			.unwrap();
+		let mut bad_proof = proof;
+		bad_proof.mask_commitment = other_commitment;
+		assert!(verify_zk::<_, P, _, _, _>(&claim, bad_proof, &pcs, challenger).is_err());
+	}
+}
@@ -10,7 +10,7 @@ use crate::{
 		evalcheck::{
 			subclaims::{
 				non_same_query_pcs_sumcheck_claim, non_same_query_pcs_sumcheck_metas,
-				non_same_query_pcs_sumcheck_witness, BivariateSumcheck, MemoizedQueries,
+				non_same_query_pcs_sumcheck_witness, BivariateSumcheck,
 			},
 			CommittedEvalClaim, Error as EvalcheckError, EvalcheckClaim, EvalcheckProver,
 			EvalcheckVerifier,
@@ -213,15 +213,13 @@ where
 		Some(&mut prover.memoized_eq_ind),
 	)?;
 
-	let mut memoized_queries = MemoizedQueries::new();
-
 	let sumchecks = metas
 		.into_iter()
 		.map(|meta| {
 			let claim = non_same_query_pcs_sumcheck_claim(prover.oracles, meta.clone())?;
 			let witness = non_same_query_pcs_sumcheck_witness(
 				prover.witness_index,
-				&mut memoized_queries,
+				&mut prover.memoized_queries,
 				meta,
 			)?;
 			Ok((claim, witness))
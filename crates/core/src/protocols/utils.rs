@@ -1,6 +1,7 @@
 // Copyright 2024 Ulvetanna Inc.
 
-use binius_field::PackedField;
+use crate::polynomial::MultilinearExtension;
+use binius_field::{Field, PackedField};
 
 /// Construct a packed field element from a function that returns scalar values by index with the
 /// given offset in packed elements. E.g. if `offset` is 2, and `WIDTH` is 4, `f(9)` will be used
@@ -12,3 +13,23 @@ pub fn packed_from_fn_with_offset<P: PackedField>(
 ) -> P {
 	P::from_fn(|i| f(i + offset * P::WIDTH))
 }
+
+/// Returns the packed element at the given offset of the hypercube evaluations of a multilinear.
+///
+/// When the multilinear spans at least one packed element, the element is read directly from its
+/// packed evaluations and `offset` must be in range. Otherwise, the multilinear fits in the
+/// element at offset 0, which is gathered scalar by scalar and padded with zeros past the end of
+/// the hypercube.
+#[inline]
+pub fn packed_evals_at_offset<P: PackedField>(
+	multilin: &MultilinearExtension<P, &[P]>,
+	offset: usize,
+) -> P {
+	if multilin.n_vars() >= P::LOG_WIDTH {
+		multilin.evals()[offset]
+	} else {
+		packed_from_fn_with_offset(offset, |i| {
+			multilin.evaluate_on_hypercube(i).unwrap_or(P::Scalar::ZERO)
+		})
+	}
+}
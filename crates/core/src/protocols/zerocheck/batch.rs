@@ -70,7 +70,7 @@ where
 
 	let oracles = zerochecks
 		.iter()
-		.map(|(claim, _)| claim.poly.clone())
+		.map(|(claim, _)| claim.poly().clone())
 		.collect::<Vec<_>>();
 
 	let AbstractSumcheckBatchProveOutput {
@@ -105,13 +105,13 @@ where
 	let claims_vec = claims.into_iter().collect::<Vec<_>>();
 	let poly_oracles = claims_vec
 		.iter()
-		.map(|c| c.poly.clone())
+		.map(|c| c.poly().clone())
 		.collect::<Vec<_>>();
 
 	// Ensure all claims have at least one variable
 	claims_vec
 		.iter()
-		.all(|claim| claim.poly.n_vars() > 0)
+		.all(|claim| claim.poly().n_vars() > 0)
 		.then_some(())
 		.ok_or(Error::ZeroVariableClaim)?;
 
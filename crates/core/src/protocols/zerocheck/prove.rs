@@ -14,7 +14,6 @@ use crate::{
 	polynomial::{
 		extrapolate_line, transparent::eq_ind::EqIndPartialEval, CompositionPoly,
 		Error as PolynomialError, EvaluationDomain, EvaluationDomainFactory, MultilinearExtension,
-		MultilinearQuery,
 	},
 	protocols::{
 		abstract_sumcheck::{
@@ -22,15 +21,17 @@ use crate::{
 			AbstractSumcheckEvaluator, AbstractSumcheckProversState, AbstractSumcheckReductor,
 			AbstractSumcheckWitness, CommonProversState, ReducedClaim,
 		},
-		utils::packed_from_fn_with_offset,
+		utils::{packed_evals_at_offset, packed_from_fn_with_offset},
 	},
 };
 use binius_field::{
 	packed::{get_packed_slice, mul_by_subfield_scalar},
 	ExtensionField, Field, PackedExtension, PackedField,
 };
-use binius_utils::bail;
-use bytemuck::zeroed_vec;
+use binius_utils::{
+	alloc::{zeroed_std_vec, zeroed_vec, AlignedVec},
+	bail,
+};
 use getset::Getters;
 use rayon::prelude::*;
 use std::{cmp::max, marker::PhantomData};
@@ -200,12 +201,12 @@ where
 		witness: W,
 		seq_id: usize,
 	) -> Result<Self::Prover, Error> {
-		let ids = claim.poly.inner_polys_oracle_ids().collect::<Vec<_>>();
+		let ids = claim.poly().inner_polys_oracle_ids().collect::<Vec<_>>();
 		self.common
 			.extend(witness.multilinears(seq_id, ids.as_slice())?)?;
 		let domain = self
 			.evaluation_domain_factory
-			.create(claim.poly.max_individual_degree() + 1)?;
+			.create(claim.poly().max_individual_degree() + 1)?;
 		let prover = ZerocheckProver::new(claim, witness, domain, self.zerocheck_challenges)?;
 		Ok(prover)
 	}
@@ -281,7 +282,7 @@ where
 	// * $d$ is degree of polynomial in zerocheck claim
 	// * $r_i$ is the verifier challenge at the end of round $i$.
 	// with sorting that is lexicographically-inspired (lowest index = least significant).
-	round_q: Vec<PW>,
+	round_q: AlignedVec<PW>,
 	// None initially
 	// After ith round, represents the $\bar{Q}_i$ multilinear polynomial partially evaluated
 	// at lowest $i$ variables with the $i$ verifier challenges received so far.
@@ -314,14 +315,14 @@ where
 		validate_witness(&claim, &witness)?;
 
 		let n_vars = claim.n_vars();
-		let degree = claim.poly.max_individual_degree();
+		let degree = claim.poly().max_individual_degree();
 
 		if degree == 0 {
 			bail!(Error::PolynomialDegreeIsZero);
 		}
 		check_evaluation_domain(degree, &domain)?;
 
-		let oracle_ids = claim.poly.inner_polys_oracle_ids().collect::<Vec<_>>();
+		let oracle_ids = claim.poly().inner_polys_oracle_ids().collect::<Vec<_>>();
 
 		if zerocheck_challenges.len() + 1 < n_vars {
 			bail!(Error::NotEnoughZerocheckChallenges);
@@ -385,10 +386,12 @@ where
 		// Let d be the degree of the polynomial in the zerocheck claim.
 		let specialized_q_values = if self.smaller_domain.size() == 0 {
 			// Special handling for the d = 1 (multilinear) case
-			zeroed_vec((1usize << rd_vars).div_ceil(PW::WIDTH))
+			zeroed_std_vec((1usize << rd_vars).div_ceil(PW::WIDTH))
 		} else if self.smaller_domain.size() == 1 {
 			// We do not need to interpolate in this special d = 2 case
-			std::mem::replace(&mut self.round_q, zeroed_vec(new_q_len))
+			let q_values = self.round_q.to_vec();
+			self.round_q.fill(PW::zero());
+			q_values
 		} else {
 			// This is for the d >= 3 case
 			//
@@ -415,8 +418,8 @@ where
 		new_q_bar_values.par_iter_mut().for_each(|e| *e *= coeff);
 
 		if let Some(prev_q_bar) = self.round_q_bar.as_mut() {
-			let query = MultilinearQuery::<PW::Scalar>::with_full_query(&[prev_rd_challenge])?;
-			let specialized_prev_q_bar = prev_q_bar.evaluate_partial_low(&query)?;
+			let specialized_prev_q_bar =
+				prev_q_bar.partial_evaluate_low::<PW::Scalar>(&[prev_rd_challenge])?;
 			let specialized_prev_q_bar_evals = specialized_prev_q_bar.evals();
 
 			const CHUNK_SIZE: usize = 64;
@@ -451,7 +454,7 @@ where
 	where
 		EDF: EvaluationDomainFactory<DomainField>,
 	{
-		let degree = self.claim.poly.max_individual_degree();
+		let degree = self.claim.poly().max_individual_degree();
 		if degree == 1 {
 			return Ok(vec![PW::Scalar::default()]);
 		}
@@ -616,31 +619,22 @@ where
 	) {
 		debug_assert!(i * P::WIDTH < self.eq_ind.size());
 
-		let eq_ind_factor = packed_from_fn_with_offset::<P>(i, |j| {
-			self.eq_ind
-				.evaluate_on_hypercube(j)
-				.unwrap_or(P::Scalar::ZERO)
-		});
+		let eq_ind_factor = packed_evals_at_offset(&self.eq_ind, i);
 
 		// The rest require interpolation.
-		for d in 2..self.domain_points.len() {
-			evals_0
-				.iter()
-				.zip(evals_1.iter())
-				.zip(evals_z.iter_mut())
-				.for_each(|((&evals_0_j, &evals_1_j), evals_z_j)| {
-					*evals_z_j =
-						extrapolate_line::<P, FS>(evals_0_j, evals_1_j, self.domain_points[d]);
-				});
-
-			let composite_value = self
-				.composition
-				.evaluate(evals_z)
-				.expect("evals_z is initialized with a length of poly.composition.n_vars()");
-
-			round_evals[d - 2] += composite_value * eq_ind_factor;
-			round_q_chunk[d - 2] = mul_by_subfield_scalar(composite_value, self.denom_inv[d - 2]);
-		}
+		accumulate_eq_weighted_composite::<P, FS, C>(
+			self.composition,
+			self.domain_points,
+			eq_ind_factor,
+			evals_0,
+			evals_1,
+			evals_z,
+			round_evals,
+			|d, composite_value| {
+				round_q_chunk[d - 2] =
+					mul_by_subfield_scalar(composite_value, self.denom_inv[d - 2]);
+			},
+		);
 	}
 
 	fn round_evals_to_coeffs(
@@ -719,45 +713,34 @@ where
 				.unwrap_or(P::Scalar::ZERO)
 		});
 
-		let eq_ind_factor = packed_from_fn_with_offset::<P>(i, |j| {
-			self.eq_ind
-				.evaluate_on_hypercube(j)
-				.unwrap_or(P::Scalar::ZERO)
-		});
+		let eq_ind_factor = packed_evals_at_offset(&self.eq_ind, i);
 
 		// We can replace constraint polynomial evaluations at C(r, 1, x) with Q_i_bar(r, 1, x)
 		// See section 4 of [https://eprint.iacr.org/2024/108] for details
 		round_evals[0] += q_bar_one * eq_ind_factor;
 
 		// The rest require interpolation.
-		for d in 2..self.domain_points.len() {
-			evals_0
-				.iter()
-				.zip(evals_1.iter())
-				.zip(evals_z.iter_mut())
-				.for_each(|((&evals_0_j, &evals_1_j), evals_z_j)| {
-					*evals_z_j =
-						extrapolate_line::<P, FS>(evals_0_j, evals_1_j, self.domain_points[d]);
-				});
-
-			let composite_value = self
-				.composition
-				.evaluate(evals_z)
-				.expect("evals_z is initialized with a length of poly.composition.n_vars()");
-
-			round_evals[d - 1] += composite_value * eq_ind_factor;
-
-			// We compute Q_i(r, domain[d], x) values with minimal additional work (linear extrapolation, multiplication, and inversion)
-			// and cache these values for later use. These values will help us update Q_i_bar into Q_{i+1}_bar, which will in turn
-			// help us avoid next round's constraint polynomial evaluations at X = 1.
-			// For more details, see section 4 of [https://eprint.iacr.org/2024/108]
-			let specialized_qbar_eval =
-				extrapolate_line::<P, FS>(q_bar_zero, q_bar_one, self.domain_points[d]);
-			round_q_chunk[d - 2] = mul_by_subfield_scalar(
-				composite_value - specialized_qbar_eval,
-				self.denom_inv[d - 2],
-			);
-		}
+		accumulate_eq_weighted_composite::<P, FS, C>(
+			self.composition,
+			self.domain_points,
+			eq_ind_factor,
+			evals_0,
+			evals_1,
+			evals_z,
+			&mut round_evals[1..],
+			|d, composite_value| {
+				// We compute Q_i(r, domain[d], x) values with minimal additional work (linear extrapolation, multiplication, and inversion)
+				// and cache these values for later use. These values will help us update Q_i_bar into Q_{i+1}_bar, which will in turn
+				// help us avoid next round's constraint polynomial evaluations at X = 1.
+				// For more details, see section 4 of [https://eprint.iacr.org/2024/108]
+				let specialized_qbar_eval =
+					extrapolate_line::<P, FS>(q_bar_zero, q_bar_one, self.domain_points[d]);
+				round_q_chunk[d - 2] = mul_by_subfield_scalar(
+					composite_value - specialized_qbar_eval,
+					self.denom_inv[d - 2],
+				);
+			},
+		);
 	}
 
 	fn round_evals_to_coeffs(
@@ -786,3 +769,44 @@ where
 		Ok(coeffs)
 	}
 }
+
+/// Evaluates the composition of a vertex at every domain point from index 2 on, interpolating the
+/// multilinears linearly between `evals_0` and `evals_1`.
+///
+/// For each domain point index `d`, the composite value multiplied by `eq_ind_factor` is added to
+/// `round_evals[d - 2]`, and `on_composite` is called with `d` and the composite value before the
+/// multiplication. The product is taken as soon as the composite value is computed, without
+/// storing the unweighted values.
+#[allow(clippy::too_many_arguments)]
+#[inline]
+fn accumulate_eq_weighted_composite<P, FS, C>(
+	composition: &C,
+	domain_points: &[FS],
+	eq_ind_factor: P,
+	evals_0: &[P],
+	evals_1: &[P],
+	evals_z: &mut [P],
+	round_evals: &mut [P],
+	mut on_composite: impl FnMut(usize, P),
+) where
+	P: PackedExtension<FS, Scalar: ExtensionField<FS>>,
+	FS: Field,
+	C: CompositionPoly<P>,
+{
+	for d in 2..domain_points.len() {
+		evals_0
+			.iter()
+			.zip(evals_1.iter())
+			.zip(evals_z.iter_mut())
+			.for_each(|((&evals_0_j, &evals_1_j), evals_z_j)| {
+				*evals_z_j = extrapolate_line::<P, FS>(evals_0_j, evals_1_j, domain_points[d]);
+			});
+
+		let composite_value = composition
+			.evaluate(evals_z)
+			.expect("evals_z is initialized with a length of poly.composition.n_vars()");
+
+		round_evals[d - 2] += composite_value * eq_ind_factor;
+		on_composite(d, composite_value);
+	}
+}
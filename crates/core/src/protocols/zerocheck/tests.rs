@@ -109,9 +109,7 @@ fn test_prove_verify_interaction_helper(
 	let composite_poly =
 		CompositePolyOracle::new(n_vars, h, TestProductComposition::new(n_multilinears)).unwrap();
 
-	let zc_claim = ZerocheckClaim {
-		poly: composite_poly,
-	};
+	let zc_claim = ZerocheckClaim::new(composite_poly).unwrap();
 
 	// Zerocheck
 	let domain_factory = IsomorphicEvaluationDomainFactory::<BinaryField32b>::default();
@@ -254,9 +252,7 @@ where
 				.collect(),
 		)
 		.unwrap();
-		let claim = ZerocheckClaim {
-			poly: composite_oracle,
-		};
+		let claim = ZerocheckClaim::new(composite_oracle).unwrap();
 		new_claims.push(claim);
 		new_witnesses.push(witness);
 	});
@@ -394,9 +390,7 @@ fn test_prove_verify_packed() {
 	let composite_poly =
 		CompositePolyOracle::new(n_vars, h, TestProductComposition::new(n_multilinears)).unwrap();
 
-	let zc_claim = ZerocheckClaim {
-		poly: composite_poly,
-	};
+	let zc_claim = ZerocheckClaim::new(composite_poly).unwrap();
 
 	// Zerocheck
 	let domain_factory = IsomorphicEvaluationDomainFactory::<BinaryField32b>::default();
@@ -26,12 +26,12 @@ where
 	F: TowerField,
 	CH: CanSample<F> + CanObserve<F>,
 {
-	if claim.poly.max_individual_degree() == 0 {
+	if claim.poly().max_individual_degree() == 0 {
 		bail!(Error::PolynomialDegreeIsZero);
 	}
 
 	// Reduction
-	let n_vars = claim.poly.n_vars();
+	let n_vars = claim.poly().n_vars();
 	let n_rounds = proof.rounds.len();
 	if n_rounds != n_vars {
 		bail!(VerificationError::NumberOfRounds);
@@ -44,5 +44,5 @@ where
 	};
 	let reduced_claim = abstract_sumcheck::verify(claim, proof, reductor, challenger)?;
 
-	finalize_evalcheck_claim(&claim.poly, reduced_claim).map_err(Into::into)
+	finalize_evalcheck_claim(claim.poly(), reduced_claim).map_err(Into::into)
 }
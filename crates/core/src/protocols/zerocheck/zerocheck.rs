@@ -1,7 +1,7 @@
 // Copyright 2023 Ulvetanna Inc.
 
 use crate::{
-	oracle::{CompositePolyOracle, OracleId},
+	oracle::{CompositePolyOracle, Error as OracleError, OracleId},
 	polynomial::{evaluate_univariate, MultilinearComposite},
 	protocols::{
 		abstract_sumcheck::{
@@ -12,8 +12,9 @@ use crate::{
 	},
 	witness::MultilinearWitness,
 };
-use binius_field::{Field, PackedField};
+use binius_field::{Field, PackedField, TowerField};
 use binius_utils::bail;
+use getset::Getters;
 use std::fmt::Debug;
 
 use super::{Error, VerificationError};
@@ -22,10 +23,11 @@ use super::{Error, VerificationError};
 ///
 /// The claim is that a multilinear composite polynomial, that the verifier has oracle access to,
 /// evaluates to zero on the boolean hypercube.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Getters)]
 pub struct ZerocheckClaim<F: Field> {
 	/// Virtual Polynomial Oracle of the function claimed to be zero on hypercube
-	pub poly: CompositePolyOracle<F>,
+	#[getset(get = "pub")]
+	poly: CompositePolyOracle<F>,
 }
 
 impl<F: Field> AbstractSumcheckClaim<F> for ZerocheckClaim<F> {
@@ -42,6 +44,19 @@ impl<F: Field> AbstractSumcheckClaim<F> for ZerocheckClaim<F> {
 	}
 }
 
+impl<F: TowerField> ZerocheckClaim<F> {
+	/// Constructs a new zerocheck claim.
+	///
+	/// ## Throws
+	///
+	/// * [`OracleError::CompositionTowerLevelTooHigh`] if the composition has a tower level
+	///   exceeding that of all the composed oracles, other than the tower level of `F`
+	pub fn new(poly: CompositePolyOracle<F>) -> Result<Self, OracleError> {
+		poly.check_tower_levels(F::TOWER_LEVEL)?;
+		Ok(Self { poly })
+	}
+}
+
 impl<F: Field> ZerocheckClaim<F> {
 	/// The number of variables of the composite polynomial the claim is about.
 	pub fn n_vars(&self) -> usize {
@@ -212,7 +227,7 @@ where
 {
 	let log_size = claim.n_vars();
 
-	let oracle_ids = claim.poly.inner_polys_oracle_ids().collect::<Vec<_>>();
+	let oracle_ids = claim.poly().inner_polys_oracle_ids().collect::<Vec<_>>();
 	let multilinears = witness
 		.multilinears(0, oracle_ids.as_slice())?
 		.into_iter()
@@ -0,0 +1,291 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Prover resources that are reused across proofs.
+
+use crate::{
+	params::{ProveParams, ProverThreadPool},
+	protocols::sumcheck_v2::prove::{ComputeBackend, CpuBackend, MemoryBudget},
+	prover_key::ProverKey,
+	witness::MultilinearExtensionIndex,
+};
+use binius_field::{as_packed_field::PackScalar, underlier::UnderlierType, Field};
+use rayon::ThreadPool;
+use std::{
+	any::{Any, TypeId},
+	collections::HashMap,
+	sync::{Arc, Mutex},
+};
+
+type PreprocessedCache = HashMap<(TypeId, Vec<usize>), Arc<dyn Any + Send + Sync>>;
+
+/// The warm state of a long-running prover, reused across many proofs.
+///
+/// A proving service that builds a fresh prover for every request pays for initialization that
+/// does not depend on the request: building the thread pool, setting up the compute backend,
+/// preprocessing the [`ProverKey`], constructing the polynomial commitment schemes along with
+/// the NTT twiddles and dispatch tables of their Reed–Solomon codes, and allocating the scratch
+/// buffers of the sumcheck loops. A context owns all of these and hands them out to each proof.
+///
+/// The scratch buffers live in the scratch arena of the memory budget of the context, see
+/// [`MemoryBudget::scratch_arena`], which reaches the sumcheck provers through
+/// [`Self::memory_budget`]. The provers return their buffers to the arena after each round, so the
+/// next rounds and the next proofs of the same shape reuse them.
+///
+/// The context is shared by reference, so concurrent proofs can use one context. Between requests,
+/// [`Self::reset`] clears the per-proof state, while the thread pool, backend, prover key,
+/// preprocessed objects and scratch buffers stay warm until the context is dropped or
+/// [`Self::clear_preprocessed`] and [`Self::clear_scratch`] are called.
+#[derive(Debug)]
+pub struct ProverContext<U, FW, Backend = CpuBackend>
+where
+	U: UnderlierType + PackScalar<FW>,
+	FW: Field,
+{
+	params: ProveParams,
+	thread_pool: Option<Arc<ThreadPool>>,
+	backend: Backend,
+	prover_key: ProverKey<U, FW>,
+	memory_budget: MemoryBudget,
+	preprocessed: Mutex<PreprocessedCache>,
+}
+
+impl<U, FW> ProverContext<U, FW, CpuBackend>
+where
+	U: UnderlierType + PackScalar<FW>,
+	FW: Field,
+{
+	/// Creates a context running the sumcheck loops on the CPU.
+	pub fn new(params: ProveParams, prover_key: ProverKey<U, FW>) -> Self {
+		Self::new_with_backend(params, prover_key, CpuBackend)
+	}
+}
+
+impl<U, FW, Backend> ProverContext<U, FW, Backend>
+where
+	U: UnderlierType + PackScalar<FW>,
+	FW: Field,
+	Backend: ComputeBackend,
+{
+	/// Creates a context running the sumcheck loops on the given backend.
+	pub fn new_with_backend(
+		params: ProveParams,
+		prover_key: ProverKey<U, FW>,
+		backend: Backend,
+	) -> Self {
+		let thread_pool = match params.thread_pool() {
+			ProverThreadPool::Global => None,
+			ProverThreadPool::Pool(pool) => Some(pool.clone()),
+		};
+
+		Self {
+			params,
+			thread_pool,
+			backend,
+			prover_key,
+			memory_budget: MemoryBudget::unlimited(),
+			preprocessed: Mutex::default(),
+		}
+	}
+
+	pub fn params(&self) -> &ProveParams {
+		&self.params
+	}
+
+	/// The thread pool proofs run on, or `None` for the global rayon thread pool.
+	pub fn thread_pool(&self) -> Option<&Arc<ThreadPool>> {
+		self.thread_pool.as_ref()
+	}
+
+	pub fn backend(&self) -> &Backend {
+		&self.backend
+	}
+
+	pub fn prover_key(&self) -> &ProverKey<U, FW> {
+		&self.prover_key
+	}
+
+	/// Returns a witness index containing the oracles preprocessed by the prover key, see
+	/// [`ProverKey::witness_index`].
+	pub fn witness_index<'a>(&self) -> MultilinearExtensionIndex<'a, U, FW> {
+		self.prover_key.witness_index()
+	}
+
+	/// The scratch memory budget for the sumcheck provers.
+	///
+	/// The returned budget shares its peak usage with the context, so the peak over all the
+	/// proofs since the last [`Self::reset`] can be read from [`MemoryBudget::peak_scratch_bytes`].
+	/// It also shares the scratch arena of the context, see [`MemoryBudget::scratch_arena`].
+	pub fn memory_budget(&self) -> MemoryBudget {
+		self.memory_budget.clone()
+	}
+
+	/// Sets the scratch memory budget, keeping the scratch arena of the context.
+	pub fn set_memory_budget(&mut self, memory_budget: MemoryBudget) {
+		self.memory_budget =
+			memory_budget.with_scratch_arena(self.memory_budget.scratch_arena().clone());
+	}
+
+	/// Runs `f` on the thread pool of the context.
+	pub fn install<R: Send>(&self, f: impl FnOnce() -> R + Send) -> R {
+		match &self.thread_pool {
+			Some(pool) => pool.install(f),
+			None => f(),
+		}
+	}
+
+	/// Returns the preprocessed object of type `T` for the given shape, constructing it with
+	/// `init` the first time it is requested.
+	///
+	/// This caches objects that are expensive to construct and only depend on the shape of the
+	/// statement, such as a polynomial commitment scheme for a number of variables, whose
+	/// Reed–Solomon code precomputes its NTT twiddles. Objects of different types are cached
+	/// separately, so the shape only has to identify the object among those of its type.
+	///
+	/// `init` runs without holding the cache, so it may use the context. If concurrent proofs
+	/// construct the same object, the first one cached is kept and returned to all of them.
+	pub fn preprocessed<T, E>(
+		&self,
+		shape: &[usize],
+		init: impl FnOnce() -> Result<T, E>,
+	) -> Result<Arc<T>, E>
+	where
+		T: Send + Sync + 'static,
+	{
+		let key = (TypeId::of::<T>(), shape.to_vec());
+		let cached = self
+			.preprocessed
+			.lock()
+			.expect("preprocessed cache lock is not poisoned")
+			.get(&key)
+			.cloned();
+
+		let object = match cached {
+			Some(object) => object,
+			None => {
+				let object: Arc<dyn Any + Send + Sync> = Arc::new(init()?);
+				self.preprocessed
+					.lock()
+					.expect("preprocessed cache lock is not poisoned")
+					.entry(key)
+					.or_insert(object)
+					.clone()
+			}
+		};
+		Ok(object
+			.downcast()
+			.expect("cached objects are keyed by their type"))
+	}
+
+	/// Drops the preprocessed objects cached by [`Self::preprocessed`].
+	pub fn clear_preprocessed(&mut self) {
+		self.preprocessed
+			.get_mut()
+			.expect("preprocessed cache lock is not poisoned")
+			.clear();
+	}
+
+	/// Drops the scratch buffers kept for reuse by the sumcheck provers.
+	pub fn clear_scratch(&mut self) {
+		self.memory_budget.scratch_arena().clear();
+	}
+
+	/// Clears the per-proof state of the context.
+	///
+	/// The peak scratch memory usage of the memory budget starts over from zero, keeping the
+	/// limit. Budgets handed out before the reset no longer report to the context. The thread
+	/// pool, backend, prover key, preprocessed objects and scratch buffers are kept.
+	pub fn reset(&mut self) {
+		let memory_budget = match self.memory_budget.max_scratch_bytes() {
+			Some(max_scratch_bytes) => MemoryBudget::new(max_scratch_bytes),
+			None => MemoryBudget::unlimited(),
+		};
+		self.set_memory_budget(memory_budget);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use binius_field::{underlier::WithUnderlier, BinaryField128b, PackedBinaryField1x128b};
+	use std::convert::Infallible;
+
+	type U = <PackedBinaryField1x128b as WithUnderlier>::Underlier;
+	type Context = ProverContext<U, BinaryField128b>;
+
+	#[test]
+	fn test_context_reuses_thread_pool() {
+		let params = ProveParams::builder().n_threads(2).build().unwrap();
+		let context = Context::new(params, ProverKey::new());
+
+		let pool = context.thread_pool().unwrap().clone();
+		for _ in 0..2 {
+			assert_eq!(context.install(rayon::current_num_threads), 2);
+			assert!(Arc::ptr_eq(context.thread_pool().unwrap(), &pool));
+		}
+
+		let context = Context::new(ProveParams::default(), ProverKey::new());
+		assert!(context.thread_pool().is_none());
+	}
+
+	#[test]
+	fn test_context_caches_preprocessed_objects() {
+		let mut context = Context::new(ProveParams::default(), ProverKey::new());
+		let mut n_inits = 0;
+		let mut get = |context: &Context, shape: &[usize]| {
+			context
+				.preprocessed(shape, || {
+					n_inits += 1;
+					Ok::<_, Infallible>(vec![0u8; shape.iter().sum()])
+				})
+				.unwrap()
+		};
+
+		let first = get(&context, &[4, 2]);
+		assert!(Arc::ptr_eq(&first, &get(&context, &[4, 2])));
+		assert_eq!(get(&context, &[5, 2]).len(), 7);
+
+		// Objects of different types with the same shape are cached separately.
+		let other = context
+			.preprocessed(&[4, 2], || Ok::<_, Infallible>(4u32))
+			.unwrap();
+		assert_eq!(*other, 4);
+
+		// Reset keeps the cache.
+		context.reset();
+		assert!(Arc::ptr_eq(&first, &get(&context, &[4, 2])));
+
+		context.clear_preprocessed();
+		assert!(!Arc::ptr_eq(&first, &get(&context, &[4, 2])));
+		assert_eq!(n_inits, 3);
+
+		assert_eq!(context.preprocessed::<u64, _>(&[1], || Err("init failed")), Err("init failed"));
+	}
+
+	#[test]
+	fn test_context_reset_starts_memory_peak_over() {
+		let mut context = Context::new(ProveParams::default(), ProverKey::new());
+		context.set_memory_budget(MemoryBudget::new(1 << 20));
+
+		context.memory_budget().record_scratch_bytes(1000);
+		assert_eq!(context.memory_budget().peak_scratch_bytes(), 1000);
+
+		context.reset();
+		assert_eq!(context.memory_budget().peak_scratch_bytes(), 0);
+		assert_eq!(context.memory_budget().max_scratch_bytes(), Some(1 << 20));
+	}
+
+	#[test]
+	fn test_context_keeps_scratch_buffers() {
+		let mut context = Context::new(ProveParams::default(), ProverKey::new());
+		context.memory_budget().scratch_arena().put(vec![0u8; 16]);
+
+		// Budgets handed out by the context share its scratch arena, also after a reset or a new
+		// budget.
+		context.reset();
+		context.set_memory_budget(MemoryBudget::new(1 << 20));
+		assert_eq!(context.memory_budget().scratch_arena().len(), 1);
+
+		context.clear_scratch();
+		assert!(context.memory_budget().scratch_arena().is_empty());
+	}
+}
@@ -0,0 +1,207 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Statement-independent prover preprocessing.
+
+use crate::{
+	oracle::{MultilinearOracleSet, MultilinearPolyOracle, OracleId},
+	polynomial::{Error as PolynomialError, MultivariatePoly},
+	witness::{Error as WitnessError, MultilinearExtensionIndex},
+};
+use binius_field::{
+	as_packed_field::{PackScalar, PackedType},
+	underlier::{UnderlierType, WithUnderlier},
+	ExtensionField, Field, PackedField, TowerField,
+};
+use binius_utils::bail;
+use rayon::prelude::*;
+use std::fmt::Debug;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	#[error("oracle {id} is not transparent")]
+	NotTransparent { id: OracleId },
+	#[error("oracle {id} tower level ({tower_level}) exceeds the witness tower level ({max_tower_level})")]
+	TowerLevelTooHigh {
+		id: OracleId,
+		tower_level: usize,
+		max_tower_level: usize,
+	},
+	#[error("oracle {id} evaluates outside of the witness field on the boolean hypercube")]
+	NotInSubfield { id: OracleId },
+	#[error("polynomial error: {0}")]
+	Polynomial(#[from] PolynomialError),
+	#[error("witness error: {0}")]
+	Witness(#[from] WitnessError),
+}
+
+/// Prover data that depends only on the constraint system and not on the statement being proven.
+///
+/// Transparent oracles such as [`crate::polynomial::transparent::step_down::StepDown`] and
+/// [`crate::polynomial::transparent::select_row::SelectRow`] that are used as sumcheck
+/// multilinears need their hypercube evaluations in the witness. The prover key materializes them
+/// once, and every proof starts from a witness index that shares these allocations, see
+/// [`ProverKey::witness_index`].
+///
+/// Only transparents registered while building the constraint system should be preprocessed.
+/// Statement-dependent transparents, like the equality indicators introduced by evalcheck, must
+/// still be computed per proof.
+#[derive(Debug, Default)]
+pub struct ProverKey<U, FW>
+where
+	U: UnderlierType + PackScalar<FW>,
+	FW: Field,
+{
+	transparents: MultilinearExtensionIndex<'static, U, FW>,
+	oracle_ids: Vec<OracleId>,
+}
+
+impl<U, FW> ProverKey<U, FW>
+where
+	U: UnderlierType + PackScalar<FW>,
+	FW: Field,
+{
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Materializes the given transparent oracles over the subfield `FS`.
+	///
+	/// The oracles must have a tower level of at most `FS::TOWER_LEVEL` and at least
+	/// `PackedType::<U, FS>::LOG_WIDTH` variables.
+	pub fn preprocess_transparents<F, FS>(
+		self,
+		oracles: &MultilinearOracleSet<F>,
+		ids: impl IntoIterator<Item = OracleId>,
+	) -> Result<Self, Error>
+	where
+		F: TowerField + ExtensionField<FS>,
+		FS: TowerField,
+		FW: ExtensionField<FS>,
+		U: PackScalar<FS> + Debug,
+	{
+		let Self {
+			transparents,
+			mut oracle_ids,
+		} = self;
+
+		let witnesses = ids
+			.into_iter()
+			.map(|id| -> Result<_, Error> {
+				let MultilinearPolyOracle::Transparent(_, transparent) = oracles.oracle(id) else {
+					bail!(Error::NotTransparent { id });
+				};
+				if transparent.binary_tower_level() > FS::TOWER_LEVEL {
+					bail!(Error::TowerLevelTooHigh {
+						id,
+						tower_level: transparent.binary_tower_level(),
+						max_tower_level: FS::TOWER_LEVEL,
+					});
+				}
+				let underliers = hypercube_underliers::<U, F, FS>(id, transparent.poly().as_ref())?;
+				Ok((id, underliers))
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+
+		oracle_ids.extend(witnesses.iter().map(|(id, _)| *id));
+		let transparents = transparents.update_owned::<FS, _>(witnesses)?;
+		Ok(Self {
+			transparents,
+			oracle_ids,
+		})
+	}
+
+	/// The IDs of the preprocessed oracles, in the order they were added.
+	pub fn oracle_ids(&self) -> &[OracleId] {
+		&self.oracle_ids
+	}
+
+	/// Returns a witness index containing the preprocessed oracles.
+	///
+	/// The returned index shares the materialized data with the key, so it is cheap to call once
+	/// per proof and extend with the statement-dependent witnesses.
+	pub fn witness_index<'a>(&self) -> MultilinearExtensionIndex<'a, U, FW> {
+		self.transparents.clone()
+	}
+}
+
+fn hypercube_underliers<U, F, FS>(
+	id: OracleId,
+	poly: &dyn MultivariatePoly<F>,
+) -> Result<Vec<U>, Error>
+where
+	U: UnderlierType + PackScalar<FS>,
+	F: Field + ExtensionField<FS>,
+	FS: Field,
+{
+	let n_vars = poly.n_vars();
+	let log_width = PackedType::<U, FS>::LOG_WIDTH;
+	if n_vars < log_width {
+		bail!(PolynomialError::PackedFieldNotFilled {
+			length: 1 << n_vars,
+			packed_width: 1 << log_width,
+		});
+	}
+
+	let evals = poly.hypercube_evals()?;
+	let mut underliers = vec![U::default(); 1 << (n_vars - log_width)];
+	PackedType::<U, FS>::from_underliers_ref_mut(&mut underliers)
+		.par_iter_mut()
+		.zip(evals.par_chunks(PackedType::<U, FS>::WIDTH))
+		.try_for_each(|(packed, evals)| -> Result<_, Error> {
+			for (j, &eval) in evals.iter().enumerate() {
+				let eval = eval.try_into().map_err(|_| Error::NotInSubfield { id })?;
+				packed.set(j, eval);
+			}
+			Ok(())
+		})?;
+	Ok(underliers)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::polynomial::{
+		transparent::{select_row::SelectRow, step_down::StepDown},
+		MultilinearExtension,
+	};
+	use assert_matches::assert_matches;
+	use binius_field::{BinaryField128b, BinaryField1b, PackedBinaryField128x1b};
+
+	type U = <PackedBinaryField128x1b as WithUnderlier>::Underlier;
+
+	#[test]
+	fn test_preprocessed_transparents_match_multilinear_extensions() {
+		let n_vars = 9;
+		let mut oracles = MultilinearOracleSet::<BinaryField128b>::new();
+		let step_down = StepDown::new(n_vars, 300).unwrap();
+		let select_row = SelectRow::new(n_vars, 17).unwrap();
+		let step_down_id = oracles.add_transparent(step_down.clone()).unwrap();
+		let select_row_id = oracles.add_transparent(select_row.clone()).unwrap();
+		let batch_id = oracles.add_committed_batch(n_vars, 0);
+		let committed_id = oracles.add_committed(batch_id);
+
+		let key = ProverKey::<U, BinaryField128b>::new()
+			.preprocess_transparents::<_, BinaryField1b>(&oracles, [step_down_id, select_row_id])
+			.unwrap();
+		assert_eq!(key.oracle_ids(), &[step_down_id, select_row_id]);
+
+		let expected: [MultilinearExtension<PackedBinaryField128x1b>; 2] = [
+			step_down.multilinear_extension().unwrap(),
+			select_row.multilinear_extension().unwrap(),
+		];
+		for _ in 0..2 {
+			let witness = key.witness_index();
+			for (id, expected) in [step_down_id, select_row_id].into_iter().zip(&expected) {
+				let mle = witness.get::<BinaryField1b>(id).unwrap();
+				assert_eq!(mle.evals(), expected.evals());
+			}
+			assert!(!witness.has(committed_id));
+		}
+
+		assert_matches!(
+			ProverKey::<U, BinaryField128b>::new()
+				.preprocess_transparents::<_, BinaryField1b>(&oracles, [committed_id]),
+			Err(Error::NotTransparent { id }) if id == committed_id
+		);
+	}
+}
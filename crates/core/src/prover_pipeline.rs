@@ -0,0 +1,261 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Proving as a typed pipeline of phases.
+//!
+//! An end-to-end prover runs a fixed sequence of phases, such as witness derivation, commitment,
+//! zerocheck, evalcheck and the opening of the commitments. A [`ProverPipeline`] expresses each
+//! phase as a function from the state handed off by the previous phase to the state handed to the
+//! next one, so the data each phase consumes and produces is explicit in its type. The pipeline
+//! records the start and duration of every phase in a [`PipelineReport`] and runs each phase in
+//! its own tracing span.
+//!
+//! Phases that do not depend on each other, like committing to a batch while the sumcheck of a
+//! previous batch runs, are overlapped with [`ProverPipeline::join`], which splits the state in
+//! two and runs both phases on the rayon thread pool. Phases that sample from the challenger must
+//! stay sequential, so only work that is independent of the transcript, like computing a
+//! commitment before it is observed, should be overlapped.
+
+use getset::{CopyGetters, Getters};
+use std::time::{Duration, Instant};
+use tracing::{debug_span, Span};
+
+/// Error type returned by pipeline phases.
+pub type PhaseError = Box<dyn std::error::Error + Send + Sync>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	#[error("phase {phase} failed: {source}")]
+	Phase {
+		phase: &'static str,
+		#[source]
+		source: PhaseError,
+	},
+}
+
+/// The timing of a single phase.
+#[derive(Debug, Clone, Copy, CopyGetters)]
+#[getset(get_copy = "pub")]
+pub struct PhaseTiming {
+	name: &'static str,
+	/// The time from the start of the pipeline to the start of the phase.
+	start: Duration,
+	elapsed: Duration,
+}
+
+impl PhaseTiming {
+	/// The time from the start of the pipeline to the end of the phase.
+	pub fn end(&self) -> Duration {
+		self.start + self.elapsed
+	}
+}
+
+/// The timings of the phases of a pipeline, in the order the phases were started.
+///
+/// The phases of a [`ProverPipeline::join`] are both recorded, and their intervals overlap.
+#[derive(Debug, Clone, Default, Getters)]
+#[getset(get = "pub")]
+pub struct PipelineReport {
+	phases: Vec<PhaseTiming>,
+}
+
+impl PipelineReport {
+	/// The timing of the phase named `name`, if it has run.
+	pub fn phase(&self, name: &str) -> Option<&PhaseTiming> {
+		self.phases.iter().find(|timing| timing.name == name)
+	}
+
+	/// The time from the start of the pipeline to the end of its last phase.
+	pub fn total(&self) -> Duration {
+		self.phases
+			.iter()
+			.map(PhaseTiming::end)
+			.max()
+			.unwrap_or_default()
+	}
+}
+
+/// A pipeline of proving phases, holding the state handed off by the last phase.
+#[derive(Debug)]
+pub struct ProverPipeline<T> {
+	state: T,
+	started: Instant,
+	report: PipelineReport,
+}
+
+impl<T> ProverPipeline<T> {
+	/// Starts a pipeline from the initial state.
+	pub fn new(state: T) -> Self {
+		Self {
+			state,
+			started: Instant::now(),
+			report: PipelineReport::default(),
+		}
+	}
+
+	/// Runs the phase `name` on the current state, handing its output to the next phase.
+	pub fn then<U>(
+		self,
+		name: &'static str,
+		phase: impl FnOnce(T) -> Result<U, PhaseError>,
+	) -> Result<ProverPipeline<U>, Error> {
+		let Self {
+			state,
+			started,
+			mut report,
+		} = self;
+		let (result, timing) = run_phase(started, &Span::current(), name, || phase(state));
+		report.phases.push(timing);
+		Ok(ProverPipeline {
+			state: result.map_err(|source| Error::Phase {
+				phase: name,
+				source,
+			})?,
+			started,
+			report,
+		})
+	}
+
+	/// Reshapes the state between phases without recording a phase.
+	pub fn map<U>(self, f: impl FnOnce(T) -> U) -> ProverPipeline<U> {
+		ProverPipeline {
+			state: f(self.state),
+			started: self.started,
+			report: self.report,
+		}
+	}
+
+	/// Ends the pipeline, returning the final state and the phase timings.
+	pub fn finish(self) -> (T, PipelineReport) {
+		(self.state, self.report)
+	}
+}
+
+impl<A: Send, B: Send> ProverPipeline<(A, B)> {
+	/// Runs two independent phases concurrently, each on its part of the state.
+	///
+	/// Both phases run to completion. If both fail, the error of the first phase is returned.
+	pub fn join<A2: Send, B2: Send>(
+		self,
+		name_a: &'static str,
+		phase_a: impl FnOnce(A) -> Result<A2, PhaseError> + Send,
+		name_b: &'static str,
+		phase_b: impl FnOnce(B) -> Result<B2, PhaseError> + Send,
+	) -> Result<ProverPipeline<(A2, B2)>, Error> {
+		let Self {
+			state: (state_a, state_b),
+			started,
+			mut report,
+		} = self;
+		// The phases may run on other threads, so their spans are attached to the current span
+		// explicitly.
+		let parent = Span::current();
+		let ((result_a, timing_a), (result_b, timing_b)) = rayon::join(
+			|| run_phase(started, &parent, name_a, || phase_a(state_a)),
+			|| run_phase(started, &parent, name_b, || phase_b(state_b)),
+		);
+		report.phases.push(timing_a);
+		report.phases.push(timing_b);
+
+		let state_a = result_a.map_err(|source| Error::Phase {
+			phase: name_a,
+			source,
+		})?;
+		let state_b = result_b.map_err(|source| Error::Phase {
+			phase: name_b,
+			source,
+		})?;
+		Ok(ProverPipeline {
+			state: (state_a, state_b),
+			started,
+			report,
+		})
+	}
+}
+
+fn run_phase<U>(
+	started: Instant,
+	parent: &Span,
+	name: &'static str,
+	phase: impl FnOnce() -> Result<U, PhaseError>,
+) -> (Result<U, PhaseError>, PhaseTiming) {
+	let _span = debug_span!(parent: parent, "prover_phase", name).entered();
+	let start = Instant::now();
+	let result = phase();
+	let timing = PhaseTiming {
+		name,
+		start: start.duration_since(started),
+		elapsed: start.elapsed(),
+	};
+	(result, timing)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use assert_matches::assert_matches;
+
+	#[test]
+	fn test_pipeline_hands_off_state() {
+		let (state, report) = ProverPipeline::new(3)
+			.then("double", |x| Ok(x * 2))
+			.unwrap()
+			.map(|x| (x, vec![x]))
+			.join(
+				"increment",
+				|x| Ok(x + 1),
+				"repeat",
+				|mut v| {
+					v.push(v[0]);
+					Ok(v)
+				},
+			)
+			.unwrap()
+			.then("sum", |(x, v)| Ok(x + v.iter().sum::<i32>()))
+			.unwrap()
+			.finish();
+		assert_eq!(state, 19);
+
+		let names = report
+			.phases()
+			.iter()
+			.map(|timing| timing.name())
+			.collect::<Vec<_>>();
+		assert_eq!(names, ["double", "increment", "repeat", "sum"]);
+		// The joined phases start after the previous phase and end before the next one.
+		for name in ["increment", "repeat"] {
+			let timing = report.phase(name).unwrap();
+			assert!(report.phase("double").unwrap().end() <= timing.start());
+			assert!(timing.end() <= report.phase("sum").unwrap().start());
+		}
+		assert_eq!(report.total(), report.phase("sum").unwrap().end());
+	}
+
+	#[test]
+	fn test_pipeline_attributes_errors_to_phases() {
+		let result = ProverPipeline::new(())
+			.then("commit", |_| Ok(()))
+			.unwrap()
+			.then::<()>("zerocheck", |_| Err("claim is false".into()));
+		assert_matches!(
+			result,
+			Err(Error::Phase {
+				phase: "zerocheck",
+				..
+			})
+		);
+
+		let result = ProverPipeline::new(((), ())).join(
+			"commit",
+			|_| Ok(()),
+			"sumcheck",
+			|_| Err::<(), _>("claim is false".into()),
+		);
+		assert_matches!(
+			result,
+			Err(Error::Phase {
+				phase: "sumcheck",
+				..
+			})
+		);
+	}
+}
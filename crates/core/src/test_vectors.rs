@@ -0,0 +1,418 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Machine-readable test vectors for validating third-party implementations.
+//!
+//! The vectors are JSON documents that describe binary tower field arithmetic and the Fiat-Shamir
+//! transcript of the default challenger. All field elements are written as hexadecimal strings
+//! of their canonical representation, where the element of tower level `ι` occupies the low
+//! `2^ι` bits and an extension element is the concatenation of its two subfield halves, low half
+//! first. The runners in this module check a document against this implementation; the vectors
+//! shipped in `test_vectors/tower_field.json` and `test_vectors/challenger.json` are checked in the
+//! crate's tests.
+//!
+//! The challenger vectors were computed with a model of the default challenger written
+//! independently of this crate, so they are known answers rather than a record of this
+//! implementation's output.
+//!
+//! This module is only available with the `test-vectors` feature, which pulls in `serde`.
+
+use crate::challenger::{new_hasher_challenger, CanObserve, CanSample, CanSampleBits};
+use binius_field::{
+	BinaryField128b, BinaryField16b, BinaryField1b, BinaryField2b, BinaryField32b, BinaryField4b,
+	BinaryField64b, BinaryField8b, ExtensionField, Field, PackedBinaryField128x1b,
+	PackedBinaryField16x8b, PackedBinaryField1x128b, PackedBinaryField2x64b,
+	PackedBinaryField32x4b, PackedBinaryField4x32b, PackedBinaryField64x2b, PackedBinaryField8x16b,
+	PackedField, TowerField,
+};
+use binius_hash::GroestlHasher;
+use binius_utils::bail;
+use bytemuck::Pod;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	#[error("invalid JSON: {0}")]
+	Json(#[from] serde_json::Error),
+	#[error("invalid hexadecimal value {0:?}")]
+	InvalidHex(String),
+	#[error("tower level {0} is not supported, expected at most 7")]
+	UnsupportedTowerLevel(usize),
+	#[error("value {value:?} does not fit in a field element of tower level {tower_level}")]
+	ValueOutOfRange { value: String, tower_level: usize },
+	#[error("expected {expected} scalars in {section} vector {index}, got {actual}")]
+	IncorrectNumberOfScalars {
+		section: &'static str,
+		index: usize,
+		expected: usize,
+		actual: usize,
+	},
+	#[error("{section} vector {index} does not match: expected {expected}, computed {computed}")]
+	Mismatch {
+		section: &'static str,
+		index: usize,
+		expected: String,
+		computed: String,
+	},
+}
+
+/// Test vectors for binary tower field arithmetic.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TowerFieldVectors {
+	pub multiplication: Vec<MultiplicationVector>,
+	pub inversion: Vec<InversionVector>,
+	pub packing: Vec<PackingVector>,
+}
+
+/// The product of two elements of the tower field of the given level.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MultiplicationVector {
+	pub tower_level: usize,
+	pub a: String,
+	pub b: String,
+	pub product: String,
+}
+
+/// The multiplicative inverse of an element of the tower field of the given level.
+///
+/// The inverse of zero is given as zero.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InversionVector {
+	pub tower_level: usize,
+	pub a: String,
+	pub inverse: String,
+}
+
+/// The 128-bit packed representation of `128 / 2^tower_level` scalars.
+///
+/// Scalar `i` occupies bits `i * 2^tower_level` to `(i + 1) * 2^tower_level` of the packed value,
+/// which is also the layout of an element of [`BinaryField128b`] over the scalar field.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackingVector {
+	pub tower_level: usize,
+	pub scalars: Vec<String>,
+	pub packed: String,
+}
+
+/// Test vectors for the default challenger, a Grøstl-256 hash challenger over [`BinaryField8b`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChallengerVectors {
+	pub transcripts: Vec<Vec<ChallengerOp>>,
+}
+
+/// An operation on the challenger, with the expected result for sampling operations.
+///
+/// Each transcript starts from a freshly constructed challenger. Observed and sampled values are
+/// elements of [`BinaryField128b`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChallengerOp {
+	Observe(Vec<String>),
+	Sample(String),
+	SampleBits { bits: usize, value: usize },
+}
+
+impl TowerFieldVectors {
+	pub fn from_json(json: &str) -> Result<Self, Error> {
+		Ok(serde_json::from_str(json)?)
+	}
+
+	pub fn to_json(&self) -> Result<String, Error> {
+		Ok(serde_json::to_string_pretty(self)?)
+	}
+}
+
+impl ChallengerVectors {
+	pub fn from_json(json: &str) -> Result<Self, Error> {
+		Ok(serde_json::from_str(json)?)
+	}
+
+	pub fn to_json(&self) -> Result<String, Error> {
+		Ok(serde_json::to_string_pretty(self)?)
+	}
+}
+
+macro_rules! dispatch_tower_level {
+	($tower_level:expr, $check:ident($($arg:expr),*)) => {
+		match $tower_level {
+			0 => $check::<BinaryField1b>($($arg),*),
+			1 => $check::<BinaryField2b>($($arg),*),
+			2 => $check::<BinaryField4b>($($arg),*),
+			3 => $check::<BinaryField8b>($($arg),*),
+			4 => $check::<BinaryField16b>($($arg),*),
+			5 => $check::<BinaryField32b>($($arg),*),
+			6 => $check::<BinaryField64b>($($arg),*),
+			7 => $check::<BinaryField128b>($($arg),*),
+			level => Err(Error::UnsupportedTowerLevel(level)),
+		}
+	};
+}
+
+/// Checks every tower field test vector against this implementation.
+pub fn run_tower_field_vectors(vectors: &TowerFieldVectors) -> Result<(), Error> {
+	for (index, vector) in vectors.multiplication.iter().enumerate() {
+		let computed = dispatch_tower_level!(vector.tower_level, check_multiplication(vector))?;
+		check_eq("multiplication", index, &vector.product, computed)?;
+	}
+	for (index, vector) in vectors.inversion.iter().enumerate() {
+		let computed = dispatch_tower_level!(vector.tower_level, check_inversion(vector))?;
+		check_eq("inversion", index, &vector.inverse, computed)?;
+	}
+	for (index, vector) in vectors.packing.iter().enumerate() {
+		let computed = match vector.tower_level {
+			0 => check_packing::<PackedBinaryField128x1b>(index, vector),
+			1 => check_packing::<PackedBinaryField64x2b>(index, vector),
+			2 => check_packing::<PackedBinaryField32x4b>(index, vector),
+			3 => check_packing::<PackedBinaryField16x8b>(index, vector),
+			4 => check_packing::<PackedBinaryField8x16b>(index, vector),
+			5 => check_packing::<PackedBinaryField4x32b>(index, vector),
+			6 => check_packing::<PackedBinaryField2x64b>(index, vector),
+			7 => check_packing::<PackedBinaryField1x128b>(index, vector),
+			level => Err(Error::UnsupportedTowerLevel(level)),
+		}?;
+		check_eq("packing", index, &vector.packed, computed)?;
+	}
+	Ok(())
+}
+
+/// Replays every challenger transcript and checks the sampled values.
+pub fn run_challenger_vectors(vectors: &ChallengerVectors) -> Result<(), Error> {
+	for (index, transcript) in vectors.transcripts.iter().enumerate() {
+		let mut challenger = new_hasher_challenger::<_, GroestlHasher<BinaryField8b>>();
+		for op in transcript {
+			match op {
+				ChallengerOp::Observe(values) => {
+					for value in values {
+						challenger.observe(parse_field::<BinaryField128b>(value)?);
+					}
+				}
+				ChallengerOp::Sample(expected) => {
+					let sampled: BinaryField128b = challenger.sample();
+					check_eq("challenger", index, expected, format_field(sampled))?;
+				}
+				ChallengerOp::SampleBits { bits, value } => {
+					let sampled = challenger.sample_bits(*bits);
+					if sampled != *value {
+						bail!(Error::Mismatch {
+							section: "challenger",
+							index,
+							expected: value.to_string(),
+							computed: sampled.to_string(),
+						});
+					}
+				}
+			}
+		}
+	}
+	Ok(())
+}
+
+/// Generates challenger test vectors by running the given transcripts with this implementation.
+///
+/// The expected values of the sampling operations in `transcripts` are ignored and replaced with
+/// the values sampled by the default challenger.
+pub fn generate_challenger_vectors(
+	transcripts: impl IntoIterator<Item = Vec<ChallengerOp>>,
+) -> Result<ChallengerVectors, Error> {
+	let transcripts = transcripts
+		.into_iter()
+		.map(|mut transcript| {
+			let mut challenger = new_hasher_challenger::<_, GroestlHasher<BinaryField8b>>();
+			for op in transcript.iter_mut() {
+				match op {
+					ChallengerOp::Observe(values) => {
+						for value in values.iter() {
+							challenger.observe(parse_field::<BinaryField128b>(value)?);
+						}
+					}
+					ChallengerOp::Sample(sampled) => {
+						*sampled = format_field::<BinaryField128b>(challenger.sample());
+					}
+					ChallengerOp::SampleBits { bits, value } => {
+						*value = challenger.sample_bits(*bits);
+					}
+				}
+			}
+			Ok(transcript)
+		})
+		.collect::<Result<_, Error>>()?;
+	Ok(ChallengerVectors { transcripts })
+}
+
+fn check_multiplication<F: TowerField>(vector: &MultiplicationVector) -> Result<String, Error> {
+	let a = parse_field::<F>(&vector.a)?;
+	let b = parse_field::<F>(&vector.b)?;
+	Ok(format_field(a * b))
+}
+
+fn check_inversion<F: TowerField>(vector: &InversionVector) -> Result<String, Error> {
+	let a = parse_field::<F>(&vector.a)?;
+	Ok(format_field(a.invert().unwrap_or(F::ZERO)))
+}
+
+fn check_packing<P>(index: usize, vector: &PackingVector) -> Result<String, Error>
+where
+	P: PackedField<Scalar: TowerField> + Pod,
+{
+	if vector.scalars.len() != P::WIDTH {
+		bail!(Error::IncorrectNumberOfScalars {
+			section: "packing",
+			index,
+			expected: P::WIDTH,
+			actual: vector.scalars.len(),
+		});
+	}
+	let scalars = vector
+		.scalars
+		.iter()
+		.map(|scalar| parse_field::<P::Scalar>(scalar))
+		.collect::<Result<Vec<_>, _>>()?;
+	let packed = P::from_fn(|i| scalars[i]);
+	let bytes: [u8; 16] = bytemuck::bytes_of(&packed)
+		.try_into()
+		.expect("packed fields in the test vectors are 128 bits wide");
+	Ok(format_field(BinaryField128b::new(u128::from_le_bytes(bytes))))
+}
+
+fn check_eq(
+	section: &'static str,
+	index: usize,
+	expected: &str,
+	computed: String,
+) -> Result<(), Error> {
+	// Compare numerically so that vectors may use any amount of zero padding and either case.
+	if parse_hex(expected)? != parse_hex(&computed)? {
+		bail!(Error::Mismatch {
+			section,
+			index,
+			expected: expected.to_string(),
+			computed,
+		});
+	}
+	Ok(())
+}
+
+fn parse_hex(value: &str) -> Result<u128, Error> {
+	value
+		.strip_prefix("0x")
+		.and_then(|digits| u128::from_str_radix(digits, 16).ok())
+		.ok_or_else(|| Error::InvalidHex(value.to_string()))
+}
+
+fn parse_field<F: TowerField>(value: &str) -> Result<F, Error> {
+	let bits = parse_hex(value)?;
+	if F::N_BITS < 128 && bits >> F::N_BITS != 0 {
+		bail!(Error::ValueOutOfRange {
+			value: value.to_string(),
+			tower_level: F::TOWER_LEVEL,
+		});
+	}
+	let bases = (0..F::N_BITS)
+		.map(|i| BinaryField1b::from(((bits >> i) & 1) as u8))
+		.collect::<Vec<_>>();
+	Ok(ExtensionField::<BinaryField1b>::from_bases(&bases)
+		.expect("the number of bases is the extension degree"))
+}
+
+fn format_field<F: TowerField>(value: F) -> String {
+	let bits = ExtensionField::<BinaryField1b>::iter_bases(&value)
+		.enumerate()
+		.fold(0u128, |acc, (i, bit)| acc | (u128::from(bit != BinaryField1b::ZERO) << i));
+	format!("{:#0width$x}", bits, width = 2 + F::N_BITS.div_ceil(4))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use assert_matches::assert_matches;
+
+	const TOWER_FIELD_VECTORS: &str = include_str!("../test_vectors/tower_field.json");
+	const CHALLENGER_VECTORS: &str = include_str!("../test_vectors/challenger.json");
+
+	#[test]
+	fn test_tower_field_vectors() {
+		let vectors = TowerFieldVectors::from_json(TOWER_FIELD_VECTORS).unwrap();
+		assert!(!vectors.multiplication.is_empty());
+		assert!(!vectors.inversion.is_empty());
+		assert!(!vectors.packing.is_empty());
+		run_tower_field_vectors(&vectors).unwrap();
+
+		let roundtrip = TowerFieldVectors::from_json(&vectors.to_json().unwrap()).unwrap();
+		assert_eq!(roundtrip, vectors);
+	}
+
+	#[test]
+	fn test_tower_field_vectors_detect_mismatch() {
+		let mut vectors = TowerFieldVectors::from_json(TOWER_FIELD_VECTORS).unwrap();
+		let vector = vectors
+			.multiplication
+			.iter_mut()
+			.find(|vector| vector.tower_level == 7)
+			.unwrap();
+		let product = parse_hex(&vector.product).unwrap();
+		vector.product = format!("{:#x}", product ^ 1);
+		assert_matches!(
+			run_tower_field_vectors(&vectors),
+			Err(Error::Mismatch {
+				section: "multiplication",
+				..
+			})
+		);
+
+		let vectors = TowerFieldVectors {
+			multiplication: vec![MultiplicationVector {
+				tower_level: 3,
+				a: "0x100".to_string(),
+				b: "0x1".to_string(),
+				product: "0x100".to_string(),
+			}],
+			..Default::default()
+		};
+		assert_matches!(
+			run_tower_field_vectors(&vectors),
+			Err(Error::ValueOutOfRange { tower_level: 3, .. })
+		);
+	}
+
+	#[test]
+	fn test_challenger_vectors() {
+		let vectors = ChallengerVectors::from_json(CHALLENGER_VECTORS).unwrap();
+		assert!(!vectors.transcripts.is_empty());
+		run_challenger_vectors(&vectors).unwrap();
+
+		// Regenerating the vectors from their transcripts reproduces the known answers.
+		let generated = generate_challenger_vectors(vectors.transcripts.clone()).unwrap();
+		assert_eq!(generated, vectors);
+	}
+
+	#[test]
+	fn test_challenger_vectors_roundtrip() {
+		let transcripts = vec![
+			vec![
+				ChallengerOp::Sample(String::new()),
+				ChallengerOp::SampleBits { bits: 10, value: 0 },
+			],
+			vec![
+				ChallengerOp::Observe(vec!["0x1".to_string(), format!("{:#x}", u128::MAX)]),
+				ChallengerOp::Sample(String::new()),
+				ChallengerOp::Sample(String::new()),
+				ChallengerOp::Observe(vec!["0xdeadbeef".to_string()]),
+				ChallengerOp::SampleBits { bits: 20, value: 0 },
+				ChallengerOp::Sample(String::new()),
+			],
+		];
+		let vectors = generate_challenger_vectors(transcripts).unwrap();
+		let vectors = ChallengerVectors::from_json(&vectors.to_json().unwrap()).unwrap();
+		run_challenger_vectors(&vectors).unwrap();
+
+		let mut corrupted = vectors.clone();
+		corrupted.transcripts[1][0] = ChallengerOp::Observe(vec!["0x2".to_string()]);
+		assert_matches!(
+			run_challenger_vectors(&corrupted),
+			Err(Error::Mismatch {
+				section: "challenger",
+				index: 1,
+				..
+			})
+		);
+	}
+}
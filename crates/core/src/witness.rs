@@ -3,27 +3,34 @@
 use crate::{
 	oracle::OracleId,
 	polynomial::{
-		util::PackingDeref, Error as PolynomialError, MultilinearExtension,
+		util::PackingDeref, ConstantValueCached, Error as PolynomialError, MultilinearExtension,
 		MultilinearExtensionBorrowed, MultilinearPoly,
 	},
 };
 use binius_field::{
 	as_packed_field::{PackScalar, PackedType},
+	packed::{get_packed_slice, set_packed_slice},
 	underlier::{UnderlierType, WithUnderlier},
-	ExtensionField, Field, TowerField,
+	BinaryField1b, ExtensionField, Field, PackedField, TowerField,
 };
 use binius_utils::bail;
-use std::{fmt::Debug, sync::Arc};
+use std::{
+	collections::{hash_map::DefaultHasher, HashMap, HashSet},
+	fmt::Debug,
+	hash::{Hash, Hasher},
+	ops::{Deref, Range},
+	sync::Arc,
+};
 
 pub type MultilinearWitness<'a, P> = Arc<dyn MultilinearPoly<P> + Send + Sync + 'a>;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct MultilinearExtensionBacking<'a, U: UnderlierType> {
-	underliers: ArcOrRef<'a, [U]>,
+	underliers: ArcOrRef<'a, U>,
 	tower_level: usize,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct MultilinearExtensionIndexEntry<'a, U: UnderlierType, F>
 where
 	U: UnderlierType + PackScalar<F>,
@@ -39,13 +46,18 @@ where
 /// unique, sequential oracle IDs. The caller can get the [`MultilinearExtension`] defined natively
 /// over a subfield. This is possible because the [`MultilinearExtensionIndex::get`] method is
 /// generic over the subfield type and the struct itself only stores the underlying data.
-#[derive(Default, Debug)]
+///
+/// When deduplication is enabled with [`MultilinearExtensionIndex::with_deduplication`], owned
+/// witnesses with identical contents share a single allocation. This is useful when the same
+/// constant table is registered under several oracle IDs.
+#[derive(Default, Debug, Clone)]
 pub struct MultilinearExtensionIndex<'a, U: UnderlierType, FW>
 where
 	U: UnderlierType + PackScalar<FW>,
 	FW: Field,
 {
 	entries: Vec<Option<MultilinearExtensionIndexEntry<'a, U, FW>>>,
+	dedup_cache: Option<HashMap<u64, Vec<Arc<[U]>>>>,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -60,6 +72,14 @@ pub enum Error {
 		oracle_level: usize,
 		field_level: usize,
 	},
+	#[error("bit {bit} is out of range for the {n_bits}-bit witness of oracle {id}")]
+	BitOutOfRange {
+		id: OracleId,
+		bit: usize,
+		n_bits: usize,
+	},
+	#[error("oracle {id} is not in the witness namespace {namespace:?}")]
+	OracleOutsideNamespace { id: OracleId, namespace: String },
 	#[error("polynomial error: {0}")]
 	Polynomial(#[from] PolynomialError),
 }
@@ -73,6 +93,22 @@ where
 		Self::default()
 	}
 
+	/// Enables content-hash deduplication of witnesses added with
+	/// [`MultilinearExtensionIndex::update_owned`].
+	pub fn with_deduplication(mut self) -> Self {
+		self.dedup_cache.get_or_insert_with(HashMap::new);
+		self
+	}
+
+	/// Number of distinct owned allocations tracked by the deduplication cache.
+	///
+	/// Returns `None` if deduplication is disabled.
+	pub fn dedup_allocation_count(&self) -> Option<usize> {
+		self.dedup_cache
+			.as_ref()
+			.map(|cache| cache.values().map(Vec::len).sum())
+	}
+
 	pub fn get<FS>(
 		&self,
 		id: OracleId,
@@ -128,8 +164,155 @@ where
 		self.entries.get(id).map_or(false, Option::is_some)
 	}
 
+	/// Opens a view of the index restricted to the oracles of one gadget.
+	///
+	/// See [`WitnessNamespace`].
+	pub fn namespace<'i>(
+		&'i mut self,
+		name: impl Into<String>,
+		ids: impl IntoIterator<Item = OracleId>,
+	) -> WitnessNamespace<'i, 'a, U, FW> {
+		WitnessNamespace {
+			index: self,
+			name: name.into(),
+			ids: ids.into_iter().collect(),
+		}
+	}
+
+	/// Returns the packed underliers backing the witness for the given oracle id, together with
+	/// the tower level of its scalars and its number of variables.
+	pub(crate) fn backing_underliers(&self, id: OracleId) -> Result<(&[U], usize, usize), Error> {
+		let entry = self
+			.entries
+			.get(id)
+			.ok_or(Error::MissingWitness { id })?
+			.as_ref()
+			.ok_or(Error::MissingWitness { id })?;
+		let backing = entry
+			.backing
+			.as_ref()
+			.ok_or(Error::NoExplicitBackingMultilinearExtension { id })?;
+		Ok((backing.underliers.as_ref(), backing.tower_level, entry.type_erased.n_vars()))
+	}
+
+	/// Returns a copy of the index in which one bit of the witness for the given oracle is flipped.
+	///
+	/// Bit `bit` is coordinate `bit % FS::DEGREE` of the scalar at hypercube vertex
+	/// `bit / FS::DEGREE`, in the tower basis over $\mathbb{F}_2$. All other witnesses share their
+	/// storage with `self`. This is intended for soundness testing, see [`crate::witness_fuzz`].
+	pub fn with_flipped_bit<FS>(&self, id: OracleId, bit: usize) -> Result<Self, Error>
+	where
+		FS: TowerField,
+		FW: ExtensionField<FS>,
+		U: PackScalar<FS> + Debug,
+	{
+		let (underliers, tower_level, n_vars) = self.backing_underliers(id)?;
+		if tower_level != FS::TOWER_LEVEL {
+			bail!(Error::OracleTowerHeightMismatch {
+				oracle_id: id,
+				oracle_level: tower_level,
+				field_level: FS::TOWER_LEVEL,
+			});
+		}
+		let n_bits = FS::DEGREE << n_vars;
+		if bit >= n_bits {
+			bail!(Error::BitOutOfRange { id, bit, n_bits });
+		}
+
+		let mut underliers = underliers.to_vec();
+		let packed = PackedType::<U, FS>::from_underliers_ref_mut(&mut underliers);
+		let vertex = bit / FS::DEGREE;
+		let basis = <FS as ExtensionField<BinaryField1b>>::basis(bit % FS::DEGREE)
+			.expect("bit % FS::DEGREE is less than the extension degree");
+		set_packed_slice(packed, vertex, get_packed_slice(packed, vertex) + basis);
+
+		let mut perturbed = self.clone();
+		perturbed.insert_owned::<FS, _>([(id, underliers)])?;
+		Ok(perturbed)
+	}
+
+	/// Removes the witnesses for the given oracle IDs from the index.
+	///
+	/// Returns the owned backing buffers of the removed witnesses that are no longer referenced
+	/// anywhere else. These can be reused as scratch space by later phases of the pipeline, for
+	/// example by obtaining mutable access with [`Arc::get_mut`]. Buffers still referenced by other
+	/// witnesses or by outstanding [`MultilinearWitness`] handles are freed once the last reference
+	/// is dropped.
+	pub fn release(&mut self, ids: impl IntoIterator<Item = OracleId>) -> Vec<Arc<[U]>> {
+		let mut released = Vec::new();
+		for id in ids {
+			let Some(entry) = self.entries.get_mut(id).and_then(Option::take) else {
+				continue;
+			};
+			let MultilinearExtensionIndexEntry {
+				type_erased,
+				backing,
+			} = entry;
+			// The type-erased multilinear holds a reference to the backing buffer, so it must be
+			// dropped before checking whether the buffer is uniquely owned.
+			drop(type_erased);
+
+			if let Some(MultilinearExtensionBacking {
+				underliers: ArcOrRef::Arc(underliers) | ArcOrRef::ArcRange(underliers, _),
+				..
+			}) = backing
+			{
+				released.push(underliers);
+			}
+		}
+
+		// Several removed witnesses may share one buffer when deduplication is enabled.
+		let mut unique_released = Vec::<Arc<[U]>>::with_capacity(released.len());
+		for underliers in released {
+			if !unique_released
+				.iter()
+				.any(|existing| Arc::ptr_eq(existing, &underliers))
+			{
+				unique_released.push(underliers);
+			}
+		}
+		let mut released = unique_released;
+
+		// Stop tracking buffers for deduplication once the cache holds the only other reference.
+		if let Some(cache) = self.dedup_cache.as_mut() {
+			for bucket in cache.values_mut() {
+				bucket.retain(|cached| {
+					!released.iter().any(|underliers| {
+						Arc::ptr_eq(cached, underliers) && Arc::strong_count(underliers) == 2
+					})
+				});
+			}
+			cache.retain(|_, bucket| !bucket.is_empty());
+		}
+		released.retain(|underliers| Arc::strong_count(underliers) == 1);
+		released
+	}
+
+	/// Transitions the index to the next proving phase, keeping only the witnesses in `retain`.
+	///
+	/// This is intended to be called between pipeline phases, e.g. after the commit phase, so that
+	/// witness data which is no longer needed is released before peak memory is reached. See
+	/// [`Self::release`] for the meaning of the returned buffers.
+	pub fn transition_phase(
+		&mut self,
+		retain: impl IntoIterator<Item = OracleId>,
+	) -> Vec<Arc<[U]>> {
+		let mut keep = vec![false; self.entries.len()];
+		for id in retain {
+			if let Some(keep_id) = keep.get_mut(id) {
+				*keep_id = true;
+			}
+		}
+		let release_ids = keep
+			.into_iter()
+			.enumerate()
+			.filter_map(|(id, keep_id)| (!keep_id).then_some(id))
+			.collect::<Vec<_>>();
+		self.release(release_ids)
+	}
+
 	pub fn update_owned<FS, Data>(
-		self,
+		mut self,
 		witnesses: impl IntoIterator<Item = (OracleId, Data)>,
 	) -> Result<MultilinearExtensionIndex<'a, U, FW>, Error>
 	where
@@ -138,13 +321,30 @@ where
 		U: PackScalar<FS> + Debug,
 		Data: Into<Arc<[U]>>,
 	{
-		let MultilinearExtensionIndex { mut entries } = self;
+		self.insert_owned::<FS, _>(witnesses)?;
+		Ok(self)
+	}
+
+	fn insert_owned<FS, Data>(
+		&mut self,
+		witnesses: impl IntoIterator<Item = (OracleId, Data)>,
+	) -> Result<(), Error>
+	where
+		FS: TowerField,
+		FW: ExtensionField<FS>,
+		U: PackScalar<FS> + Debug,
+		Data: Into<Arc<[U]>>,
+	{
 		for (id, witness) in witnesses {
-			if id >= entries.len() {
-				entries.resize_with(id + 1, || None);
+			if id >= self.entries.len() {
+				self.entries.resize_with(id + 1, || None);
+			}
+
+			let mut witness = witness.into();
+			if let Some(cache) = self.dedup_cache.as_mut() {
+				witness = dedup_underliers(cache, witness);
 			}
 
-			let witness = witness.into();
 			let mle = MultilinearExtension::<_, PackingDeref<U, FS, _>>::from_underliers(
 				witness.clone(),
 			)?;
@@ -152,12 +352,48 @@ where
 				underliers: ArcOrRef::Arc(witness),
 				tower_level: FS::TOWER_LEVEL,
 			};
-			entries[id] = Some(MultilinearExtensionIndexEntry {
-				type_erased: mle.specialize_arc_dyn(),
+			self.entries[id] = Some(MultilinearExtensionIndexEntry {
+				type_erased: type_erase(mle),
 				backing: Some(backing),
 			});
 		}
-		Ok(MultilinearExtensionIndex { entries })
+		Ok(())
+	}
+
+	/// Adds witnesses that are sub-ranges of one shared buffer of underliers.
+	///
+	/// Each witness is a view of `underliers[range]`, so no data is copied. The buffer is freed
+	/// once all of these witnesses have been released.
+	pub(crate) fn update_owned_ranges<FS>(
+		mut self,
+		underliers: impl Into<Arc<[U]>>,
+		witnesses: impl IntoIterator<Item = (OracleId, Range<usize>)>,
+	) -> Result<MultilinearExtensionIndex<'a, U, FW>, Error>
+	where
+		FS: TowerField,
+		FW: ExtensionField<FS>,
+		U: PackScalar<FS> + Debug,
+	{
+		let underliers = underliers.into();
+		for (id, range) in witnesses {
+			if id >= self.entries.len() {
+				self.entries.resize_with(id + 1, || None);
+			}
+
+			let witness = ArcOrRef::ArcRange(underliers.clone(), range);
+			let mle = MultilinearExtension::<_, PackingDeref<U, FS, _>>::from_underliers(
+				witness.clone(),
+			)?;
+			let backing = MultilinearExtensionBacking {
+				underliers: witness,
+				tower_level: FS::TOWER_LEVEL,
+			};
+			self.entries[id] = Some(MultilinearExtensionIndexEntry {
+				type_erased: type_erase(mle),
+				backing: Some(backing),
+			});
+		}
+		Ok(self)
 	}
 
 	pub fn update_borrowed<'new, FS>(
@@ -170,7 +406,10 @@ where
 		FW: ExtensionField<FS>,
 		U: PackScalar<FS>,
 	{
-		let MultilinearExtensionIndex { mut entries } = self;
+		let MultilinearExtensionIndex {
+			mut entries,
+			dedup_cache,
+		} = self;
 		for (id, witness) in witnesses {
 			if id >= entries.len() {
 				entries.resize_with(id + 1, || None);
@@ -184,11 +423,14 @@ where
 				tower_level: FS::TOWER_LEVEL,
 			};
 			entries[id] = Some(MultilinearExtensionIndexEntry {
-				type_erased: mle.specialize_arc_dyn(),
+				type_erased: type_erase(mle),
 				backing: Some(backing),
 			});
 		}
-		Ok(MultilinearExtensionIndex { entries })
+		Ok(MultilinearExtensionIndex {
+			entries,
+			dedup_cache,
+		})
 	}
 
 	pub fn update_multilin_poly(
@@ -201,13 +443,39 @@ where
 			}
 
 			self.entries[id] = Some(MultilinearExtensionIndexEntry {
-				type_erased: witness,
+				type_erased: Arc::new(ConstantValueCached::<PackedType<U, FW>, _>::new(witness)),
 				backing: None,
 			});
 		}
 		Ok(())
 	}
 
+	/// Registers the witness of each source oracle under another oracle ID, sharing the data.
+	///
+	/// This is the witness of oracles added with
+	/// [`crate::oracle::MultilinearOracleSet::add_committed_shared`], whose data is that of their
+	/// source oracle. The entries share the same allocation, or the same borrowed slice, so the
+	/// data is not copied. Sharing an oracle whose witness is later replaced does not update the
+	/// oracles that share it.
+	pub fn update_shared(
+		&mut self,
+		ids: impl IntoIterator<Item = (OracleId, OracleId)>,
+	) -> Result<(), Error> {
+		for (id, source_id) in ids {
+			let entry = self
+				.entries
+				.get(source_id)
+				.cloned()
+				.flatten()
+				.ok_or(Error::MissingWitness { id: source_id })?;
+			if id >= self.entries.len() {
+				self.entries.resize_with(id + 1, || None);
+			}
+			self.entries[id] = Some(entry);
+		}
+		Ok(())
+	}
+
 	pub fn update_packed<'new, FS>(
 		self,
 		witnesses: impl IntoIterator<Item = (OracleId, &'new [PackedType<U, FS>])>,
@@ -226,17 +494,357 @@ where
 	}
 }
 
+/// A view of a [`MultilinearExtensionIndex`] that can only read and write the witnesses of a fixed
+/// set of oracles.
+///
+/// A system assembled from many independently developed gadgets populates a single witness index.
+/// Handing each gadget a namespace over its own oracle IDs, rather than the whole index, turns an
+/// oracle ID mixed up between gadgets into an [`Error::OracleOutsideNamespace`] error instead of a
+/// silently overwritten or misread witness.
+#[derive(Debug)]
+pub struct WitnessNamespace<'i, 'a, U, FW>
+where
+	U: UnderlierType + PackScalar<FW>,
+	FW: Field,
+{
+	index: &'i mut MultilinearExtensionIndex<'a, U, FW>,
+	name: String,
+	ids: HashSet<OracleId>,
+}
+
+impl<'i, 'a, U, FW> WitnessNamespace<'i, 'a, U, FW>
+where
+	U: UnderlierType + PackScalar<FW>,
+	FW: Field,
+{
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// Whether the oracle id belongs to the namespace.
+	pub fn contains(&self, id: OracleId) -> bool {
+		self.ids.contains(&id)
+	}
+
+	/// Whether the namespace has data for the given oracle id.
+	pub fn has(&self, id: OracleId) -> bool {
+		self.contains(id) && self.index.has(id)
+	}
+
+	pub fn get<FS>(
+		&self,
+		id: OracleId,
+	) -> Result<MultilinearExtensionBorrowed<PackedType<U, FS>>, Error>
+	where
+		FS: TowerField,
+		FW: ExtensionField<FS>,
+		U: PackScalar<FS>,
+	{
+		self.check_id(id)?;
+		self.index.get::<FS>(id)
+	}
+
+	pub fn get_multilin_poly(
+		&self,
+		id: OracleId,
+	) -> Result<MultilinearWitness<'a, PackedType<U, FW>>, Error> {
+		self.check_id(id)?;
+		self.index.get_multilin_poly(id)
+	}
+
+	/// Adds owned witnesses to the index.
+	///
+	/// No witness is added if any oracle id is outside the namespace.
+	pub fn update_owned<FS, Data>(
+		&mut self,
+		witnesses: impl IntoIterator<Item = (OracleId, Data)>,
+	) -> Result<(), Error>
+	where
+		FS: TowerField,
+		FW: ExtensionField<FS>,
+		U: PackScalar<FS> + Debug,
+		Data: Into<Arc<[U]>>,
+	{
+		let witnesses = witnesses.into_iter().collect::<Vec<_>>();
+		for (id, _) in witnesses.iter() {
+			self.check_id(*id)?;
+		}
+		self.index.insert_owned::<FS, _>(witnesses)
+	}
+
+	/// Adds type-erased witnesses to the index.
+	///
+	/// No witness is added if any oracle id is outside the namespace.
+	pub fn update_multilin_poly(
+		&mut self,
+		witnesses: impl IntoIterator<Item = (OracleId, MultilinearWitness<'a, PackedType<U, FW>>)>,
+	) -> Result<(), Error> {
+		let witnesses = witnesses.into_iter().collect::<Vec<_>>();
+		for (id, _) in witnesses.iter() {
+			self.check_id(*id)?;
+		}
+		self.index.update_multilin_poly(witnesses)
+	}
+
+	fn check_id(&self, id: OracleId) -> Result<(), Error> {
+		if !self.contains(id) {
+			bail!(Error::OracleOutsideNamespace {
+				id,
+				namespace: self.name.clone(),
+			});
+		}
+		Ok(())
+	}
+}
+
+/// Type-erases a witness, caching whether it is constant for all the provers that share it.
+fn type_erase<'a, P, PE, Data>(mle: MultilinearExtension<P, Data>) -> MultilinearWitness<'a, PE>
+where
+	P: PackedField + Debug,
+	PE: PackedField,
+	PE::Scalar: ExtensionField<P::Scalar>,
+	Data: Deref<Target = [P]> + Send + Sync + Debug + 'a,
+{
+	Arc::new(ConstantValueCached::<PE, _>::new(mle.specialize::<PE>()))
+}
+
+/// Returns a previously cached allocation with the same contents as `underliers`, or caches and
+/// returns `underliers` itself if no such allocation exists.
+fn dedup_underliers<U: UnderlierType>(
+	cache: &mut HashMap<u64, Vec<Arc<[U]>>>,
+	underliers: Arc<[U]>,
+) -> Arc<[U]> {
+	let bytes = bytemuck::cast_slice::<U, u8>(&underliers);
+	let mut hasher = DefaultHasher::new();
+	bytes.hash(&mut hasher);
+
+	let bucket = cache.entry(hasher.finish()).or_default();
+	if let Some(existing) = bucket
+		.iter()
+		.find(|existing| bytemuck::cast_slice::<U, u8>(existing) == bytes)
+	{
+		return existing.clone();
+	}
+
+	bucket.push(underliers.clone());
+	underliers
+}
+
 #[derive(Debug)]
-enum ArcOrRef<'a, T: ?Sized> {
-	Arc(Arc<T>),
-	Ref(&'a T),
+enum ArcOrRef<'a, T> {
+	Arc(Arc<[T]>),
+	/// A sub-range of a buffer that may be shared with other witnesses.
+	ArcRange(Arc<[T]>, Range<usize>),
+	Ref(&'a [T]),
 }
 
-impl<'a, T: ?Sized> AsRef<T> for ArcOrRef<'a, T> {
-	fn as_ref(&self) -> &T {
+impl<'a, T> Clone for ArcOrRef<'a, T> {
+	fn clone(&self) -> Self {
+		match self {
+			Self::Arc(owned) => Self::Arc(owned.clone()),
+			Self::ArcRange(owned, range) => Self::ArcRange(owned.clone(), range.clone()),
+			Self::Ref(borrowed) => Self::Ref(borrowed),
+		}
+	}
+}
+
+impl<'a, T> Deref for ArcOrRef<'a, T> {
+	type Target = [T];
+
+	fn deref(&self) -> &[T] {
 		match self {
 			Self::Arc(owned) => owned,
+			Self::ArcRange(owned, range) => &owned[range.clone()],
 			Self::Ref(borrowed) => borrowed,
 		}
 	}
 }
+
+impl<'a, T> AsRef<[T]> for ArcOrRef<'a, T> {
+	fn as_ref(&self) -> &[T] {
+		self
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use binius_field::{
+		underlier::Random, BinaryField128b, BinaryField8b, PackedBinaryField1x128b,
+	};
+	use rand::{rngs::StdRng, SeedableRng};
+
+	type U = <PackedBinaryField1x128b as WithUnderlier>::Underlier;
+
+	fn backing_ptr(
+		index: &MultilinearExtensionIndex<U, BinaryField128b>,
+		id: OracleId,
+	) -> *const U {
+		let backing = index.entries[id]
+			.as_ref()
+			.unwrap()
+			.backing
+			.as_ref()
+			.unwrap();
+		backing.underliers.as_ref().as_ptr()
+	}
+
+	#[test]
+	fn test_witness_constant_value() {
+		let mut rng = StdRng::seed_from_u64(0);
+		let index = MultilinearExtensionIndex::<U, BinaryField128b>::new()
+			.update_owned::<BinaryField8b, _>([
+				(0, vec![U::default(); 4]),
+				(1, vec![U::random(&mut rng); 4]),
+			])
+			.unwrap();
+
+		let zero = index.get_multilin_poly(0).unwrap();
+		assert_eq!(zero.constant_value(), Some(BinaryField128b::ZERO));
+		// Every handle to the witness shares the cached result.
+		assert_eq!(
+			index.get_multilin_poly(0).unwrap().constant_value(),
+			Some(BinaryField128b::ZERO)
+		);
+		assert_eq!(index.get_multilin_poly(1).unwrap().constant_value(), None);
+	}
+
+	#[test]
+	fn test_update_owned_deduplicates_identical_columns() {
+		let mut rng = StdRng::seed_from_u64(0);
+		let table = vec![U::random(&mut rng); 4];
+		let other = vec![U::random(&mut rng); 4];
+
+		let index = MultilinearExtensionIndex::<U, BinaryField128b>::new()
+			.with_deduplication()
+			.update_owned::<BinaryField8b, _>([(0, table.clone()), (1, other), (2, table)])
+			.unwrap();
+
+		assert_eq!(index.dedup_allocation_count(), Some(2));
+		assert_eq!(backing_ptr(&index, 0), backing_ptr(&index, 2));
+		assert_ne!(backing_ptr(&index, 0), backing_ptr(&index, 1));
+		assert_eq!(index.get::<BinaryField8b>(2).unwrap().n_vars(), 6);
+	}
+
+	#[test]
+	fn test_update_shared_reuses_data() {
+		let mut rng = StdRng::seed_from_u64(0);
+		let column = (0..4).map(|_| U::random(&mut rng)).collect::<Vec<_>>();
+
+		let mut index = MultilinearExtensionIndex::<U, BinaryField128b>::new()
+			.update_owned::<BinaryField8b, _>([(0, column)])
+			.unwrap();
+		index.update_shared([(3, 0)]).unwrap();
+
+		assert_eq!(backing_ptr(&index, 0), backing_ptr(&index, 3));
+		assert_eq!(index.get::<BinaryField8b>(3).unwrap().n_vars(), 6);
+		assert!(matches!(index.update_shared([(4, 1)]), Err(Error::MissingWitness { id: 1 })));
+	}
+
+	#[test]
+	fn test_update_owned_without_deduplication() {
+		let mut rng = StdRng::seed_from_u64(0);
+		let table = vec![U::random(&mut rng); 4];
+
+		let index = MultilinearExtensionIndex::<U, BinaryField128b>::new()
+			.update_owned::<BinaryField8b, _>([(0, table.clone()), (1, table)])
+			.unwrap();
+
+		assert_eq!(index.dedup_allocation_count(), None);
+		assert_ne!(backing_ptr(&index, 0), backing_ptr(&index, 1));
+	}
+
+	#[test]
+	fn test_release_returns_unique_buffers() {
+		let mut rng = StdRng::seed_from_u64(0);
+		let table = vec![U::random(&mut rng); 4];
+		let other = vec![U::random(&mut rng); 4];
+
+		let mut index = MultilinearExtensionIndex::<U, BinaryField128b>::new()
+			.with_deduplication()
+			.update_owned::<BinaryField8b, _>([(0, table.clone()), (1, other), (2, table)])
+			.unwrap();
+
+		// Oracle 0 shares its buffer with oracle 2, so releasing it frees nothing for reuse.
+		assert!(index.release([0]).is_empty());
+		assert!(!index.has(0));
+		assert!(index.get::<BinaryField8b>(2).is_ok());
+
+		let mut released = index.transition_phase([2]);
+		assert_eq!(released.len(), 1);
+		assert!(Arc::get_mut(&mut released[0]).is_some());
+		assert!(!index.has(1));
+		assert!(index.has(2));
+		assert_eq!(index.dedup_allocation_count(), Some(1));
+	}
+
+	#[test]
+	fn test_namespace_rejects_foreign_oracles() {
+		let mut rng = StdRng::seed_from_u64(0);
+		let column = vec![U::random(&mut rng); 4];
+
+		let mut index = MultilinearExtensionIndex::<U, BinaryField128b>::new()
+			.update_owned::<BinaryField8b, _>([(0, column.clone())])
+			.unwrap();
+
+		let mut gadget = index.namespace("gadget", [1, 2]);
+		assert!(!gadget.has(0));
+		assert!(matches!(
+			gadget.get::<BinaryField8b>(0),
+			Err(Error::OracleOutsideNamespace { id: 0, .. })
+		));
+		assert!(matches!(
+			gadget.update_owned::<BinaryField8b, _>([(1, column.clone()), (3, column.clone())]),
+			Err(Error::OracleOutsideNamespace { id: 3, .. })
+		));
+		assert!(!gadget.has(1));
+
+		gadget
+			.update_owned::<BinaryField8b, _>([(1, column.clone())])
+			.unwrap();
+		assert_eq!(gadget.get::<BinaryField8b>(1).unwrap().n_vars(), 6);
+		let poly = gadget.get_multilin_poly(1).unwrap();
+		gadget.update_multilin_poly([(2, poly)]).unwrap();
+
+		assert!(index.has(1));
+		assert!(index.has(2));
+		assert!(!index.has(3));
+	}
+
+	#[test]
+	fn test_with_flipped_bit() {
+		let mut rng = StdRng::seed_from_u64(0);
+		let columns = (0..2)
+			.map(|id| (id, (0..4).map(|_| U::random(&mut rng)).collect::<Vec<_>>()))
+			.collect::<Vec<_>>();
+
+		let index = MultilinearExtensionIndex::<U, BinaryField128b>::new()
+			.update_owned::<BinaryField8b, _>(columns)
+			.unwrap();
+		let perturbed = index
+			.with_flipped_bit::<BinaryField8b>(1, 8 * 37 + 5)
+			.unwrap();
+		assert_eq!(backing_ptr(&index, 0), backing_ptr(&perturbed, 0));
+
+		let original = index.get::<BinaryField8b>(1).unwrap();
+		let flipped = perturbed.get::<BinaryField8b>(1).unwrap();
+		for i in 0..1 << 6 {
+			let expected = original.evaluate_on_hypercube(i).unwrap()
+				+ if i == 37 {
+					BinaryField8b::new(1 << 5)
+				} else {
+					BinaryField8b::ZERO
+				};
+			assert_eq!(flipped.evaluate_on_hypercube(i).unwrap(), expected);
+		}
+
+		assert!(matches!(
+			index.with_flipped_bit::<BinaryField8b>(1, 8 << 6),
+			Err(Error::BitOutOfRange { id: 1, .. })
+		));
+		assert!(matches!(
+			index.with_flipped_bit::<BinaryField1b>(1, 0),
+			Err(Error::OracleTowerHeightMismatch { .. })
+		));
+	}
+}
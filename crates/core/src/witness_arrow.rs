@@ -0,0 +1,227 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Zero-copy ingestion of witness columns from [Apache Arrow] arrays.
+//!
+//! Arrow stores boolean arrays as bitmaps with the least significant bit first and integer arrays
+//! as little-endian values, which is exactly the packed layout of [`BinaryField1b`] and the
+//! unsigned-integer-sized binary tower fields. Columns are therefore borrowed directly from the
+//! Arrow buffers without conversion, as long as they are aligned to the underlier type.
+//!
+//! [Apache Arrow]: <https://arrow.apache.org/>
+
+use crate::{oracle::OracleId, witness::MultilinearExtensionIndex};
+use arrow_array::{Array, BooleanArray, UInt32Array, UInt64Array, UInt8Array};
+use binius_field::{
+	as_packed_field::PackScalar, underlier::UnderlierType, BinaryField1b, BinaryField32b,
+	BinaryField64b, BinaryField8b, ExtensionField, Field,
+};
+use binius_utils::bail;
+use bytemuck::AnyBitPattern;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	#[error("column for oracle {id} contains null values")]
+	ContainsNulls { id: OracleId },
+	#[error(
+		"column for oracle {id} has length {len}, expected a power of two of at least {min_len}"
+	)]
+	IncorrectLength {
+		id: OracleId,
+		len: usize,
+		min_len: usize,
+	},
+	#[error("bitmap for oracle {id} starts at bit offset {offset}, which is not byte-aligned")]
+	UnalignedBitOffset { id: OracleId, offset: usize },
+	#[error("buffer for oracle {id} is not aligned to the underlier type")]
+	UnalignedBuffer { id: OracleId },
+	#[error("witness error: {0}")]
+	Witness(#[from] crate::witness::Error),
+}
+
+/// An Arrow array to be used as a witness column.
+///
+/// Each supported array type maps to the binary tower field of the same bit width.
+#[derive(Debug, Clone, Copy)]
+pub enum ArrowColumn<'a> {
+	/// A column over [`BinaryField1b`]
+	Boolean(&'a BooleanArray),
+	/// A column over [`BinaryField8b`]
+	UInt8(&'a UInt8Array),
+	/// A column over [`BinaryField32b`]
+	UInt32(&'a UInt32Array),
+	/// A column over [`BinaryField64b`]
+	UInt64(&'a UInt64Array),
+}
+
+impl<'a> ArrowColumn<'a> {
+	/// Base-2 logarithm of the bit width of the column values.
+	pub fn log_bits(&self) -> usize {
+		match self {
+			ArrowColumn::Boolean(_) => 0,
+			ArrowColumn::UInt8(_) => 3,
+			ArrowColumn::UInt32(_) => 5,
+			ArrowColumn::UInt64(_) => 6,
+		}
+	}
+
+	fn array(&self) -> &'a dyn Array {
+		match self {
+			ArrowColumn::Boolean(array) => *array,
+			ArrowColumn::UInt8(array) => *array,
+			ArrowColumn::UInt32(array) => *array,
+			ArrowColumn::UInt64(array) => *array,
+		}
+	}
+
+	fn bytes(&self, id: OracleId) -> Result<&'a [u8], Error> {
+		let bytes = match self {
+			ArrowColumn::Boolean(array) => {
+				let values = array.values();
+				if values.offset() % 8 != 0 {
+					bail!(Error::UnalignedBitOffset {
+						id,
+						offset: values.offset(),
+					});
+				}
+				&values.inner().as_slice()[values.offset() / 8..]
+			}
+			ArrowColumn::UInt8(array) => &**array.values(),
+			ArrowColumn::UInt32(array) => bytemuck::cast_slice::<u32, u8>(array.values()),
+			ArrowColumn::UInt64(array) => bytemuck::cast_slice::<u64, u8>(array.values()),
+		};
+		Ok(bytes)
+	}
+
+	/// Borrows the column values as packed underliers, validating length, nulls, and alignment.
+	fn underliers<U>(&self, id: OracleId) -> Result<&'a [U], Error>
+	where
+		U: UnderlierType + AnyBitPattern,
+	{
+		let array = self.array();
+		if array.null_count() != 0 {
+			bail!(Error::ContainsNulls { id });
+		}
+
+		let len = array.len();
+		let min_len = (U::BITS >> self.log_bits()).max(1);
+		if !len.is_power_of_two() || len < min_len {
+			bail!(Error::IncorrectLength { id, len, min_len });
+		}
+
+		let n_bytes = (len << self.log_bits()) / 8;
+		bytemuck::try_cast_slice(&self.bytes(id)?[..n_bytes])
+			.map_err(|_| Error::UnalignedBuffer { id })
+	}
+}
+
+/// Adds witness columns borrowed from Arrow arrays to the witness index.
+///
+/// The arrays must not contain nulls, their lengths must be powers of two filling at least one
+/// underlier, and their buffers must be aligned to the underlier type. Arrow allocates buffers
+/// with 64-byte alignment, so this holds for arrays that are not sliced at an unaligned offset.
+pub fn update_from_arrow<'a, 'new, U, FW>(
+	index: MultilinearExtensionIndex<'a, U, FW>,
+	columns: impl IntoIterator<Item = (OracleId, ArrowColumn<'new>)>,
+) -> Result<MultilinearExtensionIndex<'new, U, FW>, Error>
+where
+	'a: 'new,
+	U: UnderlierType
+		+ AnyBitPattern
+		+ PackScalar<FW>
+		+ PackScalar<BinaryField1b>
+		+ PackScalar<BinaryField8b>
+		+ PackScalar<BinaryField32b>
+		+ PackScalar<BinaryField64b>,
+	FW: Field
+		+ ExtensionField<BinaryField1b>
+		+ ExtensionField<BinaryField8b>
+		+ ExtensionField<BinaryField32b>
+		+ ExtensionField<BinaryField64b>,
+{
+	let mut index = index.update_borrowed::<BinaryField1b>([])?;
+	for (id, column) in columns {
+		let underliers = column.underliers::<U>(id)?;
+		index = match column {
+			ArrowColumn::Boolean(_) => index.update_borrowed::<BinaryField1b>([(id, underliers)]),
+			ArrowColumn::UInt8(_) => index.update_borrowed::<BinaryField8b>([(id, underliers)]),
+			ArrowColumn::UInt32(_) => index.update_borrowed::<BinaryField32b>([(id, underliers)]),
+			ArrowColumn::UInt64(_) => index.update_borrowed::<BinaryField64b>([(id, underliers)]),
+		}?;
+	}
+	Ok(index)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::polynomial::MultilinearPoly;
+	use assert_matches::assert_matches;
+	use binius_field::{underlier::WithUnderlier, BinaryField128b, PackedBinaryField1x128b};
+
+	type U = <PackedBinaryField1x128b as WithUnderlier>::Underlier;
+
+	#[test]
+	fn test_update_from_arrow() {
+		let bits = BooleanArray::from((0..128).map(|i| i % 3 == 0).collect::<Vec<_>>());
+		let bytes = UInt8Array::from_iter_values(0..32);
+		let words = UInt64Array::from_iter_values((0..8).map(|i| i << 40));
+
+		let index = update_from_arrow(
+			MultilinearExtensionIndex::<U, BinaryField128b>::new(),
+			[
+				(0, ArrowColumn::Boolean(&bits)),
+				(1, ArrowColumn::UInt8(&bytes)),
+				(2, ArrowColumn::UInt64(&words)),
+			],
+		)
+		.unwrap();
+
+		let bits_mle = index.get::<BinaryField1b>(0).unwrap();
+		assert_eq!(bits_mle.n_vars(), 7);
+		for i in 0..128 {
+			assert_eq!(
+				bits_mle.evaluate_on_hypercube(i).unwrap(),
+				BinaryField1b::from((i % 3 == 0) as u8)
+			);
+		}
+		let bytes_mle = index.get::<BinaryField8b>(1).unwrap();
+		assert_eq!(bytes_mle.evaluate_on_hypercube(17).unwrap(), BinaryField8b::new(17));
+		let words_mle = index.get::<BinaryField64b>(2).unwrap();
+		assert_eq!(words_mle.evaluate_on_hypercube(5).unwrap(), BinaryField64b::new(5 << 40));
+	}
+
+	#[test]
+	fn test_update_from_arrow_rejects_invalid_columns() {
+		let new_index = MultilinearExtensionIndex::<U, BinaryField128b>::new;
+
+		let short = UInt32Array::from_iter_values(0..2);
+		assert_matches!(
+			update_from_arrow(new_index(), [(0, ArrowColumn::UInt32(&short))]),
+			Err(Error::IncorrectLength {
+				id: 0,
+				len: 2,
+				min_len: 4
+			})
+		);
+
+		let nulls = UInt32Array::from(vec![Some(1), None, Some(2), Some(3)]);
+		assert_matches!(
+			update_from_arrow(new_index(), [(0, ArrowColumn::UInt32(&nulls))]),
+			Err(Error::ContainsNulls { id: 0 })
+		);
+
+		let words = UInt32Array::from_iter_values(0..5);
+		let unaligned = words.slice(1, 4);
+		assert_matches!(
+			update_from_arrow(new_index(), [(0, ArrowColumn::UInt32(&unaligned))]),
+			Err(Error::UnalignedBuffer { id: 0 })
+		);
+
+		let bits = BooleanArray::from(vec![true; 129]);
+		let unaligned = bits.slice(1, 128);
+		assert_matches!(
+			update_from_arrow(new_index(), [(0, ArrowColumn::Boolean(&unaligned))]),
+			Err(Error::UnalignedBitOffset { id: 0, offset: 1 })
+		);
+	}
+}
@@ -0,0 +1,247 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Debugging utility for dumping witness columns as text.
+//!
+//! A [`ColumnDump`] prints selected columns of a [`MultilinearExtensionIndex`] side by side, one
+//! hypercube vertex per row, so that bugs in witness generation can be spotted by eye. Values are
+//! read directly from the packed backing storage, so a 1-bit column prints as a single digit per
+//! row regardless of how many scalars are packed into each underlier.
+
+use crate::{oracle::OracleId, witness::MultilinearExtensionIndex};
+use binius_field::{as_packed_field::PackScalar, underlier::UnderlierType, Field};
+use std::{
+	fmt::Write as _,
+	fs::File,
+	io::{self, BufWriter, Write},
+	ops::Range,
+	path::Path,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	#[error("witness error: {0}")]
+	Witness(#[from] crate::witness::Error),
+	#[error("I/O error: {0}")]
+	Io(#[from] io::Error),
+}
+
+/// The textual representation of the column values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColumnFormat {
+	/// Hexadecimal, with one digit per four bits of the scalar. Scalars narrower than four bits
+	/// take one digit.
+	#[default]
+	Hex,
+	/// Binary, most significant bit first, with one digit per bit of the scalar.
+	Bits,
+}
+
+/// Dumps labelled witness columns as an aligned text matrix.
+///
+/// Columns may have different numbers of variables; rows past the end of a shorter column are
+/// left blank.
+#[derive(Debug, Clone, Default)]
+pub struct ColumnDump {
+	format: ColumnFormat,
+	columns: Vec<(String, OracleId)>,
+	rows: Option<Range<usize>>,
+}
+
+struct DumpColumn<'b> {
+	label: &'b str,
+	bytes: &'b [u8],
+	log_bits: usize,
+	n_rows: usize,
+	width: usize,
+}
+
+impl ColumnDump {
+	pub fn new(format: ColumnFormat) -> Self {
+		Self {
+			format,
+			..Default::default()
+		}
+	}
+
+	/// Adds the column with the given oracle id, shown under `label`.
+	pub fn column(mut self, label: impl Into<String>, id: OracleId) -> Self {
+		self.columns.push((label.into(), id));
+		self
+	}
+
+	/// Restricts the dump to the given range of rows. By default all rows are dumped.
+	pub fn rows(mut self, rows: Range<usize>) -> Self {
+		self.rows = Some(rows);
+		self
+	}
+
+	/// Writes the selected columns of `witness` to `out`.
+	pub fn write<U, FW>(
+		&self,
+		witness: &MultilinearExtensionIndex<U, FW>,
+		out: &mut impl Write,
+	) -> Result<(), Error>
+	where
+		U: UnderlierType + PackScalar<FW>,
+		FW: Field,
+	{
+		let columns = self
+			.columns
+			.iter()
+			.map(|(label, id)| {
+				let (underliers, tower_level, n_vars) = witness.backing_underliers(*id)?;
+				let width = self.cell_width(tower_level).max(label.len());
+				Ok(DumpColumn {
+					label,
+					bytes: bytemuck::cast_slice::<U, u8>(underliers),
+					log_bits: tower_level,
+					n_rows: 1 << n_vars,
+					width,
+				})
+			})
+			.collect::<Result<Vec<_>, Error>>()?;
+
+		let n_rows = columns
+			.iter()
+			.map(|column| column.n_rows)
+			.max()
+			.unwrap_or(0);
+		let rows = match &self.rows {
+			Some(rows) => rows.start.min(n_rows)..rows.end.min(n_rows),
+			None => 0..n_rows,
+		};
+		let row_width = n_rows.saturating_sub(1).to_string().len().max(3);
+
+		let mut line = format!("{:>row_width$}", "row");
+		for column in &columns {
+			write!(line, " | {:<width$}", column.label, width = column.width)
+				.expect("writing to a String cannot fail");
+		}
+		writeln!(out, "{}", line.trim_end())?;
+
+		for row in rows {
+			line.clear();
+			write!(line, "{:>row_width$}", row).expect("writing to a String cannot fail");
+			for column in &columns {
+				let cell = if row < column.n_rows {
+					self.format_cell(column, row)
+				} else {
+					String::new()
+				};
+				write!(line, " | {:<width$}", cell, width = column.width)
+					.expect("writing to a String cannot fail");
+			}
+			writeln!(out, "{}", line.trim_end())?;
+		}
+		Ok(())
+	}
+
+	/// Writes the selected columns of `witness` to standard error.
+	pub fn print<U, FW>(&self, witness: &MultilinearExtensionIndex<U, FW>) -> Result<(), Error>
+	where
+		U: UnderlierType + PackScalar<FW>,
+		FW: Field,
+	{
+		self.write(witness, &mut io::stderr().lock())
+	}
+
+	/// Writes the selected columns of `witness` to the file at `path`, replacing its contents.
+	pub fn write_to_file<U, FW>(
+		&self,
+		witness: &MultilinearExtensionIndex<U, FW>,
+		path: impl AsRef<Path>,
+	) -> Result<(), Error>
+	where
+		U: UnderlierType + PackScalar<FW>,
+		FW: Field,
+	{
+		let mut out = BufWriter::new(File::create(path)?);
+		self.write(witness, &mut out)?;
+		out.flush()?;
+		Ok(())
+	}
+
+	fn cell_width(&self, log_bits: usize) -> usize {
+		match self.format {
+			ColumnFormat::Hex => (1 << log_bits).max(4) / 4,
+			ColumnFormat::Bits => 1 << log_bits,
+		}
+	}
+
+	fn format_cell(&self, column: &DumpColumn, row: usize) -> String {
+		let bits = 1 << column.log_bits;
+		if bits < 8 {
+			// Several scalars share a byte, least significant bits first.
+			let byte = column.bytes[row * bits / 8];
+			let value = (byte >> (row * bits % 8)) & ((1u16 << bits) - 1) as u8;
+			match self.format {
+				ColumnFormat::Hex => format!("{:x}", value),
+				ColumnFormat::Bits => format!("{:0bits$b}", value),
+			}
+		} else {
+			let n_bytes = bits / 8;
+			let value = &column.bytes[row * n_bytes..(row + 1) * n_bytes];
+			// Scalars are stored little-endian, so print the bytes in reverse order.
+			value
+				.iter()
+				.rev()
+				.map(|byte| match self.format {
+					ColumnFormat::Hex => format!("{:02x}", byte),
+					ColumnFormat::Bits => format!("{:08b}", byte),
+				})
+				.collect()
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use binius_field::{
+		underlier::WithUnderlier, BinaryField128b, BinaryField16b, BinaryField1b,
+		PackedBinaryField1x128b,
+	};
+
+	type U = <PackedBinaryField1x128b as WithUnderlier>::Underlier;
+
+	#[test]
+	fn test_dump_aligns_packed_columns() {
+		// 128 1-bit values with only bits 0, 1 and 3 set.
+		let bits = vec![U::from(0b1011u128)];
+		// 16 16-bit values 0x0000, 0x0101, ..., 0x0f0f.
+		let words = (0..2)
+			.map(|i| (0..8).fold(0u128, |acc, j| acc | (0x0101 * (8 * i + j) as u128) << (16 * j)))
+			.map(U::from)
+			.collect::<Vec<_>>();
+
+		let index = MultilinearExtensionIndex::<U, BinaryField128b>::new()
+			.update_owned::<BinaryField1b, _>([(0, bits)])
+			.unwrap()
+			.update_owned::<BinaryField16b, _>([(1, words)])
+			.unwrap();
+
+		let mut out = Vec::new();
+		ColumnDump::new(ColumnFormat::Hex)
+			.column("flag", 0)
+			.column("word", 1)
+			.rows(0..4)
+			.write(&index, &mut out)
+			.unwrap();
+		assert_eq!(
+			String::from_utf8(out).unwrap(),
+			"row | flag | word\n  0 | 1    | 0000\n  1 | 1    | 0101\n  2 | 0    | 0202\n  3 | 1    | 0303\n"
+		);
+
+		let mut out = Vec::new();
+		ColumnDump::new(ColumnFormat::Bits)
+			.column("flag", 0)
+			.column("word", 1)
+			.rows(15..17)
+			.write(&index, &mut out)
+			.unwrap();
+		assert_eq!(
+			String::from_utf8(out).unwrap(),
+			"row | flag | word\n 15 | 0    | 0000111100001111\n 16 | 0\n"
+		);
+	}
+}
@@ -0,0 +1,202 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Randomized soundness checks that perturb valid witnesses.
+//!
+//! A constraint system should reject any witness that differs from a valid one in a column the
+//! prover commits to. [`check_bit_flips_rejected`] flips a single bit of a valid witness at a
+//! random oracle and hypercube vertex, runs the full prove and verify pipeline on the perturbed
+//! witness, and repeats. A perturbation that is accepted points at a column, or a part of one, that
+//! no constraint depends on. This gives empirical coverage that hand-written circuits constrain all
+//! of their columns.
+
+use crate::{
+	oracle::OracleId,
+	polynomial::MultilinearPoly,
+	witness::{Error as WitnessError, MultilinearExtensionIndex},
+};
+use binius_field::{
+	as_packed_field::PackScalar, underlier::UnderlierType, ExtensionField, TowerField,
+};
+use binius_utils::bail;
+use rand::Rng;
+use std::fmt::{Debug, Display};
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	#[error("there are no oracles to perturb")]
+	NoOracles,
+	#[error("the witness with bit {} of oracle {} flipped was accepted", .0.bit, .0.id)]
+	PerturbationAccepted(BitFlip),
+	#[error("witness error: {0}")]
+	Witness(#[from] WitnessError),
+}
+
+/// A single bit flip of a witness, see [`MultilinearExtensionIndex::with_flipped_bit`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitFlip {
+	pub id: OracleId,
+	pub bit: usize,
+}
+
+/// Flips one uniformly random bit of the witness of one uniformly random oracle in `ids`.
+///
+/// All oracles in `ids` must have witnesses with scalars in the field `FS`.
+pub fn flip_random_bit<'a, U, FW, FS, R>(
+	witness: &MultilinearExtensionIndex<'a, U, FW>,
+	ids: &[OracleId],
+	rng: &mut R,
+) -> Result<(MultilinearExtensionIndex<'a, U, FW>, BitFlip), Error>
+where
+	U: UnderlierType + PackScalar<FW> + PackScalar<FS> + Debug,
+	FW: ExtensionField<FS>,
+	FS: TowerField,
+	R: Rng + ?Sized,
+{
+	if ids.is_empty() {
+		bail!(Error::NoOracles);
+	}
+	let id = ids[rng.gen_range(0..ids.len())];
+	let n_vars = witness.get_multilin_poly(id)?.n_vars();
+	let bit = rng.gen_range(0..FS::DEGREE << n_vars);
+	let perturbed = witness.with_flipped_bit::<FS>(id, bit)?;
+	Ok((perturbed, BitFlip { id, bit }))
+}
+
+/// Checks that `prove_and_verify` rejects `n_trials` random single bit flips of a valid witness.
+///
+/// `prove_and_verify` runs the full pipeline, from constraint construction through proving to
+/// verification, and returns an error if either the prover or the verifier fails. The witness
+/// itself is expected to be valid, so callers should check that it is accepted first. Returns the
+/// first perturbation that is accepted.
+pub fn check_bit_flips_rejected<'a, U, FW, FS, R, E>(
+	witness: &MultilinearExtensionIndex<'a, U, FW>,
+	ids: &[OracleId],
+	n_trials: usize,
+	rng: &mut R,
+	mut prove_and_verify: impl FnMut(MultilinearExtensionIndex<'a, U, FW>) -> Result<(), E>,
+) -> Result<(), Error>
+where
+	U: UnderlierType + PackScalar<FW> + PackScalar<FS> + Debug,
+	FW: ExtensionField<FS>,
+	FS: TowerField,
+	R: Rng + ?Sized,
+	E: Display,
+{
+	for trial in 0..n_trials {
+		let (perturbed, flip) = flip_random_bit::<U, FW, FS, R>(witness, ids, rng)?;
+		match prove_and_verify(perturbed) {
+			Ok(()) => bail!(Error::PerturbationAccepted(flip)),
+			Err(err) => tracing::debug!(
+				trial,
+				oracle_id = flip.id,
+				bit = flip.bit,
+				%err,
+				"perturbed witness rejected"
+			),
+		}
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use assert_matches::assert_matches;
+	use binius_field::{
+		underlier::{Random, WithUnderlier},
+		BinaryField128b, BinaryField1b, Field, PackedBinaryField128x1b,
+	};
+	use rand::{rngs::StdRng, SeedableRng};
+
+	type U = <PackedBinaryField128x1b as WithUnderlier>::Underlier;
+	type FW = BinaryField128b;
+
+	/// Checks the constraint `c = a * b` at every vertex, standing in for the full pipeline.
+	fn check_and(
+		witness: MultilinearExtensionIndex<U, FW>,
+		[a, b, c]: [OracleId; 3],
+	) -> Result<(), String> {
+		let [a, b, c] = [a, b, c].map(|id| witness.get::<BinaryField1b>(id).unwrap());
+		for i in 0..1 << a.n_vars() {
+			let [a, b, c] = [&a, &b, &c].map(|poly| poly.evaluate_on_hypercube(i).unwrap());
+			if a * b != c {
+				return Err(format!("constraint violated at vertex {i}"));
+			}
+		}
+		Ok(())
+	}
+
+	#[test]
+	fn test_bit_flips_rejected() {
+		let mut rng = StdRng::seed_from_u64(0);
+		let a = (0..2).map(|_| U::random(&mut rng)).collect::<Vec<_>>();
+		let b = (0..2).map(|_| U::random(&mut rng)).collect::<Vec<_>>();
+		let c = a
+			.iter()
+			.zip(b.iter())
+			.map(|(&a, &b)| a & b)
+			.collect::<Vec<_>>();
+		let unconstrained = vec![U::random(&mut rng); 2];
+		let witness = MultilinearExtensionIndex::<U, FW>::new()
+			.update_owned::<BinaryField1b, _>([(0, a), (1, b), (2, c), (3, unconstrained)])
+			.unwrap();
+		check_and(witness.clone(), [0, 1, 2]).unwrap();
+
+		check_bit_flips_rejected::<_, _, BinaryField1b, _, _>(
+			&witness,
+			&[0, 1, 2],
+			32,
+			&mut rng,
+			|w| check_and(w, [0, 1, 2]),
+		)
+		.unwrap();
+
+		// A column that no constraint reads is caught on the first flip.
+		assert_matches!(
+			check_bit_flips_rejected::<_, _, BinaryField1b, _, _>(
+				&witness,
+				&[3],
+				1,
+				&mut rng,
+				|w| check_and(w, [0, 1, 2])
+			),
+			Err(Error::PerturbationAccepted(BitFlip { id: 3, .. }))
+		);
+		assert_matches!(
+			check_bit_flips_rejected::<_, _, BinaryField1b, _, _>(
+				&witness,
+				&[],
+				1,
+				&mut rng,
+				|w| check_and(w, [0, 1, 2])
+			),
+			Err(Error::NoOracles)
+		);
+	}
+
+	#[test]
+	fn test_flip_random_bit_changes_one_vertex() {
+		let mut rng = StdRng::seed_from_u64(1);
+		let column = (0..2).map(|_| U::random(&mut rng)).collect::<Vec<_>>();
+		let witness = MultilinearExtensionIndex::<U, FW>::new()
+			.update_owned::<BinaryField1b, _>([(0, column)])
+			.unwrap();
+		let (perturbed, flip) =
+			flip_random_bit::<_, _, BinaryField1b, _>(&witness, &[0], &mut rng).unwrap();
+		assert_eq!(flip.id, 0);
+
+		let original = witness.get::<BinaryField1b>(0).unwrap();
+		let perturbed = perturbed.get::<BinaryField1b>(0).unwrap();
+		let changed = (0..1 << 8)
+			.filter(|&i| {
+				original.evaluate_on_hypercube(i).unwrap()
+					!= perturbed.evaluate_on_hypercube(i).unwrap()
+			})
+			.collect::<Vec<_>>();
+		assert_eq!(changed, vec![flip.bit]);
+		assert_eq!(
+			original.evaluate_on_hypercube(flip.bit).unwrap() + BinaryField1b::ONE,
+			perturbed.evaluate_on_hypercube(flip.bit).unwrap()
+		);
+	}
+}
@@ -0,0 +1,288 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Recording and replaying the external inputs of witness generation.
+//!
+//! Witness generators derive most columns deterministically from a few external inputs, such as
+//! hash preimages or the initial state of a computation. A generator that requests these inputs
+//! through [`WitnessInputs`] can record them to an [`InputLog`], which is saved to a file next to
+//! the proof. Running the generator again with [`WitnessInputs::replay`] on the loaded log
+//! regenerates the identical witness, so a proof that failed to verify in production can be
+//! reproduced and debugged locally.
+//!
+//! Inputs are identified by their label and their position in the sequence of requests, so a
+//! generator must request them in the same order when replaying. A mismatched label is reported
+//! as an error rather than silently returning the wrong input.
+//!
+//! ## File format
+//!
+//! The log is stored as the magic bytes `BWIL`, a format version and the number of records, each a
+//! little-endian `u64`, followed by the records. Each record is the length of its label, the UTF-8
+//! label, the length of its data and the data, with lengths as little-endian `u64`. Inputs recorded
+//! with [`WitnessInputs::values`] are stored in the native byte order, so logs only replay on
+//! machines of the same endianness.
+
+use bytemuck::Pod;
+use getset::Getters;
+use std::{
+	fs::File,
+	io::{self, BufReader, BufWriter, Read, Write},
+	mem,
+	path::Path,
+};
+
+const MAGIC: &[u8; 4] = b"BWIL";
+const VERSION: u64 = 1;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	#[error("I/O error: {0}")]
+	Io(#[from] io::Error),
+	#[error("invalid input log: {0}")]
+	InvalidFormat(&'static str),
+	#[error("input {position} was recorded as {recorded:?} but requested as {requested:?}")]
+	LabelMismatch {
+		position: usize,
+		recorded: String,
+		requested: String,
+	},
+	#[error("input {label:?} was requested but the log has no more inputs")]
+	MissingInput { label: String },
+	#[error("recorded input {label:?} is not a whole number of values")]
+	ValueSizeMismatch { label: String },
+	#[error("{remaining} recorded inputs were not requested when replaying")]
+	UnconsumedInputs { remaining: usize },
+}
+
+/// A labelled external input of witness generation.
+#[derive(Debug, Clone, PartialEq, Eq, Getters)]
+#[getset(get = "pub")]
+pub struct InputRecord {
+	label: String,
+	bytes: Vec<u8>,
+}
+
+/// The external inputs of a witness generation run, in the order they were requested.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Getters)]
+#[getset(get = "pub")]
+pub struct InputLog {
+	records: Vec<InputRecord>,
+}
+
+impl InputLog {
+	pub fn write_to(&self, mut writer: impl Write) -> Result<(), Error> {
+		writer.write_all(MAGIC)?;
+		writer.write_all(&VERSION.to_le_bytes())?;
+		writer.write_all(&(self.records.len() as u64).to_le_bytes())?;
+		for record in &self.records {
+			for data in [record.label.as_bytes(), &record.bytes] {
+				writer.write_all(&(data.len() as u64).to_le_bytes())?;
+				writer.write_all(data)?;
+			}
+		}
+		Ok(())
+	}
+
+	pub fn read_from(mut reader: impl Read) -> Result<Self, Error> {
+		let mut magic = [0u8; 4];
+		reader.read_exact(&mut magic)?;
+		if &magic != MAGIC {
+			return Err(Error::InvalidFormat("bad magic bytes"));
+		}
+		if read_u64(&mut reader)? != VERSION {
+			return Err(Error::InvalidFormat("unsupported version"));
+		}
+
+		let n_records = read_u64(&mut reader)?;
+		let mut records = Vec::new();
+		for _ in 0..n_records {
+			let label = String::from_utf8(read_data(&mut reader)?)
+				.map_err(|_| Error::InvalidFormat("label is not UTF-8"))?;
+			let bytes = read_data(&mut reader)?;
+			records.push(InputRecord { label, bytes });
+		}
+		Ok(Self { records })
+	}
+
+	/// Saves the log to a file, replacing it if it exists.
+	pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+		let mut writer = BufWriter::new(File::create(path)?);
+		self.write_to(&mut writer)?;
+		writer.flush()?;
+		Ok(())
+	}
+
+	pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+		Self::read_from(BufReader::new(File::open(path)?))
+	}
+}
+
+fn read_u64(reader: &mut impl Read) -> Result<u64, Error> {
+	let mut bytes = [0u8; 8];
+	reader.read_exact(&mut bytes)?;
+	Ok(u64::from_le_bytes(bytes))
+}
+
+fn read_data(reader: &mut impl Read) -> Result<Vec<u8>, Error> {
+	let len = read_u64(reader)?;
+	// Read through `take` so that a corrupted length does not allocate a huge buffer up front.
+	let mut data = Vec::new();
+	reader.take(len).read_to_end(&mut data)?;
+	if data.len() as u64 != len {
+		return Err(Error::InvalidFormat("record is truncated"));
+	}
+	Ok(data)
+}
+
+/// The source of the external inputs of a witness generator.
+///
+/// When recording, every input is produced by the generator and appended to the log. When
+/// replaying, every input is read back from the log instead, and the generator's closure is not
+/// called.
+#[derive(Debug)]
+pub struct WitnessInputs {
+	log: InputLog,
+	/// The position of the next input to replay, or `None` when recording.
+	replay_position: Option<usize>,
+}
+
+impl WitnessInputs {
+	pub fn record() -> Self {
+		Self {
+			log: InputLog::default(),
+			replay_position: None,
+		}
+	}
+
+	pub fn replay(log: InputLog) -> Self {
+		Self {
+			log,
+			replay_position: Some(0),
+		}
+	}
+
+	pub fn is_replaying(&self) -> bool {
+		self.replay_position.is_some()
+	}
+
+	/// Returns the input labelled `label`, generating it with `generate` when recording.
+	pub fn bytes(
+		&mut self,
+		label: &str,
+		generate: impl FnOnce() -> Vec<u8>,
+	) -> Result<Vec<u8>, Error> {
+		let Some(position) = self.replay_position.as_mut() else {
+			let bytes = generate();
+			self.log.records.push(InputRecord {
+				label: label.to_string(),
+				bytes: bytes.clone(),
+			});
+			return Ok(bytes);
+		};
+
+		let record = self
+			.log
+			.records
+			.get(*position)
+			.ok_or_else(|| Error::MissingInput {
+				label: label.to_string(),
+			})?;
+		if record.label != label {
+			return Err(Error::LabelMismatch {
+				position: *position,
+				recorded: record.label.clone(),
+				requested: label.to_string(),
+			});
+		}
+		*position += 1;
+		Ok(record.bytes.clone())
+	}
+
+	/// Returns the input labelled `label` as plain values, generating it with `generate` when
+	/// recording.
+	pub fn values<T: Pod>(
+		&mut self,
+		label: &str,
+		generate: impl FnOnce() -> Vec<T>,
+	) -> Result<Vec<T>, Error> {
+		let bytes = self.bytes(label, || bytemuck::cast_slice(&generate()).to_vec())?;
+		if mem::size_of::<T>() != 0 && bytes.len() % mem::size_of::<T>() != 0 {
+			return Err(Error::ValueSizeMismatch {
+				label: label.to_string(),
+			});
+		}
+		Ok(bytemuck::pod_collect_to_vec(&bytes))
+	}
+
+	/// Ends the run, returning the log of the inputs.
+	///
+	/// When replaying, every recorded input must have been requested.
+	pub fn finish(self) -> Result<InputLog, Error> {
+		if let Some(position) = self.replay_position {
+			let remaining = self.log.records.len() - position;
+			if remaining != 0 {
+				return Err(Error::UnconsumedInputs { remaining });
+			}
+		}
+		Ok(self.log)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use assert_matches::assert_matches;
+	use rand::{rngs::StdRng, Rng, SeedableRng};
+	use std::iter::repeat_with;
+
+	fn generate(inputs: &mut WitnessInputs, rng: &mut StdRng) -> Result<Vec<u32>, Error> {
+		let preimage = inputs.bytes("preimage", || repeat_with(|| rng.gen()).take(13).collect())?;
+		let state =
+			inputs.values("state", || repeat_with(|| rng.gen::<u32>()).take(4).collect())?;
+		Ok(state
+			.into_iter()
+			.map(|word| word ^ preimage.len() as u32)
+			.collect())
+	}
+
+	#[test]
+	fn test_replay_regenerates_witness() {
+		let mut inputs = WitnessInputs::record();
+		let witness = generate(&mut inputs, &mut StdRng::seed_from_u64(0)).unwrap();
+		let log = inputs.finish().unwrap();
+		assert_eq!(log.records().len(), 2);
+
+		let mut encoded = Vec::new();
+		log.write_to(&mut encoded).unwrap();
+		let decoded = InputLog::read_from(encoded.as_slice()).unwrap();
+		assert_eq!(decoded, log);
+
+		// A different rng shows the replayed witness comes from the log.
+		let mut inputs = WitnessInputs::replay(decoded);
+		let replayed = generate(&mut inputs, &mut StdRng::seed_from_u64(1)).unwrap();
+		assert_eq!(replayed, witness);
+		assert_eq!(inputs.finish().unwrap(), log);
+	}
+
+	#[test]
+	fn test_replay_detects_divergence() {
+		let mut inputs = WitnessInputs::record();
+		inputs.bytes("preimage", || vec![1, 2, 3]).unwrap();
+		let log = inputs.finish().unwrap();
+
+		let mut inputs = WitnessInputs::replay(log.clone());
+		assert_matches!(
+			inputs.bytes("state", Vec::new),
+			Err(Error::LabelMismatch { position: 0, .. })
+		);
+		assert_matches!(
+			inputs.values::<u16>("preimage", Vec::new),
+			Err(Error::ValueSizeMismatch { .. })
+		);
+		assert_matches!(inputs.bytes("state", Vec::new), Err(Error::MissingInput { .. }));
+
+		let inputs = WitnessInputs::replay(log);
+		assert_matches!(inputs.finish(), Err(Error::UnconsumedInputs { remaining: 1 }));
+
+		assert_matches!(InputLog::read_from(&b"BWIX"[..]), Err(Error::InvalidFormat(_)));
+	}
+}
@@ -0,0 +1,217 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Dependency-aware parallel witness generation.
+//!
+//! Gadgets usually generate their witness columns from columns produced by other gadgets. A
+//! [`WitnessScheduler`] takes one witness generation task per gadget along with the columns each
+//! task reads and writes, and runs the tasks in parallel wherever their dependencies allow.
+
+use crate::oracle::OracleId;
+use binius_utils::bail;
+use rayon::prelude::*;
+use std::collections::HashMap;
+use tracing::debug_span;
+
+/// Error type returned by witness generation tasks.
+pub type TaskError = Box<dyn std::error::Error + Send + Sync>;
+
+/// The columns available to a task, indexed by oracle ID.
+pub type WitnessColumns<T> = HashMap<OracleId, T>;
+
+type TaskFn<'a, T> = Box<dyn FnOnce(&WitnessColumns<T>) -> Result<Vec<T>, TaskError> + Send + 'a>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	#[error("column {id} is written by more than one task")]
+	DuplicateOutput { id: OracleId },
+	#[error("task {task} reads column {id}, which is neither provided nor written by any task")]
+	MissingInput { task: &'static str, id: OracleId },
+	#[error("the dependencies of task {task} are cyclic")]
+	CyclicDependency { task: &'static str },
+	#[error("task {task} returned an incorrect number of columns, expected {expected}")]
+	IncorrectNumberOfOutputs { task: &'static str, expected: usize },
+	#[error("task {task} failed: {source}")]
+	Task {
+		task: &'static str,
+		#[source]
+		source: TaskError,
+	},
+}
+
+struct Task<'a, T> {
+	name: &'static str,
+	inputs: Vec<OracleId>,
+	outputs: Vec<OracleId>,
+	run: TaskFn<'a, T>,
+}
+
+/// Runs witness generation tasks in parallel, respecting the column dependencies between them.
+///
+/// Tasks are executed in waves: every task whose input columns are all available runs in
+/// parallel with the other tasks in its wave, and the columns they return become available to
+/// the next wave.
+pub struct WitnessScheduler<'a, T> {
+	tasks: Vec<Task<'a, T>>,
+}
+
+impl<'a, T> Default for WitnessScheduler<'a, T> {
+	fn default() -> Self {
+		Self { tasks: Vec::new() }
+	}
+}
+
+impl<'a, T: Send + Sync> WitnessScheduler<'a, T> {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Adds a task that computes the `outputs` columns, in order, from the `inputs` columns.
+	pub fn add_task(
+		&mut self,
+		name: &'static str,
+		inputs: impl IntoIterator<Item = OracleId>,
+		outputs: impl IntoIterator<Item = OracleId>,
+		run: impl FnOnce(&WitnessColumns<T>) -> Result<Vec<T>, TaskError> + Send + 'a,
+	) -> &mut Self {
+		self.tasks.push(Task {
+			name,
+			inputs: inputs.into_iter().collect(),
+			outputs: outputs.into_iter().collect(),
+			run: Box::new(run),
+		});
+		self
+	}
+
+	/// Runs all tasks, starting from the `provided` columns.
+	///
+	/// Returns the provided columns together with all columns written by the tasks.
+	pub fn run(self, provided: WitnessColumns<T>) -> Result<WitnessColumns<T>, Error> {
+		self.check_dependencies(&provided)?;
+
+		let mut columns = provided;
+		let mut pending = self.tasks;
+		while !pending.is_empty() {
+			let (ready, blocked): (Vec<_>, Vec<_>) = pending
+				.into_iter()
+				.partition(|task| task.inputs.iter().all(|id| columns.contains_key(id)));
+			if ready.is_empty() {
+				bail!(Error::CyclicDependency {
+					task: blocked[0].name,
+				});
+			}
+
+			let results = ready
+				.into_par_iter()
+				.map(|task| -> Result<_, Error> {
+					let Task {
+						name, outputs, run, ..
+					} = task;
+					let _span = debug_span!("witness_task", name).entered();
+					let values =
+						run(&columns).map_err(|source| Error::Task { task: name, source })?;
+					if values.len() != outputs.len() {
+						bail!(Error::IncorrectNumberOfOutputs {
+							task: name,
+							expected: outputs.len(),
+						});
+					}
+					Ok(outputs.into_iter().zip(values).collect::<Vec<_>>())
+				})
+				.collect::<Result<Vec<_>, _>>()?;
+			columns.extend(results.into_iter().flatten());
+			pending = blocked;
+		}
+		Ok(columns)
+	}
+
+	fn check_dependencies(&self, provided: &WitnessColumns<T>) -> Result<(), Error> {
+		let mut written = HashMap::new();
+		for task in &self.tasks {
+			for &id in &task.outputs {
+				if provided.contains_key(&id) || written.insert(id, task.name).is_some() {
+					bail!(Error::DuplicateOutput { id });
+				}
+			}
+		}
+		for task in &self.tasks {
+			if let Some(&id) = task
+				.inputs
+				.iter()
+				.find(|id| !provided.contains_key(id) && !written.contains_key(id))
+			{
+				bail!(Error::MissingInput {
+					task: task.name,
+					id,
+				});
+			}
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use assert_matches::assert_matches;
+
+	#[test]
+	fn test_tasks_run_in_dependency_order() {
+		let mut scheduler = WitnessScheduler::<Vec<u32>>::new();
+		// Tasks are added out of dependency order on purpose.
+		scheduler
+			.add_task("sum", [1, 2], [3], |columns| {
+				let sum = columns[&1]
+					.iter()
+					.zip(&columns[&2])
+					.map(|(x, y)| x + y)
+					.collect();
+				Ok(vec![sum])
+			})
+			.add_task("double", [0], [1], |columns| {
+				Ok(vec![columns[&0].iter().map(|x| 2 * x).collect()])
+			})
+			.add_task("square_and_cube", [0], [2, 4], |columns| {
+				let squares = columns[&0].iter().map(|x| x * x).collect();
+				let cubes = columns[&0].iter().map(|x| x * x * x).collect();
+				Ok(vec![squares, cubes])
+			});
+
+		let columns = scheduler.run(HashMap::from([(0, vec![1, 2, 3])])).unwrap();
+		assert_eq!(columns.len(), 5);
+		assert_eq!(columns[&3], vec![3, 8, 15]);
+		assert_eq!(columns[&4], vec![1, 8, 27]);
+	}
+
+	#[test]
+	fn test_invalid_dependencies() {
+		let mut scheduler = WitnessScheduler::<u32>::new();
+		scheduler
+			.add_task("a", [1], [0], |columns| Ok(vec![columns[&1]]))
+			.add_task("b", [0], [1], |columns| Ok(vec![columns[&0]]));
+		assert_matches!(scheduler.run(HashMap::new()), Err(Error::CyclicDependency { .. }));
+
+		let mut scheduler = WitnessScheduler::<u32>::new();
+		scheduler.add_task("a", [2], [0], |columns| Ok(vec![columns[&2]]));
+		assert_matches!(
+			scheduler.run(HashMap::new()),
+			Err(Error::MissingInput { task: "a", id: 2 })
+		);
+
+		let mut scheduler = WitnessScheduler::<u32>::new();
+		scheduler.add_task("a", [], [0], |_| Ok(vec![1]));
+		assert_matches!(
+			scheduler.run(HashMap::from([(0, 0)])),
+			Err(Error::DuplicateOutput { id: 0 })
+		);
+
+		let mut scheduler = WitnessScheduler::<u32>::new();
+		scheduler.add_task("a", [], [0, 1], |_| Ok(vec![1]));
+		assert_matches!(
+			scheduler.run(HashMap::new()),
+			Err(Error::IncorrectNumberOfOutputs {
+				task: "a",
+				expected: 2
+			})
+		);
+	}
+}
@@ -4,8 +4,8 @@ use binius_field::{
 	aes_field::{
 		AESTowerField128b, AESTowerField16b, AESTowerField32b, AESTowerField64b, AESTowerField8b,
 	},
-	BinaryField128b, BinaryField128bPolyval, BinaryField16b, BinaryField32b, BinaryField64b,
-	BinaryField8b, Field,
+	BinaryField128b, BinaryField128bPolyval, BinaryField16b, BinaryField256b,
+	BinaryField256bPolyval, BinaryField32b, BinaryField64b, BinaryField8b, Field,
 };
 use criterion::{
 	criterion_group, criterion_main, measurement::Measurement, BenchmarkGroup, Criterion,
@@ -58,6 +58,9 @@ fn bench_all_fields<Op: FieldOperation>(c: &mut Criterion) {
 	run_bench!(group, AESTowerField128b, Op);
 
 	run_bench!(group, BinaryField128bPolyval, Op);
+
+	run_bench!(group, BinaryField256b, Op);
+	run_bench!(group, BinaryField256bPolyval, Op);
 }
 
 struct MultiplyOp;
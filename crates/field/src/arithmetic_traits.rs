@@ -13,6 +13,12 @@ pub trait Square {
 	fn square(self) -> Self;
 }
 
+/// Value that has a square root
+pub trait Sqrt {
+	/// Returns the unique value whose square is `self`
+	fn sqrt(self) -> Self;
+}
+
 /// Value that can be inverted
 pub trait InvertOrZero {
 	/// Returns the inverted value or zero in case when `self` is zero
@@ -22,6 +22,18 @@ use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
 pub trait BinaryField: ExtensionField<BinaryField1b> {
 	const N_BITS: usize = Self::DEGREE;
 	const MULTIPLICATIVE_GENERATOR: Self;
+
+	/// Computes the absolute trace of the element, $\sum_{i=0}^{n-1} x^{2^i}$ for a field with
+	/// $2^n$ elements.
+	fn trace(self) -> BinaryField1b {
+		let mut pow = self;
+		let mut trace = self;
+		for _ in 1..Self::N_BITS {
+			pow = pow.square();
+			trace += pow;
+		}
+		BinaryField1b::from((trace == Self::ONE) as u8)
+	}
 }
 
 /// A binary field *isomorphic* to a binary tower field.
@@ -60,6 +72,38 @@ pub trait TowerField: BinaryField {
 	fn mul_primitive(self, iota: usize) -> Result<Self, Error> {
 		Ok(self * <Self as ExtensionField<BinaryField1b>>::basis(1 << iota)?)
 	}
+
+	/// Solves the quadratic equation $x^2 + x = c$, where $c$ is this element.
+	///
+	/// The equation has a solution if and only if the absolute trace of $c$ is zero, in which case
+	/// the two solutions are $x$ and $x + 1$ and this returns one of them. A general equation
+	/// $a x^2 + b x + c = 0$ with $b \neq 0$ reduces to this form by substituting $x = b y / a$.
+	///
+	/// The half-trace solves the equation only in fields of odd degree, so the solution is
+	/// computed with the generalized half-trace $\sum_{i=0}^{n-2} \left( \sum_{j=i+1}^{n-1}
+	/// \delta^{2^j} \right) c^{2^i}$, where $\delta$ is an element of trace one. The canonical
+	/// primitive element of the topmost extension in the tower has trace one.
+	fn solve_quadratic(self) -> Option<Self> {
+		if Self::TOWER_LEVEL == 0 {
+			return self.is_zero().then_some(Self::ZERO);
+		}
+
+		let mut delta_pow = Self::ONE
+			.mul_primitive(Self::TOWER_LEVEL - 1)
+			.expect("TOWER_LEVEL is at least 1");
+		// Since the trace of delta is one, the inner sum equals one plus the prefix sum of the
+		// powers of delta.
+		let mut delta_sum = delta_pow;
+		let mut c_pow = self;
+		let mut solution = Self::ZERO;
+		for _ in 0..Self::N_BITS - 1 {
+			solution += (Self::ONE + delta_sum) * c_pow;
+			delta_pow = delta_pow.square();
+			delta_sum += delta_pow;
+			c_pow = c_pow.square();
+		}
+		(solution.square() + solution == self).then_some(solution)
+	}
 }
 
 pub(super) trait TowerExtensionField:
@@ -735,6 +779,10 @@ pub(crate) mod tests {
 		BinaryField16b as BF16, BinaryField1b as BF1, BinaryField2b as BF2, BinaryField4b as BF4,
 		BinaryField64b as BF64, BinaryField8b as BF8, *,
 	};
+	use crate::{
+		arithmetic_traits::{Sqrt, Square},
+		AESTowerField64b, BinaryField128bPolyval, PackedBinaryField16x8b, PackedField,
+	};
 	use proptest::prelude::*;
 
 	#[test]
@@ -1089,6 +1137,92 @@ pub(crate) mod tests {
 		}
 	}
 
+	fn test_solve_quadratic<F: TowerField>(val: F) {
+		// y^2 + y always has a solution, val itself may not.
+		let c = Square::square(val) + val;
+		let solution = c.solve_quadratic().unwrap();
+		assert_eq!(Square::square(solution) + solution, c);
+		assert_eq!(c.trace(), BinaryField1b::ZERO);
+
+		match val.solve_quadratic() {
+			Some(solution) => {
+				assert_eq!(Square::square(solution) + solution, val);
+				assert_eq!(val.trace(), BinaryField1b::ZERO);
+			}
+			None => assert_eq!(val.trace(), BinaryField1b::ONE),
+		}
+	}
+
+	fn test_sqrt<F: BinaryField>(val: F) {
+		assert_eq!(Sqrt::sqrt(Square::square(val)), val);
+		assert_eq!(Square::square(Sqrt::sqrt(val)), val);
+	}
+
+	#[test]
+	fn test_trace_one_element() {
+		assert_eq!(BinaryField2b::from(0x2u8).trace(), BinaryField1b::ONE);
+		assert_eq!(BinaryField8b::new(0x10).trace(), BinaryField1b::ONE);
+		assert_eq!(BinaryField128b::new(1 << 64).trace(), BinaryField1b::ONE);
+		assert_eq!(BinaryField128b::ONE.trace(), BinaryField1b::ZERO);
+	}
+
+	proptest! {
+		#[test]
+		fn test_solve_quadratic_1b(val in 0u8..2u8) {
+			test_solve_quadratic::<BinaryField1b>(val.into())
+		}
+
+		#[test]
+		fn test_solve_quadratic_4b(val in 0u8..16u8) {
+			test_solve_quadratic::<BinaryField4b>(val.into())
+		}
+
+		#[test]
+		fn test_solve_quadratic_8b(val in 0u8..) {
+			test_solve_quadratic::<BinaryField8b>(val.into())
+		}
+
+		#[test]
+		fn test_solve_quadratic_32b(val in 0u32..) {
+			test_solve_quadratic::<BinaryField32b>(val.into())
+		}
+
+		#[test]
+		fn test_solve_quadratic_128b(val in 0u128..) {
+			test_solve_quadratic::<BinaryField128b>(val.into())
+		}
+
+		#[test]
+		fn test_solve_quadratic_aes_64b(val in 0u64..) {
+			test_solve_quadratic::<AESTowerField64b>(val.into())
+		}
+
+		#[test]
+		fn test_solve_quadratic_polyval(val in 0u128..) {
+			test_solve_quadratic::<BinaryField128bPolyval>(val.into())
+		}
+
+		#[test]
+		fn test_sqrt_8b(val in 0u8..) {
+			test_sqrt::<BinaryField8b>(val.into())
+		}
+
+		#[test]
+		fn test_sqrt_128b(val in 0u128..) {
+			test_sqrt::<BinaryField128b>(val.into())
+		}
+
+		#[test]
+		fn test_sqrt_packed(val in 0u128..) {
+			let packed = PackedBinaryField16x8b::from_fn(|i| BinaryField8b::new((val >> (8 * i)) as u8));
+			let sqrt = Sqrt::sqrt(packed);
+			for i in 0..16 {
+				assert_eq!(sqrt.get(i), Sqrt::sqrt(packed.get(i)));
+			}
+			assert_eq!(PackedField::square(sqrt), packed);
+		}
+	}
+
 	#[test]
 	fn test_1b_to_choice() {
 		for i in 0..2 {
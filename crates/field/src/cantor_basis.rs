@@ -0,0 +1,210 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Conversions between the tower basis and a Cantor basis of binary tower fields.
+//!
+//! Field elements are natively represented in the tower basis, the multiplicative basis of the
+//! Fan–Paar tower construction in which each extension adjoins a root of
+//! $X_{\iota}^2 + X_{\iota - 1} X_{\iota} + 1$. A Cantor basis $\beta_0, \ldots, \beta_{n-1}$ of a
+//! field of degree $n = 2^k$ over $\mathbb{F}_2$ is instead defined by $\beta_0 = 1$ and
+//! $\beta_i^2 + \beta_i = \beta_{i-1}$. The subspace vanishing polynomials of the spans of its
+//! prefixes are sparse linearized polynomials, which removes most twiddle factor multiplications
+//! from the additive NTT over the novel polynomial basis.
+//!
+//! A [`CantorBasis`] precomputes the transformation matrices in both directions. An element
+//! converted to Cantor coordinates is stored in the same field type, with bit $i$ holding the
+//! coefficient of $\beta_i$; it must be converted back before any field arithmetic. Packed
+//! elements are converted with the packed kernels of [`PackedTransformationFactory`].
+
+use crate::{
+	linear_transformation::{
+		FieldLinearTransformation, PackedTransformationFactory, Transformation,
+	},
+	BinaryField1b, ExtensionField, Field, PackedField, TowerField,
+};
+
+/// A Cantor basis of a binary tower field, with the change of basis transformations to and from
+/// the tower basis.
+#[derive(Debug, Clone)]
+pub struct CantorBasis<F: TowerField> {
+	basis: Vec<F>,
+	tower_to_cantor: FieldLinearTransformation<F, Vec<F>>,
+	cantor_to_tower: FieldLinearTransformation<F, Vec<F>>,
+}
+
+impl<F: TowerField> CantorBasis<F> {
+	/// Computes a Cantor basis of `F` and the transformation matrices.
+	///
+	/// This takes $O(n^2)$ field operations, so the result should be computed once and reused.
+	pub fn new() -> Self {
+		assert!(F::N_BITS <= 128, "fields wider than 128 bits are not supported");
+
+		let mut basis = Vec::with_capacity(F::N_BITS);
+		basis.push(F::ONE);
+		for i in 1..F::N_BITS {
+			let beta = basis[i - 1]
+				.solve_quadratic()
+				.expect("a Cantor basis exists in fields of degree 2^k");
+			basis.push(beta);
+		}
+
+		// Invert the matrix whose columns are the Cantor basis elements with Gauss-Jordan
+		// elimination over GF(2), tracking the Cantor coordinates of each row.
+		let mut rows = basis
+			.iter()
+			.enumerate()
+			.map(|(i, &beta)| (to_bits(beta), 1u128 << i))
+			.collect::<Vec<_>>();
+		for bit in 0..F::N_BITS {
+			let pivot = (bit..rows.len())
+				.find(|&row| (rows[row].0 >> bit) & 1 == 1)
+				.expect("the Cantor basis elements are linearly independent");
+			rows.swap(bit, pivot);
+			let (pivot_value, pivot_coords) = rows[bit];
+			for (row, (value, coords)) in rows.iter_mut().enumerate() {
+				if row != bit && (*value >> bit) & 1 == 1 {
+					*value ^= pivot_value;
+					*coords ^= pivot_coords;
+				}
+			}
+		}
+		let to_cantor_bases = rows
+			.into_iter()
+			.map(|(_, coords)| from_bits(coords))
+			.collect();
+
+		Self {
+			tower_to_cantor: FieldLinearTransformation::new(to_cantor_bases),
+			cantor_to_tower: FieldLinearTransformation::new(basis.clone()),
+			basis,
+		}
+	}
+
+	/// The Cantor basis elements $\beta_0, \ldots, \beta_{n-1}$, in the tower basis.
+	pub fn basis(&self) -> &[F] {
+		&self.basis
+	}
+
+	/// Converts an element from the tower basis to Cantor coordinates.
+	pub fn tower_to_cantor(&self, value: F) -> F {
+		self.tower_to_cantor.transform(&value)
+	}
+
+	/// Converts an element from Cantor coordinates to the tower basis.
+	pub fn cantor_to_tower(&self, value: F) -> F {
+		self.cantor_to_tower.transform(&value)
+	}
+
+	/// The linear transformation from the tower basis to Cantor coordinates.
+	pub fn tower_to_cantor_transformation(&self) -> &FieldLinearTransformation<F, Vec<F>> {
+		&self.tower_to_cantor
+	}
+
+	/// The linear transformation from Cantor coordinates to the tower basis.
+	pub fn cantor_to_tower_transformation(&self) -> &FieldLinearTransformation<F, Vec<F>> {
+		&self.cantor_to_tower
+	}
+
+	/// Creates a packed transformation from the tower basis to Cantor coordinates.
+	///
+	/// Creation of the packed transformation is not cheap, so it is better to create it once and
+	/// reuse.
+	pub fn make_packed_tower_to_cantor<P>(&self) -> impl Transformation<P, P>
+	where
+		P: PackedField<Scalar = F> + PackedTransformationFactory<P>,
+	{
+		P::make_packed_transformation(self.tower_to_cantor.clone())
+	}
+
+	/// Creates a packed transformation from Cantor coordinates to the tower basis.
+	///
+	/// Creation of the packed transformation is not cheap, so it is better to create it once and
+	/// reuse.
+	pub fn make_packed_cantor_to_tower<P>(&self) -> impl Transformation<P, P>
+	where
+		P: PackedField<Scalar = F> + PackedTransformationFactory<P>,
+	{
+		P::make_packed_transformation(self.cantor_to_tower.clone())
+	}
+}
+
+impl<F: TowerField> Default for CantorBasis<F> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+fn to_bits<F: TowerField>(value: F) -> u128 {
+	ExtensionField::<BinaryField1b>::iter_bases(&value)
+		.enumerate()
+		.fold(0, |acc, (i, bit)| acc | (u128::from(bit == BinaryField1b::ONE) << i))
+}
+
+fn from_bits<F: TowerField>(bits: u128) -> F {
+	let bases = (0..F::N_BITS)
+		.map(|i| BinaryField1b::from(((bits >> i) & 1) as u8))
+		.collect::<Vec<_>>();
+	ExtensionField::<BinaryField1b>::from_bases(&bases)
+		.expect("the number of bases is the extension degree")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		arithmetic_traits::Square, BinaryField128b, BinaryField32b, BinaryField8b,
+		PackedBinaryField4x32b,
+	};
+	use proptest::prelude::*;
+
+	fn check_cantor_basis<F: TowerField>(basis: &CantorBasis<F>) {
+		assert_eq!(basis.basis()[0], F::ONE);
+		for window in basis.basis().windows(2) {
+			assert_eq!(Square::square(window[1]) + window[1], window[0]);
+		}
+		for (i, &beta) in basis.basis().iter().enumerate() {
+			assert_eq!(basis.tower_to_cantor(beta), from_bits::<F>(1 << i));
+		}
+	}
+
+	#[test]
+	fn test_cantor_basis_8b() {
+		let basis = CantorBasis::<BinaryField8b>::new();
+		check_cantor_basis(&basis);
+		for i in 0..=255 {
+			let value = BinaryField8b::new(i);
+			assert_eq!(basis.cantor_to_tower(basis.tower_to_cantor(value)), value);
+		}
+	}
+
+	#[test]
+	fn test_cantor_basis_128b() {
+		check_cantor_basis(&CantorBasis::<BinaryField128b>::new());
+	}
+
+	proptest! {
+		#[test]
+		fn test_cantor_roundtrip_32b(val in any::<u32>()) {
+			let basis = CantorBasis::<BinaryField32b>::new();
+			let value = BinaryField32b::new(val);
+			assert_eq!(basis.tower_to_cantor(basis.cantor_to_tower(value)), value);
+			assert_eq!(basis.cantor_to_tower(basis.tower_to_cantor(value)), value);
+		}
+
+		#[test]
+		fn test_packed_cantor_conversion(val in any::<[u32; 4]>()) {
+			let basis = CantorBasis::<BinaryField32b>::new();
+			let packed = PackedBinaryField4x32b::from_fn(|i| BinaryField32b::new(val[i]));
+
+			let converted = basis
+				.make_packed_tower_to_cantor::<PackedBinaryField4x32b>()
+				.transform(&packed);
+			for i in 0..4 {
+				assert_eq!(converted.get(i), basis.tower_to_cantor(packed.get(i)));
+			}
+			let restored = basis
+				.make_packed_cantor_to_tower::<PackedBinaryField4x32b>()
+				.transform(&converted);
+			assert_eq!(restored, packed);
+		}
+	}
+}
@@ -18,6 +18,7 @@ pub mod arithmetic_traits;
 pub mod as_packed_field;
 pub mod binary_field;
 mod binary_field_arithmetic;
+pub mod cantor_basis;
 pub mod error;
 pub mod extension;
 pub mod field;
@@ -33,6 +34,7 @@ mod tracing;
 pub mod transpose;
 pub mod underlier;
 pub mod util;
+pub mod wide_field;
 
 pub use aes_field::*;
 pub use binary_field::*;
@@ -45,3 +47,4 @@ pub use packed_binary_field::*;
 pub use packed_extension::*;
 pub use polyval::*;
 pub use transpose::{square_transpose, transpose_scalars, Error as TransposeError};
+pub use wide_field::*;
@@ -5,7 +5,7 @@
 //! Interfaces are derived from [`plonky2`](https://github.com/mir-protocol/plonky2).
 
 use super::{
-	arithmetic_traits::{Broadcast, MulAlpha, Square},
+	arithmetic_traits::{Broadcast, MulAlpha, Sqrt, Square},
 	binary_field_arithmetic::TowerFieldArithmetic,
 	Error,
 };
@@ -337,3 +337,11 @@ impl<F: Field> PackedField for F {
 pub trait PackedBinaryField: PackedField<Scalar: BinaryField> {}
 
 impl<PT> PackedBinaryField for PT where PT: PackedField<Scalar: BinaryField> {}
+
+/// In a field with $2^n$ elements squaring is a bijection of order $n$, so the square root is the
+/// squaring applied $n - 1$ times.
+impl<P: PackedBinaryField> Sqrt for P {
+	fn sqrt(self) -> Self {
+		(1..<P::Scalar as BinaryField>::N_BITS).fold(self, |value, _| PackedField::square(value))
+	}
+}
@@ -441,10 +441,13 @@ impl BinaryField for BinaryField128bPolyval {
 }
 
 impl TowerField for BinaryField128bPolyval {
-	fn mul_primitive(self, _iota: usize) -> Result<Self, Error> {
-		// This method could be implemented by multiplying by isomorphic alpha value
-		// But it's not being used as for now
-		unimplemented!()
+	fn mul_primitive(self, iota: usize) -> Result<Self, Error> {
+		if iota >= Self::TOWER_LEVEL {
+			return Err(Error::ExtensionDegreeMismatch);
+		}
+		// The isomorphic primitive element is the image of the canonical one, which is the basis
+		// element at index 2^iota.
+		Ok(self * BINARY_TO_POLYVAL_TRANSFORMATION.bases()[1 << iota])
 	}
 }
 
@@ -0,0 +1,486 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! 256-bit binary fields for challenge sampling at higher security levels.
+//!
+//! The fields are degree-2 extensions of a 128-bit field isomorphic to $T_7$, constructed as the
+//! next level of the binary tower: $T_8 = T_7[X_7] / (X_7^2 + X_6 X_7 + 1)$, where $X_6$ is the
+//! canonical primitive element of $T_7$ over $T_6$. [`BinaryField256b`] extends
+//! [`BinaryField128b`] and [`BinaryField256bPolyval`] extends [`BinaryField128bPolyval`], which
+//! is isomorphic to it.
+//!
+//! Multiplication uses the Karatsuba method, computing a product with three multiplications in
+//! the 128-bit subfield rather than four. Squaring needs only two subfield squarings, and
+//! inversion reduces to a single subfield inversion through the norm. Toom-Cook splitting has no
+//! advantage over Karatsuba for a degree-2 extension.
+
+use crate::{
+	arithmetic_traits::{InvertOrZero, Square},
+	underlier::{ScaledUnderlier, WithUnderlier},
+	BinaryField128b, BinaryField128bPolyval, Error, ExtensionField, Field, TowerField,
+};
+use bytemuck::{TransparentWrapper, Zeroable};
+use rand::{Rng, RngCore};
+use std::{
+	array,
+	fmt::{Display, Formatter},
+	iter::{Product, Sum},
+	ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+
+macro_rules! wide_tower_field {
+	($vis:vis $name:ident($subfield_name:ident)) => {
+		#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Zeroable, TransparentWrapper)]
+		#[repr(transparent)]
+		$vis struct $name(pub(crate) ScaledUnderlier<u128, 2>);
+
+		impl $name {
+			pub fn new(lo: u128, hi: u128) -> Self {
+				Self(ScaledUnderlier([lo, hi]))
+			}
+
+			/// Multiplies a subfield element by the primitive element of the subfield's topmost
+			/// extension, which is the linear coefficient of the defining polynomial.
+			#[inline]
+			fn mul_subfield_alpha(value: $subfield_name) -> $subfield_name {
+				value
+					.mul_primitive(<$subfield_name as TowerField>::TOWER_LEVEL - 1)
+					.expect("the subfield tower level is at least 1")
+			}
+		}
+
+		unsafe impl WithUnderlier for $name {
+			type Underlier = ScaledUnderlier<u128, 2>;
+
+			fn to_underlier(self) -> Self::Underlier {
+				TransparentWrapper::peel(self)
+			}
+
+			fn to_underlier_ref(&self) -> &Self::Underlier {
+				TransparentWrapper::peel_ref(self)
+			}
+
+			fn to_underlier_ref_mut(&mut self) -> &mut Self::Underlier {
+				TransparentWrapper::peel_mut(self)
+			}
+
+			fn to_underliers_ref(val: &[Self]) -> &[Self::Underlier] {
+				TransparentWrapper::peel_slice(val)
+			}
+
+			fn to_underliers_ref_mut(val: &mut [Self]) -> &mut [Self::Underlier] {
+				TransparentWrapper::peel_slice_mut(val)
+			}
+
+			fn from_underlier(val: Self::Underlier) -> Self {
+				TransparentWrapper::wrap(val)
+			}
+
+			fn from_underlier_ref(val: &Self::Underlier) -> &Self {
+				TransparentWrapper::wrap_ref(val)
+			}
+
+			fn from_underlier_ref_mut(val: &mut Self::Underlier) -> &mut Self {
+				TransparentWrapper::wrap_mut(val)
+			}
+
+			fn from_underliers_ref(val: &[Self::Underlier]) -> &[Self] {
+				TransparentWrapper::wrap_slice(val)
+			}
+
+			fn from_underliers_ref_mut(val: &mut [Self::Underlier]) -> &mut [Self] {
+				TransparentWrapper::wrap_slice_mut(val)
+			}
+		}
+
+		impl From<$name> for ($subfield_name, $subfield_name) {
+			#[inline]
+			fn from(src: $name) -> ($subfield_name, $subfield_name) {
+				let [lo, hi] = src.0 .0;
+				($subfield_name::from_underlier(lo), $subfield_name::from_underlier(hi))
+			}
+		}
+
+		impl From<($subfield_name, $subfield_name)> for $name {
+			#[inline]
+			fn from((lo, hi): ($subfield_name, $subfield_name)) -> Self {
+				$name::new(lo.to_underlier(), hi.to_underlier())
+			}
+		}
+
+		impl From<$subfield_name> for $name {
+			#[inline]
+			fn from(elem: $subfield_name) -> Self {
+				(elem, $subfield_name::ZERO).into()
+			}
+		}
+
+		impl TryFrom<$name> for $subfield_name {
+			type Error = ();
+
+			#[inline]
+			fn try_from(elem: $name) -> Result<Self, Self::Error> {
+				let (lo, hi) = elem.into();
+				if hi == $subfield_name::ZERO {
+					Ok(lo)
+				} else {
+					Err(())
+				}
+			}
+		}
+
+		impl Neg for $name {
+			type Output = Self;
+
+			fn neg(self) -> Self::Output {
+				self
+			}
+		}
+
+		impl Add<Self> for $name {
+			type Output = Self;
+
+			#[allow(clippy::suspicious_arithmetic_impl)]
+			fn add(self, rhs: Self) -> Self::Output {
+				let [a_lo, a_hi] = self.0 .0;
+				let [b_lo, b_hi] = rhs.0 .0;
+				$name::new(a_lo ^ b_lo, a_hi ^ b_hi)
+			}
+		}
+
+		impl Add<&Self> for $name {
+			type Output = Self;
+
+			fn add(self, rhs: &Self) -> Self::Output {
+				self + *rhs
+			}
+		}
+
+		impl Sub<Self> for $name {
+			type Output = Self;
+
+			#[allow(clippy::suspicious_arithmetic_impl)]
+			fn sub(self, rhs: Self) -> Self::Output {
+				self + rhs
+			}
+		}
+
+		impl Sub<&Self> for $name {
+			type Output = Self;
+
+			#[allow(clippy::suspicious_arithmetic_impl)]
+			fn sub(self, rhs: &Self) -> Self::Output {
+				self + *rhs
+			}
+		}
+
+		impl Mul<Self> for $name {
+			type Output = Self;
+
+			#[allow(clippy::suspicious_arithmetic_impl)]
+			fn mul(self, rhs: Self) -> Self::Output {
+				$crate::tracing::trace_multiplication!($name);
+
+				// (a0 + a1 X)(b0 + b1 X) = a0 b0 + a1 b1 + (a0 b1 + a1 b0 + alpha a1 b1) X, where
+				// the middle coefficient is obtained from one product by the Karatsuba identity.
+				let (a0, a1) = self.into();
+				let (b0, b1) = rhs.into();
+				let z0 = a0 * b0;
+				let z2 = a1 * b1;
+				let z1 = (a0 + a1) * (b0 + b1) - z0 - z2;
+				(z0 + z2, z1 + Self::mul_subfield_alpha(z2)).into()
+			}
+		}
+
+		impl Mul<&Self> for $name {
+			type Output = Self;
+
+			fn mul(self, rhs: &Self) -> Self::Output {
+				self * *rhs
+			}
+		}
+
+		impl AddAssign<Self> for $name {
+			fn add_assign(&mut self, rhs: Self) {
+				*self = *self + rhs;
+			}
+		}
+
+		impl AddAssign<&Self> for $name {
+			fn add_assign(&mut self, rhs: &Self) {
+				*self = *self + *rhs;
+			}
+		}
+
+		impl SubAssign<Self> for $name {
+			fn sub_assign(&mut self, rhs: Self) {
+				*self = *self - rhs;
+			}
+		}
+
+		impl SubAssign<&Self> for $name {
+			fn sub_assign(&mut self, rhs: &Self) {
+				*self = *self - *rhs;
+			}
+		}
+
+		impl MulAssign<Self> for $name {
+			fn mul_assign(&mut self, rhs: Self) {
+				*self = *self * rhs;
+			}
+		}
+
+		impl MulAssign<&Self> for $name {
+			fn mul_assign(&mut self, rhs: &Self) {
+				*self = *self * rhs;
+			}
+		}
+
+		impl Sum<Self> for $name {
+			fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+				iter.fold(Self::ZERO, |acc, x| acc + x)
+			}
+		}
+
+		impl<'a> Sum<&'a Self> for $name {
+			fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+				iter.fold(Self::ZERO, |acc, x| acc + x)
+			}
+		}
+
+		impl Product<Self> for $name {
+			fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+				iter.fold(Self::ONE, |acc, x| acc * x)
+			}
+		}
+
+		impl<'a> Product<&'a Self> for $name {
+			fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+				iter.fold(Self::ONE, |acc, x| acc * x)
+			}
+		}
+
+		impl ConstantTimeEq for $name {
+			fn ct_eq(&self, other: &Self) -> Choice {
+				self.0.ct_eq(&other.0)
+			}
+		}
+
+		impl ConditionallySelectable for $name {
+			fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+				let [a_lo, a_hi] = a.0 .0;
+				let [b_lo, b_hi] = b.0 .0;
+				$name::new(
+					u128::conditional_select(&a_lo, &b_lo, choice),
+					u128::conditional_select(&a_hi, &b_hi, choice),
+				)
+			}
+		}
+
+		impl Square for $name {
+			fn square(self) -> Self {
+				// (a0 + a1 X)^2 = a0^2 + a1^2 X^2 = a0^2 + a1^2 + alpha a1^2 X
+				let (a0, a1) = self.into();
+				let a0_square = Square::square(a0);
+				let a1_square = Square::square(a1);
+				(a0_square + a1_square, Self::mul_subfield_alpha(a1_square)).into()
+			}
+		}
+
+		impl InvertOrZero for $name {
+			fn invert_or_zero(self) -> Self {
+				// The product of a0 + a1 X with its conjugate (a0 + alpha a1) + a1 X is the norm
+				// a0 (a0 + alpha a1) + a1^2, which lies in the subfield.
+				let (a0, a1) = self.into();
+				let conjugate_lo = a0 + Self::mul_subfield_alpha(a1);
+				let norm = a0 * conjugate_lo + Square::square(a1);
+				let norm_inv = InvertOrZero::invert_or_zero(norm);
+				(conjugate_lo * norm_inv, a1 * norm_inv).into()
+			}
+		}
+
+		impl Field for $name {
+			const ZERO: Self = $name(ScaledUnderlier([0, 0]));
+			const ONE: Self = $name(ScaledUnderlier([1, 0]));
+
+			fn random(mut rng: impl RngCore) -> Self {
+				$name::new(rng.gen(), rng.gen())
+			}
+
+			fn double(&self) -> Self {
+				Self::ZERO
+			}
+		}
+
+		impl Display for $name {
+			fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+				let [lo, hi] = self.0 .0;
+				write!(f, "0x{hi:032x}{lo:032x}")
+			}
+		}
+
+		impl Add<$subfield_name> for $name {
+			type Output = Self;
+
+			#[inline]
+			fn add(self, rhs: $subfield_name) -> Self::Output {
+				self + Self::from(rhs)
+			}
+		}
+
+		impl Sub<$subfield_name> for $name {
+			type Output = Self;
+
+			#[inline]
+			fn sub(self, rhs: $subfield_name) -> Self::Output {
+				self - Self::from(rhs)
+			}
+		}
+
+		impl Mul<$subfield_name> for $name {
+			type Output = Self;
+
+			#[inline]
+			fn mul(self, rhs: $subfield_name) -> Self::Output {
+				$crate::tracing::trace_multiplication!($name, $subfield_name);
+
+				let (a0, a1) = self.into();
+				(a0 * rhs, a1 * rhs).into()
+			}
+		}
+
+		impl AddAssign<$subfield_name> for $name {
+			#[inline]
+			fn add_assign(&mut self, rhs: $subfield_name) {
+				*self = *self + rhs;
+			}
+		}
+
+		impl SubAssign<$subfield_name> for $name {
+			#[inline]
+			fn sub_assign(&mut self, rhs: $subfield_name) {
+				*self = *self - rhs;
+			}
+		}
+
+		impl MulAssign<$subfield_name> for $name {
+			#[inline]
+			fn mul_assign(&mut self, rhs: $subfield_name) {
+				*self = *self * rhs;
+			}
+		}
+
+		impl ExtensionField<$subfield_name> for $name {
+			type Iterator = array::IntoIter<$subfield_name, 2>;
+
+			const DEGREE: usize = 2;
+
+			#[inline]
+			fn basis(i: usize) -> Result<Self, Error> {
+				match i {
+					0 => Ok(Self::ONE),
+					1 => Ok(($subfield_name::ZERO, $subfield_name::ONE).into()),
+					_ => Err(Error::ExtensionDegreeMismatch),
+				}
+			}
+
+			#[inline]
+			fn from_bases(base_elems: &[$subfield_name]) -> Result<Self, Error> {
+				match base_elems {
+					[] => Ok(Self::ZERO),
+					[lo] => Ok(Self::from(*lo)),
+					[lo, hi] => Ok((*lo, *hi).into()),
+					_ => Err(Error::ExtensionDegreeMismatch),
+				}
+			}
+
+			#[inline]
+			fn iter_bases(&self) -> Self::Iterator {
+				let (lo, hi) = (*self).into();
+				[lo, hi].into_iter()
+			}
+		}
+	};
+}
+
+wide_tower_field!(pub BinaryField256b(BinaryField128b));
+wide_tower_field!(pub BinaryField256bPolyval(BinaryField128bPolyval));
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{linear_transformation::Transformation, polyval::BINARY_TO_POLYVAL_TRANSFORMATION};
+	use proptest::prelude::*;
+
+	/// Schoolbook multiplication with four subfield products, as a reference for Karatsuba.
+	fn schoolbook_mul<F: Field>((a0, a1): (F, F), (b0, b1): (F, F), alpha: F) -> (F, F) {
+		let z2 = a1 * b1;
+		(a0 * b0 + z2, a0 * b1 + a1 * b0 + alpha * z2)
+	}
+
+	fn polyval_256b(value: BinaryField256b) -> BinaryField256bPolyval {
+		let (lo, hi) = value.into();
+		(
+			BINARY_TO_POLYVAL_TRANSFORMATION.transform(&lo),
+			BINARY_TO_POLYVAL_TRANSFORMATION.transform(&hi),
+		)
+			.into()
+	}
+
+	#[test]
+	fn test_defining_polynomial() {
+		// X^2 = alpha X + 1
+		let x = <BinaryField256b as ExtensionField<BinaryField128b>>::basis(1).unwrap();
+		let alpha = BinaryField128b::new(1 << 64);
+		assert_eq!(Square::square(x), x * alpha + BinaryField256b::ONE);
+		assert_eq!(
+			BinaryField256b::new(0, 1).to_string(),
+			"0x0000000000000000000000000000000100000000000000000000000000000000"
+		);
+	}
+
+	proptest! {
+		#[test]
+		fn test_karatsuba_matches_schoolbook(a in any::<[u128; 2]>(), b in any::<[u128; 2]>()) {
+			let a = BinaryField256b::new(a[0], a[1]);
+			let b = BinaryField256b::new(b[0], b[1]);
+			let expected: BinaryField256b =
+				schoolbook_mul(a.into(), b.into(), BinaryField128b::new(1 << 64)).into();
+			assert_eq!(a * b, expected);
+			assert_eq!(a * b, b * a);
+		}
+
+		#[test]
+		fn test_square_and_invert(a in any::<[u128; 2]>()) {
+			let a = BinaryField256b::new(a[0], a[1]);
+			assert_eq!(Square::square(a), a * a);
+			match a.invert() {
+				Some(inv) => assert_eq!(a * inv, BinaryField256b::ONE),
+				None => assert_eq!(a, BinaryField256b::ZERO),
+			}
+		}
+
+		#[test]
+		fn test_polyval_isomorphism(a in any::<[u128; 2]>(), b in any::<[u128; 2]>()) {
+			let a = BinaryField256b::new(a[0], a[1]);
+			let b = BinaryField256b::new(b[0], b[1]);
+			assert_eq!(polyval_256b(a * b), polyval_256b(a) * polyval_256b(b));
+			assert_eq!(polyval_256b(Square::square(a)), Square::square(polyval_256b(a)));
+			assert_eq!(
+				polyval_256b(InvertOrZero::invert_or_zero(a)),
+				InvertOrZero::invert_or_zero(polyval_256b(a))
+			);
+		}
+
+		#[test]
+		fn test_subfield_ops(a in any::<[u128; 2]>(), b in any::<u128>()) {
+			let a = BinaryField256b::new(a[0], a[1]);
+			let b = BinaryField128b::new(b);
+			assert_eq!(a * b, a * BinaryField256b::from(b));
+			assert_eq!(a + b, a + BinaryField256b::from(b));
+			let bases = ExtensionField::<BinaryField128b>::iter_bases(&a).collect::<Vec<_>>();
+			assert_eq!(BinaryField256b::from_bases(&bases).unwrap(), a);
+			assert_eq!(BinaryField128b::try_from(BinaryField256b::from(b)), Ok(b));
+		}
+	}
+}
@@ -1,6 +1,9 @@
 // Copyright 2024 Ulvetanna Inc.
 
-use super::{super::hasher::Hasher, arch::Groestl256Core};
+use super::{
+	super::{hasher::Hasher, tracing::trace_hash_invocation},
+	arch::Groestl256Core,
+};
 use binius_field::{
 	arch::OptimalUnderlier256b,
 	as_packed_field::{PackScalar, PackedType},
@@ -107,6 +110,7 @@ fn compression_func(
 	h: PackedAESBinaryField64x8b,
 	m: PackedAESBinaryField64x8b,
 ) -> PackedAESBinaryField64x8b {
+	trace_hash_invocation!("groestl256");
 	let (a, b) = Groestl256Core.permutation_pq(h + m, m);
 	a + b + h
 }
@@ -237,6 +241,7 @@ where
 	F: BinaryField + From<AESTowerField8b> + Into<AESTowerField8b>,
 {
 	fn compress(&self, input: [GroestlDigest<F>; 2]) -> GroestlDigest<F> {
+		trace_hash_invocation!("groestl256_compression");
 		let input_as_slice_bin: [F; 64] = PackedFieldIndexable::unpack_scalars(&input)
 			.try_into()
 			.unwrap();
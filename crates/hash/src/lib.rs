@@ -3,6 +3,7 @@
 
 mod groestl;
 pub mod hasher;
+mod tracing;
 
 mod vision;
 mod vision_constants;
@@ -0,0 +1,13 @@
+// Copyright 2024 Ulvetanna Inc.
+
+/// Trace hash invocation event
+macro_rules! trace_hash_invocation {
+	($name: literal) => {
+		#[cfg(feature = "trace_hash_invocations")]
+		{
+			tracing::event!(name: "hash", tracing::Level::TRACE, { function = $name });
+		}
+	};
+}
+
+pub(crate) use trace_hash_invocation;
@@ -2,6 +2,7 @@
 
 use crate::{
 	hasher::{FixedLenHasher, HashError},
+	tracing::trace_hash_invocation,
 	vision_constants::{
 		AFFINE_FWD_AES, AFFINE_FWD_CONST_AES, AFFINE_INV_AES, AFFINE_INV_CONST_AES, NUM_ROUNDS,
 		ROUND_KEYS,
@@ -138,6 +139,7 @@ pub struct Vision32bPermutation {
 
 impl Permutation<[PackedAESBinaryField8x32b; 3]> for Vision32bPermutation {
 	fn permute_mut(&self, input: &mut [PackedAESBinaryField8x32b; 3]) {
+		trace_hash_invocation!("vision32b");
 		add_packed_768(input, &ROUND_KEYS_PACKED_AES[0]);
 		for r in 0..NUM_ROUNDS {
 			*input = self.sbox_step(*input, &INV_PACKED_TRANS_AES, *INV_CONST_AES);
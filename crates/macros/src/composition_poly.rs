@@ -28,13 +28,18 @@ impl ToTokens for CompositionPolyItem {
 			#[derive(Debug, Clone, Copy)]
 			struct #name;
 
+			impl #name {
+				/// The total degree of the composition, available in constant expressions.
+				pub const DEGREE: usize = #degree;
+			}
+
 			impl<P: binius_field::PackedField> binius_core::polynomial::multivariate::CompositionPoly<P> for #name {
 				fn n_vars(&self) -> usize {
 					#n_vars
 				}
 
 				fn degree(&self) -> usize {
-					#degree
+					Self::DEGREE
 				}
 
 				fn evaluate(&self, query: &[P]) -> Result<P, binius_core::polynomial::Error> {
@@ -91,6 +96,71 @@ impl Parse for CompositionPolyItem {
 	}
 }
 
+/// The target and bound of an `assert_composition_degree!` invocation.
+#[derive(Debug)]
+pub(crate) struct AssertCompositionDegreeItem {
+	pub target: CompositionDegreeTarget,
+	pub max_degree: syn::LitInt,
+}
+
+#[derive(Debug)]
+pub(crate) enum CompositionDegreeTarget {
+	/// A composition type defined with `composition_poly!`, checked through its `DEGREE` constant.
+	Type(syn::Path),
+	/// A composition written inline as `[x, y, z] = expr`, whose degree is computed by the macro.
+	Inline(syn::Expr),
+}
+
+impl ToTokens for AssertCompositionDegreeItem {
+	fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+		let max_degree = &self.max_degree;
+		let bound = match max_degree.base10_parse::<usize>() {
+			Ok(bound) => bound,
+			Err(err) => {
+				tokens.extend(err.to_compile_error());
+				return;
+			}
+		};
+		match &self.target {
+			CompositionDegreeTarget::Type(path) => {
+				let message = format!(
+					"composition `{}` has degree greater than {}",
+					path.to_token_stream(),
+					bound
+				);
+				tokens.extend(quote! {
+					const _: () = assert!(#path::DEGREE <= #max_degree, #message);
+				});
+			}
+			CompositionDegreeTarget::Inline(expr) => match poly_degree(expr) {
+				Ok(degree) if degree > bound => {
+					let message = format!("composition has degree {degree}, greater than {bound}");
+					tokens.extend(syn::Error::new(expr.span(), message).to_compile_error());
+				}
+				Ok(_) => {}
+				Err(err) => tokens.extend(err.to_compile_error()),
+			},
+		}
+	}
+}
+
+impl Parse for AssertCompositionDegreeItem {
+	fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+		let target = if input.peek(syn::token::Bracket) {
+			let content;
+			bracketed!(content in input);
+			content.parse_terminated(syn::Ident::parse, Token![,])?;
+			input.parse::<Token![=]>()?;
+			CompositionDegreeTarget::Inline(input.parse()?)
+		} else {
+			CompositionDegreeTarget::Type(input.parse()?)
+		};
+		input.parse::<Token![,]>()?;
+		let max_degree = input.parse()?;
+		Ok(Self { target, max_degree })
+	}
+}
+
 /// Make sure to run this before rewrite_literals as it will rewrite Lit to Path,
 /// which will mess up the degree
 fn poly_degree(expr: &syn::Expr) -> Result<usize, syn::Error> {
@@ -8,11 +8,14 @@ use quote::{quote, ToTokens};
 use std::collections::BTreeSet;
 use syn::{parse_macro_input, Data, DeriveInput, Fields};
 
-use crate::composition_poly::CompositionPolyItem;
+use crate::composition_poly::{AssertCompositionDegreeItem, CompositionPolyItem};
 
 /// Useful for concisely creating structs that implement CompositionPoly.
 /// This currently only supports creating composition polynomials of tower level 0.
 ///
+/// Named compositions also get a `DEGREE` associated constant with their total degree, which can
+/// be used in constant expressions, for example with [`assert_composition_degree!`].
+///
 /// ```
 /// use binius_macros::composition_poly;
 /// use binius_core::polynomial::CompositionPoly;
@@ -39,6 +42,41 @@ pub fn composition_poly(input: TokenStream) -> TokenStream {
 		.into()
 }
 
+/// Asserts at compile time that a composition has at most the given total degree.
+///
+/// The prover cost of a sumcheck grows with the degree of the composition, so an accidental
+/// degree blowup in a gadget constraint is caught when the gadget is compiled rather than observed
+/// as a slow proof. The composition is either a type defined with [`composition_poly!`] or an
+/// inline composition in the same syntax.
+///
+/// ```
+/// use binius_macros::{assert_composition_degree, composition_poly};
+///
+/// composition_poly!(BitwiseAnd[a, b, c] = a * b - c);
+/// assert_composition_degree!(BitwiseAnd, 2);
+///
+/// assert_composition_degree!([x, y, z] = x * y * z + x, 3);
+/// ```
+///
+/// ```compile_fail
+/// use binius_macros::{assert_composition_degree, composition_poly};
+///
+/// composition_poly!(Cube[x] = x * x * x);
+/// assert_composition_degree!(Cube, 2);
+/// ```
+///
+/// ```compile_fail
+/// use binius_macros::assert_composition_degree;
+///
+/// assert_composition_degree!([x, y] = (x + y) * (x + 1) * y, 2);
+/// ```
+#[proc_macro]
+pub fn assert_composition_degree(input: TokenStream) -> TokenStream {
+	parse_macro_input!(input as AssertCompositionDegreeItem)
+		.into_token_stream()
+		.into()
+}
+
 /// Implements `pub fn iter_oracles(&self) -> impl Iterator<Item = OracleId>`.
 ///
 /// Detects and includes fields with type `OracleId`, `[OracleId; N]`
@@ -0,0 +1,360 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Backend-aware allocation of large witness and scratch buffers.
+//!
+//! Scratch buffers are allocated through [`zeroed_vec`] as an [`AlignedVec`], whose storage is
+//! aligned to [`BUFFER_ALIGNMENT`] bytes so that rows never straddle a cache line boundary.
+//! Buffers that are handed off as a `Vec<T>`, such as the evaluations of a multilinear
+//! extension, are allocated through [`zeroed_std_vec`] and are only aligned to
+//! `align_of::<T>()`, since a `Vec` deallocates with the alignment of its element type.
+//!
+//! Both functions apply the process-wide [`AllocationPolicy`] after allocation. On Linux the
+//! policy can request transparent hugepages and bind the buffer pages to a NUMA node. On other
+//! platforms, and for buffers smaller than [`AllocationPolicy::min_advised_bytes`], the policy is
+//! ignored.
+
+use crate::env::boolean_env_flag_set;
+use bytemuck::Zeroable;
+use std::{
+	alloc::{self, Layout},
+	cmp::max,
+	env, fmt, mem,
+	ops::{Deref, DerefMut},
+	ptr::{self, NonNull},
+	slice,
+	sync::RwLock,
+};
+
+/// Alignment in bytes of the buffers allocated with [`zeroed_vec`].
+pub const BUFFER_ALIGNMENT: usize = 64;
+
+/// Process-wide policy applied to buffers allocated with [`zeroed_vec`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllocationPolicy {
+	/// Advise the kernel to back buffers with transparent hugepages.
+	pub hugepages: bool,
+	/// Bind buffer pages to the given NUMA node.
+	pub numa_node: Option<usize>,
+	/// Buffers smaller than this size in bytes are allocated without applying the policy.
+	pub min_advised_bytes: usize,
+}
+
+impl AllocationPolicy {
+	/// Default threshold for applying the policy, 2 MiB is the size of a hugepage on x86-64.
+	pub const DEFAULT_MIN_ADVISED_BYTES: usize = 1 << 21;
+
+	/// Reads the policy from the `BINIUS_HUGEPAGES` and `BINIUS_NUMA_NODE` environment variables.
+	pub fn from_env() -> Self {
+		Self {
+			hugepages: boolean_env_flag_set("BINIUS_HUGEPAGES"),
+			numa_node: env::var("BINIUS_NUMA_NODE")
+				.ok()
+				.and_then(|node| node.parse().ok()),
+			..Self::default()
+		}
+	}
+}
+
+impl Default for AllocationPolicy {
+	fn default() -> Self {
+		Self {
+			hugepages: false,
+			numa_node: None,
+			min_advised_bytes: Self::DEFAULT_MIN_ADVISED_BYTES,
+		}
+	}
+}
+
+static POLICY: RwLock<Option<AllocationPolicy>> = RwLock::new(None);
+
+/// Sets the process-wide allocation policy.
+pub fn set_allocation_policy(policy: AllocationPolicy) {
+	*POLICY
+		.write()
+		.expect("allocation policy lock is not poisoned") = Some(policy);
+}
+
+/// Returns the process-wide allocation policy, initializing it from the environment if unset.
+pub fn allocation_policy() -> AllocationPolicy {
+	if let Some(policy) = *POLICY
+		.read()
+		.expect("allocation policy lock is not poisoned")
+	{
+		return policy;
+	}
+	*POLICY
+		.write()
+		.expect("allocation policy lock is not poisoned")
+		.get_or_insert_with(AllocationPolicy::from_env)
+}
+
+/// A fixed-capacity vector whose storage is aligned to [`BUFFER_ALIGNMENT`] bytes.
+///
+/// The standard `Vec<T>` deallocates with the alignment of `T`, so it cannot own a buffer that was
+/// allocated with a larger alignment. `AlignedVec` owns the allocation instead and dereferences to
+/// a slice. It can be shrunk with [`AlignedVec::truncate`] but never grows.
+pub struct AlignedVec<T> {
+	ptr: NonNull<T>,
+	len: usize,
+	capacity: usize,
+}
+
+// SAFETY: AlignedVec uniquely owns its elements, like Vec.
+unsafe impl<T: Send> Send for AlignedVec<T> {}
+// SAFETY: AlignedVec only hands out shared references to its elements through &self, like Vec.
+unsafe impl<T: Sync> Sync for AlignedVec<T> {}
+
+impl<T> AlignedVec<T> {
+	fn layout(capacity: usize) -> Layout {
+		Layout::array::<T>(capacity)
+			.and_then(|layout| layout.align_to(max(BUFFER_ALIGNMENT, mem::align_of::<T>())))
+			.expect("buffer size does not overflow isize")
+	}
+
+	/// Allocates uninitialized storage for `capacity` elements, zeroing it if `zeroed` is set.
+	fn allocate(capacity: usize, zeroed: bool) -> NonNull<T> {
+		let layout = Self::layout(capacity);
+		if layout.size() == 0 {
+			// A dangling pointer at the alignment is valid for zero-sized accesses.
+			return NonNull::new(layout.align() as *mut T).expect("alignment is non-zero");
+		}
+		// SAFETY: the layout has non-zero size.
+		let ptr = unsafe {
+			if zeroed {
+				alloc::alloc_zeroed(layout)
+			} else {
+				alloc::alloc(layout)
+			}
+		};
+		match NonNull::new(ptr as *mut T) {
+			Some(ptr) => ptr,
+			None => alloc::handle_alloc_error(layout),
+		}
+	}
+
+	/// Creates a vector of `len` clones of `elem`.
+	pub fn from_elem(elem: T, len: usize) -> Self
+	where
+		T: Clone,
+	{
+		let ptr = Self::allocate(len, false);
+		for i in 0..len {
+			// SAFETY: the allocation holds `len` elements and `i` is in range.
+			unsafe { ptr::write(ptr.as_ptr().add(i), elem.clone()) };
+		}
+		Self {
+			ptr,
+			len,
+			capacity: len,
+		}
+	}
+
+	/// Shortens the vector to `len` elements, keeping the allocation.
+	///
+	/// Has no effect if `len` is not less than the current length.
+	pub fn truncate(&mut self, len: usize) {
+		if len >= self.len {
+			return;
+		}
+		let tail = ptr::slice_from_raw_parts_mut(
+			// SAFETY: `len` is less than the current length.
+			unsafe { self.ptr.as_ptr().add(len) },
+			self.len - len,
+		);
+		self.len = len;
+		// SAFETY: the tail elements are initialized and no longer reachable through the vector.
+		unsafe { ptr::drop_in_place(tail) };
+	}
+}
+
+impl<T> Deref for AlignedVec<T> {
+	type Target = [T];
+
+	fn deref(&self) -> &[T] {
+		// SAFETY: the first `len` elements are initialized.
+		unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+	}
+}
+
+impl<T> DerefMut for AlignedVec<T> {
+	fn deref_mut(&mut self) -> &mut [T] {
+		// SAFETY: the first `len` elements are initialized and uniquely borrowed.
+		unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+	}
+}
+
+impl<T> Drop for AlignedVec<T> {
+	fn drop(&mut self) {
+		let layout = Self::layout(self.capacity);
+		// SAFETY: the first `len` elements are initialized and are not used after this.
+		unsafe { ptr::drop_in_place(ptr::slice_from_raw_parts_mut(self.ptr.as_ptr(), self.len)) };
+		if layout.size() != 0 {
+			// SAFETY: the pointer was allocated in `allocate` with this layout.
+			unsafe { alloc::dealloc(self.ptr.as_ptr() as *mut u8, layout) };
+		}
+	}
+}
+
+impl<T: Clone> Clone for AlignedVec<T> {
+	fn clone(&self) -> Self {
+		let ptr = Self::allocate(self.len, false);
+		for (i, elem) in self.iter().enumerate() {
+			// SAFETY: the allocation holds `len` elements and `i` is in range.
+			unsafe { ptr::write(ptr.as_ptr().add(i), elem.clone()) };
+		}
+		Self {
+			ptr,
+			len: self.len,
+			capacity: self.len,
+		}
+	}
+}
+
+impl<T: fmt::Debug> fmt::Debug for AlignedVec<T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_list().entries(self.iter()).finish()
+	}
+}
+
+/// Allocates a zero-initialized vector aligned to [`BUFFER_ALIGNMENT`] bytes, applying the
+/// process-wide [`AllocationPolicy`].
+pub fn zeroed_vec<T: Zeroable>(len: usize) -> AlignedVec<T> {
+	let vec = AlignedVec {
+		ptr: AlignedVec::<T>::allocate(len, true),
+		len,
+		capacity: len,
+	};
+	advise(vec.as_ptr() as *const u8, len * mem::size_of::<T>());
+	vec
+}
+
+/// Allocates a zero-initialized `Vec`, applying the process-wide [`AllocationPolicy`].
+///
+/// Unlike [`zeroed_vec`], the buffer is only aligned to `align_of::<T>()`. Use this for buffers
+/// that are handed off to code that requires a `Vec<T>`.
+pub fn zeroed_std_vec<T: Zeroable>(len: usize) -> Vec<T> {
+	let vec = bytemuck::zeroed_vec(len);
+	advise(vec.as_ptr() as *const u8, len * mem::size_of::<T>());
+	vec
+}
+
+fn advise(ptr: *const u8, n_bytes: usize) {
+	let policy = allocation_policy();
+	if n_bytes >= policy.min_advised_bytes {
+		apply_policy(&policy, ptr, n_bytes);
+	}
+}
+
+#[cfg(target_os = "linux")]
+fn apply_policy(policy: &AllocationPolicy, ptr: *const u8, n_bytes: usize) {
+	// Advice only applies to whole pages, so restrict it to the pages inside the buffer.
+	// SAFETY: sysconf has no preconditions.
+	let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as usize;
+	let start = (ptr as usize).next_multiple_of(page_size);
+	let end = (ptr as usize + n_bytes) / page_size * page_size;
+	if start >= end {
+		return;
+	}
+	let addr = start as *mut libc::c_void;
+	let len = end - start;
+
+	// Failures below are deliberately ignored: the policy is a performance hint and the buffer is
+	// valid regardless of whether the kernel honors it.
+	if policy.hugepages {
+		// SAFETY: the range lies within a live allocation owned by the caller.
+		unsafe {
+			libc::madvise(addr, len, libc::MADV_HUGEPAGE);
+		}
+	}
+	if let Some(node) = policy.numa_node {
+		const MPOL_BIND: libc::c_long = 2;
+		const MPOL_MF_MOVE: libc::c_ulong = 1 << 1;
+		let word_bits = libc::c_ulong::BITS as usize;
+		let mut nodemask = vec![0 as libc::c_ulong; node / word_bits + 1];
+		nodemask[node / word_bits] |= 1 << (node % word_bits);
+		// SAFETY: the range lies within a live allocation owned by the caller and the nodemask
+		// buffer holds at least `node + 1` bits.
+		unsafe {
+			libc::syscall(
+				libc::SYS_mbind,
+				addr,
+				len,
+				MPOL_BIND,
+				nodemask.as_ptr(),
+				nodemask.len() * word_bits,
+				MPOL_MF_MOVE,
+			);
+		}
+	}
+}
+
+#[cfg(not(target_os = "linux"))]
+fn apply_policy(_policy: &AllocationPolicy, _ptr: *const u8, _n_bytes: usize) {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Overrides the process-wide policy until dropped, then restores the previous one, so that the
+	/// override does not leak into other tests of the same process.
+	struct PolicyGuard(Option<AllocationPolicy>);
+
+	impl PolicyGuard {
+		fn set(policy: AllocationPolicy) -> Self {
+			let previous = POLICY
+				.write()
+				.expect("allocation policy lock is not poisoned")
+				.replace(policy);
+			Self(previous)
+		}
+	}
+
+	impl Drop for PolicyGuard {
+		fn drop(&mut self) {
+			if let Ok(mut policy) = POLICY.write() {
+				*policy = self.0.take();
+			}
+		}
+	}
+
+	#[test]
+	fn test_zeroed_vec_is_zeroed() {
+		let previous = allocation_policy();
+		let guard = PolicyGuard::set(AllocationPolicy {
+			hugepages: true,
+			numa_node: None,
+			min_advised_bytes: 0,
+		});
+		let vec = zeroed_vec::<u64>(1 << 20);
+		assert_eq!(vec.len(), 1 << 20);
+		assert!(vec.iter().all(|&x| x == 0));
+
+		let vec = zeroed_std_vec::<u64>(1 << 20);
+		assert_eq!(vec.len(), 1 << 20);
+		assert!(vec.iter().all(|&x| x == 0));
+
+		drop(guard);
+		assert_eq!(allocation_policy(), previous);
+	}
+
+	#[test]
+	fn test_zeroed_vec_is_aligned() {
+		for len in [1, 3, 1 << 10] {
+			let vec = zeroed_vec::<u8>(len);
+			assert_eq!(vec.as_ptr() as usize % BUFFER_ALIGNMENT, 0);
+			assert_eq!(vec.clone().as_ptr() as usize % BUFFER_ALIGNMENT, 0);
+		}
+		let vec = zeroed_vec::<u64>(0);
+		assert!(vec.is_empty());
+		assert_eq!(vec.as_ptr() as usize % BUFFER_ALIGNMENT, 0);
+	}
+
+	#[test]
+	fn test_aligned_vec_truncate() {
+		let mut vec = AlignedVec::from_elem(vec![1u32], 4);
+		vec.truncate(2);
+		assert_eq!(&*vec, &[vec![1], vec![1]]);
+		vec.truncate(3);
+		assert_eq!(vec.len(), 2);
+	}
+}
@@ -2,12 +2,13 @@
 
 use std::ops::{AddAssign, Index, IndexMut};
 
-use bytemuck::{allocation::zeroed_vec, Zeroable};
+use crate::alloc::{zeroed_vec, AlignedVec};
+use bytemuck::Zeroable;
 
 /// 2D array with row-major layout.
 #[derive(Debug)]
 pub struct Array2D<T> {
-	data: Vec<T>,
+	data: AlignedVec<T>,
 	rows: usize,
 	cols: usize,
 }
@@ -16,7 +17,7 @@ impl<T: Default + Clone> Array2D<T> {
 	/// Create a new 2D array of the given size initialized with default values.
 	pub fn new(rows: usize, cols: usize) -> Self {
 		Self {
-			data: vec![T::default(); rows * cols],
+			data: AlignedVec::from_elem(T::default(), rows * cols),
 			rows,
 			cols,
 		}
@@ -0,0 +1,257 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Verifier cost profiling.
+//!
+//! [`CostReportLayer`] is a tracing layer that aggregates the cost events emitted by the field,
+//! hash and challenger crates, attributing each event to the innermost span it was emitted in.
+//! Since the verifiers are instrumented with one span per sub-protocol, the resulting
+//! [`CostReport`] breaks down the verification cost per sub-protocol.
+//!
+//! The following events are recognized:
+//!
+//! * `mul` — a field multiplication, emitted when `binius_field` is built with the
+//!   `trace_multiplications` feature.
+//! * `hash` — a hash compression or permutation, emitted when `binius_hash` is built with the
+//!   `trace_hash_invocations` feature.
+//! * `observe` and `sample` — a challenger observation or sample, emitted by
+//!   `binius_core::challenger::InstrumentedChallenger`, with the size in a `bytes` field. The
+//!   verifier also observes data it knows itself, such as the claims and the protocol parameters,
+//!   so observed bytes measure the transcript rather than the proof.
+//! * `proof` — proof data written by the prover and read by the verifier, emitted with
+//!   [`record_proof_bytes`] with the size in a `bytes` field.
+
+use std::{
+	collections::BTreeMap,
+	fmt,
+	sync::{Arc, Mutex},
+};
+use tracing::{
+	field::{Field, Visit},
+	Event, Subscriber,
+};
+use tracing_subscriber::{
+	layer::{Context, SubscriberExt},
+	registry::LookupSpan,
+	Layer,
+};
+
+/// Section name for events emitted outside of any span.
+pub const ROOT_SECTION: &str = "(root)";
+
+/// Costs accumulated within a single section.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SectionCost {
+	pub field_mults: u64,
+	pub hash_invocations: u64,
+	/// Bytes of proof data written by the prover.
+	pub proof_bytes: u64,
+	/// Bytes observed by the challenger, including data the verifier knows itself.
+	pub observed_bytes: u64,
+	pub sampled_bytes: u64,
+}
+
+impl SectionCost {
+	fn accumulate(&mut self, other: &Self) {
+		self.field_mults += other.field_mults;
+		self.hash_invocations += other.hash_invocations;
+		self.proof_bytes += other.proof_bytes;
+		self.observed_bytes += other.observed_bytes;
+		self.sampled_bytes += other.sampled_bytes;
+	}
+}
+
+/// Costs accumulated per section, keyed by span name.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CostReport {
+	pub sections: BTreeMap<&'static str, SectionCost>,
+}
+
+impl CostReport {
+	pub fn section(&self, name: &str) -> Option<&SectionCost> {
+		self.sections.get(name)
+	}
+
+	/// Returns the costs summed over all sections.
+	pub fn total(&self) -> SectionCost {
+		let mut total = SectionCost::default();
+		for cost in self.sections.values() {
+			total.accumulate(cost);
+		}
+		total
+	}
+}
+
+impl fmt::Display for CostReport {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let name_width = self
+			.sections
+			.keys()
+			.map(|name| name.len())
+			.max()
+			.unwrap_or(0)
+			.max("total".len());
+		writeln!(
+			f,
+			"{:<name_width$} {:>14} {:>12} {:>12} {:>14} {:>14}",
+			"section", "field_mults", "hashes", "proof_bytes", "observed_bytes", "sampled_bytes"
+		)?;
+		let total = self.total();
+		for (name, cost) in self.sections.iter().chain([(&"total", &total)]) {
+			writeln!(
+				f,
+				"{:<name_width$} {:>14} {:>12} {:>12} {:>14} {:>14}",
+				name,
+				cost.field_mults,
+				cost.hash_invocations,
+				cost.proof_bytes,
+				cost.observed_bytes,
+				cost.sampled_bytes
+			)?;
+		}
+		Ok(())
+	}
+}
+
+/// A tracing layer that aggregates cost events into a [`CostReport`].
+#[derive(Debug, Default, Clone)]
+pub struct CostReportLayer {
+	report: Arc<Mutex<CostReport>>,
+}
+
+impl CostReportLayer {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Returns a snapshot of the costs aggregated so far.
+	pub fn report(&self) -> CostReport {
+		self.report
+			.lock()
+			.expect("cost report lock is not poisoned")
+			.clone()
+	}
+}
+
+impl<S> Layer<S> for CostReportLayer
+where
+	S: Subscriber + for<'a> LookupSpan<'a>,
+{
+	fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+		let kind = event.metadata().name();
+		if !matches!(kind, "mul" | "hash" | "observe" | "sample" | "proof") {
+			return;
+		}
+
+		let section = ctx
+			.event_span(event)
+			.map_or(ROOT_SECTION, |span| span.name());
+
+		let mut bytes = BytesVisitor(0);
+		event.record(&mut bytes);
+
+		let mut report = self
+			.report
+			.lock()
+			.expect("cost report lock is not poisoned");
+		let cost = report.sections.entry(section).or_default();
+		match kind {
+			"mul" => cost.field_mults += 1,
+			"hash" => cost.hash_invocations += 1,
+			"observe" => cost.observed_bytes += bytes.0,
+			"sample" => cost.sampled_bytes += bytes.0,
+			"proof" => cost.proof_bytes += bytes.0,
+			_ => unreachable!(),
+		}
+	}
+}
+
+struct BytesVisitor(u64);
+
+impl Visit for BytesVisitor {
+	fn record_u64(&mut self, field: &Field, value: u64) {
+		if field.name() == "bytes" {
+			self.0 = value;
+		}
+	}
+
+	fn record_debug(&mut self, _field: &Field, _value: &dyn fmt::Debug) {}
+}
+
+/// Records that the verifier read `n_bytes` bytes of proof data written by the prover.
+///
+/// The bytes are attributed to the current span, like the other cost events.
+pub fn record_proof_bytes(n_bytes: usize) {
+	tracing::event!(name: "proof", tracing::Level::TRACE, { bytes = n_bytes });
+}
+
+/// Runs `f` with a subscriber that aggregates cost events and returns the resulting report.
+///
+/// The subscriber is installed for the current thread only, so costs incurred on other threads,
+/// such as rayon worker threads, are not counted.
+pub fn with_cost_report<R>(f: impl FnOnce() -> R) -> (R, CostReport) {
+	let layer = CostReportLayer::new();
+	let subscriber = tracing_subscriber::registry().with(layer.clone());
+	let result = tracing::subscriber::with_default(subscriber, f);
+	(result, layer.report())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use tracing::{debug_span, event, Level};
+
+	#[test]
+	fn test_costs_are_attributed_to_innermost_span() {
+		let ((), report) = with_cost_report(|| {
+			event!(name: "observe", Level::TRACE, { bytes = 16usize });
+			let _outer = debug_span!("outer::verify").entered();
+			event!(name: "mul", Level::TRACE, { lhs = "a", rhs = "b" });
+			event!(name: "observe", Level::TRACE, { bytes = 32usize });
+			record_proof_bytes(24);
+			{
+				let _inner = debug_span!("inner::verify").entered();
+				event!(name: "mul", Level::TRACE, { lhs = "a", rhs = "b" });
+				event!(name: "mul", Level::TRACE, { lhs = "a", rhs = "b" });
+				event!(name: "hash", Level::TRACE, { function = "groestl256" });
+				event!(name: "sample", Level::TRACE, { bytes = 8usize });
+			}
+			event!(name: "unrelated", Level::TRACE, { bytes = 1024usize });
+		});
+
+		assert_eq!(
+			report.section(ROOT_SECTION),
+			Some(&SectionCost {
+				observed_bytes: 16,
+				..Default::default()
+			})
+		);
+		assert_eq!(
+			report.section("outer::verify"),
+			Some(&SectionCost {
+				field_mults: 1,
+				proof_bytes: 24,
+				observed_bytes: 32,
+				..Default::default()
+			})
+		);
+		assert_eq!(
+			report.section("inner::verify"),
+			Some(&SectionCost {
+				field_mults: 2,
+				hash_invocations: 1,
+				sampled_bytes: 8,
+				..Default::default()
+			})
+		);
+		assert_eq!(
+			report.total(),
+			SectionCost {
+				field_mults: 3,
+				hash_invocations: 1,
+				proof_bytes: 24,
+				observed_bytes: 48,
+				sampled_bytes: 8,
+			}
+		);
+	}
+}
@@ -11,3 +11,25 @@ pub fn get_log_trace_size() -> Option<usize> {
 		Err(_) => None,
 	}
 }
+
+// Get the number of witness perturbation trials from the environment variable.
+// Panics if the environment variable is not a valid integer.
+pub fn get_witness_fuzz_trials() -> Option<usize> {
+	match std::env::var("BINIUS_WITNESS_FUZZ_TRIALS") {
+		Ok(val) => Some(
+			val.parse::<usize>()
+				.expect("BINIUS_WITNESS_FUZZ_TRIALS must be a valid integer"),
+		),
+		Err(_) => None,
+	}
+}
+
+// Get the path to record the external witness inputs to from the environment variable.
+pub fn get_witness_record_path() -> Option<String> {
+	std::env::var("BINIUS_WITNESS_RECORD").ok()
+}
+
+// Get the path to replay the external witness inputs from from the environment variable.
+pub fn get_witness_replay_path() -> Option<String> {
+	std::env::var("BINIUS_WITNESS_REPLAY").ok()
+}
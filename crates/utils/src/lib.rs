@@ -1,7 +1,9 @@
 // Copyright 2024 Ulvetanna Inc.
 
+pub mod alloc;
 pub mod array_2d;
 pub mod checked_arithmetics;
+pub mod cost_report;
 pub mod env;
 pub mod error_utils;
 pub mod examples;
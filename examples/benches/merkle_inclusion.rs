@@ -0,0 +1,117 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Benchmark of the `merkle_inclusion` example, which proves membership of a batch of leaves in a
+//! SHA-256 Merkle tree. It tracks the proving and verification time of the circuit as a
+//! performance regression test.
+
+#[path = "../merkle_inclusion/circuit.rs"]
+mod circuit;
+
+use binius_core::{
+	challenger::new_hasher_challenger, oracle::MultilinearOracleSet, poly_commit::tensor_pcs,
+	polynomial::IsomorphicEvaluationDomainFactory,
+};
+use binius_field::{
+	underlier::WithUnderlier, BinaryField128b, BinaryField16b, BinaryField1b,
+	PackedBinaryField128x1b,
+};
+use binius_hash::GroestlHasher;
+use circuit::{
+	generate_trace, prove, verify, ChannelOracle, FixedOracle, MerkleParams, MerkleTree,
+	TraceOracle,
+};
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use rand::thread_rng;
+
+type U = <PackedBinaryField128x1b as WithUnderlier>::Underlier;
+type FW = BinaryField128b;
+
+fn bench_merkle_inclusion(c: &mut Criterion) {
+	const SECURITY_BITS: usize = 100;
+	const LOG_INV_RATE: usize = 1;
+
+	let mut group = c.benchmark_group("merkle_inclusion");
+	group.sample_size(10);
+
+	// Trace sizes below 2^14 are rejected by `find_proof_size_optimal_pcs()`.
+	for (depth, log_batch) in [(16, 5), (20, 5), (20, 8)] {
+		let params = MerkleParams::new(depth, log_batch).unwrap();
+		let log_size = params.log_size();
+
+		let mut rng = thread_rng();
+		let tree = MerkleTree::random(params.depth, &mut rng);
+		let leaf_indices = params.random_leaf_indices(&mut rng);
+
+		let mut oracles = MultilinearOracleSet::new();
+		let fixed_oracle = FixedOracle::new(&mut oracles, params, tree.root()).unwrap();
+		let trace_oracle = TraceOracle::new(&mut oracles, params);
+		let channel_oracle = ChannelOracle::new(&mut oracles, params, &trace_oracle).unwrap();
+
+		let n_polys = oracles.committed_batch(trace_oracle.batch_id).n_polys;
+		let pcs = tensor_pcs::find_proof_size_optimal_pcs::<
+			U,
+			BinaryField1b,
+			BinaryField16b,
+			BinaryField16b,
+			BinaryField128b,
+		>(SECURITY_BITS, log_size, n_polys, LOG_INV_RATE, false)
+		.unwrap();
+
+		let witness = generate_trace::<U, FW>(
+			params,
+			&tree,
+			&leaf_indices,
+			&fixed_oracle,
+			&trace_oracle,
+			&channel_oracle,
+		)
+		.unwrap();
+
+		let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+		let domain_factory = IsomorphicEvaluationDomainFactory::<BinaryField128b>::default();
+
+		let prove_once = || {
+			prove::<_, BinaryField128b, FW, FW, _, _>(
+				log_size,
+				&mut oracles.clone(),
+				&fixed_oracle,
+				&trace_oracle,
+				&channel_oracle,
+				&pcs,
+				challenger.clone(),
+				witness.clone(),
+				domain_factory.clone(),
+			)
+			.unwrap()
+		};
+
+		let parameter = format!("depth={}/log_batch={}", params.depth, params.log_batch);
+		group.throughput(Throughput::Elements(leaf_indices.len() as u64));
+		group
+			.bench_function(BenchmarkId::new("prove", &parameter), |bench| bench.iter(&prove_once));
+		group.bench_function(BenchmarkId::new("verify", &parameter), |bench| {
+			bench.iter_batched(
+				&prove_once,
+				|proof| {
+					verify(
+						log_size,
+						&mut oracles.clone(),
+						&fixed_oracle,
+						&trace_oracle,
+						&channel_oracle,
+						&pcs,
+						challenger.clone(),
+						proof,
+					)
+					.unwrap()
+				},
+				BatchSize::PerIteration,
+			)
+		});
+	}
+
+	group.finish()
+}
+
+criterion_main!(merkle_inclusion);
+criterion_group!(merkle_inclusion, bench_merkle_inclusion);
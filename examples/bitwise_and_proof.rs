@@ -2,47 +2,58 @@
 
 use anyhow::Result;
 use binius_core::{
-	challenger::new_hasher_challenger,
+	challenger::{new_hasher_challenger, Transcript},
 	oracle::{BatchId, CommittedId, CompositePolyOracle, MultilinearOracleSet, OracleId},
+	params::{ProveParams, VerifyParams},
 	poly_commit::{tensor_pcs, PolyCommitScheme},
 	polynomial::{
 		EvaluationDomainFactory, IsomorphicEvaluationDomainFactory, MultilinearComposite,
 	},
 	protocols::{
-		abstract_sumcheck::standard_switchover_heuristic,
 		greedy_evalcheck::{self, GreedyEvalcheckProof, GreedyEvalcheckProveOutput},
+		sumcheck_v2::ProtocolFeatures,
 		zerocheck::{self, ZerocheckBatchProof, ZerocheckBatchProveOutput, ZerocheckClaim},
 	},
+	prover_pipeline::ProverPipeline,
 	witness::MultilinearExtensionIndex,
+	witness_fuzz::check_bit_flips_rejected,
 };
 use binius_field::{
 	as_packed_field::{PackScalar, PackedType},
 	underlier::{UnderlierType, WithUnderlier},
 	BinaryField, BinaryField128b, BinaryField128bPolyval, BinaryField16b, BinaryField1b,
-	PackedBinaryField128x1b, PackedField, PackedFieldIndexable, TowerField,
+	BinaryField8b, PackedBinaryField128x1b, PackedField, PackedFieldIndexable, TowerField,
 };
 use binius_hash::GroestlHasher;
-use binius_macros::{composition_poly, IterOracles};
+use binius_macros::{assert_composition_degree, composition_poly, IterOracles};
 use binius_utils::{
-	examples::get_log_trace_size, rayon::adjust_thread_pool, tracing::init_tracing,
+	examples::{get_log_trace_size, get_witness_fuzz_trials},
+	rayon::adjust_thread_pool,
+	tracing::init_tracing,
 };
 use bytemuck::{must_cast, must_cast_mut, Pod};
 use p3_challenger::{CanObserve, CanSample, CanSampleBits};
 use rand::thread_rng;
 use rayon::prelude::*;
-use std::{fmt::Debug, iter};
+use std::{fmt::Debug, iter, mem};
 use tracing::instrument;
 
 composition_poly!(BitwiseAndConstraint[a, b, c] = a * b - c);
+assert_composition_degree!(BitwiseAndConstraint, 2);
 
+/// Proves the bitwise AND constraint over the trace.
+///
+/// The challenger must be fresh. It is wrapped in a [`Transcript`] bound to the parameters before
+/// the prover sends any message.
 #[instrument(skip_all, level = "debug")]
 fn prove<U, PCS, CH>(
+	params: &ProveParams,
 	pcs: &PCS,
 	oracles: &mut MultilinearOracleSet<BinaryField128b>,
 	trace: &TraceOracle,
 	constraints: &[CompositePolyOracle<BinaryField128b>],
 	mut witness: MultilinearExtensionIndex<U, BinaryField128bPolyval>,
-	mut challenger: CH,
+	challenger: CH,
 	domain_factory: impl EvaluationDomainFactory<BinaryField128bPolyval>,
 ) -> Result<Proof<PCS::Commitment, PCS::Proof>>
 where
@@ -57,83 +68,127 @@ where
 		+ CanSampleBits<usize>,
 {
 	let log_size = trace.log_size;
-	let commit_span = tracing::debug_span!("commit").entered();
 	assert_eq!(pcs.n_vars(), log_size);
+	params.audit(oracles, constraints, [])?;
+
+	let transcript_params =
+		params.transcript_params(pcs.n_test_queries(), ProtocolFeatures::empty());
+	let mut challenger = Transcript::new::<BinaryField128b>(challenger, transcript_params);
 
 	assert_eq!(constraints.len(), 1);
 	let constraint = constraints[0].clone();
-
-	// Round 1
-	let commit_polys = oracles
-		.committed_oracle_ids(trace.batch_id)
-		.map(|oracle_id| witness.get::<BinaryField1b>(oracle_id))
-		.collect::<Result<Vec<_>, _>>()?;
-	let (abc_comm, abc_committed) = pcs.commit(&commit_polys).unwrap();
-	challenger.observe(abc_comm.clone());
-
-	drop(commit_span);
-
-	// Round 2
-	tracing::debug!("Proving zerocheck");
-	let zerocheck_witness = MultilinearComposite::new(
-		log_size,
-		BitwiseAndConstraint,
-		trace
-			.iter_oracles()
-			.map(|oracle_id| witness.get_multilin_poly(oracle_id))
-			.collect::<Result<_, _>>()?,
-	)?;
-	let zerocheck_claim = ZerocheckClaim { poly: constraint };
-	let switchover_fn = standard_switchover_heuristic(-2);
-
-	let ZerocheckBatchProveOutput {
-		evalcheck_claims,
-		proof: zerocheck_proof,
-	} = zerocheck::batch_prove(
-		[(zerocheck_claim, zerocheck_witness)],
-		domain_factory.clone(),
-		switchover_fn,
-		&mut challenger,
-	)?;
-
-	// Prove evaluation claims
-	let GreedyEvalcheckProveOutput {
-		same_query_claims,
-		proof: evalcheck_proof,
-	} = greedy_evalcheck::prove::<_, PackedType<U, BinaryField128bPolyval>, _, _>(
-		oracles,
-		&mut witness,
-		evalcheck_claims,
-		switchover_fn,
-		&mut challenger,
-		domain_factory,
-	)?;
-
-	assert_eq!(same_query_claims.len(), 1);
-	let (_, same_query_pcs_claim) = same_query_claims
-		.into_iter()
-		.next()
-		.expect("length is asserted to be 1");
-
-	let commit_polys = oracles
-		.committed_oracle_ids(trace.batch_id)
-		.map(|oracle_id| witness.get::<BinaryField1b>(oracle_id))
-		.collect::<Result<Vec<_>, _>>()?;
-
-	// Prove commitment openings
-	let abc_eval_proof = pcs.prove_evaluation(
-		&mut challenger,
-		&abc_committed,
-		&commit_polys,
-		&same_query_pcs_claim.eval_point,
-	)?;
-
-	Ok(Proof {
-		abc_comm,
-		abc_eval_proof,
-		zerocheck_proof,
-		evalcheck_proof,
-	})
+	let switchover_fn = params.switchover_fn();
+
+	let (proof, report) = ProverPipeline::new(witness)
+		.then("commit", |witness| {
+			let commit_polys = oracles
+				.committed_oracle_ids(trace.batch_id)
+				.map(|oracle_id| witness.get::<BinaryField1b>(oracle_id))
+				.collect::<Result<Vec<_>, _>>()?;
+			let (abc_comm, abc_committed) = pcs.commit(&commit_polys)?;
+			challenger.observe(abc_comm.clone());
+			Ok((witness, abc_comm, abc_committed))
+		})?
+		.then("zerocheck", |(witness, abc_comm, abc_committed)| {
+			let zerocheck_witness = MultilinearComposite::new(
+				log_size,
+				BitwiseAndConstraint,
+				trace
+					.iter_oracles()
+					.map(|oracle_id| witness.get_multilin_poly(oracle_id))
+					.collect::<Result<_, _>>()?,
+			)?;
+			let zerocheck_claim = ZerocheckClaim::new(constraint)?;
+			let zerocheck_output = zerocheck::batch_prove(
+				[(zerocheck_claim, zerocheck_witness)],
+				domain_factory.clone(),
+				switchover_fn,
+				&mut challenger,
+			)?;
+			Ok((witness, abc_comm, abc_committed, zerocheck_output))
+		})?
+		.then("evalcheck", |(mut witness, abc_comm, abc_committed, zerocheck_output)| {
+			let ZerocheckBatchProveOutput {
+				evalcheck_claims,
+				proof: zerocheck_proof,
+			} = zerocheck_output;
+
+			let GreedyEvalcheckProveOutput {
+				same_query_claims,
+				proof: evalcheck_proof,
+			} = greedy_evalcheck::prove::<_, PackedType<U, BinaryField128bPolyval>, _, _>(
+				oracles,
+				&mut witness,
+				evalcheck_claims,
+				switchover_fn,
+				&mut challenger,
+				domain_factory,
+			)?;
+
+			assert_eq!(same_query_claims.len(), 1);
+			let (_, same_query_pcs_claim) = same_query_claims
+				.into_iter()
+				.next()
+				.expect("length is asserted to be 1");
+
+			// Only the committed polynomials are opened, so the witnesses derived during
+			// evalcheck can be freed before the opening phase.
+			let released = witness.transition_phase(oracles.committed_oracle_ids(trace.batch_id));
+			tracing::debug!(
+				n_buffers = released.len(),
+				bytes = released
+					.iter()
+					.map(|underliers| mem::size_of_val(&**underliers))
+					.sum::<usize>(),
+				"Released witness buffers after evalcheck"
+			);
+			drop(released);
+
+			Ok((
+				witness,
+				abc_comm,
+				abc_committed,
+				same_query_pcs_claim,
+				zerocheck_proof,
+				evalcheck_proof,
+			))
+		})?
+		.then(
+			"opening",
+			|(
+				witness,
+				abc_comm,
+				abc_committed,
+				same_query_pcs_claim,
+				zerocheck_proof,
+				evalcheck_proof,
+			)| {
+				let commit_polys = oracles
+					.committed_oracle_ids(trace.batch_id)
+					.map(|oracle_id| witness.get::<BinaryField1b>(oracle_id))
+					.collect::<Result<Vec<_>, _>>()?;
+
+				let abc_eval_proof = pcs.prove_evaluation(
+					&mut challenger,
+					&abc_committed,
+					&commit_polys,
+					&same_query_pcs_claim.eval_point,
+				)?;
+
+				Ok(Proof {
+					abc_comm,
+					abc_eval_proof,
+					zerocheck_proof,
+					evalcheck_proof,
+				})
+			},
+		)?
+		.finish();
+
+	for timing in report.phases() {
+		tracing::debug!(phase = timing.name(), elapsed = ?timing.elapsed(), "Finished proving phase");
+	}
+	Ok(proof)
 }
 
 struct Proof<C, P> {
@@ -143,14 +198,19 @@ struct Proof<C, P> {
 	evalcheck_proof: GreedyEvalcheckProof<BinaryField128b>,
 }
 
+/// Verifies a proof of the bitwise AND constraint.
+///
+/// The challenger must be fresh. The verifier binds it to its own parameters and commitment
+/// scheme, so a proof produced under different parameters is rejected.
 #[instrument(skip_all, level = "debug")]
 fn verify<PCS, CH>(
+	params: &VerifyParams,
 	log_size: usize,
 	pcs: &PCS,
 	trace: &mut MultilinearOracleSet<BinaryField128b>,
 	constraints: &[CompositePolyOracle<BinaryField128b>],
 	proof: Proof<PCS::Commitment, PCS::Proof>,
-	mut challenger: CH,
+	challenger: CH,
 ) -> Result<()>
 where
 	PCS: PolyCommitScheme<PackedBinaryField128x1b, BinaryField128b>,
@@ -163,6 +223,10 @@ where
 {
 	assert_eq!(pcs.n_vars(), log_size);
 
+	let transcript_params =
+		params.transcript_params(pcs.n_test_queries(), ProtocolFeatures::empty());
+	let mut challenger = Transcript::new::<BinaryField128b>(challenger, transcript_params);
+
 	assert_eq!(constraints.len(), 1);
 	let constraint = constraints[0].clone();
 
@@ -177,7 +241,7 @@ where
 	challenger.observe(abc_comm.clone());
 
 	// Run zerocheck protocol
-	let zerocheck_claim = ZerocheckClaim { poly: constraint };
+	let zerocheck_claim = ZerocheckClaim::new(constraint)?;
 
 	let evalcheck_claims =
 		zerocheck::batch_verify([zerocheck_claim], zerocheck_proof, &mut challenger)?;
@@ -298,10 +362,14 @@ fn main() {
 
 	init_tracing().expect("failed to initialize tracing");
 
-	const SECURITY_BITS: usize = 100;
+	let params = ProveParams::builder()
+		.security_bits(100)
+		.log_inv_rate(1)
+		.strict_audit(true)
+		.build()
+		.expect("parameters are valid");
 
 	let log_size = get_log_trace_size().unwrap_or(20);
-	let log_inv_rate = 1;
 
 	type U = <PackedBinaryField128x1b as WithUnderlier>::Underlier;
 
@@ -310,13 +378,13 @@ fn main() {
 	let batch = oracles.committed_batch(trace_oracle.batch_id);
 
 	// Set up the public parameters
-	let pcs = tensor_pcs::find_proof_size_optimal_pcs::<
+	let pcs = tensor_pcs::find_proof_size_optimal_pcs_with_params::<
 		U,
 		BinaryField1b,
 		BinaryField16b,
 		BinaryField16b,
 		BinaryField128b,
-	>(SECURITY_BITS, batch.n_vars, batch.n_polys, log_inv_rate, false)
+	>(&params.pcs(), batch.n_vars, batch.n_polys)
 	.unwrap();
 
 	tracing::debug!(
@@ -329,19 +397,70 @@ fn main() {
 	let constraints = make_constraints(log_size, &oracles);
 
 	let witness = generate_trace::<U, BinaryField128bPolyval>(log_size, &trace_oracle).unwrap();
-	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	// The prover and the verifier each start from a fresh challenger and bind their own
+	// parameters into it.
+	let new_challenger = new_hasher_challenger::<BinaryField8b, GroestlHasher<BinaryField8b>>;
+	let verify_params = params.verify_params();
 	let domain_factory = IsomorphicEvaluationDomainFactory::<BinaryField128b>::default();
 
-	let proof = prove(
+	let proof = params
+		.install(|| {
+			prove(
+				&params,
+				&pcs,
+				&mut oracles.clone(),
+				&trace_oracle,
+				&constraints,
+				witness.clone(),
+				new_challenger(),
+				domain_factory.clone(),
+			)
+		})
+		.unwrap();
+
+	verify(
+		&verify_params,
+		log_size,
 		&pcs,
 		&mut oracles.clone(),
-		&trace_oracle,
 		&constraints,
-		witness,
-		challenger.clone(),
-		domain_factory,
+		proof,
+		new_challenger(),
 	)
 	.unwrap();
 
-	verify(log_size, &pcs, &mut oracles.clone(), &constraints, proof, challenger.clone()).unwrap();
+	if let Some(n_trials) = get_witness_fuzz_trials() {
+		tracing::info!(n_trials, "Checking that perturbed witnesses are rejected");
+		let committed_ids = oracles
+			.committed_oracle_ids(trace_oracle.batch_id)
+			.collect::<Vec<_>>();
+		check_bit_flips_rejected::<_, _, BinaryField1b, _, _>(
+			&witness,
+			&committed_ids,
+			n_trials,
+			&mut thread_rng(),
+			|witness| {
+				let proof = prove(
+					&params,
+					&pcs,
+					&mut oracles.clone(),
+					&trace_oracle,
+					&constraints,
+					witness,
+					new_challenger(),
+					domain_factory.clone(),
+				)?;
+				verify(
+					&verify_params,
+					log_size,
+					&pcs,
+					&mut oracles.clone(),
+					&constraints,
+					proof,
+					new_challenger(),
+				)
+			},
+		)
+		.unwrap();
+	}
 }
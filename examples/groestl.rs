@@ -843,13 +843,11 @@ where
 		.iter_oracle_ids()
 		.map(|id| oracles.oracle(id))
 		.collect::<Vec<_>>();
-	let zerocheck_claim = ZerocheckClaim {
-		poly: CompositePolyOracle::new(
-			trace_oracle.log_size,
-			zerocheck_column_oracles,
-			mix_composition_verifier,
-		)?,
-	};
+	let zerocheck_claim = ZerocheckClaim::new(CompositePolyOracle::new(
+		trace_oracle.log_size,
+		zerocheck_column_oracles,
+		mix_composition_verifier,
+	)?)?;
 
 	let zerocheck_witness = MultilinearComposite::new(
 		zerocheck_claim.n_vars(),
@@ -967,13 +965,11 @@ where
 		.iter_oracle_ids()
 		.map(|id| oracles.oracle(id))
 		.collect::<Vec<_>>();
-	let zerocheck_claim = ZerocheckClaim {
-		poly: CompositePolyOracle::new(
-			trace_oracle.log_size,
-			zerocheck_column_oracles,
-			mix_composition,
-		)?,
-	};
+	let zerocheck_claim = ZerocheckClaim::new(CompositePolyOracle::new(
+		trace_oracle.log_size,
+		zerocheck_column_oracles,
+		mix_composition,
+	)?)?;
 
 	let evalcheck_claims =
 		zerocheck::batch_verify([zerocheck_claim], zerocheck_proof, &mut challenger)?;
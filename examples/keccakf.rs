@@ -35,6 +35,7 @@ use binius_core::{
 		zerocheck::{self, ZerocheckBatchProof, ZerocheckBatchProveOutput, ZerocheckClaim},
 	},
 	witness::MultilinearExtensionIndex,
+	witness_fuzz::check_bit_flips_rejected,
 };
 use binius_field::{
 	arch::packed_64::PackedBinaryField64x1b,
@@ -46,7 +47,9 @@ use binius_field::{
 use binius_hash::GroestlHasher;
 use binius_macros::{composition_poly, IterOracles};
 use binius_utils::{
-	examples::get_log_trace_size, rayon::adjust_thread_pool, tracing::init_tracing,
+	examples::{get_log_trace_size, get_witness_fuzz_trials},
+	rayon::adjust_thread_pool,
+	tracing::init_tracing,
 };
 use bytemuck::{must_cast_slice, must_cast_slice_mut, Pod};
 use bytesize::ByteSize;
@@ -459,13 +462,11 @@ where
 		.collect();
 
 	// Zerocheck
-	let zerocheck_claim = ZerocheckClaim {
-		poly: CompositePolyOracle::new(
-			log_size,
-			zerocheck_column_oracles,
-			mix_composition_verifier,
-		)?,
-	};
+	let zerocheck_claim = ZerocheckClaim::new(CompositePolyOracle::new(
+		log_size,
+		zerocheck_column_oracles,
+		mix_composition_verifier,
+	)?)?;
 
 	let zerocheck_witness = MultilinearComposite::new(
 		log_size,
@@ -561,9 +562,11 @@ where
 	let zerocheck_column_oracles = constrained_oracles(fixed_oracle, trace_oracle)
 		.map(|id| oracles.oracle(id))
 		.collect();
-	let zerocheck_claim = ZerocheckClaim {
-		poly: CompositePolyOracle::new(log_size, zerocheck_column_oracles, mix_composition)?,
-	};
+	let zerocheck_claim = ZerocheckClaim::new(CompositePolyOracle::new(
+		log_size,
+		zerocheck_column_oracles,
+		mix_composition,
+	)?)?;
 
 	let evalcheck_claims =
 		zerocheck::batch_verify([zerocheck_claim], zerocheck_proof, &mut challenger)?;
@@ -737,8 +740,8 @@ fn main() {
 		&trace_oracle,
 		&pcs,
 		challenger.clone(),
-		witness,
-		domain_factory,
+		witness.clone(),
+		domain_factory.clone(),
 	)
 	.unwrap();
 
@@ -752,4 +755,39 @@ fn main() {
 		proof,
 	)
 	.unwrap();
+
+	if let Some(n_trials) = get_witness_fuzz_trials() {
+		tracing::info!(n_trials, "Checking that perturbed witnesses are rejected");
+		let committed_ids = oracles
+			.committed_oracle_ids(trace_oracle.batch_id)
+			.collect::<Vec<_>>();
+		check_bit_flips_rejected::<_, _, BinaryField1b, _, _>(
+			&witness,
+			&committed_ids,
+			n_trials,
+			&mut thread_rng(),
+			|witness| {
+				let proof = prove::<_, BinaryField128b, FW, FW, _, _>(
+					log_size,
+					&mut oracles.clone(),
+					&fixed_oracle,
+					&trace_oracle,
+					&pcs,
+					challenger.clone(),
+					witness,
+					domain_factory.clone(),
+				)?;
+				verify(
+					log_size,
+					&mut oracles.clone(),
+					&fixed_oracle,
+					&trace_oracle,
+					&pcs,
+					challenger.clone(),
+					proof,
+				)
+			},
+		)
+		.unwrap();
+	}
 }
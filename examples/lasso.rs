@@ -18,6 +18,7 @@ use binius_core::{
 		zerocheck::{self, ZerocheckBatchProof, ZerocheckBatchProveOutput},
 	},
 	witness::MultilinearExtensionIndex,
+	witness_scheduler::WitnessScheduler,
 };
 use binius_field::{
 	as_packed_field::{PackScalar, PackedType},
@@ -31,7 +32,7 @@ use binius_utils::{
 };
 use itertools::izip;
 use rand::thread_rng;
-use std::{fmt::Debug, marker::PhantomData};
+use std::{collections::HashMap, fmt::Debug, marker::PhantomData};
 use tracing::instrument;
 
 type B1 = BinaryField1b;
@@ -120,6 +121,15 @@ fn make_underliers<U: UnderlierType + PackScalar<FS>, FS: Field>(log_size: usize
 	let packing_log_width = PackedType::<U, FS>::LOG_WIDTH;
 	vec![U::default(); 1 << (log_size - packing_log_width)]
 }
+fn random_underliers<U: UnderlierType + PackScalar<FS>, FS: Field>(log_size: usize) -> Vec<U> {
+	let mut underliers = make_underliers::<_, FS>(log_size);
+	let mut rng = thread_rng();
+	PackedType::<U, FS>::from_underliers_ref_mut(underliers.as_mut_slice())
+		.iter_mut()
+		.for_each(|x| *x = PackedType::<U, FS>::random(&mut rng));
+	underliers
+}
+
 #[instrument(skip_all, level = "debug")]
 fn generate_trace<U, FW>(log_size: usize, trace_oracle: &TraceOracle) -> Result<TraceWitness<U, FW>>
 where
@@ -129,59 +139,69 @@ where
 	PackedType<U, B32>: PackedFieldIndexable,
 	FW: BinaryField + ExtensionField<B8> + ExtensionField<B16> + ExtensionField<B32>,
 {
-	let mut mult_a = make_underliers::<_, B8>(log_size);
-	let mut mult_b = make_underliers::<_, B8>(log_size);
-	let mut product = make_underliers::<_, B16>(log_size);
-	let mut lookup_u = make_underliers::<_, B32>(log_size);
-	let mut lookup_t = make_underliers::<_, B32>(T_LOG_SIZE);
-	let mut u_to_t_mapping = vec![0; 1 << log_size];
-
-	let mut rng = thread_rng();
-
-	PackedType::<U, B8>::from_underliers_ref_mut(mult_a.as_mut_slice())
-		.iter_mut()
-		.for_each(|a| *a = PackedType::<U, B8>::random(&mut rng));
-	PackedType::<U, B8>::from_underliers_ref_mut(mult_b.as_mut_slice())
-		.iter_mut()
-		.for_each(|b| *b = PackedType::<U, B8>::random(&mut rng));
-
-	let mult_a_scalars = underliers_unpack_scalars::<_, B8>(mult_a.as_slice());
-	let mult_b_scalars = underliers_unpack_scalars::<_, B8>(mult_b.as_slice());
-	let product_scalars = underliers_unpack_scalars_mut::<_, B16>(product.as_mut_slice());
-	let lookup_u_scalars = underliers_unpack_scalars_mut::<_, B32>(lookup_u.as_mut_slice());
-	let lookup_t_scalars = underliers_unpack_scalars_mut::<_, B32>(lookup_t.as_mut_slice());
-
-	for (a, b, lookup_u, product, u_to_t) in izip!(
-		mult_a_scalars,
-		mult_b_scalars,
-		lookup_u_scalars.iter_mut(),
-		product_scalars.iter_mut(),
-		u_to_t_mapping.iter_mut()
-	) {
-		let a_int = u8::from(*a) as usize;
-		let b_int = u8::from(*b) as usize;
-		let ab_product = a_int * b_int;
-		let lookup_index = a_int << 8 | b_int;
-		*lookup_u = BinaryField32b::new((lookup_index << 16 | ab_product) as u32);
-		*product = BinaryField16b::new(ab_product as u16);
-		*u_to_t = lookup_index;
-	}
-
-	for (i, lookup_t) in lookup_t_scalars.iter_mut().enumerate() {
-		let a_int = (i >> 8) & 0xff;
-		let b_int = i & 0xff;
-		let ab_product = a_int * b_int;
-		let lookup_index = a_int << 8 | b_int;
-		*lookup_t = BinaryField32b::new((lookup_index << 16 | ab_product) as u32);
-	}
-
+	let TraceOracle {
+		mult_a,
+		mult_b,
+		product,
+		lookup_t,
+		lookup_u,
+		..
+	} = *trace_oracle;
+
+	// The operands and the table are independent, and the products and lookup values depend only
+	// on the operands, so the columns are generated in two parallel waves.
+	let mut scheduler = WitnessScheduler::<Vec<U>>::new();
+	scheduler
+		.add_task("mult_a", [], [mult_a], |_| Ok(vec![random_underliers::<U, B8>(log_size)]))
+		.add_task("mult_b", [], [mult_b], |_| Ok(vec![random_underliers::<U, B8>(log_size)]))
+		.add_task("lookup_t", [], [lookup_t], |_| {
+			let mut lookup_t = make_underliers::<U, B32>(T_LOG_SIZE);
+			let lookup_t_scalars = underliers_unpack_scalars_mut::<_, B32>(lookup_t.as_mut_slice());
+			for (i, lookup_t) in lookup_t_scalars.iter_mut().enumerate() {
+				let a_int = (i >> 8) & 0xff;
+				let b_int = i & 0xff;
+				let ab_product = a_int * b_int;
+				let lookup_index = a_int << 8 | b_int;
+				*lookup_t = BinaryField32b::new((lookup_index << 16 | ab_product) as u32);
+			}
+			Ok(vec![lookup_t])
+		})
+		.add_task("mult", [mult_a, mult_b], [product, lookup_u], |columns| {
+			let mut product = make_underliers::<U, B16>(log_size);
+			let mut lookup_u = make_underliers::<U, B32>(log_size);
+			for (a, b, lookup_u, product) in izip!(
+				underliers_unpack_scalars::<_, B8>(&columns[&mult_a]),
+				underliers_unpack_scalars::<_, B8>(&columns[&mult_b]),
+				underliers_unpack_scalars_mut::<_, B32>(lookup_u.as_mut_slice()),
+				underliers_unpack_scalars_mut::<_, B16>(product.as_mut_slice()),
+			) {
+				let a_int = u8::from(*a) as usize;
+				let b_int = u8::from(*b) as usize;
+				let ab_product = a_int * b_int;
+				let lookup_index = a_int << 8 | b_int;
+				*lookup_u = BinaryField32b::new((lookup_index << 16 | ab_product) as u32);
+				*product = BinaryField16b::new(ab_product as u16);
+			}
+			Ok(vec![product, lookup_u])
+		});
+	let mut columns = scheduler.run(HashMap::new())?;
+
+	let u_to_t_mapping = izip!(
+		underliers_unpack_scalars::<_, B8>(&columns[&mult_a]),
+		underliers_unpack_scalars::<_, B8>(&columns[&mult_b]),
+	)
+	.map(|(a, b)| (u8::from(*a) as usize) << 8 | u8::from(*b) as usize)
+	.collect();
+
+	let mut take = |id| {
+		columns
+			.remove(&id)
+			.expect("the scheduler returns the outputs of all tasks")
+	};
 	let index = MultilinearExtensionIndex::new()
-		.update_owned::<B8, _>([(trace_oracle.mult_a, mult_a), (trace_oracle.mult_b, mult_b)])?
-		.update_owned::<B16, _>([(trace_oracle.product, product)])?
-		.update_owned::<B32, _>([
-			(trace_oracle.lookup_u, lookup_u),
-			(trace_oracle.lookup_t, lookup_t),
-		])?;
+		.update_owned::<B8, _>([(mult_a, take(mult_a)), (mult_b, take(mult_b))])?
+		.update_owned::<B16, _>([(product, take(product))])?
+		.update_owned::<B32, _>([(lookup_u, take(lookup_u)), (lookup_t, take(lookup_t))])?;
 
 	Ok(TraceWitness {
 		index,
@@ -274,7 +294,7 @@ where
 	PackedType<U, B32>: PackedFieldIndexable,
 	PackedType<U, B128>: PackedFieldIndexable,
 {
-	// Round 1 - trace commitments & Lasso deterministic reduction
+	// Round 1 - trace and Lasso counts commitments & Lasso deterministic reduction
 	let (mults_comm, mults_committed) = pcs8.commit(&extract_batch_id_polys::<_, _, B8>(
 		trace_oracle.mults_batch,
 		&witness.index,
@@ -305,16 +325,36 @@ where
 
 	let lasso_batches = &trace_oracle.lasso_batches;
 
+	// The Lasso counts are committed along with the trace, before the challenges are sampled
+	let witness_index =
+		lasso::prove_counts::<B32, U, _, _>(witness.index, &lasso_witness, lasso_batches)?;
+
+	let (lasso_counts_comm, lasso_counts_committed) =
+		lasso_counts_pcs.commit(&extract_batch_id_polys::<_, _, B1>(
+			trace_oracle.lasso_batches.counts_batch_id(),
+			&witness_index,
+			oracles,
+		)?)?;
+
+	let (lasso_final_counts_comm, lasso_final_counts_committed) =
+		lasso_final_counts_pcs.commit(&extract_batch_id_polys::<_, _, B1>(
+			trace_oracle.lasso_batches.final_counts_batch_id(),
+			&witness_index,
+			oracles,
+		)?)?;
+
 	challenger.observe(mults_comm.clone());
 	challenger.observe(product_comm.clone());
 	challenger.observe(lookup_t_comm.clone());
+	challenger.observe(lasso_counts_comm.clone());
+	challenger.observe(lasso_final_counts_comm.clone());
 
 	let gamma = challenger.sample();
 	let alpha = challenger.sample();
 
 	let lasso_prove_output = lasso::prove::<B32, U, _, _, _>(
 		oracles,
-		witness.index,
+		witness_index,
 		&lasso_claim,
 		lasso_witness,
 		lasso_batches,
@@ -331,23 +371,6 @@ where
 
 	let mut witness_index = witness_index;
 
-	let (lasso_counts_comm, lasso_counts_committed) =
-		lasso_counts_pcs.commit(&extract_batch_id_polys::<_, _, B1>(
-			trace_oracle.lasso_batches.counts_batch_id(),
-			&witness_index,
-			oracles,
-		)?)?;
-
-	let (lasso_final_counts_comm, lasso_final_counts_committed) =
-		lasso_final_counts_pcs.commit(&extract_batch_id_polys::<_, _, B1>(
-			trace_oracle.lasso_batches.final_counts_batch_id(),
-			&witness_index,
-			oracles,
-		)?)?;
-
-	challenger.observe(lasso_counts_comm.clone());
-	challenger.observe(lasso_final_counts_comm.clone());
-
 	// Round 2 - GKR-Based Prodcheck
 
 	let ProdcheckBatchProveOutput {
@@ -538,6 +561,8 @@ where
 	challenger.observe(mults_comm.clone());
 	challenger.observe(product_comm.clone());
 	challenger.observe(lookup_t_comm.clone());
+	challenger.observe(lasso_counts_comm.clone());
+	challenger.observe(lasso_final_counts_comm.clone());
 
 	let gamma = challenger.sample();
 	let alpha = challenger.sample();
@@ -545,9 +570,6 @@ where
 	let reduced_lasso_claims =
 		lasso::verify::<B32, _>(oracles, &lasso_claim, &trace_oracle.lasso_batches, gamma, alpha)?;
 
-	challenger.observe(lasso_counts_comm.clone());
-	challenger.observe(lasso_final_counts_comm.clone());
-
 	// Round 2 - Prodcheck
 	let reduced_prodcheck_claims = gkr_prodcheck::batch_verify(
 		reduced_lasso_claims.prodcheck_claims,
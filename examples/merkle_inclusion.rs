@@ -0,0 +1,190 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Example of a Binius SNARK that proves inclusion of a batch of leaves in a Merkle tree.
+//!
+//! The prover builds a binary SHA-256 Merkle tree over `2^depth` random leaves, samples a batch of
+//! leaf indices and proves that every committed leaf hashes up to the public root along its
+//! committed authentication path. Every tree level is proven with the SHA-256 gadget, and the
+//! levels are connected by a channel; see `merkle_inclusion/circuit.rs` for the arithmetization.
+//!
+//! The tree depth and the batch size are given on the command line, for instance
+//!
+//! ```text
+//! cargo run --release --example merkle_inclusion -- --depth 20 --log-batch 10
+//! ```
+//!
+//! and the prover and verifier timings are logged. The `merkle_inclusion` benchmark of this crate
+//! runs the same circuit under criterion, as a performance regression test.
+
+#[path = "merkle_inclusion/circuit.rs"]
+mod circuit;
+
+use anyhow::{bail, Context, Result};
+use binius_core::{
+	challenger::new_hasher_challenger, oracle::MultilinearOracleSet, poly_commit::tensor_pcs,
+	polynomial::IsomorphicEvaluationDomainFactory, witness_fuzz::check_bit_flips_rejected,
+};
+use binius_field::{
+	underlier::WithUnderlier, BinaryField128b, BinaryField16b, BinaryField1b,
+	PackedBinaryField128x1b,
+};
+use binius_hash::GroestlHasher;
+use binius_utils::{
+	examples::get_witness_fuzz_trials, rayon::adjust_thread_pool, tracing::init_tracing,
+};
+use bytesize::ByteSize;
+use circuit::{
+	generate_trace, prove, verify, ChannelOracle, FixedOracle, MerkleParams, MerkleTree,
+	TraceOracle,
+};
+use rand::thread_rng;
+use std::time::Instant;
+
+const DEFAULT_DEPTH: usize = 20;
+const DEFAULT_LOG_BATCH: usize = 10;
+
+/// Parses `--depth <N>` and `--log-batch <N>` from the command line arguments.
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<MerkleParams> {
+	let mut depth = DEFAULT_DEPTH;
+	let mut log_batch = DEFAULT_LOG_BATCH;
+	while let Some(arg) = args.next() {
+		let value = match arg.as_str() {
+			"--depth" => &mut depth,
+			"--log-batch" => &mut log_batch,
+			_ => bail!("unknown argument {arg}, expected --depth <N> or --log-batch <N>"),
+		};
+		*value = args
+			.next()
+			.with_context(|| format!("missing value for {arg}"))?
+			.parse()
+			.with_context(|| format!("the value of {arg} must be a valid integer"))?;
+	}
+	MerkleParams::new(depth, log_batch)
+}
+
+fn main() -> Result<()> {
+	const SECURITY_BITS: usize = 100;
+
+	adjust_thread_pool()
+		.as_ref()
+		.expect("failed to init thread pool");
+
+	init_tracing().expect("failed to initialize tracing");
+
+	// Note that trace sizes below 2^14 are rejected by `find_proof_size_optimal_pcs()`.
+	let params = parse_args(std::env::args().skip(1))?;
+	let log_size = params.log_size();
+	let log_inv_rate = 1;
+
+	type U = <PackedBinaryField128x1b as WithUnderlier>::Underlier;
+	type FW = BinaryField128b;
+
+	let mut rng = thread_rng();
+	let tree = MerkleTree::random(params.depth, &mut rng);
+	let leaf_indices = params.random_leaf_indices(&mut rng);
+
+	let mut oracles = MultilinearOracleSet::new();
+	let fixed_oracle = FixedOracle::new(&mut oracles, params, tree.root())?;
+	let trace_oracle = TraceOracle::new(&mut oracles, params);
+	let channel_oracle = ChannelOracle::new(&mut oracles, params, &trace_oracle)?;
+
+	let trace_batch = oracles.committed_batch(trace_oracle.batch_id);
+
+	// Set up the public parameters
+	let pcs = tensor_pcs::find_proof_size_optimal_pcs::<
+		U,
+		BinaryField1b,
+		BinaryField16b,
+		BinaryField16b,
+		BinaryField128b,
+	>(SECURITY_BITS, log_size, trace_batch.n_polys, log_inv_rate, false)
+	.context("no polynomial commitment scheme for this trace size")?;
+
+	let tensorpcs_size = ByteSize::b(pcs.proof_size(trace_batch.n_polys) as u64);
+	tracing::info!(
+		depth = params.depth,
+		n_leaves = leaf_indices.len(),
+		log_size,
+		"Merkle inclusion"
+	);
+	tracing::info!("Size of PCS opening proof: {}", tensorpcs_size);
+
+	let witness = generate_trace::<U, FW>(
+		params,
+		&tree,
+		&leaf_indices,
+		&fixed_oracle,
+		&trace_oracle,
+		&channel_oracle,
+	)?;
+
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	let domain_factory = IsomorphicEvaluationDomainFactory::<BinaryField128b>::default();
+
+	let start = Instant::now();
+	let proof = prove::<_, BinaryField128b, FW, FW, _, _>(
+		log_size,
+		&mut oracles.clone(),
+		&fixed_oracle,
+		&trace_oracle,
+		&channel_oracle,
+		&pcs,
+		challenger.clone(),
+		witness.clone(),
+		domain_factory.clone(),
+	)?;
+	let prove_time = start.elapsed();
+
+	let start = Instant::now();
+	verify(
+		log_size,
+		&mut oracles.clone(),
+		&fixed_oracle,
+		&trace_oracle,
+		&channel_oracle,
+		&pcs,
+		challenger.clone(),
+		proof,
+	)?;
+	let verify_time = start.elapsed();
+
+	tracing::info!(?prove_time, ?verify_time, "Proved and verified Merkle inclusion");
+
+	if let Some(n_trials) = get_witness_fuzz_trials() {
+		tracing::info!(n_trials, "Checking that perturbed witnesses are rejected");
+		let committed_ids = oracles
+			.committed_oracle_ids(trace_oracle.batch_id)
+			.collect::<Vec<_>>();
+		check_bit_flips_rejected::<_, _, BinaryField1b, _, _>(
+			&witness,
+			&committed_ids,
+			n_trials,
+			&mut thread_rng(),
+			|witness| {
+				let proof = prove::<_, BinaryField128b, FW, FW, _, _>(
+					log_size,
+					&mut oracles.clone(),
+					&fixed_oracle,
+					&trace_oracle,
+					&channel_oracle,
+					&pcs,
+					challenger.clone(),
+					witness,
+					domain_factory.clone(),
+				)?;
+				verify(
+					log_size,
+					&mut oracles.clone(),
+					&fixed_oracle,
+					&trace_oracle,
+					&channel_oracle,
+					&pcs,
+					challenger.clone(),
+					proof,
+				)
+			},
+		)?;
+	}
+
+	Ok(())
+}
@@ -0,0 +1,818 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Arithmetization of a batch of Merkle inclusion proofs over SHA-256, shared by the
+//! `merkle_inclusion` example and its benchmark.
+//!
+//! The tree is a binary Merkle tree of 256-bit nodes, where a parent is the SHA-256 compression
+//! of the concatenation of its children, starting from the SHA-256 initial hash value.
+//!
+//! The arithmetization uses 1-bit committed columns, where every row of 32-bit words attests to
+//! one compression along an authentication path with the SHA-256 gadget. An authentication path
+//! occupies a block of `2^log_rows` rows, where the first `depth` rows are the tree levels from
+//! the leaf to the root and the remaining rows are zero padding.
+//!
+//! The levels of the paths are connected by a channel. Every level below the root pushes the
+//! message `(level + 1, parent)` and every level above the leaf pulls the message
+//! `(level, node)`, so the channel is balanced exactly when each level hashes the node output by
+//! the level below. The padding rows push and pull all-zero messages. The balance is proven with
+//! a multiset check between the pushed and pulled messages, reduced to a GKR grand product
+//! argument, and the last level of every path is constrained to output the public root.
+
+use anyhow::{ensure, Result};
+use binius_core::{
+	challenger::{CanObserve, CanSample, CanSampleBits},
+	oracle::{BatchId, CompositePolyOracle, MultilinearOracleSet, OracleId, ShiftVariant},
+	poly_commit::PolyCommitScheme,
+	polynomial::{
+		composition::{empty_mix_composition, index_composition},
+		transparent::{
+			multilinear_extension::MultilinearExtensionTransparent, step_down::StepDown,
+		},
+		CompositionPoly, EvaluationDomainFactory, MultilinearComposite,
+	},
+	protocols::{
+		abstract_sumcheck::standard_switchover_heuristic,
+		evalcheck::EvalcheckClaim,
+		gkr_prodcheck::{self, ProdcheckGrandProductProof, ProdcheckGrandProductProveOutput},
+		greedy_evalcheck::{self, GreedyEvalcheckProof, GreedyEvalcheckProveOutput},
+		msetcheck::{self, MsetcheckClaim, MsetcheckProveOutput, MsetcheckWitness},
+		zerocheck::{self, ZerocheckBatchProof, ZerocheckBatchProveOutput, ZerocheckClaim},
+	},
+	witness::MultilinearExtensionIndex,
+};
+use binius_field::{
+	as_packed_field::{PackScalar, PackedType},
+	underlier::UnderlierType,
+	BinaryField, BinaryField1b, BinaryField32b, ExtensionField, Field, PackedField,
+	PackedFieldIndexable, TowerField,
+};
+use binius_macros::{composition_poly, IterOracles};
+use bytemuck::{must_cast_slice_mut, Pod};
+use rand::Rng;
+use rayon::prelude::*;
+use std::{fmt::Debug, iter};
+use tracing::instrument;
+
+#[path = "../gadgets/sha256.rs"]
+mod sha256;
+
+use sha256::{Sha256Compression, INIT, LOG_U32_BITS};
+
+/// The largest supported tree depth. The prover materializes the whole tree in memory.
+const MAX_DEPTH: usize = 24;
+
+/// A tree node, as eight 32-bit words.
+pub type Digest = [u32; 8];
+
+/// Compresses two children into their parent node.
+fn compress_children(left: &Digest, right: &Digest) -> Digest {
+	let mut block = [0; 16];
+	block[..8].copy_from_slice(left);
+	block[8..].copy_from_slice(right);
+	sha256::compress(&block)
+}
+
+/// A binary Merkle tree, stored level by level from the leaves to the root.
+pub struct MerkleTree {
+	levels: Vec<Vec<Digest>>,
+}
+
+impl MerkleTree {
+	pub fn random(depth: usize, rng: &mut impl Rng) -> Self {
+		let leaves = (0..1 << depth).map(|_| rng.gen()).collect::<Vec<Digest>>();
+		let mut levels = vec![leaves];
+		for _ in 0..depth {
+			let level = levels
+				.last()
+				.expect("levels is not empty")
+				.par_chunks_exact(2)
+				.map(|pair| compress_children(&pair[0], &pair[1]))
+				.collect();
+			levels.push(level);
+		}
+		Self { levels }
+	}
+
+	fn depth(&self) -> usize {
+		self.levels.len() - 1
+	}
+
+	pub fn root(&self) -> Digest {
+		self.levels[self.depth()][0]
+	}
+}
+
+/// Shape of the trace: the number of tree levels, the rows per path and the number of paths.
+#[derive(Debug, Clone, Copy)]
+pub struct MerkleParams {
+	pub depth: usize,
+	log_rows: usize,
+	pub log_batch: usize,
+}
+
+impl MerkleParams {
+	pub fn new(depth: usize, log_batch: usize) -> Result<Self> {
+		ensure!(
+			(1..=MAX_DEPTH).contains(&depth),
+			"tree depth must be between 1 and {MAX_DEPTH}, got {depth}"
+		);
+		ensure!(log_batch <= depth, "cannot prove more leaves than the tree has");
+		Ok(Self {
+			depth,
+			log_rows: depth.next_power_of_two().ilog2() as usize,
+			log_batch,
+		})
+	}
+
+	/// Number of variables of a single path block.
+	fn log_path_size(&self) -> usize {
+		LOG_U32_BITS + self.log_rows
+	}
+
+	pub fn log_size(&self) -> usize {
+		self.log_path_size() + self.log_batch
+	}
+
+	/// Samples the indices of the proven leaves.
+	pub fn random_leaf_indices(&self, rng: &mut impl Rng) -> Vec<usize> {
+		(0..1 << self.log_batch)
+			.map(|_| rng.gen_range(0..1 << self.depth))
+			.collect()
+	}
+}
+
+/// Fixed (ie. statement independent) trace columns, except for the root which is public input.
+#[derive(Debug, IterOracles)]
+pub struct FixedOracle {
+	/// 1 on the leaf row of every path.
+	first: OracleId,
+	/// 1 on the `depth` rows of every path that hold tree levels.
+	active: OracleId,
+	/// 1 on all but the last level row of every path.
+	active_prefix: OracleId,
+	/// The root words, repeated on every row.
+	root: [OracleId; 8],
+}
+
+impl FixedOracle {
+	pub fn new<F: TowerField>(
+		oracles: &mut MultilinearOracleSet<F>,
+		params: MerkleParams,
+		root: Digest,
+	) -> Result<Self> {
+		let log_path_size = params.log_path_size();
+		let mut add_path_selector = |n_rows: usize| -> Result<OracleId> {
+			let single =
+				oracles.add_transparent(StepDown::new(log_path_size, n_rows << LOG_U32_BITS)?)?;
+			Ok(oracles.add_repeating(single, params.log_batch)?)
+		};
+
+		let first = add_path_selector(1)?;
+		let active = add_path_selector(params.depth)?;
+		let active_prefix = add_path_selector(params.depth - 1)?;
+
+		let root = root.map(|word| sha256::u32_const_oracle(oracles, params.log_size(), word));
+
+		Ok(Self {
+			first,
+			active,
+			active_prefix,
+			root,
+		})
+	}
+}
+
+/// Instance and witness trace columns.
+#[derive(Debug, IterOracles)]
+pub struct TraceOracle {
+	pub batch_id: BatchId,
+	/// The node on the path at the current level.
+	cur: [OracleId; 8],
+	/// The sibling of the current node.
+	sib: [OracleId; 8],
+	/// All ones if the current node is a right child, all zeros otherwise.
+	dir: OracleId,
+	dir_rot: OracleId,
+	/// The parent node on the levels that push it to the channel, zero elsewhere.
+	push: [OracleId; 8],
+	/// The current node on the levels that pull it from the channel, zero elsewhere.
+	pull: [OracleId; 8],
+	/// The compression of the children, with the initial hash value as input state.
+	compression: Sha256Compression,
+}
+
+impl TraceOracle {
+	pub fn new<F: TowerField>(oracles: &mut MultilinearOracleSet<F>, params: MerkleParams) -> Self {
+		let log_size = params.log_size();
+		let batch_id = oracles.add_committed_batch(log_size, BinaryField1b::TOWER_LEVEL);
+		let cur = oracles.add_committed_multiple(batch_id);
+		let sib = oracles.add_committed_multiple(batch_id);
+		let dir = oracles.add_committed(batch_id);
+		let push = oracles.add_committed_multiple(batch_id);
+		let pull = oracles.add_committed_multiple(batch_id);
+
+		let dir_rot = oracles
+			.add_shifted(dir, 1, LOG_U32_BITS, ShiftVariant::CircularLeft)
+			.unwrap();
+
+		let h_in = INIT.map(|word| sha256::u32_const_oracle(oracles, log_size, word));
+		let compression = Sha256Compression::new(oracles, batch_id, log_size, h_in);
+
+		Self {
+			batch_id,
+			cur,
+			sib,
+			dir,
+			dir_rot,
+			push,
+			pull,
+			compression,
+		}
+	}
+}
+
+/// The messages pushed to and pulled from the channel, one per row of 32-bit words.
+#[derive(Debug, IterOracles)]
+pub struct ChannelOracle {
+	/// The level of the pushed node, `level + 1` on the pushing rows and zero elsewhere.
+	push_level: OracleId,
+	/// The level of the pulled node, `level` on the pulling rows and zero elsewhere.
+	pull_level: OracleId,
+	/// The pushed words, viewed as 32-bit field elements.
+	push: [OracleId; 8],
+	/// The pulled words, viewed as 32-bit field elements.
+	pull: [OracleId; 8],
+}
+
+impl ChannelOracle {
+	pub fn new<F: TowerField + ExtensionField<BinaryField32b>>(
+		oracles: &mut MultilinearOracleSet<F>,
+		params: MerkleParams,
+		trace_oracle: &TraceOracle,
+	) -> Result<Self> {
+		let mut add_level_tag =
+			|level_of_row: fn(MerkleParams, usize) -> usize| -> Result<OracleId> {
+				let values = (0..1 << params.log_rows)
+					.map(|row| BinaryField32b::new(level_of_row(params, row) as u32))
+					.collect::<Vec<_>>();
+				let single = oracles.add_transparent(MultilinearExtensionTransparent::<
+					BinaryField32b,
+					F,
+					_,
+				>::from_values(values)?)?;
+				Ok(oracles.add_repeating(single, params.log_batch)?)
+			};
+
+		let push_level = add_level_tag(push_level)?;
+		let pull_level = add_level_tag(pull_level)?;
+
+		let mut add_packed = |ids: [OracleId; 8]| -> Result<[OracleId; 8]> {
+			let mut packed = [0; 8];
+			for (packed, id) in iter::zip(&mut packed, ids) {
+				*packed = oracles.add_packed(id, LOG_U32_BITS)?;
+			}
+			Ok(packed)
+		};
+
+		let push = add_packed(trace_oracle.push)?;
+		let pull = add_packed(trace_oracle.pull)?;
+
+		Ok(Self {
+			push_level,
+			pull_level,
+			push,
+			pull,
+		})
+	}
+
+	/// The columns of the pushed messages, the T relation of the multiset check.
+	fn pushed(&self) -> impl Iterator<Item = OracleId> {
+		iter::once(self.push_level).chain(self.push)
+	}
+
+	/// The columns of the pulled messages, the U relation of the multiset check.
+	fn pulled(&self) -> impl Iterator<Item = OracleId> {
+		iter::once(self.pull_level).chain(self.pull)
+	}
+
+	fn msetcheck_claim<F: TowerField>(
+		&self,
+		oracles: &MultilinearOracleSet<F>,
+	) -> Result<MsetcheckClaim<F>> {
+		Ok(MsetcheckClaim::new(
+			self.pushed().map(|id| oracles.oracle(id)),
+			self.pulled().map(|id| oracles.oracle(id)),
+		)?)
+	}
+}
+
+/// The level tag pushed by a row of a path block.
+fn push_level(params: MerkleParams, row: usize) -> usize {
+	if row + 1 < params.depth {
+		row + 1
+	} else {
+		0
+	}
+}
+
+/// The level tag pulled by a row of a path block. The leaf row pulls nothing, as does padding.
+fn pull_level(params: MerkleParams, row: usize) -> usize {
+	if row < params.depth {
+		row
+	} else {
+		0
+	}
+}
+
+fn constrained_oracles<'a>(
+	fixed_oracle: &'a FixedOracle,
+	trace_oracle: &'a TraceOracle,
+) -> impl Iterator<Item = OracleId> + 'a {
+	fixed_oracle
+		.iter_oracles()
+		.chain(trace_oracle.iter_oracles())
+		.chain(trace_oracle.compression.iter())
+}
+
+pub struct Proof<F: Field, PCSComm, PCSProof> {
+	trace_comm: PCSComm,
+	channel_proof: ProdcheckGrandProductProof<F>,
+	zerocheck_proof: ZerocheckBatchProof<F>,
+	evalcheck_proof: GreedyEvalcheckProof<F>,
+	trace_open_proof: PCSProof,
+}
+
+#[instrument(skip_all, level = "debug")]
+pub fn generate_trace<U, FW>(
+	params: MerkleParams,
+	tree: &MerkleTree,
+	leaf_indices: &[usize],
+	fixed_oracle: &FixedOracle,
+	trace_oracle: &TraceOracle,
+	channel_oracle: &ChannelOracle,
+) -> Result<MultilinearExtensionIndex<'static, U, FW>>
+where
+	U: UnderlierType
+		+ PackScalar<BinaryField1b>
+		+ PackScalar<BinaryField32b>
+		+ PackScalar<FW>
+		+ Pod,
+	FW: BinaryField + ExtensionField<BinaryField32b>,
+{
+	let log_size = params.log_size();
+	assert!(log_size >= <PackedType<U, BinaryField1b>>::LOG_WIDTH);
+	assert_eq!(leaf_indices.len(), 1 << params.log_batch);
+	assert_eq!(tree.depth(), params.depth);
+	// A row of 32 bits holds one 32-bit word, so the 1-bit columns and their 32-bit views have the
+	// same underliers.
+	let len = 1 << (log_size - <PackedType<U, BinaryField1b>>::LOG_WIDTH);
+	let build_trace_column = || vec![U::default(); len].into_boxed_slice();
+
+	fn cast_u32_cols<U: Pod, const N: usize>(cols: &mut [Box<[U]>; N]) -> [&mut [u32]; N] {
+		cols.each_mut()
+			.map(|col| must_cast_slice_mut::<_, u32>(&mut *col))
+	}
+
+	let mut selectors = [(); 3].map(|_| build_trace_column());
+	let mut root = [(); 8].map(|_| build_trace_column());
+	let mut cur = [(); 8].map(|_| build_trace_column());
+	let mut sib = [(); 8].map(|_| build_trace_column());
+	let mut dir = [(); 2].map(|_| build_trace_column());
+	let mut push = [(); 8].map(|_| build_trace_column());
+	let mut pull = [(); 8].map(|_| build_trace_column());
+	let mut level_tags = [(); 2].map(|_| build_trace_column());
+
+	let [first, active, active_prefix] = cast_u32_cols(&mut selectors);
+	let root_u32 = cast_u32_cols(&mut root);
+	let cur_u32 = cast_u32_cols(&mut cur);
+	let sib_u32 = cast_u32_cols(&mut sib);
+	let [dir_u32, dir_rot_u32] = cast_u32_cols(&mut dir);
+	let push_u32 = cast_u32_cols(&mut push);
+	let pull_u32 = cast_u32_cols(&mut pull);
+	let [push_level_u32, pull_level_u32] = cast_u32_cols(&mut level_tags);
+
+	for (col, word) in iter::zip(root_u32, tree.root()) {
+		col.fill(word);
+	}
+
+	let mut blocks = vec![[0; 16]; 1 << (log_size - LOG_U32_BITS)];
+	for (path_i, &leaf_index) in leaf_indices.iter().enumerate() {
+		let offset = path_i << params.log_rows;
+
+		for row in 0..1 << params.log_rows {
+			let i = offset | row;
+			push_level_u32[i] = push_level(params, row) as u32;
+			pull_level_u32[i] = pull_level(params, row) as u32;
+		}
+
+		for level in 0..params.depth {
+			let i = offset | level;
+			let node_index = leaf_index >> level;
+			let is_right = node_index & 1 == 1;
+
+			let node = tree.levels[level][node_index];
+			let sibling = tree.levels[level][node_index ^ 1];
+			let parent = tree.levels[level + 1][node_index >> 1];
+			let (left, right) = if is_right {
+				(sibling, node)
+			} else {
+				(node, sibling)
+			};
+			blocks[i][..8].copy_from_slice(&left);
+			blocks[i][8..].copy_from_slice(&right);
+
+			for j in 0..8 {
+				cur_u32[j][i] = node[j];
+				sib_u32[j][i] = sibling[j];
+				if level + 1 < params.depth {
+					push_u32[j][i] = parent[j];
+				}
+				if level > 0 {
+					pull_u32[j][i] = node[j];
+				}
+			}
+
+			dir_u32[i] = if is_right { u32::MAX } else { 0 };
+			dir_rot_u32[i] = dir_u32[i].rotate_left(1);
+
+			first[i] = if level == 0 { u32::MAX } else { 0 };
+			active[i] = u32::MAX;
+			active_prefix[i] = if level + 1 < params.depth {
+				u32::MAX
+			} else {
+				0
+			};
+		}
+	}
+
+	// The columns are in the order of the oracles of each struct
+	let fixed_columns = selectors.into_iter().chain(root);
+	let trace_columns = cur
+		.into_iter()
+		.chain(sib)
+		.chain(dir)
+		.chain(push.clone())
+		.chain(pull.clone());
+	let channel_columns = level_tags.into_iter().chain(push).chain(pull);
+
+	let index = trace_oracle
+		.compression
+		.generate_trace::<U, FW>(log_size, &blocks)?
+		.update_owned::<BinaryField1b, _>(iter::zip(
+			fixed_oracle
+				.iter_oracles()
+				.chain(trace_oracle.iter_oracles()),
+			fixed_columns.chain(trace_columns),
+		))?
+		.update_owned::<BinaryField32b, _>(iter::zip(
+			channel_oracle.iter_oracles(),
+			channel_columns,
+		))?;
+
+	Ok(index)
+}
+
+#[allow(clippy::too_many_arguments)]
+#[instrument(skip_all, level = "debug")]
+pub fn prove<U, F, FW, DomainField, PCS, CH>(
+	log_size: usize,
+	oracles: &mut MultilinearOracleSet<F>,
+	fixed_oracle: &FixedOracle,
+	trace_oracle: &TraceOracle,
+	channel_oracle: &ChannelOracle,
+	pcs: &PCS,
+	mut challenger: CH,
+	witness: MultilinearExtensionIndex<U, FW>,
+	domain_factory: impl EvaluationDomainFactory<DomainField>,
+) -> Result<Proof<F, PCS::Commitment, PCS::Proof>>
+where
+	U: UnderlierType + PackScalar<BinaryField1b> + PackScalar<FW> + PackScalar<DomainField>,
+	F: TowerField + From<FW>,
+	PackedType<U, FW>: PackedFieldIndexable<Scalar = FW>,
+	FW: TowerField + From<F> + ExtensionField<DomainField>,
+	DomainField: TowerField,
+	PCS: PolyCommitScheme<PackedType<U, BinaryField1b>, F, Error: Debug, Proof: 'static>,
+	CH: CanObserve<F> + CanObserve<PCS::Commitment> + CanSample<F> + CanSampleBits<usize>,
+{
+	// Round 1
+	let trace_commit_polys = oracles
+		.committed_oracle_ids(trace_oracle.batch_id)
+		.map(|oracle_id| witness.get::<BinaryField1b>(oracle_id))
+		.collect::<Result<Vec<_>, _>>()?;
+	let (trace_comm, trace_committed) = pcs.commit(&trace_commit_polys)?;
+	challenger.observe(trace_comm.clone());
+
+	// Channel balancing
+	let gamma = challenger.sample();
+	let alpha = challenger.sample();
+
+	let msetcheck_claim = channel_oracle.msetcheck_claim(oracles)?;
+	let msetcheck_witness = MsetcheckWitness::new(
+		channel_oracle
+			.pushed()
+			.map(|id| witness.get_multilin_poly(id))
+			.collect::<Result<Vec<_>, _>>()?,
+		channel_oracle
+			.pulled()
+			.map(|id| witness.get_multilin_poly(id))
+			.collect::<Result<Vec<_>, _>>()?,
+	)?;
+
+	let MsetcheckProveOutput {
+		prodcheck_claim,
+		prodcheck_witness,
+		witness_index,
+	} = msetcheck::prove(oracles, witness, &msetcheck_claim, msetcheck_witness, gamma, Some(alpha))?;
+	let mut witness = witness_index;
+
+	let ProdcheckGrandProductProveOutput {
+		evalcheck_multilinear_claims: channel_claims,
+		proof: channel_proof,
+	} = gkr_prodcheck::prove_grand_product::<_, _, DomainField, _>(
+		[prodcheck_witness],
+		[prodcheck_claim],
+		domain_factory.clone(),
+		&mut challenger,
+	)?;
+
+	// Zerocheck mixing
+	let mixing_challenge = challenger.sample();
+
+	let mix_composition_verifier = make_constraints(fixed_oracle, trace_oracle, mixing_challenge)?;
+	let mix_composition_prover =
+		make_constraints(fixed_oracle, trace_oracle, FW::from(mixing_challenge))?;
+
+	let zerocheck_column_oracles = constrained_oracles(fixed_oracle, trace_oracle)
+		.map(|id| oracles.oracle(id))
+		.collect();
+
+	// Zerocheck
+	let zerocheck_claim = ZerocheckClaim::new(CompositePolyOracle::new(
+		log_size,
+		zerocheck_column_oracles,
+		mix_composition_verifier,
+	)?)?;
+
+	let zerocheck_witness = MultilinearComposite::new(
+		log_size,
+		mix_composition_prover,
+		constrained_oracles(fixed_oracle, trace_oracle)
+			.map(|oracle_id| witness.get_multilin_poly(oracle_id))
+			.collect::<Result<_, _>>()?,
+	)?;
+
+	let switchover_fn = standard_switchover_heuristic(-2);
+
+	let ZerocheckBatchProveOutput {
+		evalcheck_claims,
+		proof: zerocheck_proof,
+	} = zerocheck::batch_prove(
+		[(zerocheck_claim, zerocheck_witness)],
+		domain_factory.clone(),
+		switchover_fn,
+		&mut challenger,
+	)?;
+
+	// Evalcheck
+	let evalcheck_claims = channel_claims
+		.into_iter()
+		.map(|claim| EvalcheckClaim {
+			poly: claim.poly.into_composite(),
+			eval_point: claim.eval_point,
+			eval: claim.eval,
+			is_random_point: claim.is_random_point,
+		})
+		.chain(evalcheck_claims);
+
+	let GreedyEvalcheckProveOutput {
+		same_query_claims,
+		proof: evalcheck_proof,
+	} = greedy_evalcheck::prove::<_, PackedType<U, FW>, _, _>(
+		oracles,
+		&mut witness,
+		evalcheck_claims,
+		switchover_fn,
+		&mut challenger,
+		domain_factory,
+	)?;
+
+	assert_eq!(same_query_claims.len(), 1);
+	let (batch_id, same_query_claim) = same_query_claims
+		.into_iter()
+		.next()
+		.expect("length is asserted to be 1");
+	assert_eq!(batch_id, trace_oracle.batch_id);
+
+	let trace_commit_polys = oracles
+		.committed_oracle_ids(trace_oracle.batch_id)
+		.map(|oracle_id| witness.get::<BinaryField1b>(oracle_id))
+		.collect::<Result<Vec<_>, _>>()?;
+	let trace_open_proof = pcs.prove_evaluation(
+		&mut challenger,
+		&trace_committed,
+		&trace_commit_polys,
+		&same_query_claim.eval_point,
+	)?;
+
+	Ok(Proof {
+		trace_comm,
+		channel_proof,
+		zerocheck_proof,
+		evalcheck_proof,
+		trace_open_proof,
+	})
+}
+
+#[allow(clippy::too_many_arguments)]
+#[instrument(skip_all, level = "debug")]
+pub fn verify<P, F, PCS, CH>(
+	log_size: usize,
+	oracles: &mut MultilinearOracleSet<F>,
+	fixed_oracle: &FixedOracle,
+	trace_oracle: &TraceOracle,
+	channel_oracle: &ChannelOracle,
+	pcs: &PCS,
+	mut challenger: CH,
+	proof: Proof<F, PCS::Commitment, PCS::Proof>,
+) -> Result<()>
+where
+	P: PackedField<Scalar = BinaryField1b>,
+	F: TowerField,
+	PCS: PolyCommitScheme<P, F, Error: Debug, Proof: 'static>,
+	CH: CanObserve<F> + CanObserve<PCS::Commitment> + CanSample<F> + CanSampleBits<usize>,
+{
+	let Proof {
+		trace_comm,
+		channel_proof,
+		zerocheck_proof,
+		evalcheck_proof,
+		trace_open_proof,
+	} = proof;
+
+	// Round 1
+	challenger.observe(trace_comm.clone());
+
+	// Channel balancing
+	let gamma = challenger.sample();
+	let alpha = challenger.sample();
+
+	let msetcheck_claim = channel_oracle.msetcheck_claim(oracles)?;
+	let prodcheck_claim = msetcheck::verify(oracles, &msetcheck_claim, gamma, Some(alpha))?;
+	let channel_claims =
+		gkr_prodcheck::verify_grand_product([prodcheck_claim], channel_proof, &mut challenger)?;
+
+	// Zerocheck mixing
+	let mixing_challenge = challenger.sample();
+	let mix_composition = make_constraints(fixed_oracle, trace_oracle, mixing_challenge)?;
+
+	// Zerocheck
+	let zerocheck_column_oracles = constrained_oracles(fixed_oracle, trace_oracle)
+		.map(|id| oracles.oracle(id))
+		.collect();
+	let zerocheck_claim = ZerocheckClaim::new(CompositePolyOracle::new(
+		log_size,
+		zerocheck_column_oracles,
+		mix_composition,
+	)?)?;
+
+	let evalcheck_claims =
+		zerocheck::batch_verify([zerocheck_claim], zerocheck_proof, &mut challenger)?;
+
+	// Evalcheck
+	let evalcheck_claims = channel_claims
+		.into_iter()
+		.map(|claim| EvalcheckClaim {
+			poly: claim.poly.into_composite(),
+			eval_point: claim.eval_point,
+			eval: claim.eval,
+			is_random_point: claim.is_random_point,
+		})
+		.chain(evalcheck_claims);
+
+	let same_query_claims =
+		greedy_evalcheck::verify(oracles, evalcheck_claims, evalcheck_proof, &mut challenger)?;
+
+	assert_eq!(same_query_claims.len(), 1);
+	let (batch_id, same_query_claim) = same_query_claims
+		.into_iter()
+		.next()
+		.expect("length is asserted to be 1");
+	assert_eq!(batch_id, trace_oracle.batch_id);
+
+	pcs.verify_evaluation(
+		&mut challenger,
+		&trace_comm,
+		&same_query_claim.eval_point,
+		trace_open_proof,
+		&same_query_claim.evals,
+	)?;
+
+	Ok(())
+}
+
+fn make_constraints<P: PackedField<Scalar: TowerField>>(
+	fixed_oracle: &FixedOracle,
+	trace_oracle: &TraceOracle,
+	challenge: P::Scalar,
+) -> Result<impl CompositionPoly<P> + Clone + 'static> {
+	let zerocheck_column_ids = constrained_oracles(fixed_oracle, trace_oracle).collect::<Vec<_>>();
+	let mix = empty_mix_composition(zerocheck_column_ids.len(), challenge);
+	let compression = &trace_oracle.compression;
+
+	// The direction bit is broadcast to all bits of the word
+	let mix = mix.include([index_composition(
+		&zerocheck_column_ids,
+		[trace_oracle.dir, trace_oracle.dir_rot],
+		composition_poly!([dir, dir_rot] = dir - dir_rot),
+	)?])?;
+
+	// The compressed block is the current node and its sibling, ordered by direction
+	let mix = mix.include(
+		(0..8)
+			.map(|j| {
+				index_composition(
+					&zerocheck_column_ids,
+					[
+						compression.w[j],
+						trace_oracle.cur[j],
+						trace_oracle.sib[j],
+						trace_oracle.dir,
+					],
+					composition_poly!([left, cur, sib, dir] = left - (cur + dir * (cur + sib))),
+				)
+			})
+			.collect::<Result<Vec<_>, _>>()?,
+	)?;
+	let mix = mix.include(
+		(0..8)
+			.map(|j| {
+				index_composition(
+					&zerocheck_column_ids,
+					[
+						compression.w[j],
+						compression.w[8 + j],
+						trace_oracle.cur[j],
+						trace_oracle.sib[j],
+					],
+					composition_poly!([left, right, cur, sib] = left + right - cur - sib),
+				)
+			})
+			.collect::<Result<Vec<_>, _>>()?,
+	)?;
+
+	// Every level below the last pushes its parent, and every level above the leaf pulls its node
+	let mix = mix.include(
+		(0..8)
+			.map(|j| {
+				index_composition(
+					&zerocheck_column_ids,
+					[
+						trace_oracle.push[j],
+						compression.h_out[j],
+						fixed_oracle.active_prefix,
+					],
+					composition_poly!([push, out, active_prefix] = push - active_prefix * out),
+				)
+			})
+			.collect::<Result<Vec<_>, _>>()?,
+	)?;
+	let mix = mix.include(
+		(0..8)
+			.map(|j| {
+				index_composition(
+					&zerocheck_column_ids,
+					[
+						trace_oracle.pull[j],
+						trace_oracle.cur[j],
+						fixed_oracle.active,
+						fixed_oracle.first,
+					],
+					composition_poly!([pull, cur, active, first] = pull - (active - first) * cur),
+				)
+			})
+			.collect::<Result<Vec<_>, _>>()?,
+	)?;
+
+	// The last level outputs the root
+	let mix = mix.include(
+		(0..8)
+			.map(|j| {
+				index_composition(
+					&zerocheck_column_ids,
+					[
+						compression.h_out[j],
+						fixed_oracle.root[j],
+						fixed_oracle.active,
+						fixed_oracle.active_prefix,
+					],
+					composition_poly!(
+						[out, root, active, active_prefix] =
+							(active - active_prefix) * (out - root)
+					),
+				)
+			})
+			.collect::<Result<Vec<_>, _>>()?,
+	)?;
+
+	compression.mix_constraints(mix, &zerocheck_column_ids)
+}
@@ -0,0 +1,458 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Example of a Binius SNARK that proves a committed blob is consistent with its Reed–Solomon
+//! encoding.
+//!
+//! This is the check a data-availability light client relies on: the prover commits to a blob and
+//! to its rate 1/2 Reed–Solomon codeword over `GF(2^16)`, and proves that the codeword is the
+//! encoding of the blob, so that any half of the codeword symbols recovers the blob.
+//!
+//! Both the blob and the codeword are committed as 1-bit columns and viewed as columns of 16-bit
+//! symbols with packed oracles. The encoding map `Enc` is linear, so for a random point `r` the
+//! evaluation of the codeword multilinear satisfies
+//!
+//! ```txt
+//! c~(r) = \sum_x d(x) * M_r(x),    M_r(x) = \sum_j eq(r, j) * Enc(e_x)_j
+//! ```
+//!
+//! The prover claims `c~(r)`, which is checked against the codeword commitment, and proves the
+//! sum on the right with a sumcheck over the blob and the transparent `M_r`, which reduces to an
+//! evaluation of the blob. Both evaluation claims are reduced to openings of the two commitments
+//! through the packed oracles.
+//!
+//! This is a reduced version of the protocol. The verifier computes `M_r` by encoding every unit
+//! vector with the additive NTT, which takes time quadratic in the blob size, whereas a succinct
+//! verifier would evaluate `M_r` at a single point.
+//!
+//! The example encodes the proof, decodes it and verifies the decoded proof as a light client
+//! would. If a path is given as the first argument, the encoded proof is also written there, to be
+//! checked by the verify-only `rs_erasure_verify` binary. The log of the number of 16-bit blob
+//! symbols is read from the `BINIUS_RS_LOG_DIM` environment variable.
+
+#![feature(step_trait)]
+
+#[path = "rs_erasure/verifier.rs"]
+mod verifier;
+
+use anyhow::Result;
+use binius_core::{
+	challenger::{new_hasher_challenger, CanObserve, CanSample, CanSampleBits},
+	linear_code::LinearCode,
+	merkle_tree::MerkleCap,
+	oracle::CompositePolyOracle,
+	poly_commit::PolyCommitScheme,
+	polynomial::{
+		composition::BivariateProduct, EvaluationDomainFactory, IsomorphicEvaluationDomainFactory,
+		MultilinearComposite,
+	},
+	protocols::{
+		abstract_sumcheck::{standard_switchover_heuristic, AbstractSumcheckRound},
+		evalcheck::{EvalcheckClaim, EvalcheckProof},
+		greedy_evalcheck::{self, GreedyEvalcheckProof, GreedyEvalcheckProveOutput},
+		sumcheck::{self, SumcheckBatchProof, SumcheckClaim, SumcheckProveOutput},
+	},
+	reed_solomon::reed_solomon::ReedSolomonCode,
+	witness::MultilinearExtensionIndex,
+	witness_fuzz::check_bit_flips_rejected,
+};
+use binius_field::{
+	as_packed_field::PackedType, BinaryField1b, ExtensionField, Field, PackedField, TowerField,
+};
+use binius_hash::GroestlHasher;
+use binius_utils::{
+	examples::get_witness_fuzz_trials, rayon::adjust_thread_pool, tracing::init_tracing,
+};
+use bytesize::ByteSize;
+use rand::thread_rng;
+use tracing::instrument;
+use verifier::{
+	add_encoding_row_combination, batch_claim, decode_proof, eq_expansion, eq_inner_product,
+	verify, Digest, ErasureOracle, ErasureParams, ErasureProof, PcsProof, Proof, Symbol, F,
+	LOG_INV_RATE, U,
+};
+
+// Get the log of the number of blob symbols from the environment variable.
+// Panics if the environment variable is not a valid integer.
+fn get_log_dim() -> Option<usize> {
+	match std::env::var("BINIUS_RS_LOG_DIM") {
+		Ok(val) => Some(
+			val.parse::<usize>()
+				.expect("BINIUS_RS_LOG_DIM must be a valid integer"),
+		),
+		Err(_) => None,
+	}
+}
+
+#[instrument(skip_all, level = "debug")]
+fn generate_witness(
+	rs_code: &ReedSolomonCode<PackedType<U, Symbol>>,
+	oracle: &ErasureOracle,
+) -> Result<MultilinearExtensionIndex<'static, U, F>> {
+	let mut rng = thread_rng();
+
+	let packed_dim = rs_code.dim() / <PackedType<U, Symbol>>::WIDTH;
+	let data = (0..packed_dim)
+		.map(|_| PackedType::<U, Symbol>::random(&mut rng))
+		.collect::<Vec<_>>();
+	let code = rs_code.encode(data.clone())?;
+
+	let data = PackedType::<U, Symbol>::to_underliers_ref(&data).to_vec();
+	let code = PackedType::<U, Symbol>::to_underliers_ref(&code).to_vec();
+
+	let index = MultilinearExtensionIndex::new()
+		.update_owned::<BinaryField1b, _>([
+			(oracle.data_bits, data.clone()),
+			(oracle.code_bits, code.clone()),
+		])?
+		.update_owned::<Symbol, _>([(oracle.data, data), (oracle.code, code)])?;
+
+	Ok(index)
+}
+
+#[instrument(skip_all, level = "debug")]
+fn prove<CH>(
+	params: &ErasureParams,
+	mut challenger: CH,
+	mut witness: MultilinearExtensionIndex<U, F>,
+	domain_factory: impl EvaluationDomainFactory<F>,
+) -> Result<ErasureProof>
+where
+	CH: CanObserve<F> + CanObserve<MerkleCap<Digest>> + CanSample<F> + CanSampleBits<usize>,
+{
+	let ErasureParams {
+		log_dim,
+		rs_code,
+		oracles,
+		oracle,
+		data_pcs,
+		code_pcs,
+	} = params;
+	let mut oracles = oracles.clone();
+
+	// Round 1
+	let data_polys = [witness.get::<BinaryField1b>(oracle.data_bits)?];
+	let (data_comm, data_committed) = data_pcs.commit(&data_polys)?;
+	let code_polys = [witness.get::<BinaryField1b>(oracle.code_bits)?];
+	let (code_comm, code_committed) = code_pcs.commit(&code_polys)?;
+	challenger.observe(data_comm.clone());
+	challenger.observe(code_comm.clone());
+
+	// Evaluate the codeword at a random point
+	let r: Vec<F> = (0..log_dim + LOG_INV_RATE)
+		.map(|_| challenger.sample())
+		.collect();
+	let code = witness.get::<Symbol>(oracle.code)?;
+	let code_eval = eq_inner_product(&eq_expansion(&r)?, code.evals());
+	challenger.observe(code_eval);
+
+	// Sumcheck the codeword evaluation against the blob
+	let (row_combination, row_combination_values) =
+		add_encoding_row_combination(&mut oracles, rs_code, &r)?;
+	witness = witness.update_owned::<F, _>([(
+		row_combination,
+		PackedType::<U, F>::to_underliers_ref(&row_combination_values).to_vec(),
+	)])?;
+
+	let sumcheck_claim = SumcheckClaim::new(
+		CompositePolyOracle::new(
+			*log_dim,
+			vec![oracles.oracle(oracle.data), oracles.oracle(row_combination)],
+			BivariateProduct,
+		)?,
+		code_eval,
+	)?;
+	let sumcheck_witness = MultilinearComposite::new(
+		*log_dim,
+		BivariateProduct,
+		vec![
+			witness.get_multilin_poly(oracle.data)?,
+			witness.get_multilin_poly(row_combination)?,
+		],
+	)?;
+
+	let switchover_fn = standard_switchover_heuristic(-2);
+
+	let SumcheckProveOutput {
+		evalcheck_claim: data_claim,
+		sumcheck_proof,
+	} = sumcheck::prove::<F, PackedType<U, F>, F, _>(
+		&sumcheck_claim,
+		sumcheck_witness,
+		domain_factory.clone(),
+		switchover_fn,
+		&mut challenger,
+	)?;
+
+	let code_claim = EvalcheckClaim {
+		poly: oracles.oracle(oracle.code).into_composite(),
+		eval_point: r,
+		eval: code_eval,
+		is_random_point: true,
+	};
+
+	// Evalcheck
+	let GreedyEvalcheckProveOutput {
+		mut same_query_claims,
+		proof: evalcheck_proof,
+	} = greedy_evalcheck::prove::<F, PackedType<U, F>, F, _>(
+		&mut oracles,
+		&mut witness,
+		[code_claim, data_claim],
+		switchover_fn,
+		&mut challenger,
+		domain_factory,
+	)?;
+
+	assert_eq!(same_query_claims.len(), 2);
+	let data_query_claim = batch_claim(&mut same_query_claims, oracle.data_batch_id)?;
+	let code_query_claim = batch_claim(&mut same_query_claims, oracle.code_batch_id)?;
+
+	let data_polys = [witness.get::<BinaryField1b>(oracle.data_bits)?];
+	let data_open_proof = data_pcs.prove_evaluation(
+		&mut challenger,
+		&data_committed,
+		&data_polys,
+		&data_query_claim.eval_point,
+	)?;
+	let code_polys = [witness.get::<BinaryField1b>(oracle.code_bits)?];
+	let code_open_proof = code_pcs.prove_evaluation(
+		&mut challenger,
+		&code_committed,
+		&code_polys,
+		&code_query_claim.eval_point,
+	)?;
+
+	Ok(Proof {
+		data_comm,
+		code_comm,
+		code_eval,
+		sumcheck_proof,
+		evalcheck_proof,
+		data_open_proof,
+		code_open_proof,
+	})
+}
+
+/// Encodes a proof in the format read by [`decode_proof`].
+fn encode_proof(log_dim: usize, proof: &ErasureProof) -> Vec<u8> {
+	let mut writer = Writer { bytes: Vec::new() };
+	writer.len(log_dim);
+	writer.commitment(&proof.data_comm);
+	writer.commitment(&proof.code_comm);
+	writer.elem(proof.code_eval);
+	writer.rounds(&proof.sumcheck_proof.rounds);
+	writer.greedy_evalcheck_proof(&proof.evalcheck_proof);
+	writer.pcs_proof(&proof.data_open_proof);
+	writer.pcs_proof(&proof.code_open_proof);
+	writer.bytes
+}
+
+struct Writer {
+	bytes: Vec<u8>,
+}
+
+impl Writer {
+	fn len(&mut self, len: usize) {
+		self.bytes.extend((len as u64).to_le_bytes());
+	}
+
+	fn tag(&mut self, tag: u8) {
+		self.bytes.push(tag);
+	}
+
+	fn elem<FS: TowerField>(&mut self, elem: FS) {
+		let degree = <FS as ExtensionField<BinaryField1b>>::DEGREE;
+		let mut bytes = vec![0u8; degree.div_ceil(8)];
+		for (i, bit) in ExtensionField::<BinaryField1b>::iter_bases(&elem).enumerate() {
+			if bit == BinaryField1b::ONE {
+				bytes[i / 8] |= 1 << (i % 8);
+			}
+		}
+		self.bytes.extend(bytes);
+	}
+
+	fn elems<FS: TowerField>(&mut self, elems: &[FS]) {
+		self.len(elems.len());
+		for &elem in elems {
+			self.elem(elem);
+		}
+	}
+
+	fn packed<P: PackedField<Scalar: TowerField>>(&mut self, values: &[P]) {
+		self.len(values.len());
+		for value in values {
+			for scalar in value.iter() {
+				self.elem(scalar);
+			}
+		}
+	}
+
+	fn commitment(&mut self, commitment: &MerkleCap<Digest>) {
+		self.packed(&commitment.0);
+	}
+
+	fn rounds(&mut self, rounds: &[AbstractSumcheckRound<F>]) {
+		self.len(rounds.len());
+		for round in rounds {
+			self.elems(&round.coeffs);
+		}
+	}
+
+	fn sumcheck_batch_proof(&mut self, proof: &SumcheckBatchProof<F>) {
+		self.rounds(&proof.rounds);
+		self.elems(&proof.sorted_evals);
+	}
+
+	fn evalcheck_proof(&mut self, proof: &EvalcheckProof<F>) {
+		match proof {
+			EvalcheckProof::Transparent => self.tag(0),
+			EvalcheckProof::Committed => self.tag(1),
+			EvalcheckProof::Shifted => self.tag(2),
+			EvalcheckProof::Packed => self.tag(3),
+			EvalcheckProof::Repeating(subproof) => {
+				self.tag(4);
+				self.evalcheck_proof(subproof);
+			}
+			EvalcheckProof::Interleaved {
+				eval1,
+				eval2,
+				subproof1,
+				subproof2,
+			} => {
+				self.tag(5);
+				self.elem(*eval1);
+				self.elem(*eval2);
+				self.evalcheck_proof(subproof1);
+				self.evalcheck_proof(subproof2);
+			}
+			EvalcheckProof::Merged {
+				eval1,
+				eval2,
+				subproof1,
+				subproof2,
+			} => {
+				self.tag(6);
+				self.elem(*eval1);
+				self.elem(*eval2);
+				self.evalcheck_proof(subproof1);
+				self.evalcheck_proof(subproof2);
+			}
+			EvalcheckProof::Composite { subproofs } => {
+				self.tag(7);
+				self.len(subproofs.len());
+				for (eval, subproof) in subproofs {
+					self.elem(*eval);
+					self.evalcheck_proof(subproof);
+				}
+			}
+			EvalcheckProof::ZeroPadded(eval, subproof) => {
+				self.tag(8);
+				self.elem(*eval);
+				self.evalcheck_proof(subproof);
+			}
+		}
+	}
+
+	fn evalcheck_proofs(&mut self, proofs: &[EvalcheckProof<F>]) {
+		self.len(proofs.len());
+		for proof in proofs {
+			self.evalcheck_proof(proof);
+		}
+	}
+
+	fn virtual_opening_proof(
+		&mut self,
+		(sumcheck_proof, evalcheck_proofs): &(SumcheckBatchProof<F>, Vec<EvalcheckProof<F>>),
+	) {
+		self.sumcheck_batch_proof(sumcheck_proof);
+		self.evalcheck_proofs(evalcheck_proofs);
+	}
+
+	fn greedy_evalcheck_proof(&mut self, proof: &GreedyEvalcheckProof<F>) {
+		self.evalcheck_proofs(&proof.initial_evalcheck_proofs);
+		self.len(proof.virtual_opening_proofs.len());
+		for virtual_opening_proof in &proof.virtual_opening_proofs {
+			self.virtual_opening_proof(virtual_opening_proof);
+		}
+		self.len(proof.batch_opening_proof.len());
+		for batch_opening_proof in &proof.batch_opening_proof {
+			match batch_opening_proof {
+				None => self.tag(0),
+				Some(batch_opening_proof) => {
+					self.tag(1);
+					self.virtual_opening_proof(batch_opening_proof);
+				}
+			}
+		}
+	}
+
+	fn pcs_proof(&mut self, proof: &PcsProof) {
+		self.len(proof.n_polys);
+		self.packed(proof.mixed_t_prime.evals());
+		self.len(proof.vcs_proofs.len());
+		for (columns, path) in &proof.vcs_proofs {
+			self.len(columns.len());
+			for column in columns {
+				self.packed(column);
+			}
+			self.packed(path);
+		}
+	}
+}
+
+fn main() {
+	adjust_thread_pool()
+		.as_ref()
+		.expect("failed to init thread pool");
+
+	init_tracing().expect("failed to initialize tracing");
+
+	// Note that blobs below 2^10 symbols are rejected by `find_proof_size_optimal_pcs()`, as the
+	// blob is committed as a column of 2^14 bits.
+	let log_dim = get_log_dim().unwrap_or(10);
+	let proof_path = std::env::args().nth(1);
+
+	// Set up the public parameters
+	let params = ErasureParams::new(log_dim).unwrap();
+
+	let blob_size = ByteSize::b((params.rs_code.dim() * 2) as u64);
+	let pcs_size =
+		ByteSize::b((params.data_pcs.proof_size(1) + params.code_pcs.proof_size(1)) as u64);
+	tracing::info!("Size of the blob: {}", blob_size);
+	tracing::info!("Size of PCS opening proofs: {}", pcs_size);
+
+	let witness = generate_witness(&params.rs_code, &params.oracle).unwrap();
+
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	let domain_factory = IsomorphicEvaluationDomainFactory::<F>::default();
+
+	let proof =
+		prove(&params, challenger.clone(), witness.clone(), domain_factory.clone()).unwrap();
+
+	let proof_bytes = encode_proof(log_dim, &proof);
+	tracing::info!("Size of the encoded proof: {}", ByteSize::b(proof_bytes.len() as u64));
+	if let Some(proof_path) = &proof_path {
+		std::fs::write(proof_path, &proof_bytes).expect("failed to write the proof");
+		tracing::info!("Wrote the proof to {}", proof_path);
+	}
+
+	// Verify the decoded proof, as a light client would
+	let (decoded_log_dim, proof) = decode_proof(&proof_bytes).unwrap();
+	assert_eq!(decoded_log_dim, log_dim);
+	verify(&params, challenger.clone(), proof).unwrap();
+
+	if let Some(n_trials) = get_witness_fuzz_trials() {
+		tracing::info!(n_trials, "Checking that perturbed witnesses are rejected");
+		check_bit_flips_rejected::<_, _, BinaryField1b, _, _>(
+			&witness,
+			&[params.oracle.data_bits, params.oracle.code_bits],
+			n_trials,
+			&mut thread_rng(),
+			|witness| {
+				let proof = prove(&params, challenger.clone(), witness, domain_factory.clone())?;
+				verify(&params, challenger.clone(), proof)
+			},
+		)
+		.unwrap();
+	}
+}
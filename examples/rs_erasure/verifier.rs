@@ -0,0 +1,528 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! The public parameters, the verifier and the proof decoding of the Reed–Solomon erasure proof,
+//! shared by the `rs_erasure` prover and the `rs_erasure_verify` light client.
+//!
+//! A proof is exchanged as bytes, in the following encoding. Lengths are written as 64-bit
+//! little-endian integers, and field elements in tower-canonical form: the coordinates of the
+//! element in the binary tower basis, packed little-endian into whole bytes. Packed field elements
+//! are written as their scalars in order. The encoding starts with the log of the number of blob
+//! symbols, followed by the fields of [`Proof`] in declaration order, where
+//!
+//! * a commitment is the length-prefixed digests of the Merkle cap,
+//! * a sumcheck proof is the length-prefixed rounds, each round its length-prefixed coefficients,
+//! * an evalcheck proof is a tag byte for its variant followed by its evaluations and subproofs,
+//! * a PCS opening is the number of polynomials, the length-prefixed packed evaluations of the
+//!   mixed `t'`, and the length-prefixed opened columns, each with its Merkle authentication path.
+//!
+//! Decoding is strict, so trailing bytes and non-canonical field elements are rejected.
+
+use anyhow::{bail, ensure, Context, Result};
+use binius_core::{
+	challenger::{CanObserve, CanSample, CanSampleBits},
+	linear_code::LinearCode,
+	merkle_tree::{MerkleCap, MerkleTreeVCS},
+	oracle::{BatchId, CompositePolyOracle, MultilinearOracleSet, OracleId},
+	poly_commit::{tensor_pcs, PolyCommitScheme, TensorPCS},
+	polynomial::{
+		composition::BivariateProduct,
+		transparent::multilinear_extension::MultilinearExtensionTransparent, MultilinearExtension,
+		MultilinearQuery,
+	},
+	protocols::{
+		abstract_sumcheck::{
+			AbstractSumcheckBatchProof, AbstractSumcheckProof, AbstractSumcheckRound,
+		},
+		evalcheck::{EvalcheckClaim, EvalcheckProof},
+		greedy_evalcheck::{self, GreedyEvalcheckProof},
+		sumcheck::{self, SumcheckBatchProof, SumcheckClaim, SumcheckProof},
+	},
+	reed_solomon::reed_solomon::ReedSolomonCode,
+};
+use binius_field::{
+	as_packed_field::PackedType,
+	packed::{iter_packed_slice, set_packed_slice},
+	underlier::WithUnderlier,
+	BinaryField128b, BinaryField16b, BinaryField1b, BinaryField8b, ExtensionField, Field,
+	PackedBinaryField128x1b, PackedField, PackedFieldIndexable, TowerField,
+};
+use binius_hash::{GroestlDigest, GroestlDigestCompression, GroestlHasher, HasherDigest};
+use binius_ntt::NTTOptions;
+use std::iter;
+use tracing::instrument;
+
+pub type U = <PackedBinaryField128x1b as WithUnderlier>::Underlier;
+pub type F = BinaryField128b;
+pub type Symbol = BinaryField16b;
+
+/// Log of the number of bits of a symbol.
+pub const LOG_SYMBOL_BITS: usize = 4;
+
+/// Log of the inverse rate of the erasure code.
+pub const LOG_INV_RATE: usize = 1;
+
+/// The largest supported log of the number of blob symbols. The verifier takes time quadratic in
+/// the blob size, which bounds the work a light client does for an untrusted proof.
+const MAX_LOG_DIM: usize = 16;
+
+const SECURITY_BITS: usize = 100;
+
+/// The recursion limit of evalcheck proofs, which bounds the stack depth when decoding an
+/// untrusted proof.
+const MAX_EVALCHECK_DEPTH: usize = 64;
+
+pub type Digest = GroestlDigest<BinaryField8b>;
+pub type PcsProof = tensor_pcs::Proof<U, Symbol, F, Vec<Digest>>;
+
+/// The polynomial commitment scheme of the blob and the codeword.
+pub type Pcs = TensorPCS<
+	U,
+	BinaryField1b,
+	Symbol,
+	Symbol,
+	F,
+	ReedSolomonCode<PackedType<U, Symbol>>,
+	HasherDigest<PackedType<U, Symbol>, GroestlHasher<PackedType<U, Symbol>>>,
+	MerkleTreeVCS<Digest, Digest, GroestlHasher<Digest>, GroestlDigestCompression<BinaryField8b>>,
+>;
+
+/// Oracles of the committed blob and codeword.
+#[derive(Debug)]
+pub struct ErasureOracle {
+	pub data_batch_id: BatchId,
+	pub code_batch_id: BatchId,
+	/// The blob, as a column of bits.
+	pub data_bits: OracleId,
+	/// The codeword, as a column of bits.
+	pub code_bits: OracleId,
+	/// The blob, as a column of symbols.
+	pub data: OracleId,
+	/// The codeword, as a column of symbols.
+	pub code: OracleId,
+}
+
+impl ErasureOracle {
+	fn new(oracles: &mut MultilinearOracleSet<F>, log_dim: usize) -> Result<Self> {
+		let data_batch_id =
+			oracles.add_committed_batch(log_dim + LOG_SYMBOL_BITS, BinaryField1b::TOWER_LEVEL);
+		let data_bits = oracles.add_committed(data_batch_id);
+		let code_batch_id = oracles.add_committed_batch(
+			log_dim + LOG_INV_RATE + LOG_SYMBOL_BITS,
+			BinaryField1b::TOWER_LEVEL,
+		);
+		let code_bits = oracles.add_committed(code_batch_id);
+
+		let data = oracles.add_packed(data_bits, LOG_SYMBOL_BITS)?;
+		let code = oracles.add_packed(code_bits, LOG_SYMBOL_BITS)?;
+
+		Ok(Self {
+			data_batch_id,
+			code_batch_id,
+			data_bits,
+			code_bits,
+			data,
+			code,
+		})
+	}
+}
+
+/// The public parameters of the erasure proofs of blobs of `2^log_dim` symbols, which the prover
+/// and the light client derive independently.
+pub struct ErasureParams {
+	pub log_dim: usize,
+	pub rs_code: ReedSolomonCode<PackedType<U, Symbol>>,
+	pub oracles: MultilinearOracleSet<F>,
+	pub oracle: ErasureOracle,
+	pub data_pcs: Pcs,
+	pub code_pcs: Pcs,
+}
+
+impl ErasureParams {
+	pub fn new(log_dim: usize) -> Result<Self> {
+		ensure!(
+			log_dim <= MAX_LOG_DIM,
+			"the log of the number of blob symbols must be at most {MAX_LOG_DIM}, got {log_dim}"
+		);
+
+		let rs_code = ReedSolomonCode::new(log_dim, LOG_INV_RATE, NTTOptions::default())?;
+
+		let mut oracles = MultilinearOracleSet::new();
+		let oracle = ErasureOracle::new(&mut oracles, log_dim)?;
+
+		// Note that columns below 2^14 bits are rejected by `find_proof_size_optimal_pcs()`.
+		let make_pcs = |n_vars| {
+			tensor_pcs::find_proof_size_optimal_pcs::<U, BinaryField1b, Symbol, Symbol, F>(
+				SECURITY_BITS,
+				n_vars,
+				1,
+				LOG_INV_RATE,
+				false,
+			)
+			.with_context(|| {
+				format!("no polynomial commitment scheme for a column of 2^{n_vars} bits")
+			})
+		};
+		let data_pcs = make_pcs(oracles.n_vars(oracle.data_bits))?;
+		let code_pcs = make_pcs(oracles.n_vars(oracle.code_bits))?;
+
+		Ok(Self {
+			log_dim,
+			rs_code,
+			oracles,
+			oracle,
+			data_pcs,
+			code_pcs,
+		})
+	}
+}
+
+pub struct Proof<PCSComm, PCSProof> {
+	pub data_comm: PCSComm,
+	pub code_comm: PCSComm,
+	pub code_eval: F,
+	pub sumcheck_proof: SumcheckProof<F>,
+	pub evalcheck_proof: GreedyEvalcheckProof<F>,
+	pub data_open_proof: PCSProof,
+	pub code_open_proof: PCSProof,
+}
+
+/// A proof with the commitment scheme of [`ErasureParams`].
+pub type ErasureProof = Proof<MerkleCap<Digest>, PcsProof>;
+
+/// Inner product of the evaluations of `eq(r, .)` with a vector of symbols.
+pub fn eq_inner_product(eq: &[F], symbols: &[PackedType<U, Symbol>]) -> F {
+	iter::zip(eq, iter_packed_slice(symbols))
+		.map(|(&eq_j, symbol)| eq_j * F::from(symbol))
+		.sum()
+}
+
+/// Evaluations of `eq(r, .)` over the codeword coordinates.
+pub fn eq_expansion(r: &[F]) -> Result<Vec<F>> {
+	let expansion = MultilinearQuery::<PackedType<U, F>>::with_full_query(r)?.into_expansion();
+	Ok(PackedType::<U, F>::unpack_scalars(&expansion).to_vec())
+}
+
+/// Computes `M_r(x) = \sum_j eq(r, j) * Enc(e_x)_j` by encoding every unit vector.
+#[instrument(skip_all, level = "debug")]
+fn encoding_row_combination(
+	rs_code: &ReedSolomonCode<PackedType<U, Symbol>>,
+	eq: &[F],
+) -> Result<Vec<PackedType<U, F>>> {
+	let packed_dim = rs_code.dim() / <PackedType<U, Symbol>>::WIDTH;
+	let values = (0..rs_code.dim())
+		.map(|x| {
+			let mut unit = vec![PackedType::<U, Symbol>::zero(); packed_dim];
+			set_packed_slice(&mut unit, x, Symbol::ONE);
+			let codeword = rs_code.encode(unit)?;
+			Ok(eq_inner_product(eq, &codeword))
+		})
+		.collect::<Result<Vec<_>>>()?;
+
+	Ok(values
+		.chunks(<PackedType<U, F>>::WIDTH)
+		.map(|chunk| PackedType::<U, F>::from_scalars(chunk.iter().copied()))
+		.collect())
+}
+
+/// Adds the transparent `M_r` for the evaluation point `r` of the codeword.
+pub fn add_encoding_row_combination(
+	oracles: &mut MultilinearOracleSet<F>,
+	rs_code: &ReedSolomonCode<PackedType<U, Symbol>>,
+	r: &[F],
+) -> Result<(OracleId, Vec<PackedType<U, F>>)> {
+	let values = encoding_row_combination(rs_code, &eq_expansion(r)?)?;
+	let id = oracles.add_transparent(MultilinearExtensionTransparent::<_, F, _>::from_values(
+		values.clone(),
+	)?)?;
+	Ok((id, values))
+}
+
+/// Looks up the same query claim on a committed batch.
+pub fn batch_claim<T>(same_query_claims: &mut Vec<(BatchId, T)>, batch_id: BatchId) -> Result<T> {
+	let position = same_query_claims.iter().position(|(id, _)| *id == batch_id);
+	ensure!(position.is_some(), "missing the evaluation claim on batch {batch_id}");
+	Ok(same_query_claims
+		.swap_remove(position.expect("checked above"))
+		.1)
+}
+
+/// Verifies a proof using only the public parameters and the commitments in the proof.
+#[instrument(skip_all, level = "debug")]
+pub fn verify<CH>(params: &ErasureParams, mut challenger: CH, proof: ErasureProof) -> Result<()>
+where
+	CH: CanObserve<F> + CanObserve<MerkleCap<Digest>> + CanSample<F> + CanSampleBits<usize>,
+{
+	let ErasureParams {
+		log_dim,
+		rs_code,
+		oracles,
+		oracle,
+		data_pcs,
+		code_pcs,
+	} = params;
+	let mut oracles = oracles.clone();
+
+	let Proof {
+		data_comm,
+		code_comm,
+		code_eval,
+		sumcheck_proof,
+		evalcheck_proof,
+		data_open_proof,
+		code_open_proof,
+	} = proof;
+
+	// Round 1
+	challenger.observe(data_comm.clone());
+	challenger.observe(code_comm.clone());
+
+	// Evaluate the codeword at a random point
+	let r: Vec<F> = (0..log_dim + LOG_INV_RATE)
+		.map(|_| challenger.sample())
+		.collect();
+	challenger.observe(code_eval);
+
+	// Sumcheck the codeword evaluation against the blob
+	let (row_combination, _) = add_encoding_row_combination(&mut oracles, rs_code, &r)?;
+	let sumcheck_claim = SumcheckClaim::new(
+		CompositePolyOracle::new(
+			*log_dim,
+			vec![oracles.oracle(oracle.data), oracles.oracle(row_combination)],
+			BivariateProduct,
+		)?,
+		code_eval,
+	)?;
+	let data_claim = sumcheck::verify(&sumcheck_claim, sumcheck_proof, &mut challenger)?;
+
+	let code_claim = EvalcheckClaim {
+		poly: oracles.oracle(oracle.code).into_composite(),
+		eval_point: r,
+		eval: code_eval,
+		is_random_point: true,
+	};
+
+	// Evalcheck
+	let mut same_query_claims = greedy_evalcheck::verify(
+		&mut oracles,
+		[code_claim, data_claim],
+		evalcheck_proof,
+		&mut challenger,
+	)?;
+
+	ensure!(same_query_claims.len() == 2, "expected evaluation claims on two batches");
+	let data_query_claim = batch_claim(&mut same_query_claims, oracle.data_batch_id)?;
+	let code_query_claim = batch_claim(&mut same_query_claims, oracle.code_batch_id)?;
+
+	data_pcs.verify_evaluation(
+		&mut challenger,
+		&data_comm,
+		&data_query_claim.eval_point,
+		data_open_proof,
+		&data_query_claim.evals,
+	)?;
+	code_pcs.verify_evaluation(
+		&mut challenger,
+		&code_comm,
+		&code_query_claim.eval_point,
+		code_open_proof,
+		&code_query_claim.evals,
+	)?;
+
+	Ok(())
+}
+
+/// Decodes a proof, returning the log of the number of blob symbols and the proof.
+pub fn decode_proof(bytes: &[u8]) -> Result<(usize, ErasureProof)> {
+	let mut reader = Reader { bytes };
+	let log_dim = reader.len(0)?;
+	let proof = Proof {
+		data_comm: reader.commitment()?,
+		code_comm: reader.commitment()?,
+		code_eval: reader.elem()?,
+		sumcheck_proof: AbstractSumcheckProof {
+			rounds: reader.rounds()?,
+		},
+		evalcheck_proof: reader.greedy_evalcheck_proof()?,
+		data_open_proof: reader.pcs_proof()?,
+		code_open_proof: reader.pcs_proof()?,
+	};
+	ensure!(reader.bytes.is_empty(), "{} trailing bytes after the proof", reader.bytes.len());
+	Ok((log_dim, proof))
+}
+
+struct Reader<'a> {
+	bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+	fn take(&mut self, n: usize) -> Result<&'a [u8]> {
+		ensure!(n <= self.bytes.len(), "unexpected end of the proof");
+		let (head, tail) = self.bytes.split_at(n);
+		self.bytes = tail;
+		Ok(head)
+	}
+
+	/// Reads a length, checking that the remaining input can hold `len` items of at least
+	/// `min_item_size` bytes each, which bounds allocations by the proof size.
+	fn len(&mut self, min_item_size: usize) -> Result<usize> {
+		let len = u64::from_le_bytes(self.take(8)?.try_into().expect("took 8 bytes"));
+		ensure!(
+			len.saturating_mul(min_item_size as u64) <= self.bytes.len() as u64,
+			"length {len} does not fit in the remaining proof"
+		);
+		Ok(len as usize)
+	}
+
+	fn tag(&mut self) -> Result<u8> {
+		Ok(self.take(1)?[0])
+	}
+
+	fn elem<FS: TowerField>(&mut self) -> Result<FS> {
+		let degree = <FS as ExtensionField<BinaryField1b>>::DEGREE;
+		let bytes = self.take(elem_bytes::<FS>())?;
+		let bit = |pos: usize| (bytes[pos / 8] >> (pos % 8)) & 1 == 1;
+		ensure!(
+			!(degree..bytes.len() * 8).any(bit),
+			"field element has nonzero bits beyond the extension degree"
+		);
+		let bits = (0..degree)
+			.map(|pos| {
+				if bit(pos) {
+					BinaryField1b::ONE
+				} else {
+					BinaryField1b::ZERO
+				}
+			})
+			.collect::<Vec<_>>();
+		Ok(ExtensionField::<BinaryField1b>::from_bases(&bits)?)
+	}
+
+	fn elems<FS: TowerField>(&mut self) -> Result<Vec<FS>> {
+		let len = self.len(elem_bytes::<FS>())?;
+		(0..len).map(|_| self.elem()).collect()
+	}
+
+	fn packed<P: PackedField<Scalar: TowerField>>(&mut self) -> Result<Vec<P>> {
+		let len = self.len(P::WIDTH * elem_bytes::<P::Scalar>())?;
+		(0..len)
+			.map(|_| {
+				let scalars = (0..P::WIDTH)
+					.map(|_| self.elem())
+					.collect::<Result<Vec<_>>>()?;
+				Ok(P::from_scalars(scalars))
+			})
+			.collect()
+	}
+
+	fn commitment(&mut self) -> Result<MerkleCap<Digest>> {
+		Ok(MerkleCap(self.packed()?))
+	}
+
+	fn rounds(&mut self) -> Result<Vec<AbstractSumcheckRound<F>>> {
+		let len = self.len(8)?;
+		(0..len)
+			.map(|_| {
+				Ok(AbstractSumcheckRound {
+					coeffs: self.elems()?,
+				})
+			})
+			.collect()
+	}
+
+	fn sumcheck_batch_proof(&mut self) -> Result<SumcheckBatchProof<F>> {
+		Ok(AbstractSumcheckBatchProof {
+			rounds: self.rounds()?,
+			sorted_evals: self.elems()?,
+		})
+	}
+
+	fn evalcheck_proof(&mut self, depth: usize) -> Result<EvalcheckProof<F>> {
+		ensure!(depth < MAX_EVALCHECK_DEPTH, "evalcheck proof is nested too deeply");
+		let subproof = |reader: &mut Self| reader.evalcheck_proof(depth + 1).map(Box::new);
+		let proof = match self.tag()? {
+			0 => EvalcheckProof::Transparent,
+			1 => EvalcheckProof::Committed,
+			2 => EvalcheckProof::Shifted,
+			3 => EvalcheckProof::Packed,
+			4 => EvalcheckProof::Repeating(subproof(self)?),
+			5 => EvalcheckProof::Interleaved {
+				eval1: self.elem()?,
+				eval2: self.elem()?,
+				subproof1: subproof(self)?,
+				subproof2: subproof(self)?,
+			},
+			6 => EvalcheckProof::Merged {
+				eval1: self.elem()?,
+				eval2: self.elem()?,
+				subproof1: subproof(self)?,
+				subproof2: subproof(self)?,
+			},
+			7 => {
+				let len = self.len(elem_bytes::<F>() + 1)?;
+				let subproofs = (0..len)
+					.map(|_| Ok((self.elem::<F>()?, self.evalcheck_proof(depth + 1)?)))
+					.collect::<Result<Vec<_>>>()?;
+				EvalcheckProof::Composite { subproofs }
+			}
+			8 => EvalcheckProof::ZeroPadded(self.elem()?, subproof(self)?),
+			tag => bail!("invalid evalcheck proof tag {tag}"),
+		};
+		Ok(proof)
+	}
+
+	fn evalcheck_proofs(&mut self) -> Result<Vec<EvalcheckProof<F>>> {
+		let len = self.len(1)?;
+		(0..len).map(|_| self.evalcheck_proof(0)).collect()
+	}
+
+	fn virtual_opening_proof(&mut self) -> Result<(SumcheckBatchProof<F>, Vec<EvalcheckProof<F>>)> {
+		Ok((self.sumcheck_batch_proof()?, self.evalcheck_proofs()?))
+	}
+
+	fn greedy_evalcheck_proof(&mut self) -> Result<GreedyEvalcheckProof<F>> {
+		let initial_evalcheck_proofs = self.evalcheck_proofs()?;
+		let len = self.len(16)?;
+		let virtual_opening_proofs = (0..len)
+			.map(|_| self.virtual_opening_proof())
+			.collect::<Result<Vec<_>>>()?;
+		let len = self.len(1)?;
+		let batch_opening_proof = (0..len)
+			.map(|_| match self.tag()? {
+				0 => Ok(None),
+				1 => Ok(Some(self.virtual_opening_proof()?)),
+				tag => bail!("invalid option tag {tag}"),
+			})
+			.collect::<Result<Vec<_>>>()?;
+		Ok(GreedyEvalcheckProof {
+			initial_evalcheck_proofs,
+			virtual_opening_proofs,
+			batch_opening_proof,
+		})
+	}
+
+	fn pcs_proof(&mut self) -> Result<PcsProof> {
+		let n_polys = self.len(0)?;
+		let mixed_t_prime = MultilinearExtension::from_values(self.packed()?)?;
+		let len = self.len(16)?;
+		let vcs_proofs = (0..len)
+			.map(|_| {
+				let n_columns = self.len(8)?;
+				let columns: Vec<Vec<PackedType<U, Symbol>>> = (0..n_columns)
+					.map(|_| self.packed())
+					.collect::<Result<_>>()?;
+				let path: Vec<Digest> = self.packed()?;
+				Ok((columns, path))
+			})
+			.collect::<Result<Vec<_>>>()?;
+		Ok(PcsProof {
+			n_polys,
+			mixed_t_prime,
+			vcs_proofs,
+		})
+	}
+}
+
+/// The number of bytes of an encoded field element.
+fn elem_bytes<FS: TowerField>() -> usize {
+	<FS as ExtensionField<BinaryField1b>>::DEGREE.div_ceil(8)
+}
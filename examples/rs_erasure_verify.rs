@@ -0,0 +1,51 @@
+// Copyright 2024 Ulvetanna Inc.
+
+//! Verify-only light client for the Reed–Solomon erasure proofs of the `rs_erasure` example.
+//!
+//! The client reads an encoded proof from the path given as the first argument, derives the
+//! public parameters from the blob size in the proof and checks the proof against the commitments
+//! it contains, without access to the blob. A proof is produced with
+//!
+//! ```text
+//! cargo run --release --example rs_erasure -- rs_erasure.proof
+//! cargo run --release --example rs_erasure_verify -- rs_erasure.proof
+//! ```
+//!
+//! See `rs_erasure/verifier.rs` for the proof encoding.
+
+#[path = "rs_erasure/verifier.rs"]
+mod verifier;
+
+use anyhow::{Context, Result};
+use binius_core::{challenger::new_hasher_challenger, linear_code::LinearCode};
+use binius_hash::GroestlHasher;
+use binius_utils::{rayon::adjust_thread_pool, tracing::init_tracing};
+use bytesize::ByteSize;
+use std::time::Instant;
+use verifier::{decode_proof, verify, ErasureParams};
+
+fn main() -> Result<()> {
+	adjust_thread_pool()
+		.as_ref()
+		.expect("failed to init thread pool");
+
+	init_tracing().expect("failed to initialize tracing");
+
+	let proof_path = std::env::args()
+		.nth(1)
+		.context("usage: rs_erasure_verify <proof path>")?;
+	let proof_bytes = std::fs::read(&proof_path)
+		.with_context(|| format!("failed to read the proof from {proof_path}"))?;
+	tracing::info!("Size of the encoded proof: {}", ByteSize::b(proof_bytes.len() as u64));
+
+	let start = Instant::now();
+	let (log_dim, proof) = decode_proof(&proof_bytes).context("malformed proof")?;
+	let params = ErasureParams::new(log_dim)?;
+	tracing::info!("Size of the blob: {}", ByteSize::b((params.rs_code.dim() * 2) as u64));
+
+	let challenger = new_hasher_challenger::<_, GroestlHasher<_>>();
+	verify(&params, challenger, proof).context("the proof is invalid")?;
+	tracing::info!("Verified the proof in {:?}", start.elapsed());
+
+	Ok(())
+}
@@ -21,6 +21,7 @@ use binius_core::{
 		zerocheck::{self, ZerocheckBatchProof, ZerocheckBatchProveOutput, ZerocheckClaim},
 	},
 	witness::MultilinearExtensionIndex,
+	witness_fuzz::check_bit_flips_rejected,
 };
 use binius_field::{
 	as_packed_field::{PackScalar, PackedType},
@@ -31,11 +32,14 @@ use binius_field::{
 use binius_hash::GroestlHasher;
 use binius_macros::{composition_poly, IterOracles};
 use binius_utils::{
-	examples::get_log_trace_size, rayon::adjust_thread_pool, tracing::init_tracing,
+	examples::{get_log_trace_size, get_witness_fuzz_trials},
+	rayon::adjust_thread_pool,
+	tracing::init_tracing,
 };
 use bytemuck::{must_cast_slice_mut, Pod};
 use itertools::Itertools;
 use p3_challenger::{CanObserve, CanSample, CanSampleBits};
+use rand::thread_rng;
 use std::fmt::Debug;
 use tracing::{debug, info, instrument};
 
@@ -289,13 +293,11 @@ where
 	let mix_composition_prover = oracle.mixed_constraints(FW::from(mixing_challenge))?;
 
 	let zerocheck_column_oracles = oracle.iter_oracles().map(|id| oracles.oracle(id)).collect();
-	let zerocheck_claim = ZerocheckClaim {
-		poly: CompositePolyOracle::new(
-			log_size,
-			zerocheck_column_oracles,
-			mix_composition_verifier,
-		)?,
-	};
+	let zerocheck_claim = ZerocheckClaim::new(CompositePolyOracle::new(
+		log_size,
+		zerocheck_column_oracles,
+		mix_composition_verifier,
+	)?)?;
 
 	let zerocheck_witness = MultilinearComposite::new(
 		log_size,
@@ -398,9 +400,11 @@ where
 
 	// Zerocheck
 	let zerocheck_column_oracles = oracle.iter_oracles().map(|id| oracles.oracle(id)).collect();
-	let zerocheck_claim = ZerocheckClaim {
-		poly: CompositePolyOracle::new(log_size, zerocheck_column_oracles, mix_composition)?,
-	};
+	let zerocheck_claim = ZerocheckClaim::new(CompositePolyOracle::new(
+		log_size,
+		zerocheck_column_oracles,
+		mix_composition,
+	)?)?;
 
 	let evalcheck_claims =
 		zerocheck::batch_verify([zerocheck_claim], zerocheck_proof, &mut challenger)?;
@@ -470,11 +474,44 @@ fn main() {
 		&pcs,
 		&oracle,
 		challenger.clone(),
-		witness,
-		domain_factory,
+		witness.clone(),
+		domain_factory.clone(),
 	)
 	.unwrap();
 
 	info!("Verifying");
 	verify(log_size, &mut oracles.clone(), &oracle, &pcs, challenger.clone(), proof).unwrap();
+
+	if let Some(n_trials) = get_witness_fuzz_trials() {
+		info!(n_trials, "Checking that perturbed witnesses are rejected");
+		let committed_ids = oracles
+			.committed_oracle_ids(oracle.batch_id)
+			.collect::<Vec<_>>();
+		check_bit_flips_rejected::<_, _, BinaryField1b, _, _>(
+			&witness,
+			&committed_ids,
+			n_trials,
+			&mut thread_rng(),
+			|witness| {
+				let proof = prove::<
+					U,
+					BinaryField128b,
+					field_types::Field,
+					field_types::DomainField,
+					_,
+					_,
+				>(
+					log_size,
+					&mut oracles.clone(),
+					&pcs,
+					&oracle,
+					challenger.clone(),
+					witness,
+					domain_factory.clone(),
+				)?;
+				verify(log_size, &mut oracles.clone(), &oracle, &pcs, challenger.clone(), proof)
+			},
+		)
+		.unwrap();
+	}
 }
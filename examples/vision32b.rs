@@ -952,13 +952,11 @@ where
 		.map(|oracle| oracle.n_vars())
 		.max()
 		.unwrap_or(0);
-	let zerocheck_claim = ZerocheckClaim {
-		poly: CompositePolyOracle::new(
-			max_n_vars,
-			zerocheck_column_oracles,
-			mix_composition_verifier,
-		)?,
-	};
+	let zerocheck_claim = ZerocheckClaim::new(CompositePolyOracle::new(
+		max_n_vars,
+		zerocheck_column_oracles,
+		mix_composition_verifier,
+	)?)?;
 
 	let zerocheck_witness = MultilinearComposite::new(
 		zerocheck_claim.n_vars(),
@@ -1057,9 +1055,11 @@ where
 		.map(|oracle| oracle.n_vars())
 		.max()
 		.unwrap_or(0);
-	let zerocheck_claim = ZerocheckClaim {
-		poly: CompositePolyOracle::new(max_n_vars, zerocheck_column_oracles, mix_composition)?,
-	};
+	let zerocheck_claim = ZerocheckClaim::new(CompositePolyOracle::new(
+		max_n_vars,
+		zerocheck_column_oracles,
+		mix_composition,
+	)?)?;
 
 	let evalcheck_claims =
 		zerocheck::batch_verify([zerocheck_claim], zerocheck_proof, &mut challenger)?;